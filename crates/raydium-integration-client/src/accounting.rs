@@ -0,0 +1,639 @@
+//! Accounting export of a wallet's history with the program.
+//!
+//! [`fetch_history`] walks a wallet's transactions and keeps those that
+//! emitted the program's events, with the wallet's token balance changes in
+//! each. [`AccountingReport::from_history`] turns them into one record per
+//! swap and one per position. Swaps are taken at the amounts that left and
+//! reached the wallet when its balances show them, protocol fee included,
+//! and otherwise at the event's figures; a sale of token_0 realizes PnL in
+//! token_1 against the average cost of the token_0 bought through the same
+//! pool. Positions sum their deposits, withdrawals, fees and rewards, and
+//! carry the cost basis and realized PnL the program recorded when they
+//! closed with a cost basis account. The report serializes to JSON, and
+//! [`AccountingReport::swaps_csv`] and [`AccountingReport::positions_csv`]
+//! render CSV for tax and treasury reporting. Amounts are raw token units.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+
+use anchor_lang::prelude::Pubkey;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding, UiTransactionTokenBalance,
+};
+
+use crate::events::{parse_transaction, ProgramEvent};
+use crate::Result;
+
+/// Signatures per `getSignaturesForAddress` page, the RPC's maximum
+const SIGNATURES_PAGE: usize = 1_000;
+
+/// A transaction of the wallet that emitted the program's events
+#[derive(Debug)]
+pub struct HistoryEntry {
+    pub signature: Signature,
+    pub slot: u64,
+    pub events: Vec<ProgramEvent>,
+    /// Net change of the wallet's token accounts, by mint
+    pub token_changes: HashMap<Pubkey, i128>,
+}
+
+/// One swap, with its realized PnL where the cost basis is known
+#[derive(Clone, Debug, Serialize)]
+pub struct SwapRecord {
+    pub signature: String,
+    pub timestamp: i64,
+    pub pool: String,
+    pub zero_for_one: bool,
+    /// None when the wallet's balances don't show the swap
+    pub mint_in: Option<String>,
+    pub mint_out: Option<String>,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// False when the amounts are the event's expected figures
+    pub amounts_realized: bool,
+    pub slippage_bps: u16,
+    /// Average cost in token_1 of the token_0 sold
+    pub cost_basis_token_1: Option<u128>,
+    pub realized_pnl_token_1: Option<i128>,
+}
+
+/// One position's flows over the history, and its PnL once closed
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct PositionRecord {
+    pub position_nft: String,
+    /// None when the position was opened before the history starts
+    pub pool: Option<String>,
+    pub opened_at: Option<i64>,
+    pub closed_at: Option<i64>,
+    pub tick_lower: Option<i32>,
+    pub tick_upper: Option<i32>,
+    pub deposited_0: u64,
+    pub deposited_1: u64,
+    pub withdrawn_0: u64,
+    pub withdrawn_1: u64,
+    pub fees_0: u64,
+    pub fees_1: u64,
+    /// Rewards collected, by reward mint
+    pub rewards: BTreeMap<String, u64>,
+    /// From the program's cost basis, when the position closed with one
+    pub cost_token_1: Option<u128>,
+    pub proceeds_token_1: Option<u128>,
+    pub realized_pnl_token_1: Option<i128>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AccountingReport {
+    pub wallet: String,
+    /// In the order they happened
+    pub swaps: Vec<SwapRecord>,
+    /// In the order they were first seen
+    pub positions: Vec<PositionRecord>,
+}
+
+/// Token_0 bought through a pool and what it cost in token_1
+#[derive(Default)]
+struct Inventory {
+    amount_0: u128,
+    cost_1: u128,
+}
+
+impl Inventory {
+    fn buy(&mut self, amount_0: u64, cost_1: u64) {
+        self.amount_0 += amount_0 as u128;
+        self.cost_1 += cost_1 as u128;
+    }
+
+    /*
+     * Remove `amount_0` at its average cost. None when more is sold than
+     * was bought, the cost of the rest being unknown
+     */
+    fn sell(&mut self, amount_0: u64) -> Option<u128> {
+        let sold = (amount_0 as u128).min(self.amount_0);
+        let cost = if sold == self.amount_0 {
+            self.cost_1
+        } else {
+            self.cost_1 * sold / self.amount_0
+        };
+        self.amount_0 -= sold;
+        self.cost_1 -= cost;
+        (sold == amount_0 as u128).then_some(cost)
+    }
+}
+
+impl AccountingReport {
+    /*
+     * Records of `wallet`'s swaps and positions from its history, oldest
+     * entry first. Events of other users in the same transactions are
+     * skipped
+     */
+    pub fn from_history(wallet: &Pubkey, history: &[HistoryEntry]) -> Self {
+        let mut report = Self {
+            wallet: wallet.to_string(),
+            ..Self::default()
+        };
+        let mut inventories: HashMap<Pubkey, Inventory> = HashMap::new();
+        let mut indices: HashMap<Pubkey, usize> = HashMap::new();
+        for entry in history {
+            let swaps = entry
+                .events
+                .iter()
+                .filter(|event| {
+                    matches!(event, ProgramEvent::SwapExecuted(swap) if swap.user == *wallet)
+                })
+                .count();
+            for event in &entry.events {
+                match event {
+                    ProgramEvent::SwapExecuted(swap) if swap.user == *wallet => {
+                        let zero_for_one = swap.sqrt_price_after_x64 < swap.sqrt_price_before_x64;
+                        // An exact-out swap's amount is its output and the
+                        // expected amount its input
+                        let (amount_in, amount_out) = if swap.is_base_input {
+                            (swap.amount_in, swap.amount_out)
+                        } else {
+                            (swap.expected_amount, swap.amount_in)
+                        };
+                        let mut record = SwapRecord {
+                            signature: entry.signature.to_string(),
+                            timestamp: swap.timestamp,
+                            pool: swap.pool.to_string(),
+                            zero_for_one,
+                            mint_in: None,
+                            mint_out: None,
+                            amount_in,
+                            amount_out,
+                            amounts_realized: false,
+                            slippage_bps: swap.slippage_bps,
+                            cost_basis_token_1: None,
+                            realized_pnl_token_1: None,
+                        };
+                        // Balances only tell a single swap's amounts apart
+                        if swaps == 1 {
+                            if let Some(((mint_in, spent), (mint_out, received))) =
+                                swap_balances(&entry.token_changes)
+                            {
+                                record.mint_in = Some(mint_in.to_string());
+                                record.mint_out = Some(mint_out.to_string());
+                                record.amount_in = spent;
+                                record.amount_out = received;
+                                record.amounts_realized = true;
+                            }
+                        }
+                        let inventory = inventories.entry(swap.pool).or_default();
+                        if zero_for_one {
+                            record.cost_basis_token_1 = inventory.sell(record.amount_in);
+                            record.realized_pnl_token_1 = record
+                                .cost_basis_token_1
+                                .map(|cost| record.amount_out as i128 - cost as i128);
+                        } else {
+                            inventory.buy(record.amount_out, record.amount_in);
+                        }
+                        report.swaps.push(record);
+                    }
+                    ProgramEvent::PositionOpened(opened) if opened.user == *wallet => {
+                        let record = position_record(
+                            &mut report.positions,
+                            &mut indices,
+                            &opened.position_nft,
+                        );
+                        record.pool = Some(opened.pool.to_string());
+                        record.opened_at = Some(opened.timestamp);
+                        record.tick_lower = Some(opened.tick_lower);
+                        record.tick_upper = Some(opened.tick_upper);
+                        record.deposited_0 += opened.amount_0;
+                        record.deposited_1 += opened.amount_1;
+                    }
+                    ProgramEvent::CpmmMigrated(migrated) if migrated.user == *wallet => {
+                        let record = position_record(
+                            &mut report.positions,
+                            &mut indices,
+                            &migrated.position_nft,
+                        );
+                        record.pool = Some(migrated.pool.to_string());
+                        record.opened_at = Some(migrated.timestamp);
+                        record.tick_lower = Some(migrated.tick_lower);
+                        record.tick_upper = Some(migrated.tick_upper);
+                        record.deposited_0 += migrated.amount_0_deposited;
+                        record.deposited_1 += migrated.amount_1_deposited;
+                    }
+                    ProgramEvent::LiquidityIncreased(increased) if increased.user == *wallet => {
+                        let record = position_record(
+                            &mut report.positions,
+                            &mut indices,
+                            &increased.position_nft,
+                        );
+                        record.deposited_0 += increased.amount_0_added;
+                        record.deposited_1 += increased.amount_1_added;
+                    }
+                    ProgramEvent::LiquidityDecreased(decreased) if decreased.user == *wallet => {
+                        let record = position_record(
+                            &mut report.positions,
+                            &mut indices,
+                            &decreased.position_nft,
+                        );
+                        record.withdrawn_0 += decreased.amount_0_removed;
+                        record.withdrawn_1 += decreased.amount_1_removed;
+                    }
+                    ProgramEvent::FeesCollected(fees) if fees.user == *wallet => {
+                        let record = position_record(
+                            &mut report.positions,
+                            &mut indices,
+                            &fees.position_nft,
+                        );
+                        record.pool = Some(fees.pool.to_string());
+                        record.fees_0 += fees.amount_0;
+                        record.fees_1 += fees.amount_1;
+                    }
+                    ProgramEvent::RewardsCollected(rewards) if rewards.user == *wallet => {
+                        let record = position_record(
+                            &mut report.positions,
+                            &mut indices,
+                            &rewards.position_nft,
+                        );
+                        record.pool = Some(rewards.pool.to_string());
+                        *record
+                            .rewards
+                            .entry(rewards.reward_mint.to_string())
+                            .or_default() += rewards.amount;
+                    }
+                    ProgramEvent::PositionPnlRealized(pnl) if pnl.user == *wallet => {
+                        let record =
+                            position_record(&mut report.positions, &mut indices, &pnl.position_nft);
+                        record.pool = Some(pnl.pool.to_string());
+                        record.cost_token_1 = Some(pnl.cost_token_1);
+                        record.proceeds_token_1 = Some(pnl.proceeds_token_1);
+                        record.realized_pnl_token_1 = Some(pnl.realized_pnl_token_1);
+                    }
+                    ProgramEvent::PositionClosed(closed) if closed.user == *wallet => {
+                        let record = position_record(
+                            &mut report.positions,
+                            &mut indices,
+                            &closed.position_nft,
+                        );
+                        record.pool = Some(closed.pool.to_string());
+                        record.closed_at = Some(closed.timestamp);
+                        record.tick_lower = Some(closed.tick_lower);
+                        record.tick_upper = Some(closed.tick_upper);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        report
+    }
+
+    pub fn swaps_csv(&self) -> String {
+        let mut csv = String::from(
+            "signature,timestamp,pool,zero_for_one,mint_in,mint_out,amount_in,amount_out,\
+             amounts_realized,slippage_bps,cost_basis_token_1,realized_pnl_token_1\n",
+        );
+        for swap in &self.swaps {
+            csv += &csv_row(&[
+                &swap.signature,
+                &swap.timestamp,
+                &swap.pool,
+                &swap.zero_for_one,
+                &optional(&swap.mint_in),
+                &optional(&swap.mint_out),
+                &swap.amount_in,
+                &swap.amount_out,
+                &swap.amounts_realized,
+                &swap.slippage_bps,
+                &optional(&swap.cost_basis_token_1),
+                &optional(&swap.realized_pnl_token_1),
+            ]);
+        }
+        csv
+    }
+
+    /*
+     * One row per position. Rewards are `mint:amount` pairs separated by
+     * semicolons
+     */
+    pub fn positions_csv(&self) -> String {
+        let mut csv = String::from(
+            "position_nft,pool,opened_at,closed_at,tick_lower,tick_upper,deposited_0,\
+             deposited_1,withdrawn_0,withdrawn_1,fees_0,fees_1,rewards,cost_token_1,\
+             proceeds_token_1,realized_pnl_token_1\n",
+        );
+        for position in &self.positions {
+            let rewards = position
+                .rewards
+                .iter()
+                .map(|(mint, amount)| format!("{mint}:{amount}"))
+                .collect::<Vec<_>>()
+                .join(";");
+            csv += &csv_row(&[
+                &position.position_nft,
+                &optional(&position.pool),
+                &optional(&position.opened_at),
+                &optional(&position.closed_at),
+                &optional(&position.tick_lower),
+                &optional(&position.tick_upper),
+                &position.deposited_0,
+                &position.deposited_1,
+                &position.withdrawn_0,
+                &position.withdrawn_1,
+                &position.fees_0,
+                &position.fees_1,
+                &rewards,
+                &optional(&position.cost_token_1),
+                &optional(&position.proceeds_token_1),
+                &optional(&position.realized_pnl_token_1),
+            ]);
+        }
+        csv
+    }
+}
+
+/*
+ * The record of `position_nft`, added when it is first seen
+ */
+fn position_record<'a>(
+    records: &'a mut Vec<PositionRecord>,
+    indices: &mut HashMap<Pubkey, usize>,
+    position_nft: &Pubkey,
+) -> &'a mut PositionRecord {
+    let index = *indices.entry(*position_nft).or_insert_with(|| {
+        records.push(PositionRecord {
+            position_nft: position_nft.to_string(),
+            ..PositionRecord::default()
+        });
+        records.len() - 1
+    });
+    &mut records[index]
+}
+
+/// Fields hold addresses and numbers only, so none needs quoting
+fn csv_row(fields: &[&dyn Display]) -> String {
+    let mut row = fields
+        .iter()
+        .map(|field| field.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+/// An empty field for None
+fn optional<T: Display>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+/*
+ * The one mint the wallet spent and the one it received, when its balances
+ * changed in exactly those two
+ */
+fn swap_balances(changes: &HashMap<Pubkey, i128>) -> Option<((Pubkey, u64), (Pubkey, u64))> {
+    let mut spent = changes.iter().filter(|(_, change)| **change < 0);
+    let mut received = changes.iter().filter(|(_, change)| **change > 0);
+    match (spent.next(), spent.next(), received.next(), received.next()) {
+        (Some((mint_in, spent)), None, Some((mint_out, received)), None) => Some((
+            (*mint_in, u64::try_from(-spent).ok()?),
+            (*mint_out, u64::try_from(*received).ok()?),
+        )),
+        _ => None,
+    }
+}
+
+/*
+ * Net change of the token accounts `wallet` owns, by mint. Accounts opened
+ * or closed in the transaction count from or to zero
+ */
+fn token_changes(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+    wallet: &Pubkey,
+) -> HashMap<Pubkey, i128> {
+    let mut changes = HashMap::new();
+    let Some(meta) = &transaction.transaction.meta else {
+        return changes;
+    };
+    let wallet = wallet.to_string();
+    let mut add = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>, sign: i128| {
+        let OptionSerializer::Some(balances) = balances else {
+            return;
+        };
+        for balance in balances {
+            if balance.owner.as_ref() != OptionSerializer::Some(&wallet) {
+                continue;
+            }
+            let (Ok(mint), Ok(amount)) = (
+                balance.mint.parse::<Pubkey>(),
+                balance.ui_token_amount.amount.parse::<u64>(),
+            ) else {
+                continue;
+            };
+            *changes.entry(mint).or_insert(0) += sign * amount as i128;
+        }
+    };
+    add(&meta.pre_token_balances, -1);
+    add(&meta.post_token_balances, 1);
+    changes.retain(|_, change| *change != 0);
+    changes
+}
+
+/*
+ * `wallet`'s successful transactions among its `limit` latest that emitted
+ * the program's events, oldest first
+ */
+pub async fn fetch_history(
+    rpc: &RpcClient,
+    wallet: &Pubkey,
+    limit: usize,
+) -> Result<Vec<HistoryEntry>> {
+    // Transactions are only served at confirmed or finalized commitment
+    let commitment = match rpc.commitment() {
+        commitment if commitment.is_at_least_confirmed() => commitment,
+        _ => CommitmentConfig::confirmed(),
+    };
+    let mut signatures = Vec::new();
+    let mut before = None;
+    while signatures.len() < limit {
+        let page = rpc
+            .get_signatures_for_address_with_config(
+                wallet,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    limit: Some((limit - signatures.len()).min(SIGNATURES_PAGE)),
+                    commitment: Some(commitment),
+                    ..GetConfirmedSignaturesForAddress2Config::default()
+                },
+            )
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(
+            last.signature
+                .parse()
+                .expect("RPC returns valid signatures"),
+        );
+        signatures.extend(page);
+    }
+
+    let mut history = Vec::new();
+    for status in signatures
+        .iter()
+        .rev()
+        .filter(|status| status.err.is_none())
+    {
+        let signature: Signature = status
+            .signature
+            .parse()
+            .expect("RPC returns valid signatures");
+        let transaction = rpc
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        let events = parse_transaction(&transaction);
+        if events.is_empty() {
+            continue;
+        }
+        history.push(HistoryEntry {
+            signature,
+            slot: transaction.slot,
+            events,
+            token_changes: token_changes(&transaction, wallet),
+        });
+    }
+    Ok(history)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raydium_integration::{PositionOpened, PositionPnlRealized, SwapExecuted};
+
+    fn swap(
+        user: Pubkey,
+        pool: Pubkey,
+        zero_for_one: bool,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> ProgramEvent {
+        let (before, after) = if zero_for_one {
+            (1 << 64, (1 << 64) - 1)
+        } else {
+            (1 << 64, (1 << 64) + 1)
+        };
+        ProgramEvent::SwapExecuted(SwapExecuted {
+            user,
+            pool,
+            amount_in,
+            amount_out,
+            expected_amount: amount_out,
+            slippage_bps: 50,
+            is_base_input: true,
+            timestamp: 1_700_000_000,
+            sqrt_price_before_x64: before,
+            sqrt_price_after_x64: after,
+            tick_after: 0,
+            amm_fee: 0,
+            protocol_fee: 0,
+            realized_slippage_bps: 0,
+            default_slippage: false,
+            user_seq: 0,
+            op_id: 0,
+            ui_amount_in: amount_in,
+            ui_amount_out: amount_out,
+        })
+    }
+
+    fn entry(events: Vec<ProgramEvent>) -> HistoryEntry {
+        HistoryEntry {
+            signature: Signature::default(),
+            slot: 0,
+            events,
+            token_changes: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn realizes_swap_pnl_against_the_average_cost() {
+        let wallet = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let history = [
+            entry(vec![swap(wallet, pool, false, 1_000, 100)]),
+            entry(vec![swap(wallet, pool, false, 3_000, 100)]),
+            entry(vec![swap(wallet, pool, true, 100, 2_500)]),
+            // More than is left of what was bought
+            entry(vec![swap(wallet, pool, true, 200, 5_000)]),
+            entry(vec![swap(Pubkey::new_unique(), pool, true, 100, 1)]),
+        ];
+        let report = AccountingReport::from_history(&wallet, &history);
+        assert_eq!(report.swaps.len(), 4);
+        assert_eq!(report.swaps[2].cost_basis_token_1, Some(2_000));
+        assert_eq!(report.swaps[2].realized_pnl_token_1, Some(500));
+        assert_eq!(report.swaps[3].realized_pnl_token_1, None);
+        assert!(report
+            .swaps_csv()
+            .lines()
+            .nth(3)
+            .unwrap()
+            .ends_with(",2000,500"));
+    }
+
+    #[test]
+    fn sums_position_flows_and_takes_the_recorded_pnl() {
+        let wallet = Pubkey::new_unique();
+        let pool = Pubkey::new_unique();
+        let position_nft = Pubkey::new_unique();
+        let history = [
+            entry(vec![ProgramEvent::PositionOpened(PositionOpened {
+                user: wallet,
+                pool,
+                position_nft,
+                tick_lower: -60,
+                tick_upper: 60,
+                liquidity: 1_000,
+                amount_0: 500,
+                amount_1: 700,
+                timestamp: 1,
+                owner: wallet,
+                user_seq: 0,
+                ui_amount_0: 500,
+                ui_amount_1: 700,
+            })]),
+            entry(vec![ProgramEvent::PositionPnlRealized(
+                PositionPnlRealized {
+                    user: wallet,
+                    pool,
+                    position_nft,
+                    deposited_0: 500,
+                    deposited_1: 700,
+                    withdrawn_0: 400,
+                    withdrawn_1: 850,
+                    fees_collected_0: 5,
+                    fees_collected_1: 7,
+                    cost_token_1: 1_200,
+                    proceeds_token_1: 1_262,
+                    realized_pnl_token_1: 62,
+                    timestamp: 2,
+                    op_id: 0,
+                },
+            )]),
+        ];
+        let report = AccountingReport::from_history(&wallet, &history);
+        let position = &report.positions[0];
+        assert_eq!((position.deposited_0, position.deposited_1), (500, 700));
+        assert_eq!(position.opened_at, Some(1));
+        assert_eq!(position.realized_pnl_token_1, Some(62));
+        assert!(report
+            .positions_csv()
+            .lines()
+            .nth(1)
+            .unwrap()
+            .ends_with(",1200,1262,62"));
+    }
+}
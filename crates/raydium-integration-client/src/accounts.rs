@@ -0,0 +1,355 @@
+//! Fetching and decoding the accounts instructions are built from.
+use anchor_lang::prelude::*;
+use anchor_lang::{Discriminator, ZeroCopy};
+use raydium_amm_v3::states::{PersonalPositionState, PoolState};
+use raydium_integration::math::{MAX_TICK, MIN_TICK};
+use raydium_integration::{
+    pda, DutchOrder, FloorOrder, GlobalConfig, PositionRegistryPage, SharedPositionShare,
+    SwapValidatorRegistry, UserPositionRegistry,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::Account;
+
+use crate::helpers::{
+    personal_position_address, tick_array_address, tick_array_bitmap_extension_address,
+    tick_array_start_index, ticks_per_tick_array,
+};
+use crate::{ClientError, Result};
+
+/// Tick arrays past the current one a swap looks ahead for initialized ones
+const SWAP_TICK_ARRAY_LOOKAHEAD: usize = 10;
+
+/// Keys and parameters of a Raydium CLMM pool, as of when it was fetched
+#[derive(Clone, Debug)]
+pub struct PoolKeys {
+    pub address: Pubkey,
+    pub amm_config: Pubkey,
+    pub token_mint_0: Pubkey,
+    pub token_mint_1: Pubkey,
+    pub token_vault_0: Pubkey,
+    pub token_vault_1: Pubkey,
+    /// Token program owning `token_mint_0`
+    pub token_program_0: Pubkey,
+    /// Token program owning `token_mint_1`
+    pub token_program_1: Pubkey,
+    pub observation_key: Pubkey,
+    /// Raydium's tick array bitmap extension, if the pool has one. Pools
+    /// created before Raydium introduced it don't
+    pub tick_array_bitmap_extension: Option<Pubkey>,
+    pub tick_spacing: u16,
+    pub tick_current: i32,
+    pub sqrt_price_x64: u128,
+    pub mint_decimals_0: u8,
+    pub mint_decimals_1: u8,
+}
+
+impl PoolKeys {
+    /*
+     * Load a pool and the token programs of its mints
+     */
+    pub async fn fetch(rpc: &RpcClient, pool: &Pubkey) -> Result<Self> {
+        let pool_state: PoolState = fetch_zero_copy(rpc, pool).await?;
+        let mints = [pool_state.token_mint_0, pool_state.token_mint_1];
+        let bitmap_extension = tick_array_bitmap_extension_address(pool).0;
+        let accounts = rpc
+            .get_multiple_accounts(&[mints[0], mints[1], bitmap_extension])
+            .await?;
+        let token_program = |index: usize| {
+            accounts[index]
+                .as_ref()
+                .map(|mint| mint.owner)
+                .ok_or(ClientError::AccountNotFound(mints[index]))
+        };
+        let mut keys = Self::new(*pool, &pool_state, token_program(0)?, token_program(1)?);
+        keys.tick_array_bitmap_extension = accounts[2].as_ref().map(|_| bitmap_extension);
+        Ok(keys)
+    }
+
+    pub fn new(
+        address: Pubkey,
+        pool_state: &PoolState,
+        token_program_0: Pubkey,
+        token_program_1: Pubkey,
+    ) -> Self {
+        Self {
+            address,
+            amm_config: pool_state.amm_config,
+            token_mint_0: pool_state.token_mint_0,
+            token_mint_1: pool_state.token_mint_1,
+            token_vault_0: pool_state.token_vault_0,
+            token_vault_1: pool_state.token_vault_1,
+            token_program_0,
+            token_program_1,
+            observation_key: pool_state.observation_key,
+            tick_array_bitmap_extension: None,
+            tick_spacing: pool_state.tick_spacing,
+            tick_current: pool_state.tick_current,
+            sqrt_price_x64: pool_state.sqrt_price_x64,
+            mint_decimals_0: pool_state.mint_decimals_0,
+            mint_decimals_1: pool_state.mint_decimals_1,
+        }
+    }
+
+    /*
+     * Whether a swap paying in `input_mint` sells token_0 for token_1
+     */
+    pub fn zero_for_one(&self, input_mint: &Pubkey) -> Result<bool> {
+        if *input_mint == self.token_mint_0 {
+            Ok(true)
+        } else if *input_mint == self.token_mint_1 {
+            Ok(false)
+        } else {
+            Err(ClientError::MintNotInPool {
+                mint: *input_mint,
+                pool: self.address,
+            })
+        }
+    }
+
+    /*
+     * Initialized tick arrays a swap would walk from the current price, in
+     * order and at most `max`. The first is the one Raydium starts from
+     */
+    pub async fn fetch_swap_tick_arrays(
+        &self,
+        rpc: &RpcClient,
+        zero_for_one: bool,
+        max: usize,
+    ) -> Result<Vec<Pubkey>> {
+        let ticks_in_array = ticks_per_tick_array(self.tick_spacing);
+        let step = if zero_for_one {
+            -ticks_in_array
+        } else {
+            ticks_in_array
+        };
+        let first = tick_array_start_index(self.tick_current, self.tick_spacing);
+        let candidates: Vec<Pubkey> = (0..=SWAP_TICK_ARRAY_LOOKAHEAD as i32)
+            .map(|offset| first + offset * step)
+            .take_while(|start| *start + ticks_in_array > MIN_TICK && *start <= MAX_TICK)
+            .map(|start| tick_array_address(&self.address, start).0)
+            .collect();
+        let accounts = rpc.get_multiple_accounts(&candidates).await?;
+        Ok(candidates
+            .into_iter()
+            .zip(accounts)
+            .filter(|(_, account)| account.is_some())
+            .map(|(address, _)| address)
+            .take(max)
+            .collect())
+    }
+}
+
+/// Keys of a Raydium position, as of when it was fetched
+#[derive(Clone, Debug)]
+pub struct PositionKeys {
+    pub nft_mint: Pubkey,
+    /// Token program owning `nft_mint`
+    pub nft_token_program: Pubkey,
+    pub personal_position: Pubkey,
+    pub pool: Pubkey,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub liquidity: u128,
+}
+
+impl PositionKeys {
+    /*
+     * Load the position behind a position NFT
+     */
+    pub async fn fetch(rpc: &RpcClient, nft_mint: &Pubkey) -> Result<Self> {
+        let personal_position = personal_position_address(nft_mint).0;
+        let state: PersonalPositionState = fetch_account(rpc, &personal_position).await?;
+        let nft_token_program = fetch_raw(rpc, nft_mint).await?.owner;
+        Ok(Self {
+            nft_mint: *nft_mint,
+            nft_token_program,
+            personal_position,
+            pool: state.pool_id,
+            tick_lower_index: state.tick_lower_index,
+            tick_upper_index: state.tick_upper_index,
+            liquidity: state.liquidity,
+        })
+    }
+}
+
+/*
+ * Protocol governance settings every swap reads: pause flags, the protocol
+ * fee and the round-trip policy
+ */
+pub async fn fetch_global_config(rpc: &RpcClient) -> Result<GlobalConfig> {
+    fetch_account(rpc, &pda::global_config_address().0).await
+}
+
+/*
+ * Programs registered to vet every swap. The program requires them first in
+ * the remaining accounts of swaps
+ */
+pub async fn fetch_swap_validators(rpc: &RpcClient) -> Result<Vec<Pubkey>> {
+    let address = pda::swap_validators_address().0;
+    let Some(account) = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+    else {
+        return Ok(Vec::new());
+    };
+    let registry = SwapValidatorRegistry::try_deserialize(&mut account.data.as_slice())
+        .map_err(|_| ClientError::InvalidAccountData(address))?;
+    Ok(registry.validators[..registry.validator_count as usize].to_vec())
+}
+
+/*
+ * Position NFT mints in the owner's position registry, with the page each
+ * is recorded on. Empty when the owner has no registry
+ */
+pub async fn fetch_registered_positions(
+    rpc: &RpcClient,
+    owner: &Pubkey,
+) -> Result<Vec<(u32, Pubkey)>> {
+    let address = pda::position_registry_address(owner).0;
+    let registry: UserPositionRegistry = match fetch_account(rpc, &address).await {
+        Ok(registry) => registry,
+        Err(ClientError::AccountNotFound(_)) => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut positions = Vec::with_capacity(registry.position_count as usize);
+    for page in 0..registry.page_count {
+        let address = pda::position_registry_page_address(owner, page).0;
+        let registry_page: PositionRegistryPage = fetch_account(rpc, &address).await?;
+        positions.extend(registry_page.positions.into_iter().map(|mint| (page, mint)));
+    }
+    Ok(positions)
+}
+
+/*
+ * Every open Dutch order, found by discriminator and size
+ */
+pub async fn fetch_dutch_orders(rpc: &RpcClient) -> Result<Vec<(Pubkey, DutchOrder)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize((8 + DutchOrder::SIZE) as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, DutchOrder::DISCRIMINATOR)),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    rpc.get_program_accounts_with_config(&raydium_integration::ID, config)
+        .await?
+        .into_iter()
+        .map(|(address, account)| {
+            DutchOrder::try_deserialize(&mut account.data.as_slice())
+                .map(|order| (address, order))
+                .map_err(|_| ClientError::InvalidAccountData(address))
+        })
+        .collect()
+}
+
+/*
+ * Every open floor order, found by discriminator and size
+ */
+pub async fn fetch_floor_orders(rpc: &RpcClient) -> Result<Vec<(Pubkey, FloorOrder)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize((8 + FloorOrder::SIZE) as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, FloorOrder::DISCRIMINATOR)),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    rpc.get_program_accounts_with_config(&raydium_integration::ID, config)
+        .await?
+        .into_iter()
+        .map(|(address, account)| {
+            FloorOrder::try_deserialize(&mut account.data.as_slice())
+                .map(|order| (address, order))
+                .map_err(|_| ClientError::InvalidAccountData(address))
+        })
+        .collect()
+}
+
+/*
+ * A contributor's shares in shared positions, found by discriminator, size
+ * and contributor
+ */
+pub async fn fetch_shared_position_shares(
+    rpc: &RpcClient,
+    contributor: &Pubkey,
+) -> Result<Vec<(Pubkey, SharedPositionShare)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::DataSize((8 + SharedPositionShare::SIZE) as u64),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                SharedPositionShare::DISCRIMINATOR,
+            )),
+            // After the discriminator and the shared position
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8 + 32, contributor.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            commitment: Some(rpc.commitment()),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    rpc.get_program_accounts_with_config(&raydium_integration::ID, config)
+        .await?
+        .into_iter()
+        .map(|(address, account)| {
+            SharedPositionShare::try_deserialize(&mut account.data.as_slice())
+                .map(|share| (address, share))
+                .map_err(|_| ClientError::InvalidAccountData(address))
+        })
+        .collect()
+}
+
+/*
+ * Fetch and deserialize a Borsh account, checking its discriminator
+ */
+pub async fn fetch_account<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T> {
+    let account = fetch_raw(rpc, address).await?;
+    T::try_deserialize(&mut account.data.as_slice())
+        .map_err(|_| ClientError::InvalidAccountData(*address))
+}
+
+/*
+ * Fetch and copy out a zero-copy account, checking its discriminator
+ */
+pub async fn fetch_zero_copy<T: ZeroCopy + Discriminator>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<T> {
+    let account = fetch_raw(rpc, address).await?;
+    decode_zero_copy(address, &account.data)
+}
+
+/*
+ * Copy a zero-copy account out of its data; the copy is unaligned-safe
+ */
+pub fn decode_zero_copy<T: ZeroCopy + Discriminator>(address: &Pubkey, data: &[u8]) -> Result<T> {
+    let discriminator_len = T::DISCRIMINATOR.len();
+    match data.get(..discriminator_len + std::mem::size_of::<T>()) {
+        Some(data) if data.starts_with(T::DISCRIMINATOR) => {
+            Ok(bytemuck::pod_read_unaligned(&data[discriminator_len..]))
+        }
+        _ => Err(ClientError::InvalidAccountData(*address)),
+    }
+}
+
+async fn fetch_raw(rpc: &RpcClient, address: &Pubkey) -> Result<Account> {
+    rpc.get_account_with_commitment(address, rpc.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))
+}
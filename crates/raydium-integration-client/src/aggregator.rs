@@ -0,0 +1,253 @@
+//! Proxy quotes against an aggregator's.
+//!
+//! [`JupiterClient`] asks Jupiter's quote API for the best route of a swap,
+//! and [`compare_quotes`] sets it beside the proxy's own quote of the same
+//! pair and size on one pool. The spread tells an integrator, trade by
+//! trade, whether routing through the proxy is competitive. The proxy's
+//! protocol fee is paid on top of the input, so the comparison scales the
+//! proxy's output down to what the aggregator's input would buy with the
+//! fee included.
+use anchor_lang::prelude::Pubkey;
+use raydium_integration::math::compute_protocol_fee;
+use serde::{Deserialize, Deserializer};
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::quote::{Quote, SwapQuoter};
+use crate::{ClientError, PoolKeys, Result};
+
+/// Jupiter's public quote endpoint
+pub const JUPITER_QUOTE_URL: &str = "https://lite-api.jup.ag/swap/v1/quote";
+
+/// Tick arrays a comparison quote loads ahead of the price
+const COMPARE_TICK_ARRAYS: usize = 10;
+
+/// Jupiter's best route for a swap
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JupiterQuote {
+    #[serde(deserialize_with = "u64_string")]
+    pub in_amount: u64,
+    #[serde(deserialize_with = "u64_string")]
+    pub out_amount: u64,
+    /// Output after Jupiter's slippage tolerance
+    #[serde(deserialize_with = "u64_string")]
+    pub other_amount_threshold: u64,
+    pub route_plan: Vec<RoutePlanStep>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutePlanStep {
+    pub swap_info: SwapInfo,
+    /// Share of the input routed through this step
+    pub percent: u8,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapInfo {
+    /// The venue's pool
+    pub amm_key: String,
+    /// The venue's name
+    pub label: Option<String>,
+}
+
+impl JupiterQuote {
+    /// Venues the route goes through, in order
+    pub fn venues(&self) -> Vec<&str> {
+        self.route_plan
+            .iter()
+            .map(|step| step.swap_info.label.as_deref().unwrap_or("unknown"))
+            .collect()
+    }
+}
+
+fn u64_string<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<u64, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+/// HTTP client of Jupiter's quote API
+#[derive(Clone, Debug)]
+pub struct JupiterClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl Default for JupiterClient {
+    fn default() -> Self {
+        Self::new(JUPITER_QUOTE_URL)
+    }
+}
+
+impl JupiterClient {
+    pub fn new(quote_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: quote_url.to_string(),
+        }
+    }
+
+    /*
+     * Best route selling exactly `amount` of `input_mint` for `output_mint`
+     */
+    pub async fn quote(
+        &self,
+        input_mint: &Pubkey,
+        output_mint: &Pubkey,
+        amount: u64,
+        slippage_bps: u16,
+    ) -> Result<JupiterQuote> {
+        self.http
+            .get(&self.url)
+            .query(&[
+                ("inputMint", input_mint.to_string()),
+                ("outputMint", output_mint.to_string()),
+                ("amount", amount.to_string()),
+                ("slippageBps", slippage_bps.to_string()),
+                ("swapMode", "ExactIn".to_string()),
+            ])
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|error| ClientError::Aggregator(error.to_string()))?
+            .json()
+            .await
+            .map_err(|error| ClientError::Aggregator(error.to_string()))
+    }
+}
+
+/// The proxy's quote beside the aggregator's, for the same input
+#[derive(Clone, Debug)]
+pub struct QuoteComparison {
+    pub proxy: Quote,
+    pub jupiter: JupiterQuote,
+    /// Protocol fee the proxy charges on top of the input
+    pub protocol_fee: u64,
+    /// The proxy's output had the fee come out of the input
+    pub proxy_amount_out: u64,
+    /// How far the proxy's output is above the aggregator's, in basis
+    /// points of the aggregator's; negative when the aggregator pays more
+    pub spread_bps: i64,
+}
+
+impl QuoteComparison {
+    pub fn new(proxy: Quote, jupiter: JupiterQuote, protocol_fee_bps: u16) -> Self {
+        let protocol_fee = compute_protocol_fee(proxy.amount_in, protocol_fee_bps);
+        let proxy_amount_out = (proxy.amount_out as u128 * proxy.amount_in as u128)
+            .checked_div(proxy.amount_in as u128 + protocol_fee as u128)
+            .unwrap_or_default() as u64;
+        let spread_bps = match jupiter.out_amount {
+            0 => 0,
+            jupiter_out => {
+                (proxy_amount_out as i128 - jupiter_out as i128) * 10_000 / jupiter_out as i128
+            }
+        } as i64;
+        Self {
+            proxy,
+            jupiter,
+            protocol_fee,
+            proxy_amount_out,
+            spread_bps,
+        }
+    }
+
+    /*
+     * Whether the proxy pays at least the aggregator's output less
+     * `tolerance_bps`
+     */
+    pub fn proxy_is_competitive(&self, tolerance_bps: u16) -> bool {
+        self.proxy.fully_filled && self.spread_bps >= -(tolerance_bps as i64)
+    }
+}
+
+/*
+ * Quote selling exactly `amount_in` of `input_mint` on `pool` through the
+ * proxy and through Jupiter, and compare them. `protocol_fee_bps` is the
+ * global config's fee when the swap would pass it, zero otherwise
+ */
+pub async fn compare_quotes(
+    rpc: &RpcClient,
+    jupiter: &JupiterClient,
+    pool: &PoolKeys,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    slippage_bps: u16,
+    protocol_fee_bps: u16,
+) -> Result<QuoteComparison> {
+    let output_mint = if pool.zero_for_one(input_mint)? {
+        pool.token_mint_1
+    } else {
+        pool.token_mint_0
+    };
+    let quoter = SwapQuoter::fetch(rpc, pool, input_mint, COMPARE_TICK_ARRAYS).await?;
+    let proxy = quoter.quote_exact_in(amount_in, 0)?;
+    let jupiter = jupiter
+        .quote(input_mint, &output_mint, amount_in, slippage_bps)
+        .await?;
+    Ok(QuoteComparison::new(proxy, jupiter, protocol_fee_bps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = r#"{
+        "inputMint": "So11111111111111111111111111111111111111112",
+        "inAmount": "1000000000",
+        "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "outAmount": "150000000",
+        "otherAmountThreshold": "149250000",
+        "swapMode": "ExactIn",
+        "slippageBps": 50,
+        "priceImpactPct": "0",
+        "routePlan": [
+            {
+                "swapInfo": {
+                    "ammKey": "3ucNos4NbumPLZNWztqGHNFFgkHeRMBQAVemeeomsUxv",
+                    "label": "Raydium CLMM",
+                    "inputMint": "So11111111111111111111111111111111111111112",
+                    "outputMint": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                    "inAmount": "1000000000",
+                    "outAmount": "150000000",
+                    "feeAmount": "250000",
+                    "feeMint": "So11111111111111111111111111111111111111112"
+                },
+                "percent": 100
+            }
+        ]
+    }"#;
+
+    fn proxy_quote(amount_in: u64, amount_out: u64) -> Quote {
+        Quote {
+            amount_in,
+            amount_out,
+            fee: 0,
+            sqrt_price_after_x64: 1 << 64,
+            tick_after: 0,
+            price_impact_bps: 0,
+            tick_arrays: Vec::new(),
+            fully_filled: true,
+        }
+    }
+
+    #[test]
+    fn spread_counts_the_protocol_fee() {
+        let jupiter: JupiterQuote = serde_json::from_str(RESPONSE).unwrap();
+        assert_eq!(jupiter.out_amount, 150_000_000);
+        assert_eq!(jupiter.venues(), ["Raydium CLMM"]);
+
+        let even = QuoteComparison::new(proxy_quote(1_000_000_000, 150_000_000), jupiter, 0);
+        assert_eq!(even.spread_bps, 0);
+        assert!(even.proxy_is_competitive(0));
+
+        // A 1% fee on top of the input costs about 1% of the output
+        let with_fee =
+            QuoteComparison::new(proxy_quote(1_000_000_000, 150_000_000), even.jupiter, 100);
+        assert_eq!(with_fee.protocol_fee, 10_000_000);
+        assert_eq!(with_fee.spread_bps, -99);
+        assert!(!with_fee.proxy_is_competitive(50));
+        assert!(with_fee.proxy_is_competitive(100));
+    }
+}
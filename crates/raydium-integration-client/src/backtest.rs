@@ -0,0 +1,465 @@
+//! Replaying pool history against liquidity and DCA strategies.
+//!
+//! A backtest walks a pool's swaps in order, as [`SwapObservation`]s taken
+//! from the program's `SwapExecuted` events ([`fetch_swap_events`] reads
+//! them from chain) or from any other source. [`backtest_range`] holds a
+//! position through them and [`backtest_dca`] buys on a schedule. Position
+//! amounts, deposits and fees go through the program's own `math` module, so
+//! a strategy is sized and valued the way the program would size it on
+//! chain. Fees accrue to the position by its share of the in-range
+//! liquidity; price impact of the strategy's own trades is left out.
+use anchor_lang::prelude::Pubkey;
+use raydium_integration::math::{
+    amount_0_delta, amount_1_delta, compute_trade_fee, convert_at_sqrt_price, full_range_ticks,
+    liquidity_for_amounts, sqrt_price_at_tick, ticks_around_price, value_in_token_1,
+};
+use raydium_integration::SwapExecuted;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::events::{parse_transaction, ProgramEvent};
+use crate::Result;
+
+/// One swap in a pool's history
+#[derive(Clone, Copy, Debug)]
+pub struct SwapObservation {
+    pub timestamp: i64,
+    pub zero_for_one: bool,
+    /// Input, fee included
+    pub amount_in: u64,
+    pub sqrt_price_before_x64: u128,
+    pub sqrt_price_after_x64: u128,
+    /// The pool's in-range liquidity at the swap
+    pub pool_liquidity: u128,
+}
+
+impl SwapObservation {
+    /*
+     * An observation from a `SwapExecuted` event. The event carries no pool
+     * liquidity, so it is given; the direction is read from the price move
+     */
+    pub fn from_event(event: &SwapExecuted, pool_liquidity: u128) -> Self {
+        Self {
+            timestamp: event.timestamp,
+            zero_for_one: event.sqrt_price_after_x64 < event.sqrt_price_before_x64,
+            // An exact-out swap's expected amount is its input bound
+            amount_in: if event.is_base_input {
+                event.amount_in
+            } else {
+                event.expected_amount
+            },
+            sqrt_price_before_x64: event.sqrt_price_before_x64,
+            sqrt_price_after_x64: event.sqrt_price_after_x64,
+            pool_liquidity,
+        }
+    }
+}
+
+/// The pool parameters a backtest needs
+#[derive(Clone, Copy, Debug)]
+pub struct BacktestPool {
+    pub tick_spacing: u16,
+    /// The AMM config's trade fee rate, per million
+    pub trade_fee_rate: u32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum RangeStrategy {
+    /// Hold one range throughout
+    Fixed { tick_lower: i32, tick_upper: i32 },
+    /// Hold the full range throughout
+    FullRange,
+    /// Open `range_bps` around the price, and whenever the price leaves
+    /// the range, withdraw, swap back to the range's ratio and reopen
+    /// around the new price
+    Rebalancing { range_bps: u16 },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RangeBacktest {
+    pub fees_0: u64,
+    pub fees_1: u64,
+    /// Withdrawn liquidity and idle tokens at the end, fees excluded
+    pub amount_0: u64,
+    pub amount_1: u64,
+    /// Everything at the end, fees included, in raw token_1 at the last price
+    pub value_in_token_1: u128,
+    /// The starting amounts held untouched, in raw token_1 at the last price
+    pub hold_value_in_token_1: u128,
+    pub swaps_in_range: usize,
+    pub swaps: usize,
+    pub rebalances: usize,
+    /// Trade fees the strategy's own rebalancing swaps paid
+    pub rebalance_fees_in_token_1: u128,
+}
+
+/// An open position in a backtest
+struct Position {
+    sqrt_price_lower_x64: u128,
+    sqrt_price_upper_x64: u128,
+    liquidity: u128,
+}
+
+impl Position {
+    fn open(
+        sqrt_price_x64: u128,
+        (tick_lower, tick_upper): (i32, i32),
+        amount_0: &mut u64,
+        amount_1: &mut u64,
+    ) -> Result<Self> {
+        let liquidity =
+            liquidity_for_amounts(sqrt_price_x64, tick_lower, tick_upper, *amount_0, *amount_1)?;
+        let position = Self {
+            sqrt_price_lower_x64: sqrt_price_at_tick(tick_lower)?,
+            sqrt_price_upper_x64: sqrt_price_at_tick(tick_upper)?,
+            liquidity,
+        };
+        // Deposits round up, as Raydium takes them
+        let (deposit_0, deposit_1) = position.amounts(sqrt_price_x64, true);
+        *amount_0 = amount_0.saturating_sub(deposit_0);
+        *amount_1 = amount_1.saturating_sub(deposit_1);
+        Ok(position)
+    }
+
+    fn in_range(&self, sqrt_price_x64: u128) -> bool {
+        self.sqrt_price_lower_x64 <= sqrt_price_x64 && sqrt_price_x64 < self.sqrt_price_upper_x64
+    }
+
+    /*
+     * Token amounts behind the liquidity at a sqrt price
+     */
+    fn amounts(&self, sqrt_price_x64: u128, round_up: bool) -> (u64, u64) {
+        let (lower, upper, liquidity) = (
+            self.sqrt_price_lower_x64,
+            self.sqrt_price_upper_x64,
+            self.liquidity,
+        );
+        if sqrt_price_x64 <= lower {
+            (amount_0_delta(lower, upper, liquidity, round_up), 0)
+        } else if sqrt_price_x64 < upper {
+            (
+                amount_0_delta(sqrt_price_x64, upper, liquidity, round_up),
+                amount_1_delta(lower, sqrt_price_x64, liquidity, round_up),
+            )
+        } else {
+            (0, amount_1_delta(lower, upper, liquidity, round_up))
+        }
+    }
+}
+
+/*
+ * Provide `amount_0` and `amount_1` through `observations`, which must be in
+ * order, with `strategy`
+ */
+pub fn backtest_range(
+    pool: &BacktestPool,
+    observations: &[SwapObservation],
+    strategy: RangeStrategy,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<RangeBacktest> {
+    let mut result = RangeBacktest::default();
+    let Some(first) = observations.first() else {
+        result.amount_0 = amount_0;
+        result.amount_1 = amount_1;
+        return Ok(result);
+    };
+    let (mut idle_0, mut idle_1) = (amount_0, amount_1);
+    let mut sqrt_price_x64 = first.sqrt_price_before_x64;
+    let ticks = |sqrt_price_x64| match strategy {
+        RangeStrategy::Fixed {
+            tick_lower,
+            tick_upper,
+        } => Ok((tick_lower, tick_upper)),
+        RangeStrategy::FullRange => Ok(full_range_ticks(pool.tick_spacing)),
+        RangeStrategy::Rebalancing { range_bps } => {
+            ticks_around_price(sqrt_price_x64, range_bps, pool.tick_spacing)
+        }
+    };
+    if let RangeStrategy::Rebalancing { .. } = strategy {
+        let range = ticks(sqrt_price_x64)?;
+        result.rebalance_fees_in_token_1 +=
+            swap_to_range_ratio(pool, sqrt_price_x64, range, &mut idle_0, &mut idle_1)?;
+    }
+    let mut position = Position::open(
+        sqrt_price_x64,
+        ticks(sqrt_price_x64)?,
+        &mut idle_0,
+        &mut idle_1,
+    )?;
+
+    for observation in observations {
+        result.swaps += 1;
+        if position.in_range(sqrt_price_x64) && position.liquidity > 0 {
+            result.swaps_in_range += 1;
+            let fee = compute_trade_fee(observation.amount_in, pool.trade_fee_rate) as u128;
+            let share = fee * position.liquidity
+                / observation
+                    .pool_liquidity
+                    .saturating_add(position.liquidity)
+                    .max(1);
+            if observation.zero_for_one {
+                result.fees_0 = result.fees_0.saturating_add(share as u64);
+            } else {
+                result.fees_1 = result.fees_1.saturating_add(share as u64);
+            }
+        }
+        sqrt_price_x64 = observation.sqrt_price_after_x64;
+
+        if let RangeStrategy::Rebalancing { .. } = strategy {
+            if !position.in_range(sqrt_price_x64) {
+                let (withdrawn_0, withdrawn_1) = position.amounts(sqrt_price_x64, false);
+                idle_0 = idle_0.saturating_add(withdrawn_0);
+                idle_1 = idle_1.saturating_add(withdrawn_1);
+                let range = ticks(sqrt_price_x64)?;
+                result.rebalance_fees_in_token_1 +=
+                    swap_to_range_ratio(pool, sqrt_price_x64, range, &mut idle_0, &mut idle_1)?;
+                position = Position::open(sqrt_price_x64, range, &mut idle_0, &mut idle_1)?;
+                result.rebalances += 1;
+            }
+        }
+    }
+
+    let (withdrawn_0, withdrawn_1) = position.amounts(sqrt_price_x64, false);
+    result.amount_0 = idle_0.saturating_add(withdrawn_0);
+    result.amount_1 = idle_1.saturating_add(withdrawn_1);
+    result.value_in_token_1 = value_in_token_1(
+        result.amount_0.saturating_add(result.fees_0),
+        result.amount_1.saturating_add(result.fees_1),
+        sqrt_price_x64,
+    );
+    result.hold_value_in_token_1 = value_in_token_1(amount_0, amount_1, sqrt_price_x64);
+    Ok(result)
+}
+
+/*
+ * Swap the idle amounts, at the price and paying the trade fee, to the
+ * ratio the range takes at the price. Returns the fee in token_1
+ */
+fn swap_to_range_ratio(
+    pool: &BacktestPool,
+    sqrt_price_x64: u128,
+    (tick_lower, tick_upper): (i32, i32),
+    amount_0: &mut u64,
+    amount_1: &mut u64,
+) -> Result<u128> {
+    // The range's token_1 share of value, from the amounts of a unit of
+    // liquidity
+    let unit = Position {
+        sqrt_price_lower_x64: sqrt_price_at_tick(tick_lower)?,
+        sqrt_price_upper_x64: sqrt_price_at_tick(tick_upper)?,
+        liquidity: 1 << 64,
+    };
+    let (unit_0, unit_1) = unit.amounts(sqrt_price_x64, false);
+    let unit_value = value_in_token_1(unit_0, unit_1, sqrt_price_x64);
+    if unit_value == 0 {
+        return Ok(0);
+    }
+    let total_value = value_in_token_1(*amount_0, *amount_1, sqrt_price_x64);
+    let target_1 = (total_value as f64 * unit_1 as f64 / unit_value as f64) as u64;
+
+    if *amount_1 > target_1 {
+        let amount_in = *amount_1 - target_1;
+        let fee = compute_trade_fee(amount_in, pool.trade_fee_rate);
+        *amount_1 -= amount_in;
+        *amount_0 = amount_0.saturating_add(convert_at_sqrt_price(
+            amount_in - fee,
+            sqrt_price_x64,
+            false,
+        ));
+        Ok(fee as u128)
+    } else {
+        let amount_in =
+            convert_at_sqrt_price(target_1 - *amount_1, sqrt_price_x64, false).min(*amount_0);
+        let fee = compute_trade_fee(amount_in, pool.trade_fee_rate);
+        *amount_0 -= amount_in;
+        *amount_1 =
+            amount_1.saturating_add(convert_at_sqrt_price(amount_in - fee, sqrt_price_x64, true));
+        Ok(value_in_token_1(fee, 0, sqrt_price_x64))
+    }
+}
+
+/// Buying with a fixed amount at a fixed interval
+#[derive(Clone, Copy, Debug)]
+pub struct DcaSchedule {
+    pub interval_secs: i64,
+    pub amount_per_buy: u64,
+    /// Spend token_0 for token_1
+    pub zero_for_one: bool,
+    /// Buys at most, unlimited when None
+    pub max_buys: Option<usize>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct DcaBacktest {
+    pub buys: usize,
+    pub spent: u64,
+    pub received: u64,
+    pub fees: u64,
+    /// What spending everything in one buy at the first observation
+    /// would have received
+    pub lump_sum_received: u64,
+}
+
+/*
+ * Buy on `schedule` through `observations`, which must be in order, at the
+ * price each buy time falls on
+ */
+pub fn backtest_dca(
+    pool: &BacktestPool,
+    observations: &[SwapObservation],
+    schedule: DcaSchedule,
+) -> DcaBacktest {
+    let mut result = DcaBacktest::default();
+    let Some(first) = observations.first() else {
+        return result;
+    };
+    let mut next_buy = first.timestamp;
+    let mut sqrt_price_x64 = first.sqrt_price_before_x64;
+    let buy = |amount: u64, sqrt_price_x64: u128| {
+        let fee = compute_trade_fee(amount, pool.trade_fee_rate);
+        let received = convert_at_sqrt_price(amount - fee, sqrt_price_x64, schedule.zero_for_one);
+        (received, fee)
+    };
+
+    for observation in observations {
+        while observation.timestamp >= next_buy
+            && schedule.max_buys.is_none_or(|max| result.buys < max)
+        {
+            let (received, fee) = buy(schedule.amount_per_buy, sqrt_price_x64);
+            result.buys += 1;
+            result.spent += schedule.amount_per_buy;
+            result.received = result.received.saturating_add(received);
+            result.fees += fee;
+            next_buy += schedule.interval_secs.max(1);
+        }
+        sqrt_price_x64 = observation.sqrt_price_after_x64;
+    }
+    result.lump_sum_received = buy(result.spent, first.sqrt_price_before_x64).0;
+    result
+}
+
+/*
+ * The pool's `SwapExecuted` events among its `limit` latest transactions,
+ * oldest first. Only swaps through this program emit them
+ */
+pub async fn fetch_swap_events(
+    rpc: &RpcClient,
+    pool: &Pubkey,
+    limit: usize,
+) -> Result<Vec<SwapExecuted>> {
+    let signatures = rpc
+        .get_signatures_for_address_with_config(
+            pool,
+            GetConfirmedSignaturesForAddress2Config {
+                limit: Some(limit),
+                commitment: Some(rpc.commitment()),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        )
+        .await?;
+    let mut events = Vec::new();
+    for status in signatures
+        .iter()
+        .rev()
+        .filter(|status| status.err.is_none())
+    {
+        let transaction = rpc
+            .get_transaction_with_config(
+                &status
+                    .signature
+                    .parse()
+                    .expect("RPC returns valid signatures"),
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(rpc.commitment()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        events.extend(parse_transaction(&transaction).into_iter().filter_map(
+            |event| match event {
+                ProgramEvent::SwapExecuted(swap) if swap.pool == *pool => Some(swap),
+                _ => None,
+            },
+        ));
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raydium_integration::math::price_to_sqrt_price;
+
+    const POOL: BacktestPool = BacktestPool {
+        tick_spacing: 10,
+        trade_fee_rate: 2_500,
+    };
+
+    fn swaps(prices: &[f64]) -> Vec<SwapObservation> {
+        prices
+            .windows(2)
+            .enumerate()
+            .map(|(index, pair)| {
+                let before = price_to_sqrt_price(pair[0], 6, 6);
+                let after = price_to_sqrt_price(pair[1], 6, 6);
+                SwapObservation {
+                    timestamp: index as i64 * 60,
+                    zero_for_one: after < before,
+                    amount_in: 1_000_000_000,
+                    sqrt_price_before_x64: before,
+                    sqrt_price_after_x64: after,
+                    pool_liquidity: 1_000_000_000_000,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fees_only_accrue_while_in_range() {
+        let observations = swaps(&[1.0, 1.01, 1.02, 1.2, 1.3, 1.25]);
+        let strategy = RangeStrategy::Rebalancing { range_bps: 500 };
+        let fixed = backtest_range(
+            &POOL,
+            &observations,
+            RangeStrategy::Fixed {
+                tick_lower: -500,
+                tick_upper: 500,
+            },
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .unwrap();
+        assert_eq!(fixed.swaps, 5);
+        assert_eq!(fixed.swaps_in_range, 3);
+        assert_eq!(fixed.rebalances, 0);
+
+        let rebalancing =
+            backtest_range(&POOL, &observations, strategy, 1_000_000_000, 1_000_000_000).unwrap();
+        assert!(rebalancing.rebalances > 0);
+        assert!(rebalancing.swaps_in_range > fixed.swaps_in_range);
+        assert!(rebalancing.rebalance_fees_in_token_1 > 0);
+    }
+
+    #[test]
+    fn dca_buys_on_schedule() {
+        let observations = swaps(&[1.0, 1.0, 2.0, 2.0, 2.0]);
+        let result = backtest_dca(
+            &POOL,
+            &observations,
+            DcaSchedule {
+                interval_secs: 120,
+                amount_per_buy: 1_000_000,
+                zero_for_one: false,
+                max_buys: None,
+            },
+        );
+        // Buys at 0s and 120s, the second after the price doubled
+        assert_eq!(result.buys, 2);
+        assert_eq!(result.spent, 2_000_000);
+        assert!(result.received < result.lump_sum_received);
+    }
+}
@@ -0,0 +1,566 @@
+//! `raydium-proxy`: the client's operations from the command line.
+//!
+//! The RPC URL, keypair and commitment come from the Solana CLI config
+//! (`~/.config/solana/cli/config.yml`) unless overridden. Each transaction
+//! prints its signature, then its events as JSON lines. `monitor` and
+//! `keeper` run until stopped, logging as they go, and serve Prometheus
+//! metrics with `--metrics-addr`; `RUST_LOG` sets the log level. `export`
+//! writes a wallet's history as JSON or CSV.
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anchor_client::Cluster;
+use anchor_lang::prelude::Pubkey;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use raydium_integration_client::accounting::{fetch_history, AccountingReport};
+use raydium_integration_client::accounts::fetch_registered_positions;
+use raydium_integration_client::aggregator::{compare_quotes, JupiterClient, JUPITER_QUOTE_URL};
+use raydium_integration_client::builders::{DcaLadder, PositionRange};
+use raydium_integration_client::compute_budget::ComputeBudget;
+use raydium_integration_client::explain::explain_client_error;
+use raydium_integration_client::jito::{JitoConfig, MAINNET_BLOCK_ENGINE_URL};
+use raydium_integration_client::keeper::{Keeper, KeeperConfig};
+use raydium_integration_client::metrics::Metrics;
+use raydium_integration_client::monitor::{AlertSink, PositionMonitor};
+use raydium_integration_client::rpc_pool::{RpcEndpoint, RpcPool, RpcPoolConfig};
+use raydium_integration_client::submit::SendConfig;
+use raydium_integration_client::{ClientError, Executed, PoolKeys, RaydiumIntegrationClient};
+use solana_cli_config::{Config, CONFIG_FILE};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+
+type Error = Box<dyn std::error::Error>;
+
+#[derive(Parser)]
+#[command(name = "raydium-proxy", version, about)]
+struct Cli {
+    /// Solana CLI config file to read defaults from
+    #[arg(long, global = true)]
+    config: Option<String>,
+    /// RPC URL, instead of the config's
+    #[arg(long, short = 'u', global = true)]
+    url: Option<String>,
+    /// RPC URL to fail over to when the main one fails; repeatable
+    #[arg(long, global = true)]
+    fallback_url: Vec<String>,
+    /// Payer keypair file, instead of the config's
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<String>,
+    /// Compute unit limit, instead of simulating for it
+    #[arg(long, global = true)]
+    compute_unit_limit: Option<u32>,
+    /// Priority fee in micro-lamports per compute unit, instead of the
+    /// percentile of recent fees
+    #[arg(long, global = true)]
+    compute_unit_price: Option<u64>,
+    /// Percentile of recent priority fees to pay
+    #[arg(
+        long,
+        global = true,
+        default_value_t = 75,
+        value_parser = clap::value_parser!(u8).range(0..=100)
+    )]
+    fee_percentile: u8,
+    /// Nonce account, with the payer as authority, to sign transactions
+    /// against instead of a recent blockhash
+    #[arg(long, global = true)]
+    durable_nonce: Option<Pubkey>,
+    /// Send as Jito bundles, tipping this many lamports
+    #[arg(long, global = true)]
+    jito_tip: Option<u64>,
+    /// Jito block engine to send bundles to
+    #[arg(long, global = true, default_value = MAINNET_BLOCK_ENGINE_URL)]
+    jito_url: String,
+    /// Address to serve `monitor` and `keeper` metrics on at `/metrics`
+    #[arg(long, global = true)]
+    metrics_addr: Option<SocketAddr>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// User settings
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Sell an exact amount, expecting the quoted output
+    Swap {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        input_mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Slippage tolerance; set on the user config if it differs
+        #[arg(long, default_value_t = 50)]
+        slippage_bps: u16,
+    },
+    /// Compare the pool's quote for a swap with Jupiter's best route
+    Compare {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        input_mint: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long, default_value_t = 50)]
+        slippage_bps: u16,
+        /// The proxy's protocol fee, when the swap would pay one
+        #[arg(long, default_value_t = 0)]
+        protocol_fee_bps: u16,
+        /// Jupiter quote endpoint
+        #[arg(long, default_value = JUPITER_QUOTE_URL)]
+        jupiter_url: String,
+    },
+    /// Liquidity positions
+    #[command(subcommand)]
+    Position(PositionCommand),
+    /// Dollar-cost averaging through ladders of Dutch orders
+    #[command(subcommand)]
+    Dca(DcaCommand),
+    /// Watch positions and alert when they leave or re-enter their range
+    Monitor {
+        /// Positions to watch; the payer's registered positions when left out
+        #[arg(long)]
+        nft_mint: Vec<Pubkey>,
+        /// URL to POST each alert to as JSON, in addition to the log
+        #[arg(long)]
+        webhook: Vec<String>,
+    },
+    /// Fill Dutch and floor orders as they become fillable, paid by the payer
+    Keeper {
+        /// Seconds between rounds
+        #[arg(long, default_value_t = 5)]
+        poll_secs: u64,
+    },
+    /// Export a wallet's swaps and positions with cost basis and PnL
+    Export {
+        /// Wallet to export; the payer when left out
+        #[arg(long)]
+        wallet: Option<Pubkey>,
+        /// Latest transactions of the wallet to walk
+        #[arg(long, default_value_t = 1_000)]
+        limit: usize,
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// File for JSON, directory for `swaps.csv` and `positions.csv`;
+        /// stdout when left out
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Csv,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    SetSlippage { bps: u16 },
+}
+
+#[derive(Subcommand)]
+enum PositionCommand {
+    /// Open a position with a fresh NFT mint
+    Open {
+        #[arg(long)]
+        pool: Pubkey,
+        #[command(flatten)]
+        range: RangeArgs,
+        #[arg(long)]
+        amount_0_max: u64,
+        #[arg(long)]
+        amount_1_max: u64,
+    },
+    Increase {
+        #[arg(long)]
+        nft_mint: Pubkey,
+        #[arg(long)]
+        amount_0_max: u64,
+        #[arg(long)]
+        amount_1_max: u64,
+    },
+    Decrease {
+        #[arg(long)]
+        nft_mint: Pubkey,
+        /// Liquidity to withdraw; all of it when left out
+        #[arg(long)]
+        liquidity: Option<u128>,
+    },
+    /// Close an emptied position
+    Close {
+        #[arg(long)]
+        nft_mint: Pubkey,
+    },
+    /// Positions in the payer's position registry
+    List,
+}
+
+#[derive(Subcommand)]
+enum DcaCommand {
+    /// Escrow one Dutch order per slice, each auctioned over its interval
+    Create {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        input_mint: Pubkey,
+        /// Input sold in each slice
+        #[arg(long)]
+        amount_per_slice: u64,
+        #[arg(long)]
+        slices: u64,
+        /// Seconds between slices, and each slice's auction length
+        #[arg(long)]
+        interval_secs: i64,
+        /// Least output per slice as its auction opens
+        #[arg(long)]
+        start_min_out: u64,
+        /// Least output per slice as its auction closes
+        #[arg(long)]
+        end_min_out: u64,
+        /// Id of the first slice's order, the rest following it; the
+        /// current Unix time when left out
+        #[arg(long)]
+        order_id: Option<u64>,
+        /// Unix time the first slice's auction opens; now when left out
+        #[arg(long)]
+        start_ts: Option<i64>,
+    },
+    /// Cancel the slices still open, refunding their escrows
+    Cancel {
+        /// Id of the first slice's order
+        #[arg(long)]
+        order_id: u64,
+        #[arg(long)]
+        slices: u64,
+    },
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct RangeArgs {
+    #[arg(long)]
+    full_range: bool,
+    /// Range below and above the current price
+    #[arg(long)]
+    range_bps: Option<u16>,
+    /// Lower and upper tick
+    #[arg(long, num_args = 2, allow_negative_numbers = true)]
+    ticks: Option<Vec<i32>>,
+    /// Lower and upper price, token_1 per token_0 in whole tokens
+    #[arg(long, num_args = 2)]
+    prices: Option<Vec<f64>>,
+}
+
+impl RangeArgs {
+    fn position_range(&self) -> PositionRange {
+        match (&self.ticks, &self.prices, self.range_bps) {
+            (Some(ticks), _, _) => PositionRange::Ticks {
+                tick_lower_index: ticks[0],
+                tick_upper_index: ticks[1],
+            },
+            (None, Some(prices), _) => PositionRange::Prices {
+                price_lower: prices[0],
+                price_upper: prices[1],
+            },
+            (None, None, Some(range_bps)) => PositionRange::AroundPrice { range_bps },
+            (None, None, None) => PositionRange::FullRange,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let result = run(Cli::parse()).await;
+    // Say what a program error means before the raw error is printed
+    if let Err(error) = &result {
+        if let Some(explained) = error
+            .downcast_ref::<ClientError>()
+            .and_then(explain_client_error)
+        {
+            eprintln!("{explained}");
+        }
+    }
+    result
+}
+
+async fn run(cli: Cli) -> Result<(), Error> {
+    let config = match cli.config.as_ref().or(CONFIG_FILE.as_ref()) {
+        Some(path) => Config::load(path).unwrap_or_default(),
+        None => Config::default(),
+    };
+    let url = cli.url.unwrap_or(config.json_rpc_url);
+    let keypair_path = cli.keypair.unwrap_or(config.keypair_path);
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|error| format!("reading keypair {keypair_path}: {error}"))?;
+    let commitment = CommitmentConfig::from_str(&config.commitment)?;
+    let ws_url = Config::compute_websocket_url(&url);
+    let payer = Arc::new(payer);
+    let rpc_pool = (!cli.fallback_url.is_empty()).then(|| {
+        let endpoints = std::iter::once(&url)
+            .chain(&cli.fallback_url)
+            .map(|url| RpcEndpoint::new(url.clone(), 1))
+            .collect();
+        RpcPool::new(endpoints, RpcPoolConfig::default())
+    });
+    if let Some(rpc_pool) = rpc_pool.clone() {
+        tokio::spawn(async move { rpc_pool.run_health_checks().await });
+    }
+    let new_rpc = || match &rpc_pool {
+        Some(rpc_pool) => rpc_pool.rpc_client(commitment),
+        None => RpcClient::new_with_commitment(url.clone(), commitment),
+    };
+    let client = RaydiumIntegrationClient::new(
+        Cluster::Custom(url.clone(), ws_url.clone()),
+        payer.clone(),
+        commitment,
+    )?
+    .with_compute_budget(ComputeBudget {
+        compute_unit_limit: cli.compute_unit_limit,
+        compute_unit_price: cli.compute_unit_price,
+        fee_percentile: cli.fee_percentile,
+        ..ComputeBudget::default()
+    })
+    .with_send_config(SendConfig {
+        durable_nonce: cli.durable_nonce,
+        jito: cli.jito_tip.map(|tip_lamports| JitoConfig {
+            block_engine_url: cli.jito_url.clone(),
+            tip_lamports,
+        }),
+        ..SendConfig::default()
+    })
+    .with_rpc(new_rpc());
+    let metrics = cli.metrics_addr.map(|addr| {
+        let metrics = Metrics::new();
+        let served = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(error) = served.serve(addr).await {
+                log::error!("metrics server on {addr}: {error}");
+            }
+        });
+        metrics
+    });
+
+    match cli.command {
+        Command::Config(ConfigCommand::SetSlippage { bps }) => {
+            print_executed(&client.set_slippage(bps).await?)?;
+        }
+        Command::Swap {
+            pool,
+            input_mint,
+            amount,
+            slippage_bps,
+        } => {
+            let executed = client
+                .swap(&pool, &input_mint, amount, slippage_bps)
+                .await?;
+            print_executed(&executed)?;
+        }
+        Command::Compare {
+            pool,
+            input_mint,
+            amount,
+            slippage_bps,
+            protocol_fee_bps,
+            jupiter_url,
+        } => {
+            let rpc = new_rpc();
+            let pool = PoolKeys::fetch(&rpc, &pool).await?;
+            let comparison = compare_quotes(
+                &rpc,
+                &JupiterClient::new(&jupiter_url),
+                &pool,
+                &input_mint,
+                amount,
+                slippage_bps,
+                protocol_fee_bps,
+            )
+            .await?;
+            println!(
+                "proxy {} (fee {}) jupiter {} via {}: spread {} bps",
+                comparison.proxy_amount_out,
+                comparison.protocol_fee,
+                comparison.jupiter.out_amount,
+                comparison.jupiter.venues().join(" > "),
+                comparison.spread_bps
+            );
+        }
+        Command::Position(command) => run_position(&client, command).await?,
+        Command::Dca(command) => run_dca(&client, command).await?,
+        Command::Monitor { nft_mint, webhook } => {
+            let rpc = new_rpc();
+            let nft_mints = if nft_mint.is_empty() {
+                fetch_registered_positions(&rpc, &payer.pubkey())
+                    .await?
+                    .into_iter()
+                    .map(|(_, nft_mint)| nft_mint)
+                    .collect()
+            } else {
+                nft_mint
+            };
+            let sinks = std::iter::once(AlertSink::Log)
+                .chain(webhook.into_iter().map(AlertSink::Webhook))
+                .collect();
+            let mut monitor = PositionMonitor::new(rpc, ws_url, &nft_mints, sinks).await?;
+            if let Some(metrics) = metrics {
+                monitor = monitor.with_metrics(metrics);
+            }
+            monitor.run().await?;
+        }
+        Command::Keeper { poll_secs } => {
+            let rpc = new_rpc();
+            let config = KeeperConfig {
+                poll_interval: Duration::from_secs(poll_secs),
+                ..KeeperConfig::default()
+            };
+            let mut keeper = Keeper::new(rpc, payer.insecure_clone(), config);
+            if let Some(metrics) = metrics {
+                keeper = keeper.with_metrics(metrics);
+            }
+            keeper.run().await?;
+        }
+        Command::Export {
+            wallet,
+            limit,
+            format,
+            output,
+        } => {
+            let wallet = wallet.unwrap_or(payer.pubkey());
+            let history = fetch_history(&new_rpc(), &wallet, limit).await?;
+            let report = AccountingReport::from_history(&wallet, &history);
+            write_report(&report, format, output)?;
+        }
+    }
+    Ok(())
+}
+
+async fn run_position(
+    client: &RaydiumIntegrationClient<Arc<Keypair>>,
+    command: PositionCommand,
+) -> Result<(), Error> {
+    match command {
+        PositionCommand::Open {
+            pool,
+            range,
+            amount_0_max,
+            amount_1_max,
+        } => {
+            let (nft_mint, executed) = client
+                .open_position(&pool, range.position_range(), amount_0_max, amount_1_max)
+                .await?;
+            println!("position {nft_mint}");
+            print_executed(&executed)?;
+        }
+        PositionCommand::Increase {
+            nft_mint,
+            amount_0_max,
+            amount_1_max,
+        } => {
+            let executed = client
+                .increase_liquidity(&nft_mint, amount_0_max, amount_1_max)
+                .await?;
+            print_executed(&executed)?;
+        }
+        PositionCommand::Decrease {
+            nft_mint,
+            liquidity,
+        } => {
+            print_executed(&client.decrease_liquidity(&nft_mint, liquidity).await?)?;
+        }
+        PositionCommand::Close { nft_mint } => {
+            print_executed(&client.close_position(&nft_mint).await?)?;
+        }
+        PositionCommand::List => {
+            for position in client.positions().await? {
+                println!(
+                    "{} pool {} ticks [{}, {}) liquidity {}",
+                    position.nft_mint,
+                    position.pool,
+                    position.tick_lower_index,
+                    position.tick_upper_index,
+                    position.liquidity
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_dca(
+    client: &RaydiumIntegrationClient<Arc<Keypair>>,
+    command: DcaCommand,
+) -> Result<(), Error> {
+    match command {
+        DcaCommand::Create {
+            pool,
+            input_mint,
+            amount_per_slice,
+            slices,
+            interval_secs,
+            start_min_out,
+            end_min_out,
+            order_id,
+            start_ts,
+        } => {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            let ladder = DcaLadder {
+                first_order_id: order_id.unwrap_or(now),
+                slices,
+                amount_per_slice,
+                start_min_out,
+                end_min_out,
+                start_ts: start_ts.unwrap_or(now as i64),
+                interval: interval_secs,
+            };
+            let order_ids = ladder.order_ids();
+            println!("dca orders {}..{}", order_ids.start, order_ids.end);
+            for executed in client.create_dca(&pool, &input_mint, &ladder).await? {
+                print_executed(&executed)?;
+            }
+        }
+        DcaCommand::Cancel { order_id, slices } => {
+            for executed in client.cancel_dca(order_id..order_id + slices).await? {
+                print_executed(&executed)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_report(
+    report: &AccountingReport,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) -> Result<(), Error> {
+    match (format, output) {
+        (ExportFormat::Json, Some(path)) => {
+            std::fs::write(path, serde_json::to_string_pretty(report)?)?;
+        }
+        (ExportFormat::Json, None) => println!("{}", serde_json::to_string_pretty(report)?),
+        (ExportFormat::Csv, Some(dir)) => {
+            std::fs::create_dir_all(&dir)?;
+            std::fs::write(dir.join("swaps.csv"), report.swaps_csv())?;
+            std::fs::write(dir.join("positions.csv"), report.positions_csv())?;
+        }
+        (ExportFormat::Csv, None) => {
+            println!("{}", report.swaps_csv());
+            print!("{}", report.positions_csv());
+        }
+    }
+    Ok(())
+}
+
+fn print_executed(executed: &Executed) -> Result<(), Error> {
+    println!("signature {}", executed.signature);
+    for event in &executed.events {
+        println!("{}", serde_json::to_string(event)?);
+    }
+    Ok(())
+}
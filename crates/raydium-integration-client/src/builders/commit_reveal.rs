@@ -0,0 +1,277 @@
+//! `commit_swap` and `reveal_and_swap`.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{accounts, instruction, pda, swap_commitment_hash, GlobalConfig};
+
+#[cfg(feature = "event-cpi")]
+use super::event_authority;
+use super::{associated_token_account, build};
+use crate::helpers::{tick_array_address, tick_array_start_index};
+use crate::{PoolKeys, Result};
+
+/// A commit-reveal swap between the payer's associated token accounts. Send
+/// [`Self::commit_instruction`] first, then [`Self::instruction`] between
+/// `MIN_REVEAL_DELAY_SLOTS` and `REVEAL_WINDOW_SLOTS` slots after the commit
+/// lands. The salt must stay secret until the reveal
+#[derive(Clone, Debug)]
+pub struct RevealAndSwapBuilder<'a> {
+    pool: &'a PoolKeys,
+    payer: Pubkey,
+    zero_for_one: bool,
+    amount: u64,
+    expected_other_amount: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    salt: [u8; 32],
+    tick_arrays: Vec<Pubkey>,
+    swap_validators: Vec<Pubkey>,
+    treasury: Option<Pubkey>,
+}
+
+impl<'a> RevealAndSwapBuilder<'a> {
+    /*
+     * Sell exactly `amount` of `input_mint`, expecting `expected_amount_out`
+     * back before the payer's slippage tolerance
+     */
+    pub fn exact_in(
+        pool: &'a PoolKeys,
+        payer: &Pubkey,
+        input_mint: &Pubkey,
+        amount: u64,
+        expected_amount_out: u64,
+        salt: [u8; 32],
+    ) -> Result<Self> {
+        Self::new(
+            pool,
+            payer,
+            input_mint,
+            amount,
+            expected_amount_out,
+            true,
+            salt,
+        )
+    }
+
+    /*
+     * Buy exactly `amount` of the other token paying `input_mint`, expecting
+     * to pay `expected_amount_in` before the payer's slippage tolerance
+     */
+    pub fn exact_out(
+        pool: &'a PoolKeys,
+        payer: &Pubkey,
+        input_mint: &Pubkey,
+        amount: u64,
+        expected_amount_in: u64,
+        salt: [u8; 32],
+    ) -> Result<Self> {
+        Self::new(
+            pool,
+            payer,
+            input_mint,
+            amount,
+            expected_amount_in,
+            false,
+            salt,
+        )
+    }
+
+    fn new(
+        pool: &'a PoolKeys,
+        payer: &Pubkey,
+        input_mint: &Pubkey,
+        amount: u64,
+        expected_other_amount: u64,
+        is_base_input: bool,
+        salt: [u8; 32],
+    ) -> Result<Self> {
+        Ok(Self {
+            pool,
+            payer: *payer,
+            zero_for_one: pool.zero_for_one(input_mint)?,
+            amount,
+            expected_other_amount,
+            sqrt_price_limit_x64: 0,
+            is_base_input,
+            salt,
+            tick_arrays: Vec::new(),
+            swap_validators: Vec::new(),
+            treasury: None,
+        })
+    }
+
+    /*
+     * Price the swap stops at; zero for none. Part of the commitment
+     */
+    pub fn sqrt_price_limit_x64(mut self, sqrt_price_limit_x64: u128) -> Self {
+        self.sqrt_price_limit_x64 = sqrt_price_limit_x64;
+        self
+    }
+
+    /*
+     * Tick arrays to walk, in swap order, as `PoolKeys::fetch_swap_tick_arrays`
+     * returns them
+     */
+    pub fn tick_arrays(mut self, tick_arrays: Vec<Pubkey>) -> Self {
+        self.tick_arrays = tick_arrays;
+        self
+    }
+
+    /*
+     * Programs registered to vet swaps, as `fetch_swap_validators` returns
+     * them
+     */
+    pub fn swap_validators(mut self, swap_validators: Vec<Pubkey>) -> Self {
+        self.swap_validators = swap_validators;
+        self
+    }
+
+    /*
+     * Pay the protocol fee `global_config` charges, skimmed from the input
+     * into the treasury's associated token account and the input mint's
+     * insurance vault. Needed whenever the fee is nonzero
+     */
+    pub fn protocol_fee(mut self, global_config: &GlobalConfig) -> Self {
+        self.treasury = (global_config.protocol_fee_bps > 0).then_some(global_config.treasury);
+        self
+    }
+
+    fn mints(&self) -> (Pubkey, Pubkey) {
+        if self.zero_for_one {
+            (self.pool.token_mint_0, self.pool.token_mint_1)
+        } else {
+            (self.pool.token_mint_1, self.pool.token_mint_0)
+        }
+    }
+
+    fn token_accounts(&self) -> (Pubkey, Pubkey) {
+        let (input_mint, output_mint) = self.mints();
+        let token_program = anchor_spl::token::ID;
+        (
+            associated_token_account(&self.payer, &input_mint, &token_program),
+            associated_token_account(&self.payer, &output_mint, &token_program),
+        )
+    }
+
+    /*
+     * Hash the commit stores and the reveal must match
+     */
+    pub fn commitment_hash(&self) -> [u8; 32] {
+        let (input_token_account, output_token_account) = self.token_accounts();
+        swap_commitment_hash(
+            &self.pool.address,
+            &input_token_account,
+            &output_token_account,
+            self.amount,
+            self.expected_other_amount,
+            self.sqrt_price_limit_x64,
+            self.is_base_input,
+            &self.salt,
+        )
+    }
+
+    pub fn commit_instruction(&self) -> Instruction {
+        build(
+            accounts::CommitSwap {
+                payer: self.payer,
+                swap_commitment: pda::swap_commitment_address(&self.payer).0,
+                system_program: anchor_lang::system_program::ID,
+                #[cfg(feature = "event-cpi")]
+                event_authority: event_authority(),
+                #[cfg(feature = "event-cpi")]
+                program: raydium_integration::ID,
+            },
+            instruction::CommitSwap {
+                hash: self.commitment_hash(),
+            },
+            Vec::new(),
+        )
+    }
+
+    /*
+     * The reveal, executing the committed swap
+     */
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let (input_mint, output_mint) = self.mints();
+        let (input_vault, output_vault) = if self.zero_for_one {
+            (pool.token_vault_0, pool.token_vault_1)
+        } else {
+            (pool.token_vault_1, pool.token_vault_0)
+        };
+        let (input_token_account, output_token_account) = self.token_accounts();
+        let token_program = anchor_spl::token::ID;
+        let (tick_array, extra_tick_arrays) = match self.tick_arrays.split_first() {
+            Some((first, rest)) => (*first, rest),
+            None => {
+                let start_index = tick_array_start_index(pool.tick_current, pool.tick_spacing);
+                (tick_array_address(&pool.address, start_index).0, &[][..])
+            }
+        };
+
+        let accounts = accounts::RevealAndSwap {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            payer: self.payer,
+            swap_commitment: pda::swap_commitment_address(&self.payer).0,
+            user_cfg: pda::user_config_address(&self.payer).0,
+            amm_config: pool.amm_config,
+            pool_state: pool.address,
+            input_token_account,
+            output_token_account,
+            input_vault,
+            output_vault,
+            input_vault_mint: input_mint,
+            output_vault_mint: output_mint,
+            observation_state: pool.observation_key,
+            token_program,
+            tick_array,
+            pool_stats: None,
+            global_stats: None,
+            tick_array_bitmap_extension: pool.tick_array_bitmap_extension,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            global_config: pda::global_config_address().0,
+            swap_validators: pda::swap_validators_address().0,
+            destination_allowlist: pda::destination_allowlist_address(&self.payer).0,
+            mint_extension_opt_in: None,
+            fee_mint: self.treasury.map(|_| input_mint),
+            treasury_token_account: self
+                .treasury
+                .map(|treasury| associated_token_account(&treasury, &input_mint, &token_program)),
+            insurance_vault: self
+                .treasury
+                .map(|_| pda::insurance_vault_address(&input_mint).0),
+            round_trip_counter: pda::round_trip_counter_address(&self.payer, &pool.address).0,
+            pool_creator_record: None,
+            creator_fee_vault: None,
+            points_config: None,
+            points_ledger: None,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+
+        // Swap validators go first in the remaining accounts, then the tick
+        // arrays past the first
+        let remaining_accounts = self
+            .swap_validators
+            .iter()
+            .map(|validator| AccountMeta::new_readonly(*validator, false))
+            .chain(
+                extra_tick_arrays
+                    .iter()
+                    .map(|tick_array| AccountMeta::new(*tick_array, false)),
+            )
+            .collect();
+        build(
+            accounts,
+            instruction::RevealAndSwap {
+                amount: self.amount,
+                expected_other_amount: self.expected_other_amount,
+                sqrt_price_limit_x64: self.sqrt_price_limit_x64,
+                is_base_input: self.is_base_input,
+                salt: self.salt,
+            },
+            remaining_accounts,
+        )
+    }
+}
@@ -0,0 +1,280 @@
+//! Dutch orders: creating, filling and cancelling them, and DCA ladders of
+//! them.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{accounts, instruction, pda, DutchOrder, SPL_MEMO_PROGRAM_ID};
+
+#[cfg(feature = "event-cpi")]
+use super::event_authority;
+use super::{associated_token_account, build};
+use crate::helpers::{tick_array_address, tick_array_start_index};
+use crate::{ClientError, PoolKeys, Result};
+
+/// Dollar-cost averaging as a ladder of Dutch orders: `slices` orders of
+/// `amount_per_slice` each, with consecutive ids from `first_order_id`.
+/// Slice `i` is auctioned over the `interval` seconds from
+/// `start_ts + i * interval`, its least output falling from `start_min_out`
+/// to `end_min_out`. No keeper can fill a slice before its turn
+#[derive(Clone, Copy, Debug)]
+pub struct DcaLadder {
+    pub first_order_id: u64,
+    pub slices: u64,
+    pub amount_per_slice: u64,
+    pub start_min_out: u64,
+    pub end_min_out: u64,
+    pub start_ts: i64,
+    pub interval: i64,
+}
+
+impl DcaLadder {
+    pub fn order_ids(&self) -> std::ops::Range<u64> {
+        self.first_order_id..self.first_order_id + self.slices
+    }
+
+    /*
+     * One `create_dutch_order` per slice, escrowing `input_mint` from the
+     * owner's associated token account and paying out to their associated
+     * token account of the pool's other mint
+     */
+    pub fn instructions(
+        &self,
+        pool: &PoolKeys,
+        owner: &Pubkey,
+        input_mint: &Pubkey,
+    ) -> Result<Vec<Instruction>> {
+        let (input_token_program, output_mint, output_token_program) =
+            if pool.zero_for_one(input_mint)? {
+                (
+                    pool.token_program_0,
+                    pool.token_mint_1,
+                    pool.token_program_1,
+                )
+            } else {
+                (
+                    pool.token_program_1,
+                    pool.token_mint_0,
+                    pool.token_program_0,
+                )
+            };
+        let instructions = self
+            .order_ids()
+            .zip(0..)
+            .map(|(order_id, slice)| {
+                let dutch_order = pda::dutch_order_address(owner, order_id).0;
+                let start_ts = self.start_ts + slice * self.interval;
+                let accounts = accounts::CreateDutchOrder {
+                    owner: *owner,
+                    pool_state: pool.address,
+                    input_mint: *input_mint,
+                    owner_input_account: associated_token_account(
+                        owner,
+                        input_mint,
+                        &input_token_program,
+                    ),
+                    destination: associated_token_account(
+                        owner,
+                        &output_mint,
+                        &output_token_program,
+                    ),
+                    dutch_order,
+                    escrow: pda::dutch_escrow_address(&dutch_order).0,
+                    token_program: input_token_program,
+                    system_program: anchor_lang::system_program::ID,
+                    global_config: pda::global_config_address().0,
+                    user_cfg: pda::user_config_address(owner).0,
+                    instructions_sysvar: Some(
+                        anchor_lang::solana_program::sysvar::instructions::ID,
+                    ),
+                    destination_allowlist: pda::destination_allowlist_address(owner).0,
+                    #[cfg(feature = "event-cpi")]
+                    event_authority: event_authority(),
+                    #[cfg(feature = "event-cpi")]
+                    program: raydium_integration::ID,
+                };
+                build(
+                    accounts,
+                    instruction::CreateDutchOrder {
+                        order_id,
+                        amount_in: self.amount_per_slice,
+                        start_min_out: self.start_min_out,
+                        end_min_out: self.end_min_out,
+                        start_ts,
+                        end_ts: start_ts + self.interval,
+                    },
+                    Vec::new(),
+                )
+            })
+            .collect();
+        Ok(instructions)
+    }
+}
+
+/*
+ * Cancel one of the owner's open Dutch orders, refunding the escrow to
+ * their associated token account of the input mint
+ */
+pub fn cancel_dutch_order(
+    owner: &Pubkey,
+    order_id: u64,
+    input_mint: &Pubkey,
+    input_token_program: &Pubkey,
+) -> Instruction {
+    let dutch_order = pda::dutch_order_address(owner, order_id).0;
+    build(
+        accounts::CancelDutchOrder {
+            owner: *owner,
+            dutch_order,
+            escrow: pda::dutch_escrow_address(&dutch_order).0,
+            input_mint: *input_mint,
+            owner_input_account: associated_token_account(owner, input_mint, input_token_program),
+            token_program: *input_token_program,
+            user_cfg: pda::user_config_address(owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CancelDutchOrder {},
+        Vec::new(),
+    )
+}
+
+/// Fill a Dutch order in full, as the keeper signing the transaction.
+/// Without [`Self::tick_arrays`], only the tick array at the current price
+/// is passed
+#[derive(Clone, Debug)]
+pub struct FillDutchOrderBuilder<'a> {
+    pool: &'a PoolKeys,
+    keeper: Pubkey,
+    dutch_order: Pubkey,
+    owner: Pubkey,
+    destination: Pubkey,
+    zero_for_one: bool,
+    tick_arrays: Vec<Pubkey>,
+    swap_validators: Vec<Pubkey>,
+}
+
+impl<'a> FillDutchOrderBuilder<'a> {
+    pub fn new(
+        pool: &'a PoolKeys,
+        keeper: &Pubkey,
+        dutch_order: &Pubkey,
+        order: &DutchOrder,
+    ) -> Result<Self> {
+        if order.pool != pool.address {
+            return Err(ClientError::OrderNotInPool {
+                order: *dutch_order,
+                pool: pool.address,
+            });
+        }
+        Ok(Self {
+            pool,
+            keeper: *keeper,
+            dutch_order: *dutch_order,
+            owner: order.owner,
+            destination: order.destination,
+            zero_for_one: pool.zero_for_one(&order.input_mint)?,
+            tick_arrays: Vec::new(),
+            swap_validators: Vec::new(),
+        })
+    }
+
+    /*
+     * Tick arrays to walk, in swap order, as a quote's `tick_arrays` lists
+     * them
+     */
+    pub fn tick_arrays(mut self, tick_arrays: Vec<Pubkey>) -> Self {
+        self.tick_arrays = tick_arrays;
+        self
+    }
+
+    /*
+     * Programs registered to vet swaps, as `fetch_swap_validators` returns
+     * them
+     */
+    pub fn swap_validators(mut self, swap_validators: Vec<Pubkey>) -> Self {
+        self.swap_validators = swap_validators;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let (input_mint, output_mint, input_vault, output_vault, input_token_program) =
+            if self.zero_for_one {
+                (
+                    pool.token_mint_0,
+                    pool.token_mint_1,
+                    pool.token_vault_0,
+                    pool.token_vault_1,
+                    pool.token_program_0,
+                )
+            } else {
+                (
+                    pool.token_mint_1,
+                    pool.token_mint_0,
+                    pool.token_vault_1,
+                    pool.token_vault_0,
+                    pool.token_program_1,
+                )
+            };
+        let (tick_array, extra_tick_arrays) = match self.tick_arrays.split_first() {
+            Some((first, rest)) => (*first, rest),
+            None => {
+                let start_index = tick_array_start_index(pool.tick_current, pool.tick_spacing);
+                (tick_array_address(&pool.address, start_index).0, &[][..])
+            }
+        };
+
+        let accounts = accounts::FillDutchOrder {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            keeper: self.keeper,
+            dutch_order: self.dutch_order,
+            owner: self.owner,
+            escrow: pda::dutch_escrow_address(&self.dutch_order).0,
+            destination: self.destination,
+            amm_config: pool.amm_config,
+            pool_state: pool.address,
+            input_vault,
+            output_vault,
+            input_vault_mint: input_mint,
+            output_vault_mint: output_mint,
+            observation_state: pool.observation_key,
+            token_program: anchor_spl::token::ID,
+            tick_array,
+            tick_array_bitmap_extension: pool.tick_array_bitmap_extension,
+            swap_validators: pda::swap_validators_address().0,
+            global_config: pda::global_config_address().0,
+            mint_extension_opt_in: None,
+            destination_allowlist: pda::destination_allowlist_address(&self.owner).0,
+            token_program_2022: anchor_spl::token_2022::ID,
+            memo_program: SPL_MEMO_PROGRAM_ID,
+            input_token_program,
+            owner_input_account: associated_token_account(
+                &self.owner,
+                &input_mint,
+                &input_token_program,
+            ),
+            round_trip_counter: pda::round_trip_counter_address(&self.owner, &pool.address).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+
+        // Swap validators go first in the remaining accounts, then the tick
+        // arrays past the first
+        let remaining_accounts = self
+            .swap_validators
+            .iter()
+            .map(|validator| AccountMeta::new_readonly(*validator, false))
+            .chain(
+                extra_tick_arrays
+                    .iter()
+                    .map(|tick_array| AccountMeta::new(*tick_array, false)),
+            )
+            .collect();
+        build(accounts, instruction::FillDutchOrder {}, remaining_accounts)
+    }
+}
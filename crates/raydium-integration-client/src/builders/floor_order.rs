@@ -0,0 +1,152 @@
+//! Filling floor orders.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{accounts, instruction, pda, FloorOrder, SPL_MEMO_PROGRAM_ID};
+
+use super::build;
+#[cfg(feature = "event-cpi")]
+use super::event_authority;
+use crate::helpers::{tick_array_address, tick_array_start_index};
+use crate::{ClientError, PoolKeys, Result};
+
+/// Sell `amount_in` of a floor order's escrow, as the keeper signing the
+/// transaction. Without [`Self::tick_arrays`], only the tick array at the
+/// current price is passed
+#[derive(Clone, Debug)]
+pub struct FillFloorOrderBuilder<'a> {
+    pool: &'a PoolKeys,
+    keeper: Pubkey,
+    floor_order: Pubkey,
+    owner: Pubkey,
+    destination: Pubkey,
+    zero_for_one: bool,
+    amount_in: u64,
+    tick_arrays: Vec<Pubkey>,
+    swap_validators: Vec<Pubkey>,
+}
+
+impl<'a> FillFloorOrderBuilder<'a> {
+    pub fn new(
+        pool: &'a PoolKeys,
+        keeper: &Pubkey,
+        floor_order: &Pubkey,
+        order: &FloorOrder,
+        amount_in: u64,
+    ) -> Result<Self> {
+        if order.pool != pool.address {
+            return Err(ClientError::OrderNotInPool {
+                order: *floor_order,
+                pool: pool.address,
+            });
+        }
+        Ok(Self {
+            pool,
+            keeper: *keeper,
+            floor_order: *floor_order,
+            owner: order.owner,
+            destination: order.destination,
+            zero_for_one: pool.zero_for_one(&order.input_mint)?,
+            amount_in,
+            tick_arrays: Vec::new(),
+            swap_validators: Vec::new(),
+        })
+    }
+
+    /*
+     * Tick arrays to walk, in swap order, as a quote's `tick_arrays` lists
+     * them
+     */
+    pub fn tick_arrays(mut self, tick_arrays: Vec<Pubkey>) -> Self {
+        self.tick_arrays = tick_arrays;
+        self
+    }
+
+    /*
+     * Programs registered to vet swaps, as `fetch_swap_validators` returns
+     * them
+     */
+    pub fn swap_validators(mut self, swap_validators: Vec<Pubkey>) -> Self {
+        self.swap_validators = swap_validators;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let (input_mint, output_mint, input_vault, output_vault, input_token_program) =
+            if self.zero_for_one {
+                (
+                    pool.token_mint_0,
+                    pool.token_mint_1,
+                    pool.token_vault_0,
+                    pool.token_vault_1,
+                    pool.token_program_0,
+                )
+            } else {
+                (
+                    pool.token_mint_1,
+                    pool.token_mint_0,
+                    pool.token_vault_1,
+                    pool.token_vault_0,
+                    pool.token_program_1,
+                )
+            };
+        let (tick_array, extra_tick_arrays) = match self.tick_arrays.split_first() {
+            Some((first, rest)) => (*first, rest),
+            None => {
+                let start_index = tick_array_start_index(pool.tick_current, pool.tick_spacing);
+                (tick_array_address(&pool.address, start_index).0, &[][..])
+            }
+        };
+
+        let accounts = accounts::FillFloorOrder {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            keeper: self.keeper,
+            floor_order: self.floor_order,
+            owner: self.owner,
+            escrow: pda::floor_escrow_address(&self.floor_order).0,
+            destination: self.destination,
+            amm_config: pool.amm_config,
+            pool_state: pool.address,
+            input_vault,
+            output_vault,
+            input_vault_mint: input_mint,
+            output_vault_mint: output_mint,
+            observation_state: pool.observation_key,
+            token_program: anchor_spl::token::ID,
+            tick_array,
+            tick_array_bitmap_extension: pool.tick_array_bitmap_extension,
+            swap_validators: pda::swap_validators_address().0,
+            global_config: pda::global_config_address().0,
+            mint_extension_opt_in: None,
+            destination_allowlist: pda::destination_allowlist_address(&self.owner).0,
+            token_program_2022: anchor_spl::token_2022::ID,
+            memo_program: SPL_MEMO_PROGRAM_ID,
+            input_token_program,
+            round_trip_counter: pda::round_trip_counter_address(&self.owner, &pool.address).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+
+        // Swap validators go first in the remaining accounts, then the tick
+        // arrays past the first
+        let remaining_accounts = self
+            .swap_validators
+            .iter()
+            .map(|validator| AccountMeta::new_readonly(*validator, false))
+            .chain(
+                extra_tick_arrays
+                    .iter()
+                    .map(|tick_array| AccountMeta::new(*tick_array, false)),
+            )
+            .collect();
+        build(
+            accounts,
+            instruction::FillFloorOrder {
+                amount_in: self.amount_in,
+            },
+            remaining_accounts,
+        )
+    }
+}
@@ -0,0 +1,61 @@
+//! Instruction builders.
+//!
+//! Each builder takes the few keys that identify an operation, plus the
+//! [`PoolKeys`](crate::PoolKeys) or [`PositionKeys`](crate::PositionKeys)
+//! fetched for it, and derives the rest: the proxy's PDAs, Raydium's tick
+//! arrays and positions, and the owner's associated token accounts. Optional
+//! accounts are left out unless a builder method asks for them.
+//!
+//! Instructions the builders don't cover can still be assembled from the
+//! program's generated `accounts` and `instruction` structs, re-exported as
+//! [`crate::program`].
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::InstructionData;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+mod commit_reveal;
+mod dutch_order;
+mod floor_order;
+mod position;
+mod session;
+mod swap;
+mod user_config;
+
+pub use commit_reveal::*;
+pub use dutch_order::*;
+pub use floor_order::*;
+pub use position::*;
+pub use session::*;
+pub use swap::*;
+pub use user_config::*;
+
+/*
+ * Proxy instruction from its accounts, arguments and any remaining accounts
+ */
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining_accounts);
+    Instruction {
+        program_id: raydium_integration::ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+/*
+ * `owner`'s associated token account for `mint`
+ */
+fn associated_token_account(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+/// Event authority the program emits events through with `event-cpi`
+#[cfg(feature = "event-cpi")]
+fn event_authority() -> Pubkey {
+    raydium_integration::pda::event_authority_address().0
+}
@@ -0,0 +1,765 @@
+//! Position opens, liquidity changes and closes.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::math::{full_range_ticks, ticks_around_price};
+use raydium_integration::{accounts, instruction, pda, SPL_MEMO_PROGRAM_ID};
+
+#[cfg(feature = "event-cpi")]
+use super::event_authority;
+use super::{associated_token_account, build};
+use crate::helpers::{
+    metadata_address, personal_position_address, position_tick_array_addresses,
+    protocol_position_address, tick_array_address, tick_array_start_indices,
+};
+use crate::{ClientError, PoolKeys, PositionKeys, Result};
+
+/// Tick bounds of a new position
+#[derive(Clone, Copy, Debug)]
+pub enum PositionRange {
+    Ticks {
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    },
+    /// The widest range the pool's tick spacing allows
+    FullRange,
+    /// `range_bps` below and above the price. The program derives the ticks
+    /// from the price at execution, so the open fails if the price moves the
+    /// bounds into other tick arrays than the ones derived here
+    AroundPrice { range_bps: u16 },
+    /// Human prices, token_1 per token_0 in whole tokens. The bounds widen
+    /// outward to the tick spacing
+    Prices { price_lower: f64, price_upper: f64 },
+}
+
+/// Open a position, paid from the payer's associated token accounts unless
+/// others are set. The position NFT mint is a fresh keypair that signs the
+/// transaction alongside the payer
+#[derive(Clone, Debug)]
+pub struct OpenPositionBuilder<'a> {
+    pool: &'a PoolKeys,
+    payer: Pubkey,
+    position_nft_mint: Pubkey,
+    position_nft_owner: Pubkey,
+    range: PositionRange,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    with_metadata: bool,
+    simulate_only: bool,
+    token_account_0: Option<Pubkey>,
+    token_account_1: Option<Pubkey>,
+    position_registry_page: Option<u32>,
+    with_cost_basis: bool,
+    with_stats: bool,
+    with_mint_extension_opt_in: bool,
+    with_open_approval: bool,
+    with_pool_exposure: bool,
+}
+
+impl<'a> OpenPositionBuilder<'a> {
+    /*
+     * Deposit up to the given amounts; Raydium takes the most liquidity they
+     * cover at the current price
+     */
+    pub fn new(
+        pool: &'a PoolKeys,
+        payer: &Pubkey,
+        position_nft_mint: &Pubkey,
+        range: PositionRange,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Self {
+        Self {
+            pool,
+            payer: *payer,
+            position_nft_mint: *position_nft_mint,
+            position_nft_owner: *payer,
+            range,
+            liquidity: 0,
+            amount_0_max,
+            amount_1_max,
+            with_metadata: false,
+            simulate_only: false,
+            token_account_0: None,
+            token_account_1: None,
+            position_registry_page: None,
+            with_cost_basis: false,
+            with_stats: false,
+            with_mint_extension_opt_in: false,
+            with_open_approval: false,
+            with_pool_exposure: false,
+        }
+    }
+
+    /*
+     * Mint exactly `liquidity`, spending at most the maximum amounts
+     */
+    pub fn liquidity(mut self, liquidity: u128) -> Self {
+        self.liquidity = liquidity;
+        self
+    }
+
+    /*
+     * Send the position NFT to `owner` instead of the payer. The owner must
+     * co-sign, or have approved the payer, see [`Self::with_open_approval`]
+     */
+    pub fn position_nft_owner(mut self, owner: &Pubkey) -> Self {
+        self.position_nft_owner = *owner;
+        self
+    }
+
+    /*
+     * Create Metaplex metadata for the position NFT
+     */
+    pub fn with_metadata(mut self) -> Self {
+        self.with_metadata = true;
+        self
+    }
+
+    /*
+     * Quote without moving funds; the result comes back as return data
+     */
+    pub fn simulate_only(mut self) -> Self {
+        self.simulate_only = true;
+        self
+    }
+
+    pub fn token_accounts(mut self, token_account_0: Pubkey, token_account_1: Pubkey) -> Self {
+        self.token_account_0 = Some(token_account_0);
+        self.token_account_1 = Some(token_account_1);
+        self
+    }
+
+    /*
+     * Record the position in the NFT owner's registry, on the given page,
+     * which must be its last
+     */
+    pub fn position_registry_page(mut self, page: u32) -> Self {
+        self.position_registry_page = Some(page);
+        self
+    }
+
+    /*
+     * Record the deposit as the position's cost basis
+     */
+    pub fn with_cost_basis(mut self) -> Self {
+        self.with_cost_basis = true;
+        self
+    }
+
+    /*
+     * Update the pool and global statistics, which must exist
+     */
+    pub fn with_stats(mut self) -> Self {
+        self.with_stats = true;
+        self
+    }
+
+    /*
+     * Pass the payer's mint extension opt-in, which must exist, for pools
+     * whose mints carry risky Token-2022 extensions
+     */
+    pub fn with_mint_extension_opt_in(mut self) -> Self {
+        self.with_mint_extension_opt_in = true;
+        self
+    }
+
+    /*
+     * Pass the NFT owner's approval of the payer, which must exist, so the
+     * owner need not co-sign
+     */
+    pub fn with_open_approval(mut self) -> Self {
+        self.with_open_approval = true;
+        self
+    }
+
+    /*
+     * Count the deposit against the payer's exposure to the pool, required
+     * while their config caps it per pool
+     */
+    pub fn with_pool_exposure(mut self) -> Self {
+        self.with_pool_exposure = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Result<Instruction> {
+        let pool = self.pool;
+        let (tick_lower_index, tick_upper_index) = match self.range {
+            PositionRange::Ticks {
+                tick_lower_index,
+                tick_upper_index,
+            } => (tick_lower_index, tick_upper_index),
+            PositionRange::FullRange => full_range_ticks(pool.tick_spacing),
+            PositionRange::AroundPrice { range_bps } => {
+                ticks_around_price(pool.sqrt_price_x64, range_bps, pool.tick_spacing)?
+            }
+            PositionRange::Prices {
+                price_lower,
+                price_upper,
+            } => pool.price_range_ticks(price_lower, price_upper)?,
+        };
+        let (tick_array_lower_start_index, tick_array_upper_start_index) =
+            tick_array_start_indices(tick_lower_index, tick_upper_index, pool.tick_spacing);
+        let nft_mint = self.position_nft_mint;
+        let owner = self.position_nft_owner;
+
+        let accounts = accounts::ProxyOpenPosition {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            payer: self.payer,
+            user_cfg: pda::user_config_address(&self.payer).0,
+            position_nft_owner: owner,
+            position_nft_mint: nft_mint,
+            position_nft_account: associated_token_account(
+                &owner,
+                &nft_mint,
+                &anchor_spl::token::ID,
+            ),
+            metadata_account: self.with_metadata.then(|| metadata_address(&nft_mint).0),
+            pool_state: pool.address,
+            protocol_position: protocol_position_address(
+                &pool.address,
+                tick_lower_index,
+                tick_upper_index,
+            )
+            .0,
+            tick_array_lower: tick_array_address(&pool.address, tick_array_lower_start_index).0,
+            tick_array_upper: tick_array_address(&pool.address, tick_array_upper_start_index).0,
+            personal_position: personal_position_address(&nft_mint).0,
+            token_account_0: self.token_account_0.unwrap_or_else(|| {
+                associated_token_account(&self.payer, &pool.token_mint_0, &pool.token_program_0)
+            }),
+            token_account_1: self.token_account_1.unwrap_or_else(|| {
+                associated_token_account(&self.payer, &pool.token_mint_1, &pool.token_program_1)
+            }),
+            token_vault_0: pool.token_vault_0,
+            token_vault_1: pool.token_vault_1,
+            programs: accounts::PositionPrograms {
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+                system_program: anchor_lang::system_program::ID,
+                token_program: anchor_spl::token::ID,
+                associated_token_program: anchor_spl::associated_token::ID,
+                metadata_program: anchor_spl::metadata::ID,
+                token_program_2022: anchor_spl::token_2022::ID,
+            },
+            vault_0_mint: pool.token_mint_0,
+            vault_1_mint: pool.token_mint_1,
+            pool_stats: self
+                .with_stats
+                .then(|| pda::pool_stats_address(&pool.address).0),
+            global_stats: self.with_stats.then(|| pda::global_stats_address().0),
+            position_registry: self
+                .position_registry_page
+                .map(|_| pda::position_registry_address(&owner).0),
+            position_registry_page: self
+                .position_registry_page
+                .map(|page| pda::position_registry_page_address(&owner, page).0),
+            cost_basis: self
+                .with_cost_basis
+                .then(|| pda::cost_basis_address(&nft_mint).0),
+            tick_array_bitmap_extension: pool.tick_array_bitmap_extension,
+            mint_extension_opt_in: self
+                .with_mint_extension_opt_in
+                .then(|| pda::extension_opt_in_address(&self.payer).0),
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            open_approval: self
+                .with_open_approval
+                .then(|| pda::open_approval_address(&owner, &self.payer).0),
+            pool_exposure: self
+                .with_pool_exposure
+                .then(|| pda::pool_exposure_address(&self.payer, &pool.address).0),
+            global_config: pda::global_config_address().0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+
+        let instruction = match self.range {
+            PositionRange::Ticks { .. } | PositionRange::Prices { .. } => build(
+                accounts,
+                instruction::ProxyOpenPosition {
+                    tick_lower_index,
+                    tick_upper_index,
+                    tick_array_lower_start_index,
+                    tick_array_upper_start_index,
+                    liquidity: self.liquidity,
+                    amount_0_max: self.amount_0_max,
+                    amount_1_max: self.amount_1_max,
+                    with_matedata: self.with_metadata,
+                    simulate_only: self.simulate_only,
+                },
+                Vec::new(),
+            ),
+            PositionRange::FullRange => build(
+                accounts,
+                instruction::ProxyOpenFullRangePosition {
+                    liquidity: self.liquidity,
+                    amount_0_max: self.amount_0_max,
+                    amount_1_max: self.amount_1_max,
+                    with_metadata: self.with_metadata,
+                    simulate_only: self.simulate_only,
+                },
+                Vec::new(),
+            ),
+            PositionRange::AroundPrice { range_bps } => build(
+                accounts,
+                instruction::ProxyOpenPositionAroundPrice {
+                    range_bps,
+                    liquidity: self.liquidity,
+                    amount_0_max: self.amount_0_max,
+                    amount_1_max: self.amount_1_max,
+                    with_metadata: self.with_metadata,
+                    simulate_only: self.simulate_only,
+                },
+                Vec::new(),
+            ),
+        };
+        Ok(instruction)
+    }
+}
+
+/// Add liquidity to a position, paid from the owner's associated token
+/// accounts unless others are set
+#[derive(Clone, Debug)]
+pub struct IncreaseLiquidityBuilder<'a> {
+    pool: &'a PoolKeys,
+    position: &'a PositionKeys,
+    nft_owner: Pubkey,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    simulate_only: bool,
+    token_account_0: Option<Pubkey>,
+    token_account_1: Option<Pubkey>,
+    with_cost_basis: bool,
+    with_mint_extension_opt_in: bool,
+    with_pool_exposure: bool,
+}
+
+impl<'a> IncreaseLiquidityBuilder<'a> {
+    /*
+     * Deposit up to the given amounts; Raydium takes the most liquidity they
+     * cover at the current price
+     */
+    pub fn new(
+        pool: &'a PoolKeys,
+        position: &'a PositionKeys,
+        nft_owner: &Pubkey,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Result<Self> {
+        check_position_pool(pool, position)?;
+        Ok(Self {
+            pool,
+            position,
+            nft_owner: *nft_owner,
+            liquidity: 0,
+            amount_0_max,
+            amount_1_max,
+            simulate_only: false,
+            token_account_0: None,
+            token_account_1: None,
+            with_cost_basis: false,
+            with_mint_extension_opt_in: false,
+            with_pool_exposure: false,
+        })
+    }
+
+    /*
+     * Add exactly `liquidity`, spending at most the maximum amounts
+     */
+    pub fn liquidity(mut self, liquidity: u128) -> Self {
+        self.liquidity = liquidity;
+        self
+    }
+
+    /*
+     * Quote without moving funds; the result comes back as return data
+     */
+    pub fn simulate_only(mut self) -> Self {
+        self.simulate_only = true;
+        self
+    }
+
+    pub fn token_accounts(mut self, token_account_0: Pubkey, token_account_1: Pubkey) -> Self {
+        self.token_account_0 = Some(token_account_0);
+        self.token_account_1 = Some(token_account_1);
+        self
+    }
+
+    /*
+     * Add the deposit to the position's cost basis, which must exist
+     */
+    pub fn with_cost_basis(mut self) -> Self {
+        self.with_cost_basis = true;
+        self
+    }
+
+    /*
+     * Pass the owner's mint extension opt-in, which must exist, for pools
+     * whose mints carry risky Token-2022 extensions
+     */
+    pub fn with_mint_extension_opt_in(mut self) -> Self {
+        self.with_mint_extension_opt_in = true;
+        self
+    }
+
+    /*
+     * Count the deposit against the owner's exposure to the pool, which must
+     * be tracked; required while their config caps it per pool
+     */
+    pub fn with_pool_exposure(mut self) -> Self {
+        self.with_pool_exposure = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (pool, position, owner) = (self.pool, self.position, self.nft_owner);
+        let ticks = PositionTicks::new(pool, position);
+        let accounts = accounts::ProxyIncreaseLiquidity {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            nft_owner: owner,
+            nft_account: nft_account(position, &owner),
+            pool_state: pool.address,
+            protocol_position: ticks.protocol_position,
+            personal_position: position.personal_position,
+            tick_array_lower: ticks.tick_array_lower,
+            tick_array_upper: ticks.tick_array_upper,
+            token_account_0: self.token_account_0.unwrap_or_else(|| {
+                associated_token_account(&owner, &pool.token_mint_0, &pool.token_program_0)
+            }),
+            token_account_1: self.token_account_1.unwrap_or_else(|| {
+                associated_token_account(&owner, &pool.token_mint_1, &pool.token_program_1)
+            }),
+            token_vault_0: pool.token_vault_0,
+            token_vault_1: pool.token_vault_1,
+            token_program: anchor_spl::token::ID,
+            token_program_2022: anchor_spl::token_2022::ID,
+            vault_0_mint: pool.token_mint_0,
+            vault_1_mint: pool.token_mint_1,
+            cost_basis: self
+                .with_cost_basis
+                .then(|| pda::cost_basis_address(&position.nft_mint).0),
+            mint_extension_opt_in: self
+                .with_mint_extension_opt_in
+                .then(|| pda::extension_opt_in_address(&owner).0),
+            points_config: None,
+            points_ledger: None,
+            position_points: None,
+            callback_program: None,
+            user_cfg: pda::user_config_address(&owner).0,
+            pool_exposure: self
+                .with_pool_exposure
+                .then(|| pda::pool_exposure_address(&owner, &pool.address).0),
+            global_config: pda::global_config_address().0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+        build(
+            accounts,
+            instruction::ProxyIncreaseLiquidity {
+                liquidity: self.liquidity,
+                amount_0_max: self.amount_0_max,
+                amount_1_max: self.amount_1_max,
+                simulate_only: self.simulate_only,
+            },
+            Vec::new(),
+        )
+    }
+}
+
+/// Remove liquidity from a position, paid out to the owner's associated
+/// token accounts unless others are set
+#[derive(Clone, Debug)]
+pub struct DecreaseLiquidityBuilder<'a> {
+    pool: &'a PoolKeys,
+    position: &'a PositionKeys,
+    nft_owner: Pubkey,
+    liquidity: u128,
+    amount_0_min: u64,
+    amount_1_min: u64,
+    simulate_only: bool,
+    recipient_token_account_0: Option<Pubkey>,
+    recipient_token_account_1: Option<Pubkey>,
+    with_cost_basis: bool,
+    with_pool_exposure: bool,
+}
+
+impl<'a> DecreaseLiquidityBuilder<'a> {
+    /*
+     * Withdraw `liquidity`, zero to only collect fees. The program floors the
+     * minimums at the principal less the slippage tolerance
+     */
+    pub fn new(
+        pool: &'a PoolKeys,
+        position: &'a PositionKeys,
+        nft_owner: &Pubkey,
+        liquidity: u128,
+    ) -> Result<Self> {
+        check_position_pool(pool, position)?;
+        Ok(Self {
+            pool,
+            position,
+            nft_owner: *nft_owner,
+            liquidity,
+            amount_0_min: 0,
+            amount_1_min: 0,
+            simulate_only: false,
+            recipient_token_account_0: None,
+            recipient_token_account_1: None,
+            with_cost_basis: false,
+            with_pool_exposure: false,
+        })
+    }
+
+    pub fn amount_mins(mut self, amount_0_min: u64, amount_1_min: u64) -> Self {
+        self.amount_0_min = amount_0_min;
+        self.amount_1_min = amount_1_min;
+        self
+    }
+
+    /*
+     * Quote without moving funds; the result comes back as return data
+     */
+    pub fn simulate_only(mut self) -> Self {
+        self.simulate_only = true;
+        self
+    }
+
+    pub fn recipient_token_accounts(
+        mut self,
+        recipient_token_account_0: Pubkey,
+        recipient_token_account_1: Pubkey,
+    ) -> Self {
+        self.recipient_token_account_0 = Some(recipient_token_account_0);
+        self.recipient_token_account_1 = Some(recipient_token_account_1);
+        self
+    }
+
+    /*
+     * Take the withdrawal out of the position's cost basis, which must exist
+     */
+    pub fn with_cost_basis(mut self) -> Self {
+        self.with_cost_basis = true;
+        self
+    }
+
+    /*
+     * Take the withdrawal out of the owner's exposure to the pool, which
+     * must be tracked
+     */
+    pub fn with_pool_exposure(mut self) -> Self {
+        self.with_pool_exposure = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (pool, position, owner) = (self.pool, self.position, self.nft_owner);
+        let ticks = PositionTicks::new(pool, position);
+        let accounts = accounts::ProxyDecreaseLiquidity {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            nft_owner: owner,
+            nft_account: nft_account(position, &owner),
+            personal_position: position.personal_position,
+            pool_state: pool.address,
+            protocol_position: ticks.protocol_position,
+            token_vault_0: pool.token_vault_0,
+            token_vault_1: pool.token_vault_1,
+            tick_array_lower: ticks.tick_array_lower,
+            tick_array_upper: ticks.tick_array_upper,
+            recipient_token_account_0: self.recipient_token_account_0.unwrap_or_else(|| {
+                associated_token_account(&owner, &pool.token_mint_0, &pool.token_program_0)
+            }),
+            recipient_token_account_1: self.recipient_token_account_1.unwrap_or_else(|| {
+                associated_token_account(&owner, &pool.token_mint_1, &pool.token_program_1)
+            }),
+            token_program: anchor_spl::token::ID,
+            token_program_2022: anchor_spl::token_2022::ID,
+            memo_program: SPL_MEMO_PROGRAM_ID,
+            vault_0_mint: pool.token_mint_0,
+            vault_1_mint: pool.token_mint_1,
+            cost_basis: self
+                .with_cost_basis
+                .then(|| pda::cost_basis_address(&position.nft_mint).0),
+            user_cfg: pda::user_config_address(&owner).0,
+            points_config: None,
+            points_ledger: None,
+            position_points: None,
+            destination_allowlist: pda::destination_allowlist_address(&owner).0,
+            callback_program: None,
+            pool_exposure: self
+                .with_pool_exposure
+                .then(|| pda::pool_exposure_address(&owner, &pool.address).0),
+            global_config: pda::global_config_address().0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+        build(
+            accounts,
+            instruction::ProxyDecreaseLiquidity {
+                liquidity: self.liquidity,
+                amount_0_min: self.amount_0_min,
+                amount_1_min: self.amount_1_min,
+                simulate_only: self.simulate_only,
+            },
+            Vec::new(),
+        )
+    }
+}
+
+/// Close an emptied position, burning its NFT
+#[derive(Clone, Debug)]
+pub struct ClosePositionBuilder<'a> {
+    position: &'a PositionKeys,
+    nft_owner: Pubkey,
+    position_registry_page: Option<u32>,
+    with_cost_basis: bool,
+}
+
+impl<'a> ClosePositionBuilder<'a> {
+    pub fn new(position: &'a PositionKeys, nft_owner: &Pubkey) -> Self {
+        Self {
+            position,
+            nft_owner: *nft_owner,
+            position_registry_page: None,
+            with_cost_basis: false,
+        }
+    }
+
+    /*
+     * Remove the position from the owner's registry page it was recorded on
+     */
+    pub fn position_registry_page(mut self, page: u32) -> Self {
+        self.position_registry_page = Some(page);
+        self
+    }
+
+    /*
+     * Close the position's cost basis too, which must exist
+     */
+    pub fn with_cost_basis(mut self) -> Self {
+        self.with_cost_basis = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (position, owner) = (self.position, self.nft_owner);
+        let accounts = accounts::ProxyClosePosition {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            nft_owner: owner,
+            position_nft_mint: position.nft_mint,
+            position_nft_account: nft_account(position, &owner),
+            personal_position: position.personal_position,
+            system_program: anchor_lang::system_program::ID,
+            token_program: position.nft_token_program,
+            position_registry: self
+                .position_registry_page
+                .map(|_| pda::position_registry_address(&owner).0),
+            position_registry_page: self
+                .position_registry_page
+                .map(|page| pda::position_registry_page_address(&owner, page).0),
+            cost_basis: self
+                .with_cost_basis
+                .then(|| pda::cost_basis_address(&position.nft_mint).0),
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(&owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+        build(accounts, instruction::ProxyClosePosition {}, Vec::new())
+    }
+}
+
+/// Raydium accounts keyed by a position's tick bounds
+struct PositionTicks {
+    protocol_position: Pubkey,
+    tick_array_lower: Pubkey,
+    tick_array_upper: Pubkey,
+}
+
+impl PositionTicks {
+    fn new(pool: &PoolKeys, position: &PositionKeys) -> Self {
+        let (tick_array_lower, tick_array_upper) = position_tick_array_addresses(
+            &pool.address,
+            position.tick_lower_index,
+            position.tick_upper_index,
+            pool.tick_spacing,
+        );
+        Self {
+            protocol_position: protocol_position_address(
+                &pool.address,
+                position.tick_lower_index,
+                position.tick_upper_index,
+            )
+            .0,
+            tick_array_lower,
+            tick_array_upper,
+        }
+    }
+}
+
+/*
+ * Let `delegate` open positions whose NFT goes to `owner` without the owner
+ * co-signing
+ */
+pub fn approve_open_on_behalf(owner: &Pubkey, payer: &Pubkey, delegate: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveOpenOnBehalf {
+            owner: *owner,
+            payer: *payer,
+            delegate: *delegate,
+            open_approval: pda::open_approval_address(owner, delegate).0,
+            system_program: anchor_lang::system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::ApproveOpenOnBehalf {},
+        Vec::new(),
+    )
+}
+
+/*
+ * Withdraw the approval, refunding its rent to the owner
+ */
+pub fn revoke_open_on_behalf(owner: &Pubkey, delegate: &Pubkey) -> Instruction {
+    build(
+        accounts::RevokeOpenOnBehalf {
+            owner: *owner,
+            open_approval: pda::open_approval_address(owner, delegate).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::RevokeOpenOnBehalf {},
+        Vec::new(),
+    )
+}
+
+/*
+ * The owner's associated token account holding the position NFT
+ */
+fn nft_account(position: &PositionKeys, owner: &Pubkey) -> Pubkey {
+    associated_token_account(owner, &position.nft_mint, &position.nft_token_program)
+}
+
+fn check_position_pool(pool: &PoolKeys, position: &PositionKeys) -> Result<()> {
+    if position.pool != pool.address {
+        return Err(ClientError::PositionNotInPool {
+            position: position.nft_mint,
+            pool: pool.address,
+        });
+    }
+    Ok(())
+}
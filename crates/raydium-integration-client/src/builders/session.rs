@@ -0,0 +1,36 @@
+//! SPL Token approvals that fund session swaps. `owner` signs; the session
+//! key never holds token authority.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token::spl_token;
+use raydium_integration::pda;
+
+/*
+ * Approve the owner's session authority PDA to spend up to `amount` of
+ * `input_token_account` through session swaps. Replaces any earlier
+ * approval on the account
+ */
+pub fn approve_session_delegate(
+    owner: &Pubkey,
+    input_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    spl_token::instruction::approve(
+        &spl_token::ID,
+        input_token_account,
+        &pda::session_authority_address(owner).0,
+        owner,
+        &[],
+        amount,
+    )
+    .expect("the SPL Token program id is valid")
+}
+
+/*
+ * Withdraw the approval, stopping every session of the owner from spending
+ * `input_token_account`
+ */
+pub fn revoke_session_delegate(owner: &Pubkey, input_token_account: &Pubkey) -> Instruction {
+    spl_token::instruction::revoke(&spl_token::ID, input_token_account, owner, &[])
+        .expect("the SPL Token program id is valid")
+}
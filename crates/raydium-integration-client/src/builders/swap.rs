@@ -0,0 +1,290 @@
+//! `proxy_swap`.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{accounts, instruction, pda, GlobalConfig};
+
+#[cfg(feature = "event-cpi")]
+use super::event_authority;
+use super::{associated_token_account, build};
+use crate::helpers::{tick_array_address, tick_array_start_index};
+use crate::quote::SwapAccounts;
+use crate::{PoolKeys, Result};
+
+/// Swap through a pool. The payer signs and trades from and to their
+/// associated token accounts unless others are set. Without
+/// [`Self::tick_arrays`], only the tick array at the current price is passed,
+/// enough for swaps that stay inside it
+#[derive(Clone, Debug)]
+pub struct ProxySwapBuilder<'a> {
+    pool: &'a PoolKeys,
+    payer: Pubkey,
+    zero_for_one: bool,
+    amount: u64,
+    expected_other_amount: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    max_tick_crossings: Option<u16>,
+    partial_fill: bool,
+    simulate_only: bool,
+    input_token_account: Option<Pubkey>,
+    output_token_account: Option<Pubkey>,
+    tick_arrays: Vec<Pubkey>,
+    tick_array_bitmap_extension: Option<Pubkey>,
+    swap_validators: Vec<Pubkey>,
+    with_stats: bool,
+    treasury: Option<Pubkey>,
+}
+
+impl<'a> ProxySwapBuilder<'a> {
+    /*
+     * Sell exactly `amount` of `input_mint`, expecting `expected_amount_out`
+     * back before the payer's slippage tolerance
+     */
+    pub fn exact_in(
+        pool: &'a PoolKeys,
+        payer: &Pubkey,
+        input_mint: &Pubkey,
+        amount: u64,
+        expected_amount_out: u64,
+    ) -> Result<Self> {
+        Self::new(pool, payer, input_mint, amount, expected_amount_out, true)
+    }
+
+    /*
+     * Buy exactly `amount` of the other token paying `input_mint`, expecting
+     * to pay `expected_amount_in` before the payer's slippage tolerance
+     */
+    pub fn exact_out(
+        pool: &'a PoolKeys,
+        payer: &Pubkey,
+        input_mint: &Pubkey,
+        amount: u64,
+        expected_amount_in: u64,
+    ) -> Result<Self> {
+        Self::new(pool, payer, input_mint, amount, expected_amount_in, false)
+    }
+
+    fn new(
+        pool: &'a PoolKeys,
+        payer: &Pubkey,
+        input_mint: &Pubkey,
+        amount: u64,
+        expected_other_amount: u64,
+        is_base_input: bool,
+    ) -> Result<Self> {
+        Ok(Self {
+            pool,
+            payer: *payer,
+            zero_for_one: pool.zero_for_one(input_mint)?,
+            amount,
+            expected_other_amount,
+            sqrt_price_limit_x64: 0,
+            is_base_input,
+            max_tick_crossings: None,
+            partial_fill: false,
+            simulate_only: false,
+            input_token_account: None,
+            output_token_account: None,
+            tick_arrays: Vec::new(),
+            tick_array_bitmap_extension: pool.tick_array_bitmap_extension,
+            swap_validators: Vec::new(),
+            with_stats: false,
+            treasury: None,
+        })
+    }
+
+    /*
+     * Price the swap stops at; zero for none
+     */
+    pub fn sqrt_price_limit_x64(mut self, sqrt_price_limit_x64: u128) -> Self {
+        self.sqrt_price_limit_x64 = sqrt_price_limit_x64;
+        self
+    }
+
+    /*
+     * Fail if the swap would cross more initialized ticks
+     */
+    pub fn max_tick_crossings(mut self, max_tick_crossings: u16) -> Self {
+        self.max_tick_crossings = Some(max_tick_crossings);
+        self
+    }
+
+    /*
+     * Stop at the price limit instead of failing, checking slippage pro rata
+     * on the filled part
+     */
+    pub fn partial_fill(mut self) -> Self {
+        self.partial_fill = true;
+        self
+    }
+
+    /*
+     * Quote without moving funds; the result comes back as return data
+     */
+    pub fn simulate_only(mut self) -> Self {
+        self.simulate_only = true;
+        self
+    }
+
+    pub fn input_token_account(mut self, input_token_account: Pubkey) -> Self {
+        self.input_token_account = Some(input_token_account);
+        self
+    }
+
+    pub fn output_token_account(mut self, output_token_account: Pubkey) -> Self {
+        self.output_token_account = Some(output_token_account);
+        self
+    }
+
+    /*
+     * Tick arrays to walk, in swap order, as `PoolKeys::fetch_swap_tick_arrays`
+     * returns them
+     */
+    pub fn tick_arrays(mut self, tick_arrays: Vec<Pubkey>) -> Self {
+        self.tick_arrays = tick_arrays;
+        self
+    }
+
+    /*
+     * Tick arrays and bitmap extension as `discover_swap_accounts` finds
+     * them, passing the extension only when the swap needs it
+     */
+    pub fn swap_accounts(mut self, swap_accounts: SwapAccounts) -> Self {
+        self.tick_arrays = swap_accounts.tick_arrays;
+        self.tick_array_bitmap_extension = swap_accounts.tick_array_bitmap_extension;
+        self
+    }
+
+    /*
+     * Programs registered to vet swaps, as `fetch_swap_validators` returns
+     * them
+     */
+    pub fn swap_validators(mut self, swap_validators: Vec<Pubkey>) -> Self {
+        self.swap_validators = swap_validators;
+        self
+    }
+
+    /*
+     * Pay the protocol fee `global_config` charges, skimmed from the input
+     * into the treasury's associated token account and the input mint's
+     * insurance vault. Needed whenever the fee is nonzero
+     */
+    pub fn protocol_fee(mut self, global_config: &GlobalConfig) -> Self {
+        self.treasury = (global_config.protocol_fee_bps > 0).then_some(global_config.treasury);
+        self
+    }
+
+    /*
+     * Update the pool and global statistics, which must exist
+     */
+    pub fn with_stats(mut self) -> Self {
+        self.with_stats = true;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let pool = self.pool;
+        let (input_mint, output_mint, input_vault, output_vault) = if self.zero_for_one {
+            (
+                pool.token_mint_0,
+                pool.token_mint_1,
+                pool.token_vault_0,
+                pool.token_vault_1,
+            )
+        } else {
+            (
+                pool.token_mint_1,
+                pool.token_mint_0,
+                pool.token_vault_1,
+                pool.token_vault_0,
+            )
+        };
+        let token_program = anchor_spl::token::ID;
+        let (tick_array, extra_tick_arrays) = match self.tick_arrays.split_first() {
+            Some((first, rest)) => (*first, rest),
+            None => {
+                let start_index = tick_array_start_index(pool.tick_current, pool.tick_spacing);
+                (tick_array_address(&pool.address, start_index).0, &[][..])
+            }
+        };
+
+        let accounts = accounts::ProxySwap {
+            clmm_program: raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+            payer: self.payer,
+            user_cfg: pda::user_config_address(&self.payer).0,
+            amm_config: pool.amm_config,
+            pool_state: pool.address,
+            input_token_account: self.input_token_account.unwrap_or_else(|| {
+                associated_token_account(&self.payer, &input_mint, &token_program)
+            }),
+            output_token_account: self.output_token_account.unwrap_or_else(|| {
+                associated_token_account(&self.payer, &output_mint, &token_program)
+            }),
+            input_vault,
+            output_vault,
+            input_vault_mint: input_mint,
+            output_vault_mint: output_mint,
+            observation_state: pool.observation_key,
+            token_program,
+            tick_array,
+            pool_stats: self
+                .with_stats
+                .then(|| pda::pool_stats_address(&pool.address).0),
+            global_stats: self.with_stats.then(|| pda::global_stats_address().0),
+            tick_array_bitmap_extension: self.tick_array_bitmap_extension,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            global_config: pda::global_config_address().0,
+            fee_mint: self.treasury.map(|_| input_mint),
+            treasury_token_account: self
+                .treasury
+                .map(|treasury| associated_token_account(&treasury, &input_mint, &token_program)),
+            insurance_vault: self
+                .treasury
+                .map(|_| pda::insurance_vault_address(&input_mint).0),
+            fee_token_program: None,
+            points_config: None,
+            points_ledger: None,
+            round_trip_counter: pda::round_trip_counter_address(&self.payer, &pool.address).0,
+            fee_oracle: None,
+            fee_oracle_pool: None,
+            fee_oracle_observation: None,
+            fee_payment_account: None,
+            pool_creator_record: None,
+            creator_fee_vault: None,
+            swap_validators: pda::swap_validators_address().0,
+            destination_allowlist: pda::destination_allowlist_address(&self.payer).0,
+            mint_extension_opt_in: None,
+            callback_program: None,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        };
+
+        // Swap validators go first in the remaining accounts, then the tick
+        // arrays past the first
+        let remaining_accounts = self
+            .swap_validators
+            .iter()
+            .map(|validator| AccountMeta::new_readonly(*validator, false))
+            .chain(
+                extra_tick_arrays
+                    .iter()
+                    .map(|tick_array| AccountMeta::new(*tick_array, false)),
+            )
+            .collect();
+        build(
+            accounts,
+            instruction::ProxySwap {
+                amount: self.amount,
+                expected_other_amount: self.expected_other_amount,
+                sqrt_price_limit_x64: self.sqrt_price_limit_x64,
+                is_base_input: self.is_base_input,
+                max_tick_crossings: self.max_tick_crossings,
+                partial_fill: self.partial_fill,
+                simulate_only: self.simulate_only,
+            },
+            remaining_accounts,
+        )
+    }
+}
@@ -0,0 +1,125 @@
+//! User settings. `owner` signs; `payer` signs and funds the account the
+//! first time it is written.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{accounts, instruction, pda};
+
+use super::build;
+#[cfg(feature = "event-cpi")]
+use super::event_authority;
+
+/*
+ * Set the owner's slippage tolerance, creating their user config if needed
+ */
+pub fn set_slippage(owner: &Pubkey, payer: &Pubkey, bps: u16) -> Instruction {
+    build(
+        set_slippage_accounts(owner, payer),
+        instruction::SetSlippage { bps },
+        Vec::new(),
+    )
+}
+
+/*
+ * Restrict the owner's swaps and opens to transactions led by `programs`;
+ * an empty list lifts the restriction
+ */
+pub fn set_allowed_callers(owner: &Pubkey, payer: &Pubkey, programs: Vec<Pubkey>) -> Instruction {
+    build(
+        set_slippage_accounts(owner, payer),
+        instruction::SetAllowedCallers { programs },
+        Vec::new(),
+    )
+}
+
+/*
+ * Accept pool mints carrying the given `token_extensions` flags
+ */
+pub fn set_mint_extension_opt_in(
+    owner: &Pubkey,
+    payer: &Pubkey,
+    allowed_extensions: u8,
+) -> Instruction {
+    build(
+        accounts::SetMintExtensionOptIn {
+            owner: *owner,
+            payer: *payer,
+            mint_extension_opt_in: pda::extension_opt_in_address(owner).0,
+            system_program: anchor_lang::system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: event_authority(),
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::SetMintExtensionOptIn { allowed_extensions },
+        Vec::new(),
+    )
+}
+
+/*
+ * Cap the owner's exposure per pool and in total, in `quote_mint` units;
+ * zero lifts a cap
+ */
+pub fn set_exposure_caps(
+    owner: &Pubkey,
+    payer: &Pubkey,
+    quote_mint: &Pubkey,
+    max_pool_exposure: u64,
+    max_total_exposure: u64,
+) -> Instruction {
+    build(
+        set_slippage_accounts(owner, payer),
+        instruction::SetExposureCaps {
+            quote_mint: *quote_mint,
+            max_pool_exposure,
+            max_total_exposure,
+        },
+        Vec::new(),
+    )
+}
+
+/*
+ * Start tracking the owner's exposure to `pool`, for increases on positions
+ * opened before a per-pool cap
+ */
+pub fn init_pool_exposure(owner: &Pubkey, payer: &Pubkey, pool: &Pubkey) -> Instruction {
+    build(
+        accounts::InitPoolExposure {
+            owner: *owner,
+            payer: *payer,
+            pool_state: *pool,
+            pool_exposure: pda::pool_exposure_address(owner, pool).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::InitPoolExposure {},
+        Vec::new(),
+    )
+}
+
+/*
+ * Grow the owner's config from an earlier layout to the current one
+ */
+pub fn migrate_user_config(owner: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateUserConfig {
+            owner: *owner,
+            payer: *payer,
+            user_cfg: pda::user_config_address(owner).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::MigrateUserConfig {},
+        Vec::new(),
+    )
+}
+
+fn set_slippage_accounts(owner: &Pubkey, payer: &Pubkey) -> accounts::SetSlippage {
+    accounts::SetSlippage {
+        owner: *owner,
+        payer: *payer,
+        user_cfg: pda::user_config_address(owner).0,
+        system_program: anchor_lang::system_program::ID,
+        #[cfg(feature = "event-cpi")]
+        event_authority: event_authority(),
+        #[cfg(feature = "event-cpi")]
+        program: raydium_integration::ID,
+    }
+}
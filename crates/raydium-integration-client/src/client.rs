@@ -0,0 +1,507 @@
+//! One-call operations over `anchor-client`.
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use anchor_client::{Client, Cluster, Program};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{pda, DutchOrder, UserConfig};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_transaction_status::UiTransactionEncoding;
+use tokio::sync::Mutex;
+
+use crate::accounts::{
+    fetch_account, fetch_global_config, fetch_registered_positions, fetch_swap_validators,
+    fetch_zero_copy,
+};
+use crate::builders::{
+    cancel_dutch_order, set_slippage, ClosePositionBuilder, DcaLadder, DecreaseLiquidityBuilder,
+    IncreaseLiquidityBuilder, OpenPositionBuilder, PositionRange, ProxySwapBuilder,
+};
+use crate::compute_budget::{compute_budget_instructions, writable_accounts, ComputeBudget};
+use crate::events::{parse_transaction, ProgramEvent};
+use crate::helpers::associated_token_account;
+use crate::lookup_tables::{static_accounts, LookupTables};
+use crate::preflight::{simulate, SwapPreflight};
+use crate::quote::discover_swap_accounts;
+use crate::submit::{send_transaction, SendConfig};
+use crate::{ClientError, PoolKeys, PositionKeys, Result};
+
+/// Dutch order creations or cancellations sent in one transaction by
+/// [`RaydiumIntegrationClient::create_dca`] and
+/// [`RaydiumIntegrationClient::cancel_dca`]
+pub const DCA_ORDERS_PER_TRANSACTION: usize = 4;
+
+/// A confirmed transaction and the program events it emitted
+#[derive(Debug)]
+pub struct Executed {
+    pub signature: Signature,
+    pub events: Vec<ProgramEvent>,
+}
+
+/// Fetches what an operation needs, builds it, signs it with the payer and
+/// sends it as a v0 transaction, waiting for confirmation at the client's
+/// commitment. The static accounts of every pool it touches go into address
+/// lookup tables the payer owns, created on first use and reused after.
+/// Each transaction sets its compute unit limit and price by the client's
+/// [`ComputeBudget`]
+pub struct RaydiumIntegrationClient<C> {
+    program: Program<C>,
+    rpc: RpcClient,
+    signer: C,
+    payer: Pubkey,
+    /// None when transactions are sent without lookup tables
+    lookup_tables: Option<Mutex<LookupTables>>,
+    /// None when transactions leave the compute budget at the defaults
+    compute_budget: Option<ComputeBudget>,
+    send_config: SendConfig,
+}
+
+impl<C, S> RaydiumIntegrationClient<C>
+where
+    C: Deref<Target = S> + Clone + Send + Sync,
+    S: Signer,
+{
+    pub fn new(cluster: Cluster, payer: C, commitment: CommitmentConfig) -> Result<Self> {
+        let rpc = RpcClient::new_with_commitment(cluster.url().to_string(), commitment);
+        let payer_key = payer.pubkey();
+        let program = Client::new_with_options(cluster, payer.clone(), commitment)
+            .program(raydium_integration::ID)?;
+        Ok(Self {
+            program,
+            rpc,
+            signer: payer,
+            payer: payer_key,
+            lookup_tables: Some(Mutex::new(LookupTables::default())),
+            compute_budget: Some(ComputeBudget::default()),
+            send_config: SendConfig::default(),
+        })
+    }
+
+    /*
+     * Read and send through `rpc`, such as an `RpcPool`'s client, instead of
+     * the cluster's URL. Its commitment replaces the client's
+     */
+    pub fn with_rpc(mut self, rpc: RpcClient) -> Self {
+        self.rpc = rpc;
+        self
+    }
+
+    /*
+     * Start from existing lookup tables, such as `LookupTables::load` of the
+     * ones a previous run created, instead of creating new ones
+     */
+    pub fn with_lookup_tables(mut self, lookup_tables: LookupTables) -> Self {
+        self.lookup_tables = Some(Mutex::new(lookup_tables));
+        self
+    }
+
+    /*
+     * Send v0 transactions without lookup tables, and never create any
+     */
+    pub fn without_lookup_tables(mut self) -> Self {
+        self.lookup_tables = None;
+        self
+    }
+
+    /*
+     * Set compute budgets by `compute_budget` instead of the default policy
+     */
+    pub fn with_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = Some(compute_budget);
+        self
+    }
+
+    /*
+     * Change the compute budget from the next call on, such as to pay more
+     * for one urgent call
+     */
+    pub fn set_compute_budget(&mut self, compute_budget: Option<ComputeBudget>) {
+        self.compute_budget = compute_budget;
+    }
+
+    /*
+     * Send transactions without compute budget instructions, under the
+     * runtime's default limit and no priority fee
+     */
+    pub fn without_compute_budget(mut self) -> Self {
+        self.compute_budget = None;
+        self
+    }
+
+    /*
+     * Send with `send_config`'s retries and timeouts, or its durable nonce
+     */
+    pub fn with_send_config(mut self, send_config: SendConfig) -> Self {
+        self.send_config = send_config;
+        self
+    }
+
+    /*
+     * Change how transactions are sent from the next call on, such as to
+     * send one call as a Jito bundle
+     */
+    pub fn set_send_config(&mut self, send_config: SendConfig) {
+        self.send_config = send_config;
+    }
+
+    /*
+     * The lookup tables as they stand, to keep their addresses for the next
+     * run
+     */
+    pub async fn lookup_tables(&self) -> Option<LookupTables> {
+        match &self.lookup_tables {
+            Some(lookup_tables) => Some(lookup_tables.lock().await.clone()),
+            None => None,
+        }
+    }
+
+    /// The `anchor-client` program, for requests the client has no call for
+    pub fn program(&self) -> &Program<C> {
+        &self.program
+    }
+
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer
+    }
+
+    /*
+     * Sell exactly `amount_in` of `input_mint`, expecting the quoted output.
+     * The program checks the output against the payer's slippage tolerance,
+     * so when `slippage_bps` differs from it a `set_slippage` goes first in
+     * the same transaction and the new tolerance stays set. The swap is
+     * simulated first, and fails with `ClientError::PreflightSlippage`
+     * without being sent when the simulated output is already short of the
+     * tolerance
+     */
+    pub async fn swap(
+        &self,
+        pool: &Pubkey,
+        input_mint: &Pubkey,
+        amount_in: u64,
+        slippage_bps: u16,
+    ) -> Result<Executed> {
+        let pool = PoolKeys::fetch(&self.rpc, pool).await?;
+        let (quote, swap_accounts) =
+            discover_swap_accounts(&self.rpc, &pool, input_mint, amount_in, true, 0).await?;
+        let swap_validators = fetch_swap_validators(&self.rpc).await?;
+        let global_config = fetch_global_config(&self.rpc).await?;
+
+        let output_mint = if pool.zero_for_one(input_mint)? {
+            pool.token_mint_1
+        } else {
+            pool.token_mint_0
+        };
+        let preflight = SwapPreflight::new(
+            associated_token_account(&self.payer, &output_mint, &anchor_spl::token::ID),
+            quote.amount_out,
+            slippage_bps,
+        );
+
+        let mut instructions = Vec::new();
+        if self.slippage_bps().await? != Some(slippage_bps) {
+            instructions.push(set_slippage(&self.payer, &self.payer, slippage_bps));
+        }
+        instructions.push(
+            ProxySwapBuilder::exact_in(
+                &pool,
+                &self.payer,
+                input_mint,
+                amount_in,
+                quote.amount_out,
+            )?
+            .swap_accounts(swap_accounts)
+            .swap_validators(swap_validators)
+            .protocol_fee(&global_config)
+            .instruction(),
+        );
+        self.send(instructions, &[], Some(&pool), Some(&preflight))
+            .await
+    }
+
+    /*
+     * Open a position depositing up to the given amounts, with a fresh
+     * position NFT mint owned by the payer. Returns the mint with the
+     * transaction
+     */
+    pub async fn open_position(
+        &self,
+        pool: &Pubkey,
+        range: PositionRange,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Result<(Pubkey, Executed)> {
+        let pool = PoolKeys::fetch(&self.rpc, pool).await?;
+        let nft_mint = Keypair::new();
+        let instruction = OpenPositionBuilder::new(
+            &pool,
+            &self.payer,
+            &nft_mint.pubkey(),
+            range,
+            amount_0_max,
+            amount_1_max,
+        )
+        .instruction()?;
+        let executed = self
+            .send(vec![instruction], &[&nft_mint], Some(&pool), None)
+            .await?;
+        Ok((nft_mint.pubkey(), executed))
+    }
+
+    /*
+     * Deposit up to the given amounts into one of the payer's positions
+     */
+    pub async fn increase_liquidity(
+        &self,
+        nft_mint: &Pubkey,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Result<Executed> {
+        let position = PositionKeys::fetch(&self.rpc, nft_mint).await?;
+        let pool = PoolKeys::fetch(&self.rpc, &position.pool).await?;
+        let instruction = IncreaseLiquidityBuilder::new(
+            &pool,
+            &position,
+            &self.payer,
+            amount_0_max,
+            amount_1_max,
+        )?
+        .instruction();
+        self.send(vec![instruction], &[], Some(&pool), None).await
+    }
+
+    /*
+     * Withdraw `liquidity` from one of the payer's positions, all of it when
+     * None. Minimums follow the payer's slippage tolerance once they have a
+     * user config
+     */
+    pub async fn decrease_liquidity(
+        &self,
+        nft_mint: &Pubkey,
+        liquidity: Option<u128>,
+    ) -> Result<Executed> {
+        let position = PositionKeys::fetch(&self.rpc, nft_mint).await?;
+        let pool = PoolKeys::fetch(&self.rpc, &position.pool).await?;
+        let liquidity = liquidity.unwrap_or(position.liquidity);
+        let builder = DecreaseLiquidityBuilder::new(&pool, &position, &self.payer, liquidity)?;
+        self.send(vec![builder.instruction()], &[], Some(&pool), None)
+            .await
+    }
+
+    /*
+     * Close one of the payer's emptied positions, dropping it from their
+     * position registry if it is recorded there
+     */
+    pub async fn close_position(&self, nft_mint: &Pubkey) -> Result<Executed> {
+        let position = PositionKeys::fetch(&self.rpc, nft_mint).await?;
+        let registry_page = fetch_registered_positions(&self.rpc, &self.payer)
+            .await?
+            .into_iter()
+            .find_map(|(page, mint)| (mint == *nft_mint).then_some(page));
+        let mut builder = ClosePositionBuilder::new(&position, &self.payer);
+        if let Some(page) = registry_page {
+            builder = builder.position_registry_page(page);
+        }
+        self.send(vec![builder.instruction()], &[], None, None)
+            .await
+    }
+
+    /*
+     * The payer's positions recorded in their position registry
+     */
+    pub async fn positions(&self) -> Result<Vec<PositionKeys>> {
+        let mut positions = Vec::new();
+        for (_, nft_mint) in fetch_registered_positions(&self.rpc, &self.payer).await? {
+            positions.push(PositionKeys::fetch(&self.rpc, &nft_mint).await?);
+        }
+        Ok(positions)
+    }
+
+    /*
+     * Escrow a DCA ladder of Dutch orders selling `input_mint` through
+     * `pool`, `DCA_ORDERS_PER_TRANSACTION` orders to a transaction
+     */
+    pub async fn create_dca(
+        &self,
+        pool: &Pubkey,
+        input_mint: &Pubkey,
+        ladder: &DcaLadder,
+    ) -> Result<Vec<Executed>> {
+        let pool = PoolKeys::fetch(&self.rpc, pool).await?;
+        let instructions = ladder.instructions(&pool, &self.payer, input_mint)?;
+        let mut executed = Vec::new();
+        for chunk in instructions.chunks(DCA_ORDERS_PER_TRANSACTION) {
+            executed.push(self.send(chunk.to_vec(), &[], Some(&pool), None).await?);
+        }
+        Ok(executed)
+    }
+
+    /*
+     * Cancel the orders of a DCA ladder that are still open, refunding their
+     * escrows. Orders already filled or cancelled are skipped
+     */
+    pub async fn cancel_dca(&self, order_ids: std::ops::Range<u64>) -> Result<Vec<Executed>> {
+        let mut instructions = Vec::new();
+        let mut pools = HashMap::new();
+        for order_id in order_ids {
+            let address = pda::dutch_order_address(&self.payer, order_id).0;
+            let order: DutchOrder = match fetch_account(&self.rpc, &address).await {
+                Ok(order) => order,
+                Err(ClientError::AccountNotFound(_)) => continue,
+                Err(error) => return Err(error),
+            };
+            if !pools.contains_key(&order.pool) {
+                let pool = PoolKeys::fetch(&self.rpc, &order.pool).await?;
+                pools.insert(order.pool, pool);
+            }
+            let pool = &pools[&order.pool];
+            let input_token_program = if pool.zero_for_one(&order.input_mint)? {
+                pool.token_program_0
+            } else {
+                pool.token_program_1
+            };
+            instructions.push(cancel_dutch_order(
+                &self.payer,
+                order_id,
+                &order.input_mint,
+                &input_token_program,
+            ));
+        }
+        let mut executed = Vec::new();
+        for chunk in instructions.chunks(DCA_ORDERS_PER_TRANSACTION) {
+            executed.push(self.send(chunk.to_vec(), &[], None, None).await?);
+        }
+        Ok(executed)
+    }
+
+    pub async fn set_slippage(&self, slippage_bps: u16) -> Result<Executed> {
+        let instruction = set_slippage(&self.payer, &self.payer, slippage_bps);
+        self.send(vec![instruction], &[], None, None).await
+    }
+
+    /*
+     * The payer's effective slippage tolerance, None before their user
+     * config exists
+     */
+    async fn slippage_bps(&self) -> Result<Option<u16>> {
+        let address = raydium_integration::pda::user_config_address(&self.payer).0;
+        match fetch_zero_copy::<UserConfig>(&self.rpc, &address).await {
+            Ok(user_config) => Ok(Some(user_config.effective_slippage_bps())),
+            Err(ClientError::AccountNotFound(_)) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /*
+     * Compile a v0 transaction against the lookup tables, first extending
+     * them with `pool`'s static accounts. Simulate it when the compute
+     * budget or `preflight` needs it, prefix the compute budget
+     * instructions, then sign and send it with the payer and `signers` until
+     * it confirms, and read the events back from the confirmed transaction
+     */
+    async fn send(
+        &self,
+        mut instructions: Vec<Instruction>,
+        signers: &[&dyn Signer],
+        pool: Option<&PoolKeys>,
+        preflight: Option<&SwapPreflight>,
+    ) -> Result<Executed> {
+        let lookup_tables = match &self.lookup_tables {
+            Some(lookup_tables) => {
+                let mut lookup_tables = lookup_tables.lock().await;
+                if let Some(pool) = pool {
+                    lookup_tables
+                        .extend(&self.rpc, &*self.signer, &static_accounts(pool))
+                        .await?;
+                }
+                lookup_tables.tables().to_vec()
+            }
+            None => Vec::new(),
+        };
+
+        let simulates = preflight.is_some()
+            || self
+                .compute_budget
+                .as_ref()
+                .is_some_and(ComputeBudget::simulates);
+        let mut units_consumed = 0;
+        if simulates {
+            let (balance_before, accounts) = match preflight {
+                Some(preflight) => (
+                    preflight.balance_before(&self.rpc).await?,
+                    vec![preflight.output_token_account],
+                ),
+                None => (0, Vec::new()),
+            };
+            let simulation = simulate(
+                &self.rpc,
+                &self.payer,
+                &instructions,
+                &lookup_tables,
+                &accounts,
+                self.compute_budget
+                    .as_ref()
+                    .and_then(|budget| budget.heap_frame_bytes),
+            )
+            .await?;
+            if let Some(preflight) = preflight {
+                preflight.check(&simulation, balance_before)?;
+            }
+            units_consumed = simulation.units_consumed.unwrap_or_default();
+        }
+
+        if let Some(budget) = &self.compute_budget {
+            let compute_unit_limit = budget.compute_unit_limit(units_consumed);
+            let compute_unit_price = budget
+                .compute_unit_price(&self.rpc, &writable_accounts(&instructions))
+                .await?;
+            instructions.splice(
+                0..0,
+                compute_budget_instructions(
+                    compute_unit_limit,
+                    compute_unit_price,
+                    budget.heap_frame_bytes,
+                ),
+            );
+        }
+
+        let mut all_signers: Vec<&dyn Signer> = vec![&*self.signer];
+        all_signers.extend_from_slice(signers);
+        let signature = send_transaction(
+            &self.rpc,
+            &self.send_config,
+            &instructions,
+            &lookup_tables,
+            &all_signers,
+        )
+        .await?;
+
+        // Transactions are only served at confirmed or finalized commitment
+        let commitment = match self.rpc.commitment() {
+            commitment if commitment.is_at_least_confirmed() => commitment,
+            _ => CommitmentConfig::confirmed(),
+        };
+        let transaction = self
+            .rpc
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        Ok(Executed {
+            signature,
+            events: parse_transaction(&transaction),
+        })
+    }
+}
@@ -0,0 +1,180 @@
+//! Compute budget instructions for client transactions.
+//!
+//! A [`ComputeBudget`] decides the `SetComputeUnitLimit` and
+//! `SetComputeUnitPrice` a transaction starts with. Left on auto, the limit
+//! is what a simulation of the transaction consumed plus a margin, and the
+//! price a percentile of the priority fees recently paid to write the
+//! accounts the transaction writes. Programs built with `custom-heap` also
+//! need a `RequestHeapFrame` of the heap they allocate from.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+
+use crate::Result;
+
+/// Most compute units a transaction may ask for
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+#[derive(Clone, Debug)]
+pub struct ComputeBudget {
+    /// Fixed compute unit limit, instead of simulating
+    pub compute_unit_limit: Option<u32>,
+    /// Headroom added to the simulated units, as state can move between the
+    /// simulation and the transaction landing
+    pub margin_bps: u16,
+    /// Fixed price in micro-lamports per compute unit, instead of the
+    /// percentile of recent fees
+    pub compute_unit_price: Option<u64>,
+    /// Percentile of recent priority fees to pay, from 0 to 100
+    pub fee_percentile: u8,
+    /// Cap on the percentile price
+    pub max_compute_unit_price: Option<u64>,
+    /// Heap frame to request, in bytes, for a program built with
+    /// `custom-heap`: `raydium_integration::HEAP_FRAME_BYTES` for the proxy
+    pub heap_frame_bytes: Option<u32>,
+}
+
+impl Default for ComputeBudget {
+    fn default() -> Self {
+        Self {
+            compute_unit_limit: None,
+            margin_bps: 1_000,
+            compute_unit_price: None,
+            fee_percentile: 75,
+            max_compute_unit_price: None,
+            heap_frame_bytes: None,
+        }
+    }
+}
+
+impl ComputeBudget {
+    /*
+     * A fixed limit and price, with nothing simulated or fetched
+     */
+    pub fn fixed(compute_unit_limit: u32, compute_unit_price: u64) -> Self {
+        Self {
+            compute_unit_limit: Some(compute_unit_limit),
+            compute_unit_price: Some(compute_unit_price),
+            ..Self::default()
+        }
+    }
+
+    /// Whether the limit needs a simulation
+    pub fn simulates(&self) -> bool {
+        self.compute_unit_limit.is_none()
+    }
+
+    /*
+     * The limit to set, from what a simulation consumed when no fixed limit
+     * is configured
+     */
+    pub fn compute_unit_limit(&self, units_consumed: u64) -> u32 {
+        match self.compute_unit_limit {
+            Some(limit) => limit,
+            None => {
+                let with_margin =
+                    units_consumed as u128 * (10_000 + self.margin_bps as u128) / 10_000;
+                with_margin.min(MAX_COMPUTE_UNIT_LIMIT as u128) as u32
+            }
+        }
+    }
+
+    /*
+     * The price to set, from the priority fees recently paid to write
+     * `writable_accounts` when no fixed price is configured
+     */
+    pub async fn compute_unit_price(
+        &self,
+        rpc: &RpcClient,
+        writable_accounts: &[Pubkey],
+    ) -> Result<u64> {
+        if let Some(price) = self.compute_unit_price {
+            return Ok(price);
+        }
+        let fees: Vec<u64> = rpc
+            .get_recent_prioritization_fees(writable_accounts)
+            .await?
+            .into_iter()
+            .map(|fee| fee.prioritization_fee)
+            .collect();
+        let price = percentile(fees, self.fee_percentile);
+        Ok(match self.max_compute_unit_price {
+            Some(max) => price.min(max),
+            None => price,
+        })
+    }
+}
+
+/*
+ * The instructions a transaction starts with to set its limit and price,
+ * and its heap frame when one is requested
+ */
+pub fn compute_budget_instructions(
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+    heap_frame_bytes: Option<u32>,
+) -> Vec<Instruction> {
+    let mut instructions = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit),
+        ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price),
+    ];
+    instructions.extend(heap_frame_bytes.map(ComputeBudgetInstruction::request_heap_frame));
+    instructions
+}
+
+/*
+ * Accounts the instructions write, which recent fees are looked up for
+ */
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = instructions
+        .iter()
+        .flat_map(|instruction| &instruction.accounts)
+        .filter(|meta| meta.is_writable)
+        .map(|meta| meta.pubkey)
+        .collect();
+    accounts.sort();
+    accounts.dedup();
+    // The RPC looks up fees for at most 128 accounts
+    accounts.truncate(128);
+    accounts
+}
+
+/*
+ * The value below which `percentile` percent of `values` fall, zero when
+ * there are none
+ */
+fn percentile(mut values: Vec<u64>, percentile: u8) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let rank = (values.len() - 1) * percentile.min(100) as usize / 100;
+    values[rank]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_picks_the_ranked_fee() {
+        let fees = vec![0, 500, 100, 0, 10_000, 200, 300, 0, 400, 1_000, 50];
+        assert_eq!(percentile(fees.clone(), 0), 0);
+        assert_eq!(percentile(fees.clone(), 50), 200);
+        assert_eq!(percentile(fees.clone(), 90), 1_000);
+        assert_eq!(percentile(fees, 100), 10_000);
+        assert_eq!(percentile(Vec::new(), 75), 0);
+    }
+
+    #[test]
+    fn simulated_limit_adds_the_margin_up_to_the_maximum() {
+        let budget = ComputeBudget::default();
+        assert_eq!(budget.compute_unit_limit(100_000), 110_000);
+        assert_eq!(budget.compute_unit_limit(1_390_000), MAX_COMPUTE_UNIT_LIMIT);
+        assert_eq!(
+            ComputeBudget::fixed(250_000, 1).compute_unit_limit(100_000),
+            250_000
+        );
+    }
+}
@@ -0,0 +1,322 @@
+//! Liquidity-by-price histograms of a pool.
+//!
+//! [`fetch_depth_histogram`] loads a pool and the initialized tick arrays
+//! around its price and buckets the liquidity into bins of equal tick width,
+//! the data front-ends draw depth charts from. Each bin carries the
+//! liquidity active across it and the tokens that liquidity holds: token_0
+//! above the price, token_1 below it, both in the bin the price is in. The
+//! histogram serializes to JSON as is.
+use anchor_lang::prelude::Pubkey;
+use raydium_amm_v3::libraries::big_num::U256;
+use raydium_amm_v3::states::{PoolState, TickArrayState};
+use raydium_integration::math::{
+    amount_0_delta, amount_1_delta, sqrt_price_at_tick, sqrt_price_to_price,
+    tick_array_start_index, tick_to_price, MAX_TICK, MIN_TICK,
+};
+use raydium_integration::CustomError;
+use serde::Serialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::accounts::{decode_zero_copy, fetch_zero_copy};
+use crate::helpers::{tick_array_address, ticks_per_tick_array};
+use crate::{ClientError, Result};
+
+/// Accounts one `getMultipleAccounts` call returns at most
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// A pool's liquidity bucketed by price
+#[derive(Clone, Debug, Serialize)]
+pub struct DepthHistogram {
+    #[serde(serialize_with = "pubkey_string")]
+    pub pool: Pubkey,
+    pub tick_current: i32,
+    /// Token_1 per token_0 in whole tokens
+    pub price: f64,
+    /// Ticks each bin spans, a multiple of the pool's tick spacing
+    pub bin_ticks: u32,
+    /// Ascending by price
+    pub bins: Vec<DepthBin>,
+}
+
+/// Liquidity in `[tick_lower, tick_upper)`
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DepthBin {
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub price_lower: f64,
+    pub price_upper: f64,
+    /// Liquidity active across the bin, averaged over its ticks
+    pub liquidity: u128,
+    /// Raw token_0 the liquidity holds within the bin
+    pub amount_0: u64,
+    /// Raw token_1 the liquidity holds within the bin
+    pub amount_1: u64,
+}
+
+fn pubkey_string<S: serde::Serializer>(
+    key: &Pubkey,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(key)
+}
+
+/*
+ * Load `pool` and its tick arrays within `bins_each_side` bins of
+ * `bin_ticks` either side of the price, and bucket its liquidity
+ */
+pub async fn fetch_depth_histogram(
+    rpc: &RpcClient,
+    pool: &Pubkey,
+    bin_ticks: u32,
+    bins_each_side: u32,
+) -> Result<DepthHistogram> {
+    let pool_state: PoolState = fetch_zero_copy(rpc, pool).await?;
+    let (lower, upper) = histogram_range(&pool_state, bin_ticks, bins_each_side);
+
+    let ticks_in_array = ticks_per_tick_array(pool_state.tick_spacing);
+    let first = tick_array_start_index(lower, pool_state.tick_spacing);
+    let last = tick_array_start_index(upper - 1, pool_state.tick_spacing);
+    let addresses: Vec<Pubkey> = (first..=last)
+        .step_by(ticks_in_array as usize)
+        .map(|start_index| tick_array_address(pool, start_index).0)
+        .collect();
+
+    let mut tick_arrays = Vec::new();
+    for chunk in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        let accounts = rpc.get_multiple_accounts(chunk).await?;
+        for (address, account) in chunk.iter().zip(accounts) {
+            // Tick arrays nobody has opened a position in don't exist
+            if let Some(account) = account {
+                tick_arrays.push(decode_zero_copy::<TickArrayState>(address, &account.data)?);
+            }
+        }
+    }
+    depth_histogram(pool, &pool_state, &tick_arrays, bin_ticks, bins_each_side)
+}
+
+/*
+ * Width of a bin rounded up to the tick spacing, and the ticks the
+ * histogram covers
+ */
+fn histogram_range(pool_state: &PoolState, bin_ticks: u32, bins_each_side: u32) -> (i32, i32) {
+    let width = bin_width(pool_state.tick_spacing, bin_ticks);
+    let bin_start = pool_state.tick_current.div_euclid(width) * width;
+    let reach = (bins_each_side as i64 * width as i64).min((MAX_TICK - MIN_TICK) as i64) as i32;
+    (
+        bin_start.saturating_sub(reach).max(MIN_TICK),
+        bin_start
+            .saturating_add(width)
+            .saturating_add(reach)
+            .min(MAX_TICK),
+    )
+}
+
+fn bin_width(tick_spacing: u16, bin_ticks: u32) -> i32 {
+    let tick_spacing = tick_spacing.max(1) as u32;
+    let width = bin_ticks.max(1).div_ceil(tick_spacing) * tick_spacing;
+    width.min((MAX_TICK - MIN_TICK) as u32) as i32
+}
+
+/*
+ * Bucket a pool's liquidity over already loaded tick arrays. Bins are
+ * `bin_ticks` wide, rounded up to the tick spacing, with `bins_each_side`
+ * of them either side of the one holding the price; those past the tick
+ * bounds are left out or cut short
+ */
+pub fn depth_histogram(
+    pool: &Pubkey,
+    pool_state: &PoolState,
+    tick_arrays: &[TickArrayState],
+    bin_ticks: u32,
+    bins_each_side: u32,
+) -> Result<DepthHistogram> {
+    let width = bin_width(pool_state.tick_spacing, bin_ticks);
+    let (lower, upper) = histogram_range(pool_state, bin_ticks, bins_each_side);
+    let tick_current = pool_state.tick_current;
+    let sqrt_price_x64 = pool_state.sqrt_price_x64;
+
+    let mut ticks: Vec<(i32, i128)> = tick_arrays
+        .iter()
+        .flat_map(|tick_array| tick_array.ticks.iter())
+        .filter(|tick| tick.liquidity_gross > 0)
+        .map(|tick| (tick.tick, tick.liquidity_net))
+        .collect();
+    ticks.sort_unstable_by_key(|(tick, _)| *tick);
+    ticks.dedup_by_key(|(tick, _)| *tick);
+
+    // Liquidity at the bottom of the range: undo every tick crossed on the
+    // way down from the current one
+    let mut liquidity = pool_state.liquidity;
+    for &(_, liquidity_net) in ticks
+        .iter()
+        .filter(|(tick, _)| *tick > lower && *tick <= tick_current)
+    {
+        liquidity = checked_liquidity(liquidity, -liquidity_net)?;
+    }
+    let mut ticks = ticks
+        .into_iter()
+        .filter(|(tick, _)| *tick > lower && *tick < upper)
+        .peekable();
+
+    let mut bins = Vec::new();
+    let mut bin_lower = lower;
+    while bin_lower < upper {
+        let bin_upper = (bin_lower - bin_lower.rem_euclid(width) + width).min(upper);
+        let mut bin = DepthBin {
+            tick_lower: bin_lower,
+            tick_upper: bin_upper,
+            price_lower: tick_to_price(
+                bin_lower,
+                pool_state.mint_decimals_0,
+                pool_state.mint_decimals_1,
+            ),
+            price_upper: tick_to_price(
+                bin_upper,
+                pool_state.mint_decimals_0,
+                pool_state.mint_decimals_1,
+            ),
+            liquidity: 0,
+            amount_0: 0,
+            amount_1: 0,
+        };
+        let mut liquidity_ticks = U256::zero();
+
+        // Split the bin where initialized ticks change the liquidity
+        let mut segment_lower = bin_lower;
+        while segment_lower < bin_upper {
+            let segment_upper = match ticks.peek() {
+                Some(&(tick, _)) if tick < bin_upper => tick,
+                _ => bin_upper,
+            };
+            if segment_upper > segment_lower {
+                let (amount_0, amount_1) =
+                    segment_amounts(segment_lower, segment_upper, liquidity, sqrt_price_x64)?;
+                bin.amount_0 = bin.amount_0.saturating_add(amount_0);
+                bin.amount_1 = bin.amount_1.saturating_add(amount_1);
+                liquidity_ticks +=
+                    U256::from(liquidity) * U256::from((segment_upper - segment_lower) as u64);
+            }
+            if segment_upper < bin_upper {
+                let (_, liquidity_net) = ticks.next().unwrap_or_default();
+                liquidity = checked_liquidity(liquidity, liquidity_net)?;
+            }
+            segment_lower = segment_upper;
+        }
+
+        bin.liquidity = (liquidity_ticks / U256::from((bin_upper - bin_lower) as u64)).as_u128();
+        bins.push(bin);
+        bin_lower = bin_upper;
+    }
+
+    Ok(DepthHistogram {
+        pool: *pool,
+        tick_current,
+        price: sqrt_price_to_price(
+            sqrt_price_x64,
+            pool_state.mint_decimals_0,
+            pool_state.mint_decimals_1,
+        ),
+        bin_ticks: width as u32,
+        bins,
+    })
+}
+
+/*
+ * Tokens `liquidity` holds between two ticks: token_0 above the price,
+ * token_1 below it
+ */
+fn segment_amounts(
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    sqrt_price_x64: u128,
+) -> Result<(u64, u64)> {
+    let sqrt_price_lower_x64 = sqrt_price_at_tick(tick_lower)?;
+    let sqrt_price_upper_x64 = sqrt_price_at_tick(tick_upper)?;
+    let sqrt_price_x64 = sqrt_price_x64.clamp(sqrt_price_lower_x64, sqrt_price_upper_x64);
+    Ok((
+        amount_0_delta(sqrt_price_x64, sqrt_price_upper_x64, liquidity, false),
+        amount_1_delta(sqrt_price_lower_x64, sqrt_price_x64, liquidity, false),
+    ))
+}
+
+fn checked_liquidity(liquidity: u128, liquidity_net: i128) -> Result<u128> {
+    liquidity
+        .checked_add_signed(liquidity_net)
+        .ok_or_else(|| ClientError::Program(CustomError::MathOverflow.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    const LIQUIDITY: u128 = 1_000_000_000_000;
+
+    /*
+     * A pool at tick 0 with LIQUIDITY in [-100, 100) and as much again in
+     * [50, 100)
+     */
+    fn pool_and_tick_arrays() -> (PoolState, Vec<TickArrayState>) {
+        let mut pool_state = PoolState::zeroed();
+        pool_state.tick_spacing = 10;
+        pool_state.sqrt_price_x64 = sqrt_price_at_tick(0).unwrap();
+        pool_state.liquidity = LIQUIDITY;
+
+        let mut below = TickArrayState::zeroed();
+        below.start_tick_index = -600;
+        below.ticks[50].tick = -100;
+        below.ticks[50].liquidity_gross = LIQUIDITY;
+        below.ticks[50].liquidity_net = LIQUIDITY as i128;
+
+        let mut above = TickArrayState::zeroed();
+        above.start_tick_index = 0;
+        above.ticks[5].tick = 50;
+        above.ticks[5].liquidity_gross = LIQUIDITY;
+        above.ticks[5].liquidity_net = LIQUIDITY as i128;
+        above.ticks[10].tick = 100;
+        above.ticks[10].liquidity_gross = 2 * LIQUIDITY;
+        above.ticks[10].liquidity_net = -2 * LIQUIDITY as i128;
+        (pool_state, vec![below, above])
+    }
+
+    #[test]
+    fn buckets_liquidity_by_tick() {
+        let (pool_state, tick_arrays) = pool_and_tick_arrays();
+        let histogram =
+            depth_histogram(&Pubkey::default(), &pool_state, &tick_arrays, 100, 2).unwrap();
+
+        assert_eq!(histogram.bin_ticks, 100);
+        let ranges: Vec<_> = histogram
+            .bins
+            .iter()
+            .map(|bin| (bin.tick_lower, bin.tick_upper))
+            .collect();
+        assert_eq!(
+            ranges,
+            [(-200, -100), (-100, 0), (0, 100), (100, 200), (200, 300)]
+        );
+        let liquidity: Vec<_> = histogram.bins.iter().map(|bin| bin.liquidity).collect();
+        assert_eq!(liquidity, [0, LIQUIDITY, 3 * LIQUIDITY / 2, 0, 0]);
+
+        // Below the price only token_1, above it only token_0
+        let below = &histogram.bins[1];
+        assert_eq!(below.amount_0, 0);
+        assert!(below.amount_1 > 0);
+        let above = &histogram.bins[2];
+        assert!(above.amount_0 > 0);
+        assert_eq!(above.amount_1, 0);
+        assert!(histogram.bins[0].price_upper <= histogram.price);
+    }
+
+    #[test]
+    fn rounds_bins_up_to_the_tick_spacing() {
+        let (pool_state, tick_arrays) = pool_and_tick_arrays();
+        let histogram =
+            depth_histogram(&Pubkey::default(), &pool_state, &tick_arrays, 15, 1).unwrap();
+        assert_eq!(histogram.bin_ticks, 20);
+        assert_eq!(histogram.bins.len(), 3);
+        assert_eq!(histogram.bins[1].tick_lower, 0);
+        assert_eq!(histogram.bins[1].liquidity, LIQUIDITY);
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::Pubkey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    #[error("subscription failed: {0}")]
+    Subscription(Box<solana_client::nonblocking::pubsub_client::PubsubClientError>),
+    #[cfg(feature = "yellowstone")]
+    #[error("geyser stream failed: {0}")]
+    Geyser(String),
+    #[error("Jito block engine: {0}")]
+    Jito(String),
+    #[error("aggregator quote failed: {0}")]
+    Aggregator(String),
+    #[error("I/O failed: {0}")]
+    Io(std::io::Error),
+    #[error("transaction failed: {0}")]
+    Transaction(Box<anchor_client::ClientError>),
+    #[error("transaction does not compile: {0}")]
+    Compile(solana_sdk::message::CompileError),
+    #[error("signing failed: {0}")]
+    Signer(solana_sdk::signer::SignerError),
+    #[error("simulation failed: {error}")]
+    Simulation {
+        error: solana_sdk::transaction::TransactionError,
+        logs: Vec<String>,
+    },
+    #[error("transaction {signature} failed: {error}")]
+    Failed {
+        signature: solana_sdk::signature::Signature,
+        error: solana_sdk::transaction::TransactionError,
+    },
+    #[error("transaction {0} was not confirmed in time")]
+    Unconfirmed(solana_sdk::signature::Signature),
+    #[error("simulated swap pays out {amount_out}, below the minimum of {min_amount_out}")]
+    PreflightSlippage {
+        amount_out: u64,
+        min_amount_out: u64,
+    },
+    #[error("account {0} does not exist")]
+    AccountNotFound(Pubkey),
+    #[error("account {0} is not of the expected type")]
+    InvalidAccountData(Pubkey),
+    #[error("mint {mint} is not one of pool {pool}'s tokens")]
+    MintNotInPool { mint: Pubkey, pool: Pubkey },
+    #[error("position {position} is not in pool {pool}")]
+    PositionNotInPool { position: Pubkey, pool: Pubkey },
+    #[error("order {order} is not on pool {pool}")]
+    OrderNotInPool { order: Pubkey, pool: Pubkey },
+    #[error("price {0} is not a positive number")]
+    InvalidPrice(f64),
+    #[error("price range {price_lower} to {price_upper} is empty")]
+    InvalidPriceRange { price_lower: f64, price_upper: f64 },
+    #[error("{0}")]
+    Program(anchor_lang::error::Error),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(error))
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<anchor_client::ClientError> for ClientError {
+    fn from(error: anchor_client::ClientError) -> Self {
+        Self::Transaction(Box::new(error))
+    }
+}
+
+impl From<solana_sdk::message::CompileError> for ClientError {
+    fn from(error: solana_sdk::message::CompileError) -> Self {
+        Self::Compile(error)
+    }
+}
+
+impl From<solana_sdk::signer::SignerError> for ClientError {
+    fn from(error: solana_sdk::signer::SignerError) -> Self {
+        Self::Signer(error)
+    }
+}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        Self::Program(error)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
@@ -0,0 +1,465 @@
+//! Decoding the program's events from transactions.
+//!
+//! Without `event-cpi` the program logs each event as a `Program data:` line;
+//! with it, the event is the data of a self-CPI inner instruction. Both
+//! decode into a [`ProgramEvent`], which serializes as
+//! `{"name": "SwapExecuted", "data": {...}}` with addresses as base58
+//! strings, ready for JSON or a Postgres `jsonb` column.
+//!
+//! Events about a user carry the user's `user_seq`; [`UserSequences`] follows
+//! it to report events an indexer missed or received out of order. Events of
+//! one multi-event instruction share an `op_id`; [`group_operations`] puts
+//! them back together.
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use raydium_integration::events::*;
+use serde::Serialize;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiInstruction};
+
+/// Anchor's prefix on event-CPI instruction data, ahead of the event's
+/// discriminator
+const EVENT_IX_TAG_LE: [u8; 8] = 0x1d9acb512ea545e4u64.to_le_bytes();
+
+const PROGRAM_DATA_LOG: &str = "Program data: ";
+
+macro_rules! program_events {
+    ($($event:ident),* $(,)?) => {
+        /// One of the program's events, tagged with its name when serialized
+        #[derive(Debug, Serialize)]
+        #[serde(tag = "name", content = "data")]
+        pub enum ProgramEvent {
+            $($event($event),)*
+        }
+
+        impl ProgramEvent {
+            /*
+             * Decode an event from its discriminator and Borsh data. None
+             * when the data is not one of the program's events
+             */
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if let Some(mut fields) = data.strip_prefix($event::DISCRIMINATOR) {
+                        return $event::deserialize(&mut fields).ok().map(Self::$event);
+                    }
+                )*
+                None
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event(_) => stringify!($event),)*
+                }
+            }
+        }
+    };
+}
+
+program_events!(
+    SlippageSet,
+    SwapExecuted,
+    PositionOpened,
+    LiquidityIncreased,
+    LiquidityDecreased,
+    PositionClosed,
+    FeesCollected,
+    RewardsCollected,
+    PositionPnlRealized,
+    FeeCheckpointStamped,
+    SwapPartiallyFilled,
+    PositionSplit,
+    PositionsMerged,
+    PositionStreamCreated,
+    StreamClaimed,
+    SharedPositionCreated,
+    SharedPositionChanged,
+    SharedFeesClaimed,
+    DustSwept,
+    MintExtensionOptInSet,
+    OpenApprovalSet,
+    AllowedCallersSet,
+    ExposureCapsSet,
+    GlobalConfigUpdated,
+    ParamChangeScheduled,
+    ParamChangeCancelled,
+    ConfigsMigrated,
+    ProtocolFeeCollected,
+    RoundTripsFlagged,
+    InsuranceClaimPaid,
+    PointsRatesSet,
+    PointsClaimed,
+    FeeOracleSet,
+    SwapValidatorsSet,
+    DestinationAdded,
+    DestinationRemoved,
+    DestinationAllowlistToggled,
+    GuardiansSet,
+    RecoveryApproved,
+    RecoveryCancelled,
+    OwnerRecovered,
+    SessionCreated,
+    SessionRevoked,
+    SessionLimitsSet,
+    DutchOrderCreated,
+    DutchOrderFilled,
+    DutchOrderCancelled,
+    FloorOrderCreated,
+    FloorOrderFilled,
+    FloorOrderCancelled,
+    SwapCommitted,
+    PoolCreated,
+    RewardEmissionSet,
+    CpmmMigrated,
+    OpenbookMarketCreated,
+    AmmPoolCreated,
+    AmmLpLocked,
+    AmmLpUnlocked,
+);
+
+/*
+ * Events the program logged, in order. Follows the invoke and
+ * success/failed lines so `Program data:` lines of other programs, Raydium's
+ * own events among them, are skipped
+ */
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<ProgramEvent> {
+    let program_id = raydium_integration::ID.to_string();
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_LOG) {
+            if invocations.last() == Some(&program_id.as_str()) {
+                if let Some(event) = BASE64
+                    .decode(data)
+                    .ok()
+                    .and_then(|data| ProgramEvent::decode(&data))
+                {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            match (words.next(), words.next()) {
+                (Some(program), Some("invoke")) => invocations.push(program),
+                (Some(_), Some("success" | "failed:")) => {
+                    invocations.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+/*
+ * Event carried by an event-CPI inner instruction, if `data` is one of the
+ * program's self-CPIs
+ */
+pub fn decode_cpi_event(program_id: &Pubkey, data: &[u8]) -> Option<ProgramEvent> {
+    if *program_id != raydium_integration::ID {
+        return None;
+    }
+    ProgramEvent::decode(data.strip_prefix(EVENT_IX_TAG_LE.as_slice())?)
+}
+
+/*
+ * Every event of a fetched transaction: logged ones, then those of event-CPI
+ * inner instructions. Fetch it with `UiTransactionEncoding::Base64` so the
+ * account keys can be read back
+ */
+pub fn parse_transaction(
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Vec<ProgramEvent> {
+    let Some(meta) = &transaction.transaction.meta else {
+        return Vec::new();
+    };
+    let mut events = match &meta.log_messages {
+        OptionSerializer::Some(logs) => parse_logs(logs),
+        _ => Vec::new(),
+    };
+
+    let OptionSerializer::Some(inner_instructions) = &meta.inner_instructions else {
+        return events;
+    };
+    let Some(decoded) = transaction.transaction.transaction.decode() else {
+        return events;
+    };
+    // Lookup table addresses follow the static keys, writable ones first
+    let mut account_keys = decoded.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        account_keys.extend(
+            loaded
+                .writable
+                .iter()
+                .chain(&loaded.readonly)
+                .filter_map(|key| key.parse::<Pubkey>().ok()),
+        );
+    }
+    for instruction in inner_instructions
+        .iter()
+        .flat_map(|inner| &inner.instructions)
+    {
+        let UiInstruction::Compiled(instruction) = instruction else {
+            continue;
+        };
+        let Some(program_id) = account_keys.get(instruction.program_id_index as usize) else {
+            continue;
+        };
+        let Ok(data) = bs58::decode(&instruction.data).into_vec() else {
+            continue;
+        };
+        events.extend(decode_cpi_event(program_id, &data));
+    }
+    events
+}
+
+impl ProgramEvent {
+    /*
+     * The user whose sequence the event is numbered in, and its number. None
+     * for events outside any user's sequence, or emitted without the user's
+     * config
+     */
+    pub fn user_seq(&self) -> Option<(Pubkey, u64)> {
+        let (user, seq) = match self {
+            Self::SlippageSet(event) => (event.owner, event.user_seq),
+            Self::SwapExecuted(event) => (event.user, event.user_seq),
+            Self::PositionOpened(event) => (event.user, event.user_seq),
+            Self::LiquidityIncreased(event) => (event.user, event.user_seq),
+            Self::LiquidityDecreased(event) => (event.user, event.user_seq),
+            Self::FeesCollected(event) => (event.user, event.user_seq),
+            Self::RewardsCollected(event) => (event.user, event.user_seq),
+            Self::SwapPartiallyFilled(event) => (event.user, event.user_seq),
+            Self::AllowedCallersSet(event) => (event.owner, event.user_seq),
+            Self::ExposureCapsSet(event) => (event.owner, event.user_seq),
+            Self::ProtocolFeeCollected(event) => (event.user, event.user_seq),
+            Self::RoundTripsFlagged(event) => (event.user, event.user_seq),
+            Self::OwnerRecovered(event) => (event.new_owner, event.user_seq),
+            Self::CpmmMigrated(event) => (event.user, event.user_seq),
+            _ => return None,
+        };
+        (seq > 0).then_some((user, seq))
+    }
+
+    /// ID of the operation the event belongs to, for events of instructions
+    /// that emit several
+    pub fn op_id(&self) -> Option<u64> {
+        match self {
+            Self::SwapExecuted(event) => Some(event.op_id),
+            Self::SwapPartiallyFilled(event) => Some(event.op_id),
+            Self::ProtocolFeeCollected(event) => Some(event.op_id),
+            Self::RoundTripsFlagged(event) => Some(event.op_id),
+            Self::LiquidityDecreased(event) => Some(event.op_id),
+            Self::FeesCollected(event) => Some(event.op_id),
+            Self::RewardsCollected(event) => Some(event.op_id),
+            Self::PositionPnlRealized(event) => Some(event.op_id),
+            Self::PositionClosed(event) => Some(event.op_id),
+            Self::PositionsMerged(event) => Some(event.op_id),
+            _ => None,
+        }
+    }
+}
+
+/*
+ * Events of one transaction grouped by operation, in order of each
+ * operation's first event. Events without an `op_id` stand alone
+ */
+pub fn group_operations(events: Vec<ProgramEvent>) -> Vec<Vec<ProgramEvent>> {
+    let mut groups: Vec<(Option<u64>, Vec<ProgramEvent>)> = Vec::new();
+    for event in events {
+        let op_id = event.op_id();
+        match groups
+            .iter_mut()
+            .find(|(group_op_id, _)| op_id.is_some() && *group_op_id == op_id)
+        {
+            Some((_, group)) => group.push(event),
+            None => groups.push((op_id, vec![event])),
+        }
+    }
+    groups.into_iter().map(|(_, group)| group).collect()
+}
+
+/// A break in a user's event sequence
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SequenceGap {
+    /// Events numbered `from` to `to` were never seen
+    Missed { user: Pubkey, from: u64, to: u64 },
+    /// `seq` arrived after a later event of the user, or twice
+    Stale { user: Pubkey, seq: u64 },
+}
+
+/// Last sequence number seen per user
+#[derive(Clone, Debug, Default)]
+pub struct UserSequences {
+    last: HashMap<Pubkey, u64>,
+}
+
+impl UserSequences {
+    /// Continue a user's sequence after `seq`, the last one already processed
+    pub fn resume(&mut self, user: Pubkey, seq: u64) {
+        self.last.insert(user, seq);
+    }
+
+    pub fn last(&self, user: &Pubkey) -> Option<u64> {
+        self.last.get(user).copied()
+    }
+
+    /*
+     * Track `event`, reporting any break it reveals. A user's first event
+     * starts their sequence unless `resume` set where it continues
+     */
+    pub fn observe(&mut self, event: &ProgramEvent) -> Option<SequenceGap> {
+        let (user, seq) = event.user_seq()?;
+        let last = self.last.entry(user).or_insert(seq - 1);
+        if seq <= *last {
+            return Some(SequenceGap::Stale { user, seq });
+        }
+        let gap = (seq > *last + 1).then_some(SequenceGap::Missed {
+            user,
+            from: *last + 1,
+            to: seq - 1,
+        });
+        *last = seq;
+        gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Event;
+
+    fn swap_executed(user: Pubkey) -> SwapExecuted {
+        SwapExecuted {
+            user,
+            pool: Pubkey::new_unique(),
+            amount_in: 1_000,
+            amount_out: 990,
+            expected_amount: 995,
+            slippage_bps: 50,
+            is_base_input: true,
+            timestamp: 1_700_000_000,
+            sqrt_price_before_x64: 1 << 64,
+            sqrt_price_after_x64: (1 << 64) - 1,
+            tick_after: -1,
+            amm_fee: 3,
+            protocol_fee: 0,
+            realized_slippage_bps: 0,
+            default_slippage: false,
+            user_seq: 0,
+            op_id: 0,
+            ui_amount_in: 1_000,
+            ui_amount_out: 990,
+        }
+    }
+
+    #[test]
+    fn logs_decode_only_the_programs_events() {
+        let user = Pubkey::new_unique();
+        let data = format!(
+            "{PROGRAM_DATA_LOG}{}",
+            BASE64.encode(swap_executed(user).data())
+        );
+        let program = raydium_integration::ID;
+        let raydium = raydium_integration::RAYDIUM_CLMM_PROGRAM_ID;
+        let logs = [
+            format!("Program {program} invoke [1]"),
+            format!("Program {raydium} invoke [2]"),
+            data.clone(),
+            format!("Program {raydium} success"),
+            data,
+            format!("Program {program} success"),
+        ];
+
+        let events = parse_logs(&logs);
+        assert_eq!(events.len(), 1);
+        let ProgramEvent::SwapExecuted(event) = &events[0] else {
+            panic!("decoded {}", events[0].name());
+        };
+        assert_eq!(event.user, user);
+    }
+
+    #[test]
+    fn cpi_events_need_the_tag_and_the_program() {
+        let user = Pubkey::new_unique();
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend(swap_executed(user).data());
+
+        assert!(decode_cpi_event(&raydium_integration::ID, &data).is_some());
+        assert!(decode_cpi_event(&Pubkey::new_unique(), &data).is_none());
+        assert!(decode_cpi_event(&raydium_integration::ID, &data[8..]).is_none());
+    }
+
+    #[test]
+    fn sequences_report_missed_and_stale_events() {
+        let user = Pubkey::new_unique();
+        let event = |user_seq| {
+            ProgramEvent::SwapExecuted(SwapExecuted {
+                user_seq,
+                ..swap_executed(user)
+            })
+        };
+        let mut sequences = UserSequences::default();
+        assert_eq!(sequences.observe(&event(0)), None);
+        assert_eq!(sequences.observe(&event(7)), None);
+        assert_eq!(sequences.observe(&event(8)), None);
+        assert_eq!(
+            sequences.observe(&event(11)),
+            Some(SequenceGap::Missed {
+                user,
+                from: 9,
+                to: 10
+            })
+        );
+        assert_eq!(
+            sequences.observe(&event(9)),
+            Some(SequenceGap::Stale { user, seq: 9 })
+        );
+        assert_eq!(sequences.last(&user), Some(11));
+
+        sequences.resume(user, 20);
+        assert_eq!(sequences.observe(&event(21)), None);
+    }
+
+    #[test]
+    fn operations_group_events_by_op_id() {
+        let user = Pubkey::new_unique();
+        let swap = |op_id| {
+            ProgramEvent::SwapExecuted(SwapExecuted {
+                op_id,
+                ..swap_executed(user)
+            })
+        };
+        let slippage_set = || {
+            ProgramEvent::SlippageSet(SlippageSet {
+                owner: user,
+                slippage_bps: 50,
+                timestamp: 0,
+                user_seq: 0,
+            })
+        };
+        let groups = group_operations(vec![
+            swap(1),
+            slippage_set(),
+            swap(2),
+            swap(1),
+            slippage_set(),
+        ]);
+        let sizes: Vec<usize> = groups.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [2, 1, 1, 1]);
+        assert_eq!(groups[0][1].op_id(), Some(1));
+    }
+
+    #[test]
+    fn events_serialize_with_base58_addresses() {
+        let user = Pubkey::new_unique();
+        let json = serde_json::to_string(&ProgramEvent::SwapExecuted(swap_executed(user))).unwrap();
+        assert!(json.starts_with(r#"{"name":"SwapExecuted","data":{"#));
+        assert!(json.contains(&format!(r#""user":"{user}""#)));
+        assert!(json.contains(r#""amount_out":990"#));
+        assert!(json.contains(r#""sqrt_price_before_x64":18446744073709551616"#));
+    }
+}
@@ -0,0 +1,353 @@
+//! Explaining failed transactions.
+//!
+//! A failed instruction only reports a number, such as `custom program
+//! error: 0x1782`, and both this program and Raydium number their errors
+//! from 6000. [`explain_error`] reads the logs to find which program failed
+//! and, from Anchor's `AnchorError` log line or this program's own error
+//! table, what the error is called and means, with a suggested remedy for
+//! the errors users run into in practice.
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use raydium_integration::CustomError;
+use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+
+use crate::ClientError;
+
+/// A program error with what it means and how to get past it
+#[derive(Clone, Debug)]
+pub struct ExplainedError {
+    /// The program that failed, when the logs tell
+    pub program_id: Option<Pubkey>,
+    pub instruction_index: u8,
+    pub code: u32,
+    /// The error's variant name, such as `SlippageExceeded`
+    pub name: Option<String>,
+    pub message: Option<String>,
+    pub remedy: Option<&'static str>,
+}
+
+impl ExplainedError {
+    pub fn program_name(&self) -> &'static str {
+        match self.program_id {
+            Some(id) if id == raydium_integration::ID => "raydium-integration",
+            Some(id) if id == raydium_integration::RAYDIUM_CLMM_PROGRAM_ID => "Raydium CLMM",
+            Some(_) => "another program",
+            None => "an unknown program",
+        }
+    }
+}
+
+impl fmt::Display for ExplainedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "instruction {} failed in {} with error {}",
+            self.instruction_index,
+            self.program_name(),
+            self.code
+        )?;
+        if let Some(name) = &self.name {
+            write!(f, " {name}")?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {message}")?;
+        }
+        if let Some(remedy) = self.remedy {
+            write!(f, ". {remedy}")?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! program_errors {
+    ($($error:ident $(=> $remedy:literal)?),* $(,)?) => {
+        /*
+         * Name, message and remedy of this program's error `code`
+         */
+        fn program_error(code: u32) -> Option<(String, String, Option<&'static str>)> {
+            $(
+                if code == u32::from(CustomError::$error) {
+                    let remedy: Option<&'static str> = None $(.or(Some($remedy)))?;
+                    return Some((
+                        stringify!($error).to_string(),
+                        CustomError::$error.to_string(),
+                        remedy,
+                    ));
+                }
+            )*
+            None
+        }
+    };
+}
+
+program_errors!(
+    InvalidSlippage => "Slippage must be at most 10000 basis points",
+    InvalidTickRange => "Ticks must be ordered and multiples of the pool's tick spacing",
+    ZeroLiquidity,
+    ZeroDeposit => "Deposit a nonzero amount of at least one token",
+    InvalidVault,
+    ZeroSwapAmount,
+    InvalidExpectedAmount => "Quote the swap again and pass the quoted amount",
+    ConfigUpToDate,
+    PositionNotInRegistry => "Pass the registry page the position is recorded on",
+    RegistryAccountsMismatch,
+    RegistryPageFull => "Register the position on the next page",
+    MathOverflow,
+    PositionPoolMismatch,
+    InvalidTickArray => "Pass the tick arrays for the position's ticks",
+    CheckpointTooSoon,
+    SimulationComplete => "Expected from a simulate_only call; read the return data",
+    TooManyTickCrossings => "Split the swap into smaller ones",
+    PriceLimitRequired,
+    SlippageExceeded => "The price moved: quote again or raise the slippage tolerance",
+    InvalidRangeBps,
+    InvalidSplitLiquidity,
+    SamePosition,
+    PositionRangeMismatch,
+    InvalidStreamSchedule,
+    NothingToClaim => "Wait for more of the stream to unlock",
+    InsufficientShares,
+    UnsupportedMintExtension => "Opt in to the mint's extensions with set_extension_opt_in",
+    UnknownExtensionFlag,
+    MetadataAccountRequired,
+    TooManyAllowedCallers,
+    CallerNotAllowed => "Add the calling program to the owner's caller allowlist",
+    InstructionsSysvarRequired,
+    Unauthorized => "Sign with the account's owner or authority",
+    InvalidProtocolFee,
+    ProtocolFeeAccountsRequired => "Pass the protocol fee accounts, as the protocol fee is on",
+    InvalidTreasuryAccount,
+    ZeroClaimAmount,
+    InsufficientInsuranceFund,
+    NoPointsToClaim,
+    InvalidInsuranceVault,
+    FeeOracleRequired,
+    InvalidFeeOracle,
+    TooManySwapValidators,
+    SwapValidatorMissing => "Pass every swap validator fetch_swap_validators lists",
+    DestinationAlreadyListed,
+    DestinationAllowlistFull,
+    DestinationNotListed,
+    DestinationNotAllowed => "Add the destination to the allowlist and wait out its delay",
+    InvalidGuardianSet,
+    NotGuardian,
+    InvalidRecoveryOwner,
+    NoRecoveryPending,
+    RecoveryNotReady => "Collect the remaining guardian approvals or wait out the delay",
+    InvalidSessionExpiry,
+    InvalidSessionScope,
+    SessionExpired => "Open a new session",
+    OutOfSessionScope,
+    TooManySessionPools,
+    InvalidSessionDirection,
+    SessionPoolNotAllowed,
+    SessionDirectionNotAllowed,
+    SessionTradeTooLarge,
+    SessionCapExceeded,
+    InvalidDutchOrder,
+    InvalidOrderMint,
+    DutchOrderNotActive => "The auction has not started or has ended",
+    CommitmentMismatch,
+    RevealOutsideWindow => "Reveal within the commitment's slot window",
+    FlowInvariantViolated,
+    InvalidGlobalFlags,
+    SlippageNotSet => "Call set_slippage first",
+    WrongVaultOrder => "Pass the input mint's pool vault as input_vault",
+    OpenNotApproved => "Have the owner co-sign or call approve_open_on_behalf",
+    TooManyConfigs => "Split the configs across several calls",
+    NotPoolCreator,
+    NothingToSweep,
+    ExposureCapExceeded => "Deposit less, or raise the cap with set_exposure_caps",
+    ExposureUnpriced => "Pick a pool that trades the exposure quote mint",
+    PoolExposureRequired => "Pass the pool_exposure PDA; init_pool_exposure creates it",
+    InvalidExposureCaps,
+    ActivationTooSoon => "Schedule at least MIN_PARAM_CHANGE_NOTICE_SLOTS ahead",
+    ParamChangeNotActive => "Crank apply_param_change once the activation slot passes",
+    TickArrayMissing => "Pass every initialized tick array the band touches",
+    ProgramPaused => "Governance paused the program; check get_status before retrying",
+    FeaturePaused => "Governance paused this feature; get_status shows which",
+    InvalidCreatorFeeVault => "Pass the pool's creator fee vault in the fee mint; init_creator_fee_vault creates it",
+    NoCreatorFees,
+    RoundTripLimitExceeded => "Wait for the round-trip window to pass before reversing again",
+    RoundTripCounterRequired => "Create the round_trip_counter PDA with init_round_trip_counter first",
+    InvalidFloorOrder,
+    FloorOrderLimitExceeded => "Fill at most the escrow and the day's remaining allowance",
+    SessionDelegateNotApproved => "Have the owner approve the session authority, see approve_session_delegate",
+    UserConfigMigrationRequired => "Call migrate_user_config",
+    InsufficientComputeBudget => "Raise the compute unit limit, see ComputeBudget",
+    InvalidFeeOracleSettings,
+    FeeOracleStale => "Pay the fee in the input mint until the oracle pool trades again",
+    FeeOraclePriceDeviation => "Retry once the oracle pool's price settles",
+    RegistryPageNotLast => "Pass the registry's last page, adding one once it is full",
+    InvalidSessionSpendLimits,
+    SessionMintNotAllowed => "Add the input mint to the session's spend limits with set_session_limits",
+);
+
+/*
+ * Remedies for Raydium CLMM errors callers can fix, by name
+ */
+fn raydium_remedy(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "PriceSlippageCheck" | "TooLittleOutputReceived" | "TooMuchInputPaid" => {
+            "The price moved since the quote: quote again, or raise the slippage tolerance"
+        }
+        "NotEnoughTickArrayAccount" | "InvalidFirstTickArrayAccount" => {
+            "Pass the tick arrays the swap crosses, starting at the current price"
+        }
+        "MissingTickArrayBitmapExtensionAccount" => "Pass the pool's tick array bitmap extension",
+        "LiquidityInsufficient" | "InsufficientLiquidityForDirection" => {
+            "The pool lacks the liquidity: swap less or set a price limit"
+        }
+        "SqrtPriceLimitOverflow" => "The price limit must lie on the side the swap moves the price",
+        "TransactionTooOld" => "Send the transaction again with a new deadline",
+        _ => return None,
+    })
+}
+
+/*
+ * Explain an instruction's custom error from the transaction's logs. None
+ * unless the transaction failed with a custom program error. Without logs
+ * the error is read as this program's
+ */
+pub fn explain_error<S: AsRef<str>>(
+    error: &TransactionError,
+    logs: &[S],
+) -> Option<ExplainedError> {
+    let TransactionError::InstructionError(instruction_index, InstructionError::Custom(code)) =
+        error
+    else {
+        return None;
+    };
+    let code = *code;
+
+    let program_id = logs
+        .iter()
+        .find_map(|log| failed_program(log.as_ref(), code))
+        .or(logs.is_empty().then_some(raydium_integration::ID));
+    let anchor_error = logs.iter().find_map(|log| anchor_error(log.as_ref(), code));
+
+    let mut explained = ExplainedError {
+        program_id,
+        instruction_index: *instruction_index,
+        code,
+        name: None,
+        message: None,
+        remedy: None,
+    };
+    if program_id == Some(raydium_integration::ID) {
+        if let Some((name, message, remedy)) = program_error(code) {
+            explained.name = Some(name);
+            explained.message = Some(message);
+            explained.remedy = remedy;
+        }
+    }
+    if let Some((name, message)) = anchor_error {
+        if program_id == Some(raydium_integration::RAYDIUM_CLMM_PROGRAM_ID) {
+            explained.remedy = raydium_remedy(&name);
+        }
+        explained.name.get_or_insert(name);
+        explained.message.get_or_insert(message);
+    }
+    Some(explained)
+}
+
+/*
+ * Explain a client error that carries a failed transaction: a failed
+ * simulation or preflight, or a transaction that landed and failed
+ */
+pub fn explain_client_error(error: &ClientError) -> Option<ExplainedError> {
+    match error {
+        ClientError::Simulation { error, logs } => explain_error(error, logs),
+        ClientError::Failed { error, .. } => explain_error::<&str>(error, &[]),
+        ClientError::Rpc(error) => match error.kind() {
+            solana_client::client_error::ErrorKind::RpcError(RpcError::RpcResponseError {
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(simulation),
+                ..
+            }) => explain_error(
+                simulation.err.as_ref()?,
+                simulation.logs.as_deref().unwrap_or_default(),
+            ),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/*
+ * The program a `Program <id> failed: custom program error: 0x..` line
+ * names, when it reports `code`
+ */
+fn failed_program(log: &str, code: u32) -> Option<Pubkey> {
+    let rest = log.strip_prefix("Program ")?;
+    let (program_id, rest) = rest.split_once(' ')?;
+    let hex = rest.strip_prefix("failed: custom program error: 0x")?;
+    if u32::from_str_radix(hex.trim(), 16).ok()? != code {
+        return None;
+    }
+    program_id.parse().ok()
+}
+
+/*
+ * Name and message from an Anchor error log line reporting `code`
+ */
+fn anchor_error(log: &str, code: u32) -> Option<(String, String)> {
+    let (_, rest) = log.split_once("AnchorError")?;
+    let (_, rest) = rest.split_once("Error Code: ")?;
+    let (name, rest) = rest.split_once(". Error Number: ")?;
+    let (number, message) = rest.split_once(". Error Message: ")?;
+    if number.parse::<u32>().ok()? != code {
+        return None;
+    }
+    Some((name.to_string(), message.trim_end_matches('.').to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_this_programs_error_from_its_code() {
+        let code = u32::from(CustomError::SlippageExceeded);
+        let logs = [
+            format!("Program {} invoke [1]", raydium_integration::ID),
+            "Program log: AnchorError thrown in programs/raydium-integration/src/instructions/swap.rs:487. Error Code: SlippageExceeded. Error Number: 6018. Error Message: Realized price is worse than the slippage threshold.".to_string(),
+            format!(
+                "Program {} failed: custom program error: {code:#x}",
+                raydium_integration::ID
+            ),
+        ];
+        let error = TransactionError::InstructionError(2, InstructionError::Custom(code));
+        let explained = explain_error(&error, &logs).unwrap();
+        assert_eq!(explained.program_id, Some(raydium_integration::ID));
+        assert_eq!(explained.instruction_index, 2);
+        assert_eq!(explained.name.as_deref(), Some("SlippageExceeded"));
+        assert!(explained.remedy.unwrap().contains("quote again"));
+    }
+
+    #[test]
+    fn attributes_the_error_to_the_program_that_raised_it() {
+        let clmm = raydium_integration::RAYDIUM_CLMM_PROGRAM_ID;
+        let logs = [
+            format!("Program {} invoke [1]", raydium_integration::ID),
+            format!("Program {clmm} invoke [2]"),
+            "Program log: AnchorError occurred. Error Code: NotEnoughTickArrayAccount. Error Number: 6028. Error Message: Not enough tick array account.".to_string(),
+            format!("Program {clmm} failed: custom program error: 0x178c"),
+            format!(
+                "Program {} failed: custom program error: 0x178c",
+                raydium_integration::ID
+            ),
+        ];
+        let error = TransactionError::InstructionError(0, InstructionError::Custom(6028));
+        let explained = explain_error(&error, &logs).unwrap();
+        assert_eq!(explained.program_id, Some(clmm));
+        assert_eq!(explained.name.as_deref(), Some("NotEnoughTickArrayAccount"));
+        assert_eq!(
+            explained.message.as_deref(),
+            Some("Not enough tick array account")
+        );
+        assert!(explained.remedy.is_some());
+    }
+}
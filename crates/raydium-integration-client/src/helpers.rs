@@ -0,0 +1,236 @@
+//! Addresses and tick array indices of the accounts proxy instructions take.
+//!
+//! Seeds match the program's account constraints and Raydium's own, so an
+//! address derived here is the one the instruction checks against. The
+//! proxy's other PDAs are in [`raydium_integration::pda`].
+use anchor_lang::prelude::*;
+use raydium_amm_v3::states::{
+    POOL_TICK_ARRAY_BITMAP_SEED, POSITION_SEED, TICK_ARRAY_SEED, TICK_ARRAY_SIZE,
+};
+use raydium_integration::RAYDIUM_CLMM_PROGRAM_ID;
+
+pub use raydium_integration::math::tick_array_start_index;
+pub use raydium_integration::pda::user_config_address;
+
+/*
+ * Start indices of the tick arrays holding a position's lower and upper
+ * ticks, the `tick_array_*_start_index` arguments of opens
+ */
+pub fn tick_array_start_indices(
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_spacing: u16,
+) -> (i32, i32) {
+    (
+        tick_array_start_index(tick_lower_index, tick_spacing),
+        tick_array_start_index(tick_upper_index, tick_spacing),
+    )
+}
+
+/*
+ * Ticks one tick array spans at a tick spacing
+ */
+pub fn ticks_per_tick_array(tick_spacing: u16) -> i32 {
+    TICK_ARRAY_SIZE * tick_spacing as i32
+}
+
+pub fn tick_array_address(pool: &Pubkey, start_index: i32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            TICK_ARRAY_SEED.as_bytes(),
+            pool.as_ref(),
+            &start_index.to_be_bytes(),
+        ],
+        &RAYDIUM_CLMM_PROGRAM_ID,
+    )
+}
+
+/*
+ * Tick arrays holding a position's lower and upper ticks
+ */
+pub fn position_tick_array_addresses(
+    pool: &Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_spacing: u16,
+) -> (Pubkey, Pubkey) {
+    let (lower_start_index, upper_start_index) =
+        tick_array_start_indices(tick_lower_index, tick_upper_index, tick_spacing);
+    (
+        tick_array_address(pool, lower_start_index).0,
+        tick_array_address(pool, upper_start_index).0,
+    )
+}
+
+/// Raydium's per-range position, shared by every position on the same ticks
+pub fn protocol_position_address(
+    pool: &Pubkey,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            POSITION_SEED.as_bytes(),
+            pool.as_ref(),
+            &tick_lower_index.to_be_bytes(),
+            &tick_upper_index.to_be_bytes(),
+        ],
+        &RAYDIUM_CLMM_PROGRAM_ID,
+    )
+}
+
+pub fn personal_position_address(position_nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POSITION_SEED.as_bytes(), position_nft_mint.as_ref()],
+        &RAYDIUM_CLMM_PROGRAM_ID,
+    )
+}
+
+pub fn tick_array_bitmap_extension_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool.as_ref()],
+        &RAYDIUM_CLMM_PROGRAM_ID,
+    )
+}
+
+/// Metaplex metadata of a position NFT
+pub fn metadata_address(position_nft_mint: &Pubkey) -> (Pubkey, u8) {
+    let metadata_program = anchor_spl::metadata::ID;
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            metadata_program.as_ref(),
+            position_nft_mint.as_ref(),
+        ],
+        &metadata_program,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use raydium_amm_v3::states::TickArrayState;
+    use raydium_integration::math::{full_range_ticks, MAX_TICK, MIN_TICK};
+
+    const TICK_SPACINGS: [u16; 6] = [1, 8, 10, 60, 120, 200];
+
+    #[test]
+    fn start_indices_match_raydium() {
+        for tick_spacing in TICK_SPACINGS {
+            let (min_tick, max_tick) = full_range_ticks(tick_spacing);
+            let step = (ticks_per_tick_array(tick_spacing) / 7).max(1) as usize;
+            for tick in (min_tick..=max_tick).step_by(step).chain([-1, 0, 1]) {
+                assert_eq!(
+                    tick_array_start_index(tick, tick_spacing),
+                    TickArrayState::get_array_start_index(tick, tick_spacing),
+                    "tick {tick}, spacing {tick_spacing}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn start_index_round_trips() {
+        for tick_spacing in TICK_SPACINGS {
+            let span = ticks_per_tick_array(tick_spacing);
+            for tick in [
+                MIN_TICK,
+                -span - 1,
+                -span,
+                -1,
+                0,
+                1,
+                span - 1,
+                span,
+                MAX_TICK,
+            ] {
+                let start_index = tick_array_start_index(tick, tick_spacing);
+                assert_eq!(start_index % span, 0);
+                assert!(start_index <= tick && tick < start_index + span);
+                // Every tick of an array maps back to its start
+                assert_eq!(
+                    tick_array_start_index(start_index, tick_spacing),
+                    start_index
+                );
+                assert_eq!(
+                    tick_array_start_index(start_index + span - 1, tick_spacing),
+                    start_index
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn start_indices_of_a_range() {
+        assert_eq!(tick_array_start_indices(-1, 1, 10), (-600, 0));
+        assert_eq!(tick_array_start_indices(0, 599, 10), (0, 0));
+        assert_eq!(tick_array_start_indices(-601, 600, 10), (-1200, 600));
+    }
+
+    #[test]
+    fn program_addresses_recreate_from_their_bumps() {
+        let pool = Pubkey::new_unique();
+        let nft_mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let recreate = |seeds: &[&[u8]], bump: u8, program_id: &Pubkey| {
+            let mut seeds = seeds.to_vec();
+            let bump = [bump];
+            seeds.push(&bump);
+            Pubkey::create_program_address(&seeds, program_id).unwrap()
+        };
+
+        let (address, bump) = user_config_address(&owner);
+        assert_eq!(
+            recreate(
+                &[b"user_cfg", owner.as_ref()],
+                bump,
+                &raydium_integration::ID
+            ),
+            address
+        );
+
+        let start_index = tick_array_start_index(-601, 10);
+        let (address, bump) = tick_array_address(&pool, start_index);
+        assert_eq!(
+            recreate(
+                &[b"tick_array", pool.as_ref(), &start_index.to_be_bytes()],
+                bump,
+                &RAYDIUM_CLMM_PROGRAM_ID
+            ),
+            address
+        );
+
+        let (address, bump) = protocol_position_address(&pool, -600, 600);
+        assert_eq!(
+            recreate(
+                &[
+                    b"position",
+                    pool.as_ref(),
+                    &(-600i32).to_be_bytes(),
+                    &600i32.to_be_bytes()
+                ],
+                bump,
+                &RAYDIUM_CLMM_PROGRAM_ID
+            ),
+            address
+        );
+
+        let (address, bump) = personal_position_address(&nft_mint);
+        assert_eq!(
+            recreate(
+                &[b"position", nft_mint.as_ref()],
+                bump,
+                &RAYDIUM_CLMM_PROGRAM_ID
+            ),
+            address
+        );
+    }
+
+    #[test]
+    fn position_tick_arrays_follow_start_indices() {
+        let pool = Pubkey::new_unique();
+        let (lower, upper) = position_tick_array_addresses(&pool, -601, 600, 10);
+        assert_eq!(lower, tick_array_address(&pool, -1200).0);
+        assert_eq!(upper, tick_array_address(&pool, 600).0);
+    }
+}
@@ -0,0 +1,139 @@
+//! Jito block-engine bundles.
+//!
+//! With a [`JitoConfig`] on the `SendConfig`, [`crate::submit`] sends each
+//! transaction to a Jito block engine as a bundle instead of through the RPC
+//! node: the transaction, then a second one tipping a Jito tip account.
+//! Bundles land whole or not at all and skip the public mempool, so the tip
+//! is only paid when the transaction lands and nobody sees the swap before
+//! it does.
+use anchor_lang::prelude::Pubkey;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::system_instruction::transfer;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::{ClientError, Result};
+
+/// Mainnet block engine; regional ones are listed in Jito's docs
+pub const MAINNET_BLOCK_ENGINE_URL: &str = "https://mainnet.block-engine.jito.wtf";
+
+#[derive(Clone, Debug)]
+pub struct JitoConfig {
+    pub block_engine_url: String,
+    /// Lamports paid to the tip account per bundle that lands
+    pub tip_lamports: u64,
+}
+
+impl JitoConfig {
+    pub fn mainnet(tip_lamports: u64) -> Self {
+        Self {
+            block_engine_url: MAINNET_BLOCK_ENGINE_URL.to_string(),
+            tip_lamports,
+        }
+    }
+}
+
+/// JSON-RPC client of a block engine's bundle API
+#[derive(Clone, Debug)]
+pub struct JitoClient {
+    http: reqwest::Client,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct Response<T> {
+    result: Option<T>,
+    error: Option<Value>,
+}
+
+impl JitoClient {
+    pub fn new(block_engine_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: format!("{}/api/v1/bundles", block_engine_url.trim_end_matches('/')),
+        }
+    }
+
+    /*
+     * Accounts the block engine takes tips at
+     */
+    pub async fn tip_accounts(&self) -> Result<Vec<Pubkey>> {
+        let accounts: Vec<String> = self.call("getTipAccounts", json!([])).await?;
+        accounts
+            .iter()
+            .map(|account| {
+                account
+                    .parse()
+                    .map_err(|_| ClientError::Jito(format!("invalid tip account {account}")))
+            })
+            .collect()
+    }
+
+    /*
+     * Submit `transactions` as one bundle, in order, returning its id
+     */
+    pub async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String> {
+        let encoded = transactions
+            .iter()
+            .map(|transaction| {
+                bincode::serialize(transaction)
+                    .map(|bytes| BASE64.encode(bytes))
+                    .map_err(|error| ClientError::Jito(error.to_string()))
+            })
+            .collect::<Result<Vec<String>>>()?;
+        self.call("sendBundle", json!([encoded, { "encoding": "base64" }]))
+            .await
+    }
+
+    async fn call<T: serde::de::DeserializeOwned>(&self, method: &str, params: Value) -> Result<T> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Response<T> = self
+            .http
+            .post(&self.url)
+            .json(&request)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|error| ClientError::Jito(error.to_string()))?
+            .json()
+            .await
+            .map_err(|error| ClientError::Jito(error.to_string()))?;
+        match (response.result, response.error) {
+            (Some(result), None) => Ok(result),
+            (_, Some(error)) => Err(ClientError::Jito(format!("{method}: {error}"))),
+            (None, None) => Err(ClientError::Jito(format!("{method}: empty response"))),
+        }
+    }
+}
+
+/*
+ * A transaction from `payer` tipping `tip_account`, to follow the
+ * transaction it pays for in a bundle
+ */
+pub fn tip_transaction(
+    payer: &dyn Signer,
+    tip_account: &Pubkey,
+    tip_lamports: u64,
+    blockhash: Hash,
+) -> Result<VersionedTransaction> {
+    let instruction = transfer(&payer.pubkey(), tip_account, tip_lamports);
+    let message = v0::Message::try_compile(&payer.pubkey(), &[instruction], &[], blockhash)?;
+    Ok(VersionedTransaction::try_new(
+        VersionedMessage::V0(message),
+        &[payer],
+    )?)
+}
+
+/*
+ * One of the tip accounts, varied by `signature` to spread tips, and the
+ * write locks on them, across the accounts
+ */
+pub fn pick_tip_account(tip_accounts: &[Pubkey], signature: &Signature) -> Option<Pubkey> {
+    let index = *signature.as_ref().first()? as usize % tip_accounts.len().max(1);
+    tip_accounts.get(index).copied()
+}
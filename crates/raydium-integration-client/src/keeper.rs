@@ -0,0 +1,393 @@
+//! Reference keeper for the program's automation orders.
+//!
+//! Dutch and floor orders are the orders the program lets anyone execute, so
+//! they are what the keeper cranks. Each round it lists the open orders with
+//! `getProgramAccounts` and quotes each against its pool. A Dutch order in its
+//! window is filled in full when the pool pays the current auction price. A
+//! floor order sells what is left of the day's allowance and its escrow,
+//! halved a few times if the pool cannot take it all at the floor price. The
+//! program pays keepers no tip, so fills go in order of surplus: how far the
+//! quoted output clears the order's minimum, the fills least likely to fail
+//! by the time they land. Every fill is simulated before it is sent. Given
+//! [`Metrics`], the keeper counts its quotes and fills, the slippage of each
+//! fill against its quote, crank latency and RPC errors.
+//!
+//! Commit-reveal swaps are not cranked: a reveal needs the committer's
+//! signature and secret salt, so only the committer can send it.
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{pda, DutchOrder, FloorOrder};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::accounts::{fetch_dutch_orders, fetch_floor_orders, fetch_swap_validators};
+use crate::builders::{FillDutchOrderBuilder, FillFloorOrderBuilder};
+use crate::events::{parse_transaction, ProgramEvent};
+use crate::metrics::Metrics;
+use crate::quote::{Quote, SwapQuoter};
+use crate::{ClientError, PoolKeys, Result};
+
+/// Tick arrays a fill quote loads ahead of the price
+const FILL_TICK_ARRAYS: usize = 5;
+
+/// Times a floor order's fill is halved looking for a size the pool takes
+/// at the floor price
+const FLOOR_FILL_HALVINGS: u32 = 4;
+
+/// The keeper's `daemon` label in metrics
+const DAEMON: &str = "keeper";
+
+#[derive(Clone, Debug)]
+pub struct KeeperConfig {
+    /// Wait between rounds
+    pub poll_interval: Duration,
+    /// Fills sent per round at most, highest surplus first
+    pub max_fills_per_round: usize,
+    /// Sends of one fill before giving up on it for the round, at least one
+    pub max_attempts: u32,
+    /// How long an order stays skipped after a fill of it was sent, so a
+    /// fill still landing is not sent twice
+    pub in_flight_ttl: Duration,
+}
+
+impl Default for KeeperConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(5),
+            max_fills_per_round: 8,
+            max_attempts: 3,
+            in_flight_ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+/// An order the keeper cranks
+#[derive(Clone, Debug)]
+pub enum KeeperOrder {
+    Dutch(DutchOrder),
+    Floor(FloorOrder),
+}
+
+impl KeeperOrder {
+    pub fn pool(&self) -> Pubkey {
+        match self {
+            Self::Dutch(order) => order.pool,
+            Self::Floor(order) => order.pool,
+        }
+    }
+
+    pub fn input_mint(&self) -> Pubkey {
+        match self {
+            Self::Dutch(order) => order.input_mint,
+            Self::Floor(order) => order.input_mint,
+        }
+    }
+}
+
+/// An order the pool can fill now
+#[derive(Clone, Debug)]
+pub struct FillableOrder {
+    pub address: Pubkey,
+    pub order: KeeperOrder,
+    /// Input the fill sells: all of a Dutch order, part of a floor order
+    pub amount_in: u64,
+    /// The order's minimum output for `amount_in` at the time it was quoted
+    pub min_out: u64,
+    pub quote: Quote,
+}
+
+impl FillableOrder {
+    /// Quoted output above the minimum
+    pub fn surplus(&self) -> u64 {
+        self.quote.amount_out - self.min_out
+    }
+}
+
+pub struct Keeper {
+    rpc: RpcClient,
+    keeper: Keypair,
+    config: KeeperConfig,
+    /// Orders a fill was sent for, and when
+    in_flight: HashMap<Pubkey, Instant>,
+    metrics: Option<Metrics>,
+}
+
+impl Keeper {
+    pub fn new(rpc: RpcClient, keeper: Keypair, config: KeeperConfig) -> Self {
+        Self {
+            rpc,
+            keeper,
+            config,
+            in_flight: HashMap::new(),
+            metrics: None,
+        }
+    }
+
+    /// Record the keeper's activity in `metrics`
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /*
+     * Run rounds until the process stops. A failed round is logged and the
+     * next one starts on schedule
+     */
+    pub async fn run(&mut self) -> Result<()> {
+        loop {
+            match self.run_round().await {
+                Ok(signatures) if !signatures.is_empty() => {
+                    log::info!("filled {} orders", signatures.len());
+                }
+                Ok(_) => {}
+                Err(error) => {
+                    log::error!("keeper round failed: {error}");
+                    if let Some(metrics) = &self.metrics {
+                        metrics.round_failed();
+                        metrics.error(DAEMON, &error);
+                    }
+                }
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    /*
+     * One round: find the fillable orders and fill the best of them,
+     * returning the signatures of the fills that landed
+     */
+    pub async fn run_round(&mut self) -> Result<Vec<Signature>> {
+        let ttl = self.config.in_flight_ttl;
+        self.in_flight.retain(|_, sent| sent.elapsed() < ttl);
+
+        let mut fillable = self.fillable_orders().await?;
+        fillable.retain(|fill| !self.in_flight.contains_key(&fill.address));
+        fillable.sort_by_key(|fill| std::cmp::Reverse(fill.surplus()));
+        fillable.truncate(self.config.max_fills_per_round);
+
+        let swap_validators = fetch_swap_validators(&self.rpc).await?;
+        let mut pools: HashMap<Pubkey, PoolKeys> = HashMap::new();
+        let mut signatures = Vec::new();
+        for fill in fillable {
+            let pool_address = fill.order.pool();
+            if !pools.contains_key(&pool_address) {
+                let pool = PoolKeys::fetch(&self.rpc, &pool_address).await?;
+                pools.insert(pool_address, pool);
+            }
+            let pool = &pools[&pool_address];
+            let keeper = self.keeper.pubkey();
+            let tick_arrays = fill.quote.tick_arrays.clone();
+            let instruction = match &fill.order {
+                KeeperOrder::Dutch(order) => {
+                    FillDutchOrderBuilder::new(pool, &keeper, &fill.address, order)?
+                        .tick_arrays(tick_arrays)
+                        .swap_validators(swap_validators.clone())
+                        .instruction()
+                }
+                KeeperOrder::Floor(order) => {
+                    FillFloorOrderBuilder::new(pool, &keeper, &fill.address, order, fill.amount_in)?
+                        .tick_arrays(tick_arrays)
+                        .swap_validators(swap_validators.clone())
+                        .instruction()
+                }
+            };
+            match self.send(&fill.address, instruction).await {
+                Ok(Some(signature)) => {
+                    self.record_fill(&signature, fill.quote.amount_out).await;
+                    signatures.push(signature);
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    log::warn!("order {}: {error}", fill.address);
+                    if let Some(metrics) = &self.metrics {
+                        metrics.error(DAEMON, &error);
+                    }
+                }
+            }
+        }
+        Ok(signatures)
+    }
+
+    /*
+     * Active orders whose pool covers their minimum output, quoted at the
+     * chain's latest block time: Dutch orders in full, floor orders for the
+     * largest of their available input and its halvings that clears the
+     * floor
+     */
+    pub async fn fillable_orders(&self) -> Result<Vec<FillableOrder>> {
+        let now = self.rpc.get_block_time(self.rpc.get_slot().await?).await?;
+        let mut quoters: HashMap<(Pubkey, Pubkey), SwapQuoter> = HashMap::new();
+        let mut fillable = Vec::new();
+        for (address, order) in fetch_dutch_orders(&self.rpc).await? {
+            if now < order.start_ts || now > order.end_ts {
+                continue;
+            }
+            let quoter = self
+                .quoter(&mut quoters, &order.pool, &order.input_mint)
+                .await?;
+            let quote = quoter.quote_exact_in(order.amount_in, 0)?;
+            if let Some(metrics) = &self.metrics {
+                metrics.quote_served(DAEMON);
+            }
+            let min_out = order.min_out(now);
+            if quote.fully_filled && quote.amount_out >= min_out {
+                fillable.push(FillableOrder {
+                    address,
+                    amount_in: order.amount_in,
+                    order: KeeperOrder::Dutch(order),
+                    min_out,
+                    quote,
+                });
+            }
+        }
+        for (address, order) in fetch_floor_orders(&self.rpc).await? {
+            let escrow = pda::floor_escrow_address(&address).0;
+            let escrowed = self
+                .rpc
+                .get_token_account_balance(&escrow)
+                .await?
+                .amount
+                .parse::<u64>()
+                .map_err(|_| ClientError::InvalidAccountData(escrow))?;
+            let mut amount_in = order.clone().available_today(now).min(escrowed);
+            if amount_in == 0 {
+                continue;
+            }
+            let quoter = self
+                .quoter(&mut quoters, &order.pool, &order.input_mint)
+                .await?;
+            for _ in 0..=FLOOR_FILL_HALVINGS {
+                let quote = quoter.quote_exact_in(amount_in, 0)?;
+                if let Some(metrics) = &self.metrics {
+                    metrics.quote_served(DAEMON);
+                }
+                let min_out = order.min_out(amount_in);
+                if quote.fully_filled && quote.amount_out >= min_out {
+                    fillable.push(FillableOrder {
+                        address,
+                        order: KeeperOrder::Floor(order),
+                        amount_in,
+                        min_out,
+                        quote,
+                    });
+                    break;
+                }
+                amount_in /= 2;
+                if amount_in == 0 {
+                    break;
+                }
+            }
+        }
+        Ok(fillable)
+    }
+
+    /*
+     * Quoter for swaps of `input_mint` on `pool`, fetched once per round
+     */
+    async fn quoter<'q>(
+        &self,
+        quoters: &'q mut HashMap<(Pubkey, Pubkey), SwapQuoter>,
+        pool: &Pubkey,
+        input_mint: &Pubkey,
+    ) -> Result<&'q SwapQuoter> {
+        let key = (*pool, *input_mint);
+        if !quoters.contains_key(&key) {
+            let pool = PoolKeys::fetch(&self.rpc, pool).await?;
+            let quoter = SwapQuoter::fetch(&self.rpc, &pool, input_mint, FILL_TICK_ARRAYS).await?;
+            quoters.insert(key, quoter);
+        }
+        Ok(&quoters[&key])
+    }
+
+    /*
+     * Simulate the fill, then send it until it confirms or the attempts run
+     * out. None when simulation shows it would fail, most often because
+     * another keeper filled the order first
+     */
+    async fn send(
+        &mut self,
+        order: &Pubkey,
+        instruction: Instruction,
+    ) -> Result<Option<Signature>> {
+        let payer = self.keeper.pubkey();
+        let started = Instant::now();
+        let mut attempt = 1;
+        loop {
+            let blockhash = self.rpc.get_latest_blockhash().await?;
+            let transaction = Transaction::new_signed_with_payer(
+                &[instruction.clone()],
+                Some(&payer),
+                &[&self.keeper],
+                blockhash,
+            );
+            let simulation = self.rpc.simulate_transaction(&transaction).await?.value;
+            if let Some(error) = simulation.err {
+                log::debug!("order {order}: simulation failed: {error}");
+                return Ok(None);
+            }
+
+            self.in_flight.insert(*order, Instant::now());
+            match self.rpc.send_and_confirm_transaction(&transaction).await {
+                Ok(signature) => {
+                    if let Some(metrics) = &self.metrics {
+                        metrics.crank_latency(started.elapsed());
+                    }
+                    return Ok(Some(signature));
+                }
+                Err(error) if attempt < self.config.max_attempts => {
+                    log::warn!("order {order}: attempt {attempt} failed: {error}");
+                    attempt += 1;
+                }
+                Err(error) => return Err(error.into()),
+            }
+        }
+    }
+
+    /*
+     * Record a landed fill in the metrics, with the output its
+     * `DutchOrderFilled` or `FloorOrderFilled` event reports against
+     * `quoted_amount_out`. Reading the transaction back is best effort
+     */
+    async fn record_fill(&self, signature: &Signature, quoted_amount_out: u64) {
+        let Some(metrics) = &self.metrics else {
+            return;
+        };
+        // Transactions are only served at confirmed or finalized commitment
+        let commitment = match self.rpc.commitment() {
+            commitment if commitment.is_at_least_confirmed() => commitment,
+            _ => CommitmentConfig::confirmed(),
+        };
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(commitment),
+            max_supported_transaction_version: Some(0),
+        };
+        let transaction = match self
+            .rpc
+            .get_transaction_with_config(signature, config)
+            .await
+        {
+            Ok(transaction) => transaction,
+            Err(error) => {
+                log::debug!("fill {signature}: {error}");
+                metrics.error(DAEMON, &error.into());
+                return;
+            }
+        };
+        for event in parse_transaction(&transaction) {
+            let amount_out = match event {
+                ProgramEvent::DutchOrderFilled(filled) => filled.amount_out,
+                ProgramEvent::FloorOrderFilled(filled) => filled.amount_out,
+                _ => continue,
+            };
+            metrics.swap_executed(DAEMON, quoted_amount_out, amount_out);
+        }
+    }
+}
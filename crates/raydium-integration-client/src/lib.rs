@@ -0,0 +1,58 @@
+//! Off-chain client for the `raydium-integration` program.
+//!
+//! [`PoolKeys`] and [`PositionKeys`] load what instructions need about a
+//! Raydium pool or position over RPC; the [`builders`] turn them into proxy
+//! instructions, deriving every PDA and ordering the accounts as the program
+//! expects; [`helpers`] derives the same addresses on their own,
+//! [`price`] converts wallet prices to ticks and sqrt price limits,
+//! [`quote`] prices swaps without a round trip to the chain,
+//! [`aggregator`] sets those prices beside Jupiter's and [`depth`] buckets a
+//! pool's liquidity by price for depth charts. [`events`] decodes the
+//! program's events from logs and inner instructions, and [`explain`] turns
+//! failed transactions into named errors with remedies.
+//! [`RaydiumIntegrationClient`] ties them together into operations that are
+//! fetched, built, sent and confirmed in one call, as v0 transactions
+//! against the [`lookup_tables`] it keeps for their pools and under the
+//! [`compute_budget`] it sets for them, with swaps checked by a
+//! [`preflight`] simulation and every transaction landed by [`submit`],
+//! through the RPC node or as a [`jito`] bundle. [`monitor`] watches
+//! positions and alerts when they leave their range; [`keeper`] fills Dutch
+//! orders; [`stream`] keeps pool accounts current in memory for both, and
+//! [`metrics`] exports what they do to Prometheus. An [`rpc_pool`] spreads
+//! any of them over several RPC endpoints, and [`backtest`] replays a
+//! pool's swaps offline against range and DCA strategies with the
+//! program's own math. [`accounting`] exports a wallet's history with cost
+//! basis and realized PnL as CSV or JSON, and [`portfolio`] sums what many
+//! wallets hold through the proxy. Enable `event-cpi` or `devnet` to
+//! match the deployed program, and `yellowstone` to stream from a Geyser
+//! endpoint.
+pub mod accounting;
+pub mod accounts;
+pub mod aggregator;
+pub mod backtest;
+pub mod builders;
+mod client;
+pub mod compute_budget;
+pub mod depth;
+mod error;
+pub mod events;
+pub mod explain;
+pub mod helpers;
+pub mod jito;
+pub mod keeper;
+pub mod lookup_tables;
+pub mod metrics;
+pub mod monitor;
+pub mod portfolio;
+pub mod preflight;
+pub mod price;
+pub mod quote;
+pub mod rpc_pool;
+pub mod stream;
+pub mod submit;
+
+pub use accounts::{PoolKeys, PositionKeys};
+pub use client::*;
+pub use error::*;
+
+pub use raydium_integration as program;
@@ -0,0 +1,186 @@
+//! Address lookup tables for v0 transactions.
+//!
+//! Opens and multi-hop swaps take more accounts than a legacy transaction
+//! fits. The accounts every call on a pool repeats, [`static_accounts`], go
+//! into lookup tables the payer owns; [`LookupTables`] creates and extends
+//! them as new pools come up and keeps them for reuse across calls.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::sysvar;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::address_lookup_table::instruction::{create_lookup_table, extend_lookup_table};
+use solana_sdk::address_lookup_table::state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES};
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+
+use crate::{ClientError, PoolKeys, Result};
+
+/// Addresses per extension, small enough for a legacy transaction
+const EXTEND_CHUNK: usize = 20;
+
+/*
+ * Accounts every proxy call on the pool passes: programs, sysvars, the
+ * pool's own accounts and the proxy's global PDAs
+ */
+pub fn static_accounts(pool: &PoolKeys) -> Vec<Pubkey> {
+    let mut accounts = vec![
+        raydium_integration::ID,
+        raydium_integration::RAYDIUM_CLMM_PROGRAM_ID,
+        raydium_integration::SPL_MEMO_PROGRAM_ID,
+        anchor_spl::token::ID,
+        anchor_spl::token_2022::ID,
+        anchor_spl::associated_token::ID,
+        anchor_lang::system_program::ID,
+        sysvar::rent::ID,
+        sysvar::instructions::ID,
+        raydium_integration::pda::swap_validators_address().0,
+        raydium_integration::pda::event_authority_address().0,
+        pool.address,
+        pool.amm_config,
+        pool.token_mint_0,
+        pool.token_mint_1,
+        pool.token_vault_0,
+        pool.token_vault_1,
+        pool.observation_key,
+    ];
+    accounts.extend(pool.tick_array_bitmap_extension);
+    accounts
+}
+
+/// Lookup tables transactions are compiled against
+#[derive(Clone, Debug, Default)]
+pub struct LookupTables {
+    tables: Vec<AddressLookupTableAccount>,
+}
+
+impl LookupTables {
+    /*
+     * Reuse existing tables, such as those a previous run created
+     */
+    pub async fn load(rpc: &RpcClient, table_addresses: &[Pubkey]) -> Result<Self> {
+        let mut tables = Vec::with_capacity(table_addresses.len());
+        for address in table_addresses {
+            tables.push(fetch_table(rpc, address).await?);
+        }
+        Ok(Self { tables })
+    }
+
+    pub fn tables(&self) -> &[AddressLookupTableAccount] {
+        &self.tables
+    }
+
+    /// Addresses of the tables, to `load` them again later
+    pub fn table_addresses(&self) -> Vec<Pubkey> {
+        self.tables.iter().map(|table| table.key).collect()
+    }
+
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.tables
+            .iter()
+            .any(|table| table.addresses.contains(address))
+    }
+
+    /*
+     * Put every address not yet in a table into one, extending the last
+     * table or creating a new one when it is full. `authority` owns new
+     * tables and pays for them. Returns once the new entries can be used
+     */
+    pub async fn extend(
+        &mut self,
+        rpc: &RpcClient,
+        authority: &dyn Signer,
+        addresses: &[Pubkey],
+    ) -> Result<()> {
+        let mut missing: Vec<Pubkey> = addresses
+            .iter()
+            .filter(|address| !self.contains(address))
+            .copied()
+            .collect();
+        missing.sort();
+        missing.dedup();
+
+        let mut last_extended_slot = None;
+        while !missing.is_empty() {
+            let table = match self
+                .tables
+                .iter()
+                .rposition(|table| table.addresses.len() < LOOKUP_TABLE_MAX_ADDRESSES)
+            {
+                Some(index) => index,
+                None => {
+                    self.tables.push(create_table(rpc, authority).await?);
+                    self.tables.len() - 1
+                }
+            };
+            let room = LOOKUP_TABLE_MAX_ADDRESSES - self.tables[table].addresses.len();
+            let chunk: Vec<Pubkey> = missing
+                .drain(..missing.len().min(room).min(EXTEND_CHUNK))
+                .collect();
+            let instruction = extend_lookup_table(
+                self.tables[table].key,
+                authority.pubkey(),
+                Some(authority.pubkey()),
+                chunk.clone(),
+            );
+            send(rpc, authority, &instruction).await?;
+            last_extended_slot = Some(rpc.get_slot().await?);
+            self.tables[table].addresses.extend(chunk);
+        }
+
+        // Entries only resolve from the slot after the one that added them
+        if let Some(extended_slot) = last_extended_slot {
+            while rpc.get_slot().await? <= extended_slot {
+                tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn create_table(
+    rpc: &RpcClient,
+    authority: &dyn Signer,
+) -> Result<AddressLookupTableAccount> {
+    // The slot a table is derived from must be a recent finalized one
+    let recent_slot = rpc
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .await?;
+    let (instruction, address) =
+        create_lookup_table(authority.pubkey(), authority.pubkey(), recent_slot);
+    send(rpc, authority, &instruction).await?;
+    Ok(AddressLookupTableAccount {
+        key: address,
+        addresses: Vec::new(),
+    })
+}
+
+async fn fetch_table(rpc: &RpcClient, address: &Pubkey) -> Result<AddressLookupTableAccount> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    let table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|_| ClientError::InvalidAccountData(*address))?;
+    Ok(AddressLookupTableAccount {
+        key: *address,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+async fn send(
+    rpc: &RpcClient,
+    authority: &dyn Signer,
+    instruction: &anchor_lang::solana_program::instruction::Instruction,
+) -> Result<()> {
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        std::slice::from_ref(instruction),
+        Some(&authority.pubkey()),
+        &[authority],
+        blockhash,
+    );
+    rpc.send_and_confirm_transaction(&transaction).await?;
+    Ok(())
+}
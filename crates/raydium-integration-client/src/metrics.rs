@@ -0,0 +1,225 @@
+//! Prometheus metrics for the keeper and monitor.
+//!
+//! One [`Metrics`] is shared by the daemons of a process, each given a clone
+//! with `with_metrics`, and [`Metrics::serve`] exposes them in the text
+//! format on `/metrics`. The counters cover quotes served, swaps executed,
+//! realized slippage against the quote, crank latency, range alerts and
+//! RPC errors, labelled by the daemon that saw them.
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts, Registry,
+    TextEncoder,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::{ClientError, Result};
+
+/// Buckets of realized slippage, in basis points below the quote
+const SLIPPAGE_BUCKETS_BPS: &[f64] = &[
+    -50.0, -10.0, 0.0, 1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0,
+];
+
+/// Buckets of crank latency, in seconds
+const LATENCY_BUCKETS_SECS: &[f64] = &[0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 15.0, 30.0, 60.0, 120.0];
+
+/// The daemons' metrics. Clones share the same series
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    quotes_served: IntCounterVec,
+    swaps_executed: IntCounterVec,
+    slippage_realized_bps: HistogramVec,
+    crank_latency: Histogram,
+    range_alerts: IntCounterVec,
+    rpc_errors: IntCounterVec,
+    rounds_failed: IntCounter,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let quotes_served = IntCounterVec::new(
+            Opts::new("raydium_quotes_served_total", "Swap quotes computed"),
+            &["daemon"],
+        )
+        .unwrap();
+        let swaps_executed = IntCounterVec::new(
+            Opts::new("raydium_swaps_executed_total", "Swaps that landed"),
+            &["daemon"],
+        )
+        .unwrap();
+        let slippage_realized_bps = HistogramVec::new(
+            HistogramOpts::new(
+                "raydium_slippage_realized_bps",
+                "Output short of the quote, in basis points; negative when it beat the quote",
+            )
+            .buckets(SLIPPAGE_BUCKETS_BPS.to_vec()),
+            &["daemon"],
+        )
+        .unwrap();
+        let crank_latency = Histogram::with_opts(
+            HistogramOpts::new(
+                "raydium_crank_latency_seconds",
+                "Time from simulating a keeper fill to its confirmation",
+            )
+            .buckets(LATENCY_BUCKETS_SECS.to_vec()),
+        )
+        .unwrap();
+        let range_alerts = IntCounterVec::new(
+            Opts::new(
+                "raydium_range_alerts_total",
+                "Positions leaving or re-entering their range",
+            ),
+            &["in_range"],
+        )
+        .unwrap();
+        let rpc_errors = IntCounterVec::new(
+            Opts::new(
+                "raydium_rpc_errors_total",
+                "Failed RPC and subscription requests",
+            ),
+            &["daemon"],
+        )
+        .unwrap();
+        let rounds_failed = IntCounter::new(
+            "raydium_keeper_rounds_failed_total",
+            "Keeper rounds that failed",
+        )
+        .unwrap();
+
+        registry.register(Box::new(quotes_served.clone())).unwrap();
+        registry.register(Box::new(swaps_executed.clone())).unwrap();
+        registry
+            .register(Box::new(slippage_realized_bps.clone()))
+            .unwrap();
+        registry.register(Box::new(crank_latency.clone())).unwrap();
+        registry.register(Box::new(range_alerts.clone())).unwrap();
+        registry.register(Box::new(rpc_errors.clone())).unwrap();
+        registry.register(Box::new(rounds_failed.clone())).unwrap();
+        Self {
+            registry,
+            quotes_served,
+            swaps_executed,
+            slippage_realized_bps,
+            crank_latency,
+            range_alerts,
+            rpc_errors,
+            rounds_failed,
+        }
+    }
+
+    pub fn quote_served(&self, daemon: &str) {
+        self.quotes_served.with_label_values(&[daemon]).inc();
+    }
+
+    /*
+     * A swap that landed, paying `amount_out` against a quote of
+     * `quoted_amount_out`
+     */
+    pub fn swap_executed(&self, daemon: &str, quoted_amount_out: u64, amount_out: u64) {
+        self.swaps_executed.with_label_values(&[daemon]).inc();
+        if quoted_amount_out > 0 {
+            let shortfall = quoted_amount_out as f64 - amount_out as f64;
+            self.slippage_realized_bps
+                .with_label_values(&[daemon])
+                .observe(shortfall * 10_000.0 / quoted_amount_out as f64);
+        }
+    }
+
+    pub fn crank_latency(&self, latency: Duration) {
+        self.crank_latency.observe(latency.as_secs_f64());
+    }
+
+    pub fn range_alert(&self, in_range: bool) {
+        let label = if in_range { "true" } else { "false" };
+        self.range_alerts.with_label_values(&[label]).inc();
+    }
+
+    pub fn round_failed(&self) {
+        self.rounds_failed.inc();
+    }
+
+    /*
+     * Count `error` against `daemon` when it is an RPC or subscription
+     * failure
+     */
+    pub fn error(&self, daemon: &str, error: &ClientError) {
+        if matches!(error, ClientError::Rpc(_) | ClientError::Subscription(_)) {
+            self.rpc_errors.with_label_values(&[daemon]).inc();
+        }
+    }
+
+    /// Every series in the Prometheus text format
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("metrics encode to text");
+        String::from_utf8(buffer).expect("the text format is UTF-8")
+    }
+
+    /*
+     * Answer `GET /metrics` on `addr` until the process stops. Any other
+     * path gets a 404
+     */
+    pub async fn serve(&self, addr: SocketAddr) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        log::info!("serving metrics on http://{addr}/metrics");
+        loop {
+            let (mut stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(error) => {
+                    log::warn!("metrics connection failed: {error}");
+                    continue;
+                }
+            };
+            let metrics = self.clone();
+            tokio::spawn(async move {
+                let mut request = [0; 1024];
+                let read = stream.read(&mut request).await.unwrap_or_default();
+                let request = String::from_utf8_lossy(&request[..read]);
+                let mut request_line = request.split_whitespace();
+                let (status, body) = match (request_line.next(), request_line.next()) {
+                    (Some("GET"), Some("/metrics")) => ("200 OK", metrics.render()),
+                    _ => ("404 Not Found", String::new()),
+                };
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+                     Connection: close\r\n\r\n{body}",
+                    TextEncoder::new().format_type(),
+                    body.len()
+                );
+                if let Err(error) = stream.write_all(response.as_bytes()).await {
+                    log::debug!("metrics response to {peer}: {error}");
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_recorded_series() {
+        let metrics = Metrics::new();
+        metrics.quote_served("keeper");
+        metrics.swap_executed("keeper", 1_000_000, 995_000);
+        metrics.range_alert(false);
+        let text = metrics.render();
+        assert!(text.contains(r#"raydium_quotes_served_total{daemon="keeper"} 1"#));
+        assert!(text.contains(r#"raydium_swaps_executed_total{daemon="keeper"} 1"#));
+        assert!(text.contains(r#"raydium_slippage_realized_bps_sum{daemon="keeper"} 50"#));
+        assert!(text.contains(r#"raydium_range_alerts_total{in_range="false"} 1"#));
+    }
+}
@@ -0,0 +1,258 @@
+//! Watching positions move in and out of range.
+//!
+//! [`PositionMonitor`] subscribes to the pools of a set of positions over
+//! websocket and, each time a pool's price takes a position across one of
+//! its bounds, sends a [`RangeAlert`] with the fees the position has accrued
+//! to every [`AlertSink`]. Given [`Metrics`], it counts alerts and RPC
+//! errors.
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use futures::stream::{select_all, StreamExt};
+use raydium_amm_v3::states::{PersonalPositionState, PoolState, TickArrayState};
+use raydium_integration::uncollected_fees;
+use serde::Serialize;
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcAccountInfoConfig;
+
+use crate::accounts::{decode_zero_copy, fetch_account, fetch_zero_copy};
+use crate::helpers::{personal_position_address, position_tick_array_addresses};
+use crate::metrics::Metrics;
+use crate::{ClientError, Result};
+
+/// Wait before resubscribing after the websocket drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// The monitor's `daemon` label in metrics
+const DAEMON: &str = "monitor";
+
+/// Where alerts go
+#[derive(Clone, Debug)]
+pub enum AlertSink {
+    /// A `log::warn!` line with the alert as JSON
+    Log,
+    /// A JSON POST of the alert to the URL
+    Webhook(String),
+}
+
+/// A position crossed one of its bounds
+#[derive(Clone, Debug, Serialize)]
+pub struct RangeAlert {
+    #[serde(serialize_with = "pubkey_string")]
+    pub position_nft: Pubkey,
+    #[serde(serialize_with = "pubkey_string")]
+    pub pool: Pubkey,
+    /// False when the price just left the range, true when it came back
+    pub in_range: bool,
+    pub tick_current: i32,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub uncollected_fee_0: u64,
+    pub uncollected_fee_1: u64,
+}
+
+fn pubkey_string<S: serde::Serializer>(
+    key: &Pubkey,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(key)
+}
+
+/// A watched position and whether it was in range at the last update
+struct Watched {
+    nft_mint: Pubkey,
+    tick_lower: i32,
+    tick_upper: i32,
+    in_range: Option<bool>,
+}
+
+pub struct PositionMonitor {
+    rpc: RpcClient,
+    ws_url: String,
+    sinks: Vec<AlertSink>,
+    http: reqwest::Client,
+    /// Watched positions by pool
+    pools: HashMap<Pubkey, Vec<Watched>>,
+    metrics: Option<Metrics>,
+}
+
+impl PositionMonitor {
+    /*
+     * Monitor the positions behind `nft_mints`, reading accounts through
+     * `rpc` and subscribing through `ws_url`
+     */
+    pub async fn new(
+        rpc: RpcClient,
+        ws_url: String,
+        nft_mints: &[Pubkey],
+        sinks: Vec<AlertSink>,
+    ) -> Result<Self> {
+        let mut pools: HashMap<Pubkey, Vec<Watched>> = HashMap::new();
+        for nft_mint in nft_mints {
+            let position = fetch_position(&rpc, nft_mint).await?;
+            pools.entry(position.pool_id).or_default().push(Watched {
+                nft_mint: *nft_mint,
+                tick_lower: position.tick_lower_index,
+                tick_upper: position.tick_upper_index,
+                in_range: None,
+            });
+        }
+        Ok(Self {
+            rpc,
+            ws_url,
+            sinks,
+            http: reqwest::Client::new(),
+            pools,
+            metrics: None,
+        })
+    }
+
+    /// Record alerts and errors in `metrics`
+    pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /*
+     * Follow the pools until the process stops, resubscribing whenever the
+     * websocket drops. Only errors connecting fail the monitor; an update
+     * that can't be handled is logged and skipped
+     */
+    pub async fn run(&mut self) -> Result<()> {
+        loop {
+            let pubsub = PubsubClient::new(&self.ws_url)
+                .await
+                .map_err(|error| ClientError::Subscription(Box::new(error)))?;
+            let config = RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                commitment: Some(self.rpc.commitment()),
+                ..RpcAccountInfoConfig::default()
+            };
+            let mut streams = Vec::with_capacity(self.pools.len());
+            for pool in self.pools.keys().copied().collect::<Vec<_>>() {
+                let (stream, _unsubscribe) = pubsub
+                    .account_subscribe(&pool, Some(config.clone()))
+                    .await
+                    .map_err(|error| ClientError::Subscription(Box::new(error)))?;
+                streams.push(stream.map(move |update| (pool, update.value)));
+                // Catch up on what changed while unsubscribed
+                if let Err(error) = self.refresh(&pool).await {
+                    self.failed(&pool, &error);
+                }
+            }
+
+            let mut updates = select_all(streams);
+            while let Some((pool, account)) = updates.next().await {
+                let result = match account.data.decode() {
+                    Some(data) => match decode_zero_copy::<PoolState>(&pool, &data) {
+                        Ok(pool_state) => self.update(&pool, &pool_state).await,
+                        Err(error) => Err(error),
+                    },
+                    None => Err(ClientError::InvalidAccountData(pool)),
+                };
+                if let Err(error) = result {
+                    self.failed(&pool, &error);
+                }
+            }
+            log::warn!("pool subscriptions dropped, reconnecting");
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    fn failed(&self, pool: &Pubkey, error: &ClientError) {
+        log::error!("pool {pool}: {error}");
+        if let Some(metrics) = &self.metrics {
+            metrics.error(DAEMON, error);
+        }
+    }
+
+    async fn refresh(&mut self, pool: &Pubkey) -> Result<()> {
+        let pool_state: PoolState = fetch_zero_copy(&self.rpc, pool).await?;
+        self.update(pool, &pool_state).await
+    }
+
+    /*
+     * Alert on every position of the pool whose side of its range changed.
+     * The first update only records where each position stands, unless it
+     * starts out of range
+     */
+    async fn update(&mut self, pool: &Pubkey, pool_state: &PoolState) -> Result<()> {
+        let tick_current = pool_state.tick_current;
+        let mut alerts = Vec::new();
+        for watched in self.pools.get_mut(pool).into_iter().flatten() {
+            let in_range = watched.tick_lower <= tick_current && tick_current < watched.tick_upper;
+            let previous = watched.in_range.replace(in_range);
+            if previous != Some(in_range) && (previous.is_some() || !in_range) {
+                alerts.push((watched.nft_mint, in_range));
+            }
+        }
+        for (nft_mint, in_range) in alerts {
+            let alert = self
+                .range_alert(pool, pool_state, &nft_mint, in_range)
+                .await?;
+            self.send(&alert).await;
+        }
+        Ok(())
+    }
+
+    async fn range_alert(
+        &self,
+        pool: &Pubkey,
+        pool_state: &PoolState,
+        nft_mint: &Pubkey,
+        in_range: bool,
+    ) -> Result<RangeAlert> {
+        let position = fetch_position(&self.rpc, nft_mint).await?;
+        let (lower, upper) = position_tick_array_addresses(
+            pool,
+            position.tick_lower_index,
+            position.tick_upper_index,
+            pool_state.tick_spacing,
+        );
+        let tick_array_lower: TickArrayState = fetch_zero_copy(&self.rpc, &lower).await?;
+        let tick_array_upper: TickArrayState = fetch_zero_copy(&self.rpc, &upper).await?;
+        let (uncollected_fee_0, uncollected_fee_1) =
+            uncollected_fees(pool_state, &position, &tick_array_lower, &tick_array_upper)?;
+        Ok(RangeAlert {
+            position_nft: *nft_mint,
+            pool: *pool,
+            in_range,
+            tick_current: pool_state.tick_current,
+            tick_lower: position.tick_lower_index,
+            tick_upper: position.tick_upper_index,
+            uncollected_fee_0,
+            uncollected_fee_1,
+        })
+    }
+
+    /*
+     * Deliver an alert to every sink. A failing webhook is logged and does
+     * not hold back the others
+     */
+    async fn send(&self, alert: &RangeAlert) {
+        if let Some(metrics) = &self.metrics {
+            metrics.range_alert(alert.in_range);
+        }
+        for sink in &self.sinks {
+            match sink {
+                AlertSink::Log => match serde_json::to_string(alert) {
+                    Ok(json) => log::warn!("{json}"),
+                    Err(error) => log::error!("alert for {}: {error}", alert.position_nft),
+                },
+                AlertSink::Webhook(url) => {
+                    let response = self.http.post(url).json(alert).send().await;
+                    if let Err(error) = response.and_then(|response| response.error_for_status()) {
+                        log::error!("webhook {url}: {error}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn fetch_position(rpc: &RpcClient, nft_mint: &Pubkey) -> Result<PersonalPositionState> {
+    fetch_account(rpc, &personal_position_address(nft_mint).0).await
+}
@@ -0,0 +1,306 @@
+//! Holdings of many wallets through the proxy, in one view.
+//!
+//! [`fetch_portfolio`] loads, for each wallet, the positions in its position
+//! registry, its open Dutch orders and pending commit-reveal swap, its shares
+//! in shared positions, its user config with the statistics it keeps and its
+//! points ledger. [`Portfolio`] sums them across the wallets, for treasury
+//! teams that trade and provide liquidity from many keys.
+use std::collections::{BTreeMap, HashSet};
+
+use anchor_lang::prelude::*;
+use raydium_amm_v3::states::PersonalPositionState;
+use raydium_integration::{
+    pda, DutchOrder, PointsLedger, SharedPositionShare, SwapCommitment, UserConfig,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+
+use crate::accounts::{
+    decode_zero_copy, fetch_dutch_orders, fetch_registered_positions, fetch_shared_position_shares,
+};
+use crate::helpers::personal_position_address;
+use crate::{ClientError, PositionKeys, Result};
+
+/// Accounts one `getMultipleAccounts` call returns at most
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// One wallet's holdings through the proxy
+#[derive(Clone, Debug)]
+pub struct WalletPortfolio {
+    pub wallet: Pubkey,
+    /// Positions in the wallet's position registry that are still open
+    pub positions: Vec<PositionKeys>,
+    /// Dutch orders the wallet placed and nobody has filled or cancelled
+    pub dutch_orders: Vec<(Pubkey, DutchOrder)>,
+    /// A commit-reveal swap committed and not yet revealed
+    pub swap_commitment: Option<SwapCommitment>,
+    /// Shares in shared positions the wallet contributed to
+    pub shares: Vec<(Pubkey, SharedPositionShare)>,
+    /// The wallet's config and the statistics it keeps; `None` without one,
+    /// or for a config from before the statistics fields
+    pub user_config: Option<UserConfig>,
+    pub points_ledger: Option<PointsLedger>,
+}
+
+/// Sums across the wallets of a portfolio
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PortfolioTotals {
+    pub positions: usize,
+    /// Position liquidity by pool
+    pub liquidity_by_pool: BTreeMap<Pubkey, u128>,
+    pub dutch_orders: usize,
+    /// Input escrowed by open Dutch orders, by mint
+    pub escrowed_by_mint: BTreeMap<Pubkey, u64>,
+    /// Shares held by shared position
+    pub shares_by_shared_position: BTreeMap<Pubkey, u128>,
+    pub total_swaps: u64,
+    /// AMM trade fees paid, raw units summed across pools and mints
+    pub fees_paid: u128,
+    pub positions_opened: u64,
+    /// Points earned and not yet claimed
+    pub unclaimed_points: u128,
+}
+
+/// Holdings of a set of wallets
+#[derive(Clone, Debug)]
+pub struct Portfolio {
+    pub wallets: Vec<WalletPortfolio>,
+    pub totals: PortfolioTotals,
+}
+
+impl Portfolio {
+    pub fn new(wallets: Vec<WalletPortfolio>) -> Self {
+        let mut totals = PortfolioTotals::default();
+        for wallet in &wallets {
+            totals.positions += wallet.positions.len();
+            for position in &wallet.positions {
+                let liquidity = totals.liquidity_by_pool.entry(position.pool).or_default();
+                *liquidity = liquidity.saturating_add(position.liquidity);
+            }
+            totals.dutch_orders += wallet.dutch_orders.len();
+            for (_, order) in &wallet.dutch_orders {
+                let escrowed = totals.escrowed_by_mint.entry(order.input_mint).or_default();
+                *escrowed = escrowed.saturating_add(order.amount_in);
+            }
+            for (_, share) in &wallet.shares {
+                let shares = totals
+                    .shares_by_shared_position
+                    .entry(share.shared_position)
+                    .or_default();
+                *shares = shares.saturating_add(share.shares);
+            }
+            if let Some(user_config) = &wallet.user_config {
+                totals.total_swaps = totals.total_swaps.saturating_add(user_config.total_swaps);
+                totals.fees_paid = totals.fees_paid.saturating_add(user_config.fees_paid);
+                totals.positions_opened = totals
+                    .positions_opened
+                    .saturating_add(user_config.positions_opened);
+            }
+            if let Some(ledger) = &wallet.points_ledger {
+                let earned = ledger.swap_points.saturating_add(ledger.lp_points);
+                totals.unclaimed_points = totals
+                    .unclaimed_points
+                    .saturating_add(earned.saturating_sub(ledger.claimed));
+            }
+        }
+        Self { wallets, totals }
+    }
+
+    /// The portfolio of one wallet, if it is part of this one
+    pub fn wallet(&self, wallet: &Pubkey) -> Option<&WalletPortfolio> {
+        self.wallets
+            .iter()
+            .find(|portfolio| portfolio.wallet == *wallet)
+    }
+}
+
+/*
+ * Load the holdings of every wallet in `wallets` and sum them. Dutch orders
+ * come from one scan of the program's accounts; the rest is per wallet
+ */
+pub async fn fetch_portfolio(rpc: &RpcClient, wallets: &[Pubkey]) -> Result<Portfolio> {
+    let owners: HashSet<Pubkey> = wallets.iter().copied().collect();
+    let mut dutch_orders: Vec<(Pubkey, DutchOrder)> = fetch_dutch_orders(rpc)
+        .await?
+        .into_iter()
+        .filter(|(_, order)| owners.contains(&order.owner))
+        .collect();
+
+    // Config, points ledger and commitment of each wallet, in that order
+    let addresses: Vec<Pubkey> = wallets
+        .iter()
+        .flat_map(|wallet| {
+            [
+                pda::user_config_address(wallet).0,
+                pda::points_ledger_address(wallet).0,
+                pda::swap_commitment_address(wallet).0,
+            ]
+        })
+        .collect();
+    let accounts = fetch_multiple(rpc, &addresses).await?;
+
+    let mut portfolios = Vec::with_capacity(wallets.len());
+    for ((wallet, addresses), accounts) in wallets
+        .iter()
+        .zip(addresses.chunks(3))
+        .zip(accounts.chunks(3))
+    {
+        let user_config = accounts[0]
+            .as_ref()
+            .and_then(|account| decode_zero_copy::<UserConfig>(&addresses[0], &account.data).ok());
+        let points_ledger = decode_optional(&addresses[1], accounts[1].as_ref())?;
+        let swap_commitment = decode_optional(&addresses[2], accounts[2].as_ref())?;
+
+        let (wallet_orders, rest): (Vec<_>, Vec<_>) = dutch_orders
+            .into_iter()
+            .partition(|(_, order)| order.owner == *wallet);
+        dutch_orders = rest;
+
+        portfolios.push(WalletPortfolio {
+            wallet: *wallet,
+            positions: fetch_positions(rpc, wallet).await?,
+            dutch_orders: wallet_orders,
+            swap_commitment,
+            shares: fetch_shared_position_shares(rpc, wallet).await?,
+            user_config,
+            points_ledger,
+        });
+    }
+    Ok(Portfolio::new(portfolios))
+}
+
+/*
+ * Open positions in a wallet's registry. Positions closed since they were
+ * registered are left out
+ */
+async fn fetch_positions(rpc: &RpcClient, wallet: &Pubkey) -> Result<Vec<PositionKeys>> {
+    let nft_mints: Vec<Pubkey> = fetch_registered_positions(rpc, wallet)
+        .await?
+        .into_iter()
+        .map(|(_, nft_mint)| nft_mint)
+        .collect();
+    // Each position's state, then its NFT mint for the token program
+    let addresses: Vec<Pubkey> = nft_mints
+        .iter()
+        .flat_map(|nft_mint| [personal_position_address(nft_mint).0, *nft_mint])
+        .collect();
+    let accounts = fetch_multiple(rpc, &addresses).await?;
+
+    let mut positions = Vec::new();
+    for ((nft_mint, addresses), accounts) in nft_mints
+        .iter()
+        .zip(addresses.chunks(2))
+        .zip(accounts.chunks(2))
+    {
+        let (Some(position), Some(mint)) = (&accounts[0], &accounts[1]) else {
+            continue;
+        };
+        let state = PersonalPositionState::try_deserialize(&mut position.data.as_slice())
+            .map_err(|_| ClientError::InvalidAccountData(addresses[0]))?;
+        positions.push(PositionKeys {
+            nft_mint: *nft_mint,
+            nft_token_program: mint.owner,
+            personal_position: addresses[0],
+            pool: state.pool_id,
+            tick_lower_index: state.tick_lower_index,
+            tick_upper_index: state.tick_upper_index,
+            liquidity: state.liquidity,
+        });
+    }
+    Ok(positions)
+}
+
+async fn fetch_multiple(rpc: &RpcClient, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+        accounts.extend(rpc.get_multiple_accounts(chunk).await?);
+    }
+    Ok(accounts)
+}
+
+fn decode_optional<T: AccountDeserialize>(
+    address: &Pubkey,
+    account: Option<&Account>,
+) -> Result<Option<T>> {
+    account
+        .map(|account| {
+            T::try_deserialize(&mut account.data.as_slice())
+                .map_err(|_| ClientError::InvalidAccountData(*address))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(pool: Pubkey, liquidity: u128) -> PositionKeys {
+        PositionKeys {
+            nft_mint: Pubkey::new_unique(),
+            nft_token_program: anchor_spl::token::ID,
+            personal_position: Pubkey::new_unique(),
+            pool,
+            tick_lower_index: -600,
+            tick_upper_index: 600,
+            liquidity,
+        }
+    }
+
+    fn order(owner: Pubkey, input_mint: Pubkey, amount_in: u64) -> (Pubkey, DutchOrder) {
+        let order = DutchOrder {
+            owner,
+            order_id: 0,
+            pool: Pubkey::new_unique(),
+            input_mint,
+            destination: Pubkey::new_unique(),
+            amount_in,
+            start_min_out: 0,
+            end_min_out: 0,
+            start_ts: 0,
+            end_ts: 0,
+        };
+        (Pubkey::new_unique(), order)
+    }
+
+    fn wallet(wallet: Pubkey) -> WalletPortfolio {
+        WalletPortfolio {
+            wallet,
+            positions: Vec::new(),
+            dutch_orders: Vec::new(),
+            swap_commitment: None,
+            shares: Vec::new(),
+            user_config: None,
+            points_ledger: None,
+        }
+    }
+
+    #[test]
+    fn totals_sum_across_wallets() {
+        let (pool, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let mut first = wallet(alice);
+        first.positions = vec![position(pool, 100), position(Pubkey::new_unique(), 7)];
+        first.dutch_orders = vec![order(alice, mint, 1_000)];
+        first.points_ledger = Some(PointsLedger {
+            owner: alice,
+            swap_points: 50,
+            lp_points: 25,
+            claimed: 30,
+            last_update_ts: 0,
+        });
+        let mut second = wallet(bob);
+        second.positions = vec![position(pool, 200)];
+        second.dutch_orders = vec![order(bob, mint, 500), order(bob, pool, 1)];
+
+        let portfolio = Portfolio::new(vec![first, second]);
+        let totals = &portfolio.totals;
+        assert_eq!(totals.positions, 3);
+        assert_eq!(totals.liquidity_by_pool[&pool], 300);
+        assert_eq!(totals.dutch_orders, 3);
+        assert_eq!(totals.escrowed_by_mint[&mint], 1_500);
+        assert_eq!(totals.unclaimed_points, 45);
+        assert_eq!(portfolio.wallet(&bob).unwrap().positions.len(), 1);
+        assert!(portfolio.wallet(&Pubkey::new_unique()).is_none());
+    }
+}
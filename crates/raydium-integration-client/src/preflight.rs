@@ -0,0 +1,187 @@
+//! Simulating transactions before they are sent.
+//!
+//! [`simulate`] runs a transaction unsigned at the latest blockhash; the
+//! client simulates every transaction it sizes a compute budget for, and
+//! every swap. A [`SwapPreflight`] reads what the simulated swap paid out,
+//! from the program's `SwapFill` return data when it set any and otherwise
+//! from the output token account's balance, and fails before anything is
+//! sent when a stale quote would take more slippage than asked for.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+use anchor_spl::token::TokenAccount;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use raydium_integration::{compute_slippage_threshold, SwapFill};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{
+    RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::UiReturnDataEncoding;
+
+use crate::compute_budget::MAX_COMPUTE_UNIT_LIMIT;
+use crate::{ClientError, Result};
+
+/// The least a swap may pay into its output token account
+#[derive(Clone, Debug)]
+pub struct SwapPreflight {
+    pub output_token_account: Pubkey,
+    pub min_amount_out: u64,
+}
+
+impl SwapPreflight {
+    /*
+     * The quote's output less `slippage_bps` of it, rounded down like the
+     * program's own minimum output
+     */
+    pub fn new(output_token_account: Pubkey, quoted_amount_out: u64, slippage_bps: u16) -> Self {
+        Self {
+            output_token_account,
+            min_amount_out: compute_slippage_threshold(
+                quoted_amount_out,
+                slippage_bps.min(10_000),
+                true,
+            ),
+        }
+    }
+
+    /*
+     * The output token account's balance before the swap, zero until it
+     * exists
+     */
+    pub async fn balance_before(&self, rpc: &RpcClient) -> Result<u64> {
+        let account = rpc
+            .get_account_with_commitment(&self.output_token_account, rpc.commitment())
+            .await?
+            .value;
+        match account {
+            Some(account) => token_amount(&self.output_token_account, &account.data),
+            None => Ok(0),
+        }
+    }
+
+    /*
+     * What the simulated swap paid out, failing with
+     * `ClientError::PreflightSlippage` below the minimum. The simulation
+     * must have returned the output token account
+     */
+    pub fn check(
+        &self,
+        simulation: &RpcSimulateTransactionResult,
+        balance_before: u64,
+    ) -> Result<u64> {
+        let amount_out = match swap_fill(simulation) {
+            Some(fill) => fill.amount_out,
+            None => {
+                let data = simulation
+                    .accounts
+                    .as_ref()
+                    .and_then(|accounts| accounts.first()?.as_ref())
+                    .and_then(|account| account.data.decode())
+                    .ok_or(ClientError::AccountNotFound(self.output_token_account))?;
+                token_amount(&self.output_token_account, &data)?.saturating_sub(balance_before)
+            }
+        };
+        if amount_out < self.min_amount_out {
+            return Err(ClientError::PreflightSlippage {
+                amount_out,
+                min_amount_out: self.min_amount_out,
+            });
+        }
+        Ok(amount_out)
+    }
+}
+
+/*
+ * Simulate `instructions` as a v0 transaction from `payer` under the
+ * maximum compute unit limit and `heap_frame_bytes` of heap, if set,
+ * without signatures and at the latest blockhash, returning the state of
+ * `accounts` after it. A transaction error comes back as
+ * `ClientError::Simulation`
+ */
+pub async fn simulate(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    accounts: &[Pubkey],
+    heap_frame_bytes: Option<u32>,
+) -> Result<RpcSimulateTransactionResult> {
+    let mut simulated = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        MAX_COMPUTE_UNIT_LIMIT,
+    )];
+    simulated.extend(heap_frame_bytes.map(ComputeBudgetInstruction::request_heap_frame));
+    simulated.extend_from_slice(instructions);
+    let message = v0::Message::try_compile(payer, &simulated, lookup_tables, Hash::default())?;
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header.num_required_signatures as usize],
+        message: VersionedMessage::V0(message),
+    };
+    let result = rpc
+        .simulate_transaction_with_config(
+            &transaction,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(rpc.commitment()),
+                accounts: (!accounts.is_empty()).then(|| RpcSimulateTransactionAccountsConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    addresses: accounts.iter().map(Pubkey::to_string).collect(),
+                }),
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )
+        .await?
+        .value;
+    match result.err {
+        Some(error) => Err(ClientError::Simulation {
+            error,
+            logs: result.logs.unwrap_or_default(),
+        }),
+        None => Ok(result),
+    }
+}
+
+/*
+ * The `SwapFill` the program returned, which it only sets when the swap
+ * could fill partially
+ */
+fn swap_fill(simulation: &RpcSimulateTransactionResult) -> Option<SwapFill> {
+    let return_data = simulation.return_data.as_ref()?;
+    if return_data.program_id != raydium_integration::ID.to_string() {
+        return None;
+    }
+    let (data, UiReturnDataEncoding::Base64) = &return_data.data;
+    let data = BASE64.decode(data).ok()?;
+    SwapFill::deserialize(&mut data.as_slice()).ok()
+}
+
+fn token_amount(address: &Pubkey, data: &[u8]) -> Result<u64> {
+    TokenAccount::try_deserialize(&mut &data[..])
+        .map(|account| account.amount)
+        .map_err(|_| ClientError::InvalidAccountData(*address))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minimum_takes_the_slippage_off_the_quote() {
+        let output = Pubkey::new_unique();
+        assert_eq!(
+            SwapPreflight::new(output, 1_000_000, 50).min_amount_out,
+            995_000
+        );
+        assert_eq!(SwapPreflight::new(output, 999, 0).min_amount_out, 999);
+        assert_eq!(SwapPreflight::new(output, 1_000, 10_000).min_amount_out, 0);
+    }
+}
@@ -0,0 +1,166 @@
+//! Human prices in and out of ticks and sqrt prices.
+//!
+//! Prices here are what a wallet shows: token_1 per token_0 in whole tokens,
+//! both mints' decimals applied. [`PoolKeys`] converts them to the ticks a
+//! position opens at, widened outward to the pool's tick spacing, and to the
+//! Q64.64 sqrt price limits swaps take, so callers never handle Q64.64 math
+//! themselves. The conversions are `raydium_integration::math`'s.
+use raydium_integration::math::{
+    full_range_ticks, price_to_sqrt_price, price_to_tick, sqrt_price_to_price, tick_to_price,
+    MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
+};
+
+use crate::{ClientError, PoolKeys, Result};
+
+fn check_price(price: f64) -> Result<f64> {
+    if price.is_finite() && price > 0.0 {
+        Ok(price)
+    } else {
+        Err(ClientError::InvalidPrice(price))
+    }
+}
+
+/*
+ * Usable ticks bounding `price_lower` and `price_upper`, widened outward to
+ * the tick spacing and clamped to the full range
+ */
+pub fn price_range_ticks(
+    price_lower: f64,
+    price_upper: f64,
+    decimals_0: u8,
+    decimals_1: u8,
+    tick_spacing: u16,
+) -> Result<(i32, i32)> {
+    if check_price(price_lower)? >= check_price(price_upper)? {
+        return Err(ClientError::InvalidPriceRange {
+            price_lower,
+            price_upper,
+        });
+    }
+    let spacing = tick_spacing as i32;
+    let (min_tick, max_tick) = full_range_ticks(tick_spacing);
+    let tick_lower = price_to_tick(price_lower, decimals_0, decimals_1);
+    // The greatest tick at or below the upper price, moved up unless it is
+    // the price exactly
+    let mut tick_upper = price_to_tick(price_upper, decimals_0, decimals_1);
+    if tick_to_price(tick_upper, decimals_0, decimals_1) < price_upper {
+        tick_upper += 1;
+    }
+    let tick_lower = (tick_lower.div_euclid(spacing) * spacing).clamp(min_tick, max_tick - spacing);
+    let tick_upper = ((tick_upper + spacing - 1).div_euclid(spacing) * spacing)
+        .clamp(tick_lower + spacing, max_tick);
+    Ok((tick_lower, tick_upper))
+}
+
+/*
+ * Q64.64 sqrt price limit of a human price, kept inside the range Raydium
+ * accepts for a limit
+ */
+pub fn price_to_sqrt_price_limit(price: f64, decimals_0: u8, decimals_1: u8) -> Result<u128> {
+    let sqrt_price_x64 = price_to_sqrt_price(check_price(price)?, decimals_0, decimals_1);
+    Ok(sqrt_price_x64.clamp(MIN_SQRT_PRICE_X64 + 1, MAX_SQRT_PRICE_X64 - 1))
+}
+
+impl PoolKeys {
+    /// The pool's price when it was loaded
+    pub fn price(&self) -> f64 {
+        sqrt_price_to_price(
+            self.sqrt_price_x64,
+            self.mint_decimals_0,
+            self.mint_decimals_1,
+        )
+    }
+
+    /// Price at a tick of this pool
+    pub fn tick_price(&self, tick: i32) -> f64 {
+        tick_to_price(tick, self.mint_decimals_0, self.mint_decimals_1)
+    }
+
+    /*
+     * Position bounds covering `price_lower` to `price_upper`, for
+     * `PositionRange::Ticks`
+     */
+    pub fn price_range_ticks(&self, price_lower: f64, price_upper: f64) -> Result<(i32, i32)> {
+        price_range_ticks(
+            price_lower,
+            price_upper,
+            self.mint_decimals_0,
+            self.mint_decimals_1,
+            self.tick_spacing,
+        )
+    }
+
+    /*
+     * Sqrt price limit stopping a swap at `price`, for
+     * `ProxySwapBuilder::sqrt_price_limit_x64`
+     */
+    pub fn sqrt_price_limit(&self, price: f64) -> Result<u128> {
+        price_to_sqrt_price_limit(price, self.mint_decimals_0, self.mint_decimals_1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A SOL/USDC-like pair: 9 and 6 decimals
+    const DECIMALS: (u8, u8) = (9, 6);
+
+    #[test]
+    fn ticks_round_trip_through_human_prices() {
+        let (decimals_0, decimals_1) = DECIMALS;
+        let tick = price_to_tick(150.0, decimals_0, decimals_1);
+        assert!(tick_to_price(tick, decimals_0, decimals_1) <= 150.0);
+        assert!(tick_to_price(tick + 1, decimals_0, decimals_1) > 150.0);
+        // Three more decimals on token_0 make the raw price a thousand times lower
+        assert_eq!(tick, price_to_tick(150.0e-3, 0, 0));
+    }
+
+    #[test]
+    fn ranges_widen_outward_to_the_tick_spacing() {
+        let (decimals_0, decimals_1) = DECIMALS;
+        let (tick_lower, tick_upper) =
+            price_range_ticks(140.0, 160.0, decimals_0, decimals_1, 60).unwrap();
+        assert_eq!(tick_lower % 60, 0);
+        assert_eq!(tick_upper % 60, 0);
+        assert!(tick_to_price(tick_lower, decimals_0, decimals_1) <= 140.0);
+        assert!(tick_to_price(tick_upper, decimals_0, decimals_1) >= 160.0);
+        assert!(tick_to_price(tick_lower + 60, decimals_0, decimals_1) > 140.0);
+        assert!(tick_to_price(tick_upper - 60, decimals_0, decimals_1) < 160.0);
+
+        // Prices closer than a tick spacing still get a range
+        let (tick_lower, tick_upper) =
+            price_range_ticks(150.0, 150.001, decimals_0, decimals_1, 60).unwrap();
+        assert_eq!(tick_upper - tick_lower, 60);
+    }
+
+    #[test]
+    fn rejects_prices_wallets_cannot_mean() {
+        for (lower, upper) in [(0.0, 1.0), (-1.0, 1.0), (1.0, f64::NAN)] {
+            assert!(matches!(
+                price_range_ticks(lower, upper, 9, 6, 60),
+                Err(ClientError::InvalidPrice(_))
+            ));
+        }
+        assert!(matches!(
+            price_range_ticks(160.0, 140.0, 9, 6, 60),
+            Err(ClientError::InvalidPriceRange { .. })
+        ));
+        assert!(price_to_sqrt_price_limit(f64::INFINITY, 9, 6).is_err());
+    }
+
+    #[test]
+    fn price_limits_stay_in_raydium_bounds() {
+        assert_eq!(
+            price_to_sqrt_price_limit(1e-30, 0, 0).unwrap(),
+            MIN_SQRT_PRICE_X64 + 1
+        );
+        assert_eq!(
+            price_to_sqrt_price_limit(1e30, 0, 0).unwrap(),
+            MAX_SQRT_PRICE_X64 - 1
+        );
+        let limit = price_to_sqrt_price_limit(150.0, 9, 6).unwrap();
+        let price = sqrt_price_to_price(limit, 9, 6);
+        assert!((price - 150.0).abs() < 1e-6, "{price}");
+    }
+}
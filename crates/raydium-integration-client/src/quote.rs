@@ -0,0 +1,622 @@
+//! Off-chain swap quotes.
+//!
+//! [`SwapQuoter`] loads a pool's price, liquidity and fee rate with the
+//! initialized tick arrays one swap direction walks, then replays Raydium's
+//! swap loop over them with Raydium's own swap step math. Quotes
+//! cover Raydium's trade fee; the proxy's protocol fee, charged only when a
+//! swap passes the global config, is not included. [`discover_swap_accounts`]
+//! loads more tick arrays until the quote walks no further, and returns the
+//! exact ones the swap needs.
+use anchor_lang::prelude::*;
+use raydium_amm_v3::states::{AmmConfig, PoolState, TickArrayBitmapExtension, TickArrayState};
+use raydium_integration::math::{
+    compute_swap_step, convert_at_sqrt_price, sqrt_price_at_tick, tick_at_sqrt_price,
+    MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64,
+};
+use raydium_integration::CustomError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+
+use crate::accounts::{decode_zero_copy, fetch_zero_copy};
+use crate::helpers::{tick_array_address, ticks_per_tick_array};
+use crate::{ClientError, PoolKeys, Result};
+
+const ONE_IN_BASIS_POINTS: u128 = 10_000;
+
+/// Tick arrays either side of tick 0 a pool's own bitmap tracks; the bitmap
+/// extension tracks those past them
+const DEFAULT_BITMAP_TICK_ARRAYS: i32 = 512;
+
+/// Tick arrays `discover_swap_accounts` loads at first
+const DISCOVERY_TICK_ARRAYS: usize = 4;
+
+/// Most tick arrays `discover_swap_accounts` loads for one swap, about as
+/// many as fit in a transaction beside the swap's other accounts
+pub const MAX_SWAP_TICK_ARRAYS: usize = 16;
+
+/// Outcome of a quoted swap
+#[derive(Clone, Debug)]
+pub struct Quote {
+    /// Input spent, trade fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Raydium trade fee, in the input token
+    pub fee: u64,
+    pub sqrt_price_after_x64: u128,
+    pub tick_after: i32,
+    /// Shortfall of `amount_out` against converting the input net of fee at
+    /// the pre-swap price, in basis points
+    pub price_impact_bps: u32,
+    /// Tick arrays the swap walks, in order; pass them to
+    /// `ProxySwapBuilder::tick_arrays`
+    pub tick_arrays: Vec<Pubkey>,
+    /// False when the price limit, or the end of the loaded tick arrays,
+    /// stops the swap short of the requested amount
+    pub fully_filled: bool,
+}
+
+/// Accounts a swap walks beyond those every swap passes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapAccounts {
+    /// Tick arrays in walk order; pass them to `ProxySwapBuilder::swap_accounts`
+    pub tick_arrays: Vec<Pubkey>,
+    /// The pool's bitmap extension, when the swap reaches past the tick
+    /// arrays the pool's own bitmap tracks
+    pub tick_array_bitmap_extension: Option<Pubkey>,
+    /// False when the swap runs past the loaded tick arrays into an
+    /// initialized one that wasn't loaded
+    pub complete: bool,
+}
+
+/// A pool's swap state in one direction, as of when it was loaded
+#[derive(Clone)]
+pub struct SwapQuoter {
+    zero_for_one: bool,
+    tick_spacing: u16,
+    sqrt_price_x64: u128,
+    tick_current: i32,
+    liquidity: u128,
+    trade_fee_rate: u32,
+    /// Initialized tick arrays in walk order, starting with the one Raydium
+    /// starts from
+    tick_arrays: Vec<(Pubkey, TickArrayState)>,
+    /// Next initialized tick array past the loaded ones, if any
+    next_tick_array_start_index: Option<i32>,
+}
+
+impl SwapQuoter {
+    /*
+     * Load the pool's current state and up to `max_tick_arrays` initialized
+     * tick arrays in the direction of a swap paying `input_mint`
+     */
+    pub async fn fetch(
+        rpc: &RpcClient,
+        pool: &PoolKeys,
+        input_mint: &Pubkey,
+        max_tick_arrays: usize,
+    ) -> Result<Self> {
+        let zero_for_one = pool.zero_for_one(input_mint)?;
+        let pool_state: PoolState = fetch_zero_copy(rpc, &pool.address).await?;
+
+        let mut addresses = vec![pool_state.amm_config];
+        addresses.extend(pool.tick_array_bitmap_extension);
+        let accounts = rpc.get_multiple_accounts(&addresses).await?;
+        let amm_config = accounts[0]
+            .as_ref()
+            .ok_or(ClientError::AccountNotFound(addresses[0]))?;
+        let amm_config = AmmConfig::try_deserialize(&mut amm_config.data.as_slice())
+            .map_err(|_| ClientError::InvalidAccountData(addresses[0]))?;
+        let bitmap_extension = match accounts.get(1) {
+            Some(Some(account)) => Some(decode_zero_copy::<TickArrayBitmapExtension>(
+                &addresses[1],
+                &account.data,
+            )?),
+            _ => None,
+        };
+
+        let (start_indices, next_tick_array_start_index) = swap_tick_array_start_indices(
+            &pool_state,
+            bitmap_extension.as_ref(),
+            zero_for_one,
+            max_tick_arrays,
+        )?;
+        let addresses: Vec<Pubkey> = start_indices
+            .iter()
+            .map(|start_index| tick_array_address(&pool.address, *start_index).0)
+            .collect();
+        let accounts = rpc.get_multiple_accounts(&addresses).await?;
+        let tick_arrays = addresses
+            .into_iter()
+            .zip(accounts)
+            .map(|(address, account)| {
+                let account = account.ok_or(ClientError::AccountNotFound(address))?;
+                Ok((address, decode_zero_copy(&address, &account.data)?))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self::new(
+            &pool_state,
+            amm_config.trade_fee_rate,
+            zero_for_one,
+            tick_arrays,
+            next_tick_array_start_index,
+        ))
+    }
+
+    /*
+     * Quoter over already loaded accounts. `tick_arrays` are the initialized
+     * ones in walk order, as `swap_tick_array_start_indices` lists them
+     */
+    pub fn new(
+        pool_state: &PoolState,
+        trade_fee_rate: u32,
+        zero_for_one: bool,
+        tick_arrays: Vec<(Pubkey, TickArrayState)>,
+        next_tick_array_start_index: Option<i32>,
+    ) -> Self {
+        Self {
+            zero_for_one,
+            tick_spacing: pool_state.tick_spacing,
+            sqrt_price_x64: pool_state.sqrt_price_x64,
+            tick_current: pool_state.tick_current,
+            liquidity: pool_state.liquidity,
+            trade_fee_rate,
+            tick_arrays,
+            next_tick_array_start_index,
+        }
+    }
+
+    /*
+     * Swap exactly `amount_in`, trade fee included. A zero price limit
+     * means none
+     */
+    pub fn quote_exact_in(&self, amount_in: u64, sqrt_price_limit_x64: u128) -> Result<Quote> {
+        self.quote(amount_in, sqrt_price_limit_x64, true)
+    }
+
+    /*
+     * Swap for exactly `amount_out`. A zero price limit means none
+     */
+    pub fn quote_exact_out(&self, amount_out: u64, sqrt_price_limit_x64: u128) -> Result<Quote> {
+        self.quote(amount_out, sqrt_price_limit_x64, false)
+    }
+
+    /*
+     * Raydium's swap loop: step to each initialized tick in turn, crossing
+     * it and moving on, until the amount or the price limit runs out. A tick
+     * array joins the path as soon as the swap looks into it
+     */
+    fn quote(&self, amount: u64, sqrt_price_limit_x64: u128, is_base_input: bool) -> Result<Quote> {
+        let zero_for_one = self.zero_for_one;
+        let sqrt_price_limit_x64 = self.effective_sqrt_price_limit(sqrt_price_limit_x64);
+        let mut walk = Walk {
+            zero_for_one,
+            is_base_input,
+            trade_fee_rate: self.trade_fee_rate,
+            sqrt_price_limit_x64,
+            sqrt_price_x64: self.sqrt_price_x64,
+            tick_current: self.tick_current,
+            liquidity: self.liquidity,
+            amount_remaining: amount,
+            amount_in: 0,
+            amount_out: 0,
+            fee: 0,
+            block_timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs() as u32),
+        };
+        let mut tick_arrays = Vec::new();
+
+        'arrays: for (address, tick_array) in &self.tick_arrays {
+            tick_arrays.push(*address);
+            let mut ticks: Vec<_> = tick_array
+                .ticks
+                .iter()
+                .filter(|tick| tick.liquidity_gross > 0)
+                .map(|tick| (tick.tick, tick.liquidity_net))
+                .collect();
+            if zero_for_one {
+                ticks.reverse();
+            }
+            for (tick, liquidity_net) in ticks {
+                let ahead = if zero_for_one {
+                    tick <= walk.tick_current
+                } else {
+                    tick > walk.tick_current
+                };
+                if !ahead {
+                    continue;
+                }
+                if !walk.step_to(sqrt_price_at_tick(tick)?)? {
+                    break 'arrays;
+                }
+                walk.cross(tick, liquidity_net)?;
+                if walk.amount_remaining == 0 {
+                    break 'arrays;
+                }
+            }
+        }
+
+        // Past the loaded arrays liquidity stays put up to the next
+        // initialized one, or all the way to the limit when there is none
+        if walk.amount_remaining > 0 && walk.sqrt_price_x64 != sqrt_price_limit_x64 {
+            let boundary = match self.next_tick_array_start_index {
+                Some(start_index) if zero_for_one => {
+                    sqrt_price_at_tick(start_index + ticks_per_tick_array(self.tick_spacing))?
+                }
+                Some(start_index) => sqrt_price_at_tick(start_index)?,
+                None => sqrt_price_limit_x64,
+            };
+            walk.step_to(boundary)?;
+        }
+
+        let amount_in_net = walk.amount_in - walk.fee;
+        let spot_amount_out =
+            convert_at_sqrt_price(amount_in_net, self.sqrt_price_x64, zero_for_one);
+        let price_impact_bps = match spot_amount_out {
+            0 => 0,
+            spot => {
+                (spot.saturating_sub(walk.amount_out) as u128 * ONE_IN_BASIS_POINTS / spot as u128)
+                    as u32
+            }
+        };
+        Ok(Quote {
+            amount_in: walk.amount_in,
+            amount_out: walk.amount_out,
+            fee: walk.fee,
+            sqrt_price_after_x64: walk.sqrt_price_x64,
+            tick_after: walk.tick_current,
+            price_impact_bps,
+            tick_arrays,
+            fully_filled: walk.amount_remaining == 0,
+        })
+    }
+
+    /*
+     * Accounts a quote from this quoter walks. `sqrt_price_limit_x64` is the
+     * quote's and `tick_array_bitmap_extension` the pool's, if it has one.
+     * The extension goes along conservatively: whenever a walked array lies
+     * past the pool's own bitmap, or the swap stops short of its amount and
+     * Raydium may search its bitmap for more
+     */
+    pub fn swap_accounts(
+        &self,
+        quote: &Quote,
+        sqrt_price_limit_x64: u128,
+        tick_array_bitmap_extension: Option<Pubkey>,
+    ) -> SwapAccounts {
+        let searches_on = !quote.fully_filled
+            && quote.sqrt_price_after_x64 != self.effective_sqrt_price_limit(sqrt_price_limit_x64);
+        let default_bitmap_reach =
+            DEFAULT_BITMAP_TICK_ARRAYS * ticks_per_tick_array(self.tick_spacing);
+        let past_default_bitmap = self
+            .tick_arrays
+            .iter()
+            .filter(|(address, _)| quote.tick_arrays.contains(address))
+            .any(|(_, tick_array)| {
+                !(-default_bitmap_reach..default_bitmap_reach)
+                    .contains(&tick_array.start_tick_index)
+            });
+        SwapAccounts {
+            tick_arrays: quote.tick_arrays.clone(),
+            tick_array_bitmap_extension: tick_array_bitmap_extension
+                .filter(|_| !quote.fully_filled || past_default_bitmap),
+            complete: !(searches_on && self.next_tick_array_start_index.is_some()),
+        }
+    }
+
+    fn effective_sqrt_price_limit(&self, sqrt_price_limit_x64: u128) -> u128 {
+        match sqrt_price_limit_x64 {
+            0 if self.zero_for_one => MIN_SQRT_PRICE_X64 + 1,
+            0 => MAX_SQRT_PRICE_X64 - 1,
+            limit => limit,
+        }
+    }
+}
+
+/*
+ * Quote a swap of `amount` paying `input_mint`, exact in or exact out, and
+ * find the accounts it walks: the tick arrays, in order, and the bitmap
+ * extension when the swap needs it. Tick arrays load a few at a time until
+ * the quote walks no further or `MAX_SWAP_TICK_ARRAYS` are loaded. A zero
+ * price limit means none
+ */
+pub async fn discover_swap_accounts(
+    rpc: &RpcClient,
+    pool: &PoolKeys,
+    input_mint: &Pubkey,
+    amount: u64,
+    is_base_input: bool,
+    sqrt_price_limit_x64: u128,
+) -> Result<(Quote, SwapAccounts)> {
+    let mut max_tick_arrays = DISCOVERY_TICK_ARRAYS;
+    loop {
+        let quoter = SwapQuoter::fetch(rpc, pool, input_mint, max_tick_arrays).await?;
+        let quote = if is_base_input {
+            quoter.quote_exact_in(amount, sqrt_price_limit_x64)?
+        } else {
+            quoter.quote_exact_out(amount, sqrt_price_limit_x64)?
+        };
+        let accounts = quoter.swap_accounts(
+            &quote,
+            sqrt_price_limit_x64,
+            pool.tick_array_bitmap_extension,
+        );
+        if accounts.complete || max_tick_arrays >= MAX_SWAP_TICK_ARRAYS {
+            return Ok((quote, accounts));
+        }
+        max_tick_arrays = (max_tick_arrays * 2).min(MAX_SWAP_TICK_ARRAYS);
+    }
+}
+
+/*
+ * Start indices of the initialized tick arrays a swap walks, the first one
+ * and up to `max` in all, and the start index of the next initialized one
+ * past those. Follows the pool's tick array bitmaps the way Raydium does
+ */
+pub fn swap_tick_array_start_indices(
+    pool_state: &PoolState,
+    bitmap_extension: Option<&TickArrayBitmapExtension>,
+    zero_for_one: bool,
+    max: usize,
+) -> Result<(Vec<i32>, Option<i32>)> {
+    let (_, first) =
+        pool_state.get_first_initialized_tick_array(&bitmap_extension, zero_for_one)?;
+    let mut start_indices = vec![first];
+    let mut next = pool_state.next_initialized_tick_array_start_index(
+        &bitmap_extension,
+        first,
+        zero_for_one,
+    )?;
+    while let Some(start_index) = next.filter(|_| start_indices.len() < max) {
+        start_indices.push(start_index);
+        next = pool_state.next_initialized_tick_array_start_index(
+            &bitmap_extension,
+            start_index,
+            zero_for_one,
+        )?;
+    }
+    Ok((start_indices, next))
+}
+
+/// Running state of a quoted swap
+struct Walk {
+    zero_for_one: bool,
+    is_base_input: bool,
+    trade_fee_rate: u32,
+    sqrt_price_limit_x64: u128,
+    sqrt_price_x64: u128,
+    tick_current: i32,
+    liquidity: u128,
+    amount_remaining: u64,
+    amount_in: u64,
+    amount_out: u64,
+    fee: u64,
+    /// Fed to Raydium's step math in place of the cluster clock
+    block_timestamp: u32,
+}
+
+impl Walk {
+    /*
+     * Swap towards a sqrt price, or the limit if that comes first. Returns
+     * whether the price got there
+     */
+    fn step_to(&mut self, sqrt_price_target_x64: u128) -> Result<bool> {
+        let limit_first = if self.zero_for_one {
+            self.sqrt_price_limit_x64 > sqrt_price_target_x64
+        } else {
+            self.sqrt_price_limit_x64 < sqrt_price_target_x64
+        };
+        let target = if limit_first {
+            self.sqrt_price_limit_x64
+        } else {
+            sqrt_price_target_x64
+        };
+        let step = compute_swap_step(
+            self.sqrt_price_x64,
+            target,
+            self.liquidity,
+            self.amount_remaining,
+            self.trade_fee_rate,
+            self.is_base_input,
+            self.zero_for_one,
+            self.block_timestamp,
+        )?;
+        let amount_in = step.amount_in.saturating_add(step.fee_amount);
+        self.amount_remaining = self.amount_remaining.saturating_sub(if self.is_base_input {
+            amount_in
+        } else {
+            step.amount_out
+        });
+        self.amount_in = self.amount_in.saturating_add(amount_in);
+        self.amount_out = self.amount_out.saturating_add(step.amount_out);
+        self.fee = self.fee.saturating_add(step.fee_amount);
+        self.sqrt_price_x64 = step.sqrt_price_next_x64;
+
+        let reached = !limit_first && self.sqrt_price_x64 == sqrt_price_target_x64;
+        if !reached {
+            self.tick_current = tick_at_sqrt_price(self.sqrt_price_x64)?;
+        }
+        Ok(reached)
+    }
+
+    /*
+     * Cross an initialized tick the price just reached
+     */
+    fn cross(&mut self, tick: i32, liquidity_net: i128) -> Result<()> {
+        let liquidity_net = if self.zero_for_one {
+            -liquidity_net
+        } else {
+            liquidity_net
+        };
+        self.liquidity = self
+            .liquidity
+            .checked_add_signed(liquidity_net)
+            .ok_or_else(|| anchor_lang::error::Error::from(CustomError::MathOverflow))?;
+        self.tick_current = if self.zero_for_one { tick - 1 } else { tick };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    const LIQUIDITY: u128 = 1_000_000_000_000;
+
+    fn pool_at_tick_zero() -> PoolState {
+        let mut pool_state = PoolState::zeroed();
+        pool_state.tick_spacing = 10;
+        pool_state.sqrt_price_x64 = sqrt_price_at_tick(0).unwrap();
+        pool_state.tick_current = 0;
+        pool_state.liquidity = LIQUIDITY;
+        pool_state
+    }
+
+    #[test]
+    fn exact_out_inverts_exact_in() {
+        let quoter = SwapQuoter::new(&pool_at_tick_zero(), 2_500, true, Vec::new(), None);
+        let exact_in = quoter.quote_exact_in(1_000_000, 0).unwrap();
+        assert!(exact_in.fully_filled);
+        assert_eq!(exact_in.amount_in, 1_000_000);
+        assert!(exact_in.fee > 0);
+        assert!(exact_in.amount_out <= exact_in.amount_in - exact_in.fee);
+
+        let exact_out = quoter.quote_exact_out(exact_in.amount_out, 0).unwrap();
+        assert!(exact_out.fully_filled);
+        assert_eq!(exact_out.amount_out, exact_in.amount_out);
+        assert!(exact_out.amount_in <= exact_in.amount_in);
+        assert!(exact_out.amount_in + 2 >= exact_in.amount_in);
+    }
+
+    #[test]
+    fn swap_stops_where_liquidity_runs_out() {
+        let mut tick_array = TickArrayState::zeroed();
+        tick_array.start_tick_index = 0;
+        tick_array.ticks[10].tick = 100;
+        tick_array.ticks[10].liquidity_gross = LIQUIDITY;
+        tick_array.ticks[10].liquidity_net = -(LIQUIDITY as i128);
+        let address = Pubkey::new_unique();
+        let quoter = SwapQuoter::new(
+            &pool_at_tick_zero(),
+            2_500,
+            false,
+            vec![(address, tick_array)],
+            None,
+        );
+
+        let quote = quoter.quote_exact_in(u64::MAX / 2, 0).unwrap();
+        assert!(!quote.fully_filled);
+        assert_eq!(quote.tick_arrays, vec![address]);
+        assert!(quote.amount_out > 0);
+        // Everything between tick 0 and tick 100 was bought, nothing past it
+        let in_range = quoter.quote_exact_out(quote.amount_out, 0).unwrap();
+        assert!(in_range.fully_filled);
+        assert!(in_range.sqrt_price_after_x64 <= sqrt_price_at_tick(100).unwrap());
+    }
+
+    #[test]
+    fn price_limit_stops_the_swap() {
+        let quoter = SwapQuoter::new(&pool_at_tick_zero(), 2_500, true, Vec::new(), None);
+        let limit = sqrt_price_at_tick(-10).unwrap();
+        let quote = quoter.quote_exact_in(u64::MAX / 2, limit).unwrap();
+        assert!(!quote.fully_filled);
+        assert_eq!(quote.sqrt_price_after_x64, limit);
+        assert!(quote.price_impact_bps > 0);
+    }
+
+    /*
+     * A tick array starting at `start_index` with LIQUIDITY leaving at `tick`
+     * on the way up
+     */
+    fn tick_array_ending_liquidity(
+        start_index: i32,
+        tick: i32,
+        tick_spacing: u16,
+    ) -> TickArrayState {
+        let mut tick_array = TickArrayState::zeroed();
+        tick_array.start_tick_index = start_index;
+        let tick_state =
+            &mut tick_array.ticks[((tick - start_index) / tick_spacing as i32) as usize];
+        tick_state.tick = tick;
+        tick_state.liquidity_gross = LIQUIDITY;
+        tick_state.liquidity_net = -(LIQUIDITY as i128);
+        tick_array
+    }
+
+    #[test]
+    fn swap_accounts_list_only_the_arrays_walked() {
+        let mut pool_state = pool_at_tick_zero();
+        pool_state.liquidity = 2 * LIQUIDITY;
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let tick_arrays = vec![
+            (first, tick_array_ending_liquidity(0, 100, 10)),
+            (second, tick_array_ending_liquidity(600, 700, 10)),
+        ];
+        let quoter = SwapQuoter::new(&pool_state, 2_500, false, tick_arrays, None);
+        let extension = Some(Pubkey::new_unique());
+
+        let small = quoter.quote_exact_in(1_000, 0).unwrap();
+        let accounts = quoter.swap_accounts(&small, 0, extension);
+        assert_eq!(accounts.tick_arrays, vec![first]);
+        assert_eq!(accounts.tick_array_bitmap_extension, None);
+        assert!(accounts.complete);
+
+        // Past tick 100 the swap walks into the second array, and past 700
+        // no liquidity is left
+        let large = quoter.quote_exact_in(u64::MAX / 2, 0).unwrap();
+        let accounts = quoter.swap_accounts(&large, 0, extension);
+        assert_eq!(accounts.tick_arrays, vec![first, second]);
+        assert_eq!(accounts.tick_array_bitmap_extension, extension);
+        assert!(accounts.complete);
+    }
+
+    #[test]
+    fn swap_accounts_ask_for_more_arrays_when_the_loaded_ones_run_out() {
+        let first = Pubkey::new_unique();
+        let quoter = SwapQuoter::new(
+            &pool_at_tick_zero(),
+            2_500,
+            false,
+            vec![(first, TickArrayState::zeroed())],
+            Some(600),
+        );
+        let quote = quoter.quote_exact_in(u64::MAX / 2, 0).unwrap();
+        let accounts = quoter.swap_accounts(&quote, 0, None);
+        assert_eq!(accounts.tick_arrays, vec![first]);
+        assert!(!accounts.complete);
+
+        // Stopped by its limit short of the next array, the swap is covered
+        let limit = sqrt_price_at_tick(300).unwrap();
+        let quote = quoter.quote_exact_in(u64::MAX / 2, limit).unwrap();
+        assert!(quoter.swap_accounts(&quote, limit, None).complete);
+    }
+
+    #[test]
+    fn swap_accounts_include_the_extension_past_the_default_bitmap() {
+        // At tick spacing 1 the pool's own bitmap ends at tick 30_720
+        let mut pool_state = PoolState::zeroed();
+        pool_state.tick_spacing = 1;
+        pool_state.tick_current = 30_700;
+        pool_state.sqrt_price_x64 = sqrt_price_at_tick(30_700).unwrap();
+        pool_state.liquidity = 2 * LIQUIDITY;
+        let (inside, outside) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let tick_arrays = vec![
+            (inside, tick_array_ending_liquidity(30_660, 30_710, 1)),
+            (outside, tick_array_ending_liquidity(30_720, 30_730, 1)),
+        ];
+        let quoter = SwapQuoter::new(&pool_state, 2_500, false, tick_arrays, None);
+        let extension = Some(Pubkey::new_unique());
+
+        let inside_only = quoter.quote_exact_in(1_000, 0).unwrap();
+        let accounts = quoter.swap_accounts(&inside_only, 0, extension);
+        assert_eq!(accounts.tick_arrays, vec![inside]);
+        assert_eq!(accounts.tick_array_bitmap_extension, None);
+
+        let limit = sqrt_price_at_tick(30_725).unwrap();
+        let crossing = quoter.quote_exact_in(u64::MAX / 2, limit).unwrap();
+        let accounts = quoter.swap_accounts(&crossing, limit, extension);
+        assert_eq!(accounts.tick_arrays, vec![inside, outside]);
+        assert_eq!(accounts.tick_array_bitmap_extension, extension);
+    }
+}
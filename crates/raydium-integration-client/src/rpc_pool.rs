@@ -0,0 +1,307 @@
+//! Several RPC endpoints behind one `RpcClient`.
+//!
+//! [`RpcPool`] is an `RpcSender`, so [`RpcPool::rpc_client`] hands the
+//! client, monitor and keeper an ordinary `RpcClient` that spreads requests
+//! over the endpoints by weight and fails over when one goes down. An
+//! endpoint whose request fails in transport, reports itself unhealthy or
+//! rate-limits is benched with a backoff that doubles on every further
+//! failure; the request moves on to the next endpoint. Errors the node
+//! returns about the request itself, such as a failed simulation, come back
+//! as they are. [`RpcPool::run_health_checks`] probes benched endpoints so
+//! they rejoin as soon as they recover.
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use solana_client::client_error::{ClientError as RpcError, ErrorKind, Result as RpcResult};
+use solana_client::http_sender::HttpSender;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_client::RpcClientConfig;
+use solana_client::rpc_request::{RpcError as RpcRequestError, RpcRequest};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+/// JSON-RPC error code of a node that is behind or otherwise unhealthy
+const NODE_UNHEALTHY: i64 = -32005;
+
+#[derive(Clone, Debug)]
+pub struct RpcEndpoint {
+    pub url: String,
+    /// Share of requests relative to the other endpoints, at least one
+    pub weight: u32,
+}
+
+impl RpcEndpoint {
+    pub fn new(url: impl Into<String>, weight: u32) -> Self {
+        Self {
+            url: url.into(),
+            weight,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RpcPoolConfig {
+    /// Bench after an endpoint's first failure, doubled per further failure
+    pub failure_backoff: Duration,
+    /// Bench after an endpoint rate-limits
+    pub rate_limit_backoff: Duration,
+    /// Longest bench
+    pub max_backoff: Duration,
+    /// Wait between health checks of benched endpoints
+    pub health_check_interval: Duration,
+}
+
+impl Default for RpcPoolConfig {
+    fn default() -> Self {
+        Self {
+            failure_backoff: Duration::from_secs(1),
+            rate_limit_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(60),
+            health_check_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+struct Endpoint {
+    url: String,
+    weight: u32,
+    sender: HttpSender,
+    health: Mutex<Health>,
+}
+
+#[derive(Default)]
+struct Health {
+    benched_until: Option<Instant>,
+    consecutive_failures: u32,
+}
+
+impl Endpoint {
+    fn is_benched(&self, now: Instant) -> bool {
+        let health = self.health.lock().unwrap();
+        health.benched_until.is_some_and(|until| until > now)
+    }
+
+    fn succeeded(&self) {
+        *self.health.lock().unwrap() = Health::default();
+    }
+
+    fn failed(&self, backoff: Duration, config: &RpcPoolConfig) {
+        let mut health = self.health.lock().unwrap();
+        let doubled = backoff.saturating_mul(1 << health.consecutive_failures.min(16));
+        health.benched_until = Some(Instant::now() + doubled.min(config.max_backoff));
+        health.consecutive_failures += 1;
+    }
+}
+
+/// Weighted, failing-over set of RPC endpoints. Clones share the endpoints
+/// and their health
+#[derive(Clone)]
+pub struct RpcPool {
+    endpoints: Arc<Vec<Endpoint>>,
+    config: RpcPoolConfig,
+    next: Arc<AtomicU64>,
+}
+
+impl RpcPool {
+    /*
+     * A pool over `endpoints`, which must not be empty
+     */
+    pub fn new(endpoints: Vec<RpcEndpoint>, config: RpcPoolConfig) -> Self {
+        assert!(!endpoints.is_empty(), "an RPC pool needs an endpoint");
+        let endpoints = endpoints
+            .into_iter()
+            .map(|endpoint| Endpoint {
+                sender: HttpSender::new(endpoint.url.clone()),
+                url: endpoint.url,
+                weight: endpoint.weight.max(1),
+                health: Mutex::new(Health::default()),
+            })
+            .collect();
+        Self {
+            endpoints: Arc::new(endpoints),
+            config,
+            next: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /*
+     * An `RpcClient` sending through the pool
+     */
+    pub fn rpc_client(&self, commitment: CommitmentConfig) -> RpcClient {
+        RpcClient::new_sender(self.clone(), RpcClientConfig::with_commitment(commitment))
+    }
+
+    /// URLs of the endpoints not currently benched
+    pub fn healthy_endpoints(&self) -> Vec<String> {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .filter(|endpoint| !endpoint.is_benched(now))
+            .map(|endpoint| endpoint.url.clone())
+            .collect()
+    }
+
+    /*
+     * Probe every benched endpoint with `getHealth` on an interval, until
+     * the process stops, returning those that answer to rotation
+     */
+    pub async fn run_health_checks(&self) {
+        loop {
+            tokio::time::sleep(self.config.health_check_interval).await;
+            let now = Instant::now();
+            for endpoint in self
+                .endpoints
+                .iter()
+                .filter(|endpoint| endpoint.is_benched(now))
+            {
+                match endpoint
+                    .sender
+                    .send(RpcRequest::GetHealth, Value::Null)
+                    .await
+                {
+                    Ok(_) => {
+                        log::info!("RPC endpoint {} recovered", endpoint.url);
+                        endpoint.succeeded();
+                    }
+                    Err(error) => log::debug!("RPC endpoint {}: {error}", endpoint.url),
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for RpcPool {
+    async fn send(&self, request: RpcRequest, params: Value) -> RpcResult<Value> {
+        let now = Instant::now();
+        let benched: Vec<bool> = self
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.is_benched(now))
+            .collect();
+        let weights: Vec<u32> = self
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.weight)
+            .collect();
+        let order = routing_order(
+            &weights,
+            &benched,
+            self.next.fetch_add(1, Ordering::Relaxed),
+        );
+
+        let mut last_error = None;
+        for index in order {
+            let endpoint = &self.endpoints[index];
+            match endpoint.sender.send(request, params.clone()).await {
+                Ok(value) => {
+                    endpoint.succeeded();
+                    return Ok(value);
+                }
+                Err(error) => match failure_backoff(&error, &self.config) {
+                    Some(backoff) => {
+                        log::warn!(
+                            "RPC endpoint {} failed, failing over: {error}",
+                            endpoint.url
+                        );
+                        endpoint.failed(backoff, &self.config);
+                        last_error = Some(error);
+                    }
+                    None => return Err(error),
+                },
+            }
+        }
+        Err(last_error.expect("an RPC pool has an endpoint"))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        let mut stats = RpcTransportStats::default();
+        for endpoint in self.endpoints.iter() {
+            let endpoint_stats = endpoint.sender.get_transport_stats();
+            stats.request_count += endpoint_stats.request_count;
+            stats.elapsed_time += endpoint_stats.elapsed_time;
+            stats.rate_limited_time += endpoint_stats.rate_limited_time;
+        }
+        stats
+    }
+
+    fn url(&self) -> String {
+        self.endpoints[0].url.clone()
+    }
+}
+
+/*
+ * How long to bench an endpoint after `error`, None when the error is
+ * about the request rather than the endpoint
+ */
+fn failure_backoff(error: &RpcError, config: &RpcPoolConfig) -> Option<Duration> {
+    match error.kind() {
+        ErrorKind::Reqwest(error) if error.status().map(|status| status.as_u16()) == Some(429) => {
+            Some(config.rate_limit_backoff)
+        }
+        ErrorKind::Io(_) | ErrorKind::Reqwest(_) | ErrorKind::Middleware(_) => {
+            Some(config.failure_backoff)
+        }
+        ErrorKind::RpcError(RpcRequestError::RpcRequestError(_)) => Some(config.failure_backoff),
+        ErrorKind::RpcError(RpcRequestError::RpcResponseError { code, .. })
+            if *code == NODE_UNHEALTHY =>
+        {
+            Some(config.failure_backoff)
+        }
+        _ => None,
+    }
+}
+
+/*
+ * Endpoint indices in the order to try them: the one `ticket` lands on in
+ * a weighted rotation of the healthy endpoints, the other healthy ones by
+ * weight, then the benched ones by weight as a last resort
+ */
+fn routing_order(weights: &[u32], benched: &[bool], ticket: u64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by_key(|&index| (benched[index], std::cmp::Reverse(weights[index])));
+
+    let healthy = order.iter().take_while(|&&index| !benched[index]).count();
+    let total: u64 = order[..healthy]
+        .iter()
+        .map(|&index| weights[index] as u64)
+        .sum();
+    if total > 0 {
+        let mut slot = ticket % total;
+        let first = order[..healthy]
+            .iter()
+            .position(|&index| {
+                let weight = weights[index] as u64;
+                if slot < weight {
+                    return true;
+                }
+                slot -= weight;
+                false
+            })
+            .unwrap_or(0);
+        order[..=first].rotate_right(1);
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_follow_the_weights_and_skip_benched_endpoints() {
+        let weights = [3, 1, 2];
+        let firsts: Vec<usize> = (0..6)
+            .map(|ticket| routing_order(&weights, &[false; 3], ticket)[0])
+            .collect();
+        assert_eq!(firsts, vec![0, 0, 0, 2, 2, 1]);
+
+        let order = routing_order(&weights, &[true, false, false], 0);
+        assert_eq!(order, vec![2, 1, 0]);
+        let order = routing_order(&weights, &[true, true, true], 4);
+        assert_eq!(order, vec![0, 2, 1]);
+    }
+}
@@ -0,0 +1,449 @@
+//! Streaming pool accounts into an in-memory cache.
+//!
+//! [`PoolStream`] loads each watched pool's state, AMM config, observations,
+//! tick array bitmap extension and tick arrays once, then keeps them current
+//! from account updates pushed over websocket or, with the `yellowstone`
+//! feature, a Yellowstone gRPC (Geyser) endpoint. Quoters and keepers read
+//! the [`AccountCache`] instead of calling `getAccountInfo` per use.
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator, ZeroCopy};
+use futures::stream::{select_all, StreamExt};
+use raydium_amm_v3::states::{
+    AmmConfig, ObservationState, PoolState, TickArrayBitmapExtension, TickArrayState,
+};
+use raydium_integration::RAYDIUM_CLMM_PROGRAM_ID;
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+
+use crate::accounts::decode_zero_copy;
+use crate::helpers::tick_array_address;
+use crate::quote::{swap_tick_array_start_indices, SwapQuoter};
+use crate::{ClientError, PoolKeys, Result};
+
+/// Wait before resubscribing after the stream drops
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Offset of `pool_id` in a tick array, right after the discriminator
+const TICK_ARRAY_POOL_OFFSET: usize = 8;
+
+/// Account data by address, each at the slot it was last seen. Clones share
+/// the same cache
+#[derive(Clone, Default)]
+pub struct AccountCache {
+    accounts: Arc<RwLock<HashMap<Pubkey, CachedAccount>>>,
+}
+
+struct CachedAccount {
+    slot: u64,
+    data: Vec<u8>,
+}
+
+impl AccountCache {
+    /*
+     * Record an account as of `slot`, unless a later slot is already cached.
+     * Empty data means the account was closed
+     */
+    pub fn update(&self, address: Pubkey, slot: u64, data: Vec<u8>) {
+        let mut accounts = self.accounts.write().unwrap();
+        if accounts
+            .get(&address)
+            .is_some_and(|cached| cached.slot > slot)
+        {
+            return;
+        }
+        if data.is_empty() {
+            accounts.remove(&address);
+        } else {
+            accounts.insert(address, CachedAccount { slot, data });
+        }
+    }
+
+    pub fn slot(&self, address: &Pubkey) -> Option<u64> {
+        self.accounts
+            .read()
+            .unwrap()
+            .get(address)
+            .map(|cached| cached.slot)
+    }
+
+    pub fn data(&self, address: &Pubkey) -> Option<Vec<u8>> {
+        let accounts = self.accounts.read().unwrap();
+        accounts.get(address).map(|cached| cached.data.clone())
+    }
+
+    /*
+     * A cached zero-copy account; AccountNotFound when it isn't cached
+     */
+    pub fn zero_copy<T: ZeroCopy + Discriminator>(&self, address: &Pubkey) -> Result<T> {
+        let accounts = self.accounts.read().unwrap();
+        let cached = accounts
+            .get(address)
+            .ok_or(ClientError::AccountNotFound(*address))?;
+        decode_zero_copy(address, &cached.data)
+    }
+
+    /*
+     * A cached Borsh account; AccountNotFound when it isn't cached
+     */
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let accounts = self.accounts.read().unwrap();
+        let cached = accounts
+            .get(address)
+            .ok_or(ClientError::AccountNotFound(*address))?;
+        T::try_deserialize(&mut cached.data.as_slice())
+            .map_err(|_| ClientError::InvalidAccountData(*address))
+    }
+
+    pub fn pool_state(&self, pool: &Pubkey) -> Result<PoolState> {
+        self.zero_copy(pool)
+    }
+
+    pub fn tick_array(&self, pool: &Pubkey, start_index: i32) -> Result<TickArrayState> {
+        self.zero_copy(&tick_array_address(pool, start_index).0)
+    }
+
+    pub fn observation(&self, pool: &PoolKeys) -> Result<ObservationState> {
+        self.zero_copy(&pool.observation_key)
+    }
+
+    /*
+     * `SwapQuoter::fetch` from the cache: the same quoter, without a round
+     * trip
+     */
+    pub fn swap_quoter(
+        &self,
+        pool: &PoolKeys,
+        input_mint: &Pubkey,
+        max_tick_arrays: usize,
+    ) -> Result<SwapQuoter> {
+        let zero_for_one = pool.zero_for_one(input_mint)?;
+        let pool_state = self.pool_state(&pool.address)?;
+        let amm_config: AmmConfig = self.account(&pool_state.amm_config)?;
+        let bitmap_extension = pool
+            .tick_array_bitmap_extension
+            .map(|address| self.zero_copy::<TickArrayBitmapExtension>(&address))
+            .transpose()?;
+        let (start_indices, next_tick_array_start_index) = swap_tick_array_start_indices(
+            &pool_state,
+            bitmap_extension.as_ref(),
+            zero_for_one,
+            max_tick_arrays,
+        )?;
+        let tick_arrays = start_indices
+            .into_iter()
+            .map(|start_index| {
+                let address = tick_array_address(&pool.address, start_index).0;
+                Ok((address, self.zero_copy(&address)?))
+            })
+            .collect::<Result<_>>()?;
+        Ok(SwapQuoter::new(
+            &pool_state,
+            amm_config.trade_fee_rate,
+            zero_for_one,
+            tick_arrays,
+            next_tick_array_start_index,
+        ))
+    }
+}
+
+/// Keeps the accounts of a set of pools current in an [`AccountCache`]
+pub struct PoolStream {
+    rpc: RpcClient,
+    cache: AccountCache,
+    pools: Vec<PoolKeys>,
+}
+
+impl PoolStream {
+    /*
+     * Load the pools' accounts into a new cache. Run one of the `run_*`
+     * loops to keep it current
+     */
+    pub async fn load(rpc: RpcClient, pools: &[Pubkey]) -> Result<Self> {
+        let mut pool_keys = Vec::with_capacity(pools.len());
+        for pool in pools {
+            pool_keys.push(PoolKeys::fetch(&rpc, pool).await?);
+        }
+        let stream = Self {
+            rpc,
+            cache: AccountCache::default(),
+            pools: pool_keys,
+        };
+        stream.reload().await?;
+        Ok(stream)
+    }
+
+    pub fn cache(&self) -> AccountCache {
+        self.cache.clone()
+    }
+
+    pub fn pools(&self) -> &[PoolKeys] {
+        &self.pools
+    }
+
+    /*
+     * Refetch every watched account, to catch up on what changed while no
+     * stream was connected
+     */
+    pub async fn reload(&self) -> Result<()> {
+        let addresses = self.accounts();
+        for chunk in addresses.chunks(100) {
+            let response = self
+                .rpc
+                .get_multiple_accounts_with_commitment(chunk, self.rpc.commitment())
+                .await?;
+            for (address, account) in chunk.iter().zip(response.value) {
+                let data = account.map(|account| account.data).unwrap_or_default();
+                self.cache.update(*address, response.context.slot, data);
+            }
+        }
+        for pool in &self.pools {
+            let config = RpcProgramAccountsConfig {
+                filters: Some(tick_array_filters(&pool.address)),
+                account_config: account_config(self.rpc.commitment()),
+                ..RpcProgramAccountsConfig::default()
+            };
+            let slot = self.rpc.get_slot().await?;
+            for (address, account) in self
+                .rpc
+                .get_program_accounts_with_config(&RAYDIUM_CLMM_PROGRAM_ID, config)
+                .await?
+            {
+                self.cache.update(address, slot, account.data);
+            }
+        }
+        Ok(())
+    }
+
+    /*
+     * Follow account notifications over websocket until the process stops,
+     * reloading and resubscribing whenever the connection drops
+     */
+    pub async fn run_websocket(&self, ws_url: &str) -> Result<()> {
+        let commitment = self.rpc.commitment();
+        loop {
+            let pubsub = PubsubClient::new(ws_url)
+                .await
+                .map_err(subscription_error)?;
+            let mut streams = Vec::new();
+            for address in self.accounts() {
+                let (stream, _unsubscribe) = pubsub
+                    .account_subscribe(&address, Some(account_config(commitment)))
+                    .await
+                    .map_err(subscription_error)?;
+                streams.push(
+                    stream
+                        .map(move |update| (address, update.context.slot, update.value))
+                        .boxed(),
+                );
+            }
+            for pool in &self.pools {
+                let config = RpcProgramAccountsConfig {
+                    filters: Some(tick_array_filters(&pool.address)),
+                    account_config: account_config(commitment),
+                    ..RpcProgramAccountsConfig::default()
+                };
+                let (stream, _unsubscribe) = pubsub
+                    .program_subscribe(&RAYDIUM_CLMM_PROGRAM_ID, Some(config))
+                    .await
+                    .map_err(subscription_error)?;
+                streams.push(
+                    stream
+                        .filter_map(|update| async move {
+                            let address = update.value.pubkey.parse().ok()?;
+                            Some((address, update.context.slot, update.value.account))
+                        })
+                        .boxed(),
+                );
+            }
+            if let Err(error) = self.reload().await {
+                log::error!("reloading pools: {error}");
+            }
+
+            let mut updates = select_all(streams);
+            while let Some((address, slot, account)) = updates.next().await {
+                self.apply(address, slot, &account);
+            }
+            log::warn!("pool stream dropped, reconnecting");
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /*
+     * Follow account updates from a Yellowstone gRPC endpoint until the
+     * process stops, reloading and resubscribing whenever the stream drops
+     */
+    #[cfg(feature = "yellowstone")]
+    pub async fn run_yellowstone(&self, endpoint: &str, x_token: Option<String>) -> Result<()> {
+        use futures::SinkExt;
+        use yellowstone_grpc_client::GeyserGrpcClient;
+        use yellowstone_grpc_proto::geyser::subscribe_update::UpdateOneof;
+        use yellowstone_grpc_proto::geyser::{SubscribeRequest, SubscribeRequestPing};
+
+        loop {
+            let mut client = GeyserGrpcClient::build_from_shared(endpoint.to_string())
+                .and_then(|builder| builder.x_token(x_token.clone()))
+                .map_err(|error| ClientError::Geyser(error.to_string()))?
+                .connect()
+                .await
+                .map_err(|error| ClientError::Geyser(error.to_string()))?;
+            let (mut requests, mut updates) = client
+                .subscribe_with_request(Some(self.geyser_request()))
+                .await
+                .map_err(|error| ClientError::Geyser(error.to_string()))?;
+            if let Err(error) = self.reload().await {
+                log::error!("reloading pools: {error}");
+            }
+
+            while let Some(update) = updates.next().await {
+                let update = match update {
+                    Ok(update) => update,
+                    Err(error) => {
+                        log::warn!("geyser stream: {error}");
+                        break;
+                    }
+                };
+                match update.update_oneof {
+                    Some(UpdateOneof::Account(update)) => {
+                        let Some(account) = update.account else {
+                            continue;
+                        };
+                        let Ok(address) = Pubkey::try_from(account.pubkey.as_slice()) else {
+                            continue;
+                        };
+                        self.cache.update(address, update.slot, account.data);
+                    }
+                    // Keep the connection open through load balancers
+                    Some(UpdateOneof::Ping(_)) => {
+                        let ping = SubscribeRequest {
+                            ping: Some(SubscribeRequestPing { id: 1 }),
+                            ..SubscribeRequest::default()
+                        };
+                        if let Err(error) = requests.send(ping).await {
+                            log::warn!("geyser ping: {error}");
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            log::warn!("geyser stream dropped, reconnecting");
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    /*
+     * One subscription for the watched accounts, one per pool for its tick
+     * arrays
+     */
+    #[cfg(feature = "yellowstone")]
+    fn geyser_request(&self) -> yellowstone_grpc_proto::geyser::SubscribeRequest {
+        use yellowstone_grpc_proto::geyser::subscribe_request_filter_accounts_filter::Filter;
+        use yellowstone_grpc_proto::geyser::subscribe_request_filter_accounts_filter_memcmp::Data;
+        use yellowstone_grpc_proto::geyser::{
+            CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts,
+            SubscribeRequestFilterAccountsFilter, SubscribeRequestFilterAccountsFilterMemcmp,
+        };
+
+        let mut accounts = HashMap::new();
+        accounts.insert(
+            "pools".to_string(),
+            SubscribeRequestFilterAccounts {
+                account: self.accounts().iter().map(Pubkey::to_string).collect(),
+                ..SubscribeRequestFilterAccounts::default()
+            },
+        );
+        for pool in &self.pools {
+            let filters = vec![
+                SubscribeRequestFilterAccountsFilter {
+                    filter: Some(Filter::Datasize(tick_array_len() as u64)),
+                },
+                SubscribeRequestFilterAccountsFilter {
+                    filter: Some(Filter::Memcmp(SubscribeRequestFilterAccountsFilterMemcmp {
+                        offset: TICK_ARRAY_POOL_OFFSET as u64,
+                        data: Some(Data::Bytes(pool.address.to_bytes().to_vec())),
+                    })),
+                },
+            ];
+            accounts.insert(
+                format!("tick_arrays:{}", pool.address),
+                SubscribeRequestFilterAccounts {
+                    owner: vec![RAYDIUM_CLMM_PROGRAM_ID.to_string()],
+                    filters,
+                    ..SubscribeRequestFilterAccounts::default()
+                },
+            );
+        }
+        let commitment = match self.rpc.commitment().commitment {
+            solana_sdk::commitment_config::CommitmentLevel::Processed => CommitmentLevel::Processed,
+            solana_sdk::commitment_config::CommitmentLevel::Confirmed => CommitmentLevel::Confirmed,
+            solana_sdk::commitment_config::CommitmentLevel::Finalized => CommitmentLevel::Finalized,
+        };
+        SubscribeRequest {
+            accounts,
+            commitment: Some(commitment as i32),
+            ..SubscribeRequest::default()
+        }
+    }
+
+    fn apply(&self, address: Pubkey, slot: u64, account: &UiAccount) {
+        match account.data.decode() {
+            Some(data) => self.cache.update(address, slot, data),
+            None => log::warn!("account {address}: undecodable update"),
+        }
+    }
+
+    /*
+     * Every watched account but the tick arrays: pools, their AMM configs,
+     * observations and bitmap extensions
+     */
+    fn accounts(&self) -> Vec<Pubkey> {
+        let mut addresses = Vec::new();
+        for pool in &self.pools {
+            addresses.extend([pool.address, pool.amm_config, pool.observation_key]);
+            addresses.extend(pool.tick_array_bitmap_extension);
+        }
+        addresses.sort();
+        addresses.dedup();
+        addresses
+    }
+}
+
+fn tick_array_len() -> usize {
+    TickArrayState::DISCRIMINATOR.len() + std::mem::size_of::<TickArrayState>()
+}
+
+/*
+ * Raydium's tick arrays of one pool
+ */
+fn tick_array_filters(pool: &Pubkey) -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::DataSize(tick_array_len() as u64),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            TICK_ARRAY_POOL_OFFSET,
+            pool.as_ref(),
+        )),
+    ]
+}
+
+fn account_config(
+    commitment: solana_sdk::commitment_config::CommitmentConfig,
+) -> RpcAccountInfoConfig {
+    RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        commitment: Some(commitment),
+        ..RpcAccountInfoConfig::default()
+    }
+}
+
+fn subscription_error(
+    error: solana_client::nonblocking::pubsub_client::PubsubClientError,
+) -> ClientError {
+    ClientError::Subscription(Box::new(error))
+}
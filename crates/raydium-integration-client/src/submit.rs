@@ -0,0 +1,234 @@
+//! Sending transactions until they land.
+//!
+//! [`send_transaction`] signs a v0 transaction, sends it without RPC-side
+//! retries and polls its status, resending it on an interval until it is
+//! confirmed, its blockhash expires or the timeout passes. Only once the
+//! blockhash has expired, when no copy of the transaction can land anymore,
+//! is it signed again with a fresh one, so one call never lands twice.
+//! Transport errors while sending or polling are logged and retried, as a
+//! flaky RPC node is no reason to give up on a transaction that may land.
+//!
+//! With a [`SendConfig::durable_nonce`] the transaction advances a nonce
+//! account instead of naming a recent blockhash. It then never expires and
+//! is signed only once, and any resubmission of it, even by another
+//! process, is the same transaction.
+//!
+//! With a [`SendConfig::jito`] every send goes to a Jito block engine as a
+//! bundle with a tip transaction, instead of to the RPC node; confirmation
+//! is still polled over RPC.
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use solana_client::nonblocking::nonce_utils;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::{v0, VersionedMessage};
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::system_instruction::advance_nonce_account;
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::jito::{pick_tip_account, tip_transaction, JitoClient, JitoConfig};
+use crate::{ClientError, Result};
+
+#[derive(Clone, Debug)]
+pub struct SendConfig {
+    /// Wait between status polls
+    pub poll_interval: Duration,
+    /// Wait between sends of the same transaction
+    pub resend_interval: Duration,
+    /// Times the transaction is signed again after its blockhash expires
+    pub max_blockhash_refreshes: u32,
+    /// How long to try before giving up with `ClientError::Unconfirmed`
+    pub timeout: Duration,
+    /// Nonce account to use instead of a recent blockhash. Its authority
+    /// must be the payer
+    pub durable_nonce: Option<Pubkey>,
+    /// Send as Jito bundles, tipping from the payer
+    pub jito: Option<JitoConfig>,
+}
+
+impl Default for SendConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(500),
+            resend_interval: Duration::from_secs(2),
+            max_blockhash_refreshes: 2,
+            timeout: Duration::from_secs(90),
+            durable_nonce: None,
+            jito: None,
+        }
+    }
+}
+
+/*
+ * Compile `instructions` into a v0 transaction paid by the first of
+ * `signers`, sign it and send it until it is confirmed at the RPC client's
+ * commitment. A transaction that lands and fails comes back as
+ * `ClientError::Failed`
+ */
+pub async fn send_transaction(
+    rpc: &RpcClient,
+    config: &SendConfig,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    signers: &[&dyn Signer],
+) -> Result<Signature> {
+    let payer = signers[0].pubkey();
+    let deadline = Instant::now() + config.timeout;
+    let mut instructions = instructions.to_vec();
+    if let Some(nonce) = &config.durable_nonce {
+        instructions.insert(0, advance_nonce_account(nonce, &payer));
+    }
+    let jito = match &config.jito {
+        Some(jito) => {
+            let client = JitoClient::new(&jito.block_engine_url);
+            let tip_accounts = client.tip_accounts().await?;
+            Some((client, tip_accounts, jito.tip_lamports))
+        }
+        None => None,
+    };
+
+    let mut signatures: Vec<Signature> = Vec::new();
+    let mut refreshes = 0;
+    loop {
+        let (blockhash, last_valid_block_height) = match &config.durable_nonce {
+            Some(nonce) => (nonce_blockhash(rpc, nonce).await?, u64::MAX),
+            None => {
+                rpc.get_latest_blockhash_with_commitment(rpc.commitment())
+                    .await?
+            }
+        };
+        let message = v0::Message::try_compile(&payer, &instructions, lookup_tables, blockhash)?;
+        let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), signers)?;
+        let signature = transaction.signatures[0];
+        if !signatures.contains(&signature) {
+            signatures.push(signature);
+        }
+
+        let mut last_sent: Option<Instant> = None;
+        loop {
+            if last_sent.is_none_or(|sent| sent.elapsed() >= config.resend_interval) {
+                let sent = match &jito {
+                    Some((client, tip_accounts, tip_lamports)) => {
+                        send_bundle(
+                            rpc,
+                            client,
+                            &transaction,
+                            signers[0],
+                            tip_accounts,
+                            *tip_lamports,
+                            config.durable_nonce.is_none().then_some(blockhash),
+                        )
+                        .await
+                    }
+                    None => rpc
+                        .send_transaction_with_config(
+                            &transaction,
+                            RpcSendTransactionConfig {
+                                skip_preflight: true,
+                                max_retries: Some(0),
+                                ..RpcSendTransactionConfig::default()
+                            },
+                        )
+                        .await
+                        .map(drop)
+                        .map_err(ClientError::from),
+                };
+                if let Err(error) = sent {
+                    log::debug!("sending {signature}: {error}");
+                }
+                last_sent = Some(Instant::now());
+            }
+
+            tokio::time::sleep(config.poll_interval).await;
+            if let Some(signature) = landed(rpc, &signatures).await? {
+                return Ok(signature);
+            }
+            if Instant::now() >= deadline {
+                return Err(ClientError::Unconfirmed(signature));
+            }
+            match rpc.get_block_height().await {
+                Ok(block_height) if block_height > last_valid_block_height => break,
+                Ok(_) => {}
+                Err(error) => log::debug!("polling block height: {error}"),
+            }
+        }
+
+        // The blockhash expired, so no copy sent so far can land from now
+        // on. One last look settles whether any already did
+        if let Some(signature) = landed(rpc, &signatures).await? {
+            return Ok(signature);
+        }
+        if refreshes == config.max_blockhash_refreshes {
+            return Err(ClientError::Unconfirmed(signature));
+        }
+        refreshes += 1;
+        log::warn!("{signature} expired unconfirmed, signing again with a new blockhash");
+    }
+}
+
+/*
+ * Send `transaction` in a bundle followed by a tip. The tip transaction
+ * takes `blockhash`, or the latest one when None; it is signed again for
+ * every bundle, which is harmless as it only lands with `transaction`
+ */
+async fn send_bundle(
+    rpc: &RpcClient,
+    client: &JitoClient,
+    transaction: &VersionedTransaction,
+    payer: &dyn Signer,
+    tip_accounts: &[Pubkey],
+    tip_lamports: u64,
+    blockhash: Option<Hash>,
+) -> Result<()> {
+    let tip_account = pick_tip_account(tip_accounts, &transaction.signatures[0])
+        .ok_or_else(|| ClientError::Jito("no tip accounts".to_string()))?;
+    let blockhash = match blockhash {
+        Some(blockhash) => blockhash,
+        None => rpc.get_latest_blockhash().await?,
+    };
+    let tip = tip_transaction(payer, &tip_account, tip_lamports, blockhash)?;
+    let bundle_id = client.send_bundle(&[transaction.clone(), tip]).await?;
+    log::debug!("bundle {bundle_id} carries {}", transaction.signatures[0]);
+    Ok(())
+}
+
+/*
+ * The signature among `signatures` confirmed at the client's commitment,
+ * if any. A transport error polling counts as none confirmed yet
+ */
+async fn landed(rpc: &RpcClient, signatures: &[Signature]) -> Result<Option<Signature>> {
+    let statuses = match rpc.get_signature_statuses(signatures).await {
+        Ok(statuses) => statuses.value,
+        Err(error) => {
+            log::debug!("polling signature statuses: {error}");
+            return Ok(None);
+        }
+    };
+    for (signature, status) in signatures.iter().zip(statuses) {
+        let Some(status) = status else { continue };
+        if !status.satisfies_commitment(rpc.commitment()) {
+            continue;
+        }
+        return match status.err {
+            Some(error) => Err(ClientError::Failed {
+                signature: *signature,
+                error,
+            }),
+            None => Ok(Some(*signature)),
+        };
+    }
+    Ok(None)
+}
+
+async fn nonce_blockhash(rpc: &RpcClient, nonce: &Pubkey) -> Result<Hash> {
+    let account = nonce_utils::get_account_with_commitment(rpc, nonce, rpc.commitment())
+        .await
+        .map_err(|_| ClientError::AccountNotFound(*nonce))?;
+    let data = nonce_utils::data_from_account(&account)
+        .map_err(|_| ClientError::InvalidAccountData(*nonce))?;
+    Ok(data.blockhash())
+}
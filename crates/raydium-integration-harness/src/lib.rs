@@ -0,0 +1,670 @@
+//! End-to-end test harness for `raydium-integration`.
+//!
+//! [`Harness`] starts a `solana-program-test` bank with the program's SBF
+//! build, the Raydium CLMM and Metaplex metadata binaries, and Raydium AMM
+//! configs written in place, so instructions run through the real CPIs
+//! rather than against mocks. Its helpers create mints, funded token
+//! accounts, pools and positions, and send instructions built with the
+//! client crate's builders. Account fixtures dumped from a cluster, such as
+//! a mainnet pool with its vaults and tick arrays, are loaded alongside.
+//!
+//! `anchor build` writes the program's binary to `target/deploy`.
+//! `scripts/dump-fixtures.sh` dumps Raydium's and Metaplex's, and the
+//! account fixtures, into [`FIXTURES_DIR`], and what it writes is committed;
+//! [`program_test`] panics naming the script when a binary is missing.
+use std::path::Path;
+
+use anchor_lang::prelude::{Clock, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use raydium_amm_v3::states::{
+    AmmConfig, PersonalPositionState, PoolState, AMM_CONFIG_SEED, OBSERVATION_SEED, POOL_SEED,
+    POOL_TICK_ARRAY_BITMAP_SEED, POOL_VAULT_SEED,
+};
+use raydium_integration::{pda, GlobalConfig, RAYDIUM_CLMM_PROGRAM_ID};
+use raydium_integration_client::accounts::decode_zero_copy;
+use raydium_integration_client::builders::{set_slippage, OpenPositionBuilder, PositionRange};
+use raydium_integration_client::events::{parse_logs, ProgramEvent};
+use raydium_integration_client::helpers::{
+    personal_position_address, tick_array_bitmap_extension_address,
+};
+use raydium_integration_client::{PoolKeys, PositionKeys};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+/// Where the Raydium and Metaplex binaries and the account fixtures go
+pub const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// Sqrt price of one, as Q64.64
+pub const SQRT_PRICE_ONE_X64: u128 = 1 << 64;
+
+/// File stem of the Raydium CLMM binary in [`FIXTURES_DIR`]
+pub const CLMM_PROGRAM_NAME: &str = "raydium_amm_v3";
+
+/// File stem of the Metaplex token metadata binary in [`FIXTURES_DIR`]
+pub const METADATA_PROGRAM_NAME: &str = "mpl_token_metadata";
+
+/// AMM configs written into every bank
+pub const AMM_CONFIGS: &[TestAmmConfig] = &[
+    TestAmmConfig {
+        index: 0,
+        tick_spacing: 1,
+        trade_fee_rate: 100,
+        protocol_fee_rate: 120_000,
+    },
+    TestAmmConfig {
+        index: 1,
+        tick_spacing: 10,
+        trade_fee_rate: 500,
+        protocol_fee_rate: 120_000,
+    },
+    TestAmmConfig {
+        index: 2,
+        tick_spacing: 60,
+        trade_fee_rate: 2_500,
+        protocol_fee_rate: 120_000,
+    },
+];
+
+/// A Raydium AMM config the harness writes into the bank. Raydium only lets
+/// its admin create them, so they are written rather than created
+#[derive(Clone, Copy, Debug)]
+pub struct TestAmmConfig {
+    pub index: u16,
+    pub tick_spacing: u16,
+    /// Per million
+    pub trade_fee_rate: u32,
+    /// Per million of the trade fee
+    pub protocol_fee_rate: u32,
+}
+
+impl TestAmmConfig {
+    pub fn address(&self) -> Pubkey {
+        amm_config_address(self.index).0
+    }
+
+    fn account(&self) -> Account {
+        let config = AmmConfig {
+            bump: amm_config_address(self.index).1,
+            index: self.index,
+            tick_spacing: self.tick_spacing,
+            trade_fee_rate: self.trade_fee_rate,
+            protocol_fee_rate: self.protocol_fee_rate,
+            ..AmmConfig::default()
+        };
+        let mut data = Vec::new();
+        config
+            .try_serialize(&mut data)
+            .expect("AMM configs serialize");
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: RAYDIUM_CLMM_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+}
+
+pub fn amm_config_address(index: u16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AMM_CONFIG_SEED.as_bytes(), &index.to_be_bytes()],
+        &RAYDIUM_CLMM_PROGRAM_ID,
+    )
+}
+
+/*
+ * An account fixture in the `solana account --output json` format, with its
+ * address
+ */
+pub fn load_account_fixture(path: &Path) -> (Pubkey, Account) {
+    let read = std::fs::read_to_string(path)
+        .unwrap_or_else(|error| panic!("reading fixture {}: {error}", path.display()));
+    let json: serde_json::Value = serde_json::from_str(&read)
+        .unwrap_or_else(|error| panic!("parsing fixture {}: {error}", path.display()));
+    let field = |value: &serde_json::Value, name: &str| {
+        value
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| panic!("fixture {} has no `{name}`", path.display()))
+    };
+    let pubkey = |value: serde_json::Value| -> Pubkey {
+        value.as_str().and_then(|key| key.parse().ok()).unwrap()
+    };
+    let account = field(&json, "account");
+    let data = field(&account, "data");
+    let data = data[0]
+        .as_str()
+        .expect("fixture data is [base64, \"base64\"]");
+    (
+        pubkey(field(&json, "pubkey")),
+        Account {
+            lamports: field(&account, "lamports").as_u64().unwrap(),
+            data: BASE64.decode(data).unwrap(),
+            owner: pubkey(field(&account, "owner")),
+            executable: field(&account, "executable").as_bool().unwrap(),
+            rent_epoch: 0,
+        },
+    )
+}
+
+/*
+ * Every account fixture in `dir`, none when it doesn't exist
+ */
+pub fn load_account_fixtures(dir: &Path) -> Vec<(Pubkey, Account)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        })
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| load_account_fixture(path))
+        .collect()
+}
+
+/*
+ * Proxy instruction from the program's generated accounts and arguments
+ */
+pub fn proxy_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: raydium_integration::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/*
+ * A `ProgramTest` with the program, Raydium CLMM, Metaplex, the
+ * [`AMM_CONFIGS`] and the account fixtures, to add to before starting
+ */
+pub fn program_test() -> ProgramTest {
+    for name in [CLMM_PROGRAM_NAME, METADATA_PROGRAM_NAME] {
+        let path = Path::new(FIXTURES_DIR).join(format!("{name}.so"));
+        assert!(
+            path.exists(),
+            "{} is missing; run scripts/dump-fixtures.sh and commit what it writes",
+            path.display()
+        );
+    }
+    // The program's binary is wherever `anchor build` put it
+    if std::env::var_os("SBF_OUT_DIR").is_none() {
+        std::env::set_var(
+            "SBF_OUT_DIR",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy"),
+        );
+    }
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(true);
+    program_test.add_program("raydium_integration", raydium_integration::ID, None);
+    program_test.add_program(CLMM_PROGRAM_NAME, RAYDIUM_CLMM_PROGRAM_ID, None);
+    program_test.add_program(METADATA_PROGRAM_NAME, anchor_spl::metadata::ID, None);
+    for config in AMM_CONFIGS {
+        program_test.add_account(config.address(), config.account());
+    }
+    for (address, account) in load_account_fixtures(&Path::new(FIXTURES_DIR).join("accounts")) {
+        program_test.add_account(address, account);
+    }
+    program_test
+}
+
+/// A running bank and helpers to set up and drive it. The context's payer
+/// pays for and signs everything unless told otherwise
+pub struct Harness {
+    pub context: ProgramTestContext,
+}
+
+/// A processed transaction's outcome, logs and events
+#[derive(Debug)]
+pub struct Processed {
+    pub result: Result<(), TransactionError>,
+    pub logs: Vec<String>,
+    pub events: Vec<ProgramEvent>,
+    pub compute_units_consumed: u64,
+}
+
+impl Processed {
+    /*
+     * The custom error code the transaction failed with, if it failed with
+     * one. Compare with `CustomError::X.into()`
+     */
+    pub fn custom_error(&self) -> Option<u32> {
+        match &self.result {
+            Err(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+                Some(*code)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Harness {
+    pub async fn start() -> Self {
+        Self::start_with(program_test()).await
+    }
+
+    /*
+     * Start the bank with a `GlobalConfig` whose admin and treasury are the
+     * context's payer, charging no fees and tracking no round trips
+     */
+    pub async fn start_with(program_test: ProgramTest) -> Self {
+        let mut harness = Self {
+            context: program_test.start_with_context().await,
+        };
+        let payer = harness.payer();
+        harness.set_global_config(&GlobalConfig {
+            admin: payer,
+            treasury: payer,
+            protocol_fee_bps: 0,
+            insurance_share_bps: 0,
+            flags: 0,
+            creator_share_bps: 0,
+            round_trip_window_secs: 0,
+            max_round_trips: 0,
+        });
+        harness
+    }
+
+    /*
+     * A started bank with a 1:1 pool of two fresh mints at the 0.25% fee
+     * tier, the payer holding plenty of both and a slippage tolerance of 1%
+     */
+    pub async fn start_with_pool() -> (Self, PoolKeys) {
+        let mut harness = Self::start().await;
+        let payer = harness.payer();
+        let mint_a = harness.create_mint(6).await;
+        let mint_b = harness.create_mint(6).await;
+        harness.fund(&mint_a, &payer, 1_000_000_000_000).await;
+        harness.fund(&mint_b, &payer, 1_000_000_000_000).await;
+        let pool = harness
+            .create_pool(
+                &amm_config_address(2).0,
+                &mint_a,
+                &mint_b,
+                SQRT_PRICE_ONE_X64,
+            )
+            .await;
+        harness
+            .execute(&[set_slippage(&payer, &payer, 100)], &[])
+            .await;
+        (harness, pool)
+    }
+
+    /*
+     * Overwrite the global config in place, for settings that otherwise only
+     * change through a scheduled change
+     */
+    pub fn set_global_config(&mut self, global_config: &GlobalConfig) {
+        let mut data = Vec::new();
+        global_config
+            .try_serialize(&mut data)
+            .expect("the global config serializes");
+        let account = Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: raydium_integration::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.context.set_account(
+            &pda::global_config_address().0,
+            &AccountSharedData::from(account),
+        );
+    }
+
+    pub async fn global_config(&mut self) -> GlobalConfig {
+        self.anchor_account(&pda::global_config_address().0).await
+    }
+
+    /*
+     * Move the bank `slots` slots ahead
+     */
+    pub async fn advance_slots(&mut self, slots: u64) {
+        let slot = self
+            .context
+            .banks_client
+            .get_root_slot()
+            .await
+            .expect("the bank serves its slot");
+        self.context
+            .warp_to_slot(slot + slots)
+            .expect("the bank warps forward");
+    }
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .expect("the bank serves the clock")
+    }
+
+    /*
+     * Move the clock's unix timestamp `seconds` ahead, leaving the slot
+     */
+    pub async fn advance_time(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /*
+     * A new keypair holding `lamports`, for signers that pay rent
+     */
+    pub async fn funded_keypair(&mut self, lamports: u64) -> Keypair {
+        let keypair = Keypair::new();
+        let transfer = system_instruction::transfer(&self.payer(), &keypair.pubkey(), lamports);
+        self.execute(&[transfer], &[]).await;
+        keypair
+    }
+
+    /*
+     * Send `instructions` in one transaction paid by the context's payer and
+     * signed by `signers` too. Fails only when the bank does; a failed
+     * transaction is reported in `Processed::result`
+     */
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Processed, BanksClientError> {
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        let processed = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await?;
+        let (logs, compute_units_consumed) = processed
+            .metadata
+            .map(|metadata| (metadata.log_messages, metadata.compute_units_consumed))
+            .unwrap_or_default();
+        Ok(Processed {
+            result: processed.result,
+            events: parse_logs(&logs),
+            logs,
+            compute_units_consumed,
+        })
+    }
+
+    /*
+     * Like `process`, panicking with the logs unless the transaction succeeds
+     */
+    pub async fn execute(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Processed {
+        let processed = self
+            .process(instructions, signers)
+            .await
+            .expect("the bank processes transactions");
+        if let Err(error) = &processed.result {
+            panic!("transaction failed: {error}\n{}", processed.logs.join("\n"));
+        }
+        processed
+    }
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .expect("the bank serves accounts")
+    }
+
+    /*
+     * An Anchor account, panicking when it is missing or of another type
+     */
+    pub async fn anchor_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self
+            .account(address)
+            .await
+            .unwrap_or_else(|| panic!("account {address} does not exist"));
+        T::try_deserialize(&mut account.data.as_slice())
+            .unwrap_or_else(|error| panic!("account {address}: {error}"))
+    }
+
+    pub async fn token_balance(&mut self, token_account: &Pubkey) -> u64 {
+        let account = self
+            .account(token_account)
+            .await
+            .unwrap_or_else(|| panic!("token account {token_account} does not exist"));
+        spl_token::state::Account::unpack(&account.data[..spl_token::state::Account::LEN])
+            .expect("token accounts unpack")
+            .amount
+    }
+
+    /*
+     * A new SPL token mint with the context's payer as mint authority
+     */
+    pub async fn create_mint(&mut self, decimals: u8) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &payer,
+                None,
+                decimals,
+            )
+            .unwrap(),
+        ];
+        self.execute(&instructions, &[&mint]).await;
+        mint.pubkey()
+    }
+
+    /*
+     * The insurance fund vault of `mint`, created through the proxy
+     */
+    pub async fn init_insurance_vault(&mut self, mint: &Pubkey) -> Pubkey {
+        let insurance_vault = pda::insurance_vault_address(mint).0;
+        let instruction = proxy_instruction(
+            raydium_integration::accounts::InitInsuranceVault {
+                payer: self.payer(),
+                global_config: pda::global_config_address().0,
+                mint: *mint,
+                insurance_vault,
+                token_program: spl_token::ID,
+                system_program: anchor_lang::system_program::ID,
+            },
+            raydium_integration::instruction::InitInsuranceVault {},
+        );
+        self.execute(&[instruction], &[]).await;
+        insurance_vault
+    }
+
+    /*
+     * `owner`'s associated token account for `mint`, created if needed and
+     * topped up by `amount` minted to it
+     */
+    pub async fn fund(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let payer = self.payer();
+        let token_account = get_associated_token_address(owner, mint);
+        let mut instructions = vec![
+            spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                &payer,
+                owner,
+                mint,
+                &spl_token::ID,
+            ),
+        ];
+        if amount > 0 {
+            instructions.push(
+                spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    mint,
+                    &token_account,
+                    &payer,
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            );
+        }
+        self.execute(&instructions, &[]).await;
+        token_account
+    }
+
+    /*
+     * A Raydium pool of two mints, in either order, at a Q64.64 sqrt price
+     * of token_1 in token_0 after ordering
+     */
+    pub async fn create_pool(
+        &mut self,
+        amm_config: &Pubkey,
+        mint_a: &Pubkey,
+        mint_b: &Pubkey,
+        sqrt_price_x64: u128,
+    ) -> PoolKeys {
+        let (token_mint_0, token_mint_1) = if mint_a < mint_b {
+            (*mint_a, *mint_b)
+        } else {
+            (*mint_b, *mint_a)
+        };
+        let clmm_address =
+            |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &RAYDIUM_CLMM_PROGRAM_ID).0;
+        let pool = clmm_address(&[
+            POOL_SEED.as_bytes(),
+            amm_config.as_ref(),
+            token_mint_0.as_ref(),
+            token_mint_1.as_ref(),
+        ]);
+        let accounts = raydium_amm_v3::accounts::CreatePool {
+            pool_creator: self.payer(),
+            amm_config: *amm_config,
+            pool_state: pool,
+            token_mint_0,
+            token_mint_1,
+            token_vault_0: clmm_address(&[
+                POOL_VAULT_SEED.as_bytes(),
+                pool.as_ref(),
+                token_mint_0.as_ref(),
+            ]),
+            token_vault_1: clmm_address(&[
+                POOL_VAULT_SEED.as_bytes(),
+                pool.as_ref(),
+                token_mint_1.as_ref(),
+            ]),
+            observation_state: clmm_address(&[OBSERVATION_SEED.as_bytes(), pool.as_ref()]),
+            tick_array_bitmap: clmm_address(&[
+                POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+                pool.as_ref(),
+            ]),
+            token_program_0: spl_token::ID,
+            token_program_1: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+        };
+        let instruction = Instruction {
+            program_id: RAYDIUM_CLMM_PROGRAM_ID,
+            accounts: accounts.to_account_metas(None),
+            data: raydium_amm_v3::instruction::CreatePool {
+                sqrt_price_x64,
+                open_time: 0,
+            }
+            .data(),
+        };
+        self.execute(&[instruction], &[]).await;
+        self.pool(&pool).await
+    }
+
+    /*
+     * The pool's keys as of now
+     */
+    pub async fn pool(&mut self, pool: &Pubkey) -> PoolKeys {
+        let account = self
+            .account(pool)
+            .await
+            .unwrap_or_else(|| panic!("pool {pool} does not exist"));
+        let pool_state: PoolState =
+            decode_zero_copy(pool, &account.data).expect("pools decode as PoolState");
+        let token_program_0 = self.account(&pool_state.token_mint_0).await.unwrap().owner;
+        let token_program_1 = self.account(&pool_state.token_mint_1).await.unwrap().owner;
+        let mut keys = PoolKeys::new(*pool, &pool_state, token_program_0, token_program_1);
+        let bitmap_extension = tick_array_bitmap_extension_address(pool).0;
+        if self.account(&bitmap_extension).await.is_some() {
+            keys.tick_array_bitmap_extension = Some(bitmap_extension);
+        }
+        keys
+    }
+
+    /*
+     * Open a position through the proxy for the context's payer, paid from
+     * their associated token accounts
+     */
+    pub async fn open_position(
+        &mut self,
+        pool: &PoolKeys,
+        range: PositionRange,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> PositionKeys {
+        let nft_mint = Keypair::new();
+        let instruction = OpenPositionBuilder::new(
+            pool,
+            &self.payer(),
+            &nft_mint.pubkey(),
+            range,
+            amount_0_max,
+            amount_1_max,
+        )
+        .instruction()
+        .expect("the range fits the pool");
+        self.execute(&[instruction], &[&nft_mint]).await;
+        self.position(&nft_mint.pubkey()).await
+    }
+
+    /*
+     * The position behind an NFT as of now
+     */
+    pub async fn position(&mut self, nft_mint: &Pubkey) -> PositionKeys {
+        let personal_position = personal_position_address(nft_mint).0;
+        let state: PersonalPositionState = self.anchor_account(&personal_position).await;
+        let nft_token_program = self.account(nft_mint).await.unwrap().owner;
+        PositionKeys {
+            nft_mint: *nft_mint,
+            nft_token_program,
+            personal_position,
+            pool: state.pool_id,
+            tick_lower_index: state.tick_lower_index,
+            tick_upper_index: state.tick_upper_index,
+            liquidity: state.liquidity,
+        }
+    }
+}
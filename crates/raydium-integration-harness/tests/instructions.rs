@@ -0,0 +1,141 @@
+//! Proxy swaps, positions and governance end to end against Raydium CLMM.
+//! Run `anchor build` first for the program's binary.
+use raydium_integration::{UserConfig, FLAG_PAUSED};
+use raydium_integration_client::accounts::decode_zero_copy;
+use raydium_integration_client::builders::{
+    set_slippage, ClosePositionBuilder, DecreaseLiquidityBuilder, IncreaseLiquidityBuilder,
+    PositionRange, ProxySwapBuilder,
+};
+use raydium_integration_client::events::ProgramEvent;
+use raydium_integration_client::helpers::user_config_address;
+use raydium_integration_harness::Harness;
+
+#[tokio::test]
+async fn set_slippage_creates_the_user_config() {
+    let mut harness = Harness::start().await;
+    let payer = harness.payer();
+    harness
+        .execute(&[set_slippage(&payer, &payer, 250)], &[])
+        .await;
+    let address = user_config_address(&payer).0;
+    let account = harness.account(&address).await.unwrap();
+    let user_config: UserConfig = decode_zero_copy(&address, &account.data).unwrap();
+    assert_eq!(user_config.effective_slippage_bps(), 250);
+}
+
+#[tokio::test]
+async fn swaps_through_the_proxy() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    harness
+        .open_position(
+            &pool,
+            PositionRange::Ticks {
+                tick_lower_index: -1200,
+                tick_upper_index: 1200,
+            },
+            100_000_000_000,
+            100_000_000_000,
+        )
+        .await;
+
+    // Upward from the 1:1 price, within the tick array at the current price
+    let output = harness.fund(&pool.token_mint_0, &payer, 0).await;
+    let before = harness.token_balance(&output).await;
+    let swap = ProxySwapBuilder::exact_in(&pool, &payer, &pool.token_mint_1, 1_000_000, 990_000)
+        .unwrap()
+        .instruction();
+    let processed = harness.execute(&[swap], &[]).await;
+
+    let received = harness.token_balance(&output).await - before;
+    assert!(received >= 990_000 * 99 / 100, "received {received}");
+    assert!(processed.events.iter().any(
+        |event| matches!(event, ProgramEvent::SwapExecuted(swap) if swap.pool == pool.address)
+    ));
+}
+
+#[tokio::test]
+async fn rejects_a_swap_short_of_the_tolerance() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    harness
+        .open_position(
+            &pool,
+            PositionRange::FullRange,
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .await;
+    // Expecting far more than the pool pays
+    let swap = ProxySwapBuilder::exact_in(&pool, &payer, &pool.token_mint_0, 1_000_000, 2_000_000)
+        .unwrap()
+        .instruction();
+    let processed = harness.process(&[swap], &[]).await.unwrap();
+    assert!(processed.result.is_err());
+}
+
+#[tokio::test]
+async fn runs_a_position_through_its_lifecycle() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let range = PositionRange::Ticks {
+        tick_lower_index: -600,
+        tick_upper_index: 600,
+    };
+    let position = harness
+        .open_position(&pool, range, 1_000_000_000, 1_000_000_000)
+        .await;
+    assert!(position.liquidity > 0);
+
+    let increase =
+        IncreaseLiquidityBuilder::new(&pool, &position, &payer, 500_000_000, 500_000_000)
+            .unwrap()
+            .instruction();
+    harness.execute(&[increase], &[]).await;
+    let increased = harness.position(&position.nft_mint).await;
+    assert!(increased.liquidity > position.liquidity);
+
+    let decrease = DecreaseLiquidityBuilder::new(&pool, &increased, &payer, increased.liquidity)
+        .unwrap()
+        .instruction();
+    harness.execute(&[decrease], &[]).await;
+    let emptied = harness.position(&position.nft_mint).await;
+    assert_eq!(emptied.liquidity, 0);
+
+    let close = ClosePositionBuilder::new(&emptied, &payer).instruction();
+    harness.execute(&[close], &[]).await;
+    assert!(harness.account(&emptied.personal_position).await.is_none());
+}
+
+#[tokio::test]
+async fn exits_stay_open_while_paused() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let position = harness
+        .open_position(
+            &pool,
+            PositionRange::FullRange,
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .await;
+    let mut global_config = harness.global_config().await;
+    global_config.flags |= FLAG_PAUSED;
+    harness.set_global_config(&global_config);
+
+    let increase =
+        IncreaseLiquidityBuilder::new(&pool, &position, &payer, 500_000_000, 500_000_000)
+            .unwrap()
+            .instruction();
+    let processed = harness.process(&[increase], &[]).await.unwrap();
+    assert!(processed.result.is_err());
+
+    let decrease = DecreaseLiquidityBuilder::new(&pool, &position, &payer, position.liquidity)
+        .unwrap()
+        .instruction();
+    harness.execute(&[decrease], &[]).await;
+    let emptied = harness.position(&position.nft_mint).await;
+    let close = ClosePositionBuilder::new(&emptied, &payer).instruction();
+    harness.execute(&[close], &[]).await;
+    assert!(harness.account(&emptied.personal_position).await.is_none());
+}
@@ -0,0 +1,529 @@
+//! Orders a keeper or a later transaction executes: Dutch auctions, floor
+//! orders and commit-reveal swaps. Every order sells token_1 for token_0,
+//! upward from the pool's 1:1 price, so the tick array at the current price
+//! is enough.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use raydium_integration::{
+    accounts, instruction, pda, CustomError, DutchOrder, FloorOrder, REVEAL_WINDOW_SLOTS,
+};
+use raydium_integration_client::builders::{
+    DcaLadder, FillDutchOrderBuilder, FillFloorOrderBuilder, PositionRange, RevealAndSwapBuilder,
+};
+use raydium_integration_client::events::ProgramEvent;
+use raydium_integration_client::PoolKeys;
+use raydium_integration_harness::{proxy_instruction, Harness, SQRT_PRICE_ONE_X64};
+use solana_sdk::signature::{Keypair, Signer};
+
+/*
+ * A pool with 100 of each token around the 1:1 price
+ */
+async fn liquid_pool() -> (Harness, PoolKeys) {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    harness
+        .open_position(
+            &pool,
+            PositionRange::Ticks {
+                tick_lower_index: -1200,
+                tick_upper_index: 1200,
+            },
+            100_000_000_000,
+            100_000_000_000,
+        )
+        .await;
+    (harness, pool)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_dutch_order(
+    pool: &PoolKeys,
+    owner: &Pubkey,
+    order_id: u64,
+    amount_in: u64,
+    start_min_out: u64,
+    end_min_out: u64,
+    start_ts: i64,
+    end_ts: i64,
+) -> Instruction {
+    let dutch_order = pda::dutch_order_address(owner, order_id).0;
+    proxy_instruction(
+        accounts::CreateDutchOrder {
+            owner: *owner,
+            pool_state: pool.address,
+            input_mint: pool.token_mint_1,
+            owner_input_account: get_associated_token_address(owner, &pool.token_mint_1),
+            destination: get_associated_token_address(owner, &pool.token_mint_0),
+            dutch_order,
+            escrow: pda::dutch_escrow_address(&dutch_order).0,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CreateDutchOrder {
+            order_id,
+            amount_in,
+            start_min_out,
+            end_min_out,
+            start_ts,
+            end_ts,
+        },
+    )
+}
+
+fn cancel_dutch_order(pool: &PoolKeys, owner: &Pubkey, order_id: u64) -> Instruction {
+    let dutch_order = pda::dutch_order_address(owner, order_id).0;
+    proxy_instruction(
+        accounts::CancelDutchOrder {
+            owner: *owner,
+            dutch_order,
+            escrow: pda::dutch_escrow_address(&dutch_order).0,
+            input_mint: pool.token_mint_1,
+            owner_input_account: get_associated_token_address(owner, &pool.token_mint_1),
+            token_program: spl_token::ID,
+            user_cfg: pda::user_config_address(owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CancelDutchOrder {},
+    )
+}
+
+async fn fill_dutch_order(
+    harness: &mut Harness,
+    pool: &PoolKeys,
+    owner: &Pubkey,
+    order_id: u64,
+) -> Instruction {
+    let dutch_order = pda::dutch_order_address(owner, order_id).0;
+    let order: DutchOrder = harness.anchor_account(&dutch_order).await;
+    FillDutchOrderBuilder::new(pool, &harness.payer(), &dutch_order, &order)
+        .unwrap()
+        .instruction()
+}
+
+fn create_floor_order(
+    pool: &PoolKeys,
+    owner: &Pubkey,
+    order_id: u64,
+    amount_in: u64,
+    floor_price_x64: u128,
+    daily_max: u64,
+) -> Instruction {
+    let floor_order = pda::floor_order_address(owner, order_id).0;
+    proxy_instruction(
+        accounts::CreateFloorOrder {
+            owner: *owner,
+            pool_state: pool.address,
+            input_mint: pool.token_mint_1,
+            owner_input_account: get_associated_token_address(owner, &pool.token_mint_1),
+            destination: get_associated_token_address(owner, &pool.token_mint_0),
+            floor_order,
+            escrow: pda::floor_escrow_address(&floor_order).0,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CreateFloorOrder {
+            order_id,
+            amount_in,
+            floor_price_x64,
+            daily_max,
+        },
+    )
+}
+
+async fn fill_floor_order(
+    harness: &mut Harness,
+    pool: &PoolKeys,
+    owner: &Pubkey,
+    order_id: u64,
+    amount_in: u64,
+) -> Instruction {
+    let floor_order = pda::floor_order_address(owner, order_id).0;
+    let order: FloorOrder = harness.anchor_account(&floor_order).await;
+    FillFloorOrderBuilder::new(pool, &harness.payer(), &floor_order, &order, amount_in)
+        .unwrap()
+        .instruction()
+}
+
+fn cancel_floor_order(pool: &PoolKeys, owner: &Pubkey, order_id: u64) -> Instruction {
+    let floor_order = pda::floor_order_address(owner, order_id).0;
+    proxy_instruction(
+        accounts::CancelFloorOrder {
+            owner: *owner,
+            floor_order,
+            escrow: pda::floor_escrow_address(&floor_order).0,
+            input_mint: pool.token_mint_1,
+            owner_input_account: get_associated_token_address(owner, &pool.token_mint_1),
+            token_program: spl_token::ID,
+            user_cfg: pda::user_config_address(owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CancelFloorOrder {},
+    )
+}
+
+/// 0.9 token_0 per token_1, as Q64.64
+const FLOOR_PRICE_X64: u128 = SQRT_PRICE_ONE_X64 / 10 * 9;
+
+#[tokio::test]
+async fn fills_a_dutch_order_in_its_window() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let now = harness.clock().await.unix_timestamp;
+    let create = create_dutch_order(
+        &pool,
+        &payer,
+        1,
+        1_000_000,
+        990_000,
+        900_000,
+        now - 10,
+        now + 3_600,
+    );
+    harness.execute(&[create], &[]).await;
+    let destination = get_associated_token_address(&payer, &pool.token_mint_0);
+    let before = harness.token_balance(&destination).await;
+
+    let fill = fill_dutch_order(&mut harness, &pool, &payer, 1).await;
+    let processed = harness.execute(&[fill], &[]).await;
+
+    assert!(harness.token_balance(&destination).await - before >= 990_000);
+    assert!(processed
+        .events
+        .iter()
+        .any(|event| matches!(event, ProgramEvent::DutchOrderFilled(_))));
+    let dutch_order = pda::dutch_order_address(&payer, 1).0;
+    assert!(harness.account(&dutch_order).await.is_none());
+}
+
+#[tokio::test]
+async fn rejects_a_dutch_order_whose_price_rises() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let now = harness.clock().await.unix_timestamp;
+    // The least output must fall over the auction, not rise
+    let create = create_dutch_order(
+        &pool,
+        &payer,
+        1,
+        1_000_000,
+        900_000,
+        990_000,
+        now,
+        now + 3_600,
+    );
+    let processed = harness.process(&[create], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::InvalidDutchOrder.into())
+    );
+}
+
+#[tokio::test]
+async fn rejects_a_dutch_fill_before_the_auction_starts() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let now = harness.clock().await.unix_timestamp;
+    let create = create_dutch_order(
+        &pool,
+        &payer,
+        1,
+        1_000_000,
+        990_000,
+        900_000,
+        now + 600,
+        now + 3_600,
+    );
+    harness.execute(&[create], &[]).await;
+
+    let fill = fill_dutch_order(&mut harness, &pool, &payer, 1).await;
+    let processed = harness.process(&[fill], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::DutchOrderNotActive.into())
+    );
+}
+
+#[tokio::test]
+async fn cancelling_a_dutch_order_returns_the_escrow() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let input = get_associated_token_address(&payer, &pool.token_mint_1);
+    let before = harness.token_balance(&input).await;
+    let now = harness.clock().await.unix_timestamp;
+    let create = create_dutch_order(
+        &pool,
+        &payer,
+        1,
+        1_000_000,
+        990_000,
+        900_000,
+        now,
+        now + 3_600,
+    );
+    harness.execute(&[create], &[]).await;
+
+    harness
+        .execute(&[cancel_dutch_order(&pool, &payer, 1)], &[])
+        .await;
+    assert_eq!(harness.token_balance(&input).await, before);
+    let dutch_order = pda::dutch_order_address(&payer, 1).0;
+    assert!(harness.account(&dutch_order).await.is_none());
+}
+
+#[tokio::test]
+async fn rejects_a_dutch_cancel_by_anyone_else() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let now = harness.clock().await.unix_timestamp;
+    let create = create_dutch_order(
+        &pool,
+        &payer,
+        1,
+        1_000_000,
+        990_000,
+        900_000,
+        now,
+        now + 3_600,
+    );
+    harness.execute(&[create], &[]).await;
+
+    let stranger = Keypair::new();
+    let mut cancel = cancel_dutch_order(&pool, &payer, 1);
+    // Signed by the stranger in the owner's place
+    cancel.accounts[0].pubkey = stranger.pubkey();
+    let processed = harness.process(&[cancel], &[&stranger]).await.unwrap();
+    assert!(processed.result.is_err());
+}
+
+#[tokio::test]
+async fn a_dca_ladder_fills_one_slice_per_interval() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let ladder = DcaLadder {
+        first_order_id: 10,
+        slices: 2,
+        amount_per_slice: 1_000_000,
+        start_min_out: 990_000,
+        end_min_out: 900_000,
+        start_ts: harness.clock().await.unix_timestamp,
+        interval: 3_600,
+    };
+    let create = ladder
+        .instructions(&pool, &payer, &pool.token_mint_1)
+        .unwrap();
+    harness.execute(&create, &[]).await;
+
+    let fill = fill_dutch_order(&mut harness, &pool, &payer, 10).await;
+    harness.execute(&[fill], &[]).await;
+    // The second slice waits for its interval
+    let fill = fill_dutch_order(&mut harness, &pool, &payer, 11).await;
+    let processed = harness.process(&[fill], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::DutchOrderNotActive.into())
+    );
+
+    harness.advance_time(ladder.interval).await;
+    let fill = fill_dutch_order(&mut harness, &pool, &payer, 11).await;
+    harness.execute(&[fill], &[]).await;
+    for order_id in ladder.order_ids() {
+        let dutch_order = pda::dutch_order_address(&payer, order_id).0;
+        assert!(harness.account(&dutch_order).await.is_none());
+    }
+}
+
+#[tokio::test]
+async fn fills_a_floor_order_in_parts() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let create = create_floor_order(&pool, &payer, 1, 2_000_000, FLOOR_PRICE_X64, 1_000_000);
+    harness.execute(&[create], &[]).await;
+    let destination = get_associated_token_address(&payer, &pool.token_mint_0);
+    let before = harness.token_balance(&destination).await;
+
+    let fill = fill_floor_order(&mut harness, &pool, &payer, 1, 1_000_000).await;
+    harness.execute(&[fill], &[]).await;
+
+    assert!(harness.token_balance(&destination).await - before >= 900_000);
+    let order: FloorOrder = harness
+        .anchor_account(&pda::floor_order_address(&payer, 1).0)
+        .await;
+    assert_eq!(order.total_sold, 1_000_000);
+    assert_eq!(order.sold_today, 1_000_000);
+}
+
+#[tokio::test]
+async fn rejects_a_floor_order_without_a_daily_max() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let create = create_floor_order(&pool, &payer, 1, 2_000_000, FLOOR_PRICE_X64, 0);
+    let processed = harness.process(&[create], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::InvalidFloorOrder.into())
+    );
+}
+
+#[tokio::test]
+async fn rejects_a_floor_fill_past_the_daily_max() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let create = create_floor_order(&pool, &payer, 1, 2_000_000, FLOOR_PRICE_X64, 1_000_000);
+    harness.execute(&[create], &[]).await;
+
+    let fill = fill_floor_order(&mut harness, &pool, &payer, 1, 1_500_000).await;
+    let processed = harness.process(&[fill], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::FloorOrderLimitExceeded.into())
+    );
+}
+
+#[tokio::test]
+async fn cancelling_a_floor_order_returns_the_escrow() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let input = get_associated_token_address(&payer, &pool.token_mint_1);
+    let before = harness.token_balance(&input).await;
+    let create = create_floor_order(&pool, &payer, 1, 2_000_000, FLOOR_PRICE_X64, 1_000_000);
+    harness.execute(&[create], &[]).await;
+
+    harness
+        .execute(&[cancel_floor_order(&pool, &payer, 1)], &[])
+        .await;
+    assert_eq!(harness.token_balance(&input).await, before);
+}
+
+#[tokio::test]
+async fn rejects_a_floor_cancel_of_a_missing_order() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let processed = harness
+        .process(&[cancel_floor_order(&pool, &payer, 1)], &[])
+        .await
+        .unwrap();
+    assert!(processed.result.is_err());
+}
+
+#[tokio::test]
+async fn reveal_charges_the_protocol_fee() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let treasury = Pubkey::new_unique();
+    let treasury_account = harness.fund(&pool.token_mint_1, &treasury, 0).await;
+    let insurance_vault = harness.init_insurance_vault(&pool.token_mint_1).await;
+    let mut global_config = harness.global_config().await;
+    global_config.treasury = treasury;
+    global_config.protocol_fee_bps = 50;
+    global_config.insurance_share_bps = 2_000;
+    harness.set_global_config(&global_config);
+
+    let reveal = RevealAndSwapBuilder::exact_in(
+        &pool,
+        &payer,
+        &pool.token_mint_1,
+        1_000_000,
+        990_000,
+        [7; 32],
+    )
+    .unwrap()
+    .protocol_fee(&global_config);
+    harness.execute(&[reveal.commit_instruction()], &[]).await;
+    harness.advance_slots(2).await;
+    let processed = harness.execute(&[reveal.instruction()], &[]).await;
+
+    let fee = processed
+        .events
+        .iter()
+        .find_map(|event| match event {
+            ProgramEvent::ProtocolFeeCollected(collected) => Some(collected.fee),
+            _ => None,
+        })
+        .expect("the reveal collects the protocol fee");
+    assert!(fee > 0);
+    assert!(processed.events.iter().any(
+        |event| matches!(event, ProgramEvent::SwapExecuted(swap) if swap.protocol_fee == fee)
+    ));
+    let insured = harness.token_balance(&insurance_vault).await;
+    assert!(insured > 0);
+    assert_eq!(
+        harness.token_balance(&treasury_account).await + insured,
+        fee
+    );
+}
+
+#[tokio::test]
+async fn rejects_a_reveal_without_the_fee_accounts() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let mut global_config = harness.global_config().await;
+    global_config.protocol_fee_bps = 50;
+    harness.set_global_config(&global_config);
+
+    // Leaves out `protocol_fee`, so no treasury or insurance accounts
+    let reveal = RevealAndSwapBuilder::exact_in(
+        &pool,
+        &payer,
+        &pool.token_mint_1,
+        1_000_000,
+        990_000,
+        [7; 32],
+    )
+    .unwrap();
+    harness.execute(&[reveal.commit_instruction()], &[]).await;
+    harness.advance_slots(2).await;
+    let processed = harness.process(&[reveal.instruction()], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::ProtocolFeeAccountsRequired.into())
+    );
+}
+
+#[tokio::test]
+async fn rejects_a_reveal_past_the_window() {
+    let (mut harness, pool) = liquid_pool().await;
+    let payer = harness.payer();
+    let reveal = RevealAndSwapBuilder::exact_in(
+        &pool,
+        &payer,
+        &pool.token_mint_1,
+        1_000_000,
+        990_000,
+        [7; 32],
+    )
+    .unwrap();
+    harness.execute(&[reveal.commit_instruction()], &[]).await;
+    harness.advance_slots(REVEAL_WINDOW_SLOTS * 2).await;
+    let processed = harness.process(&[reveal.instruction()], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::RevealOutsideWindow.into())
+    );
+}
@@ -0,0 +1,230 @@
+//! Guardians recovering an owner's config to a new key after the delay, and
+//! the owner cancelling a rogue recovery.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use raydium_integration::{
+    accounts, instruction, pda, CustomError, RecoveryConfig, UserConfig, RECOVERY_DELAY,
+};
+use raydium_integration_client::accounts::decode_zero_copy;
+use raydium_integration_client::builders::set_slippage;
+use raydium_integration_client::events::ProgramEvent;
+use raydium_integration_harness::{proxy_instruction, Harness};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn set_guardians(owner: &Pubkey, guardians: Vec<Pubkey>, threshold: u8) -> Instruction {
+    proxy_instruction(
+        accounts::SetGuardians {
+            owner: *owner,
+            payer: *owner,
+            recovery_config: pda::recovery_config_address(owner).0,
+            system_program: anchor_lang::system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::SetGuardians {
+            guardians,
+            threshold,
+        },
+    )
+}
+
+fn approve_recovery(guardian: &Pubkey, owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    proxy_instruction(
+        accounts::ApproveRecovery {
+            guardian: *guardian,
+            recovery_config: pda::recovery_config_address(owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::ApproveRecovery {
+            new_owner: *new_owner,
+        },
+    )
+}
+
+fn cancel_recovery(owner: &Pubkey) -> Instruction {
+    proxy_instruction(
+        accounts::CancelRecovery {
+            owner: *owner,
+            recovery_config: pda::recovery_config_address(owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CancelRecovery {},
+    )
+}
+
+fn execute_recovery(payer: &Pubkey, owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    proxy_instruction(
+        accounts::ExecuteRecovery {
+            payer: *payer,
+            recovery_config: pda::recovery_config_address(owner).0,
+            user_cfg: pda::user_config_address(owner).0,
+            new_owner: *new_owner,
+            new_user_cfg: pda::user_config_address(new_owner).0,
+            new_recovery_config: pda::recovery_config_address(new_owner).0,
+            system_program: anchor_lang::system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::ExecuteRecovery {},
+    )
+}
+
+/*
+ * The payer with a 0.75% tolerance and one guardian, who has approved
+ * moving their config to a new owner
+ */
+async fn approved_recovery() -> (Harness, Keypair, Pubkey) {
+    let mut harness = Harness::start().await;
+    let payer = harness.payer();
+    let guardian = Keypair::new();
+    let new_owner = Pubkey::new_unique();
+    harness
+        .execute(
+            &[
+                set_slippage(&payer, &payer, 75),
+                set_guardians(&payer, vec![guardian.pubkey()], 1),
+            ],
+            &[],
+        )
+        .await;
+    harness
+        .execute(
+            &[approve_recovery(&guardian.pubkey(), &payer, &new_owner)],
+            &[&guardian],
+        )
+        .await;
+    (harness, guardian, new_owner)
+}
+
+#[tokio::test]
+async fn sets_guardians_with_a_threshold() {
+    let mut harness = Harness::start().await;
+    let payer = harness.payer();
+    let guardians = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    harness
+        .execute(&[set_guardians(&payer, guardians.clone(), 2)], &[])
+        .await;
+    let recovery: RecoveryConfig = harness
+        .anchor_account(&pda::recovery_config_address(&payer).0)
+        .await;
+    assert_eq!(recovery.guardian_count, 2);
+    assert_eq!(recovery.guardians[..2], guardians[..]);
+    assert_eq!(recovery.threshold, 2);
+}
+
+#[tokio::test]
+async fn rejects_a_threshold_above_the_guardians() {
+    let mut harness = Harness::start().await;
+    let payer = harness.payer();
+    let set = set_guardians(&payer, vec![Pubkey::new_unique()], 2);
+    let processed = harness.process(&[set], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::InvalidGuardianSet.into())
+    );
+}
+
+#[tokio::test]
+async fn a_guardian_approval_starts_the_delay() {
+    let (mut harness, guardian, new_owner) = approved_recovery().await;
+    let payer = harness.payer();
+    let recovery: RecoveryConfig = harness
+        .anchor_account(&pda::recovery_config_address(&payer).0)
+        .await;
+    assert_eq!(recovery.pending_owner, new_owner);
+    assert_eq!(
+        recovery.ready_at,
+        harness.clock().await.unix_timestamp + RECOVERY_DELAY
+    );
+    assert_eq!(recovery.guardian_index(&guardian.pubkey()), Some(0));
+}
+
+#[tokio::test]
+async fn rejects_an_approval_by_anyone_else() {
+    let (mut harness, _guardian, new_owner) = approved_recovery().await;
+    let payer = harness.payer();
+    let stranger = Keypair::new();
+    let approve = approve_recovery(&stranger.pubkey(), &payer, &new_owner);
+    let processed = harness.process(&[approve], &[&stranger]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::NotGuardian.into())
+    );
+}
+
+#[tokio::test]
+async fn executes_the_recovery_after_the_delay() {
+    let (mut harness, _guardian, new_owner) = approved_recovery().await;
+    let payer = harness.payer();
+    harness.advance_time(RECOVERY_DELAY).await;
+
+    let processed = harness
+        .execute(&[execute_recovery(&payer, &payer, &new_owner)], &[])
+        .await;
+
+    assert!(processed.events.iter().any(|event| matches!(
+        event,
+        ProgramEvent::OwnerRecovered(recovered) if recovered.new_owner == new_owner
+    )));
+    let address = pda::user_config_address(&new_owner).0;
+    let account = harness.account(&address).await.unwrap();
+    let user_config: UserConfig = decode_zero_copy(&address, &account.data).unwrap();
+    assert_eq!({ user_config.owner }, new_owner);
+    assert_eq!(user_config.effective_slippage_bps(), 75);
+    assert!(harness
+        .account(&pda::user_config_address(&payer).0)
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn rejects_a_recovery_before_the_delay() {
+    let (mut harness, _guardian, new_owner) = approved_recovery().await;
+    let payer = harness.payer();
+    harness.advance_time(RECOVERY_DELAY - 60).await;
+    let execute = execute_recovery(&payer, &payer, &new_owner);
+    let processed = harness.process(&[execute], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::RecoveryNotReady.into())
+    );
+}
+
+#[tokio::test]
+async fn the_owner_can_cancel_a_pending_recovery() {
+    let (mut harness, _guardian, _new_owner) = approved_recovery().await;
+    let payer = harness.payer();
+    harness.execute(&[cancel_recovery(&payer)], &[]).await;
+    let recovery: RecoveryConfig = harness
+        .anchor_account(&pda::recovery_config_address(&payer).0)
+        .await;
+    assert_eq!(recovery.pending_owner, Pubkey::default());
+    assert_eq!(recovery.ready_at, 0);
+}
+
+#[tokio::test]
+async fn rejects_a_cancel_with_nothing_pending() {
+    let mut harness = Harness::start().await;
+    let payer = harness.payer();
+    harness
+        .execute(&[set_guardians(&payer, vec![Pubkey::new_unique()], 1)], &[])
+        .await;
+    let processed = harness
+        .process(&[cancel_recovery(&payer)], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::NoRecoveryPending.into())
+    );
+}
@@ -0,0 +1,282 @@
+//! Session keys: creating, limiting and revoking them, and the swaps they
+//! sign against the owner's approval.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use raydium_integration::{
+    accounts, instruction, pda, CustomError, Session, SessionSpendLimit, MAX_SESSION_DURATION,
+    MAX_SESSION_POOLS, RAYDIUM_CLMM_PROGRAM_ID, SESSION_DIRECTION_ANY, SESSION_SCOPE_SWAP,
+};
+use raydium_integration_client::builders::{approve_session_delegate, PositionRange};
+use raydium_integration_client::events::ProgramEvent;
+use raydium_integration_client::helpers::{tick_array_address, tick_array_start_index};
+use raydium_integration_client::PoolKeys;
+use raydium_integration_harness::{proxy_instruction, Harness};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn session_address(owner: &Pubkey, session_key: &Pubkey) -> Pubkey {
+    pda::session_address(&pda::user_config_address(owner).0, session_key).0
+}
+
+fn create_session(owner: &Pubkey, session_key: &Pubkey, expiry: i64, scope: u8) -> Instruction {
+    proxy_instruction(
+        accounts::CreateSession {
+            owner: *owner,
+            payer: *owner,
+            user_cfg: pda::user_config_address(owner).0,
+            session: session_address(owner, session_key),
+            system_program: anchor_lang::system_program::ID,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CreateSession {
+            session_key: *session_key,
+            expiry,
+            scope,
+        },
+    )
+}
+
+fn set_session_limits(
+    owner: &Pubkey,
+    session_key: &Pubkey,
+    allowed_pools: Vec<Pubkey>,
+    spend_limits: Vec<SessionSpendLimit>,
+) -> Instruction {
+    proxy_instruction(
+        accounts::SetSessionLimits {
+            owner: *owner,
+            session: session_address(owner, session_key),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::SetSessionLimits {
+            allowed_pools,
+            direction: SESSION_DIRECTION_ANY,
+            spend_limits,
+        },
+    )
+}
+
+fn revoke_session(authority: &Pubkey, owner: &Pubkey, session_key: &Pubkey) -> Instruction {
+    proxy_instruction(
+        accounts::RevokeSession {
+            authority: *authority,
+            owner: *owner,
+            session: session_address(owner, session_key),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::RevokeSession {},
+    )
+}
+
+/*
+ * Sell exactly `amount` of token_1 for token_0 between the owner's
+ * associated token accounts, signed by the session key. Upward from the
+ * pool's 1:1 price, so the tick array at the current price is enough
+ */
+fn session_swap(
+    pool: &PoolKeys,
+    owner: &Pubkey,
+    session_key: &Pubkey,
+    amount: u64,
+    expected_amount_out: u64,
+) -> Instruction {
+    let start_index = tick_array_start_index(pool.tick_current, pool.tick_spacing);
+    proxy_instruction(
+        accounts::SessionSwap {
+            clmm_program: RAYDIUM_CLMM_PROGRAM_ID,
+            session_key: *session_key,
+            session: session_address(owner, session_key),
+            user_cfg: pda::user_config_address(owner).0,
+            session_authority: pda::session_authority_address(owner).0,
+            amm_config: pool.amm_config,
+            pool_state: pool.address,
+            input_token_account: get_associated_token_address(owner, &pool.token_mint_1),
+            output_token_account: get_associated_token_address(owner, &pool.token_mint_0),
+            input_vault: pool.token_vault_1,
+            output_vault: pool.token_vault_0,
+            input_vault_mint: pool.token_mint_1,
+            output_vault_mint: pool.token_mint_0,
+            observation_state: pool.observation_key,
+            token_program: spl_token::ID,
+            tick_array: tick_array_address(&pool.address, start_index).0,
+            pool_stats: None,
+            global_stats: None,
+            tick_array_bitmap_extension: pool.tick_array_bitmap_extension,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            global_config: pda::global_config_address().0,
+            fee_mint: None,
+            treasury_token_account: None,
+            insurance_vault: None,
+            round_trip_counter: pda::round_trip_counter_address(owner, &pool.address).0,
+            pool_creator_record: None,
+            creator_fee_vault: None,
+            swap_validators: pda::swap_validators_address().0,
+            destination_allowlist: pda::destination_allowlist_address(owner).0,
+            mint_extension_opt_in: None,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::SessionSwap {
+            amount,
+            expected_other_amount: expected_amount_out,
+            sqrt_price_limit_x64: 0,
+            is_base_input: true,
+        },
+    )
+}
+
+/*
+ * A liquid pool and a swap session of the payer's, approved to spend 10
+ * token_1 from their associated token account
+ */
+async fn session_harness() -> (Harness, PoolKeys, Keypair) {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    harness
+        .open_position(
+            &pool,
+            PositionRange::Ticks {
+                tick_lower_index: -1200,
+                tick_upper_index: 1200,
+            },
+            100_000_000_000,
+            100_000_000_000,
+        )
+        .await;
+    let session_key = Keypair::new();
+    let expiry = harness.clock().await.unix_timestamp + 3_600;
+    let input = get_associated_token_address(&payer, &pool.token_mint_1);
+    harness
+        .execute(
+            &[
+                create_session(&payer, &session_key.pubkey(), expiry, SESSION_SCOPE_SWAP),
+                approve_session_delegate(&payer, &input, 10_000_000),
+            ],
+            &[],
+        )
+        .await;
+    (harness, pool, session_key)
+}
+
+#[tokio::test]
+async fn rejects_a_session_past_the_longest_duration() {
+    let mut harness = Harness::start().await;
+    let payer = harness.payer();
+    let expiry = harness.clock().await.unix_timestamp + MAX_SESSION_DURATION + 1;
+    let create = create_session(&payer, &Pubkey::new_unique(), expiry, SESSION_SCOPE_SWAP);
+    let processed = harness.process(&[create], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::InvalidSessionExpiry.into())
+    );
+}
+
+#[tokio::test]
+async fn session_swaps_spend_the_owners_approval() {
+    let (mut harness, pool, session_key) = session_harness().await;
+    let payer = harness.payer();
+    let output = get_associated_token_address(&payer, &pool.token_mint_0);
+    let before = harness.token_balance(&output).await;
+
+    let swap = session_swap(&pool, &payer, &session_key.pubkey(), 1_000_000, 990_000);
+    let processed = harness.execute(&[swap], &[&session_key]).await;
+
+    assert!(harness.token_balance(&output).await - before >= 990_000 * 99 / 100);
+    assert!(processed.events.iter().any(
+        |event| matches!(event, ProgramEvent::SwapExecuted(swap) if swap.pool == pool.address)
+    ));
+}
+
+#[tokio::test]
+async fn rejects_a_session_swap_without_the_approval() {
+    let (mut harness, pool, session_key) = session_harness().await;
+    let payer = harness.payer();
+    // Past the 10 token_1 approved
+    let swap = session_swap(&pool, &payer, &session_key.pubkey(), 20_000_000, 19_800_000);
+    let processed = harness.process(&[swap], &[&session_key]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::SessionDelegateNotApproved.into())
+    );
+}
+
+#[tokio::test]
+async fn session_spend_caps_hold_across_swaps() {
+    let (mut harness, pool, session_key) = session_harness().await;
+    let payer = harness.payer();
+    let limits = set_session_limits(
+        &payer,
+        &session_key.pubkey(),
+        vec![pool.address],
+        vec![SessionSpendLimit {
+            mint: pool.token_mint_1,
+            max_trade_amount: 0,
+            spend_cap: 1_500_000,
+        }],
+    );
+    harness.execute(&[limits], &[]).await;
+
+    let swap = session_swap(&pool, &payer, &session_key.pubkey(), 1_000_000, 990_000);
+    harness.execute(&[swap], &[&session_key]).await;
+    let session: Session = harness
+        .anchor_account(&session_address(&payer, &session_key.pubkey()))
+        .await;
+    assert_eq!(session.spent_in(&pool.token_mint_1), 1_000_000);
+
+    let swap = session_swap(&pool, &payer, &session_key.pubkey(), 1_000_000, 990_000);
+    let processed = harness.process(&[swap], &[&session_key]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::SessionCapExceeded.into())
+    );
+}
+
+#[tokio::test]
+async fn rejects_more_session_pools_than_fit() {
+    let (mut harness, _pool, session_key) = session_harness().await;
+    let payer = harness.payer();
+    let pools = (0..=MAX_SESSION_POOLS)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    let limits = set_session_limits(&payer, &session_key.pubkey(), pools, Vec::new());
+    let processed = harness.process(&[limits], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::TooManySessionPools.into())
+    );
+}
+
+#[tokio::test]
+async fn the_session_key_can_revoke_its_session() {
+    let (mut harness, _pool, session_key) = session_harness().await;
+    let payer = harness.payer();
+    let revoke = revoke_session(&session_key.pubkey(), &payer, &session_key.pubkey());
+    harness.execute(&[revoke], &[&session_key]).await;
+    let session = session_address(&payer, &session_key.pubkey());
+    assert!(harness.account(&session).await.is_none());
+}
+
+#[tokio::test]
+async fn rejects_a_revoke_by_anyone_else() {
+    let (mut harness, _pool, session_key) = session_harness().await;
+    let payer = harness.payer();
+    let stranger = Keypair::new();
+    let revoke = revoke_session(&stranger.pubkey(), &payer, &session_key.pubkey());
+    let processed = harness.process(&[revoke], &[&stranger]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::Unauthorized.into())
+    );
+}
@@ -0,0 +1,324 @@
+//! Positions co-owned through shares: locking the NFT, depositing and
+//! withdrawing for shares, and claiming fees.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::spl_token;
+use raydium_integration::{
+    accounts, instruction, pda, CustomError, SharedPosition, SharedPositionShare,
+    RAYDIUM_CLMM_PROGRAM_ID, SPL_MEMO_PROGRAM_ID,
+};
+use raydium_integration_client::builders::{
+    DecreaseLiquidityBuilder, PositionRange, ProxySwapBuilder,
+};
+use raydium_integration_client::helpers::{
+    position_tick_array_addresses, protocol_position_address,
+};
+use raydium_integration_client::{PoolKeys, PositionKeys};
+use raydium_integration_harness::{proxy_instruction, Harness};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn create_shared_position(position: &PositionKeys, creator: &Pubkey) -> Instruction {
+    let shared_position = pda::shared_position_address(&position.nft_mint).0;
+    proxy_instruction(
+        accounts::CreateSharedPosition {
+            creator: *creator,
+            position_nft_mint: position.nft_mint,
+            creator_nft_account: get_associated_token_address_with_program_id(
+                creator,
+                &position.nft_mint,
+                &position.nft_token_program,
+            ),
+            personal_position: position.personal_position,
+            shared_position,
+            creator_share: pda::shared_share_address(&shared_position, creator).0,
+            shared_nft_account: get_associated_token_address_with_program_id(
+                &shared_position,
+                &position.nft_mint,
+                &position.nft_token_program,
+            ),
+            nft_token_program: position.nft_token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(creator).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CreateSharedPosition {},
+    )
+}
+
+/*
+ * Accounts of `shared_deposit`, `shared_withdraw` and `claim_shared_fees`,
+ * paying from and to the contributor's associated token accounts
+ */
+fn shared_action(
+    pool: &PoolKeys,
+    position: &PositionKeys,
+    contributor: &Pubkey,
+) -> accounts::SharedPositionAction {
+    let shared_position = pda::shared_position_address(&position.nft_mint).0;
+    let (tick_array_lower, tick_array_upper) = position_tick_array_addresses(
+        &pool.address,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        pool.tick_spacing,
+    );
+    let token_account = |owner: &Pubkey, mint: &Pubkey| {
+        get_associated_token_address_with_program_id(owner, mint, &spl_token::ID)
+    };
+    accounts::SharedPositionAction {
+        clmm_program: RAYDIUM_CLMM_PROGRAM_ID,
+        contributor: *contributor,
+        shared_position,
+        share: pda::shared_share_address(&shared_position, contributor).0,
+        shared_nft_account: get_associated_token_address_with_program_id(
+            &shared_position,
+            &position.nft_mint,
+            &position.nft_token_program,
+        ),
+        personal_position: position.personal_position,
+        pool_state: pool.address,
+        protocol_position: protocol_position_address(
+            &pool.address,
+            position.tick_lower_index,
+            position.tick_upper_index,
+        )
+        .0,
+        tick_array_lower,
+        tick_array_upper,
+        token_vault_0: pool.token_vault_0,
+        token_vault_1: pool.token_vault_1,
+        shared_token_account_0: token_account(&shared_position, &pool.token_mint_0),
+        shared_token_account_1: token_account(&shared_position, &pool.token_mint_1),
+        contributor_token_account_0: token_account(contributor, &pool.token_mint_0),
+        contributor_token_account_1: token_account(contributor, &pool.token_mint_1),
+        vault_0_mint: pool.token_mint_0,
+        vault_1_mint: pool.token_mint_1,
+        token_program: spl_token::ID,
+        token_program_2022: anchor_spl::token_2022::ID,
+        memo_program: SPL_MEMO_PROGRAM_ID,
+        system_program: anchor_lang::system_program::ID,
+        global_config: pda::global_config_address().0,
+        user_cfg: pda::user_config_address(contributor).0,
+        instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+        destination_allowlist: pda::destination_allowlist_address(contributor).0,
+        mint_extension_opt_in: None,
+        #[cfg(feature = "event-cpi")]
+        event_authority: pda::event_authority_address().0,
+        #[cfg(feature = "event-cpi")]
+        program: raydium_integration::ID,
+    }
+}
+
+/*
+ * The payer's position locked as a shared position, with the shared
+ * position's token accounts created
+ */
+async fn shared_position() -> (Harness, PoolKeys, PositionKeys) {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let position = harness
+        .open_position(
+            &pool,
+            PositionRange::Ticks {
+                tick_lower_index: -1200,
+                tick_upper_index: 1200,
+            },
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .await;
+    let shared_position = pda::shared_position_address(&position.nft_mint).0;
+    harness.fund(&pool.token_mint_0, &shared_position, 0).await;
+    harness.fund(&pool.token_mint_1, &shared_position, 0).await;
+    harness
+        .execute(&[create_shared_position(&position, &payer)], &[])
+        .await;
+    (harness, pool, position)
+}
+
+/*
+ * A contributor with 10 of each token and the SOL for their share's rent
+ */
+async fn contributor(harness: &mut Harness, pool: &PoolKeys) -> Keypair {
+    let contributor = harness.funded_keypair(1_000_000_000).await;
+    for mint in [pool.token_mint_0, pool.token_mint_1] {
+        harness
+            .fund(&mint, &contributor.pubkey(), 10_000_000_000)
+            .await;
+    }
+    contributor
+}
+
+#[tokio::test]
+async fn locks_the_nft_for_one_share_per_unit_of_liquidity() {
+    let (mut harness, _pool, position) = shared_position().await;
+    let payer = harness.payer();
+    let shared_address = pda::shared_position_address(&position.nft_mint).0;
+    let shared: SharedPosition = harness.anchor_account(&shared_address).await;
+    assert_eq!(shared.total_shares, position.liquidity);
+    let share: SharedPositionShare = harness
+        .anchor_account(&pda::shared_share_address(&shared_address, &payer).0)
+        .await;
+    assert_eq!(share.shares, position.liquidity);
+    let custody = get_associated_token_address_with_program_id(
+        &shared_address,
+        &position.nft_mint,
+        &position.nft_token_program,
+    );
+    assert_eq!(harness.token_balance(&custody).await, 1);
+}
+
+#[tokio::test]
+async fn rejects_sharing_an_empty_position() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let position = harness
+        .open_position(
+            &pool,
+            PositionRange::FullRange,
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .await;
+    let decrease = DecreaseLiquidityBuilder::new(&pool, &position, &payer, position.liquidity)
+        .unwrap()
+        .instruction();
+    harness.execute(&[decrease], &[]).await;
+
+    let create = create_shared_position(&position, &payer);
+    let processed = harness.process(&[create], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::ZeroLiquidity.into())
+    );
+}
+
+#[tokio::test]
+async fn deposits_for_shares() {
+    let (mut harness, pool, position) = shared_position().await;
+    let contributor = contributor(&mut harness, &pool).await;
+    let liquidity = position.liquidity / 10;
+    let deposit = proxy_instruction(
+        shared_action(&pool, &position, &contributor.pubkey()),
+        instruction::SharedDeposit {
+            liquidity,
+            amount_0_max: 10_000_000_000,
+            amount_1_max: 10_000_000_000,
+        },
+    );
+    harness.execute(&[deposit], &[&contributor]).await;
+
+    let shared_address = pda::shared_position_address(&position.nft_mint).0;
+    let share: SharedPositionShare = harness
+        .anchor_account(&pda::shared_share_address(&shared_address, &contributor.pubkey()).0)
+        .await;
+    assert_eq!(share.shares, liquidity);
+    let shared: SharedPosition = harness.anchor_account(&shared_address).await;
+    assert_eq!(shared.total_shares, position.liquidity + liquidity);
+}
+
+#[tokio::test]
+async fn rejects_a_deposit_of_no_liquidity() {
+    let (mut harness, pool, position) = shared_position().await;
+    let contributor = contributor(&mut harness, &pool).await;
+    let deposit = proxy_instruction(
+        shared_action(&pool, &position, &contributor.pubkey()),
+        instruction::SharedDeposit {
+            liquidity: 0,
+            amount_0_max: 10_000_000_000,
+            amount_1_max: 10_000_000_000,
+        },
+    );
+    let processed = harness.process(&[deposit], &[&contributor]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::ZeroLiquidity.into())
+    );
+}
+
+#[tokio::test]
+async fn withdraws_shares_for_their_liquidity() {
+    let (mut harness, pool, position) = shared_position().await;
+    let payer = harness.payer();
+    let token_account_0 =
+        get_associated_token_address_with_program_id(&payer, &pool.token_mint_0, &spl_token::ID);
+    let before = harness.token_balance(&token_account_0).await;
+    let withdraw = proxy_instruction(
+        shared_action(&pool, &position, &payer),
+        instruction::SharedWithdraw {
+            shares: position.liquidity / 2,
+            amount_0_min: 0,
+            amount_1_min: 0,
+        },
+    );
+    harness.execute(&[withdraw], &[]).await;
+
+    assert!(harness.token_balance(&token_account_0).await > before);
+    let shared: SharedPosition = harness
+        .anchor_account(&pda::shared_position_address(&position.nft_mint).0)
+        .await;
+    assert_eq!(
+        shared.total_shares,
+        position.liquidity - position.liquidity / 2
+    );
+}
+
+#[tokio::test]
+async fn rejects_withdrawing_more_shares_than_held() {
+    let (mut harness, pool, position) = shared_position().await;
+    let payer = harness.payer();
+    let withdraw = proxy_instruction(
+        shared_action(&pool, &position, &payer),
+        instruction::SharedWithdraw {
+            shares: position.liquidity + 1,
+            amount_0_min: 0,
+            amount_1_min: 0,
+        },
+    );
+    let processed = harness.process(&[withdraw], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::InsufficientShares.into())
+    );
+}
+
+#[tokio::test]
+async fn claims_the_fees_a_swap_earned() {
+    let (mut harness, pool, position) = shared_position().await;
+    let payer = harness.payer();
+    // Upward from the 1:1 price, paying the fee in token_1
+    let swap = ProxySwapBuilder::exact_in(&pool, &payer, &pool.token_mint_1, 10_000_000, 9_900_000)
+        .unwrap()
+        .instruction();
+    harness.execute(&[swap], &[]).await;
+    let token_account_1 =
+        get_associated_token_address_with_program_id(&payer, &pool.token_mint_1, &spl_token::ID);
+    let before = harness.token_balance(&token_account_1).await;
+
+    let claim = proxy_instruction(
+        shared_action(&pool, &position, &payer),
+        instruction::ClaimSharedFees {},
+    );
+    harness.execute(&[claim], &[]).await;
+
+    assert!(harness.token_balance(&token_account_1).await > before);
+}
+
+#[tokio::test]
+async fn rejects_a_claim_paid_to_someone_elses_account() {
+    let (mut harness, pool, position) = shared_position().await;
+    let payer = harness.payer();
+    let other = Pubkey::new_unique();
+    let other_account = harness.fund(&pool.token_mint_0, &other, 0).await;
+    let mut action = shared_action(&pool, &position, &payer);
+    action.contributor_token_account_0 = other_account;
+    let claim = proxy_instruction(action, instruction::ClaimSharedFees {});
+    let processed = harness.process(&[claim], &[]).await.unwrap();
+    assert!(processed.result.is_err());
+}
@@ -0,0 +1,280 @@
+//! Splitting part of a position into a new NFT over the same range, and
+//! merging positions with the same range back into one.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::spl_token;
+use raydium_integration::{
+    accounts, instruction, pda, CustomError, RAYDIUM_CLMM_PROGRAM_ID, SPL_MEMO_PROGRAM_ID,
+};
+use raydium_integration_client::builders::PositionRange;
+use raydium_integration_client::helpers::{
+    personal_position_address, position_tick_array_addresses, protocol_position_address,
+};
+use raydium_integration_client::{PoolKeys, PositionKeys};
+use raydium_integration_harness::{proxy_instruction, Harness};
+use solana_sdk::signature::{Keypair, Signer};
+
+const RANGE: PositionRange = PositionRange::Ticks {
+    tick_lower_index: -1200,
+    tick_upper_index: 1200,
+};
+
+fn split_position(
+    pool: &PoolKeys,
+    position: &PositionKeys,
+    nft_owner: &Pubkey,
+    new_position_nft_mint: &Pubkey,
+    liquidity_to_move: u128,
+) -> Instruction {
+    let (tick_array_lower, tick_array_upper) = position_tick_array_addresses(
+        &pool.address,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        pool.tick_spacing,
+    );
+    proxy_instruction(
+        accounts::SplitPosition {
+            clmm_program: RAYDIUM_CLMM_PROGRAM_ID,
+            nft_owner: *nft_owner,
+            nft_account: get_associated_token_address_with_program_id(
+                nft_owner,
+                &position.nft_mint,
+                &position.nft_token_program,
+            ),
+            personal_position: position.personal_position,
+            pool_state: pool.address,
+            protocol_position: protocol_position_address(
+                &pool.address,
+                position.tick_lower_index,
+                position.tick_upper_index,
+            )
+            .0,
+            tick_array_lower,
+            tick_array_upper,
+            new_position_nft_mint: *new_position_nft_mint,
+            new_position_nft_account: get_associated_token_address_with_program_id(
+                nft_owner,
+                new_position_nft_mint,
+                &spl_token::ID,
+            ),
+            new_personal_position: personal_position_address(new_position_nft_mint).0,
+            metadata_account: None,
+            token_account_0: get_associated_token_address_with_program_id(
+                nft_owner,
+                &pool.token_mint_0,
+                &spl_token::ID,
+            ),
+            token_account_1: get_associated_token_address_with_program_id(
+                nft_owner,
+                &pool.token_mint_1,
+                &spl_token::ID,
+            ),
+            token_vault_0: pool.token_vault_0,
+            token_vault_1: pool.token_vault_1,
+            vault_0_mint: pool.token_mint_0,
+            vault_1_mint: pool.token_mint_1,
+            rent: anchor_lang::solana_program::sysvar::rent::ID,
+            system_program: anchor_lang::system_program::ID,
+            token_program: spl_token::ID,
+            token_program_2022: anchor_spl::token_2022::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            metadata_program: anchor_spl::metadata::ID,
+            memo_program: SPL_MEMO_PROGRAM_ID,
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(nft_owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(nft_owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::SplitPosition {
+            liquidity_to_move,
+            with_metadata: false,
+        },
+    )
+}
+
+/*
+ * Fold `source` into `target`, with the tick arrays and protocol position
+ * of the source's range
+ */
+fn merge_positions(
+    pool: &PoolKeys,
+    source: &PositionKeys,
+    target: &PositionKeys,
+    nft_owner: &Pubkey,
+) -> Instruction {
+    let (tick_array_lower, tick_array_upper) = position_tick_array_addresses(
+        &pool.address,
+        source.tick_lower_index,
+        source.tick_upper_index,
+        pool.tick_spacing,
+    );
+    let nft_account = |position: &PositionKeys| {
+        get_associated_token_address_with_program_id(
+            nft_owner,
+            &position.nft_mint,
+            &position.nft_token_program,
+        )
+    };
+    proxy_instruction(
+        accounts::MergePositions {
+            clmm_program: RAYDIUM_CLMM_PROGRAM_ID,
+            nft_owner: *nft_owner,
+            source_position_nft_mint: source.nft_mint,
+            source_nft_account: nft_account(source),
+            source_personal_position: source.personal_position,
+            target_nft_account: nft_account(target),
+            target_personal_position: target.personal_position,
+            pool_state: pool.address,
+            protocol_position: protocol_position_address(
+                &pool.address,
+                source.tick_lower_index,
+                source.tick_upper_index,
+            )
+            .0,
+            tick_array_lower,
+            tick_array_upper,
+            token_account_0: get_associated_token_address_with_program_id(
+                nft_owner,
+                &pool.token_mint_0,
+                &spl_token::ID,
+            ),
+            token_account_1: get_associated_token_address_with_program_id(
+                nft_owner,
+                &pool.token_mint_1,
+                &spl_token::ID,
+            ),
+            token_vault_0: pool.token_vault_0,
+            token_vault_1: pool.token_vault_1,
+            vault_0_mint: pool.token_mint_0,
+            vault_1_mint: pool.token_mint_1,
+            system_program: anchor_lang::system_program::ID,
+            token_program: spl_token::ID,
+            token_program_2022: anchor_spl::token_2022::ID,
+            memo_program: SPL_MEMO_PROGRAM_ID,
+            position_registry: None,
+            position_registry_page: None,
+            source_cost_basis: None,
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(nft_owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(nft_owner).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::MergePositions {},
+    )
+}
+
+/*
+ * Two of the payer's positions over the same range
+ */
+async fn two_positions() -> (Harness, PoolKeys, PositionKeys, PositionKeys) {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let source = harness
+        .open_position(&pool, RANGE, 1_000_000_000, 1_000_000_000)
+        .await;
+    let target = harness
+        .open_position(&pool, RANGE, 2_000_000_000, 2_000_000_000)
+        .await;
+    (harness, pool, source, target)
+}
+
+#[tokio::test]
+async fn splits_liquidity_into_a_new_position() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let position = harness
+        .open_position(&pool, RANGE, 1_000_000_000, 1_000_000_000)
+        .await;
+    let new_nft = Keypair::new();
+    let moved = position.liquidity / 4;
+
+    let split = split_position(&pool, &position, &payer, &new_nft.pubkey(), moved);
+    harness.execute(&[split], &[&new_nft]).await;
+
+    let source = harness.position(&position.nft_mint).await;
+    assert_eq!(source.liquidity, position.liquidity - moved);
+    let split_off = harness.position(&new_nft.pubkey()).await;
+    assert_eq!(split_off.liquidity, moved);
+    assert_eq!(
+        (split_off.tick_lower_index, split_off.tick_upper_index),
+        (position.tick_lower_index, position.tick_upper_index)
+    );
+}
+
+#[tokio::test]
+async fn rejects_splitting_off_all_the_liquidity() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let position = harness
+        .open_position(&pool, RANGE, 1_000_000_000, 1_000_000_000)
+        .await;
+    let new_nft = Keypair::new();
+    let split = split_position(
+        &pool,
+        &position,
+        &payer,
+        &new_nft.pubkey(),
+        position.liquidity,
+    );
+    let processed = harness.process(&[split], &[&new_nft]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::InvalidSplitLiquidity.into())
+    );
+}
+
+#[tokio::test]
+async fn merges_the_source_into_the_target() {
+    let (mut harness, pool, source, target) = two_positions().await;
+    let payer = harness.payer();
+
+    let merge = merge_positions(&pool, &source, &target, &payer);
+    harness.execute(&[merge], &[]).await;
+
+    let merged = harness.position(&target.nft_mint).await;
+    assert_eq!(merged.liquidity, source.liquidity + target.liquidity);
+    assert!(harness.account(&source.personal_position).await.is_none());
+}
+
+#[tokio::test]
+async fn rejects_merging_a_position_into_itself() {
+    let (mut harness, pool, source, _target) = two_positions().await;
+    let payer = harness.payer();
+    let merge = merge_positions(&pool, &source, &source, &payer);
+    let processed = harness.process(&[merge], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::SamePosition.into())
+    );
+}
+
+#[tokio::test]
+async fn rejects_merging_different_ranges() {
+    let (mut harness, pool, source, _target) = two_positions().await;
+    let payer = harness.payer();
+    let target = harness
+        .open_position(
+            &pool,
+            PositionRange::Ticks {
+                tick_lower_index: -600,
+                tick_upper_index: 600,
+            },
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .await;
+    let merge = merge_positions(&pool, &source, &target, &payer);
+    let processed = harness.process(&[merge], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::PositionRangeMismatch.into())
+    );
+}
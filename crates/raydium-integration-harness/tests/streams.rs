@@ -0,0 +1,263 @@
+//! Position streams: locking an NFT and claiming its liquidity as it
+//! unlocks, then the emptied NFT once all of it is claimed.
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::spl_token;
+use raydium_integration::{
+    accounts, instruction, pda, CustomError, PositionStream, RAYDIUM_CLMM_PROGRAM_ID,
+    SPL_MEMO_PROGRAM_ID,
+};
+use raydium_integration_client::builders::PositionRange;
+use raydium_integration_client::helpers::{
+    position_tick_array_addresses, protocol_position_address,
+};
+use raydium_integration_client::{PoolKeys, PositionKeys};
+use raydium_integration_harness::{proxy_instruction, Harness};
+use solana_sdk::signature::{Keypair, Signer};
+
+const STREAM_DURATION: i64 = 1_000;
+
+fn create_position_stream(
+    position: &PositionKeys,
+    owner: &Pubkey,
+    beneficiary: &Pubkey,
+    start_ts: i64,
+    end_ts: i64,
+) -> Instruction {
+    let position_stream = pda::position_stream_address(&position.nft_mint).0;
+    proxy_instruction(
+        accounts::CreatePositionStream {
+            owner: *owner,
+            beneficiary: *beneficiary,
+            position_nft_mint: position.nft_mint,
+            owner_nft_account: get_associated_token_address_with_program_id(
+                owner,
+                &position.nft_mint,
+                &position.nft_token_program,
+            ),
+            personal_position: position.personal_position,
+            position_stream,
+            stream_nft_account: get_associated_token_address_with_program_id(
+                &position_stream,
+                &position.nft_mint,
+                &position.nft_token_program,
+            ),
+            nft_token_program: position.nft_token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(owner).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::CreatePositionStream { start_ts, end_ts },
+    )
+}
+
+/*
+ * Claim what has unlocked to the beneficiary's associated token accounts
+ */
+fn claim_unlocked(pool: &PoolKeys, position: &PositionKeys, beneficiary: &Pubkey) -> Instruction {
+    let position_stream = pda::position_stream_address(&position.nft_mint).0;
+    let (tick_array_lower, tick_array_upper) = position_tick_array_addresses(
+        &pool.address,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        pool.tick_spacing,
+    );
+    proxy_instruction(
+        accounts::ClaimUnlocked {
+            clmm_program: RAYDIUM_CLMM_PROGRAM_ID,
+            beneficiary: *beneficiary,
+            position_stream,
+            position_nft_mint: position.nft_mint,
+            stream_nft_account: get_associated_token_address_with_program_id(
+                &position_stream,
+                &position.nft_mint,
+                &position.nft_token_program,
+            ),
+            beneficiary_nft_account: get_associated_token_address_with_program_id(
+                beneficiary,
+                &position.nft_mint,
+                &position.nft_token_program,
+            ),
+            personal_position: position.personal_position,
+            pool_state: pool.address,
+            protocol_position: protocol_position_address(
+                &pool.address,
+                position.tick_lower_index,
+                position.tick_upper_index,
+            )
+            .0,
+            token_vault_0: pool.token_vault_0,
+            token_vault_1: pool.token_vault_1,
+            tick_array_lower,
+            tick_array_upper,
+            recipient_token_account_0: get_associated_token_address_with_program_id(
+                beneficiary,
+                &pool.token_mint_0,
+                &spl_token::ID,
+            ),
+            recipient_token_account_1: get_associated_token_address_with_program_id(
+                beneficiary,
+                &pool.token_mint_1,
+                &spl_token::ID,
+            ),
+            token_program: spl_token::ID,
+            token_program_2022: anchor_spl::token_2022::ID,
+            nft_token_program: position.nft_token_program,
+            memo_program: SPL_MEMO_PROGRAM_ID,
+            vault_0_mint: pool.token_mint_0,
+            vault_1_mint: pool.token_mint_1,
+            global_config: pda::global_config_address().0,
+            user_cfg: pda::user_config_address(beneficiary).0,
+            instructions_sysvar: Some(anchor_lang::solana_program::sysvar::instructions::ID),
+            destination_allowlist: pda::destination_allowlist_address(beneficiary).0,
+            #[cfg(feature = "event-cpi")]
+            event_authority: pda::event_authority_address().0,
+            #[cfg(feature = "event-cpi")]
+            program: raydium_integration::ID,
+        },
+        instruction::ClaimUnlocked {
+            amount_0_min: 0,
+            amount_1_min: 0,
+        },
+    )
+}
+
+/*
+ * The payer's position streamed to a beneficiary over `STREAM_DURATION`
+ * seconds from now, with the beneficiary's token and NFT accounts created
+ */
+async fn streamed_position() -> (Harness, PoolKeys, PositionKeys, Keypair) {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let position = harness
+        .open_position(
+            &pool,
+            PositionRange::Ticks {
+                tick_lower_index: -1200,
+                tick_upper_index: 1200,
+            },
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .await;
+    let beneficiary = harness.funded_keypair(1_000_000_000).await;
+    for mint in [pool.token_mint_0, pool.token_mint_1, position.nft_mint] {
+        harness.fund(&mint, &beneficiary.pubkey(), 0).await;
+    }
+    let start_ts = harness.clock().await.unix_timestamp;
+    let create = create_position_stream(
+        &position,
+        &payer,
+        &beneficiary.pubkey(),
+        start_ts,
+        start_ts + STREAM_DURATION,
+    );
+    harness.execute(&[create], &[]).await;
+    (harness, pool, position, beneficiary)
+}
+
+#[tokio::test]
+async fn locks_the_nft_for_the_beneficiary() {
+    let (mut harness, _pool, position, beneficiary) = streamed_position().await;
+    let address = pda::position_stream_address(&position.nft_mint).0;
+    let stream: PositionStream = harness.anchor_account(&address).await;
+    assert_eq!(stream.beneficiary, beneficiary.pubkey());
+    assert_eq!(stream.initial_liquidity, position.liquidity);
+    assert_eq!(stream.end_ts - stream.start_ts, STREAM_DURATION);
+    let custody = get_associated_token_address_with_program_id(
+        &address,
+        &position.nft_mint,
+        &position.nft_token_program,
+    );
+    assert_eq!(harness.token_balance(&custody).await, 1);
+}
+
+#[tokio::test]
+async fn rejects_a_stream_ending_before_it_starts() {
+    let (mut harness, pool) = Harness::start_with_pool().await;
+    let payer = harness.payer();
+    let position = harness
+        .open_position(
+            &pool,
+            PositionRange::FullRange,
+            1_000_000_000,
+            1_000_000_000,
+        )
+        .await;
+    let now = harness.clock().await.unix_timestamp;
+    let create = create_position_stream(&position, &payer, &Pubkey::new_unique(), now, now);
+    let processed = harness.process(&[create], &[]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::InvalidStreamSchedule.into())
+    );
+}
+
+#[tokio::test]
+async fn claims_what_has_unlocked() {
+    let (mut harness, pool, position, beneficiary) = streamed_position().await;
+    harness.advance_time(STREAM_DURATION / 2).await;
+
+    let claim = claim_unlocked(&pool, &position, &beneficiary.pubkey());
+    harness.execute(&[claim], &[&beneficiary]).await;
+
+    let stream: PositionStream = harness
+        .anchor_account(&pda::position_stream_address(&position.nft_mint).0)
+        .await;
+    assert_eq!(stream.claimed_liquidity, position.liquidity / 2);
+    let recipient = get_associated_token_address_with_program_id(
+        &beneficiary.pubkey(),
+        &pool.token_mint_0,
+        &spl_token::ID,
+    );
+    assert!(harness.token_balance(&recipient).await > 0);
+}
+
+#[tokio::test]
+async fn releases_the_nft_once_fully_claimed() {
+    let (mut harness, pool, position, beneficiary) = streamed_position().await;
+    harness.advance_time(STREAM_DURATION).await;
+
+    let claim = claim_unlocked(&pool, &position, &beneficiary.pubkey());
+    harness.execute(&[claim], &[&beneficiary]).await;
+
+    let address = pda::position_stream_address(&position.nft_mint).0;
+    assert!(harness.account(&address).await.is_none());
+    let nft_account = get_associated_token_address_with_program_id(
+        &beneficiary.pubkey(),
+        &position.nft_mint,
+        &position.nft_token_program,
+    );
+    assert_eq!(harness.token_balance(&nft_account).await, 1);
+}
+
+#[tokio::test]
+async fn rejects_a_claim_before_anything_unlocks() {
+    let (mut harness, pool, position, beneficiary) = streamed_position().await;
+    let claim = claim_unlocked(&pool, &position, &beneficiary.pubkey());
+    let processed = harness.process(&[claim], &[&beneficiary]).await.unwrap();
+    assert_eq!(
+        processed.custom_error(),
+        Some(CustomError::NothingToClaim.into())
+    );
+}
+
+#[tokio::test]
+async fn rejects_a_claim_by_anyone_but_the_beneficiary() {
+    let (mut harness, pool, position, _beneficiary) = streamed_position().await;
+    harness.advance_time(STREAM_DURATION / 2).await;
+    let stranger = harness.funded_keypair(1_000_000_000).await;
+    for mint in [pool.token_mint_0, pool.token_mint_1, position.nft_mint] {
+        harness.fund(&mint, &stranger.pubkey(), 0).await;
+    }
+    let claim = claim_unlocked(&pool, &position, &stranger.pubkey());
+    let processed = harness.process(&[claim], &[&stranger]).await.unwrap();
+    assert!(processed.result.is_err());
+}
@@ -0,0 +1,644 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SlippageSet {
+    pub owner: Pubkey,
+    pub slippage_bps: u16,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+pub struct SwapExecuted {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub expected_amount: u64,
+    pub slippage_bps: u16,
+    pub is_base_input: bool,
+    pub timestamp: i64,
+    pub sqrt_price_before_x64: u128,
+    pub sqrt_price_after_x64: u128,
+    pub tick_after: i32,
+    pub amm_fee: u64,
+    pub protocol_fee: u64,
+    pub realized_slippage_bps: i64,
+    pub default_slippage: bool,
+    pub user_seq: u64,
+    pub op_id: u64,
+    pub ui_amount_in: u64,
+    pub ui_amount_out: u64,
+}
+
+#[event]
+pub struct PositionOpened {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+    pub owner: Pubkey,
+    pub user_seq: u64,
+    pub ui_amount_0: u64,
+    pub ui_amount_1: u64,
+}
+
+#[event]
+pub struct LiquidityIncreased {
+    pub user: Pubkey,
+    pub position_nft: Pubkey,
+    pub liquidity_added: u128,
+    pub amount_0_added: u64,
+    pub amount_1_added: u64,
+    pub ui_amount_0_added: u64,
+    pub ui_amount_1_added: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+pub struct LiquidityDecreased {
+    pub user: Pubkey,
+    pub position_nft: Pubkey,
+    pub liquidity_removed: u128,
+    pub amount_0_removed: u64,
+    pub amount_1_removed: u64,
+    pub ui_amount_0_removed: u64,
+    pub ui_amount_1_removed: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct PositionClosed {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub timestamp: i64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct FeesCollected {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+    pub ui_amount_0: u64,
+    pub ui_amount_1: u64,
+}
+
+#[event]
+pub struct RewardsCollected {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct PositionPnlRealized {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub deposited_0: u64,
+    pub deposited_1: u64,
+    pub withdrawn_0: u64,
+    pub withdrawn_1: u64,
+    pub fees_collected_0: u64,
+    pub fees_collected_1: u64,
+    pub cost_token_1: u128,
+    pub proceeds_token_1: u128,
+    pub realized_pnl_token_1: i128,
+    pub timestamp: i64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct FeeCheckpointStamped {
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub liquidity: u128,
+    pub cumulative_fees_0: u64,
+    pub cumulative_fees_1: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapPartiallyFilled {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub is_base_input: bool,
+    pub amount_requested: u64,
+    pub amount_filled: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct PositionSplit {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub source_position_nft: Pubkey,
+    pub new_position_nft: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity_moved: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionsMerged {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub source_position_nft: Pubkey,
+    pub target_position_nft: Pubkey,
+    pub liquidity_moved: u128,
+    pub timestamp: i64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct PositionStreamCreated {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub liquidity: u128,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct StreamClaimed {
+    pub beneficiary: Pubkey,
+    pub position_nft: Pubkey,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub claimed_liquidity: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SharedPositionCreated {
+    pub creator: Pubkey,
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub shares: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SharedPositionChanged {
+    pub contributor: Pubkey,
+    pub position_nft: Pubkey,
+    pub shares_delta: i128,
+    pub shares: u128,
+    pub total_shares: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DustSwept {
+    pub sweeper: Pubkey,
+    pub source: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SharedFeesClaimed {
+    pub contributor: Pubkey,
+    pub position_nft: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintExtensionOptInSet {
+    pub owner: Pubkey,
+    pub allowed_extensions: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OpenApprovalSet {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowedCallersSet {
+    pub owner: Pubkey,
+    pub programs: Vec<Pubkey>,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+pub struct ExposureCapsSet {
+    pub owner: Pubkey,
+    pub quote_mint: Pubkey,
+    pub max_pool_exposure: u64,
+    pub max_total_exposure: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+pub struct GlobalConfigUpdated {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    pub timestamp: i64,
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+}
+
+#[event]
+pub struct ParamChangeScheduled {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+    pub activation_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ParamChangeCancelled {
+    pub admin: Pubkey,
+    pub activation_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigsMigrated {
+    pub admin: Pubkey,
+    pub configs: u32,
+    pub normalized: u32,
+    pub timestamp: i64,
+    pub max_slippage_bps: u16,
+    pub resized: u32,
+}
+
+#[event]
+pub struct ProtocolFeeCollected {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    /// Mint the fee was paid in
+    pub mint: Pubkey,
+    pub fee: u64,
+    /// Part of `fee` routed to the insurance fund
+    pub insurance_amount: u64,
+    /// Part of `fee` routed to the pool creator's fee vault
+    pub creator_amount: u64,
+    /// The fee valued in input mint units
+    pub fee_in_input: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct RoundTripsFlagged {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    /// Round trips in the current window, the flagged swap included
+    pub round_trips: u16,
+    pub max_round_trips: u16,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+pub struct CreatorFeesClaimed {
+    pub creator: Pubkey,
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceClaimPaid {
+    /// Owner of the token account credited
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PointsRatesSet {
+    pub swap_points_per_unit: u64,
+    pub lp_points_per_unit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PointsClaimed {
+    pub owner: Pubkey,
+    pub amount: u128,
+    pub total_claimed: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeOracleSet {
+    pub input_mint: Pubkey,
+    pub fee_mint: Pubkey,
+    pub pool: Pubkey,
+    pub twap_window_secs: u32,
+    pub max_staleness_secs: u32,
+    pub max_deviation_ticks: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapValidatorsSet {
+    pub programs: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DestinationAdded {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub active_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DestinationRemoved {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DestinationAllowlistToggled {
+    pub owner: Pubkey,
+    pub enabled: bool,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardiansSet {
+    pub owner: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryApproved {
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    /// Zero until the threshold is met
+    pub ready_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecoveryCancelled {
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OwnerRecovered {
+    pub old_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expiry: i64,
+    pub scope: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionRevoked {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionLimitsSet {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub allowed_pools: Vec<Pubkey>,
+    pub direction: u8,
+    pub spend_limits: Vec<crate::SessionSpendLimit>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DutchOrderCreated {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub start_min_out: u64,
+    pub end_min_out: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct DutchOrderFilled {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub keeper: Pubkey,
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Auction price at the fill, as the least output accepted
+    pub min_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DutchOrderCancelled {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FloorOrderCreated {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub amount_in: u64,
+    pub floor_price_x64: u128,
+    pub daily_max: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FloorOrderFilled {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub keeper: Pubkey,
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Floor price applied to `amount_in`, as the least output accepted
+    pub min_out: u64,
+    /// Input sold in the current day, this fill included
+    pub sold_today: u64,
+    /// Whether the fill emptied the escrow and closed the order
+    pub sold_out: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FloorOrderCancelled {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapCommitted {
+    pub owner: Pubkey,
+    pub hash: [u8; 32],
+    pub slot: u64,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub creator: Pubkey,
+    pub pool: Pubkey,
+    pub amm_config: Pubkey,
+    pub token_mint_0: Pubkey,
+    pub token_mint_1: Pubkey,
+    pub sqrt_price_x64: u128,
+    pub open_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardEmissionSet {
+    pub creator: Pubkey,
+    pub pool: Pubkey,
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub emissions_per_second_x64: u128,
+    pub open_time: u64,
+    pub end_time: u64,
+    pub initialized: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CpmmMigrated {
+    pub user: Pubkey,
+    pub cpmm_pool: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub lp_token_amount: u64,
+    pub amount_0_withdrawn: u64,
+    pub amount_1_withdrawn: u64,
+    pub amount_0_deposited: u64,
+    pub amount_1_deposited: u64,
+    pub liquidity: u128,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+pub struct OpenbookMarketCreated {
+    pub creator: Pubkey,
+    pub market: Pubkey,
+    pub coin_mint: Pubkey,
+    pub pc_mint: Pubkey,
+    pub coin_lot_size: u64,
+    pub pc_lot_size: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmmPoolCreated {
+    pub creator: Pubkey,
+    pub amm_pool: Pubkey,
+    pub market: Pubkey,
+    pub coin_mint: Pubkey,
+    pub pc_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub init_coin_amount: u64,
+    pub init_pc_amount: u64,
+    pub lp_amount: u64,
+    pub open_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmmLpLocked {
+    pub owner: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmmLpUnlocked {
+    pub owner: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
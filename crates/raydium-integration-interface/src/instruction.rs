@@ -0,0 +1,3004 @@
+//! Instruction encoding for the proxy.
+//!
+//! Each instruction has a data struct (its arguments, Anchor-encoded behind
+//! the `global:<name>` discriminator) and an account struct in [`accounts`]
+//! listing keys in the order the program expects. [`build`] combines the two;
+//! on-chain callers pass the result to `invoke_signed`.
+//!
+//! With the `event-cpi` feature, [`build`] appends the event authority and
+//! program accounts that a program built with `event-cpi` expects.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{Discriminator, InstructionData};
+
+macro_rules! instruction_data {
+    ($name:ident, $discriminator:expr) => {
+        impl Discriminator for $name {
+            const DISCRIMINATOR: &'static [u8] = &$discriminator;
+        }
+        impl InstructionData for $name {}
+    };
+}
+
+/*
+ * Build a proxy instruction from its accounts and arguments
+ */
+pub fn build<A: ToAccountMetas, D: InstructionData>(accounts: &A, data: &D) -> Instruction {
+    #[allow(unused_mut)]
+    let mut metas = accounts.to_account_metas(None);
+    #[cfg(feature = "event-cpi")]
+    metas.extend([
+        AccountMeta::new_readonly(crate::pda::event_authority_address().0, false),
+        AccountMeta::new_readonly(crate::ID, false),
+    ]);
+    Instruction {
+        program_id: crate::ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetSlippage {
+    pub bps: u16,
+}
+instruction_data!(SetSlippage, [205, 3, 1, 178, 135, 154, 109, 229]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetAllowedCallers {
+    pub programs: Vec<Pubkey>,
+}
+instruction_data!(SetAllowedCallers, [206, 75, 219, 6, 147, 196, 67, 152]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetMintExtensionOptIn {
+    pub allowed_extensions: u8,
+}
+instruction_data!(SetMintExtensionOptIn, [62, 210, 172, 218, 77, 12, 132, 140]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetExposureCaps {
+    pub quote_mint: Pubkey,
+    pub max_pool_exposure: u64,
+    pub max_total_exposure: u64,
+}
+instruction_data!(SetExposureCaps, [131, 20, 220, 141, 232, 169, 44, 21]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitPoolExposure {}
+instruction_data!(InitPoolExposure, [201, 135, 152, 18, 148, 169, 196, 246]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateUserConfig {}
+instruction_data!(MigrateUserConfig, [137, 210, 158, 64, 13, 229, 244, 241]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitGlobalStats {}
+instruction_data!(InitGlobalStats, [217, 121, 117, 43, 220, 186, 177, 32]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitPoolStats {}
+instruction_data!(InitPoolStats, [70, 81, 130, 97, 57, 90, 116, 16]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitRoundTripCounter {}
+instruction_data!(InitRoundTripCounter, [96, 193, 120, 20, 174, 83, 249, 198]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxySwap {
+    pub amount: u64,
+    pub expected_other_amount: u64,
+    pub sqrt_price_limit_x64: u128,
+    pub is_base_input: bool,
+    /// Pre-walk the tick arrays and fail if the swap would cross more
+    /// initialized ticks. Extra tick arrays go after the built accounts
+    pub max_tick_crossings: Option<u16>,
+    /// Stop at `sqrt_price_limit_x64` instead of failing, checking slippage
+    /// pro rata on the filled part
+    pub partial_fill: bool,
+    pub simulate_only: bool,
+}
+instruction_data!(ProxySwap, [19, 44, 130, 148, 72, 56, 44, 238]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyOpenPosition {
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub tick_array_lower_start_index: i32,
+    pub tick_array_upper_start_index: i32,
+    /// Zero deposits by amounts
+    pub liquidity: u128,
+    pub amount_0_max: u64,
+    pub amount_1_max: u64,
+    pub with_matedata: bool,
+    pub simulate_only: bool,
+}
+instruction_data!(ProxyOpenPosition, [132, 25, 14, 151, 120, 136, 194, 196]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyOpenFullRangePosition {
+    pub liquidity: u128,
+    pub amount_0_max: u64,
+    pub amount_1_max: u64,
+    pub with_metadata: bool,
+    pub simulate_only: bool,
+}
+instruction_data!(
+    ProxyOpenFullRangePosition,
+    [30, 112, 0, 224, 134, 10, 75, 218]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyOpenPositionAroundPrice {
+    pub range_bps: u16,
+    pub liquidity: u128,
+    pub amount_0_max: u64,
+    pub amount_1_max: u64,
+    pub with_metadata: bool,
+    pub simulate_only: bool,
+}
+instruction_data!(
+    ProxyOpenPositionAroundPrice,
+    [155, 24, 26, 62, 129, 53, 249, 129]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ApproveOpenOnBehalf {}
+instruction_data!(ApproveOpenOnBehalf, [80, 13, 67, 55, 133, 14, 142, 139]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeOpenOnBehalf {}
+instruction_data!(RevokeOpenOnBehalf, [30, 18, 40, 155, 81, 29, 62, 107]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyClosePosition {}
+instruction_data!(ProxyClosePosition, [188, 254, 167, 206, 227, 160, 46, 228]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitPositionRegistry {}
+instruction_data!(InitPositionRegistry, [177, 221, 98, 50, 140, 12, 224, 245]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddPositionRegistryPage {}
+instruction_data!(AddPositionRegistryPage, [39, 5, 8, 221, 29, 217, 25, 68]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyIncreaseLiquidity {
+    /// Zero deposits by amounts
+    pub liquidity: u128,
+    pub amount_0_max: u64,
+    pub amount_1_max: u64,
+    pub simulate_only: bool,
+}
+instruction_data!(ProxyIncreaseLiquidity, [226, 97, 26, 222, 75, 125, 88, 92]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyDecreaseLiquidity {
+    pub liquidity: u128,
+    pub amount_0_min: u64,
+    pub amount_1_min: u64,
+    pub simulate_only: bool,
+}
+instruction_data!(
+    ProxyDecreaseLiquidity,
+    [197, 58, 211, 99, 115, 200, 109, 92]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetImpermanentLoss {}
+instruction_data!(GetImpermanentLoss, [154, 46, 10, 118, 2, 246, 249, 125]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitFeeCheckpoint {}
+instruction_data!(InitFeeCheckpoint, [79, 144, 79, 87, 139, 203, 102, 161]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StampFeeCheckpoint {}
+instruction_data!(StampFeeCheckpoint, [41, 63, 247, 33, 201, 19, 185, 120]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetLiquidityForAmounts {
+    pub sqrt_price_x64: u128,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+instruction_data!(
+    GetLiquidityForAmounts,
+    [174, 190, 168, 188, 32, 235, 153, 106]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetPositionValue {}
+instruction_data!(GetPositionValue, [176, 101, 36, 67, 215, 72, 215, 247]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetPoolHealth {
+    pub tick_radius: u32,
+}
+instruction_data!(GetPoolHealth, [28, 90, 19, 58, 141, 82, 91, 83]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetStatus {}
+instruction_data!(GetStatus, [199, 6, 205, 77, 193, 78, 197, 110]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SplitPosition {
+    pub liquidity_to_move: u128,
+    pub with_metadata: bool,
+}
+instruction_data!(SplitPosition, [172, 241, 221, 138, 161, 29, 253, 42]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MergePositions {}
+instruction_data!(MergePositions, [115, 76, 72, 172, 120, 248, 70, 208]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreatePositionStream {
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+instruction_data!(CreatePositionStream, [35, 180, 135, 121, 152, 250, 64, 229]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimUnlocked {
+    pub amount_0_min: u64,
+    pub amount_1_min: u64,
+}
+instruction_data!(ClaimUnlocked, [70, 139, 1, 246, 166, 193, 64, 143]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateSharedPosition {}
+instruction_data!(CreateSharedPosition, [233, 75, 153, 214, 248, 37, 60, 11]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SharedDeposit {
+    pub liquidity: u128,
+    pub amount_0_max: u64,
+    pub amount_1_max: u64,
+}
+instruction_data!(SharedDeposit, [165, 93, 218, 140, 238, 243, 253, 21]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SharedWithdraw {
+    pub shares: u128,
+    pub amount_0_min: u64,
+    pub amount_1_min: u64,
+}
+instruction_data!(SharedWithdraw, [38, 167, 119, 69, 165, 95, 248, 243]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimSharedFees {}
+instruction_data!(ClaimSharedFees, [147, 104, 178, 226, 240, 196, 70, 25]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SweepDust {}
+instruction_data!(SweepDust, [9, 49, 242, 88, 156, 84, 109, 15]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitGlobalConfig {
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+}
+instruction_data!(InitGlobalConfig, [140, 136, 214, 48, 87, 0, 120, 255]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetGlobalAdmin {
+    pub new_admin: Pubkey,
+}
+instruction_data!(SetGlobalAdmin, [221, 143, 165, 9, 48, 99, 25, 200]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPauseFlags {
+    pub pause_flags: u8,
+}
+instruction_data!(SetPauseFlags, [205, 167, 85, 237, 144, 202, 248, 175]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ScheduleParamChange {
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+    pub activation_slot: u64,
+}
+instruction_data!(ScheduleParamChange, [138, 228, 113, 11, 162, 207, 197, 179]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ApplyParamChange {}
+instruction_data!(ApplyParamChange, [61, 25, 91, 167, 81, 159, 245, 204]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelParamChange {}
+instruction_data!(CancelParamChange, [150, 147, 92, 108, 72, 160, 224, 55]);
+
+/// User configs to migrate follow as writable remaining accounts
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AdminMigrateConfigs {
+    /// Tolerances above this are lowered to it
+    pub max_slippage_bps: u16,
+}
+instruction_data!(AdminMigrateConfigs, [173, 116, 136, 122, 82, 103, 10, 140]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitInsuranceVault {}
+instruction_data!(InitInsuranceVault, [220, 144, 119, 124, 248, 58, 225, 90]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PayInsuranceClaim {
+    pub amount: u64,
+}
+instruction_data!(PayInsuranceClaim, [81, 175, 239, 169, 159, 87, 111, 32]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitCreatorFeeVault {}
+instruction_data!(InitCreatorFeeVault, [25, 184, 171, 188, 51, 25, 2, 64]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimCreatorFees {}
+instruction_data!(ClaimCreatorFees, [0, 23, 125, 234, 156, 118, 134, 89]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPointsRates {
+    pub swap_points_per_unit: u64,
+    pub lp_points_per_unit: u64,
+}
+instruction_data!(SetPointsRates, [19, 166, 209, 144, 206, 40, 205, 138]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitPointsLedger {}
+instruction_data!(InitPointsLedger, [169, 247, 51, 175, 22, 84, 147, 101]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitPositionPoints {}
+instruction_data!(InitPositionPoints, [56, 195, 70, 55, 132, 124, 45, 205]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimPoints {}
+instruction_data!(ClaimPoints, [106, 26, 99, 252, 9, 196, 78, 172]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetFeeOracle {
+    pub twap_window_secs: u32,
+    pub max_staleness_secs: u32,
+    pub max_deviation_ticks: u32,
+}
+instruction_data!(SetFeeOracle, [225, 234, 16, 176, 62, 217, 0, 103]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CloseFeeOracle {}
+instruction_data!(CloseFeeOracle, [146, 13, 162, 100, 141, 225, 118, 211]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetSwapValidators {
+    pub programs: Vec<Pubkey>,
+}
+instruction_data!(SetSwapValidators, [111, 126, 111, 38, 223, 141, 165, 147]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddAllowedDestination {
+    pub token_account: Pubkey,
+}
+instruction_data!(
+    AddAllowedDestination,
+    [204, 139, 99, 107, 105, 169, 215, 204]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveAllowedDestination {
+    pub token_account: Pubkey,
+}
+instruction_data!(
+    RemoveAllowedDestination,
+    [81, 238, 250, 2, 85, 237, 117, 131]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetDestinationAllowlistEnabled {
+    pub enabled: bool,
+}
+instruction_data!(
+    SetDestinationAllowlistEnabled,
+    [195, 249, 95, 234, 109, 55, 77, 130]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetGuardians {
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+}
+instruction_data!(SetGuardians, [166, 69, 140, 183, 157, 169, 253, 40]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ApproveRecovery {
+    pub new_owner: Pubkey,
+}
+instruction_data!(ApproveRecovery, [148, 96, 41, 38, 108, 189, 129, 214]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelRecovery {}
+instruction_data!(CancelRecovery, [176, 23, 203, 37, 121, 251, 227, 83]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ExecuteRecovery {}
+instruction_data!(ExecuteRecovery, [203, 133, 133, 228, 153, 121, 182, 237]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateSession {
+    pub session_key: Pubkey,
+    pub expiry: i64,
+    /// Bitmask of `SESSION_SCOPE_*` flags
+    pub scope: u8,
+}
+instruction_data!(CreateSession, [242, 193, 143, 179, 150, 25, 122, 227]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevokeSession {}
+instruction_data!(RevokeSession, [86, 92, 198, 120, 144, 2, 7, 194]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SessionSwap {
+    pub amount: u64,
+    pub expected_other_amount: u64,
+    pub sqrt_price_limit_x64: u128,
+    pub is_base_input: bool,
+}
+instruction_data!(SessionSwap, [164, 229, 253, 99, 64, 238, 30, 202]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SessionSetSlippage {
+    pub bps: u16,
+}
+instruction_data!(SessionSetSlippage, [185, 233, 93, 73, 8, 196, 2, 60]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetSessionLimits {
+    /// Any pool when empty
+    pub allowed_pools: Vec<Pubkey>,
+    /// One of the `SESSION_DIRECTION_*` values
+    pub direction: u8,
+    /// Up to `MAX_SESSION_SPEND_MINTS` distinct input mints; any mint, with
+    /// no amount limits, when empty
+    pub spend_limits: Vec<crate::SessionSpendLimit>,
+}
+instruction_data!(SetSessionLimits, [152, 118, 86, 174, 247, 227, 243, 123]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateDutchOrder {
+    pub order_id: u64,
+    pub amount_in: u64,
+    pub start_min_out: u64,
+    pub end_min_out: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+instruction_data!(CreateDutchOrder, [172, 135, 249, 128, 244, 155, 12, 58]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FillDutchOrder {}
+instruction_data!(FillDutchOrder, [177, 248, 47, 80, 19, 233, 186, 236]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelDutchOrder {}
+instruction_data!(CancelDutchOrder, [30, 145, 47, 30, 66, 73, 75, 187]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateFloorOrder {
+    pub order_id: u64,
+    pub amount_in: u64,
+    pub floor_price_x64: u128,
+    pub daily_max: u64,
+}
+instruction_data!(CreateFloorOrder, [44, 217, 7, 242, 164, 102, 45, 229]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FillFloorOrder {
+    pub amount_in: u64,
+}
+instruction_data!(FillFloorOrder, [168, 124, 65, 187, 227, 24, 128, 41]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelFloorOrder {}
+instruction_data!(CancelFloorOrder, [178, 193, 29, 198, 129, 165, 175, 193]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CommitSwap {
+    /// See [`swap_commitment_hash`]
+    pub hash: [u8; 32],
+}
+instruction_data!(CommitSwap, [122, 177, 32, 71, 57, 30, 201, 215]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RevealAndSwap {
+    pub amount: u64,
+    pub expected_other_amount: u64,
+    pub sqrt_price_limit_x64: u128,
+    pub is_base_input: bool,
+    pub salt: [u8; 32],
+}
+instruction_data!(RevealAndSwap, [171, 39, 209, 99, 33, 15, 82, 135]);
+
+/*
+ * Hash to pass to [`CommitSwap`] for a later [`RevealAndSwap`]: SHA-256 of
+ * the pool, both token accounts, then the amounts and price limit as
+ * little-endian integers, `is_base_input` as one byte, and the salt
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn swap_commitment_hash(
+    pool: &Pubkey,
+    input_token_account: &Pubkey,
+    output_token_account: &Pubkey,
+    amount: u64,
+    expected_other_amount: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hashv(&[
+        pool.as_ref(),
+        input_token_account.as_ref(),
+        output_token_account.as_ref(),
+        &amount.to_le_bytes(),
+        &expected_other_amount.to_le_bytes(),
+        &sqrt_price_limit_x64.to_le_bytes(),
+        &[is_base_input as u8],
+        salt,
+    ])
+    .to_bytes()
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyCreatePool {
+    pub sqrt_price_x64: u128,
+    pub open_time: u64,
+}
+instruction_data!(ProxyCreatePool, [250, 151, 238, 168, 5, 240, 236, 243]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyInitializeReward {
+    pub open_time: u64,
+    pub end_time: u64,
+    pub emissions_per_second_x64: u128,
+}
+instruction_data!(ProxyInitializeReward, [207, 174, 21, 112, 83, 148, 32, 126]);
+
+/// Top-ups follow as remaining accounts: the reward vault, the creator's
+/// token account and the reward mint
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxySetRewardParams {
+    pub reward_index: u8,
+    pub emissions_per_second_x64: u128,
+    pub open_time: u64,
+    pub end_time: u64,
+}
+instruction_data!(ProxySetRewardParams, [230, 85, 161, 19, 59, 42, 67, 31]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateCpmmToClmm {
+    pub lp_token_amount: u64,
+    pub minimum_token_0_amount: u64,
+    pub minimum_token_1_amount: u64,
+    pub tick_lower_index: i32,
+    pub tick_upper_index: i32,
+    pub tick_array_lower_start_index: i32,
+    pub tick_array_upper_start_index: i32,
+}
+instruction_data!(MigrateCpmmToClmm, [192, 32, 207, 106, 155, 77, 24, 130]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyCreateOpenbookMarket {
+    pub coin_lot_size: u64,
+    pub pc_lot_size: u64,
+    pub vault_signer_nonce: u64,
+    pub pc_dust_threshold: u64,
+}
+instruction_data!(
+    ProxyCreateOpenbookMarket,
+    [170, 104, 52, 19, 156, 12, 73, 195]
+);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProxyCreateAmmPool {
+    pub nonce: u8,
+    pub open_time: u64,
+    pub init_coin_amount: u64,
+    pub init_pc_amount: u64,
+}
+instruction_data!(ProxyCreateAmmPool, [241, 158, 133, 166, 74, 207, 136, 231]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LockAmmLp {
+    pub amount: u64,
+    pub unlock_ts: i64,
+}
+instruction_data!(LockAmmLp, [40, 247, 191, 5, 50, 158, 246, 30]);
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UnlockAmmLp {}
+instruction_data!(UnlockAmmLp, [183, 189, 221, 134, 22, 18, 205, 139]);
+
+pub mod accounts {
+    use anchor_lang::prelude::*;
+
+    /// Anchor encodes an omitted optional account as the program ID
+    fn optional(key: Option<Pubkey>, is_writable: bool) -> AccountMeta {
+        match key {
+            Some(key) if is_writable => AccountMeta::new(key, false),
+            Some(key) => AccountMeta::new_readonly(key, false),
+            None => AccountMeta::new_readonly(crate::ID, false),
+        }
+    }
+
+    pub struct SetSlippage {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub user_cfg: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for SetSlippage {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    /// Same accounts as [`SetSlippage`]
+    pub type SetAllowedCallers = SetSlippage;
+
+    /// Same accounts as [`SetSlippage`]
+    pub type SetExposureCaps = SetSlippage;
+
+    pub struct InitPoolExposure {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub pool_state: Pubkey,
+        pub pool_exposure: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitPoolExposure {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new(self.pool_exposure, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct SetMintExtensionOptIn {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub mint_extension_opt_in: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for SetMintExtensionOptIn {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.mint_extension_opt_in, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct MigrateUserConfig {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub user_cfg: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for MigrateUserConfig {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct InitGlobalStats {
+        pub payer: Pubkey,
+        pub global_stats: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitGlobalStats {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.global_stats, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct InitPoolStats {
+        pub payer: Pubkey,
+        pub pool_state: Pubkey,
+        pub pool_stats: Pubkey,
+        pub global_stats: Option<Pubkey>,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitPoolStats {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new(self.pool_stats, false),
+                optional(self.global_stats, true),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct InitRoundTripCounter {
+        pub payer: Pubkey,
+        pub owner: Pubkey,
+        pub pool_state: Pubkey,
+        /// [`crate::pda::round_trip_counter_address`]
+        pub round_trip_counter: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitRoundTripCounter {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.owner, false),
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new(self.round_trip_counter, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct ProxySwap {
+        pub clmm_program: Pubkey,
+        pub payer: Pubkey,
+        pub user_cfg: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub input_token_account: Pubkey,
+        pub output_token_account: Pubkey,
+        pub input_vault: Pubkey,
+        pub output_vault: Pubkey,
+        pub input_vault_mint: Pubkey,
+        pub output_vault_mint: Pubkey,
+        pub observation_state: Pubkey,
+        pub token_program: Pubkey,
+        pub tick_array: Pubkey,
+        pub pool_stats: Option<Pubkey>,
+        pub global_stats: Option<Pubkey>,
+        pub tick_array_bitmap_extension: Option<Pubkey>,
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`], always required. While
+        /// it charges a protocol fee the next three are required too
+        pub global_config: Pubkey,
+        /// The input mint, or a mint with a registered fee oracle
+        pub fee_mint: Option<Pubkey>,
+        pub treasury_token_account: Option<Pubkey>,
+        pub insurance_vault: Option<Pubkey>,
+        /// Token program of `fee_mint` when it isn't `token_program`
+        pub fee_token_program: Option<Pubkey>,
+        /// Earns swap points when passed with `points_ledger`
+        pub points_config: Option<Pubkey>,
+        pub points_ledger: Option<Pubkey>,
+        /// [`crate::pda::round_trip_counter_address`] of the payer, always
+        /// required. Must be initialized while governance tracks round trips
+        pub round_trip_counter: Pubkey,
+        /// Pay the fee in `fee_mint` instead of skimming the input
+        pub fee_oracle: Option<Pubkey>,
+        pub fee_oracle_pool: Option<Pubkey>,
+        /// `observation_key` of `fee_oracle_pool`
+        pub fee_oracle_observation: Option<Pubkey>,
+        pub fee_payment_account: Option<Pubkey>,
+        /// [`crate::pda::pool_creator_address`] of a pool created through
+        /// the proxy, paying its creator a share of the fee
+        pub pool_creator_record: Option<Pubkey>,
+        /// The record's token account in `fee_mint`
+        pub creator_fee_vault: Option<Pubkey>,
+        /// [`crate::pda::swap_validators_address`], always required. Programs
+        /// registered there go first in the remaining accounts
+        pub swap_validators: Pubkey,
+        /// [`crate::pda::destination_allowlist_address`] of the payer, always
+        /// required
+        pub destination_allowlist: Pubkey,
+        /// [`crate::pda::extension_opt_in_address`] of the payer, needed for
+        /// mints with risky Token-2022 extensions
+        pub mint_extension_opt_in: Option<Pubkey>,
+        /// Called after execution; repeat it in the remaining accounts,
+        /// followed by its own accounts
+        pub callback_program: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for ProxySwap {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.payer, true),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.input_token_account, false),
+                AccountMeta::new(self.output_token_account, false),
+                AccountMeta::new(self.input_vault, false),
+                AccountMeta::new(self.output_vault, false),
+                AccountMeta::new_readonly(self.input_vault_mint, false),
+                AccountMeta::new_readonly(self.output_vault_mint, false),
+                AccountMeta::new(self.observation_state, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new(self.tick_array, false),
+                optional(self.pool_stats, true),
+                optional(self.global_stats, true),
+                optional(self.tick_array_bitmap_extension, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                optional(self.fee_mint, false),
+                optional(self.treasury_token_account, true),
+                optional(self.insurance_vault, true),
+                optional(self.fee_token_program, false),
+                optional(self.points_config, false),
+                optional(self.points_ledger, true),
+                AccountMeta::new(self.round_trip_counter, false),
+                optional(self.fee_oracle, false),
+                optional(self.fee_oracle_pool, false),
+                optional(self.fee_oracle_observation, false),
+                optional(self.fee_payment_account, true),
+                optional(self.pool_creator_record, false),
+                optional(self.creator_fee_vault, true),
+                AccountMeta::new_readonly(self.swap_validators, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+                optional(self.mint_extension_opt_in, false),
+                optional(self.callback_program, false),
+            ]
+        }
+    }
+
+    pub struct ProxyOpenPosition {
+        pub clmm_program: Pubkey,
+        pub payer: Pubkey,
+        pub user_cfg: Pubkey,
+        pub position_nft_owner: Pubkey,
+        pub position_nft_mint: Pubkey,
+        pub position_nft_account: Pubkey,
+        pub metadata_account: Option<Pubkey>,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub personal_position: Pubkey,
+        pub token_account_0: Pubkey,
+        pub token_account_1: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub rent: Pubkey,
+        pub system_program: Pubkey,
+        pub token_program: Pubkey,
+        pub associated_token_program: Pubkey,
+        pub metadata_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        pub pool_stats: Option<Pubkey>,
+        pub global_stats: Option<Pubkey>,
+        pub position_registry: Option<Pubkey>,
+        pub position_registry_page: Option<Pubkey>,
+        pub cost_basis: Option<Pubkey>,
+        pub tick_array_bitmap_extension: Option<Pubkey>,
+        pub mint_extension_opt_in: Option<Pubkey>,
+        pub instructions_sysvar: Option<Pubkey>,
+        /// Needed when `position_nft_owner` is neither the payer nor a signer
+        pub open_approval: Option<Pubkey>,
+        /// Needed while the payer's config caps exposure per pool; created
+        /// when missing
+        pub pool_exposure: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+    }
+
+    impl ToAccountMetas for ProxyOpenPosition {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new_readonly(self.position_nft_owner, false),
+                AccountMeta::new(self.position_nft_mint, true),
+                AccountMeta::new(self.position_nft_account, false),
+                optional(self.metadata_account, true),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new(self.token_account_0, false),
+                AccountMeta::new(self.token_account_1, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new_readonly(self.rent, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.associated_token_program, false),
+                AccountMeta::new_readonly(self.metadata_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                optional(self.pool_stats, true),
+                optional(self.global_stats, true),
+                optional(self.position_registry, true),
+                optional(self.position_registry_page, true),
+                optional(self.cost_basis, true),
+                optional(self.tick_array_bitmap_extension, true),
+                optional(self.mint_extension_opt_in, false),
+                optional(self.instructions_sysvar, false),
+                optional(self.open_approval, false),
+                optional(self.pool_exposure, true),
+                AccountMeta::new_readonly(self.global_config, false),
+            ]
+        }
+    }
+
+    /// Same accounts as [`ProxyOpenPosition`], with the full-range ticks
+    pub type ProxyOpenFullRangePosition = ProxyOpenPosition;
+
+    /// Same accounts as [`ProxyOpenPosition`], with the ticks around the price
+    pub type ProxyOpenPositionAroundPrice = ProxyOpenPosition;
+
+    pub struct ApproveOpenOnBehalf {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub delegate: Pubkey,
+        pub open_approval: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for ApproveOpenOnBehalf {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.delegate, false),
+                AccountMeta::new(self.open_approval, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct RevokeOpenOnBehalf {
+        pub owner: Pubkey,
+        pub open_approval: Pubkey,
+    }
+
+    impl ToAccountMetas for RevokeOpenOnBehalf {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new(self.open_approval, false),
+            ]
+        }
+    }
+
+    pub struct ProxyClosePosition {
+        pub clmm_program: Pubkey,
+        pub nft_owner: Pubkey,
+        pub position_nft_mint: Pubkey,
+        pub position_nft_account: Pubkey,
+        pub personal_position: Pubkey,
+        pub system_program: Pubkey,
+        pub token_program: Pubkey,
+        pub position_registry: Option<Pubkey>,
+        pub position_registry_page: Option<Pubkey>,
+        pub cost_basis: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for ProxyClosePosition {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.nft_owner, true),
+                AccountMeta::new(self.position_nft_mint, false),
+                AccountMeta::new(self.position_nft_account, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                optional(self.position_registry, true),
+                optional(self.position_registry_page, true),
+                optional(self.cost_basis, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+            ]
+        }
+    }
+
+    pub struct InitPositionRegistry {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub position_registry: Pubkey,
+        pub position_registry_page: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitPositionRegistry {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, false),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.position_registry, false),
+                AccountMeta::new(self.position_registry_page, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct AddPositionRegistryPage {
+        pub payer: Pubkey,
+        pub position_registry: Pubkey,
+        pub position_registry_page: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for AddPositionRegistryPage {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.position_registry, false),
+                AccountMeta::new(self.position_registry_page, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct ProxyIncreaseLiquidity {
+        pub clmm_program: Pubkey,
+        pub nft_owner: Pubkey,
+        pub nft_account: Pubkey,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub personal_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub token_account_0: Pubkey,
+        pub token_account_1: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub token_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        pub cost_basis: Option<Pubkey>,
+        pub mint_extension_opt_in: Option<Pubkey>,
+        /// Earns LP points when passed with the next two
+        pub points_config: Option<Pubkey>,
+        pub points_ledger: Option<Pubkey>,
+        pub position_points: Option<Pubkey>,
+        /// Called after execution; repeat it in the remaining accounts,
+        /// followed by its own accounts
+        pub callback_program: Option<Pubkey>,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed while the owner's config caps exposure per pool
+        pub pool_exposure: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for ProxyIncreaseLiquidity {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.nft_owner, true),
+                AccountMeta::new_readonly(self.nft_account, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.token_account_0, false),
+                AccountMeta::new(self.token_account_1, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                optional(self.cost_basis, true),
+                optional(self.mint_extension_opt_in, false),
+                optional(self.points_config, false),
+                optional(self.points_ledger, true),
+                optional(self.position_points, true),
+                optional(self.callback_program, false),
+                AccountMeta::new(self.user_cfg, false),
+                optional(self.pool_exposure, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                optional(self.instructions_sysvar, false),
+            ]
+        }
+    }
+
+    pub struct ProxyDecreaseLiquidity {
+        pub clmm_program: Pubkey,
+        pub nft_owner: Pubkey,
+        pub nft_account: Pubkey,
+        pub personal_position: Pubkey,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub recipient_token_account_0: Pubkey,
+        pub recipient_token_account_1: Pubkey,
+        pub token_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub memo_program: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        pub cost_basis: Option<Pubkey>,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Earns LP points when passed with the next two
+        pub points_config: Option<Pubkey>,
+        pub points_ledger: Option<Pubkey>,
+        pub position_points: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+        /// Called after execution; repeat it in the remaining accounts,
+        /// followed by its own accounts
+        pub callback_program: Option<Pubkey>,
+        /// Lowers the owner's pool exposure while `user_cfg` caps it
+        pub pool_exposure: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for ProxyDecreaseLiquidity {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.nft_owner, true),
+                AccountMeta::new_readonly(self.nft_account, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.recipient_token_account_0, false),
+                AccountMeta::new(self.recipient_token_account_1, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                optional(self.cost_basis, true),
+                AccountMeta::new(self.user_cfg, false),
+                optional(self.points_config, false),
+                optional(self.points_ledger, true),
+                optional(self.position_points, true),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+                optional(self.callback_program, false),
+                optional(self.pool_exposure, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                optional(self.instructions_sysvar, false),
+            ]
+        }
+    }
+
+    pub struct GetImpermanentLoss {
+        pub pool_state: Pubkey,
+        pub personal_position: Pubkey,
+        pub cost_basis: Pubkey,
+    }
+
+    impl ToAccountMetas for GetImpermanentLoss {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new_readonly(self.personal_position, false),
+                AccountMeta::new_readonly(self.cost_basis, false),
+            ]
+        }
+    }
+
+    pub struct InitFeeCheckpoint {
+        pub payer: Pubkey,
+        pub personal_position: Pubkey,
+        pub fee_checkpoint: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitFeeCheckpoint {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.personal_position, false),
+                AccountMeta::new(self.fee_checkpoint, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct StampFeeCheckpoint {
+        pub pool_state: Pubkey,
+        pub personal_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub fee_checkpoint: Pubkey,
+        pub cost_basis: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for StampFeeCheckpoint {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new_readonly(self.personal_position, false),
+                AccountMeta::new_readonly(self.tick_array_lower, false),
+                AccountMeta::new_readonly(self.tick_array_upper, false),
+                AccountMeta::new(self.fee_checkpoint, false),
+                optional(self.cost_basis, false),
+            ]
+        }
+    }
+
+    pub struct GetLiquidityForAmounts {}
+
+    impl ToAccountMetas for GetLiquidityForAmounts {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            Vec::new()
+        }
+    }
+
+    pub struct GetPositionValue {
+        pub pool_state: Pubkey,
+        pub personal_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+    }
+
+    impl ToAccountMetas for GetPositionValue {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new_readonly(self.personal_position, false),
+                AccountMeta::new_readonly(self.tick_array_lower, false),
+                AccountMeta::new_readonly(self.tick_array_upper, false),
+            ]
+        }
+    }
+
+    pub struct GetPoolHealth {
+        pub pool_state: Pubkey,
+        pub amm_config: Pubkey,
+        pub observation_state: Pubkey,
+    }
+
+    impl ToAccountMetas for GetPoolHealth {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new_readonly(self.observation_state, false),
+            ]
+        }
+    }
+
+    pub struct GetStatus {
+        pub global_config: Pubkey,
+        pub pending_param_change: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for GetStatus {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.global_config, false),
+                optional(self.pending_param_change, false),
+            ]
+        }
+    }
+
+    pub struct SplitPosition {
+        pub clmm_program: Pubkey,
+        pub nft_owner: Pubkey,
+        pub nft_account: Pubkey,
+        pub personal_position: Pubkey,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub new_position_nft_mint: Pubkey,
+        pub new_position_nft_account: Pubkey,
+        pub new_personal_position: Pubkey,
+        pub metadata_account: Option<Pubkey>,
+        pub token_account_0: Pubkey,
+        pub token_account_1: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        pub rent: Pubkey,
+        pub system_program: Pubkey,
+        pub token_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub associated_token_program: Pubkey,
+        pub metadata_program: Pubkey,
+        pub memo_program: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for SplitPosition {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.nft_owner, true),
+                AccountMeta::new_readonly(self.nft_account, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.new_position_nft_mint, true),
+                AccountMeta::new(self.new_position_nft_account, false),
+                AccountMeta::new(self.new_personal_position, false),
+                optional(self.metadata_account, true),
+                AccountMeta::new(self.token_account_0, false),
+                AccountMeta::new(self.token_account_1, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                AccountMeta::new_readonly(self.rent, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.associated_token_program, false),
+                AccountMeta::new_readonly(self.metadata_program, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct MergePositions {
+        pub clmm_program: Pubkey,
+        pub nft_owner: Pubkey,
+        pub source_position_nft_mint: Pubkey,
+        pub source_nft_account: Pubkey,
+        pub source_personal_position: Pubkey,
+        pub target_nft_account: Pubkey,
+        pub target_personal_position: Pubkey,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub token_account_0: Pubkey,
+        pub token_account_1: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        pub system_program: Pubkey,
+        pub token_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub memo_program: Pubkey,
+        pub position_registry: Option<Pubkey>,
+        pub position_registry_page: Option<Pubkey>,
+        pub source_cost_basis: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for MergePositions {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.nft_owner, true),
+                AccountMeta::new(self.source_position_nft_mint, false),
+                AccountMeta::new(self.source_nft_account, false),
+                AccountMeta::new(self.source_personal_position, false),
+                AccountMeta::new_readonly(self.target_nft_account, false),
+                AccountMeta::new(self.target_personal_position, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.token_account_0, false),
+                AccountMeta::new(self.token_account_1, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                optional(self.position_registry, true),
+                optional(self.position_registry_page, true),
+                optional(self.source_cost_basis, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct CreatePositionStream {
+        pub owner: Pubkey,
+        pub beneficiary: Pubkey,
+        pub position_nft_mint: Pubkey,
+        pub owner_nft_account: Pubkey,
+        pub personal_position: Pubkey,
+        pub position_stream: Pubkey,
+        pub stream_nft_account: Pubkey,
+        pub nft_token_program: Pubkey,
+        pub associated_token_program: Pubkey,
+        pub system_program: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for CreatePositionStream {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new_readonly(self.beneficiary, false),
+                AccountMeta::new_readonly(self.position_nft_mint, false),
+                AccountMeta::new(self.owner_nft_account, false),
+                AccountMeta::new_readonly(self.personal_position, false),
+                AccountMeta::new(self.position_stream, false),
+                AccountMeta::new(self.stream_nft_account, false),
+                AccountMeta::new_readonly(self.nft_token_program, false),
+                AccountMeta::new_readonly(self.associated_token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+            ]
+        }
+    }
+
+    pub struct ClaimUnlocked {
+        pub clmm_program: Pubkey,
+        pub beneficiary: Pubkey,
+        pub position_stream: Pubkey,
+        pub position_nft_mint: Pubkey,
+        pub stream_nft_account: Pubkey,
+        pub beneficiary_nft_account: Pubkey,
+        pub personal_position: Pubkey,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub recipient_token_account_0: Pubkey,
+        pub recipient_token_account_1: Pubkey,
+        pub token_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub nft_token_program: Pubkey,
+        pub memo_program: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the beneficiary, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the beneficiary restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the beneficiary, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for ClaimUnlocked {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.beneficiary, true),
+                AccountMeta::new(self.position_stream, false),
+                AccountMeta::new_readonly(self.position_nft_mint, false),
+                AccountMeta::new(self.stream_nft_account, false),
+                AccountMeta::new(self.beneficiary_nft_account, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.recipient_token_account_0, false),
+                AccountMeta::new(self.recipient_token_account_1, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.nft_token_program, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct CreateSharedPosition {
+        pub creator: Pubkey,
+        pub position_nft_mint: Pubkey,
+        pub creator_nft_account: Pubkey,
+        pub personal_position: Pubkey,
+        pub shared_position: Pubkey,
+        pub creator_share: Pubkey,
+        pub shared_nft_account: Pubkey,
+        pub nft_token_program: Pubkey,
+        pub associated_token_program: Pubkey,
+        pub system_program: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the creator, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the creator restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for CreateSharedPosition {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.creator, true),
+                AccountMeta::new_readonly(self.position_nft_mint, false),
+                AccountMeta::new(self.creator_nft_account, false),
+                AccountMeta::new_readonly(self.personal_position, false),
+                AccountMeta::new(self.shared_position, false),
+                AccountMeta::new(self.creator_share, false),
+                AccountMeta::new(self.shared_nft_account, false),
+                AccountMeta::new_readonly(self.nft_token_program, false),
+                AccountMeta::new_readonly(self.associated_token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+            ]
+        }
+    }
+
+    /// Accounts of `shared_deposit`, `shared_withdraw` and `claim_shared_fees`
+    pub struct SharedPositionAction {
+        pub clmm_program: Pubkey,
+        pub contributor: Pubkey,
+        pub shared_position: Pubkey,
+        pub share: Pubkey,
+        pub shared_nft_account: Pubkey,
+        pub personal_position: Pubkey,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub shared_token_account_0: Pubkey,
+        pub shared_token_account_1: Pubkey,
+        pub contributor_token_account_0: Pubkey,
+        pub contributor_token_account_1: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        pub token_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub memo_program: Pubkey,
+        pub system_program: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the contributor, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the contributor restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the contributor, always
+        /// required
+        pub destination_allowlist: Pubkey,
+        /// [`crate::pda::extension_opt_in_address`] of the contributor, needed
+        /// to deposit mints with risky Token-2022 extensions
+        pub mint_extension_opt_in: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for SharedPositionAction {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.contributor, true),
+                AccountMeta::new(self.shared_position, false),
+                AccountMeta::new(self.share, false),
+                AccountMeta::new_readonly(self.shared_nft_account, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new(self.shared_token_account_0, false),
+                AccountMeta::new(self.shared_token_account_1, false),
+                AccountMeta::new(self.contributor_token_account_0, false),
+                AccountMeta::new(self.contributor_token_account_1, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+                optional(self.mint_extension_opt_in, false),
+            ]
+        }
+    }
+
+    pub struct SweepDust {
+        pub sweeper: Pubkey,
+        pub shared_position: Pubkey,
+        pub source: Pubkey,
+        /// The creator's token account of `mint`
+        pub beneficiary_token_account: Pubkey,
+        pub mint: Pubkey,
+        pub token_program: Pubkey,
+    }
+
+    impl ToAccountMetas for SweepDust {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.sweeper, true),
+                AccountMeta::new_readonly(self.shared_position, false),
+                AccountMeta::new(self.source, false),
+                AccountMeta::new(self.beneficiary_token_account, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new_readonly(self.token_program, false),
+            ]
+        }
+    }
+
+    pub struct InitGlobalConfig {
+        /// The program's upgrade authority
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub program: Pubkey,
+        pub program_data: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitGlobalConfig {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new(self.global_config, false),
+                AccountMeta::new_readonly(self.program, false),
+                AccountMeta::new_readonly(self.program_data, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct UpdateGlobalConfig {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+    }
+
+    impl ToAccountMetas for UpdateGlobalConfig {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.admin, true),
+                AccountMeta::new(self.global_config, false),
+            ]
+        }
+    }
+
+    /// Same accounts as [`UpdateGlobalConfig`]
+    pub type SetGlobalAdmin = UpdateGlobalConfig;
+    /// Same accounts as [`UpdateGlobalConfig`]
+    pub type SetPauseFlags = UpdateGlobalConfig;
+
+    pub struct ScheduleParamChange {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub pending_param_change: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for ScheduleParamChange {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.pending_param_change, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct ApplyParamChange {
+        /// The config's admin, refunded the pending change's rent
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub pending_param_change: Pubkey,
+    }
+
+    impl ToAccountMetas for ApplyParamChange {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, false),
+                AccountMeta::new(self.global_config, false),
+                AccountMeta::new(self.pending_param_change, false),
+            ]
+        }
+    }
+
+    pub struct CancelParamChange {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub pending_param_change: Pubkey,
+    }
+
+    impl ToAccountMetas for CancelParamChange {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.pending_param_change, false),
+            ]
+        }
+    }
+
+    pub struct AdminMigrateConfigs {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for AdminMigrateConfigs {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct InitInsuranceVault {
+        pub payer: Pubkey,
+        pub global_config: Pubkey,
+        pub mint: Pubkey,
+        pub insurance_vault: Pubkey,
+        pub token_program: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitInsuranceVault {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new(self.insurance_vault, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct PayInsuranceClaim {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub mint: Pubkey,
+        pub insurance_vault: Pubkey,
+        pub recipient_token_account: Pubkey,
+        pub token_program: Pubkey,
+    }
+
+    impl ToAccountMetas for PayInsuranceClaim {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new(self.insurance_vault, false),
+                AccountMeta::new(self.recipient_token_account, false),
+                AccountMeta::new_readonly(self.token_program, false),
+            ]
+        }
+    }
+
+    pub struct InitCreatorFeeVault {
+        pub payer: Pubkey,
+        pub pool_creator_record: Pubkey,
+        pub mint: Pubkey,
+        /// [`crate::pda::creator_fee_vault_address`]
+        pub creator_fee_vault: Pubkey,
+        pub token_program: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitCreatorFeeVault {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.pool_creator_record, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new(self.creator_fee_vault, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct ClaimCreatorFees {
+        pub creator: Pubkey,
+        pub pool_creator_record: Pubkey,
+        pub mint: Pubkey,
+        pub creator_fee_vault: Pubkey,
+        pub recipient_token_account: Pubkey,
+        pub token_program: Pubkey,
+        /// [`crate::pda::destination_allowlist_address`] of the creator, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for ClaimCreatorFees {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.creator, true),
+                AccountMeta::new_readonly(self.pool_creator_record, false),
+                AccountMeta::new_readonly(self.mint, false),
+                AccountMeta::new(self.creator_fee_vault, false),
+                AccountMeta::new(self.recipient_token_account, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct SetPointsRates {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub points_config: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for SetPointsRates {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.points_config, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct InitPointsLedger {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub points_ledger: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitPointsLedger {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, false),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.points_ledger, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct InitPositionPoints {
+        pub payer: Pubkey,
+        pub personal_position: Pubkey,
+        pub position_points: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for InitPositionPoints {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.personal_position, false),
+                AccountMeta::new(self.position_points, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct ClaimPoints {
+        pub owner: Pubkey,
+        pub points_ledger: Pubkey,
+    }
+
+    impl ToAccountMetas for ClaimPoints {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.points_ledger, false),
+            ]
+        }
+    }
+
+    pub struct SetFeeOracle {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub input_mint: Pubkey,
+        pub fee_mint: Pubkey,
+        pub oracle_pool: Pubkey,
+        pub fee_oracle: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for SetFeeOracle {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.input_mint, false),
+                AccountMeta::new_readonly(self.fee_mint, false),
+                AccountMeta::new_readonly(self.oracle_pool, false),
+                AccountMeta::new(self.fee_oracle, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct CloseFeeOracle {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub fee_oracle: Pubkey,
+    }
+
+    impl ToAccountMetas for CloseFeeOracle {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.fee_oracle, false),
+            ]
+        }
+    }
+
+    pub struct SetSwapValidators {
+        pub admin: Pubkey,
+        pub global_config: Pubkey,
+        pub swap_validators: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for SetSwapValidators {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.admin, true),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new(self.swap_validators, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct UpdateDestinationAllowlist {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub destination_allowlist: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for UpdateDestinationAllowlist {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.destination_allowlist, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    /// Same accounts as [`UpdateDestinationAllowlist`]
+    pub type AddAllowedDestination = UpdateDestinationAllowlist;
+
+    /// Same accounts as [`UpdateDestinationAllowlist`]
+    pub type RemoveAllowedDestination = UpdateDestinationAllowlist;
+
+    /// Same accounts as [`UpdateDestinationAllowlist`]
+    pub type SetDestinationAllowlistEnabled = UpdateDestinationAllowlist;
+
+    pub struct SetGuardians {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub recovery_config: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for SetGuardians {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.recovery_config, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct ApproveRecovery {
+        pub guardian: Pubkey,
+        pub recovery_config: Pubkey,
+    }
+
+    impl ToAccountMetas for ApproveRecovery {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.guardian, true),
+                AccountMeta::new(self.recovery_config, false),
+            ]
+        }
+    }
+
+    pub struct CancelRecovery {
+        pub owner: Pubkey,
+        pub recovery_config: Pubkey,
+    }
+
+    impl ToAccountMetas for CancelRecovery {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.recovery_config, false),
+            ]
+        }
+    }
+
+    pub struct ExecuteRecovery {
+        pub payer: Pubkey,
+        pub recovery_config: Pubkey,
+        pub user_cfg: Pubkey,
+        pub new_owner: Pubkey,
+        pub new_user_cfg: Pubkey,
+        pub new_recovery_config: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for ExecuteRecovery {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.recovery_config, false),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new(self.new_owner, false),
+                AccountMeta::new(self.new_user_cfg, false),
+                AccountMeta::new(self.new_recovery_config, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct CreateSession {
+        pub owner: Pubkey,
+        pub payer: Pubkey,
+        pub user_cfg: Pubkey,
+        pub session: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for CreateSession {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                AccountMeta::new(self.session, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct RevokeSession {
+        /// The owner or the session key
+        pub authority: Pubkey,
+        pub owner: Pubkey,
+        pub session: Pubkey,
+    }
+
+    impl ToAccountMetas for RevokeSession {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new(self.owner, false),
+                AccountMeta::new(self.session, false),
+            ]
+        }
+    }
+
+    pub struct SessionSwap {
+        pub clmm_program: Pubkey,
+        pub session_key: Pubkey,
+        pub session: Pubkey,
+        pub user_cfg: Pubkey,
+        /// [`crate::pda::session_authority_address`] of the owner, approved
+        /// as delegate of `input_token_account`
+        pub session_authority: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub input_token_account: Pubkey,
+        pub output_token_account: Pubkey,
+        pub input_vault: Pubkey,
+        pub output_vault: Pubkey,
+        pub input_vault_mint: Pubkey,
+        pub output_vault_mint: Pubkey,
+        pub observation_state: Pubkey,
+        pub token_program: Pubkey,
+        pub tick_array: Pubkey,
+        pub pool_stats: Option<Pubkey>,
+        pub global_stats: Option<Pubkey>,
+        pub tick_array_bitmap_extension: Option<Pubkey>,
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]. While it charges a
+        /// protocol fee the next three are required too
+        pub global_config: Pubkey,
+        /// The input mint
+        pub fee_mint: Option<Pubkey>,
+        pub treasury_token_account: Option<Pubkey>,
+        pub insurance_vault: Option<Pubkey>,
+        /// [`crate::pda::round_trip_counter_address`] of the owner, always
+        /// required
+        pub round_trip_counter: Pubkey,
+        /// [`crate::pda::pool_creator_address`] of a pool created through
+        /// the proxy, paying its creator a share of the fee
+        pub pool_creator_record: Option<Pubkey>,
+        /// The record's token account in the input mint
+        pub creator_fee_vault: Option<Pubkey>,
+        pub swap_validators: Pubkey,
+        pub destination_allowlist: Pubkey,
+        /// [`crate::pda::extension_opt_in_address`] of the owner, needed for
+        /// mints with risky Token-2022 extensions
+        pub mint_extension_opt_in: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for SessionSwap {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.session_key, true),
+                AccountMeta::new(self.session, false),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new_readonly(self.session_authority, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.input_token_account, false),
+                AccountMeta::new(self.output_token_account, false),
+                AccountMeta::new(self.input_vault, false),
+                AccountMeta::new(self.output_vault, false),
+                AccountMeta::new_readonly(self.input_vault_mint, false),
+                AccountMeta::new_readonly(self.output_vault_mint, false),
+                AccountMeta::new(self.observation_state, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new(self.tick_array, false),
+                optional(self.pool_stats, true),
+                optional(self.global_stats, true),
+                optional(self.tick_array_bitmap_extension, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                optional(self.fee_mint, false),
+                optional(self.treasury_token_account, true),
+                optional(self.insurance_vault, true),
+                AccountMeta::new(self.round_trip_counter, false),
+                optional(self.pool_creator_record, false),
+                optional(self.creator_fee_vault, true),
+                AccountMeta::new_readonly(self.swap_validators, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+                optional(self.mint_extension_opt_in, false),
+            ]
+        }
+    }
+
+    pub struct SessionSetSlippage {
+        pub session_key: Pubkey,
+        pub session: Pubkey,
+        pub user_cfg: Pubkey,
+    }
+
+    impl ToAccountMetas for SessionSetSlippage {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.session_key, true),
+                AccountMeta::new_readonly(self.session, false),
+                AccountMeta::new(self.user_cfg, false),
+            ]
+        }
+    }
+
+    pub struct SetSessionLimits {
+        pub owner: Pubkey,
+        pub session: Pubkey,
+    }
+
+    impl ToAccountMetas for SetSessionLimits {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.owner, true),
+                AccountMeta::new(self.session, false),
+            ]
+        }
+    }
+
+    pub struct CreateDutchOrder {
+        pub owner: Pubkey,
+        pub pool_state: Pubkey,
+        pub input_mint: Pubkey,
+        pub owner_input_account: Pubkey,
+        pub destination: Pubkey,
+        pub dutch_order: Pubkey,
+        pub escrow: Pubkey,
+        pub token_program: Pubkey,
+        pub system_program: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for CreateDutchOrder {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new_readonly(self.input_mint, false),
+                AccountMeta::new(self.owner_input_account, false),
+                AccountMeta::new_readonly(self.destination, false),
+                AccountMeta::new(self.dutch_order, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct FillDutchOrder {
+        pub clmm_program: Pubkey,
+        pub keeper: Pubkey,
+        pub dutch_order: Pubkey,
+        pub owner: Pubkey,
+        pub escrow: Pubkey,
+        pub destination: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub input_vault: Pubkey,
+        pub output_vault: Pubkey,
+        pub input_vault_mint: Pubkey,
+        pub output_vault_mint: Pubkey,
+        pub observation_state: Pubkey,
+        pub token_program: Pubkey,
+        pub tick_array: Pubkey,
+        pub tick_array_bitmap_extension: Option<Pubkey>,
+        pub swap_validators: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::extension_opt_in_address`] of the owner, needed for
+        /// mints with risky Token-2022 extensions
+        pub mint_extension_opt_in: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub memo_program: Pubkey,
+        /// Token program owning the order's input mint
+        pub input_token_program: Pubkey,
+        /// Owner's account of the input mint, swept whatever the swap leaves
+        /// in escrow
+        pub owner_input_account: Pubkey,
+        /// [`crate::pda::round_trip_counter_address`] of the owner, always
+        /// required. Must be initialized while governance tracks round trips
+        pub round_trip_counter: Pubkey,
+    }
+
+    impl ToAccountMetas for FillDutchOrder {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.keeper, true),
+                AccountMeta::new(self.dutch_order, false),
+                AccountMeta::new(self.owner, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new(self.destination, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.input_vault, false),
+                AccountMeta::new(self.output_vault, false),
+                AccountMeta::new_readonly(self.input_vault_mint, false),
+                AccountMeta::new_readonly(self.output_vault_mint, false),
+                AccountMeta::new(self.observation_state, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new(self.tick_array, false),
+                optional(self.tick_array_bitmap_extension, false),
+                AccountMeta::new_readonly(self.swap_validators, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                optional(self.mint_extension_opt_in, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                AccountMeta::new_readonly(self.input_token_program, false),
+                AccountMeta::new(self.owner_input_account, false),
+                AccountMeta::new(self.round_trip_counter, false),
+            ]
+        }
+    }
+
+    pub struct CancelDutchOrder {
+        pub owner: Pubkey,
+        pub dutch_order: Pubkey,
+        pub escrow: Pubkey,
+        pub input_mint: Pubkey,
+        pub owner_input_account: Pubkey,
+        pub token_program: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for CancelDutchOrder {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new(self.dutch_order, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(self.input_mint, false),
+                AccountMeta::new(self.owner_input_account, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct CreateFloorOrder {
+        pub owner: Pubkey,
+        pub pool_state: Pubkey,
+        pub input_mint: Pubkey,
+        pub owner_input_account: Pubkey,
+        pub destination: Pubkey,
+        pub floor_order: Pubkey,
+        pub escrow: Pubkey,
+        pub token_program: Pubkey,
+        pub system_program: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for CreateFloorOrder {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new_readonly(self.pool_state, false),
+                AccountMeta::new_readonly(self.input_mint, false),
+                AccountMeta::new(self.owner_input_account, false),
+                AccountMeta::new_readonly(self.destination, false),
+                AccountMeta::new(self.floor_order, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct FillFloorOrder {
+        pub clmm_program: Pubkey,
+        pub keeper: Pubkey,
+        pub floor_order: Pubkey,
+        pub owner: Pubkey,
+        pub escrow: Pubkey,
+        pub destination: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub input_vault: Pubkey,
+        pub output_vault: Pubkey,
+        pub input_vault_mint: Pubkey,
+        pub output_vault_mint: Pubkey,
+        pub observation_state: Pubkey,
+        pub token_program: Pubkey,
+        pub tick_array: Pubkey,
+        pub tick_array_bitmap_extension: Option<Pubkey>,
+        pub swap_validators: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        /// [`crate::pda::extension_opt_in_address`] of the owner, needed for
+        /// mints with risky Token-2022 extensions
+        pub mint_extension_opt_in: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub memo_program: Pubkey,
+        /// Token program owning the order's input mint
+        pub input_token_program: Pubkey,
+        /// [`crate::pda::round_trip_counter_address`] of the owner, always
+        /// required. Must be initialized while governance tracks round trips
+        pub round_trip_counter: Pubkey,
+    }
+
+    impl ToAccountMetas for FillFloorOrder {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.keeper, true),
+                AccountMeta::new(self.floor_order, false),
+                AccountMeta::new(self.owner, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new(self.destination, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.input_vault, false),
+                AccountMeta::new(self.output_vault, false),
+                AccountMeta::new_readonly(self.input_vault_mint, false),
+                AccountMeta::new_readonly(self.output_vault_mint, false),
+                AccountMeta::new(self.observation_state, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new(self.tick_array, false),
+                optional(self.tick_array_bitmap_extension, false),
+                AccountMeta::new_readonly(self.swap_validators, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                optional(self.mint_extension_opt_in, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                AccountMeta::new_readonly(self.input_token_program, false),
+                AccountMeta::new(self.round_trip_counter, false),
+            ]
+        }
+    }
+
+    pub struct CancelFloorOrder {
+        pub owner: Pubkey,
+        pub floor_order: Pubkey,
+        pub escrow: Pubkey,
+        pub input_mint: Pubkey,
+        pub owner_input_account: Pubkey,
+        pub token_program: Pubkey,
+        /// [`crate::pda::user_config_address`] of the owner, always required
+        pub user_cfg: Pubkey,
+        /// Needed when the owner restricts callers
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::destination_allowlist_address`] of the owner, always
+        /// required
+        pub destination_allowlist: Pubkey,
+    }
+
+    impl ToAccountMetas for CancelFloorOrder {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new(self.floor_order, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(self.input_mint, false),
+                AccountMeta::new(self.owner_input_account, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.user_cfg, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+            ]
+        }
+    }
+
+    pub struct CommitSwap {
+        pub payer: Pubkey,
+        pub swap_commitment: Pubkey,
+        pub system_program: Pubkey,
+    }
+
+    impl ToAccountMetas for CommitSwap {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.swap_commitment, false),
+                AccountMeta::new_readonly(self.system_program, false),
+            ]
+        }
+    }
+
+    pub struct RevealAndSwap {
+        pub clmm_program: Pubkey,
+        pub payer: Pubkey,
+        pub swap_commitment: Pubkey,
+        pub user_cfg: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub input_token_account: Pubkey,
+        pub output_token_account: Pubkey,
+        pub input_vault: Pubkey,
+        pub output_vault: Pubkey,
+        pub input_vault_mint: Pubkey,
+        pub output_vault_mint: Pubkey,
+        pub observation_state: Pubkey,
+        pub token_program: Pubkey,
+        pub tick_array: Pubkey,
+        pub pool_stats: Option<Pubkey>,
+        pub global_stats: Option<Pubkey>,
+        pub tick_array_bitmap_extension: Option<Pubkey>,
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+        pub swap_validators: Pubkey,
+        pub destination_allowlist: Pubkey,
+        /// [`crate::pda::extension_opt_in_address`] of the payer, needed for
+        /// mints with risky Token-2022 extensions
+        pub mint_extension_opt_in: Option<Pubkey>,
+        /// The input mint. While `global_config` charges a protocol fee this
+        /// and the next two are required
+        pub fee_mint: Option<Pubkey>,
+        pub treasury_token_account: Option<Pubkey>,
+        pub insurance_vault: Option<Pubkey>,
+        /// [`crate::pda::round_trip_counter_address`] of the payer, always
+        /// required
+        pub round_trip_counter: Pubkey,
+        /// [`crate::pda::pool_creator_address`] of a pool created through
+        /// the proxy, paying its creator a share of the fee
+        pub pool_creator_record: Option<Pubkey>,
+        /// The record's token account in the input mint
+        pub creator_fee_vault: Option<Pubkey>,
+        /// Earns swap points when passed with `points_ledger`
+        pub points_config: Option<Pubkey>,
+        pub points_ledger: Option<Pubkey>,
+    }
+
+    impl ToAccountMetas for RevealAndSwap {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.swap_commitment, false),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.input_token_account, false),
+                AccountMeta::new(self.output_token_account, false),
+                AccountMeta::new(self.input_vault, false),
+                AccountMeta::new(self.output_vault, false),
+                AccountMeta::new_readonly(self.input_vault_mint, false),
+                AccountMeta::new_readonly(self.output_vault_mint, false),
+                AccountMeta::new(self.observation_state, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new(self.tick_array, false),
+                optional(self.pool_stats, true),
+                optional(self.global_stats, true),
+                optional(self.tick_array_bitmap_extension, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.global_config, false),
+                AccountMeta::new_readonly(self.swap_validators, false),
+                AccountMeta::new_readonly(self.destination_allowlist, false),
+                optional(self.mint_extension_opt_in, false),
+                optional(self.fee_mint, false),
+                optional(self.treasury_token_account, true),
+                optional(self.insurance_vault, true),
+                AccountMeta::new(self.round_trip_counter, false),
+                optional(self.pool_creator_record, false),
+                optional(self.creator_fee_vault, true),
+                optional(self.points_config, false),
+                optional(self.points_ledger, true),
+            ]
+        }
+    }
+
+    /// Raydium derives the pool, vault, observation and bitmap addresses;
+    /// see its `create_pool` instruction
+    pub struct ProxyCreatePool {
+        pub clmm_program: Pubkey,
+        pub pool_creator: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub token_mint_0: Pubkey,
+        pub token_mint_1: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub observation_state: Pubkey,
+        pub tick_array_bitmap: Pubkey,
+        /// [`crate::pda::pool_creator_address`] of the new pool
+        pub pool_creator_record: Pubkey,
+        pub token_program_0: Pubkey,
+        pub token_program_1: Pubkey,
+        pub system_program: Pubkey,
+        pub rent: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+    }
+
+    impl ToAccountMetas for ProxyCreatePool {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.pool_creator, true),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new_readonly(self.token_mint_0, false),
+                AccountMeta::new_readonly(self.token_mint_1, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new(self.observation_state, false),
+                AccountMeta::new(self.tick_array_bitmap, false),
+                AccountMeta::new(self.pool_creator_record, false),
+                AccountMeta::new_readonly(self.token_program_0, false),
+                AccountMeta::new_readonly(self.token_program_1, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.rent, false),
+                AccountMeta::new_readonly(self.global_config, false),
+            ]
+        }
+    }
+
+    /// `operation_state` is Raydium's `["operation"]` PDA and
+    /// `reward_token_vault` its `["pool_reward_vault", pool, mint]` PDA
+    pub struct ProxyInitializeReward {
+        pub clmm_program: Pubkey,
+        pub reward_funder: Pubkey,
+        pub funder_token_account: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub operation_state: Pubkey,
+        pub reward_token_mint: Pubkey,
+        pub reward_token_vault: Pubkey,
+        pub reward_token_program: Pubkey,
+        pub system_program: Pubkey,
+        pub rent: Pubkey,
+    }
+
+    impl ToAccountMetas for ProxyInitializeReward {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new(self.reward_funder, true),
+                AccountMeta::new(self.funder_token_account, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new_readonly(self.operation_state, false),
+                AccountMeta::new_readonly(self.reward_token_mint, false),
+                AccountMeta::new(self.reward_token_vault, false),
+                AccountMeta::new_readonly(self.reward_token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.rent, false),
+            ]
+        }
+    }
+
+    pub struct ProxySetRewardParams {
+        pub clmm_program: Pubkey,
+        pub authority: Pubkey,
+        pub amm_config: Pubkey,
+        pub pool_state: Pubkey,
+        pub operation_state: Pubkey,
+        pub token_program: Pubkey,
+        pub token_program_2022: Pubkey,
+    }
+
+    impl ToAccountMetas for ProxySetRewardParams {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.authority, true),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new_readonly(self.operation_state, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+            ]
+        }
+    }
+
+    /// `cpmm_authority` is CPMM's `["vault_and_lp_mint_auth_seed"]` PDA
+    pub struct MigrateCpmmToClmm {
+        pub clmm_program: Pubkey,
+        pub cpmm_program: Pubkey,
+        pub payer: Pubkey,
+        pub user_cfg: Pubkey,
+        pub cpmm_authority: Pubkey,
+        pub cpmm_pool_state: Pubkey,
+        pub owner_lp_token: Pubkey,
+        pub cpmm_token_0_vault: Pubkey,
+        pub cpmm_token_1_vault: Pubkey,
+        pub lp_mint: Pubkey,
+        pub memo_program: Pubkey,
+        pub position_nft_owner: Pubkey,
+        pub position_nft_mint: Pubkey,
+        pub position_nft_account: Pubkey,
+        pub pool_state: Pubkey,
+        pub protocol_position: Pubkey,
+        pub tick_array_lower: Pubkey,
+        pub tick_array_upper: Pubkey,
+        pub personal_position: Pubkey,
+        pub token_account_0: Pubkey,
+        pub token_account_1: Pubkey,
+        pub token_vault_0: Pubkey,
+        pub token_vault_1: Pubkey,
+        pub rent: Pubkey,
+        pub system_program: Pubkey,
+        pub token_program: Pubkey,
+        pub associated_token_program: Pubkey,
+        pub metadata_program: Pubkey,
+        pub token_program_2022: Pubkey,
+        pub vault_0_mint: Pubkey,
+        pub vault_1_mint: Pubkey,
+        pub tick_array_bitmap_extension: Option<Pubkey>,
+        pub mint_extension_opt_in: Option<Pubkey>,
+        pub instructions_sysvar: Option<Pubkey>,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+    }
+
+    impl ToAccountMetas for MigrateCpmmToClmm {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.clmm_program, false),
+                AccountMeta::new_readonly(self.cpmm_program, false),
+                AccountMeta::new(self.payer, true),
+                AccountMeta::new(self.user_cfg, false),
+                AccountMeta::new_readonly(self.cpmm_authority, false),
+                AccountMeta::new(self.cpmm_pool_state, false),
+                AccountMeta::new(self.owner_lp_token, false),
+                AccountMeta::new(self.cpmm_token_0_vault, false),
+                AccountMeta::new(self.cpmm_token_1_vault, false),
+                AccountMeta::new(self.lp_mint, false),
+                AccountMeta::new_readonly(self.memo_program, false),
+                AccountMeta::new_readonly(self.position_nft_owner, false),
+                AccountMeta::new(self.position_nft_mint, true),
+                AccountMeta::new(self.position_nft_account, false),
+                AccountMeta::new(self.pool_state, false),
+                AccountMeta::new(self.protocol_position, false),
+                AccountMeta::new(self.tick_array_lower, false),
+                AccountMeta::new(self.tick_array_upper, false),
+                AccountMeta::new(self.personal_position, false),
+                AccountMeta::new(self.token_account_0, false),
+                AccountMeta::new(self.token_account_1, false),
+                AccountMeta::new(self.token_vault_0, false),
+                AccountMeta::new(self.token_vault_1, false),
+                AccountMeta::new_readonly(self.rent, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.associated_token_program, false),
+                AccountMeta::new_readonly(self.metadata_program, false),
+                AccountMeta::new_readonly(self.token_program_2022, false),
+                AccountMeta::new_readonly(self.vault_0_mint, false),
+                AccountMeta::new_readonly(self.vault_1_mint, false),
+                optional(self.tick_array_bitmap_extension, true),
+                optional(self.mint_extension_opt_in, false),
+                optional(self.instructions_sysvar, false),
+                AccountMeta::new_readonly(self.global_config, false),
+            ]
+        }
+    }
+
+    /// `market`, the queues and the books are allocated beforehand and owned
+    /// by OpenBook; the vaults are token accounts owned by the market's
+    /// vault signer, OpenBook's `[market, vault_signer_nonce]` address
+    pub struct ProxyCreateOpenbookMarket {
+        pub openbook_program: Pubkey,
+        pub creator: Pubkey,
+        pub market: Pubkey,
+        pub request_queue: Pubkey,
+        pub event_queue: Pubkey,
+        pub bids: Pubkey,
+        pub asks: Pubkey,
+        pub coin_vault: Pubkey,
+        pub pc_vault: Pubkey,
+        pub coin_mint: Pubkey,
+        pub pc_mint: Pubkey,
+        pub rent: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+    }
+
+    impl ToAccountMetas for ProxyCreateOpenbookMarket {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.openbook_program, false),
+                AccountMeta::new_readonly(self.creator, true),
+                AccountMeta::new(self.market, false),
+                AccountMeta::new(self.request_queue, false),
+                AccountMeta::new(self.event_queue, false),
+                AccountMeta::new(self.bids, false),
+                AccountMeta::new(self.asks, false),
+                AccountMeta::new(self.coin_vault, false),
+                AccountMeta::new(self.pc_vault, false),
+                AccountMeta::new_readonly(self.coin_mint, false),
+                AccountMeta::new_readonly(self.pc_mint, false),
+                AccountMeta::new_readonly(self.rent, false),
+                AccountMeta::new_readonly(self.global_config, false),
+            ]
+        }
+    }
+
+    /// Raydium derives the pool, authority, open orders, LP mint, vault and
+    /// target orders addresses from the market; see AMM v4's `initialize2`
+    pub struct ProxyCreateAmmPool {
+        pub amm_program: Pubkey,
+        pub creator: Pubkey,
+        pub amm_pool: Pubkey,
+        pub amm_authority: Pubkey,
+        pub amm_open_orders: Pubkey,
+        pub lp_mint: Pubkey,
+        pub coin_mint: Pubkey,
+        pub pc_mint: Pubkey,
+        pub amm_coin_vault: Pubkey,
+        pub amm_pc_vault: Pubkey,
+        pub amm_target_orders: Pubkey,
+        pub amm_config: Pubkey,
+        pub create_fee_destination: Pubkey,
+        pub market_program: Pubkey,
+        pub market: Pubkey,
+        pub creator_coin_account: Pubkey,
+        pub creator_pc_account: Pubkey,
+        /// The creator's associated token account of `lp_mint`
+        pub creator_lp_account: Pubkey,
+        pub token_program: Pubkey,
+        pub associated_token_program: Pubkey,
+        pub system_program: Pubkey,
+        pub rent: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+    }
+
+    impl ToAccountMetas for ProxyCreateAmmPool {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new_readonly(self.amm_program, false),
+                AccountMeta::new(self.creator, true),
+                AccountMeta::new(self.amm_pool, false),
+                AccountMeta::new_readonly(self.amm_authority, false),
+                AccountMeta::new(self.amm_open_orders, false),
+                AccountMeta::new(self.lp_mint, false),
+                AccountMeta::new_readonly(self.coin_mint, false),
+                AccountMeta::new_readonly(self.pc_mint, false),
+                AccountMeta::new(self.amm_coin_vault, false),
+                AccountMeta::new(self.amm_pc_vault, false),
+                AccountMeta::new(self.amm_target_orders, false),
+                AccountMeta::new_readonly(self.amm_config, false),
+                AccountMeta::new(self.create_fee_destination, false),
+                AccountMeta::new_readonly(self.market_program, false),
+                AccountMeta::new_readonly(self.market, false),
+                AccountMeta::new(self.creator_coin_account, false),
+                AccountMeta::new(self.creator_pc_account, false),
+                AccountMeta::new(self.creator_lp_account, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.associated_token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.rent, false),
+                AccountMeta::new_readonly(self.global_config, false),
+            ]
+        }
+    }
+
+    pub struct LockAmmLp {
+        pub owner: Pubkey,
+        pub lp_mint: Pubkey,
+        pub owner_lp_account: Pubkey,
+        /// [`crate::pda::lp_lock_address`]
+        pub lp_lock: Pubkey,
+        /// [`crate::pda::lp_lock_escrow_address`]
+        pub escrow: Pubkey,
+        pub token_program: Pubkey,
+        pub system_program: Pubkey,
+        /// [`crate::pda::global_config_address`]
+        pub global_config: Pubkey,
+    }
+
+    impl ToAccountMetas for LockAmmLp {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new_readonly(self.lp_mint, false),
+                AccountMeta::new(self.owner_lp_account, false),
+                AccountMeta::new(self.lp_lock, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new_readonly(self.token_program, false),
+                AccountMeta::new_readonly(self.system_program, false),
+                AccountMeta::new_readonly(self.global_config, false),
+            ]
+        }
+    }
+
+    pub struct UnlockAmmLp {
+        pub owner: Pubkey,
+        pub lp_mint: Pubkey,
+        /// [`crate::pda::lp_lock_address`]
+        pub lp_lock: Pubkey,
+        /// [`crate::pda::lp_lock_escrow_address`]
+        pub escrow: Pubkey,
+        pub owner_lp_account: Pubkey,
+        pub token_program: Pubkey,
+    }
+
+    impl ToAccountMetas for UnlockAmmLp {
+        fn to_account_metas(&self, _is_signer: Option<bool>) -> Vec<AccountMeta> {
+            vec![
+                AccountMeta::new(self.owner, true),
+                AccountMeta::new_readonly(self.lp_mint, false),
+                AccountMeta::new(self.lp_lock, false),
+                AccountMeta::new(self.escrow, false),
+                AccountMeta::new(self.owner_lp_account, false),
+                AccountMeta::new_readonly(self.token_program, false),
+            ]
+        }
+    }
+}
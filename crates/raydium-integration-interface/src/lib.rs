@@ -0,0 +1,106 @@
+//! Lightweight interface for the `raydium-integration` program.
+//!
+//! Mirrors the program's account layouts, events and instruction encoding
+//! without its handlers or the `raydium-amm-v3` dependency tree, so other
+//! programs and off-chain tools can build proxy instructions cheaply.
+use anchor_lang::prelude::*;
+
+pub mod events;
+pub mod instruction;
+pub mod pda;
+pub mod state;
+pub mod types;
+
+pub use events::*;
+pub use state::*;
+pub use types::*;
+
+declare_id!("CrMxnHJvvk2eRP8H1DLtc2ZTQfzD9NSxJKkDquCzr1Qu");
+
+/// Raydium CLMM program the proxy forwards to; match the program's `devnet`
+/// feature
+#[cfg(not(feature = "devnet"))]
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = pubkey!("CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK");
+#[cfg(feature = "devnet")]
+pub const RAYDIUM_CLMM_PROGRAM_ID: Pubkey = pubkey!("devi51mZmdwUJGU9hjN27vEz64Gps7uUefqxg27EAtH");
+
+/// Raydium CPMM program `migrate_cpmm_to_clmm` withdraws from; match the
+/// program's `devnet` feature
+#[cfg(not(feature = "devnet"))]
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+#[cfg(feature = "devnet")]
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = pubkey!("CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW");
+
+/// OpenBook program `proxy_create_openbook_market` initializes markets on;
+/// match the program's `devnet` feature
+#[cfg(not(feature = "devnet"))]
+pub const OPENBOOK_PROGRAM_ID: Pubkey = pubkey!("srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX");
+#[cfg(feature = "devnet")]
+pub const OPENBOOK_PROGRAM_ID: Pubkey = pubkey!("EoTcMgcDRTJVZDMZWBoU6rhYHZfkNTVEAfz3uUJRcYGj");
+
+/// Raydium AMM v4 program `proxy_create_amm_pool` creates pools with; match
+/// the program's `devnet` feature
+#[cfg(not(feature = "devnet"))]
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
+    pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+#[cfg(feature = "devnet")]
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
+    pubkey!("HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8");
+
+/// SPL Memo program, passed to CPMM withdrawals
+pub const SPL_MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Metaplex token metadata program used for position NFTs
+pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+pub const DEFAULT_SLIPPAGE_BPS: u16 = 500;
+/// Global flag: swaps need a user-set slippage
+pub const FLAG_REQUIRE_USER_SLIPPAGE: u8 = 1 << 0;
+/// Global flag: every user instruction adding risk is paused. Exits (decrease,
+/// close, withdrawals, claims, order cancellation) and user settings are not
+pub const FLAG_PAUSED: u8 = 1 << 1;
+/// Global flag: swaps and order fills are paused
+pub const FLAG_PAUSE_SWAPS: u8 = 1 << 2;
+/// Global flag: swaps past the round-trip threshold fail instead of being
+/// flagged
+pub const FLAG_THROTTLE_ROUND_TRIPS: u8 = 1 << 3;
+/// The only flags `set_pause_flags` changes; the rest are scheduled
+pub const PAUSE_FLAGS: u8 = FLAG_PAUSED | FLAG_PAUSE_SWAPS;
+pub const MAX_CONFIG_MIGRATION_BATCH: usize = 20;
+pub const MAX_ALLOWED_CALLERS: usize = 4;
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 100;
+/// Slots ahead a governance parameter change must be scheduled
+pub const MIN_PARAM_CHANGE_NOTICE_SLOTS: u64 = 216_000;
+pub const POINTS_RATE_DENOMINATOR: u128 = 1_000_000;
+pub const MAX_SWAP_VALIDATORS: usize = 4;
+pub const MAX_ALLOWED_DESTINATIONS: usize = 8;
+/// Seconds before an allowlist addition, or disabling the allowlist, applies
+pub const DESTINATION_ADD_DELAY: i64 = 86_400;
+pub const MAX_GUARDIANS: usize = 5;
+/// Seconds between guardians reaching their threshold and the recovery
+pub const RECOVERY_DELAY: i64 = 172_800;
+pub const MAX_SESSION_DURATION: i64 = 604_800;
+/// Session scope: `session_swap`
+pub const SESSION_SCOPE_SWAP: u8 = 1 << 0;
+/// Session scope: `session_set_slippage`
+pub const SESSION_SCOPE_SET_SLIPPAGE: u8 = 1 << 1;
+pub const MAX_SESSION_POOLS: usize = 4;
+pub const MAX_SESSION_SPEND_MINTS: usize = 4;
+pub const SESSION_DIRECTION_ANY: u8 = 0;
+pub const SESSION_DIRECTION_ZERO_FOR_ONE: u8 = 1;
+pub const SESSION_DIRECTION_ONE_FOR_ZERO: u8 = 2;
+/// Slot window after a commitment in which `reveal_and_swap` is accepted
+pub const MIN_REVEAL_DELAY_SLOTS: u64 = 1;
+pub const REVEAL_WINDOW_SLOTS: u64 = 32;
+/// Instruction discriminator swap validators receive, Anchor's
+/// `global:validate_swap`, followed by a [`SwapValidation`]
+pub const VALIDATE_SWAP_DISCRIMINATOR: [u8; 8] = [218, 214, 34, 73, 72, 12, 65, 203];
+/// Instruction discriminator of the post-swap callback, Anchor's
+/// `global:on_swap`, followed by a [`SwapCallback`]
+pub const SWAP_CALLBACK_DISCRIMINATOR: [u8; 8] = [201, 131, 123, 189, 167, 226, 124, 81];
+/// Instruction discriminator of the post-liquidity-change callback, Anchor's
+/// `global:on_liquidity_change`, followed by a [`LiquidityCallback`]
+pub const LIQUIDITY_CALLBACK_DISCRIMINATOR: [u8; 8] = [199, 227, 116, 213, 160, 46, 135, 146];
+/// Heap frame, in bytes, transactions must request from a proxy built with
+/// `custom-heap`
+pub const HEAP_FRAME_BYTES: u32 = 64 * 1024;
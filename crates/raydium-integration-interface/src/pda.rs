@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+
+pub const USER_CONFIG_SEED: &[u8] = b"user_cfg";
+pub const EXTENSION_OPT_IN_SEED: &[u8] = b"extension_opt_in";
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const FEE_ORACLE_SEED: &[u8] = b"fee_oracle";
+pub const SWAP_VALIDATORS_SEED: &[u8] = b"swap_validators";
+pub const DESTINATION_ALLOWLIST_SEED: &[u8] = b"destination_allowlist";
+pub const RECOVERY_SEED: &[u8] = b"recovery";
+pub const SESSION_SEED: &[u8] = b"session";
+pub const SESSION_AUTHORITY_SEED: &[u8] = b"session_authority";
+pub const DUTCH_ORDER_SEED: &[u8] = b"dutch_order";
+pub const DUTCH_ESCROW_SEED: &[u8] = b"dutch_escrow";
+pub const FLOOR_ORDER_SEED: &[u8] = b"floor_order";
+pub const FLOOR_ESCROW_SEED: &[u8] = b"floor_escrow";
+pub const SWAP_COMMITMENT_SEED: &[u8] = b"swap_commitment";
+pub const GLOBAL_STATS_SEED: &[u8] = b"global_stats";
+pub const POINTS_CONFIG_SEED: &[u8] = b"points_config";
+pub const POINTS_LEDGER_SEED: &[u8] = b"points";
+pub const POSITION_POINTS_SEED: &[u8] = b"position_points";
+pub const POOL_STATS_SEED: &[u8] = b"pool_stats";
+pub const POSITION_REGISTRY_SEED: &[u8] = b"position_registry";
+pub const COST_BASIS_SEED: &[u8] = b"cost_basis";
+pub const OPEN_APPROVAL_SEED: &[u8] = b"open_approval";
+pub const FEE_CHECKPOINT_SEED: &[u8] = b"fee_checkpoint";
+pub const POSITION_STREAM_SEED: &[u8] = b"position_stream";
+pub const SHARED_POSITION_SEED: &[u8] = b"shared_position";
+pub const SHARED_SHARE_SEED: &[u8] = b"shared_share";
+pub const POOL_EXPOSURE_SEED: &[u8] = b"pool_exposure";
+pub const POOL_CREATOR_SEED: &[u8] = b"pool_creator";
+pub const CREATOR_FEES_SEED: &[u8] = b"creator_fees";
+pub const ROUND_TRIPS_SEED: &[u8] = b"round_trips";
+pub const PENDING_PARAM_CHANGE_SEED: &[u8] = b"pending_param_change";
+pub const LP_LOCK_SEED: &[u8] = b"lp_lock";
+pub const LP_LOCK_ESCROW_SEED: &[u8] = b"lp_lock_escrow";
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+pub fn user_config_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[USER_CONFIG_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn extension_opt_in_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXTENSION_OPT_IN_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &crate::ID)
+}
+
+pub fn global_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &crate::ID)
+}
+
+/// Token account of the insurance fund for `mint`
+pub fn insurance_vault_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_FUND_SEED, mint.as_ref()], &crate::ID)
+}
+
+pub fn fee_oracle_address(input_mint: &Pubkey, fee_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FEE_ORACLE_SEED, input_mint.as_ref(), fee_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn swap_validators_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SWAP_VALIDATORS_SEED], &crate::ID)
+}
+
+pub fn destination_allowlist_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DESTINATION_ALLOWLIST_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn recovery_config_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECOVERY_SEED, owner.as_ref()], &crate::ID)
+}
+
+/// Session of `session_key` under the owner's `user_cfg`
+pub fn session_address(user_cfg: &Pubkey, session_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SESSION_SEED, user_cfg.as_ref(), session_key.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Delegate the owner approves on token accounts session swaps spend from
+pub fn session_authority_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SESSION_AUTHORITY_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn dutch_order_address(owner: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DUTCH_ORDER_SEED, owner.as_ref(), &order_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Token account holding a Dutch order's input
+pub fn dutch_escrow_address(dutch_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DUTCH_ESCROW_SEED, dutch_order.as_ref()], &crate::ID)
+}
+
+pub fn swap_commitment_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SWAP_COMMITMENT_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn global_stats_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GLOBAL_STATS_SEED], &crate::ID)
+}
+
+pub fn points_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POINTS_CONFIG_SEED], &crate::ID)
+}
+
+pub fn points_ledger_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POINTS_LEDGER_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn position_points_address(position_nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POSITION_POINTS_SEED, position_nft_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn pool_stats_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_STATS_SEED, pool.as_ref()], &crate::ID)
+}
+
+pub fn position_registry_address(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POSITION_REGISTRY_SEED, owner.as_ref()], &crate::ID)
+}
+
+pub fn position_registry_page_address(owner: &Pubkey, page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POSITION_REGISTRY_SEED, owner.as_ref(), &page.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+pub fn cost_basis_address(position_nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COST_BASIS_SEED, position_nft_mint.as_ref()], &crate::ID)
+}
+
+pub fn open_approval_address(owner: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[OPEN_APPROVAL_SEED, owner.as_ref(), delegate.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn fee_checkpoint_address(position_nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FEE_CHECKPOINT_SEED, position_nft_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn position_stream_address(position_nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POSITION_STREAM_SEED, position_nft_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn shared_position_address(position_nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SHARED_POSITION_SEED, position_nft_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn shared_share_address(shared_position: &Pubkey, contributor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SHARED_SHARE_SEED,
+            shared_position.as_ref(),
+            contributor.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn pool_exposure_address(owner: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[POOL_EXPOSURE_SEED, owner.as_ref(), pool.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn pending_param_change_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_PARAM_CHANGE_SEED], &crate::ID)
+}
+
+pub fn pool_creator_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[POOL_CREATOR_SEED, pool.as_ref()], &crate::ID)
+}
+
+/// Token account of `pool`'s creator fees in `mint`
+pub fn creator_fee_vault_address(pool: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CREATOR_FEES_SEED, pool.as_ref(), mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn round_trip_counter_address(owner: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ROUND_TRIPS_SEED, owner.as_ref(), pool.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn floor_order_address(owner: &Pubkey, order_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[FLOOR_ORDER_SEED, owner.as_ref(), &order_id.to_le_bytes()],
+        &crate::ID,
+    )
+}
+
+/// Token account holding a floor order's unsold input
+pub fn floor_escrow_address(floor_order: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FLOOR_ESCROW_SEED, floor_order.as_ref()], &crate::ID)
+}
+
+pub fn lp_lock_address(owner: &Pubkey, lp_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[LP_LOCK_SEED, owner.as_ref(), lp_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Token account holding an LP lock's tokens
+pub fn lp_lock_escrow_address(lp_lock: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[LP_LOCK_ESCROW_SEED, lp_lock.as_ref()], &crate::ID)
+}
@@ -0,0 +1,573 @@
+use anchor_lang::prelude::*;
+
+/*
+ * Per-user settings, PDA seeded by [b"user_cfg", owner]. Zero-copy on chain;
+ * the packed layout decodes the same as this Borsh one
+ */
+#[account]
+pub struct UserConfig {
+    pub owner: Pubkey,
+    pub slippage_bps: u16,
+    pub total_swaps: u64,
+    pub volume_zero_for_one: u128,
+    pub volume_one_for_zero: u128,
+    pub fees_paid: u128,
+    pub positions_opened: u64,
+    pub last_activity_ts: i64,
+    pub allowed_callers: [Pubkey; crate::MAX_ALLOWED_CALLERS],
+    pub allowed_caller_count: u8,
+    pub slippage_samples: u64,
+    pub realized_slippage_bps_sum: i64,
+    pub exposure_quote_mint: Pubkey,
+    pub max_pool_exposure: u64,
+    pub max_total_exposure: u64,
+    pub total_exposure: u64,
+    pub event_seq: u64,
+}
+impl UserConfig {
+    pub const SIZE: usize = 32
+        + 2
+        + 8
+        + 16
+        + 16
+        + 16
+        + 8
+        + 8
+        + 32 * crate::MAX_ALLOWED_CALLERS
+        + 1
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8;
+}
+
+/*
+ * A user's exposure to one pool while their config caps it, PDA seeded by
+ * [b"pool_exposure", owner, pool]
+ */
+#[account]
+pub struct PoolExposure {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub exposure: u64,
+}
+impl PoolExposure {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+/*
+ * Token-2022 extensions a user accepts on pool mints when depositing, PDA
+ * seeded by [b"extension_opt_in", owner]
+ */
+#[account]
+pub struct MintExtensionOptIn {
+    pub owner: Pubkey,
+    /// Bitmask of [`extension_flags`]
+    pub allowed_extensions: u8,
+}
+impl MintExtensionOptIn {
+    pub const SIZE: usize = 32 + 1;
+}
+
+/// Flags of `MintExtensionOptIn::allowed_extensions`
+pub mod extension_flags {
+    pub const PERMANENT_DELEGATE: u8 = 1 << 0;
+    pub const TRANSFER_HOOK: u8 = 1 << 1;
+    pub const CONFIDENTIAL_TRANSFER: u8 = 1 << 2;
+    pub const PAUSABLE: u8 = 1 << 3;
+}
+
+/*
+ * Protocol governance, PDA seeded by [b"global_config"]. Also the authority
+ * of the insurance fund vaults
+ */
+#[account]
+pub struct GlobalConfig {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+}
+impl GlobalConfig {
+    pub const SIZE: usize = 32 + 32 + 2 + 2 + 1 + 2 + 4 + 2;
+}
+
+/*
+ * Governance change announced ahead of taking effect, PDA seeded by
+ * [b"pending_param_change"]
+ */
+#[account]
+pub struct PendingParamChange {
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+    pub activation_slot: u64,
+    pub scheduled_slot: u64,
+}
+impl PendingParamChange {
+    pub const SIZE: usize = 32 + 2 + 2 + 1 + 2 + 4 + 2 + 8 + 8;
+}
+
+/*
+ * Raydium pool pricing protocol fees owed in `input_mint` when paid in
+ * `fee_mint`, PDA seeded by [b"fee_oracle", input_mint, fee_mint]
+ */
+#[account]
+pub struct FeeOracle {
+    pub input_mint: Pubkey,
+    pub fee_mint: Pubkey,
+    pub pool: Pubkey,
+    pub input_is_token_0: bool,
+    pub twap_window_secs: u32,
+    pub max_staleness_secs: u32,
+    pub max_deviation_ticks: u32,
+}
+impl FeeOracle {
+    pub const SIZE: usize = 32 + 32 + 32 + 1 + 4 + 4 + 4;
+}
+
+/*
+ * Creator of a pool created through the proxy, PDA seeded by
+ * [b"pool_creator", pool]. Authority of the pool's creator fee vaults
+ */
+#[account]
+pub struct PoolCreatorRecord {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+}
+impl PoolCreatorRecord {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+/*
+ * AMM v4 LP tokens locked until `unlock_ts`, PDA seeded by
+ * [b"lp_lock", owner, lp_mint]. Authority of the escrow holding them
+ */
+#[account]
+pub struct AmmLpLock {
+    pub owner: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+}
+impl AmmLpLock {
+    pub const SIZE: usize = 32 + 32 + 8 + 8;
+}
+
+/*
+ * Programs every swap is validated by, PDA seeded by [b"swap_validators"]
+ */
+#[account]
+pub struct SwapValidatorRegistry {
+    pub validators: [Pubkey; crate::MAX_SWAP_VALIDATORS],
+    pub validator_count: u8,
+}
+impl SwapValidatorRegistry {
+    pub const SIZE: usize = 32 * crate::MAX_SWAP_VALIDATORS + 1;
+}
+
+/*
+ * Token accounts an owner's swap outputs and withdrawals may go to, PDA
+ * seeded by [b"destination_allowlist", owner]. Enforced while `enabled` and
+ * before `disable_at`, when that is set
+ */
+#[account]
+pub struct DestinationAllowlist {
+    pub owner: Pubkey,
+    pub enabled: bool,
+    pub disable_at: i64,
+    pub destinations: [AllowedDestination; crate::MAX_ALLOWED_DESTINATIONS],
+    pub destination_count: u8,
+}
+impl DestinationAllowlist {
+    pub const SIZE: usize =
+        32 + 1 + 8 + AllowedDestination::SIZE * crate::MAX_ALLOWED_DESTINATIONS + 1;
+}
+
+/// A listed token account, usable from `active_at`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct AllowedDestination {
+    pub token_account: Pubkey,
+    pub active_at: i64,
+}
+impl AllowedDestination {
+    pub const SIZE: usize = 32 + 8;
+}
+
+/*
+ * Guardians able to move an owner's config to a new key, and the recovery
+ * they are voting on, PDA seeded by [b"recovery", owner]
+ */
+#[account]
+pub struct RecoveryConfig {
+    pub owner: Pubkey,
+    pub guardians: [Pubkey; crate::MAX_GUARDIANS],
+    pub guardian_count: u8,
+    pub threshold: u8,
+    /// Default when no recovery is pending
+    pub pending_owner: Pubkey,
+    /// Bit `i` is set once `guardians[i]` approved `pending_owner`
+    pub approvals: u8,
+    /// Zero until the threshold is met
+    pub ready_at: i64,
+}
+impl RecoveryConfig {
+    pub const SIZE: usize = 32 + 32 * crate::MAX_GUARDIANS + 1 + 1 + 32 + 1 + 8;
+}
+
+/*
+ * Short-lived key allowed to sign some instructions for the owner, PDA
+ * seeded by [b"session", user_cfg, session_key]
+ */
+#[account]
+pub struct Session {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expiry: i64,
+    /// Bitmask of `SESSION_SCOPE_*` flags
+    pub scope: u8,
+    /// Any pool when `pool_count` is zero
+    pub allowed_pools: [Pubkey; crate::MAX_SESSION_POOLS],
+    pub pool_count: u8,
+    /// One of the `SESSION_DIRECTION_*` values
+    pub direction: u8,
+    /// Other input mints are refused when `spend_limit_count` is nonzero
+    pub spend_limits: [SessionSpendLimit; crate::MAX_SESSION_SPEND_MINTS],
+    pub spend_limit_count: u8,
+    /// Spent so far in each mint of `spend_limits`
+    pub spent: [u64; crate::MAX_SESSION_SPEND_MINTS],
+}
+impl Session {
+    pub const SIZE: usize = 32
+        + 32
+        + 8
+        + 1
+        + 32 * crate::MAX_SESSION_POOLS
+        + 1
+        + 1
+        + SessionSpendLimit::SIZE * crate::MAX_SESSION_SPEND_MINTS
+        + 1
+        + 8 * crate::MAX_SESSION_SPEND_MINTS;
+}
+
+/// A session's amount limits in one input mint's raw units
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct SessionSpendLimit {
+    pub mint: Pubkey,
+    /// Zero for no limit
+    pub max_trade_amount: u64,
+    /// Zero for no limit
+    pub spend_cap: u64,
+}
+impl SessionSpendLimit {
+    pub const SIZE: usize = 32 + 8 + 8;
+}
+
+/*
+ * Input escrowed for a Dutch auction, PDA seeded by
+ * [b"dutch_order", owner, order_id (u64 LE)]
+ */
+#[account]
+pub struct DutchOrder {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub destination: Pubkey,
+    /// Input the escrow received, net of any transfer fee
+    pub amount_in: u64,
+    /// Least output accepted at `start_ts`, falling linearly to
+    /// `end_min_out` at `end_ts`
+    pub start_min_out: u64,
+    pub end_min_out: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+impl DutchOrder {
+    pub const SIZE: usize = 32 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8;
+}
+
+/*
+ * Input escrowed to be sold at or above a floor price, PDA seeded by
+ * [b"floor_order", owner, order_id (u64 LE)]
+ */
+#[account]
+pub struct FloorOrder {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub destination: Pubkey,
+    /// Least output per unit of input, raw units in Q64.64
+    pub floor_price_x64: u128,
+    pub daily_max: u64,
+    pub day_start_ts: i64,
+    pub sold_today: u64,
+    pub total_sold: u64,
+}
+impl FloorOrder {
+    pub const SIZE: usize = 32 + 8 + 32 + 32 + 32 + 16 + 8 + 8 + 8 + 8;
+}
+
+/*
+ * Pending commit-reveal swap of an owner, PDA seeded by
+ * [b"swap_commitment", owner]
+ */
+#[account]
+pub struct SwapCommitment {
+    pub owner: Pubkey,
+    pub hash: [u8; 32],
+    pub slot: u64,
+}
+impl SwapCommitment {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+/*
+ * Points rates, PDA seeded by [b"points_config"]. Both are points per
+ * [`crate::POINTS_RATE_DENOMINATOR`] units
+ */
+#[account]
+pub struct PointsConfig {
+    pub swap_points_per_unit: u64,
+    pub lp_points_per_unit: u64,
+}
+impl PointsConfig {
+    pub const SIZE: usize = 8 + 8;
+}
+
+/*
+ * Points an owner has earned, PDA seeded by [b"points", owner]
+ */
+#[account]
+pub struct PointsLedger {
+    pub owner: Pubkey,
+    pub swap_points: u128,
+    pub lp_points: u128,
+    pub claimed: u128,
+    pub last_update_ts: i64,
+}
+impl PointsLedger {
+    pub const SIZE: usize = 32 + 16 + 16 + 16 + 8;
+}
+
+/*
+ * Last LP points accrual of a position, PDA seeded by
+ * [b"position_points", position_nft_mint]
+ */
+#[account]
+pub struct PositionPoints {
+    pub position_nft: Pubkey,
+    pub last_accrual_ts: i64,
+}
+impl PositionPoints {
+    pub const SIZE: usize = 32 + 8;
+}
+
+/*
+ * Protocol-wide counters, PDA seeded by [b"global_stats"]. Zero-copy on
+ * chain, like `UserConfig`
+ */
+#[account]
+pub struct GlobalStats {
+    pub swap_count: u64,
+    pub volume: u128,
+    pub positions_opened: u64,
+    pub pools_tracked: u64,
+    pub protocol_fees_collected: u128,
+    pub last_update_ts: i64,
+}
+impl GlobalStats {
+    pub const SIZE: usize = 8 + 16 + 8 + 8 + 16 + 8;
+}
+
+/*
+ * Per-pool counters, PDA seeded by [b"pool_stats", pool]. Zero-copy on chain,
+ * like `UserConfig`
+ */
+#[account]
+pub struct PoolStats {
+    pub pool: Pubkey,
+    pub swap_count: u64,
+    pub volume_token_0_in: u128,
+    pub volume_token_1_in: u128,
+    pub positions_opened: u64,
+    pub protocol_fees_0: u64,
+    pub protocol_fees_1: u64,
+    pub last_update_ts: i64,
+}
+impl PoolStats {
+    pub const SIZE: usize = 32 + 8 + 16 + 16 + 8 + 8 + 8 + 8;
+}
+
+/*
+ * Round trips one user makes on one pool, PDA seeded by
+ * [b"round_trips", owner, pool]
+ */
+#[account]
+pub struct RoundTripCounter {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub last_zero_for_one: bool,
+    pub last_swap_ts: i64,
+    pub window_start_ts: i64,
+    pub round_trips: u16,
+    pub times_flagged: u32,
+}
+impl RoundTripCounter {
+    pub const SIZE: usize = 32 + 32 + 1 + 8 + 8 + 2 + 4;
+}
+
+/*
+ * Index of positions opened through the proxy, PDA seeded by
+ * [b"position_registry", owner]
+ */
+#[account]
+pub struct UserPositionRegistry {
+    pub owner: Pubkey,
+    pub page_count: u32,
+    pub position_count: u32,
+}
+impl UserPositionRegistry {
+    pub const SIZE: usize = 32 + 4 + 4;
+}
+
+/*
+ * Page of position mints, PDA seeded by
+ * [b"position_registry", owner, page (u32 LE)]
+ */
+#[account]
+pub struct PositionRegistryPage {
+    pub owner: Pubkey,
+    pub page: u32,
+    pub positions: Vec<Pubkey>,
+}
+impl PositionRegistryPage {
+    pub const MAX_POSITIONS: usize = 32;
+    pub const SIZE: usize = 32 + 4 + 4 + 32 * Self::MAX_POSITIONS;
+}
+
+/*
+ * Entry price and cumulative proxy-routed flows of a position, PDA seeded by
+ * [b"cost_basis", position_nft_mint]
+ */
+#[account]
+pub struct PositionCostBasis {
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub open_sqrt_price_x64: u128,
+    pub opened_at: i64,
+    pub deposited_0: u64,
+    pub deposited_1: u64,
+    pub withdrawn_0: u64,
+    pub withdrawn_1: u64,
+    pub fees_collected_0: u64,
+    pub fees_collected_1: u64,
+    pub cost_token_1: u128,
+    pub proceeds_token_1: u128,
+}
+impl PositionCostBasis {
+    pub const SIZE: usize = 32 + 32 + 16 + 8 + 8 * 6 + 16 + 16;
+}
+
+/*
+ * An owner's approval for `delegate` to open positions the owner receives,
+ * PDA seeded by [b"open_approval", owner, delegate]
+ */
+#[account]
+pub struct OpenApproval {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+}
+impl OpenApproval {
+    pub const SIZE: usize = 32 + 32;
+}
+
+pub const FEE_CHECKPOINT_CAPACITY: usize = 24;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeCheckpointEntry {
+    pub timestamp: i64,
+    pub liquidity: u128,
+    pub cumulative_fees_0: u64,
+    pub cumulative_fees_1: u64,
+}
+
+/*
+ * Ring of the latest fee checkpoints of a position, PDA seeded by
+ * [b"fee_checkpoint", position_nft_mint]
+ */
+#[account]
+pub struct FeeCheckpoint {
+    pub position_nft: Pubkey,
+    pub head: u32,
+    pub count: u32,
+    pub entries: [FeeCheckpointEntry; FEE_CHECKPOINT_CAPACITY],
+}
+impl FeeCheckpoint {
+    pub const SIZE: usize = 32 + 4 + 4 + (8 + 16 + 8 + 8) * FEE_CHECKPOINT_CAPACITY;
+}
+
+/*
+ * Linear unlock of a locked position, PDA seeded by
+ * [b"position_stream", position_nft_mint]
+ */
+#[account]
+pub struct PositionStream {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub initial_liquidity: u128,
+    pub claimed_liquidity: u128,
+}
+impl PositionStream {
+    pub const SIZE: usize = 32 + 32 + 32 + 32 + 8 + 8 + 16 + 16;
+}
+
+/*
+ * Position NFT held by a PDA on behalf of several contributors, PDA seeded
+ * by [b"shared_position", position_nft_mint]
+ */
+#[account]
+pub struct SharedPosition {
+    pub creator: Pubkey,
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub total_shares: u128,
+    pub fee_per_share_0_x64: u128,
+    pub fee_per_share_1_x64: u128,
+}
+impl SharedPosition {
+    pub const SIZE: usize = 32 + 32 + 32 + 16 + 16 + 16;
+}
+
+/*
+ * A contributor's stake in a shared position, PDA seeded by
+ * [b"shared_share", shared_position, contributor]
+ */
+#[account]
+pub struct SharedPositionShare {
+    pub shared_position: Pubkey,
+    pub contributor: Pubkey,
+    pub shares: u128,
+    pub fee_debt_0: u128,
+    pub fee_debt_1: u128,
+}
+impl SharedPositionShare {
+    pub const SIZE: usize = 32 + 32 + 16 + 16 + 16;
+}
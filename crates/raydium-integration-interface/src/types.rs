@@ -0,0 +1,136 @@
+//! Return-data types of the proxy's read-only instructions.
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ImpermanentLossSnapshot {
+    pub sqrt_price_x64: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub hodl_amount_0: u64,
+    pub hodl_amount_1: u64,
+    pub position_value_token_1: u128,
+    pub hodl_value_token_1: u128,
+    pub il_bps: i64,
+}
+
+/// Return data of `get_position_value`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PositionValue {
+    pub sqrt_price_x64: u128,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub fees_0: u64,
+    pub fees_1: u64,
+    pub value_token_1: u128,
+}
+
+/// Return data of `get_pool_health`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolHealth {
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    pub liquidity: u128,
+    pub min_liquidity_in_range: u128,
+    pub depth_0: u64,
+    pub depth_1: u64,
+    pub trade_fee_rate: u32,
+    pub observation_age: i64,
+}
+
+/// Return data of `get_status`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProgramStatus {
+    pub flags: u8,
+    pub paused: bool,
+    pub swaps_paused: bool,
+    pub requires_user_slippage: bool,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    /// Zero without a scheduled change
+    pub pending_flags: u8,
+    pub pending_activation_slot: u64,
+}
+
+/// Return data of a `simulate_only` swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapSimulation {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub sqrt_price_after_x64: u128,
+    pub tick_after: i32,
+    pub protocol_fee: u64,
+}
+
+/// Return data of a `partial_fill` swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapFill {
+    pub amount_requested: u64,
+    pub amount_filled: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub amm_fee: u64,
+    pub protocol_fee: u64,
+}
+
+/// Return data of a `simulate_only` open/increase/decrease
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquiditySimulation {
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub fees_0: u64,
+    pub fees_1: u64,
+    /// Principal as wallets display it for interest-bearing or scaled-UI mints
+    pub ui_amount_0: u64,
+    pub ui_amount_1: u64,
+}
+
+/// Return data of `claim_points`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PointsClaim {
+    pub amount: u128,
+    pub total_claimed: u128,
+}
+
+/// Instruction data of the CPI swap validators receive, after
+/// [`crate::VALIDATE_SWAP_DISCRIMINATOR`]. Accounts: payer, pool, input and
+/// output token accounts, all read-only
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapValidation {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount: u64,
+    pub expected_other_amount: u64,
+    pub is_base_input: bool,
+}
+
+/// Instruction data of the callback after a swap, following
+/// [`crate::SWAP_CALLBACK_DISCRIMINATOR`]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapCallback {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/// Instruction data of the callback after a liquidity change, following
+/// [`crate::LIQUIDITY_CALLBACK_DISCRIMINATOR`]. `liquidity_delta` is
+/// negative on withdrawals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquidityCallback {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub liquidity_delta: i128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub fees_0: u64,
+    pub fees_1: u64,
+}
@@ -0,0 +1,130 @@
+//! Program-wide constants and the Raydium program IDs the proxy forwards to.
+use anchor_lang::prelude::*;
+
+/// Raydium CLMM program every `clmm_program` account must match, mainnet by
+/// default and devnet with the `devnet` feature
+pub use raydium_amm_v3::ID as RAYDIUM_CLMM_PROGRAM_ID;
+/// Raydium CPMM (constant-product) program `migrate_cpmm_to_clmm` withdraws
+/// from, mainnet by default and devnet with the `devnet` feature
+#[cfg(not(feature = "devnet"))]
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+#[cfg(feature = "devnet")]
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = pubkey!("CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW");
+/// OpenBook (Serum v3 fork) program `proxy_create_openbook_market`
+/// initializes markets on, mainnet by default and devnet with the `devnet`
+/// feature
+#[cfg(not(feature = "devnet"))]
+pub const OPENBOOK_PROGRAM_ID: Pubkey = pubkey!("srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX");
+#[cfg(feature = "devnet")]
+pub const OPENBOOK_PROGRAM_ID: Pubkey = pubkey!("EoTcMgcDRTJVZDMZWBoU6rhYHZfkNTVEAfz3uUJRcYGj");
+/// Raydium AMM v4 program `proxy_create_amm_pool` creates pools with,
+/// mainnet by default and devnet with the `devnet` feature
+#[cfg(not(feature = "devnet"))]
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
+    pubkey!("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+#[cfg(feature = "devnet")]
+pub const RAYDIUM_AMM_V4_PROGRAM_ID: Pubkey =
+    pubkey!("HWy1jotHpo6UqeQxx49dpYYdQB8wj9Qk9MdxwjLvDHB8");
+/// SPL Memo program, which CPMM withdrawals of Token-2022 mints require
+pub const SPL_MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+pub const DEFAULT_SLIPPAGE_BPS: u16 = 500;
+/// Global flag: swaps fail for users who never set a slippage tolerance,
+/// instead of falling back to the default
+pub const FLAG_REQUIRE_USER_SLIPPAGE: u8 = 1 << 0;
+/// Global flag: swaps, opening and increasing positions, splits and merges,
+/// order creation and fills, shared deposits, new streams and LP locks,
+/// migrations, and pool and market creation fail with `ProgramPaused`.
+/// Decreasing and closing positions, shared withdrawals and fee claims,
+/// stream claims, LP unlocks, order cancellation, user settings and
+/// governance stay available
+pub const FLAG_PAUSED: u8 = 1 << 1;
+/// Global flag: swaps, order fills and commit-reveal swaps fail with
+/// `FeaturePaused`
+pub const FLAG_PAUSE_SWAPS: u8 = 1 << 2;
+/// Global flag: swaps past the round-trip threshold fail instead of being
+/// flagged
+pub const FLAG_THROTTLE_ROUND_TRIPS: u8 = 1 << 3;
+pub const ALL_GLOBAL_FLAGS: u8 =
+    FLAG_REQUIRE_USER_SLIPPAGE | FLAG_PAUSED | FLAG_PAUSE_SWAPS | FLAG_THROTTLE_ROUND_TRIPS;
+/// The only flags `set_pause_flags` changes, without notice
+pub const PAUSE_FLAGS: u8 = FLAG_PAUSED | FLAG_PAUSE_SWAPS;
+
+/// Raydium expresses `AmmConfig::trade_fee_rate` in millionths
+pub const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+pub const FEE_CHECKPOINT_CAPACITY: usize = 24;
+/// Minimum seconds between two stamps of the same fee checkpoint
+pub const FEE_CHECKPOINT_MIN_INTERVAL: i64 = 3_600;
+/// User configs `admin_migrate_configs` takes per call
+pub const MAX_CONFIG_MIGRATION_BATCH: usize = 20;
+/// Programs a user config can allow to call the proxy on the owner's behalf
+pub const MAX_ALLOWED_CALLERS: usize = 4;
+/// Ceiling governance can set the protocol fee to
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 100;
+/// Slots ahead a governance parameter change must be scheduled, about a day
+pub const MIN_PARAM_CHANGE_NOTICE_SLOTS: u64 = 216_000;
+/// Points rates are expressed per this many units
+pub const POINTS_RATE_DENOMINATOR: u128 = 1_000_000;
+/// Validator programs the admin can register to vet every swap
+pub const MAX_SWAP_VALIDATORS: usize = 4;
+/// Length of the day a floor order's `daily_max` applies to
+pub const FLOOR_ORDER_DAY_SECS: i64 = 86_400;
+/// Token accounts a user's destination allowlist can hold
+pub const MAX_ALLOWED_DESTINATIONS: usize = 8;
+/// Seconds before an allowlist addition, or disabling the allowlist, applies
+pub const DESTINATION_ADD_DELAY: i64 = 86_400;
+/// Guardians a user can designate for config recovery
+pub const MAX_GUARDIANS: usize = 5;
+/// Seconds between guardians reaching their threshold and the recovery
+pub const RECOVERY_DELAY: i64 = 172_800;
+/// Longest a session key can stay valid, in seconds
+pub const MAX_SESSION_DURATION: i64 = 604_800;
+/// Session scope: `session_swap`
+pub const SESSION_SCOPE_SWAP: u8 = 1 << 0;
+/// Session scope: `session_set_slippage`
+pub const SESSION_SCOPE_SET_SLIPPAGE: u8 = 1 << 1;
+pub const ALL_SESSION_SCOPES: u8 = SESSION_SCOPE_SWAP | SESSION_SCOPE_SET_SLIPPAGE;
+/// Pools a session's swaps can be limited to
+pub const MAX_SESSION_POOLS: usize = 4;
+/// Input mints a session can carry amount limits for
+pub const MAX_SESSION_SPEND_MINTS: usize = 4;
+/// Session swap direction: either way
+pub const SESSION_DIRECTION_ANY: u8 = 0;
+/// Session swap direction: token_0 for token_1 only
+pub const SESSION_DIRECTION_ZERO_FOR_ONE: u8 = 1;
+/// Session swap direction: token_1 for token_0 only
+pub const SESSION_DIRECTION_ONE_FOR_ZERO: u8 = 2;
+/// Slots a commit-reveal swap waits at least, so both phases cannot land in
+/// the same slot
+pub const MIN_REVEAL_DELAY_SLOTS: u64 = 1;
+/// Slots after its commitment a swap can still be revealed in
+pub const REVEAL_WINDOW_SLOTS: u64 = 32;
+/// Instruction discriminator swap validators receive, Anchor's
+/// `global:validate_swap`
+pub const VALIDATE_SWAP_DISCRIMINATOR: [u8; 8] = [218, 214, 34, 73, 72, 12, 65, 203];
+/// Instruction discriminator of the swap callback, Anchor's `global:on_swap`
+pub const SWAP_CALLBACK_DISCRIMINATOR: [u8; 8] = [201, 131, 123, 189, 167, 226, 124, 81];
+/// Instruction discriminator of the liquidity callback, Anchor's
+/// `global:on_liquidity_change`
+pub const LIQUIDITY_CALLBACK_DISCRIMINATOR: [u8; 8] = [199, 227, 116, 213, 160, 46, 135, 146];
+/// Instruction discriminator of Raydium CPMM's `withdraw`
+pub const CPMM_WITHDRAW_DISCRIMINATOR: [u8; 8] = [183, 18, 70, 156, 148, 109, 161, 34];
+/// Instruction tag of OpenBook's `InitializeMarket`, after the version byte
+pub const OPENBOOK_INITIALIZE_MARKET_TAG: u32 = 0;
+/// Fee rate field of `InitializeMarket`; OpenBook ignores it and charges its
+/// own fee tiers
+pub const OPENBOOK_FEE_RATE_BPS: u16 = 0;
+/// Instruction tag of Raydium AMM v4's `initialize2`
+pub const AMM_V4_INITIALIZE2_TAG: u8 = 1;
+/// Compute units left below which a Raydium CLMM CPI is not attempted. Well
+/// under what the lightest one consumes, so only calls sure to run out fail
+pub const MIN_CLMM_CPI_COMPUTE_UNITS: u64 = 20_000;
+/// Compute units added to that minimum per account after the bitmap
+/// extension, each an extra tick array a swap may cross into
+pub const TICK_ARRAY_CPI_COMPUTE_UNITS: u64 = 5_000;
+/// Heap a `custom-heap` build allocates from, in bytes, twice the runtime's
+/// 32 KiB default. Room for a swap passing a transaction's 64 accounts through
+/// validators, a callback and extra tick arrays; transactions into such a
+/// build must request a heap frame of this size
+pub const HEAP_FRAME_BYTES: u32 = 64 * 1024;
@@ -0,0 +1,38 @@
+//! Prelude every instruction module glob-imports: the Anchor, SPL and Raydium
+//! items account contexts and handlers use, plus the crate's constants,
+//! state, events, errors, math and shared helpers.
+pub use anchor_lang::prelude::*;
+pub use anchor_lang::solana_program::instruction::Instruction;
+pub use anchor_lang::solana_program::program::{invoke, invoke_signed, set_return_data};
+pub use anchor_lang::system_program;
+pub use anchor_lang::InstructionData;
+pub use anchor_spl::associated_token::{
+    get_associated_token_address_with_program_id, AssociatedToken,
+};
+pub use anchor_spl::metadata::Metadata;
+pub use anchor_spl::token::Token;
+pub use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, Token2022, TokenAccount, TokenInterface, TransferChecked,
+};
+pub use raydium_amm_v3::{
+    cpi, instruction as clmm_instruction,
+    instructions::InitializeRewardParam,
+    libraries::{big_num::U256, liquidity_math},
+    program::AmmV3,
+    states::{
+        AmmConfig, ObservationState, PersonalPositionState, PoolState, TickArrayState,
+        POOL_TICK_ARRAY_BITMAP_SEED, POSITION_SEED, TICK_ARRAY_SIZE,
+    },
+};
+
+pub use crate::constants::*;
+pub use crate::errors::*;
+pub use crate::events::*;
+pub use crate::instructions::shared::*;
+pub use crate::math::{
+    self, compute_protocol_fee, compute_slippage_threshold, compute_trade_fee,
+    count_tick_crossings, liquidity_depth, liquidity_for_amounts, prorate_threshold,
+    realized_slippage_bps, uncollected_fees, value_in_token_1,
+};
+pub use crate::state::*;
+pub use crate::token_extensions;
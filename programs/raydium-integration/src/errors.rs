@@ -0,0 +1,238 @@
+//! Errors the program fails with.
+use anchor_lang::prelude::*;
+
+/*
+ * Error codes
+ */
+#[error_code]
+pub enum CustomError {
+    #[msg("Invalid slippage basis points")]
+    InvalidSlippage,
+
+    #[msg("Invalid tick range")]
+    InvalidTickRange,
+
+    #[msg("Zero liquidity")]
+    ZeroLiquidity,
+
+    #[msg("Zero deposit")]
+    ZeroDeposit,
+
+    #[msg("Invalid vault")]
+    InvalidVault,
+
+    #[msg("Zero swap amount")]
+    ZeroSwapAmount,
+
+    #[msg("Invalid expected amount")]
+    InvalidExpectedAmount,
+
+    #[msg("User config already has the current layout")]
+    ConfigUpToDate,
+
+    #[msg("Position is not in the supplied registry page")]
+    PositionNotInRegistry,
+
+    #[msg("Registry and registry page must be passed together")]
+    RegistryAccountsMismatch,
+
+    #[msg("Registry page is full")]
+    RegistryPageFull,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Position does not belong to the pool")]
+    PositionPoolMismatch,
+
+    #[msg("Tick is not covered by the supplied tick array")]
+    InvalidTickArray,
+
+    #[msg("Fee checkpoint stamped too recently")]
+    CheckpointTooSoon,
+
+    #[msg("Simulation complete, results are in the return data")]
+    SimulationComplete,
+
+    #[msg("Swap would cross more initialized ticks than allowed")]
+    TooManyTickCrossings,
+
+    #[msg("Partial fills need a sqrt price limit")]
+    PriceLimitRequired,
+
+    #[msg("Realized price is worse than the slippage threshold")]
+    SlippageExceeded,
+
+    #[msg("Range must be between 1 and 9999 basis points")]
+    InvalidRangeBps,
+
+    #[msg("Split must leave liquidity in the source position")]
+    InvalidSplitLiquidity,
+
+    #[msg("Source and target are the same position")]
+    SamePosition,
+
+    #[msg("Positions do not share the same tick range")]
+    PositionRangeMismatch,
+
+    #[msg("Stream must end after it starts")]
+    InvalidStreamSchedule,
+
+    #[msg("No unlocked liquidity to claim")]
+    NothingToClaim,
+
+    #[msg("Not enough shares")]
+    InsufficientShares,
+    #[msg("Pool mint has a Token-2022 extension the user has not opted in to")]
+    UnsupportedMintExtension,
+    #[msg("Unknown mint extension flag")]
+    UnknownExtensionFlag,
+    #[msg("Metadata account is required to create metadata")]
+    MetadataAccountRequired,
+    #[msg("Too many allowed caller programs")]
+    TooManyAllowedCallers,
+    #[msg("Calling program is not on the owner's allowlist")]
+    CallerNotAllowed,
+    #[msg("Instructions sysvar is required to check the caller")]
+    InstructionsSysvarRequired,
+    #[msg("Signer is not authorized")]
+    Unauthorized,
+    #[msg("Invalid protocol fee")]
+    InvalidProtocolFee,
+    #[msg("Protocol fee accounts are required")]
+    ProtocolFeeAccountsRequired,
+    #[msg("Treasury token account does not match the config")]
+    InvalidTreasuryAccount,
+    #[msg("Zero claim amount")]
+    ZeroClaimAmount,
+    #[msg("Insurance fund cannot cover the claim")]
+    InsufficientInsuranceFund,
+    #[msg("No points to claim")]
+    NoPointsToClaim,
+    #[msg("Insurance vault does not match the fee mint")]
+    InvalidInsuranceVault,
+    #[msg("Fee oracle accounts are required to pay the fee in another mint")]
+    FeeOracleRequired,
+    #[msg("Fee oracle does not match the input and fee mints")]
+    InvalidFeeOracle,
+    #[msg("Too many swap validators")]
+    TooManySwapValidators,
+    #[msg("A registered swap validator program was not passed")]
+    SwapValidatorMissing,
+    #[msg("Token account is already on the destination allowlist")]
+    DestinationAlreadyListed,
+    #[msg("Destination allowlist is full")]
+    DestinationAllowlistFull,
+    #[msg("Token account is not on the destination allowlist")]
+    DestinationNotListed,
+    #[msg("Destination is not on the owner's allowlist or still in its delay")]
+    DestinationNotAllowed,
+    #[msg("Guardians must be distinct, at most MAX_GUARDIANS, with a reachable threshold")]
+    InvalidGuardianSet,
+    #[msg("Signer is not a guardian of this config")]
+    NotGuardian,
+    #[msg("Recovery owner is not the approved one")]
+    InvalidRecoveryOwner,
+    #[msg("No recovery is pending")]
+    NoRecoveryPending,
+    #[msg("Recovery lacks approvals or is still in its delay")]
+    RecoveryNotReady,
+    #[msg("Session expiry must be in the future and within MAX_SESSION_DURATION")]
+    InvalidSessionExpiry,
+    #[msg("Unknown or empty session scope")]
+    InvalidSessionScope,
+    #[msg("Session has expired")]
+    SessionExpired,
+    #[msg("Instruction is outside the session's scope")]
+    OutOfSessionScope,
+    #[msg("Too many session pools")]
+    TooManySessionPools,
+    #[msg("Unknown session swap direction")]
+    InvalidSessionDirection,
+    #[msg("Session does not allow this pool")]
+    SessionPoolNotAllowed,
+    #[msg("Session does not allow this swap direction")]
+    SessionDirectionNotAllowed,
+    #[msg("Swap exceeds the session's per-trade limit")]
+    SessionTradeTooLarge,
+    #[msg("Swap exceeds the session's cumulative cap")]
+    SessionCapExceeded,
+    #[msg("Dutch order needs an input, a falling nonzero output limit and a future window")]
+    InvalidDutchOrder,
+    #[msg("Order mints do not match the pool")]
+    InvalidOrderMint,
+    #[msg("Dutch order is outside its auction window")]
+    DutchOrderNotActive,
+    #[msg("Revealed swap does not match the commitment")]
+    CommitmentMismatch,
+    #[msg("Reveal is outside the commitment's slot window")]
+    RevealOutsideWindow,
+    #[msg("Token balances do not match the instruction's expected flows")]
+    FlowInvariantViolated,
+    #[msg("Global flag unknown or not changeable by this instruction")]
+    InvalidGlobalFlags,
+    #[msg("Slippage tolerance has not been set")]
+    SlippageNotSet,
+    #[msg("Input and output vaults are swapped for the input token's direction")]
+    WrongVaultOrder,
+    #[msg("Opening for another owner needs their signature or approval")]
+    OpenNotApproved,
+    #[msg("Too many user configs for one migration")]
+    TooManyConfigs,
+    #[msg("Only the pool's creator can manage its rewards")]
+    NotPoolCreator,
+    #[msg("Account still holds balances owed to users, or nothing to sweep")]
+    NothingToSweep,
+    #[msg("Deposit would take exposure past the user's cap")]
+    ExposureCapExceeded,
+    #[msg("Pool does not trade the user's exposure quote mint")]
+    ExposureUnpriced,
+    #[msg("Pool exposure account required while a per-pool cap is set")]
+    PoolExposureRequired,
+    #[msg("Exposure caps need a quote mint, which can't change while exposure is outstanding")]
+    InvalidExposureCaps,
+    #[msg("Activation slot leaves less than the required notice")]
+    ActivationTooSoon,
+    #[msg("Scheduled change has not reached its activation slot")]
+    ParamChangeNotActive,
+    #[msg("An initialized tick array within the band was not supplied")]
+    TickArrayMissing,
+    #[msg("The program is paused by governance")]
+    ProgramPaused,
+    #[msg("This feature is paused by governance")]
+    FeaturePaused,
+    #[msg("Creator fee vault is not a token account of the pool's creator record in the fee mint")]
+    InvalidCreatorFeeVault,
+    #[msg("Creator fee vault holds nothing to claim")]
+    NoCreatorFees,
+    #[msg("Too many round-trip swaps on this pool within the window")]
+    RoundTripLimitExceeded,
+    #[msg("Round-trip counter must be initialized while detection is on")]
+    RoundTripCounterRequired,
+    #[msg("Floor order needs an input, a nonzero floor price and a nonzero daily maximum")]
+    InvalidFloorOrder,
+    #[msg("Fill exceeds the floor order's escrow or what it can sell today")]
+    FloorOrderLimitExceeded,
+    #[msg("Input account has not approved the session authority for this swap's input")]
+    SessionDelegateNotApproved,
+    #[msg("User config predates the current layout; call migrate_user_config")]
+    UserConfigMigrationRequired,
+    #[msg("Too few compute units left for the Raydium CPI")]
+    InsufficientComputeBudget,
+    #[msg("Fee oracle needs a nonzero TWAP window and staleness limit")]
+    InvalidFeeOracleSettings,
+    #[msg("Fee oracle pool has no recent observations spanning the TWAP window")]
+    FeeOracleStale,
+    #[msg("Fee oracle pool's price strays too far from its TWAP")]
+    FeeOraclePriceDeviation,
+    #[msg("New positions are recorded on the registry's last page")]
+    RegistryPageNotLast,
+    #[msg("Session spend limits name at most MAX_SESSION_SPEND_MINTS distinct mints")]
+    InvalidSessionSpendLimits,
+    #[msg("Session's spend limits do not cover this swap's input mint")]
+    SessionMintNotAllowed,
+    #[msg("LP lock needs a nonzero amount and an unlock time in the future")]
+    InvalidLpLock,
+    #[msg("LP tokens are still locked")]
+    LpLocked,
+}
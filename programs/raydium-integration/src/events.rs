@@ -0,0 +1,895 @@
+//! Events emitted by the instructions, through `emit!` or, with the
+//! `event-cpi` feature, a self-CPI.
+//!
+//! With `cli` every event also derives `serde::Serialize`, addresses as
+//! base58 strings, for indexers writing them out as JSON.
+//!
+//! Events emitted where the user's `UserConfig` is written carry `user_seq`,
+//! the next number of the config's `event_seq`: consecutive per user, from 1,
+//! so an indexer can tell a missed or reordered event. Zero means the
+//! instruction ran without the config and the event is not sequenced.
+//!
+//! Instructions emitting several events stamp each with the same `op_id`,
+//! see `operation_id`, so they can be grouped back into one operation.
+use anchor_lang::prelude::*;
+
+use crate::SessionSpendLimit;
+
+#[cfg(feature = "cli")]
+pub(crate) fn pubkey_string<S: serde::Serializer>(
+    key: &Pubkey,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(key)
+}
+
+#[cfg(feature = "cli")]
+fn pubkey_strings<S: serde::Serializer>(
+    keys: &[Pubkey],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(keys.iter().map(Pubkey::to_string))
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SlippageSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub slippage_bps: u16,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SwapExecuted {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub expected_amount: u64,
+    pub slippage_bps: u16,
+    pub is_base_input: bool,
+    pub timestamp: i64,
+    pub sqrt_price_before_x64: u128,
+    pub sqrt_price_after_x64: u128,
+    pub tick_after: i32,
+    /// Raydium's trade fee at the pool's fee rate, in the input token
+    pub amm_fee: u64,
+    /// The proxy's protocol fee, in the input token; zero when none was charged
+    pub protocol_fee: u64,
+    /// Output short of the expected amount, or input above it, in basis
+    /// points; negative when the swap beat it
+    pub realized_slippage_bps: i64,
+    /// `slippage_bps` is the default, the user never having set one
+    pub default_slippage: bool,
+    pub user_seq: u64,
+    pub op_id: u64,
+    pub ui_amount_in: u64,
+    pub ui_amount_out: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PositionOpened {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+    /// Receives the position NFT; differs from `user`, who paid, when the
+    /// position was opened on the owner's behalf
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub user_seq: u64,
+    pub ui_amount_0: u64,
+    pub ui_amount_1: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct LiquidityIncreased {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub liquidity_added: u128,
+    pub amount_0_added: u64,
+    pub amount_1_added: u64,
+    pub ui_amount_0_added: u64,
+    pub ui_amount_1_added: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct LiquidityDecreased {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub liquidity_removed: u128,
+    pub amount_0_removed: u64,
+    pub amount_1_removed: u64,
+    pub ui_amount_0_removed: u64,
+    pub ui_amount_1_removed: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PositionClosed {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub timestamp: i64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct FeesCollected {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+    pub ui_amount_0: u64,
+    pub ui_amount_1: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct RewardsCollected {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub reward_index: u8,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PositionPnlRealized {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub deposited_0: u64,
+    pub deposited_1: u64,
+    pub withdrawn_0: u64,
+    pub withdrawn_1: u64,
+    pub fees_collected_0: u64,
+    pub fees_collected_1: u64,
+    pub cost_token_1: u128,
+    pub proceeds_token_1: u128,
+    pub realized_pnl_token_1: i128,
+    pub timestamp: i64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct FeeCheckpointStamped {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub liquidity: u128,
+    pub cumulative_fees_0: u64,
+    pub cumulative_fees_1: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SwapPartiallyFilled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub is_base_input: bool,
+    pub amount_requested: u64,
+    pub amount_filled: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PositionSplit {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub source_position_nft: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub new_position_nft: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity_moved: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PositionsMerged {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub source_position_nft: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub target_position_nft: Pubkey,
+    pub liquidity_moved: u128,
+    pub timestamp: i64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PositionStreamCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub beneficiary: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub liquidity: u128,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct StreamClaimed {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub beneficiary: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub claimed_liquidity: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SharedPositionCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub creator: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub shares: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SharedPositionChanged {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub contributor: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub shares_delta: i128,
+    pub shares: u128,
+    pub total_shares: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct DustSwept {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub sweeper: Pubkey,
+    /// The transient token account swept
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub source: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub beneficiary: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SharedFeesClaimed {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub contributor: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct MintExtensionOptInSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub allowed_extensions: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct OpenApprovalSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub delegate: Pubkey,
+    /// False when the approval was revoked
+    pub approved: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct AllowedCallersSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_strings"))]
+    pub programs: Vec<Pubkey>,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct ExposureCapsSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub quote_mint: Pubkey,
+    /// Zero for no cap
+    pub max_pool_exposure: u64,
+    /// Zero for no cap
+    pub max_total_exposure: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct GlobalConfigUpdated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub admin: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    pub timestamp: i64,
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct ParamChangeScheduled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub admin: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+    pub activation_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct ParamChangeCancelled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub admin: Pubkey,
+    pub activation_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct ConfigsMigrated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub admin: Pubkey,
+    /// Configs passed
+    pub configs: u32,
+    /// Configs whose tolerance was lowered to `max_slippage_bps`
+    pub normalized: u32,
+    pub timestamp: i64,
+    pub max_slippage_bps: u16,
+    /// Configs grown from a legacy layout
+    pub resized: u32,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct ProtocolFeeCollected {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    /// Mint the fee was paid in
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub mint: Pubkey,
+    pub fee: u64,
+    /// Part of `fee` routed to the insurance fund
+    pub insurance_amount: u64,
+    /// Part of `fee` routed to the pool creator's fee vault
+    pub creator_amount: u64,
+    /// The fee valued in input mint units
+    pub fee_in_input: u64,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct RoundTripsFlagged {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    /// Round trips in the current window, this swap included
+    pub round_trips: u16,
+    pub max_round_trips: u16,
+    pub timestamp: i64,
+    pub user_seq: u64,
+    pub op_id: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct CreatorFeesClaimed {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub creator: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct InsuranceClaimPaid {
+    /// Owner of the token account credited
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub recipient: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PointsRatesSet {
+    pub swap_points_per_unit: u64,
+    pub lp_points_per_unit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PointsClaimed {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub amount: u128,
+    pub total_claimed: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct FeeOracleSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub input_mint: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub fee_mint: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub twap_window_secs: u32,
+    pub max_staleness_secs: u32,
+    pub max_deviation_ticks: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SwapValidatorsSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_strings"))]
+    pub programs: Vec<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct DestinationAdded {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub token_account: Pubkey,
+    pub active_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct DestinationRemoved {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub token_account: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct DestinationAllowlistToggled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub enabled: bool,
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct GuardiansSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_strings"))]
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct RecoveryApproved {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub new_owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub guardian: Pubkey,
+    pub approvals: u8,
+    /// Zero until the threshold is met
+    pub ready_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct RecoveryCancelled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct OwnerRecovered {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub old_owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub new_owner: Pubkey,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SessionCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub session_key: Pubkey,
+    pub expiry: i64,
+    pub scope: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SessionRevoked {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub session_key: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SessionLimitsSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub session_key: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_strings"))]
+    pub allowed_pools: Vec<Pubkey>,
+    pub direction: u8,
+    pub spend_limits: Vec<SessionSpendLimit>,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct DutchOrderCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub order_id: u64,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub input_mint: Pubkey,
+    /// Escrowed, net of any transfer fee
+    pub amount_in: u64,
+    pub start_min_out: u64,
+    pub end_min_out: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct DutchOrderFilled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub order_id: u64,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub keeper: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Auction price at the fill, as the least output accepted
+    pub min_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct DutchOrderCancelled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct FloorOrderCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub order_id: u64,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub input_mint: Pubkey,
+    /// Escrowed, net of any transfer fee
+    pub amount_in: u64,
+    pub floor_price_x64: u128,
+    pub daily_max: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct FloorOrderFilled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub order_id: u64,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub keeper: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Floor price applied to `amount_in`, as the least output accepted
+    pub min_out: u64,
+    /// Input sold in the current day, this fill included
+    pub sold_today: u64,
+    /// Whether the fill emptied the escrow and closed the order
+    pub sold_out: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct FloorOrderCancelled {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct SwapCommitted {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    pub hash: [u8; 32],
+    pub slot: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct PoolCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub creator: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub amm_config: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub token_mint_0: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub token_mint_1: Pubkey,
+    pub sqrt_price_x64: u128,
+    pub open_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct RewardEmissionSet {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub creator: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    /// Slot of the reward in the pool's reward infos
+    pub reward_index: u8,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub reward_mint: Pubkey,
+    pub emissions_per_second_x64: u128,
+    pub open_time: u64,
+    pub end_time: u64,
+    /// True for a new reward, false for changed parameters
+    pub initialized: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct CpmmMigrated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub user: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub cpmm_pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub position_nft: Pubkey,
+    pub lp_token_amount: u64,
+    pub amount_0_withdrawn: u64,
+    pub amount_1_withdrawn: u64,
+    pub amount_0_deposited: u64,
+    pub amount_1_deposited: u64,
+    pub liquidity: u128,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub timestamp: i64,
+    pub user_seq: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct OpenbookMarketCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub creator: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub market: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub coin_mint: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pc_mint: Pubkey,
+    pub coin_lot_size: u64,
+    pub pc_lot_size: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct AmmPoolCreated {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub creator: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub amm_pool: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub market: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub coin_mint: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub pc_mint: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub lp_mint: Pubkey,
+    pub init_coin_amount: u64,
+    pub init_pc_amount: u64,
+    /// LP tokens minted to the creator
+    pub lp_amount: u64,
+    pub open_time: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct AmmLpLocked {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+#[cfg_attr(feature = "cli", derive(Debug, serde::Serialize))]
+pub struct AmmLpUnlocked {
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub owner: Pubkey,
+    #[cfg_attr(feature = "cli", serde(serialize_with = "pubkey_string"))]
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,17 @@
+//! Global allocator of `custom-heap` builds.
+//!
+//! The runtime's default bump allocator assumes a 32 KiB heap, and a bump
+//! allocator never frees. This one is the same allocator over a heap of
+//! `HEAP_FRAME_BYTES`, which every transaction into the program must then
+//! request with `ComputeBudgetInstruction::request_heap_frame`.
+#[cfg(all(
+    feature = "custom-heap",
+    target_os = "solana",
+    not(feature = "no-entrypoint")
+))]
+#[global_allocator]
+static ALLOCATOR: anchor_lang::solana_program::entrypoint::BumpAllocator =
+    anchor_lang::solana_program::entrypoint::BumpAllocator {
+        start: anchor_lang::solana_program::entrypoint::HEAP_START_ADDRESS as usize,
+        len: crate::constants::HEAP_FRAME_BYTES as usize,
+    };
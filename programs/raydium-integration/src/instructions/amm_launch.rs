@@ -0,0 +1,498 @@
+//! Launching Raydium AMM v4 pools on a new OpenBook market, and locking LP.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyCreateOpenbookMarket<'info> {
+    /// CHECK: OpenBook program, checked by address
+    #[account(address = OPENBOOK_PROGRAM_ID)]
+    pub openbook_program: UncheckedAccount<'info>,
+
+    pub creator: Signer<'info>,
+
+    /// CHECK: Allocated by the client and owned by OpenBook, which
+    /// initializes it
+    #[account(mut, owner = OPENBOOK_PROGRAM_ID)]
+    pub market: UncheckedAccount<'info>,
+
+    /// CHECK: Allocated by the client and owned by OpenBook
+    #[account(mut, owner = OPENBOOK_PROGRAM_ID)]
+    pub request_queue: UncheckedAccount<'info>,
+
+    /// CHECK: Allocated by the client and owned by OpenBook
+    #[account(mut, owner = OPENBOOK_PROGRAM_ID)]
+    pub event_queue: UncheckedAccount<'info>,
+
+    /// CHECK: Allocated by the client and owned by OpenBook
+    #[account(mut, owner = OPENBOOK_PROGRAM_ID)]
+    pub bids: UncheckedAccount<'info>,
+
+    /// CHECK: Allocated by the client and owned by OpenBook
+    #[account(mut, owner = OPENBOOK_PROGRAM_ID)]
+    pub asks: UncheckedAccount<'info>,
+
+    /// Owned by the market's vault signer; OpenBook checks the owner
+    #[account(mut, token::mint = coin_mint)]
+    pub coin_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owned by the market's vault signer; OpenBook checks the owner
+    #[account(mut, token::mint = pc_mint)]
+    pub pc_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Base mint, the token being launched
+    pub coin_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Quote mint
+    pub pc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+impl<'info> ProxyCreateOpenbookMarket<'info> {
+    fn initialize_market(
+        &self,
+        coin_lot_size: u64,
+        pc_lot_size: u64,
+        vault_signer_nonce: u64,
+        pc_dust_threshold: u64,
+    ) -> Result<()> {
+        // Version byte, then the instruction tag and fields, little-endian
+        let mut data = vec![0u8];
+        (
+            OPENBOOK_INITIALIZE_MARKET_TAG,
+            coin_lot_size,
+            pc_lot_size,
+            OPENBOOK_FEE_RATE_BPS,
+            vault_signer_nonce,
+            pc_dust_threshold,
+        )
+            .serialize(&mut data)
+            .expect("serializing into a Vec cannot fail");
+        let mut account_infos = vec![
+            self.market.to_account_info(),
+            self.request_queue.to_account_info(),
+            self.event_queue.to_account_info(),
+            self.bids.to_account_info(),
+            self.asks.to_account_info(),
+            self.coin_vault.to_account_info(),
+            self.pc_vault.to_account_info(),
+            self.coin_mint.to_account_info(),
+            self.pc_mint.to_account_info(),
+            self.rent.to_account_info(),
+        ];
+        let instruction = Instruction {
+            program_id: OPENBOOK_PROGRAM_ID,
+            accounts: account_infos
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        account_infos.push(self.openbook_program.to_account_info());
+        invoke(&instruction, &account_infos)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyCreateAmmPool<'info> {
+    /// CHECK: Raydium AMM v4 program, checked by address
+    #[account(address = RAYDIUM_AMM_V4_PROGRAM_ID)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    /// Pays the AMM's accounts and create fee, and deposits the initial
+    /// liquidity
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Initialized by Raydium; the AMM checks the PDA against the
+    /// market
+    #[account(mut)]
+    pub amm_pool: UncheckedAccount<'info>,
+
+    /// CHECK: AMM vault and LP mint authority; the AMM checks the PDA
+    pub amm_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Initialized by Raydium; the AMM checks the PDA against the
+    /// market
+    #[account(mut)]
+    pub amm_open_orders: UncheckedAccount<'info>,
+
+    /// CHECK: Initialized by Raydium; the AMM checks the PDA against the
+    /// market
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+
+    pub coin_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub pc_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Initialized by Raydium; the AMM checks the PDA against the
+    /// market
+    #[account(mut)]
+    pub amm_coin_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Initialized by Raydium; the AMM checks the PDA against the
+    /// market
+    #[account(mut)]
+    pub amm_pc_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Initialized by Raydium; the AMM checks the PDA against the
+    /// market
+    #[account(mut)]
+    pub amm_target_orders: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium's AMM config PDA; the AMM checks it
+    pub amm_config: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium's create-fee wallet; the AMM checks it against its
+    /// config
+    #[account(mut)]
+    pub create_fee_destination: UncheckedAccount<'info>,
+
+    /// CHECK: OpenBook program, checked by address
+    #[account(address = OPENBOOK_PROGRAM_ID)]
+    pub market_program: UncheckedAccount<'info>,
+
+    /// CHECK: OpenBook market of the pool's mints; the AMM checks its mints
+    /// and vaults
+    #[account(owner = OPENBOOK_PROGRAM_ID)]
+    pub market: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = coin_mint,
+        token::authority = creator
+    )]
+    pub creator_coin_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = pc_mint,
+        token::authority = creator
+    )]
+    pub creator_pc_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The creator's LP associated token account, created by Raydium
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &creator.key(),
+            &lp_mint.key(),
+            &token_program.key()
+        )
+    )]
+    pub creator_lp_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+impl<'info> ProxyCreateAmmPool<'info> {
+    fn initialize_amm(
+        &self,
+        nonce: u8,
+        open_time: u64,
+        init_pc_amount: u64,
+        init_coin_amount: u64,
+    ) -> Result<()> {
+        let mut data = Vec::new();
+        (
+            AMM_V4_INITIALIZE2_TAG,
+            nonce,
+            open_time,
+            init_pc_amount,
+            init_coin_amount,
+        )
+            .serialize(&mut data)
+            .expect("serializing into a Vec cannot fail");
+        let mut account_infos = vec![
+            self.token_program.to_account_info(),
+            self.associated_token_program.to_account_info(),
+            self.system_program.to_account_info(),
+            self.rent.to_account_info(),
+            self.amm_pool.to_account_info(),
+            self.amm_authority.to_account_info(),
+            self.amm_open_orders.to_account_info(),
+            self.lp_mint.to_account_info(),
+            self.coin_mint.to_account_info(),
+            self.pc_mint.to_account_info(),
+            self.amm_coin_vault.to_account_info(),
+            self.amm_pc_vault.to_account_info(),
+            self.amm_target_orders.to_account_info(),
+            self.amm_config.to_account_info(),
+            self.create_fee_destination.to_account_info(),
+            self.market_program.to_account_info(),
+            self.market.to_account_info(),
+            self.creator.to_account_info(),
+            self.creator_coin_account.to_account_info(),
+            self.creator_pc_account.to_account_info(),
+            self.creator_lp_account.to_account_info(),
+        ];
+        let instruction = Instruction {
+            program_id: RAYDIUM_AMM_V4_PROGRAM_ID,
+            accounts: account_infos
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        account_infos.push(self.amm_program.to_account_info());
+        invoke(&instruction, &account_infos)?;
+        Ok(())
+    }
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct LockAmmLp<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = owner
+    )]
+    pub owner_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + AmmLpLock::SIZE,
+        seeds = [b"lp_lock", owner.key().as_ref(), lp_mint.key().as_ref()],
+        bump
+    )]
+    pub lp_lock: Box<Account<'info, AmmLpLock>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"lp_lock_escrow", lp_lock.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = lp_lock,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UnlockAmmLp<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(address = lp_lock.lp_mint)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock", owner.key().as_ref(), lp_mint.key().as_ref()],
+        bump,
+        close = owner
+    )]
+    pub lp_lock: Box<Account<'info, AmmLpLock>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock_escrow", lp_lock.key().as_ref()],
+        bump
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = lp_mint)]
+    pub owner_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn proxy_create_openbook_market(
+    ctx: Context<ProxyCreateOpenbookMarket>,
+    coin_lot_size: u64,
+    pc_lot_size: u64,
+    vault_signer_nonce: u64,
+    pc_dust_threshold: u64,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+
+    ctx.accounts.initialize_market(
+        coin_lot_size,
+        pc_lot_size,
+        vault_signer_nonce,
+        pc_dust_threshold,
+    )?;
+
+    emit_event!(
+        ctx,
+        OpenbookMarketCreated {
+            creator: ctx.accounts.creator.key(),
+            market: ctx.accounts.market.key(),
+            coin_mint: ctx.accounts.coin_mint.key(),
+            pc_mint: ctx.accounts.pc_mint.key(),
+            coin_lot_size,
+            pc_lot_size,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn proxy_create_amm_pool(
+    ctx: Context<ProxyCreateAmmPool>,
+    nonce: u8,
+    open_time: u64,
+    init_coin_amount: u64,
+    init_pc_amount: u64,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+
+    ctx.accounts
+        .initialize_amm(nonce, open_time, init_pc_amount, init_coin_amount)?;
+
+    // Raydium creates the creator's LP account in the CPI, so it is read
+    // only now
+    let lp_amount = {
+        let data = ctx.accounts.creator_lp_account.try_borrow_data()?;
+        TokenAccount::try_deserialize(&mut &data[..])?.amount
+    };
+
+    emit_event!(
+        ctx,
+        AmmPoolCreated {
+            creator: ctx.accounts.creator.key(),
+            amm_pool: ctx.accounts.amm_pool.key(),
+            market: ctx.accounts.market.key(),
+            coin_mint: ctx.accounts.coin_mint.key(),
+            pc_mint: ctx.accounts.pc_mint.key(),
+            lp_mint: ctx.accounts.lp_mint.key(),
+            init_coin_amount,
+            init_pc_amount,
+            lp_amount,
+            open_time,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn lock_amm_lp(ctx: Context<LockAmmLp>, amount: u64, unlock_ts: i64) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(amount > 0 && unlock_ts > now, CustomError::InvalidLpLock);
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.owner_lp_account.to_account_info(),
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        to: ctx.accounts.escrow.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.lp_mint.decimals)?;
+
+    let lp_lock = &mut ctx.accounts.lp_lock;
+    lp_lock.owner = ctx.accounts.owner.key();
+    lp_lock.lp_mint = ctx.accounts.lp_mint.key();
+    lp_lock.amount = amount;
+    lp_lock.unlock_ts = unlock_ts;
+
+    emit_event!(
+        ctx,
+        AmmLpLocked {
+            owner: ctx.accounts.owner.key(),
+            lp_mint: ctx.accounts.lp_mint.key(),
+            amount,
+            unlock_ts,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn unlock_amm_lp(ctx: Context<UnlockAmmLp>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ctx.accounts.lp_lock.unlock_ts, CustomError::LpLocked);
+
+    let owner_key = ctx.accounts.owner.key();
+    let lp_mint_key = ctx.accounts.lp_mint.key();
+    let signer_seeds: &[&[u8]] = &[
+        b"lp_lock",
+        owner_key.as_ref(),
+        lp_mint_key.as_ref(),
+        &[ctx.bumps.lp_lock],
+    ];
+    let signer = &[signer_seeds];
+    let amount = ctx.accounts.escrow.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.escrow.to_account_info(),
+        mint: ctx.accounts.lp_mint.to_account_info(),
+        to: ctx.accounts.owner_lp_account.to_account_info(),
+        authority: ctx.accounts.lp_lock.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.lp_mint.decimals)?;
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.escrow.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: ctx.accounts.lp_lock.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::close_account(cpi_context)?;
+
+    emit_event!(
+        ctx,
+        AmmLpUnlocked {
+            owner: owner_key,
+            lp_mint: lp_mint_key,
+            amount,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
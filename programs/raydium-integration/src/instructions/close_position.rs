@@ -0,0 +1,153 @@
+//! Proxied position closes.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyClosePosition<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// CHECK: Burned by Raydium, which validates it against the personal position
+    #[account(mut, address = personal_position.nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium checks it holds the NFT and is owned by `nft_owner`
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_SEED.as_bytes(), position_nft_mint.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    #[account(mut, seeds = [b"position_registry", nft_owner.key().as_ref()], bump)]
+    pub position_registry: Option<Account<'info, UserPositionRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position_registry",
+            nft_owner.key().as_ref(),
+            &position_registry_page.page.to_le_bytes(),
+        ],
+        bump,
+        constraint = position_registry_page.owner == nft_owner.key()
+    )]
+    pub position_registry_page: Option<Account<'info, PositionRegistryPage>>,
+
+    #[account(
+        mut,
+        close = nft_owner,
+        seeds = [b"cost_basis", position_nft_mint.key().as_ref()],
+        bump
+    )]
+    pub cost_basis: Option<Account<'info, PositionCostBasis>>,
+
+    /// Not checked for the pause flags: exits stay open while the program is
+    /// paused
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The owner's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", nft_owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+pub fn proxy_close_position(ctx: Context<ProxyClosePosition>) -> Result<()> {
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+
+    let (pool, tick_lower, tick_upper) = {
+        let position = &ctx.accounts.personal_position;
+        (
+            position.pool_id,
+            position.tick_lower_index,
+            position.tick_upper_index,
+        )
+    };
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::ClosePosition {
+        nft_owner: ctx.accounts.nft_owner.to_account_info(),
+        position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+        position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+    };
+
+    // Build CPI context
+    let cpi_context = CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts);
+    cpi::close_position(cpi_context)?;
+
+    let position_nft = ctx.accounts.position_nft_mint.key();
+    match (
+        ctx.accounts.position_registry.as_mut(),
+        ctx.accounts.position_registry_page.as_mut(),
+    ) {
+        (Some(registry), Some(page)) => {
+            require!(
+                page.remove(&position_nft),
+                CustomError::PositionNotInRegistry
+            );
+            registry.position_count = registry.position_count.saturating_sub(1);
+        }
+        (None, None) => {}
+        _ => return err!(CustomError::RegistryAccountsMismatch),
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let op_id = operation_id(&ctx.accounts.nft_owner.key())?;
+    if let Some(cost_basis) = ctx.accounts.cost_basis.as_ref() {
+        emit_event!(
+            ctx,
+            PositionPnlRealized {
+                user: ctx.accounts.nft_owner.key(),
+                pool,
+                position_nft,
+                deposited_0: cost_basis.deposited_0,
+                deposited_1: cost_basis.deposited_1,
+                withdrawn_0: cost_basis.withdrawn_0,
+                withdrawn_1: cost_basis.withdrawn_1,
+                fees_collected_0: cost_basis.fees_collected_0,
+                fees_collected_1: cost_basis.fees_collected_1,
+                cost_token_1: cost_basis.cost_token_1,
+                proceeds_token_1: cost_basis.proceeds_token_1,
+                realized_pnl_token_1: cost_basis.realized_pnl(),
+                timestamp: now,
+                op_id,
+            }
+        );
+    }
+
+    emit_event!(
+        ctx,
+        PositionClosed {
+            user: ctx.accounts.nft_owner.key(),
+            pool,
+            position_nft,
+            tick_lower,
+            tick_upper,
+            timestamp: now,
+            op_id,
+        }
+    );
+
+    Ok(())
+}
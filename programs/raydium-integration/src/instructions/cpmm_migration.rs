@@ -0,0 +1,369 @@
+//! Moving CPMM liquidity into a CLMM position.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct MigrateCpmmToClmm<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    /// CHECK: Raydium CPMM program, checked by address
+    #[account(address = RAYDIUM_CPMM_PROGRAM_ID)]
+    pub cpmm_program: UncheckedAccount<'info>,
+
+    /// Owns the LP tokens and pays for the position
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserConfig::SIZE,
+        seeds = [b"user_cfg", payer.key().as_ref()],
+        bump
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+
+    /// CHECK: CPMM vault and LP mint authority; CPMM checks the PDA
+    pub cpmm_authority: UncheckedAccount<'info>,
+
+    /// CHECK: CPMM pool the LP tokens belong to; CPMM checks it against the
+    /// vaults and LP mint
+    #[account(mut, owner = RAYDIUM_CPMM_PROGRAM_ID)]
+    pub cpmm_pool_state: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = payer
+    )]
+    pub owner_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: CPMM pool vault of token 0; CPMM checks it against the pool
+    #[account(mut)]
+    pub cpmm_token_0_vault: UncheckedAccount<'info>,
+
+    /// CHECK: CPMM pool vault of token 1; CPMM checks it against the pool
+    #[account(mut)]
+    pub cpmm_token_1_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: SPL Memo program, checked by address
+    #[account(address = SPL_MEMO_PROGRAM_ID)]
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the position NFT
+    pub position_nft_owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub position_nft_mint: Signer<'info>,
+
+    /// CHECK: Token account where position NFT will be minted
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Raydium checks the PDA against the position's tick bounds
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    /// CHECK: Account to mark the lower tick as initialized; Raydium checks
+    /// the PDA against the lower tick array start index
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Account to store data for the position's upper tick; Raydium
+    /// checks the PDA against the upper tick array start index
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: personal position state
+    #[account(
+        mut,
+        seeds = [POSITION_SEED.as_bytes(), position_nft_mint.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub personal_position: UncheckedAccount<'info>,
+
+    /// Receives the CPMM withdrawal and funds the position
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Raydium's tick array bitmap extension for the pool, needed when
+    /// the position's tick arrays lie outside the pool's default bitmap
+    #[account(
+        mut,
+        seeds = [POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub tick_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(seeds = [b"extension_opt_in", payer.key().as_ref()], bump)]
+    pub mint_extension_opt_in: Option<Account<'info, MintExtensionOptIn>>,
+
+    /// CHECK: Instructions sysvar, needed when the payer restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+impl<'info> MigrateCpmmToClmm<'info> {
+    /*
+     * CPI CPMM's `withdraw`, paying both tokens into the accounts that fund
+     * the position. CPMM pools order their mints like CLMM pools, so token 0
+     * and token 1 line up
+     */
+    fn withdraw_cpmm_liquidity(
+        &self,
+        lp_token_amount: u64,
+        minimum_token_0_amount: u64,
+        minimum_token_1_amount: u64,
+    ) -> Result<()> {
+        let mut data = CPMM_WITHDRAW_DISCRIMINATOR.to_vec();
+        (
+            lp_token_amount,
+            minimum_token_0_amount,
+            minimum_token_1_amount,
+        )
+            .serialize(&mut data)
+            .expect("serializing into a Vec cannot fail");
+        let mut account_infos = vec![
+            self.payer.to_account_info(),
+            self.cpmm_authority.to_account_info(),
+            self.cpmm_pool_state.to_account_info(),
+            self.owner_lp_token.to_account_info(),
+            self.token_account_0.to_account_info(),
+            self.token_account_1.to_account_info(),
+            self.cpmm_token_0_vault.to_account_info(),
+            self.cpmm_token_1_vault.to_account_info(),
+            self.token_program.to_account_info(),
+            self.token_program_2022.to_account_info(),
+            self.vault_0_mint.to_account_info(),
+            self.vault_1_mint.to_account_info(),
+            self.lp_mint.to_account_info(),
+            self.memo_program.to_account_info(),
+        ];
+        let instruction = Instruction {
+            program_id: RAYDIUM_CPMM_PROGRAM_ID,
+            accounts: account_infos
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        account_infos.push(self.cpmm_program.to_account_info());
+        invoke(&instruction, &account_infos)?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_cpmm_to_clmm<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MigrateCpmmToClmm<'info>>,
+    lp_token_amount: u64,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_array_lower_start_index: i32,
+    tick_array_upper_start_index: i32,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+
+    require!(
+        tick_lower_index < tick_upper_index,
+        CustomError::InvalidTickRange
+    );
+    require!(lp_token_amount > 0, CustomError::ZeroDeposit);
+    load_or_init(&ctx.accounts.user_cfg)?
+        .check_caller(ctx.accounts.instructions_sysvar.as_ref())?;
+
+    token_extensions::require_allowed_extensions(
+        &[
+            ctx.accounts.vault_0_mint.to_account_info(),
+            ctx.accounts.vault_1_mint.to_account_info(),
+        ],
+        ctx.accounts.mint_extension_opt_in.as_deref(),
+    )?;
+
+    let balance_0_before = ctx.accounts.token_account_0.amount;
+    let balance_1_before = ctx.accounts.token_account_1.amount;
+
+    ctx.accounts.withdraw_cpmm_liquidity(
+        lp_token_amount,
+        minimum_token_0_amount,
+        minimum_token_1_amount,
+    )?;
+
+    ctx.accounts.token_account_0.reload()?;
+    ctx.accounts.token_account_1.reload()?;
+    let balance_0_withdrawn = ctx.accounts.token_account_0.amount;
+    let balance_1_withdrawn = ctx.accounts.token_account_1.amount;
+    let amount_0_withdrawn = balance_0_withdrawn.saturating_sub(balance_0_before);
+    let amount_1_withdrawn = balance_1_withdrawn.saturating_sub(balance_1_before);
+
+    // Deposit by amounts, as `proxy_open_position` does without a
+    // liquidity target
+    let sqrt_price_x64 = ctx.accounts.pool_state.load()?.sqrt_price_x64;
+    require!(
+        liquidity_for_amounts(
+            sqrt_price_x64,
+            tick_lower_index,
+            tick_upper_index,
+            amount_0_withdrawn,
+            amount_1_withdrawn,
+        )? > 0,
+        CustomError::ZeroLiquidity
+    );
+    let base_flag = math::base_flag_for_amounts(
+        sqrt_price_x64,
+        tick_lower_index,
+        tick_upper_index,
+        amount_0_withdrawn,
+        amount_1_withdrawn,
+    )?;
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::OpenPositionV2 {
+        payer: ctx.accounts.payer.to_account_info(),
+        position_nft_owner: ctx.accounts.position_nft_owner.to_account_info(),
+        position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+        position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+        // Unused without metadata
+        metadata_account: ctx.accounts.position_nft_mint.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        token_account_0: ctx.accounts.token_account_0.to_account_info(),
+        token_account_1: ctx.accounts.token_account_1.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        metadata_program: ctx.accounts.metadata_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+    };
+
+    // Execute CPI
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        ctx.accounts.tick_array_bitmap_extension.as_ref(),
+        ctx.remaining_accounts,
+        &clmm_instruction::OpenPositionV2 {
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+            liquidity: 0,
+            amount_0_max: amount_0_withdrawn,
+            amount_1_max: amount_1_withdrawn,
+            with_metadata: false,
+            base_flag: Some(base_flag),
+        },
+        &[],
+    )?;
+
+    ctx.accounts.token_account_0.reload()?;
+    ctx.accounts.token_account_1.reload()?;
+    let amount_0_deposited =
+        balance_0_withdrawn.saturating_sub(ctx.accounts.token_account_0.amount);
+    let amount_1_deposited =
+        balance_1_withdrawn.saturating_sub(ctx.accounts.token_account_1.amount);
+    let liquidity = {
+        let data = ctx.accounts.personal_position.try_borrow_data()?;
+        PersonalPositionState::try_deserialize(&mut &data[..])?.liquidity
+    };
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut user_cfg = load_or_init(&ctx.accounts.user_cfg)?;
+    if user_cfg.owner == Pubkey::default() {
+        user_cfg.owner = ctx.accounts.payer.key();
+    }
+    user_cfg.positions_opened = user_cfg.positions_opened.saturating_add(1);
+    user_cfg.last_activity_ts = now;
+    let user_seq = user_cfg.next_event_seq();
+
+    emit_event!(
+        ctx,
+        CpmmMigrated {
+            user: ctx.accounts.payer.key(),
+            cpmm_pool: ctx.accounts.cpmm_pool_state.key(),
+            pool: ctx.accounts.pool_state.key(),
+            position_nft: ctx.accounts.position_nft_mint.key(),
+            lp_token_amount,
+            amount_0_withdrawn,
+            amount_1_withdrawn,
+            amount_0_deposited,
+            amount_1_deposited,
+            liquidity,
+            tick_lower: tick_lower_index,
+            tick_upper: tick_upper_index,
+            timestamp: now,
+            user_seq,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+//! Raydium pool creation and reward emissions.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyCreatePool<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    #[account(mut)]
+    pub pool_creator: Signer<'info>,
+
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// CHECK: Initialized by Raydium
+    #[account(mut)]
+    pub pool_state: UncheckedAccount<'info>,
+
+    pub token_mint_0: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_mint_1: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Initialized by Raydium
+    #[account(mut)]
+    pub token_vault_0: UncheckedAccount<'info>,
+
+    /// CHECK: Initialized by Raydium
+    #[account(mut)]
+    pub token_vault_1: UncheckedAccount<'info>,
+
+    /// CHECK: Initialized by Raydium
+    #[account(mut)]
+    pub observation_state: UncheckedAccount<'info>,
+
+    /// CHECK: Initialized by Raydium
+    #[account(mut)]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// Makes the creator eligible for a share of the pool's protocol fees
+    #[account(
+        init,
+        payer = pool_creator,
+        space = 8 + PoolCreatorRecord::SIZE,
+        seeds = [b"pool_creator", pool_state.key().as_ref()],
+        bump
+    )]
+    pub pool_creator_record: Box<Account<'info, PoolCreatorRecord>>,
+
+    pub token_program_0: Interface<'info, TokenInterface>,
+
+    pub token_program_1: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyInitializeReward<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    /// The pool's creator, funding the reward vault
+    #[account(mut)]
+    pub reward_funder: Signer<'info>,
+
+    #[account(mut, token::mint = reward_token_mint)]
+    pub funder_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    #[account(
+        mut,
+        constraint = pool_state.load()?.owner == reward_funder.key() @ CustomError::NotPoolCreator
+    )]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Raydium's operation state; Raydium checks the PDA
+    pub operation_state: UncheckedAccount<'info>,
+
+    pub reward_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Initialized by Raydium
+    #[account(mut)]
+    pub reward_token_vault: UncheckedAccount<'info>,
+
+    pub reward_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxySetRewardParams<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    /// The pool's creator
+    pub authority: Signer<'info>,
+
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    #[account(
+        mut,
+        constraint = pool_state.load()?.owner == authority.key() @ CustomError::NotPoolCreator
+    )]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Raydium's operation state; Raydium checks the PDA
+    pub operation_state: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+pub fn proxy_create_pool(
+    ctx: Context<ProxyCreatePool>,
+    sqrt_price_x64: u128,
+    open_time: u64,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::CreatePool {
+        pool_creator: ctx.accounts.pool_creator.to_account_info(),
+        amm_config: ctx.accounts.amm_config.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        token_mint_0: ctx.accounts.token_mint_0.to_account_info(),
+        token_mint_1: ctx.accounts.token_mint_1.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        observation_state: ctx.accounts.observation_state.to_account_info(),
+        tick_array_bitmap: ctx.accounts.tick_array_bitmap.to_account_info(),
+        token_program_0: ctx.accounts.token_program_0.to_account_info(),
+        token_program_1: ctx.accounts.token_program_1.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+
+    // Build CPI context
+    let cpi_context = CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts);
+
+    // Execute CPI
+    cpi::create_pool(cpi_context, sqrt_price_x64, open_time)?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let pool_creator_record = &mut ctx.accounts.pool_creator_record;
+    pool_creator_record.pool = ctx.accounts.pool_state.key();
+    pool_creator_record.creator = ctx.accounts.pool_creator.key();
+    pool_creator_record.created_at = timestamp;
+
+    emit_event!(
+        ctx,
+        PoolCreated {
+            creator: ctx.accounts.pool_creator.key(),
+            pool: ctx.accounts.pool_state.key(),
+            amm_config: ctx.accounts.amm_config.key(),
+            token_mint_0: ctx.accounts.token_mint_0.key(),
+            token_mint_1: ctx.accounts.token_mint_1.key(),
+            sqrt_price_x64,
+            open_time,
+            timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn proxy_initialize_reward(
+    ctx: Context<ProxyInitializeReward>,
+    open_time: u64,
+    end_time: u64,
+    emissions_per_second_x64: u128,
+) -> Result<()> {
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::InitializeReward {
+        reward_funder: ctx.accounts.reward_funder.to_account_info(),
+        funder_token_account: ctx.accounts.funder_token_account.to_account_info(),
+        amm_config: ctx.accounts.amm_config.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        operation_state: ctx.accounts.operation_state.to_account_info(),
+        reward_token_mint: ctx.accounts.reward_token_mint.to_account_info(),
+        reward_token_vault: ctx.accounts.reward_token_vault.to_account_info(),
+        reward_token_program: ctx.accounts.reward_token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+
+    // Build CPI context
+    let cpi_context = CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts);
+
+    // Execute CPI
+    cpi::initialize_reward(
+        cpi_context,
+        InitializeRewardParam {
+            open_time,
+            end_time,
+            emissions_per_second_x64,
+        },
+    )?;
+
+    // Raydium fills the first free reward slot; a pool holds a mint once
+    let reward_mint = ctx.accounts.reward_token_mint.key();
+    let reward_infos = ctx.accounts.pool_state.load()?.reward_infos;
+    let reward_index = reward_infos
+        .iter()
+        .position(|reward| reward.token_mint == reward_mint)
+        .ok_or(CustomError::InvalidVault)? as u8;
+
+    emit_event!(
+        ctx,
+        RewardEmissionSet {
+            creator: ctx.accounts.reward_funder.key(),
+            pool: ctx.accounts.pool_state.key(),
+            reward_index,
+            reward_mint,
+            emissions_per_second_x64,
+            open_time,
+            end_time,
+            initialized: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+/*
+ * Change a reward's emission rate or window. Extending or raising an
+ * emission tops its vault up from the creator: Raydium then expects the
+ * reward vault, the creator's token account and the reward mint as
+ * remaining accounts
+ */
+pub fn proxy_set_reward_params<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ProxySetRewardParams<'info>>,
+    reward_index: u8,
+    emissions_per_second_x64: u128,
+    open_time: u64,
+    end_time: u64,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::SetRewardParams {
+        authority: ctx.accounts.authority.to_account_info(),
+        amm_config: ctx.accounts.amm_config.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        operation_state: ctx.accounts.operation_state.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+    };
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        None,
+        ctx.remaining_accounts,
+        &clmm_instruction::SetRewardParams {
+            reward_index,
+            emissions_per_second_x64,
+            open_time,
+            end_time,
+        },
+        &[],
+    )?;
+
+    // Raydium rejected out-of-range indices above; report what it applied
+    let reward_infos = ctx.accounts.pool_state.load()?.reward_infos;
+    let reward = reward_infos[reward_index as usize];
+    emit_event!(
+        ctx,
+        RewardEmissionSet {
+            creator: ctx.accounts.authority.key(),
+            pool: ctx.accounts.pool_state.key(),
+            reward_index,
+            reward_mint: reward.token_mint,
+            emissions_per_second_x64: reward.emissions_per_second_x64,
+            open_time: reward.open_time,
+            end_time: reward.end_time,
+            initialized: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
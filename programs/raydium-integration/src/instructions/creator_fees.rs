@@ -0,0 +1,108 @@
+//! Pool creators' share of protocol fees.
+use crate::contexts::*;
+
+#[derive(Accounts)]
+pub struct InitCreatorFeeVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(seeds = [b"pool_creator", pool_creator_record.pool.as_ref()], bump)]
+    pub pool_creator_record: Account<'info, PoolCreatorRecord>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"creator_fees", pool_creator_record.pool.as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pool_creator_record,
+        token::token_program = token_program,
+    )]
+    pub creator_fee_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [b"pool_creator", pool_creator_record.pool.as_ref()],
+        bump,
+        has_one = creator @ CustomError::NotPoolCreator
+    )]
+    pub pool_creator_record: Account<'info, PoolCreatorRecord>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = creator_fee_vault.owner == pool_creator_record.key() @ CustomError::InvalidCreatorFeeVault
+    )]
+    pub creator_fee_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    /// CHECK: The creator's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", creator.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+}
+
+pub fn init_creator_fee_vault(_ctx: Context<InitCreatorFeeVault>) -> Result<()> {
+    Ok(())
+}
+
+/*
+ * Pay out everything a creator fee vault holds
+ */
+pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let amount = ctx.accounts.creator_fee_vault.amount;
+    require!(amount > 0, CustomError::NoCreatorFees);
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[ctx.accounts.recipient_token_account.key()],
+    )?;
+
+    let mut flows = FlowCheck::default();
+    flows.expect(
+        &ctx.accounts.creator_fee_vault.to_account_info(),
+        -(amount as i128),
+    )?;
+    flows.expect_credit(
+        &ctx.accounts.recipient_token_account.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        amount,
+    )?;
+    let pool = ctx.accounts.pool_creator_record.pool;
+    let signer_seeds: &[&[u8]] = &[
+        b"pool_creator",
+        pool.as_ref(),
+        &[ctx.bumps.pool_creator_record],
+    ];
+    let signer = &[signer_seeds];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.creator_fee_vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.recipient_token_account.to_account_info(),
+        authority: ctx.accounts.pool_creator_record.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+    flows.verify()?;
+
+    emit_event!(
+        ctx,
+        CreatorFeesClaimed {
+            creator: ctx.accounts.creator.key(),
+            pool,
+            mint: ctx.accounts.mint.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
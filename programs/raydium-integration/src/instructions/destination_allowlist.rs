@@ -0,0 +1,111 @@
+//! Destination allowlist management.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateDestinationAllowlist<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DestinationAllowlist::SIZE,
+        seeds = [b"destination_allowlist", owner.key().as_ref()],
+        bump
+    )]
+    pub destination_allowlist: Account<'info, DestinationAllowlist>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_allowed_destination(
+    ctx: Context<UpdateDestinationAllowlist>,
+    token_account: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let allowlist = &mut ctx.accounts.destination_allowlist;
+    allowlist.owner = ctx.accounts.owner.key();
+    require!(
+        allowlist.position(&token_account).is_none(),
+        CustomError::DestinationAlreadyListed
+    );
+    let count = allowlist.destination_count as usize;
+    require!(
+        count < MAX_ALLOWED_DESTINATIONS,
+        CustomError::DestinationAllowlistFull
+    );
+    let active_at = now.saturating_add(DESTINATION_ADD_DELAY);
+    allowlist.destinations[count] = AllowedDestination {
+        token_account,
+        active_at,
+    };
+    allowlist.destination_count += 1;
+
+    emit_event!(
+        ctx,
+        DestinationAdded {
+            owner: ctx.accounts.owner.key(),
+            token_account,
+            active_at,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn remove_allowed_destination(
+    ctx: Context<UpdateDestinationAllowlist>,
+    token_account: Pubkey,
+) -> Result<()> {
+    let allowlist = &mut ctx.accounts.destination_allowlist;
+    let index = allowlist
+        .position(&token_account)
+        .ok_or(CustomError::DestinationNotListed)?;
+    let last = allowlist.destination_count as usize - 1;
+    allowlist.destinations[index] = allowlist.destinations[last];
+    allowlist.destinations[last] = AllowedDestination::default();
+    allowlist.destination_count -= 1;
+
+    emit_event!(
+        ctx,
+        DestinationRemoved {
+            owner: ctx.accounts.owner.key(),
+            token_account,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn set_destination_allowlist_enabled(
+    ctx: Context<UpdateDestinationAllowlist>,
+    enabled: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let allowlist = &mut ctx.accounts.destination_allowlist;
+    allowlist.owner = ctx.accounts.owner.key();
+    let effective_at = if enabled {
+        allowlist.enabled = true;
+        allowlist.disable_at = 0;
+        now
+    } else {
+        if allowlist.disable_at == 0 {
+            allowlist.disable_at = now.saturating_add(DESTINATION_ADD_DELAY);
+        }
+        allowlist.disable_at
+    };
+
+    emit_event!(
+        ctx,
+        DestinationAllowlistToggled {
+            owner: ctx.accounts.owner.key(),
+            enabled,
+            effective_at,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//! Position fee checkpoints stamped by keepers.
+use crate::contexts::*;
+
+#[derive(Accounts)]
+pub struct InitFeeCheckpoint<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeCheckpoint::SIZE,
+        seeds = [b"fee_checkpoint", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub fee_checkpoint: Box<Account<'info, FeeCheckpoint>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct StampFeeCheckpoint<'info> {
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        constraint = personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    #[account(constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_checkpoint", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub fee_checkpoint: Box<Account<'info, FeeCheckpoint>>,
+
+    #[account(
+        seeds = [b"cost_basis", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub cost_basis: Option<Account<'info, PositionCostBasis>>,
+}
+
+pub fn init_fee_checkpoint(ctx: Context<InitFeeCheckpoint>) -> Result<()> {
+    ctx.accounts.fee_checkpoint.position_nft = ctx.accounts.personal_position.nft_mint;
+    Ok(())
+}
+
+pub fn stamp_fee_checkpoint(ctx: Context<StampFeeCheckpoint>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let checkpoint = &mut ctx.accounts.fee_checkpoint;
+    require!(
+        checkpoint.count == 0 || now >= checkpoint.latest().timestamp + FEE_CHECKPOINT_MIN_INTERVAL,
+        CustomError::CheckpointTooSoon
+    );
+
+    let position = &ctx.accounts.personal_position;
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let tick_array_lower = ctx.accounts.tick_array_lower.load()?;
+    let tick_array_upper = ctx.accounts.tick_array_upper.load()?;
+    let (fees_owed_0, fees_owed_1) =
+        uncollected_fees(&pool_state, position, &tick_array_lower, &tick_array_upper)?;
+    let (collected_0, collected_1) = ctx
+        .accounts
+        .cost_basis
+        .as_ref()
+        .map_or((0, 0), |c| (c.fees_collected_0, c.fees_collected_1));
+
+    let entry = FeeCheckpointEntry {
+        timestamp: now,
+        liquidity: position.liquidity,
+        cumulative_fees_0: fees_owed_0.saturating_add(collected_0),
+        cumulative_fees_1: fees_owed_1.saturating_add(collected_1),
+    };
+    checkpoint.push(entry);
+
+    emit_event!(
+        ctx,
+        FeeCheckpointStamped {
+            position_nft: position.nft_mint,
+            pool: position.pool_id,
+            liquidity: entry.liquidity,
+            cumulative_fees_0: entry.cumulative_fees_0,
+            cumulative_fees_1: entry.cumulative_fees_1,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//! Price oracles for converting protocol fees.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetFeeOracle<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub input_mint: InterfaceAccount<'info, Mint>,
+    pub fee_mint: InterfaceAccount<'info, Mint>,
+    pub oracle_pool: AccountLoader<'info, PoolState>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeOracle::SIZE,
+        seeds = [b"fee_oracle", input_mint.key().as_ref(), fee_mint.key().as_ref()],
+        bump
+    )]
+    pub fee_oracle: Account<'info, FeeOracle>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseFeeOracle<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"fee_oracle", fee_oracle.input_mint.as_ref(), fee_oracle.fee_mint.as_ref()],
+        bump
+    )]
+    pub fee_oracle: Account<'info, FeeOracle>,
+}
+
+pub fn set_fee_oracle(
+    ctx: Context<SetFeeOracle>,
+    twap_window_secs: u32,
+    max_staleness_secs: u32,
+    max_deviation_ticks: u32,
+) -> Result<()> {
+    require!(
+        twap_window_secs > 0 && max_staleness_secs > 0,
+        CustomError::InvalidFeeOracleSettings
+    );
+    let (token_mint_0, token_mint_1) = {
+        let pool_state = ctx.accounts.oracle_pool.load()?;
+        (pool_state.token_mint_0, pool_state.token_mint_1)
+    };
+    let input_mint = ctx.accounts.input_mint.key();
+    let fee_mint = ctx.accounts.fee_mint.key();
+    require!(
+        (token_mint_0, token_mint_1) == (input_mint, fee_mint)
+            || (token_mint_0, token_mint_1) == (fee_mint, input_mint),
+        CustomError::InvalidFeeOracle
+    );
+
+    let fee_oracle = &mut ctx.accounts.fee_oracle;
+    fee_oracle.input_mint = input_mint;
+    fee_oracle.fee_mint = fee_mint;
+    fee_oracle.pool = ctx.accounts.oracle_pool.key();
+    fee_oracle.input_is_token_0 = token_mint_0 == input_mint;
+    fee_oracle.twap_window_secs = twap_window_secs;
+    fee_oracle.max_staleness_secs = max_staleness_secs;
+    fee_oracle.max_deviation_ticks = max_deviation_ticks;
+
+    emit_event!(
+        ctx,
+        FeeOracleSet {
+            input_mint,
+            fee_mint,
+            pool: ctx.accounts.oracle_pool.key(),
+            twap_window_secs,
+            max_staleness_secs,
+            max_deviation_ticks,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn close_fee_oracle(_ctx: Context<CloseFeeOracle>) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,534 @@
+//! Price floor sell orders for token treasuries.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateFloorOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(mint::token_program = token_program)]
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = input_mint,
+        token::authority = owner
+    )]
+    pub owner_input_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Owner's account of the other pool mint, paid the output of each fill
+    #[account(token::authority = owner)]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + FloorOrder::SIZE,
+        seeds = [b"floor_order", owner.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub floor_order: Box<Account<'info, FloorOrder>>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"floor_escrow", floor_order.key().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = floor_order,
+        token::token_program = token_program,
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token program owning the input mint
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The owner's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The owner's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", owner.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct FillFloorOrder<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"floor_order",
+            floor_order.owner.as_ref(),
+            &floor_order.order_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub floor_order: Box<Account<'info, FloorOrder>>,
+
+    /// CHECK: Order owner, credited with the order's and escrow's rent once
+    /// the escrow is sold out
+    #[account(mut, address = floor_order.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"floor_escrow", floor_order.key().as_ref()],
+        bump,
+        token::token_program = input_token_program
+    )]
+    pub escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = floor_order.destination)]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    #[account(mut, address = floor_order.pool)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        mut,
+        constraint = input_vault.mint == floor_order.input_mint @ CustomError::InvalidOrderMint
+    )]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = input_vault.mint)]
+    pub input_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = output_vault.mint)]
+    pub output_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(mut, constraint = tick_array.load()?.pool_id == pool_state.key())]
+    pub tick_array: AccountLoader<'info, TickArrayState>,
+
+    /// CHECK: Raydium's tick array bitmap extension for the pool
+    #[account(
+        seeds = [POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub tick_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: `SwapValidatorRegistry` PDA; may be uninitialized
+    #[account(seeds = [b"swap_validators"], bump)]
+    pub swap_validators: UncheckedAccount<'info>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// Risky Token-2022 extensions the owner accepts on the swapped mints
+    #[account(seeds = [b"extension_opt_in", floor_order.owner.as_ref()], bump)]
+    pub mint_extension_opt_in: Option<Account<'info, MintExtensionOptIn>>,
+
+    /// CHECK: The owner's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", floor_order.owner.as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// CHECK: Raydium checks the memo program address
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// Token program owning the input mint, and so the escrow
+    pub input_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: The owner's `RoundTripCounter` PDA for the pool, always
+    /// passed; must be initialized while governance tracks round trips
+    #[account(
+        mut,
+        seeds = [b"round_trips", floor_order.owner.as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub round_trip_counter: UncheckedAccount<'info>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelFloorOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"floor_order", owner.key().as_ref(), &floor_order.order_id.to_le_bytes()],
+        bump,
+        has_one = owner,
+        close = owner
+    )]
+    pub floor_order: Account<'info, FloorOrder>,
+
+    #[account(mut, seeds = [b"floor_escrow", floor_order.key().as_ref()], bump)]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = floor_order.input_mint, mint::token_program = token_program)]
+    pub input_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = input_mint,
+        token::authority = owner
+    )]
+    pub owner_input_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token program owning the input mint
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: The owner's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The owner's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", owner.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+}
+
+pub fn create_floor_order(
+    ctx: Context<CreateFloorOrder>,
+    order_id: u64,
+    amount_in: u64,
+    floor_price_x64: u128,
+    daily_max: u64,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[ctx.accounts.destination.key()],
+    )?;
+
+    require!(
+        amount_in > 0 && floor_price_x64 > 0 && daily_max > 0,
+        CustomError::InvalidFloorOrder
+    );
+    let (token_mint_0, token_mint_1) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.token_mint_0, pool_state.token_mint_1)
+    };
+    let input_mint = ctx.accounts.input_mint.key();
+    let output_mint = ctx.accounts.destination.mint;
+    require!(
+        (input_mint, output_mint) == (token_mint_0, token_mint_1)
+            || (input_mint, output_mint) == (token_mint_1, token_mint_0),
+        CustomError::InvalidOrderMint
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let order = &mut ctx.accounts.floor_order;
+    order.owner = ctx.accounts.owner.key();
+    order.order_id = order_id;
+    order.pool = ctx.accounts.pool_state.key();
+    order.input_mint = input_mint;
+    order.destination = ctx.accounts.destination.key();
+    order.floor_price_x64 = floor_price_x64;
+    order.daily_max = daily_max;
+    order.day_start_ts = now;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.owner_input_account.to_account_info(),
+        mint: ctx.accounts.input_mint.to_account_info(),
+        to: ctx.accounts.escrow.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_context, amount_in, ctx.accounts.input_mint.decimals)?;
+    // A transfer-fee mint escrows less than was sent; fills are bounded by
+    // what the escrow holds
+    ctx.accounts.escrow.reload()?;
+    let amount_in = ctx.accounts.escrow.amount;
+    require!(amount_in > 0, CustomError::InvalidFloorOrder);
+
+    emit_event!(
+        ctx,
+        FloorOrderCreated {
+            owner: ctx.accounts.owner.key(),
+            order_id,
+            pool: ctx.accounts.pool_state.key(),
+            input_mint,
+            amount_in,
+            floor_price_x64,
+            daily_max,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
+
+/*
+ * Sell `amount_in` of the escrow at the floor price or better. Closes the
+ * order once the escrow is sold out
+ */
+pub fn fill_floor_order<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, FillFloorOrder<'info>>,
+    amount_in: u64,
+) -> Result<()> {
+    ctx.accounts
+        .global_config
+        .require_active(FLAG_PAUSE_SWAPS, "swaps")?;
+    token_extensions::require_allowed_extensions(
+        &[
+            ctx.accounts.input_vault_mint.to_account_info(),
+            ctx.accounts.output_vault_mint.to_account_info(),
+        ],
+        ctx.accounts.mint_extension_opt_in.as_deref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[ctx.accounts.destination.key()],
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let available = ctx.accounts.floor_order.available_today(now);
+    require!(
+        amount_in > 0 && amount_in <= available && amount_in <= ctx.accounts.escrow.amount,
+        CustomError::FloorOrderLimitExceeded
+    );
+    let order = &ctx.accounts.floor_order;
+    let min_out = order.min_out(amount_in);
+    let (owner, order_id) = (order.owner, order.order_id);
+
+    let validator_count = run_swap_validators(
+        &ctx.accounts.swap_validators,
+        [
+            ctx.accounts.keeper.to_account_info(),
+            ctx.accounts.pool_state.to_account_info(),
+            ctx.accounts.escrow.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+        ],
+        ctx.remaining_accounts,
+        &SwapValidation {
+            user: owner,
+            pool: ctx.accounts.pool_state.key(),
+            input_mint: ctx.accounts.input_vault.mint,
+            output_mint: ctx.accounts.output_vault.mint,
+            amount: amount_in,
+            expected_other_amount: min_out,
+            is_base_input: true,
+        },
+    )?;
+    let remaining_accounts = &ctx.remaining_accounts[validator_count..];
+    let output_balance_before = ctx.accounts.destination.amount;
+    // The fill spends exactly `amount_in` of the escrow
+    let mut flows = FlowCheck::default();
+    flows.expect(&ctx.accounts.escrow.to_account_info(), -(amount_in as i128))?;
+
+    // Build CPI accounts. SwapV2 takes every tick array after the bitmap
+    // extension, so the first one leads the remaining accounts
+    let cpi_accounts = cpi::accounts::SwapSingleV2 {
+        payer: ctx.accounts.floor_order.to_account_info(),
+        amm_config: ctx.accounts.amm_config.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        input_token_account: ctx.accounts.escrow.to_account_info(),
+        output_token_account: ctx.accounts.destination.to_account_info(),
+        input_vault: ctx.accounts.input_vault.to_account_info(),
+        output_vault: ctx.accounts.output_vault.to_account_info(),
+        observation_state: ctx.accounts.observation_state.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        memo_program: ctx.accounts.memo_program.to_account_info(),
+        input_vault_mint: ctx.accounts.input_vault_mint.to_account_info(),
+        output_vault_mint: ctx.accounts.output_vault_mint.to_account_info(),
+    };
+    let tick_arrays: Vec<AccountInfo<'info>> =
+        std::iter::once(ctx.accounts.tick_array.to_account_info())
+            .chain(remaining_accounts.iter().cloned())
+            .collect();
+
+    let order_id_bytes = order_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        b"floor_order",
+        owner.as_ref(),
+        &order_id_bytes,
+        &[ctx.bumps.floor_order],
+    ];
+    let signer = &[signer_seeds];
+
+    // Execute CPI
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        ctx.accounts.tick_array_bitmap_extension.as_ref(),
+        &tick_arrays,
+        &clmm_instruction::SwapV2 {
+            amount: amount_in,
+            other_amount_threshold: min_out,
+            sqrt_price_limit_x64: 0,
+            is_base_input: true,
+        },
+        signer,
+    )?;
+    flows.verify()?;
+
+    // A fill counts towards the owner's round trips like a swap of their own
+    let zero_for_one = swap_direction(
+        &*ctx.accounts.pool_state.load()?,
+        &ctx.accounts.input_vault.mint,
+        &ctx.accounts.input_vault.key(),
+        &ctx.accounts.output_vault.key(),
+    )?;
+    let round_trips_flagged = record_round_trip(
+        &ctx.accounts.global_config,
+        &ctx.accounts.round_trip_counter,
+        zero_for_one,
+        now,
+    )?;
+
+    ctx.accounts.destination.reload()?;
+    let amount_out = ctx
+        .accounts
+        .destination
+        .amount
+        .saturating_sub(output_balance_before);
+    let order = &mut ctx.accounts.floor_order;
+    order.sold_today += amount_in;
+    order.total_sold = order.total_sold.saturating_add(amount_in);
+    let sold_today = order.sold_today;
+
+    ctx.accounts.escrow.reload()?;
+    let sold_out = ctx.accounts.escrow.amount == 0;
+    if sold_out {
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.escrow.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.floor_order.to_account_info(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.input_token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token_interface::close_account(cpi_context)?;
+        ctx.accounts
+            .floor_order
+            .close(ctx.accounts.owner.to_account_info())?;
+    }
+
+    emit_event!(
+        ctx,
+        FloorOrderFilled {
+            owner,
+            order_id,
+            keeper: ctx.accounts.keeper.key(),
+            pool: ctx.accounts.pool_state.key(),
+            amount_in,
+            amount_out,
+            min_out,
+            sold_today,
+            sold_out,
+            timestamp: now,
+        }
+    );
+    if let Some((round_trips, max_round_trips)) = round_trips_flagged {
+        emit_event!(
+            ctx,
+            RoundTripsFlagged {
+                user: owner,
+                pool: ctx.accounts.pool_state.key(),
+                round_trips,
+                max_round_trips,
+                timestamp: now,
+                user_seq: 0,
+                op_id: operation_id(&ctx.accounts.keeper.key())?,
+            }
+        );
+    }
+
+    Ok(())
+}
+
+pub fn cancel_floor_order(ctx: Context<CancelFloorOrder>) -> Result<()> {
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[ctx.accounts.owner_input_account.key()],
+    )?;
+
+    let order = &ctx.accounts.floor_order;
+    let order_id_bytes = order.order_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        b"floor_order",
+        order.owner.as_ref(),
+        &order_id_bytes,
+        &[ctx.bumps.floor_order],
+    ];
+    let signer = &[signer_seeds];
+    let refunded = ctx.accounts.escrow.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.escrow.to_account_info(),
+        mint: ctx.accounts.input_mint.to_account_info(),
+        to: ctx.accounts.owner_input_account.to_account_info(),
+        authority: ctx.accounts.floor_order.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::transfer_checked(cpi_context, refunded, ctx.accounts.input_mint.decimals)?;
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.escrow.to_account_info(),
+        destination: ctx.accounts.owner.to_account_info(),
+        authority: ctx.accounts.floor_order.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::close_account(cpi_context)?;
+
+    emit_event!(
+        ctx,
+        FloorOrderCancelled {
+            owner: ctx.accounts.owner.key(),
+            order_id: ctx.accounts.floor_order.order_id,
+            refunded,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
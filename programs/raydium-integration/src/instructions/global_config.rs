@@ -0,0 +1,270 @@
+//! Protocol governance.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct InitGlobalConfig<'info> {
+    /// Must be the program's upgrade authority; becomes the admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GlobalConfig::SIZE,
+        seeds = [b"global_config"],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::RaydiumIntegration>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ CustomError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_config"],
+        bump,
+        has_one = admin @ CustomError::Unauthorized
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ScheduleParamChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PendingParamChange::SIZE,
+        seeds = [b"pending_param_change"],
+        bump
+    )]
+    pub pending_param_change: Account<'info, PendingParamChange>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApplyParamChange<'info> {
+    /// CHECK: Receives the pending change's rent
+    #[account(mut, address = global_config.admin)]
+    pub admin: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"global_config"], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, close = admin, seeds = [b"pending_param_change"], bump)]
+    pub pending_param_change: Account<'info, PendingParamChange>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelParamChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, close = admin, seeds = [b"pending_param_change"], bump)]
+    pub pending_param_change: Account<'info, PendingParamChange>,
+}
+
+pub fn init_global_config(
+    ctx: Context<InitGlobalConfig>,
+    treasury: Pubkey,
+    protocol_fee_bps: u16,
+    insurance_share_bps: u16,
+) -> Result<()> {
+    GlobalConfig::validate_fees(protocol_fee_bps, insurance_share_bps)?;
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.admin = ctx.accounts.admin.key();
+    global_config.treasury = treasury;
+    global_config.protocol_fee_bps = protocol_fee_bps;
+    global_config.insurance_share_bps = insurance_share_bps;
+
+    emit_event!(
+        ctx,
+        GlobalConfigUpdated {
+            admin: ctx.accounts.admin.key(),
+            treasury,
+            protocol_fee_bps,
+            insurance_share_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+            flags: ctx.accounts.global_config.flags,
+            creator_share_bps: ctx.accounts.global_config.creator_share_bps,
+            round_trip_window_secs: ctx.accounts.global_config.round_trip_window_secs,
+            max_round_trips: ctx.accounts.global_config.max_round_trips,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn set_global_admin(ctx: Context<UpdateGlobalConfig>, new_admin: Pubkey) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.admin = new_admin;
+
+    emit_event!(
+        ctx,
+        GlobalConfigUpdated {
+            admin: new_admin,
+            treasury: ctx.accounts.global_config.treasury,
+            protocol_fee_bps: ctx.accounts.global_config.protocol_fee_bps,
+            insurance_share_bps: ctx.accounts.global_config.insurance_share_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+            flags: ctx.accounts.global_config.flags,
+            creator_share_bps: ctx.accounts.global_config.creator_share_bps,
+            round_trip_window_secs: ctx.accounts.global_config.round_trip_window_secs,
+            max_round_trips: ctx.accounts.global_config.max_round_trips,
+        }
+    );
+
+    Ok(())
+}
+
+/*
+ * Only the pause bits take effect immediately, so governance can halt the
+ * program without notice; every other setting goes through
+ * `schedule_param_change`
+ */
+pub fn set_pause_flags(ctx: Context<UpdateGlobalConfig>, pause_flags: u8) -> Result<()> {
+    require!(
+        pause_flags & !PAUSE_FLAGS == 0,
+        CustomError::InvalidGlobalFlags
+    );
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.flags = (global_config.flags & !PAUSE_FLAGS) | pause_flags;
+
+    emit_event!(
+        ctx,
+        GlobalConfigUpdated {
+            admin: ctx.accounts.global_config.admin,
+            treasury: ctx.accounts.global_config.treasury,
+            protocol_fee_bps: ctx.accounts.global_config.protocol_fee_bps,
+            insurance_share_bps: ctx.accounts.global_config.insurance_share_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+            flags: ctx.accounts.global_config.flags,
+            creator_share_bps: ctx.accounts.global_config.creator_share_bps,
+            round_trip_window_secs: ctx.accounts.global_config.round_trip_window_secs,
+            max_round_trips: ctx.accounts.global_config.max_round_trips,
+        }
+    );
+
+    Ok(())
+}
+
+/*
+ * `flags` carries the non-pause bits only; the pause bits are left to
+ * `set_pause_flags`. Zero for either round-trip value turns detection off
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn schedule_param_change(
+    ctx: Context<ScheduleParamChange>,
+    treasury: Pubkey,
+    protocol_fee_bps: u16,
+    insurance_share_bps: u16,
+    flags: u8,
+    creator_share_bps: u16,
+    round_trip_window_secs: u32,
+    max_round_trips: u16,
+    activation_slot: u64,
+) -> Result<()> {
+    GlobalConfig::validate_fees(protocol_fee_bps, insurance_share_bps)?;
+    require!(creator_share_bps <= 10_000, CustomError::InvalidProtocolFee);
+    require!(
+        flags & !ALL_GLOBAL_FLAGS == 0 && flags & PAUSE_FLAGS == 0,
+        CustomError::InvalidGlobalFlags
+    );
+    let clock = Clock::get()?;
+    require!(
+        activation_slot >= clock.slot.saturating_add(MIN_PARAM_CHANGE_NOTICE_SLOTS),
+        CustomError::ActivationTooSoon
+    );
+    let pending = &mut ctx.accounts.pending_param_change;
+    pending.treasury = treasury;
+    pending.protocol_fee_bps = protocol_fee_bps;
+    pending.insurance_share_bps = insurance_share_bps;
+    pending.flags = flags;
+    pending.creator_share_bps = creator_share_bps;
+    pending.round_trip_window_secs = round_trip_window_secs;
+    pending.max_round_trips = max_round_trips;
+    pending.activation_slot = activation_slot;
+    pending.scheduled_slot = clock.slot;
+
+    emit_event!(
+        ctx,
+        ParamChangeScheduled {
+            admin: ctx.accounts.admin.key(),
+            treasury,
+            protocol_fee_bps,
+            insurance_share_bps,
+            flags,
+            creator_share_bps,
+            round_trip_window_secs,
+            max_round_trips,
+            activation_slot,
+            timestamp: clock.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+/*
+ * Permissionless: anyone can crank a change past its activation slot. Pause
+ * bits set since scheduling are kept
+ */
+pub fn apply_param_change(ctx: Context<ApplyParamChange>) -> Result<()> {
+    let pending = &ctx.accounts.pending_param_change;
+    require!(
+        Clock::get()?.slot >= pending.activation_slot,
+        CustomError::ParamChangeNotActive
+    );
+    let global_config = &mut ctx.accounts.global_config;
+    global_config.treasury = pending.treasury;
+    global_config.protocol_fee_bps = pending.protocol_fee_bps;
+    global_config.insurance_share_bps = pending.insurance_share_bps;
+    global_config.flags = (global_config.flags & PAUSE_FLAGS) | (pending.flags & !PAUSE_FLAGS);
+    global_config.creator_share_bps = pending.creator_share_bps;
+    global_config.round_trip_window_secs = pending.round_trip_window_secs;
+    global_config.max_round_trips = pending.max_round_trips;
+
+    emit_event!(
+        ctx,
+        GlobalConfigUpdated {
+            admin: ctx.accounts.global_config.admin,
+            treasury: ctx.accounts.global_config.treasury,
+            protocol_fee_bps: ctx.accounts.global_config.protocol_fee_bps,
+            insurance_share_bps: ctx.accounts.global_config.insurance_share_bps,
+            timestamp: Clock::get()?.unix_timestamp,
+            flags: ctx.accounts.global_config.flags,
+            creator_share_bps: ctx.accounts.global_config.creator_share_bps,
+            round_trip_window_secs: ctx.accounts.global_config.round_trip_window_secs,
+            max_round_trips: ctx.accounts.global_config.max_round_trips,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn cancel_param_change(ctx: Context<CancelParamChange>) -> Result<()> {
+    emit_event!(
+        ctx,
+        ParamChangeCancelled {
+            admin: ctx.accounts.admin.key(),
+            activation_slot: ctx.accounts.pending_param_change.activation_slot,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Insurance fund.
+use crate::contexts::*;
+
+#[derive(Accounts)]
+pub struct InitInsuranceVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"insurance_fund", mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = global_config,
+        token::token_program = token_program,
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct PayInsuranceClaim<'info> {
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Account<'info, GlobalConfig>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, seeds = [b"insurance_fund", mint.key().as_ref()], bump)]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn init_insurance_vault(_ctx: Context<InitInsuranceVault>) -> Result<()> {
+    Ok(())
+}
+
+pub fn pay_insurance_claim(ctx: Context<PayInsuranceClaim>, amount: u64) -> Result<()> {
+    require!(amount > 0, CustomError::ZeroClaimAmount);
+    require!(
+        ctx.accounts.insurance_vault.amount >= amount,
+        CustomError::InsufficientInsuranceFund
+    );
+
+    let mut flows = FlowCheck::default();
+    flows.expect(
+        &ctx.accounts.insurance_vault.to_account_info(),
+        -(amount as i128),
+    )?;
+    flows.expect_credit(
+        &ctx.accounts.recipient_token_account.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        amount,
+    )?;
+
+    let signer_seeds: &[&[u8]] = &[b"global_config", &[ctx.bumps.global_config]];
+    let signer = &[signer_seeds];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.insurance_vault.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.recipient_token_account.to_account_info(),
+        authority: ctx.accounts.global_config.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+    flows.verify()?;
+
+    emit_event!(
+        ctx,
+        InsuranceClaimPaid {
+            recipient: ctx.accounts.recipient_token_account.owner,
+            mint: ctx.accounts.mint.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,714 @@
+//! Proxied liquidity increases and decreases.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyIncreaseLiquidity<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    pub nft_owner: Signer<'info>,
+
+    #[account(
+        token::authority = nft_owner,
+        constraint = nft_account.mint == personal_position.nft_mint
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"cost_basis", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub cost_basis: Option<Account<'info, PositionCostBasis>>,
+
+    #[account(seeds = [b"extension_opt_in", nft_owner.key().as_ref()], bump)]
+    pub mint_extension_opt_in: Option<Account<'info, MintExtensionOptIn>>,
+
+    /// Pass with `points_ledger` and `position_points` to earn LP points
+    #[account(seeds = [b"points_config"], bump)]
+    pub points_config: Option<Account<'info, PointsConfig>>,
+
+    #[account(mut, seeds = [b"points", nft_owner.key().as_ref()], bump)]
+    pub points_ledger: Option<Account<'info, PointsLedger>>,
+
+    #[account(
+        mut,
+        seeds = [b"position_points", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub position_points: Option<Account<'info, PositionPoints>>,
+
+    /// CHECK: Caller-chosen program called after execution with the actual
+    /// amounts. Its accounts follow it at the end of the remaining accounts
+    #[account(executable)]
+    pub callback_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The owner's `UserConfig` PDA, read for exposure caps and the
+    /// event sequence when it holds a current-layout config
+    #[account(mut, seeds = [b"user_cfg", nft_owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// The owner's exposure to the pool, needed while their config caps it
+    /// per pool
+    #[account(
+        mut,
+        seeds = [b"pool_exposure", nft_owner.key().as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub pool_exposure: Option<Box<Account<'info, PoolExposure>>>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyDecreaseLiquidity<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    pub nft_owner: Signer<'info>,
+
+    #[account(
+        token::authority = nft_owner,
+        constraint = nft_account.mint == personal_position.nft_mint
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// CHECK: Raydium checks the memo program address
+    pub memo_program: UncheckedAccount<'info>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"cost_basis", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub cost_basis: Option<Account<'info, PositionCostBasis>>,
+
+    /// CHECK: The owner's `UserConfig` PDA, always passed so allowed callers
+    /// cannot be skipped. Once initialized it supplies the slippage tolerance
+    /// for the on-chain minimums, the default applying before, and
+    /// withdrawals lower its exposure
+    #[account(mut, seeds = [b"user_cfg", nft_owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// Pass with `points_ledger` and `position_points` to earn LP points
+    #[account(seeds = [b"points_config"], bump)]
+    pub points_config: Option<Account<'info, PointsConfig>>,
+
+    #[account(mut, seeds = [b"points", nft_owner.key().as_ref()], bump)]
+    pub points_ledger: Option<Account<'info, PointsLedger>>,
+
+    #[account(
+        mut,
+        seeds = [b"position_points", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub position_points: Option<Account<'info, PositionPoints>>,
+
+    /// CHECK: The owner's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", nft_owner.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+
+    /// CHECK: Caller-chosen program called after execution with the actual
+    /// amounts. Its accounts follow it at the end of the remaining accounts
+    #[account(executable)]
+    pub callback_program: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool_exposure", nft_owner.key().as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub pool_exposure: Option<Box<Account<'info, PoolExposure>>>,
+
+    /// Not checked for the pause flags: exits stay open while the program is
+    /// paused
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+pub fn proxy_increase_liquidity<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ProxyIncreaseLiquidity<'info>>,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    simulate_only: bool,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+
+    require!(
+        amount_0_max > 0 || amount_1_max > 0,
+        CustomError::ZeroDeposit
+    );
+
+    // Zero liquidity deposits by amounts: Raydium sizes the liquidity from
+    // whichever side constrains the deposit, net of any transfer fee
+    let base_flag = if liquidity == 0 {
+        let sqrt_price_x64 = ctx.accounts.pool_state.load()?.sqrt_price_x64;
+        let (tick_lower, tick_upper) = (
+            ctx.accounts.personal_position.tick_lower_index,
+            ctx.accounts.personal_position.tick_upper_index,
+        );
+        require!(
+            liquidity_for_amounts(
+                sqrt_price_x64,
+                tick_lower,
+                tick_upper,
+                amount_0_max,
+                amount_1_max,
+            )? > 0,
+            CustomError::ZeroLiquidity
+        );
+        Some(math::base_flag_for_amounts(
+            sqrt_price_x64,
+            tick_lower,
+            tick_upper,
+            amount_0_max,
+            amount_1_max,
+        )?)
+    } else {
+        None
+    };
+
+    token_extensions::require_allowed_extensions(
+        &[
+            ctx.accounts.vault_0_mint.to_account_info(),
+            ctx.accounts.vault_1_mint.to_account_info(),
+        ],
+        ctx.accounts.mint_extension_opt_in.as_deref(),
+    )?;
+
+    // Credit LP points for the liquidity held before this change
+    accrue_lp_points(
+        ctx.accounts.points_config.as_deref(),
+        ctx.accounts.points_ledger.as_deref_mut(),
+        ctx.accounts.position_points.as_deref_mut(),
+        &ctx.accounts.personal_position,
+        ctx.accounts.pool_state.load()?.tick_current,
+    )?;
+
+    let balance_0_before = ctx.accounts.token_account_0.amount;
+    let balance_1_before = ctx.accounts.token_account_1.amount;
+    let liquidity_before = ctx.accounts.personal_position.liquidity;
+
+    let (remaining_accounts, callback_accounts) = split_callback_accounts(
+        ctx.remaining_accounts,
+        ctx.accounts.callback_program.as_ref(),
+    );
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::IncreaseLiquidityV2 {
+        nft_owner: ctx.accounts.nft_owner.to_account_info(),
+        nft_account: ctx.accounts.nft_account.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        token_account_0: ctx.accounts.token_account_0.to_account_info(),
+        token_account_1: ctx.accounts.token_account_1.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+    };
+
+    // Execute CPI
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        None,
+        remaining_accounts,
+        &clmm_instruction::IncreaseLiquidityV2 {
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            base_flag,
+        },
+        &[],
+    )?;
+
+    ctx.accounts.token_account_0.reload()?;
+    ctx.accounts.token_account_1.reload()?;
+    ctx.accounts.personal_position.reload()?;
+    let amount_0 = balance_0_before.saturating_sub(ctx.accounts.token_account_0.amount);
+    let amount_1 = balance_1_before.saturating_sub(ctx.accounts.token_account_1.amount);
+
+    if let Some(cost_basis) = ctx.accounts.cost_basis.as_mut() {
+        let sqrt_price_x64 = ctx.accounts.pool_state.load()?.sqrt_price_x64;
+        cost_basis.record_deposit(amount_0, amount_1, sqrt_price_x64);
+    }
+
+    // Configs never set up carry no caps and no event sequence
+    let mut user_seq = 0;
+    if let Some(user_cfg) = existing_user_config(&ctx.accounts.user_cfg)? {
+        let mut user_cfg = user_cfg.load_mut()?;
+        track_exposure(
+            &mut user_cfg,
+            ctx.accounts.pool_exposure.as_deref_mut(),
+            &ctx.accounts.pool_state.key(),
+            &*ctx.accounts.pool_state.load()?,
+            amount_0,
+            amount_1,
+            true,
+        )?;
+        user_seq = user_cfg.next_event_seq();
+    }
+
+    let liquidity_added = ctx
+        .accounts
+        .personal_position
+        .liquidity
+        .saturating_sub(liquidity_before);
+    let now = Clock::get()?.unix_timestamp;
+    let (ui_amount_0, ui_amount_1) = token_extensions::ui_amounts(
+        &ctx.accounts.vault_0_mint.to_account_info(),
+        &ctx.accounts.vault_1_mint.to_account_info(),
+        amount_0,
+        amount_1,
+        now,
+    )?;
+    emit_event!(
+        ctx,
+        LiquidityIncreased {
+            user: ctx.accounts.nft_owner.key(),
+            position_nft: ctx.accounts.personal_position.nft_mint,
+            liquidity_added,
+            amount_0_added: amount_0,
+            amount_1_added: amount_1,
+            ui_amount_0_added: ui_amount_0,
+            ui_amount_1_added: ui_amount_1,
+            timestamp: now,
+            user_seq,
+        }
+    );
+
+    if let Some(callback_program) = ctx.accounts.callback_program.as_ref() {
+        invoke_callback(
+            callback_program,
+            callback_accounts,
+            LIQUIDITY_CALLBACK_DISCRIMINATOR,
+            &LiquidityCallback {
+                user: ctx.accounts.nft_owner.key(),
+                pool: ctx.accounts.pool_state.key(),
+                position_nft: ctx.accounts.personal_position.nft_mint,
+                liquidity_delta: liquidity_added as i128,
+                amount_0,
+                amount_1,
+                fees_0: 0,
+                fees_1: 0,
+            },
+        )?;
+    }
+
+    if simulate_only {
+        return finish_simulation(&LiquiditySimulation {
+            liquidity: liquidity_added,
+            amount_0,
+            amount_1,
+            fees_0: 0,
+            fees_1: 0,
+            ui_amount_0,
+            ui_amount_1,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn proxy_decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ProxyDecreaseLiquidity<'info>>,
+    liquidity: u128,
+    amount_0_min: u64,
+    amount_1_min: u64,
+    simulate_only: bool,
+) -> Result<()> {
+    let user_cfg = existing_user_config(&ctx.accounts.user_cfg)?;
+    if let Some(user_cfg) = user_cfg.as_ref() {
+        user_cfg
+            .load()?
+            .check_caller(ctx.accounts.instructions_sysvar.as_ref())?;
+    }
+
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[
+            ctx.accounts.recipient_token_account_0.key(),
+            ctx.accounts.recipient_token_account_1.key(),
+        ],
+    )?;
+
+    let (sqrt_price_x64, tick_current) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.sqrt_price_x64, pool_state.tick_current)
+    };
+    let (tick_lower, tick_upper) = (
+        ctx.accounts.personal_position.tick_lower_index,
+        ctx.accounts.personal_position.tick_upper_index,
+    );
+    // Decreasing liquidity does not move the price, so the principal is
+    // what the removed liquidity is worth at the pre-CPI price
+    let (principal_0, principal_1) = liquidity_math::get_delta_amounts_signed(
+        tick_current,
+        sqrt_price_x64,
+        tick_lower,
+        tick_upper,
+        -i128::try_from(liquidity).map_err(|_| CustomError::MathOverflow)?,
+    )?;
+
+    // Floor the minimums at that principal less the user's slippage
+    // tolerance, so callers passing zero are still protected
+    let bps = match user_cfg.as_ref() {
+        Some(user_cfg) => user_cfg.load()?.effective_slippage_bps(),
+        None => DEFAULT_SLIPPAGE_BPS,
+    };
+    require!(bps > 0 && bps <= 500, CustomError::InvalidSlippage);
+    let now = Clock::get()?.unix_timestamp;
+    let amount_0_min = amount_0_min.max(token_extensions::slippage_threshold(
+        &ctx.accounts.vault_0_mint.to_account_info(),
+        principal_0,
+        bps,
+        true,
+        now,
+    )?);
+    let amount_1_min = amount_1_min.max(token_extensions::slippage_threshold(
+        &ctx.accounts.vault_1_mint.to_account_info(),
+        principal_1,
+        bps,
+        true,
+        now,
+    )?);
+
+    // Credit LP points for the liquidity held before this change
+    accrue_lp_points(
+        ctx.accounts.points_config.as_deref(),
+        ctx.accounts.points_ledger.as_deref_mut(),
+        ctx.accounts.position_points.as_deref_mut(),
+        &ctx.accounts.personal_position,
+        tick_current,
+    )?;
+
+    let balance_0_before = ctx.accounts.recipient_token_account_0.amount;
+    let balance_1_before = ctx.accounts.recipient_token_account_1.amount;
+
+    let (remaining_accounts, callback_accounts) = split_callback_accounts(
+        ctx.remaining_accounts,
+        ctx.accounts.callback_program.as_ref(),
+    );
+    let reward_payouts = reward_payouts(&ctx.accounts.clmm_program.key(), remaining_accounts)?;
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::DecreaseLiquidityV2 {
+        nft_owner: ctx.accounts.nft_owner.to_account_info(),
+        nft_account: ctx.accounts.nft_account.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        recipient_token_account_0: ctx.accounts.recipient_token_account_0.to_account_info(),
+        recipient_token_account_1: ctx.accounts.recipient_token_account_1.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        memo_program: ctx.accounts.memo_program.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+    };
+
+    // Execute CPI
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        None,
+        remaining_accounts,
+        &clmm_instruction::DecreaseLiquidityV2 {
+            liquidity,
+            amount_0_min,
+            amount_1_min,
+        },
+        &[],
+    )?;
+
+    ctx.accounts.recipient_token_account_0.reload()?;
+    ctx.accounts.recipient_token_account_1.reload()?;
+    let mut received_0 = ctx
+        .accounts
+        .recipient_token_account_0
+        .amount
+        .saturating_sub(balance_0_before);
+    let mut received_1 = ctx
+        .accounts
+        .recipient_token_account_1
+        .amount
+        .saturating_sub(balance_1_before);
+    // A reward in a pool mint may be paid to the same account; it is not fees
+    let mut rewards = Vec::with_capacity(reward_payouts.len());
+    for payout in &reward_payouts {
+        let amount = payout.received()?;
+        if payout.recipient.key() == ctx.accounts.recipient_token_account_0.key() {
+            received_0 = received_0.saturating_sub(amount);
+        } else if payout.recipient.key() == ctx.accounts.recipient_token_account_1.key() {
+            received_1 = received_1.saturating_sub(amount);
+        }
+        rewards.push((payout.reward_index, payout.reward_mint, amount));
+    }
+
+    let fees_0 = received_0.saturating_sub(principal_0);
+    let fees_1 = received_1.saturating_sub(principal_1);
+
+    if let Some(cost_basis) = ctx.accounts.cost_basis.as_mut() {
+        cost_basis.record_withdrawal(received_0, received_1, fees_0, fees_1, sqrt_price_x64);
+    }
+    if let Some(user_cfg) = user_cfg.as_ref() {
+        track_exposure(
+            &mut *user_cfg.load_mut()?,
+            ctx.accounts.pool_exposure.as_deref_mut(),
+            &ctx.accounts.pool_state.key(),
+            &*ctx.accounts.pool_state.load()?,
+            principal_0,
+            principal_1,
+            false,
+        )?;
+    }
+
+    let (amount_0, amount_1) = (
+        received_0.saturating_sub(fees_0),
+        received_1.saturating_sub(fees_1),
+    );
+    let (ui_amount_0, ui_amount_1) = token_extensions::ui_amounts(
+        &ctx.accounts.vault_0_mint.to_account_info(),
+        &ctx.accounts.vault_1_mint.to_account_info(),
+        amount_0,
+        amount_1,
+        now,
+    )?;
+    let op_id = operation_id(&ctx.accounts.nft_owner.key())?;
+    let user_seq = next_user_seq(user_cfg.as_ref())?;
+    emit_event!(
+        ctx,
+        LiquidityDecreased {
+            user: ctx.accounts.nft_owner.key(),
+            position_nft: ctx.accounts.personal_position.nft_mint,
+            liquidity_removed: liquidity,
+            amount_0_removed: amount_0,
+            amount_1_removed: amount_1,
+            ui_amount_0_removed: ui_amount_0,
+            ui_amount_1_removed: ui_amount_1,
+            timestamp: now,
+            user_seq,
+            op_id,
+        }
+    );
+    if fees_0 > 0 || fees_1 > 0 {
+        let (ui_fees_0, ui_fees_1) = token_extensions::ui_amounts(
+            &ctx.accounts.vault_0_mint.to_account_info(),
+            &ctx.accounts.vault_1_mint.to_account_info(),
+            fees_0,
+            fees_1,
+            now,
+        )?;
+        let user_seq = next_user_seq(user_cfg.as_ref())?;
+        emit_event!(
+            ctx,
+            FeesCollected {
+                user: ctx.accounts.nft_owner.key(),
+                pool: ctx.accounts.pool_state.key(),
+                position_nft: ctx.accounts.personal_position.nft_mint,
+                amount_0: fees_0,
+                amount_1: fees_1,
+                timestamp: now,
+                user_seq,
+                op_id,
+                ui_amount_0: ui_fees_0,
+                ui_amount_1: ui_fees_1,
+            }
+        );
+    }
+    for (reward_index, reward_mint, amount) in rewards {
+        if amount == 0 {
+            continue;
+        }
+        let user_seq = next_user_seq(user_cfg.as_ref())?;
+        emit_event!(
+            ctx,
+            RewardsCollected {
+                user: ctx.accounts.nft_owner.key(),
+                pool: ctx.accounts.pool_state.key(),
+                position_nft: ctx.accounts.personal_position.nft_mint,
+                reward_index,
+                reward_mint,
+                amount,
+                timestamp: now,
+                user_seq,
+                op_id,
+            }
+        );
+    }
+
+    if let Some(callback_program) = ctx.accounts.callback_program.as_ref() {
+        invoke_callback(
+            callback_program,
+            callback_accounts,
+            LIQUIDITY_CALLBACK_DISCRIMINATOR,
+            &LiquidityCallback {
+                user: ctx.accounts.nft_owner.key(),
+                pool: ctx.accounts.pool_state.key(),
+                position_nft: ctx.accounts.personal_position.nft_mint,
+                liquidity_delta: -(liquidity as i128),
+                amount_0,
+                amount_1,
+                fees_0,
+                fees_1,
+            },
+        )?;
+    }
+
+    if simulate_only {
+        return finish_simulation(&LiquiditySimulation {
+            liquidity,
+            amount_0,
+            amount_1,
+            fees_0,
+            fees_1,
+            ui_amount_0,
+            ui_amount_1,
+        });
+    }
+
+    Ok(())
+}
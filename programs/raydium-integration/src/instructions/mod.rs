@@ -0,0 +1,58 @@
+//! Instruction handlers and their account contexts, one module per feature.
+//! The `#[program]` module in `lib.rs` forwards to them.
+mod amm_launch;
+mod close_position;
+mod commit_reveal;
+mod cpmm_migration;
+mod create_pool;
+mod creator_fees;
+mod destination_allowlist;
+mod dutch_order;
+mod fee_checkpoint;
+mod fee_oracle;
+mod floor_order;
+mod global_config;
+mod insurance;
+mod liquidity;
+mod open_position;
+mod points;
+mod position_registry;
+mod position_stream;
+mod recovery;
+mod session;
+pub(crate) mod shared;
+mod shared_position;
+mod split_merge;
+mod stats;
+mod swap;
+mod swap_validators;
+mod user_config;
+mod views;
+
+pub use amm_launch::*;
+pub use close_position::*;
+pub use commit_reveal::*;
+pub use cpmm_migration::*;
+pub use create_pool::*;
+pub use creator_fees::*;
+pub use destination_allowlist::*;
+pub use dutch_order::*;
+pub use fee_checkpoint::*;
+pub use fee_oracle::*;
+pub use floor_order::*;
+pub use global_config::*;
+pub use insurance::*;
+pub use liquidity::*;
+pub use open_position::*;
+pub use points::*;
+pub use position_registry::*;
+pub use position_stream::*;
+pub use recovery::*;
+pub use session::*;
+pub use shared_position::*;
+pub use split_merge::*;
+pub use stats::*;
+pub use swap::*;
+pub use swap_validators::*;
+pub use user_config::*;
+pub use views::*;
@@ -0,0 +1,626 @@
+//! Proxied position opens.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxyOpenPosition<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    /// Pays rent for the position accounts and authorizes the deposits.
+    /// Raydium uses it as the System Program funder, so a PDA caller must be
+    /// a data-less, system-owned PDA holding enough lamports.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserConfig::SIZE,
+        seeds = [b"user_cfg", payer.key().as_ref()],
+        bump
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+
+    /// CHECK: Receives the position NFT
+    pub position_nft_owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub position_nft_mint: Signer<'info>,
+
+    /// CHECK: Token account where position NFT will be minted
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: To store metaplex metadata, only needed with `with_matedata`
+    #[account(mut)]
+    pub metadata_account: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Raydium checks the PDA against the position's tick bounds,
+    /// which full-range opens only derive inside the handler
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    /// CHECK: Account to mark the lower tick as initialized; Raydium checks
+    /// the PDA against the lower tick array start index
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Account to store data for the position's upper tick; Raydium
+    /// checks the PDA against the upper tick array start index
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: personal position state
+    #[account(
+        mut,
+        seeds = [POSITION_SEED.as_bytes(), position_nft_mint.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub personal_position: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub programs: PositionPrograms<'info>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, seeds = [b"pool_stats", pool_state.key().as_ref()], bump)]
+    pub pool_stats: Option<AccountLoader<'info, PoolStats>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump)]
+    pub global_stats: Option<AccountLoader<'info, GlobalStats>>,
+
+    #[account(
+        mut,
+        seeds = [b"position_registry", position_nft_owner.key().as_ref()],
+        bump
+    )]
+    pub position_registry: Option<Box<Account<'info, UserPositionRegistry>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position_registry",
+            position_nft_owner.key().as_ref(),
+            &position_registry_page.page.to_le_bytes(),
+        ],
+        bump,
+        constraint = position_registry_page.owner == position_nft_owner.key()
+    )]
+    pub position_registry_page: Option<Box<Account<'info, PositionRegistryPage>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PositionCostBasis::SIZE,
+        seeds = [b"cost_basis", position_nft_mint.key().as_ref()],
+        bump
+    )]
+    pub cost_basis: Option<Box<Account<'info, PositionCostBasis>>>,
+
+    /// CHECK: Raydium's tick array bitmap extension for the pool, needed when
+    /// the position's tick arrays lie outside the pool's default bitmap
+    #[account(
+        mut,
+        seeds = [POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub tick_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    #[account(seeds = [b"extension_opt_in", payer.key().as_ref()], bump)]
+    pub mint_extension_opt_in: Option<Account<'info, MintExtensionOptIn>>,
+
+    /// CHECK: Instructions sysvar, needed when the payer restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// The owner's approval of the payer, needed when the position NFT goes
+    /// to an owner who does not sign
+    #[account(
+        seeds = [b"open_approval", position_nft_owner.key().as_ref(), payer.key().as_ref()],
+        bump
+    )]
+    pub open_approval: Option<Box<Account<'info, OpenApproval>>>,
+
+    /// The payer's exposure to the pool, needed while their config caps it
+    /// per pool
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PoolExposure::SIZE,
+        seeds = [b"pool_exposure", payer.key().as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub pool_exposure: Option<Box<Account<'info, PoolExposure>>>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApproveOpenOnBehalf<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Any key the owner trusts to open positions for them
+    pub delegate: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + OpenApproval::SIZE,
+        seeds = [b"open_approval", owner.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub open_approval: Account<'info, OpenApproval>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevokeOpenOnBehalf<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"open_approval", owner.key().as_ref(), open_approval.delegate.as_ref()],
+        bump
+    )]
+    pub open_approval: Account<'info, OpenApproval>,
+}
+
+/*
+ * Programs and sysvars Raydium's `open_position_v2` needs. A separate
+ * struct, validated in its own frame, to keep `ProxyOpenPosition` small
+ */
+#[derive(Accounts)]
+pub struct PositionPrograms<'info> {
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+}
+
+/// Inline size bound of `ProxyOpenPosition`, which shares the 4KB stack
+/// frame budget with its validation code. Box new account fields, or move
+/// them into a composite struct, rather than raise it
+const MAX_OPEN_POSITION_CONTEXT_SIZE: usize = 512;
+
+const _: () =
+    assert!(std::mem::size_of::<ProxyOpenPosition<'static>>() <= MAX_OPEN_POSITION_CONTEXT_SIZE);
+
+impl<'info> ProxyOpenPosition<'info> {
+    /*
+     * CPI Raydium's `open_position_v2`. Out of line so its account structs
+     * take a frame of their own rather than the handler's
+     */
+    #[inline(never)]
+    #[allow(clippy::too_many_arguments)]
+    fn open_position_cpi(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_array_lower_start_index: i32,
+        tick_array_upper_start_index: i32,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+        with_metadata: bool,
+        base_flag: Option<bool>,
+    ) -> Result<()> {
+        // Raydium only touches the metadata account when creating metadata,
+        // so the NFT mint stands in for an omitted one
+        let metadata_account = self.metadata_account.as_ref().map_or_else(
+            || self.position_nft_mint.to_account_info(),
+            |metadata_account| metadata_account.to_account_info(),
+        );
+
+        // Build CPI accounts
+        let cpi_accounts = cpi::accounts::OpenPositionV2 {
+            payer: self.payer.to_account_info(),
+            position_nft_owner: self.position_nft_owner.to_account_info(),
+            position_nft_mint: self.position_nft_mint.to_account_info(),
+            position_nft_account: self.position_nft_account.to_account_info(),
+            metadata_account,
+            pool_state: self.pool_state.to_account_info(),
+            protocol_position: self.protocol_position.to_account_info(),
+            tick_array_lower: self.tick_array_lower.to_account_info(),
+            tick_array_upper: self.tick_array_upper.to_account_info(),
+            personal_position: self.personal_position.to_account_info(),
+            token_account_0: self.token_account_0.to_account_info(),
+            token_account_1: self.token_account_1.to_account_info(),
+            token_vault_0: self.token_vault_0.to_account_info(),
+            token_vault_1: self.token_vault_1.to_account_info(),
+            rent: self.programs.rent.to_account_info(),
+            system_program: self.programs.system_program.to_account_info(),
+            token_program: self.programs.token_program.to_account_info(),
+            associated_token_program: self.programs.associated_token_program.to_account_info(),
+            metadata_program: self.programs.metadata_program.to_account_info(),
+            token_program_2022: self.programs.token_program_2022.to_account_info(),
+            vault_0_mint: self.vault_0_mint.to_account_info(),
+            vault_1_mint: self.vault_1_mint.to_account_info(),
+        };
+
+        // Execute CPI
+        invoke_clmm(
+            &self.clmm_program,
+            cpi_accounts,
+            self.tick_array_bitmap_extension.as_ref(),
+            remaining_accounts,
+            &clmm_instruction::OpenPositionV2 {
+                tick_lower_index,
+                tick_upper_index,
+                tick_array_lower_start_index,
+                tick_array_upper_start_index,
+                liquidity,
+                amount_0_max,
+                amount_1_max,
+                with_metadata,
+                base_flag,
+            },
+            &[],
+        )
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn proxy_open_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ProxyOpenPosition<'info>>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    tick_array_lower_start_index: i32,
+    tick_array_upper_start_index: i32,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    with_matedata: bool,
+    simulate_only: bool,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+
+    require!(
+        tick_lower_index < tick_upper_index,
+        CustomError::InvalidTickRange
+    );
+    require!(
+        amount_0_max > 0 || amount_1_max > 0,
+        CustomError::ZeroDeposit
+    );
+    load_or_init(&ctx.accounts.user_cfg)?
+        .check_caller(ctx.accounts.instructions_sysvar.as_ref())?;
+    // Positions for someone else need their say, so nobody can be handed
+    // positions (and the accounts that come with them) unasked
+    let owner = ctx.accounts.position_nft_owner.key();
+    require!(
+        owner == ctx.accounts.payer.key()
+            || ctx.accounts.position_nft_owner.is_signer
+            || ctx.accounts.open_approval.is_some(),
+        CustomError::OpenNotApproved
+    );
+
+    let (sqrt_price_x64, tick_current) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.sqrt_price_x64, pool_state.tick_current)
+    };
+
+    // Zero liquidity deposits by amounts: Raydium sizes the liquidity from
+    // whichever side constrains the deposit, net of any transfer fee
+    let base_flag = if liquidity == 0 {
+        require!(
+            liquidity_for_amounts(
+                sqrt_price_x64,
+                tick_lower_index,
+                tick_upper_index,
+                amount_0_max,
+                amount_1_max,
+            )? > 0,
+            CustomError::ZeroLiquidity
+        );
+        let base_flag = math::base_flag_for_amounts(
+            sqrt_price_x64,
+            tick_lower_index,
+            tick_upper_index,
+            amount_0_max,
+            amount_1_max,
+        )?;
+        Some(base_flag)
+    } else {
+        None
+    };
+
+    // Cap the maxima at what the liquidity costs at the current price
+    // plus the user's slippage tolerance, the same bound swaps use
+    let bps = load_or_init(&ctx.accounts.user_cfg)?.effective_slippage_bps();
+    require!(bps > 0 && bps <= 500, CustomError::InvalidSlippage);
+    let (amount_0_max, amount_1_max) = if base_flag.is_none() {
+        let (amount_0, amount_1) = liquidity_math::get_delta_amounts_signed(
+            tick_current,
+            sqrt_price_x64,
+            tick_lower_index,
+            tick_upper_index,
+            i128::try_from(liquidity).map_err(|_| CustomError::MathOverflow)?,
+        )?;
+        let now = Clock::get()?.unix_timestamp;
+        (
+            amount_0_max.min(token_extensions::slippage_threshold(
+                &ctx.accounts.vault_0_mint.to_account_info(),
+                amount_0,
+                bps,
+                false,
+                now,
+            )?),
+            amount_1_max.min(token_extensions::slippage_threshold(
+                &ctx.accounts.vault_1_mint.to_account_info(),
+                amount_1,
+                bps,
+                false,
+                now,
+            )?),
+        )
+    } else {
+        (amount_0_max, amount_1_max)
+    };
+
+    token_extensions::require_allowed_extensions(
+        &[
+            ctx.accounts.vault_0_mint.to_account_info(),
+            ctx.accounts.vault_1_mint.to_account_info(),
+        ],
+        ctx.accounts.mint_extension_opt_in.as_deref(),
+    )?;
+
+    require!(
+        !with_matedata || ctx.accounts.metadata_account.is_some(),
+        CustomError::MetadataAccountRequired
+    );
+
+    let balance_0_before = ctx.accounts.token_account_0.amount;
+    let balance_1_before = ctx.accounts.token_account_1.amount;
+
+    ctx.accounts.open_position_cpi(
+        ctx.remaining_accounts,
+        tick_lower_index,
+        tick_upper_index,
+        tick_array_lower_start_index,
+        tick_array_upper_start_index,
+        liquidity,
+        amount_0_max,
+        amount_1_max,
+        with_matedata,
+        base_flag,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.token_account_0.reload()?;
+    ctx.accounts.token_account_1.reload()?;
+    let amount_0 = balance_0_before.saturating_sub(ctx.accounts.token_account_0.amount);
+    let amount_1 = balance_1_before.saturating_sub(ctx.accounts.token_account_1.amount);
+    let liquidity = {
+        let data = ctx.accounts.personal_position.try_borrow_data()?;
+        PersonalPositionState::try_deserialize(&mut &data[..])?.liquidity
+    };
+
+    if let Some(cost_basis) = ctx.accounts.cost_basis.as_mut() {
+        let sqrt_price_x64 = ctx.accounts.pool_state.load()?.sqrt_price_x64;
+        cost_basis.position_nft = ctx.accounts.position_nft_mint.key();
+        cost_basis.pool = ctx.accounts.pool_state.key();
+        cost_basis.open_sqrt_price_x64 = sqrt_price_x64;
+        cost_basis.opened_at = now;
+        cost_basis.record_deposit(amount_0, amount_1, sqrt_price_x64);
+    }
+
+    let mut user_cfg = load_or_init(&ctx.accounts.user_cfg)?;
+    if user_cfg.owner == Pubkey::default() {
+        user_cfg.owner = ctx.accounts.payer.key();
+    }
+    user_cfg.positions_opened = user_cfg.positions_opened.saturating_add(1);
+    user_cfg.last_activity_ts = now;
+    track_exposure(
+        &mut user_cfg,
+        ctx.accounts.pool_exposure.as_deref_mut(),
+        &ctx.accounts.pool_state.key(),
+        &*ctx.accounts.pool_state.load()?,
+        amount_0,
+        amount_1,
+        true,
+    )?;
+    if let Some(pool_stats) = ctx.accounts.pool_stats.as_ref() {
+        let mut pool_stats = pool_stats.load_mut()?;
+        pool_stats.positions_opened = pool_stats.positions_opened.saturating_add(1);
+        pool_stats.last_update_ts = now;
+    }
+    if let Some(global_stats) = ctx.accounts.global_stats.as_ref() {
+        let mut global_stats = global_stats.load_mut()?;
+        global_stats.positions_opened = global_stats.positions_opened.saturating_add(1);
+        global_stats.last_update_ts = now;
+    }
+    match (
+        ctx.accounts.position_registry.as_mut(),
+        ctx.accounts.position_registry_page.as_mut(),
+    ) {
+        (Some(registry), Some(page)) => {
+            require!(
+                page.page.checked_add(1) == Some(registry.page_count),
+                CustomError::RegistryPageNotLast
+            );
+            require!(!page.is_full(), CustomError::RegistryPageFull);
+            page.positions.push(ctx.accounts.position_nft_mint.key());
+            registry.position_count = registry.position_count.saturating_add(1);
+        }
+        (None, None) => {}
+        _ => return err!(CustomError::RegistryAccountsMismatch),
+    }
+    let user_seq = user_cfg.next_event_seq();
+
+    let (ui_amount_0, ui_amount_1) = token_extensions::ui_amounts(
+        &ctx.accounts.vault_0_mint.to_account_info(),
+        &ctx.accounts.vault_1_mint.to_account_info(),
+        amount_0,
+        amount_1,
+        now,
+    )?;
+    emit_event!(
+        ctx,
+        PositionOpened {
+            user: ctx.accounts.payer.key(),
+            pool: ctx.accounts.pool_state.key(),
+            position_nft: ctx.accounts.position_nft_mint.key(),
+            tick_lower: tick_lower_index,
+            tick_upper: tick_upper_index,
+            liquidity,
+            amount_0,
+            amount_1,
+            timestamp: now,
+            owner,
+            user_seq,
+            ui_amount_0,
+            ui_amount_1,
+        }
+    );
+
+    if simulate_only {
+        return finish_simulation(&LiquiditySimulation {
+            liquidity,
+            amount_0,
+            amount_1,
+            fees_0: 0,
+            fees_1: 0,
+            ui_amount_0,
+            ui_amount_1,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn proxy_open_full_range_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ProxyOpenPosition<'info>>,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    with_metadata: bool,
+    simulate_only: bool,
+) -> Result<()> {
+    let tick_spacing = ctx.accounts.pool_state.load()?.tick_spacing;
+    let (tick_lower_index, tick_upper_index) = math::full_range_ticks(tick_spacing);
+
+    proxy_open_position(
+        ctx,
+        tick_lower_index,
+        tick_upper_index,
+        math::tick_array_start_index(tick_lower_index, tick_spacing),
+        math::tick_array_start_index(tick_upper_index, tick_spacing),
+        liquidity,
+        amount_0_max,
+        amount_1_max,
+        with_metadata,
+        simulate_only,
+    )
+}
+
+pub fn proxy_open_position_around_price<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ProxyOpenPosition<'info>>,
+    range_bps: u16,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+    with_metadata: bool,
+    simulate_only: bool,
+) -> Result<()> {
+    let (sqrt_price_x64, tick_spacing) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.sqrt_price_x64, pool_state.tick_spacing)
+    };
+    let (tick_lower_index, tick_upper_index) =
+        math::ticks_around_price(sqrt_price_x64, range_bps, tick_spacing)?;
+
+    proxy_open_position(
+        ctx,
+        tick_lower_index,
+        tick_upper_index,
+        math::tick_array_start_index(tick_lower_index, tick_spacing),
+        math::tick_array_start_index(tick_upper_index, tick_spacing),
+        liquidity,
+        amount_0_max,
+        amount_1_max,
+        with_metadata,
+        simulate_only,
+    )
+}
+
+pub fn approve_open_on_behalf(ctx: Context<ApproveOpenOnBehalf>) -> Result<()> {
+    let approval = &mut ctx.accounts.open_approval;
+    approval.owner = ctx.accounts.owner.key();
+    approval.delegate = ctx.accounts.delegate.key();
+
+    emit_event!(
+        ctx,
+        OpenApprovalSet {
+            owner: ctx.accounts.owner.key(),
+            delegate: ctx.accounts.delegate.key(),
+            approved: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn revoke_open_on_behalf(ctx: Context<RevokeOpenOnBehalf>) -> Result<()> {
+    emit_event!(
+        ctx,
+        OpenApprovalSet {
+            owner: ctx.accounts.owner.key(),
+            delegate: ctx.accounts.open_approval.delegate,
+            approved: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
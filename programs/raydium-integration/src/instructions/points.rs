@@ -0,0 +1,124 @@
+//! Points rates, ledgers and claims.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetPointsRates<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + PointsConfig::SIZE,
+        seeds = [b"points_config"],
+        bump
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitPointsLedger<'info> {
+    /// CHECK: Any wallet or PDA; only used as a seed
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PointsLedger::SIZE,
+        seeds = [b"points", owner.key().as_ref()],
+        bump
+    )]
+    pub points_ledger: Account<'info, PointsLedger>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitPositionPoints<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PositionPoints::SIZE,
+        seeds = [b"position_points", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub position_points: Account<'info, PositionPoints>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimPoints<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"points", owner.key().as_ref()],
+        bump,
+        has_one = owner
+    )]
+    pub points_ledger: Account<'info, PointsLedger>,
+}
+
+pub fn set_points_rates(
+    ctx: Context<SetPointsRates>,
+    swap_points_per_unit: u64,
+    lp_points_per_unit: u64,
+) -> Result<()> {
+    let points_config = &mut ctx.accounts.points_config;
+    points_config.swap_points_per_unit = swap_points_per_unit;
+    points_config.lp_points_per_unit = lp_points_per_unit;
+
+    emit_event!(
+        ctx,
+        PointsRatesSet {
+            swap_points_per_unit,
+            lp_points_per_unit,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn init_points_ledger(ctx: Context<InitPointsLedger>) -> Result<()> {
+    ctx.accounts.points_ledger.owner = ctx.accounts.owner.key();
+    Ok(())
+}
+
+pub fn init_position_points(ctx: Context<InitPositionPoints>) -> Result<()> {
+    let position_points = &mut ctx.accounts.position_points;
+    position_points.position_nft = ctx.accounts.personal_position.nft_mint;
+    position_points.last_accrual_ts = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+pub fn claim_points(ctx: Context<ClaimPoints>) -> Result<()> {
+    let points_ledger = &mut ctx.accounts.points_ledger;
+    let amount = points_ledger.unclaimed();
+    require!(amount > 0, CustomError::NoPointsToClaim);
+    points_ledger.claimed = points_ledger.claimed.saturating_add(amount);
+    let total_claimed = points_ledger.claimed;
+
+    emit_event!(
+        ctx,
+        PointsClaimed {
+            owner: ctx.accounts.owner.key(),
+            amount,
+            total_claimed,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    write_return_data(&PointsClaim {
+        amount,
+        total_claimed,
+    });
+
+    Ok(())
+}
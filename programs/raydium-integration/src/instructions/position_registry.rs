@@ -0,0 +1,78 @@
+//! Per-owner position registry.
+use crate::contexts::*;
+
+#[derive(Accounts)]
+pub struct InitPositionRegistry<'info> {
+    /// CHECK: Any wallet or PDA; only used as a seed
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + UserPositionRegistry::SIZE,
+        seeds = [b"position_registry", owner.key().as_ref()],
+        bump
+    )]
+    pub position_registry: Account<'info, UserPositionRegistry>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PositionRegistryPage::SIZE,
+        seeds = [b"position_registry", owner.key().as_ref(), &0u32.to_le_bytes()],
+        bump
+    )]
+    pub position_registry_page: Account<'info, PositionRegistryPage>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddPositionRegistryPage<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"position_registry", position_registry.owner.as_ref()],
+        bump
+    )]
+    pub position_registry: Account<'info, UserPositionRegistry>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PositionRegistryPage::SIZE,
+        seeds = [
+            b"position_registry",
+            position_registry.owner.as_ref(),
+            &position_registry.page_count.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub position_registry_page: Account<'info, PositionRegistryPage>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_position_registry(ctx: Context<InitPositionRegistry>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let registry = &mut ctx.accounts.position_registry;
+    registry.owner = owner;
+    registry.page_count = 1;
+
+    let page = &mut ctx.accounts.position_registry_page;
+    page.owner = owner;
+    page.page = 0;
+
+    Ok(())
+}
+
+pub fn add_position_registry_page(ctx: Context<AddPositionRegistryPage>) -> Result<()> {
+    let registry = &mut ctx.accounts.position_registry;
+    let page = &mut ctx.accounts.position_registry_page;
+    page.owner = registry.owner;
+    page.page = registry.page_count;
+    registry.page_count = registry
+        .page_count
+        .checked_add(1)
+        .ok_or(CustomError::MathOverflow)?;
+
+    Ok(())
+}
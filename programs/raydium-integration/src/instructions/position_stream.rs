@@ -0,0 +1,368 @@
+//! Linearly unlocking position streams.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CreatePositionStream<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Any account; only it can claim the stream
+    pub beneficiary: UncheckedAccount<'info>,
+
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = owner
+    )]
+    pub owner_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(constraint = personal_position.nft_mint == position_nft_mint.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + PositionStream::SIZE,
+        seeds = [b"position_stream", position_nft_mint.key().as_ref()],
+        bump
+    )]
+    pub position_stream: Account<'info, PositionStream>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = position_nft_mint,
+        associated_token::authority = position_stream,
+        associated_token::token_program = nft_token_program
+    )]
+    pub stream_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The owner's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimUnlocked<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = beneficiary,
+        seeds = [b"position_stream", position_stream.position_nft.as_ref()],
+        bump
+    )]
+    pub position_stream: Account<'info, PositionStream>,
+
+    #[account(address = position_stream.position_nft)]
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = position_nft_mint,
+        associated_token::authority = position_stream,
+        associated_token::token_program = nft_token_program
+    )]
+    pub stream_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives the NFT once the stream is fully claimed
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = beneficiary
+    )]
+    pub beneficiary_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = personal_position.nft_mint == position_stream.position_nft
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut, address = position_stream.pool)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint
+    )]
+    pub recipient_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint
+    )]
+    pub recipient_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Raydium checks the memo program address
+    pub memo_program: UncheckedAccount<'info>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Not checked for the pause flags: exits stay open while the program is
+    /// paused
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The beneficiary's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", beneficiary.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the beneficiary restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The beneficiary's `DestinationAllowlist` PDA, read when
+    /// initialized
+    #[account(seeds = [b"destination_allowlist", beneficiary.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+}
+
+pub fn create_position_stream(
+    ctx: Context<CreatePositionStream>,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+
+    require!(start_ts < end_ts, CustomError::InvalidStreamSchedule);
+    let initial_liquidity = ctx.accounts.personal_position.liquidity;
+    require!(initial_liquidity > 0, CustomError::ZeroLiquidity);
+
+    let stream = &mut ctx.accounts.position_stream;
+    stream.owner = ctx.accounts.owner.key();
+    stream.beneficiary = ctx.accounts.beneficiary.key();
+    stream.position_nft = ctx.accounts.position_nft_mint.key();
+    stream.pool = ctx.accounts.personal_position.pool_id;
+    stream.start_ts = start_ts;
+    stream.end_ts = end_ts;
+    stream.initial_liquidity = initial_liquidity;
+    stream.claimed_liquidity = 0;
+
+    // Move the NFT into the stream's custody
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.owner_nft_account.to_account_info(),
+        mint: ctx.accounts.position_nft_mint.to_account_info(),
+        to: ctx.accounts.stream_nft_account.to_account_info(),
+        authority: ctx.accounts.owner.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(
+        ctx.accounts.nft_token_program.to_account_info(),
+        cpi_accounts,
+    );
+    token_interface::transfer_checked(cpi_context, 1, 0)?;
+
+    emit_event!(
+        ctx,
+        PositionStreamCreated {
+            owner: ctx.accounts.owner.key(),
+            beneficiary: ctx.accounts.beneficiary.key(),
+            position_nft: ctx.accounts.position_nft_mint.key(),
+            pool: ctx.accounts.personal_position.pool_id,
+            liquidity: initial_liquidity,
+            start_ts,
+            end_ts,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn claim_unlocked<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ClaimUnlocked<'info>>,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> Result<()> {
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[
+            ctx.accounts.recipient_token_account_0.key(),
+            ctx.accounts.recipient_token_account_1.key(),
+        ],
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let stream = &ctx.accounts.position_stream;
+    let liquidity = stream
+        .unlocked_liquidity(now)
+        .saturating_sub(stream.claimed_liquidity);
+    require!(liquidity > 0, CustomError::NothingToClaim);
+
+    let position_nft = stream.position_nft;
+    let signer_seeds: &[&[u8]] = &[
+        b"position_stream",
+        position_nft.as_ref(),
+        &[ctx.bumps.position_stream],
+    ];
+    let balance_0_before = ctx.accounts.recipient_token_account_0.amount;
+    let balance_1_before = ctx.accounts.recipient_token_account_1.amount;
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::DecreaseLiquidityV2 {
+        nft_owner: ctx.accounts.position_stream.to_account_info(),
+        nft_account: ctx.accounts.stream_nft_account.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        recipient_token_account_0: ctx.accounts.recipient_token_account_0.to_account_info(),
+        recipient_token_account_1: ctx.accounts.recipient_token_account_1.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        memo_program: ctx.accounts.memo_program.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+    };
+
+    let signer = &[signer_seeds];
+
+    // Execute CPI
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        None,
+        ctx.remaining_accounts,
+        &clmm_instruction::DecreaseLiquidityV2 {
+            liquidity,
+            amount_0_min,
+            amount_1_min,
+        },
+        signer,
+    )?;
+
+    ctx.accounts.recipient_token_account_0.reload()?;
+    ctx.accounts.recipient_token_account_1.reload()?;
+    let amount_0 = ctx
+        .accounts
+        .recipient_token_account_0
+        .amount
+        .saturating_sub(balance_0_before);
+    let amount_1 = ctx
+        .accounts
+        .recipient_token_account_1
+        .amount
+        .saturating_sub(balance_1_before);
+    let stream = &mut ctx.accounts.position_stream;
+    stream.claimed_liquidity = stream.claimed_liquidity.saturating_add(liquidity);
+    let claimed_liquidity = stream.claimed_liquidity;
+    let fully_claimed = claimed_liquidity >= stream.initial_liquidity;
+
+    emit_event!(
+        ctx,
+        StreamClaimed {
+            beneficiary: ctx.accounts.beneficiary.key(),
+            position_nft,
+            liquidity,
+            amount_0,
+            amount_1,
+            claimed_liquidity,
+            timestamp: now,
+        }
+    );
+
+    if !fully_claimed {
+        return Ok(());
+    }
+
+    // Fully vested: hand the emptied NFT to the beneficiary
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.stream_nft_account.to_account_info(),
+        mint: ctx.accounts.position_nft_mint.to_account_info(),
+        to: ctx.accounts.beneficiary_nft_account.to_account_info(),
+        authority: ctx.accounts.position_stream.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.nft_token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::transfer_checked(cpi_context, 1, 0)?;
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.stream_nft_account.to_account_info(),
+        destination: ctx.accounts.beneficiary.to_account_info(),
+        authority: ctx.accounts.position_stream.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.nft_token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token_interface::close_account(cpi_context)?;
+
+    ctx.accounts
+        .position_stream
+        .close(ctx.accounts.beneficiary.to_account_info())
+}
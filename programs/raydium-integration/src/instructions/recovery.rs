@@ -0,0 +1,210 @@
+//! Guardians and owner recovery.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RecoveryConfig::SIZE,
+        seeds = [b"recovery", owner.key().as_ref()],
+        bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    pub guardian: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"recovery", recovery_config.owner.as_ref()],
+        bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"recovery", owner.key().as_ref()],
+        bump,
+        has_one = owner
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"recovery", recovery_config.owner.as_ref()],
+        bump,
+        close = new_owner
+    )]
+    pub recovery_config: Box<Account<'info, RecoveryConfig>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_cfg", recovery_config.owner.as_ref()],
+        bump,
+        close = new_owner,
+        constraint = UserConfig::is_current(&user_cfg.to_account_info()) @ CustomError::UserConfigMigrationRequired
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+
+    /// CHECK: The approved new owner, credited with the closed accounts' rent
+    #[account(
+        mut,
+        address = recovery_config.pending_owner @ CustomError::InvalidRecoveryOwner
+    )]
+    pub new_owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + UserConfig::SIZE,
+        seeds = [b"user_cfg", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_user_cfg: AccountLoader<'info, UserConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RecoveryConfig::SIZE,
+        seeds = [b"recovery", new_owner.key().as_ref()],
+        bump
+    )]
+    pub new_recovery_config: Box<Account<'info, RecoveryConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_guardians(
+    ctx: Context<SetGuardians>,
+    guardians: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    RecoveryConfig::validate_guardians(&guardians, threshold)?;
+    let recovery = &mut ctx.accounts.recovery_config;
+    recovery.owner = ctx.accounts.owner.key();
+    recovery.guardians = [Pubkey::default(); MAX_GUARDIANS];
+    recovery.guardians[..guardians.len()].copy_from_slice(&guardians);
+    recovery.guardian_count = guardians.len() as u8;
+    recovery.threshold = threshold;
+    recovery.clear_pending();
+
+    emit_event!(
+        ctx,
+        GuardiansSet {
+            owner: ctx.accounts.owner.key(),
+            guardians,
+            threshold,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn approve_recovery(ctx: Context<ApproveRecovery>, new_owner: Pubkey) -> Result<()> {
+    require!(
+        new_owner != Pubkey::default(),
+        CustomError::InvalidRecoveryOwner
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let guardian = ctx.accounts.guardian.key();
+    let recovery = &mut ctx.accounts.recovery_config;
+    let index = recovery
+        .guardian_index(&guardian)
+        .ok_or(CustomError::NotGuardian)?;
+    if recovery.pending_owner != new_owner {
+        recovery.clear_pending();
+        recovery.pending_owner = new_owner;
+    }
+    recovery.approve(index, now);
+    let owner = recovery.owner;
+    let approvals = recovery.approvals.count_ones() as u8;
+    let ready_at = recovery.ready_at;
+
+    emit_event!(
+        ctx,
+        RecoveryApproved {
+            owner,
+            new_owner,
+            guardian,
+            approvals,
+            ready_at,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+    let recovery = &mut ctx.accounts.recovery_config;
+    require!(
+        recovery.pending_owner != Pubkey::default(),
+        CustomError::NoRecoveryPending
+    );
+    recovery.clear_pending();
+
+    emit_event!(
+        ctx,
+        RecoveryCancelled {
+            owner: ctx.accounts.owner.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut recovery = RecoveryConfig::clone(&ctx.accounts.recovery_config);
+    require!(
+        recovery.ready_at != 0 && now >= recovery.ready_at,
+        CustomError::RecoveryNotReady
+    );
+    let old_owner = recovery.owner;
+    let new_owner = ctx.accounts.new_owner.key();
+
+    let mut user_cfg = *ctx.accounts.user_cfg.load()?;
+    user_cfg.owner = new_owner;
+    user_cfg.last_activity_ts = now;
+    // The new owner's sequence carries on from the old one's
+    let user_seq = user_cfg.next_event_seq();
+    *ctx.accounts.new_user_cfg.load_init()? = user_cfg;
+
+    recovery.owner = new_owner;
+    recovery.clear_pending();
+    ctx.accounts.new_recovery_config.set_inner(recovery);
+
+    emit_event!(
+        ctx,
+        OwnerRecovered {
+            old_owner,
+            new_owner,
+            timestamp: now,
+            user_seq,
+        }
+    );
+
+    Ok(())
+}
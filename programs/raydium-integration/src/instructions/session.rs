@@ -0,0 +1,632 @@
+//! Session keys and the instructions they can sign.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"user_cfg", owner.key().as_ref()],
+        bump,
+        constraint = UserConfig::is_current(&user_cfg.to_account_info()) @ CustomError::UserConfigMigrationRequired
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Session::SIZE,
+        seeds = [b"session", user_cfg.key().as_ref(), session_key.as_ref()],
+        bump
+    )]
+    pub session: Account<'info, Session>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    /// The owner or the session key
+    #[account(
+        constraint = authority.key() == session.owner
+            || authority.key() == session.session_key @ CustomError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    /// CHECK: Session owner, credited with the session's rent
+    #[account(mut, address = session.owner)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut, close = owner)]
+    pub session: Account<'info, Session>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetSessionLimits<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut, has_one = owner)]
+    pub session: Account<'info, Session>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SessionSwap<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    pub session_key: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", user_cfg.key().as_ref(), session_key.key().as_ref()],
+        bump,
+        has_one = session_key
+    )]
+    pub session: Box<Account<'info, Session>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_cfg", session.owner.as_ref()],
+        bump,
+        constraint = UserConfig::is_current(&user_cfg.to_account_info()) @ CustomError::UserConfigMigrationRequired
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+
+    /// CHECK: PDA the owner approves as delegate of the input token account
+    #[account(seeds = [b"session_authority", session.owner.as_ref()], bump)]
+    pub session_authority: UncheckedAccount<'info>,
+
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(mut, token::authority = session.owner)]
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only the owner's own accounts can receive the output
+    #[account(mut, token::authority = session.owner)]
+    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = input_vault.mint)]
+    pub input_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = output_vault.mint)]
+    pub output_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(mut, constraint = tick_array.load()?.pool_id == pool_state.key())]
+    pub tick_array: AccountLoader<'info, TickArrayState>,
+
+    #[account(mut, seeds = [b"pool_stats", pool_state.key().as_ref()], bump)]
+    pub pool_stats: Option<AccountLoader<'info, PoolStats>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump)]
+    pub global_stats: Option<AccountLoader<'info, GlobalStats>>,
+
+    /// CHECK: Raydium's tick array bitmap extension for the pool
+    #[account(
+        seeds = [POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub tick_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Pause flags, protocol fee and round-trip policy. The three accounts
+    /// below are required unless the fee is zero
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// The input mint; session swaps skim the fee from the input
+    #[account(address = input_token_account.mint)]
+    pub fee_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Receives the protocol fee net of the insurance share; owned by
+    /// `global_config.treasury`
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Insurance fund vault of `fee_mint`
+    #[account(mut)]
+    pub insurance_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: The owner's `RoundTripCounter` PDA for the pool, always
+    /// passed; must be initialized while governance tracks round trips
+    #[account(
+        mut,
+        seeds = [b"round_trips", session.owner.as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub round_trip_counter: UncheckedAccount<'info>,
+
+    /// Pays the pool's creator a share of the protocol fee when the pool was
+    /// created through the proxy; `creator_fee_vault` is then required
+    #[account(seeds = [b"pool_creator", pool_state.key().as_ref()], bump)]
+    pub pool_creator_record: Option<Box<Account<'info, PoolCreatorRecord>>>,
+
+    /// Token account of `pool_creator_record` in `fee_mint`
+    #[account(mut)]
+    pub creator_fee_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: `SwapValidatorRegistry` PDA; may be uninitialized
+    #[account(seeds = [b"swap_validators"], bump)]
+    pub swap_validators: UncheckedAccount<'info>,
+
+    /// CHECK: The owner's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", session.owner.as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+
+    /// Risky Token-2022 extensions the owner accepts on the swapped mints
+    #[account(seeds = [b"extension_opt_in", session.owner.as_ref()], bump)]
+    pub mint_extension_opt_in: Option<Account<'info, MintExtensionOptIn>>,
+}
+
+impl<'info> SessionSwap<'info> {
+    /*
+     * Skim a protocol fee of `fee` input units from the owner's input
+     * account through the session authority. Returns its insurance and
+     * creator shares
+     */
+    fn collect_protocol_fee(
+        &self,
+        fee: u64,
+        signer_seeds: &[&[&[u8]]],
+        flows: &mut FlowCheck<'info>,
+    ) -> Result<(u64, u64)> {
+        let required = || error!(CustomError::ProtocolFeeAccountsRequired);
+        collect_protocol_fee(
+            ProtocolFeeAccounts {
+                global_config: &self.global_config,
+                fee_mint: self.fee_mint.as_deref().ok_or_else(required)?,
+                from: &self.input_token_account,
+                authority: self.session_authority.to_account_info(),
+                treasury_token_account: self
+                    .treasury_token_account
+                    .as_deref()
+                    .ok_or_else(required)?,
+                insurance_vault: self.insurance_vault.as_deref().ok_or_else(required)?,
+                pool_creator_record: self.pool_creator_record.as_deref(),
+                creator_fee_vault: self.creator_fee_vault.as_deref(),
+                token_program: self.token_program.to_account_info(),
+            },
+            fee,
+            signer_seeds,
+            flows,
+        )
+    }
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SessionSetSlippage<'info> {
+    pub session_key: Signer<'info>,
+
+    #[account(
+        seeds = [b"session", user_cfg.key().as_ref(), session_key.key().as_ref()],
+        bump,
+        has_one = session_key
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"user_cfg", session.owner.as_ref()],
+        bump,
+        constraint = UserConfig::is_current(&user_cfg.to_account_info()) @ CustomError::UserConfigMigrationRequired
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+}
+
+pub fn create_session(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    expiry: i64,
+    scope: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        expiry > now && expiry - now <= MAX_SESSION_DURATION,
+        CustomError::InvalidSessionExpiry
+    );
+    require!(
+        scope != 0 && scope & !ALL_SESSION_SCOPES == 0,
+        CustomError::InvalidSessionScope
+    );
+    let session = &mut ctx.accounts.session;
+    session.owner = ctx.accounts.owner.key();
+    session.session_key = session_key;
+    session.expiry = expiry;
+    session.scope = scope;
+
+    emit_event!(
+        ctx,
+        SessionCreated {
+            owner: ctx.accounts.owner.key(),
+            session_key,
+            expiry,
+            scope,
+            timestamp: now,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+    emit_event!(
+        ctx,
+        SessionRevoked {
+            owner: ctx.accounts.session.owner,
+            session_key: ctx.accounts.session.session_key,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn set_session_limits(
+    ctx: Context<SetSessionLimits>,
+    allowed_pools: Vec<Pubkey>,
+    direction: u8,
+    spend_limits: Vec<SessionSpendLimit>,
+) -> Result<()> {
+    require!(
+        allowed_pools.len() <= MAX_SESSION_POOLS,
+        CustomError::TooManySessionPools
+    );
+    require!(
+        direction <= SESSION_DIRECTION_ONE_FOR_ZERO,
+        CustomError::InvalidSessionDirection
+    );
+    let session = &mut ctx.accounts.session;
+    session.allowed_pools = [Pubkey::default(); MAX_SESSION_POOLS];
+    session.allowed_pools[..allowed_pools.len()].copy_from_slice(&allowed_pools);
+    session.pool_count = allowed_pools.len() as u8;
+    session.direction = direction;
+    session.set_spend_limits(&spend_limits)?;
+    let session_key = session.session_key;
+
+    emit_event!(
+        ctx,
+        SessionLimitsSet {
+            owner: ctx.accounts.owner.key(),
+            session_key,
+            allowed_pools,
+            direction,
+            spend_limits,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn session_swap<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SessionSwap<'info>>,
+    amount: u64,
+    expected_other_amount: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+) -> Result<()> {
+    require!(amount > 0, CustomError::ZeroSwapAmount);
+    require!(
+        expected_other_amount > 0,
+        CustomError::InvalidExpectedAmount
+    );
+    let owner = ctx.accounts.session.owner;
+    ctx.accounts
+        .global_config
+        .require_active(FLAG_PAUSE_SWAPS, "swaps")?;
+    ctx.accounts
+        .session
+        .authorize(SESSION_SCOPE_SWAP, Clock::get()?.unix_timestamp)?;
+    ctx.accounts
+        .user_cfg
+        .load()?
+        .check_caller(ctx.accounts.instructions_sysvar.as_ref())?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[ctx.accounts.output_token_account.key()],
+    )?;
+    token_extensions::require_allowed_extensions(
+        &[
+            ctx.accounts.input_vault_mint.to_account_info(),
+            ctx.accounts.output_vault_mint.to_account_info(),
+        ],
+        ctx.accounts.mint_extension_opt_in.as_deref(),
+    )?;
+
+    let validator_count = run_swap_validators(
+        &ctx.accounts.swap_validators,
+        [
+            ctx.accounts.session_key.to_account_info(),
+            ctx.accounts.pool_state.to_account_info(),
+            ctx.accounts.input_token_account.to_account_info(),
+            ctx.accounts.output_token_account.to_account_info(),
+        ],
+        ctx.remaining_accounts,
+        &SwapValidation {
+            user: owner,
+            pool: ctx.accounts.pool_state.key(),
+            input_mint: ctx.accounts.input_vault.mint,
+            output_mint: ctx.accounts.output_vault.mint,
+            amount,
+            expected_other_amount,
+            is_base_input,
+        },
+    )?;
+    let remaining_accounts = &ctx.remaining_accounts[validator_count..];
+
+    let require_user_slippage = ctx.accounts.global_config.requires_user_slippage();
+    let (bps, default_slippage) = ctx
+        .accounts
+        .user_cfg
+        .load()?
+        .swap_slippage_bps(require_user_slippage)?;
+    // As in `proxy_swap`, exact-input swaps leave room in `amount` for the
+    // protocol fee skimmed from the input
+    let protocol_fee_bps = ctx.accounts.global_config.protocol_fee_bps;
+    let swap_amount = if is_base_input {
+        math::amount_before_protocol_fee(amount, protocol_fee_bps)
+    } else {
+        amount
+    };
+    // The bound applies to what the user's wallet shows of the other token
+    let threshold_mint = if is_base_input {
+        ctx.accounts.output_vault_mint.to_account_info()
+    } else {
+        ctx.accounts.input_vault_mint.to_account_info()
+    };
+    let threshold = prorate_threshold(
+        token_extensions::slippage_threshold(
+            &threshold_mint,
+            expected_other_amount,
+            bps,
+            is_base_input,
+            Clock::get()?.unix_timestamp,
+        )?,
+        swap_amount,
+        amount,
+        is_base_input,
+    );
+    // The owner's SPL approval is what lets the session spend: fail clearly
+    // when it is missing or too small for the most this swap can spend,
+    // protocol fee included
+    let max_spend = if is_base_input {
+        amount
+    } else {
+        threshold.saturating_add(compute_protocol_fee(threshold, protocol_fee_bps))
+    };
+    let input = &ctx.accounts.input_token_account;
+    require!(
+        Option::<Pubkey>::from(input.delegate) == Some(ctx.accounts.session_authority.key())
+            && input.delegated_amount >= max_spend,
+        CustomError::SessionDelegateNotApproved
+    );
+
+    let (sqrt_price_before_x64, zero_for_one) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (
+            pool_state.sqrt_price_x64,
+            swap_direction(
+                &pool_state,
+                &ctx.accounts.input_token_account.mint,
+                &ctx.accounts.input_vault.key(),
+                &ctx.accounts.output_vault.key(),
+            )?,
+        )
+    };
+    ctx.accounts
+        .session
+        .check_swap(&ctx.accounts.pool_state.key(), zero_for_one)?;
+    let input_balance_before = ctx.accounts.input_token_account.amount;
+    let output_balance_before = ctx.accounts.output_token_account.amount;
+    let mut flows = FlowCheck::default();
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::SwapSingle {
+        payer: ctx.accounts.session_authority.to_account_info(),
+        amm_config: ctx.accounts.amm_config.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        input_token_account: ctx.accounts.input_token_account.to_account_info(),
+        output_token_account: ctx.accounts.output_token_account.to_account_info(),
+        input_vault: ctx.accounts.input_vault.to_account_info(),
+        output_vault: ctx.accounts.output_vault.to_account_info(),
+        observation_state: ctx.accounts.observation_state.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        tick_array: ctx.accounts.tick_array.to_account_info(),
+    };
+
+    let bump = [ctx.bumps.session_authority];
+    let seeds: &[&[u8]] = &[b"session_authority", owner.as_ref(), &bump];
+    let signer = &[seeds];
+
+    // Execute CPI
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        ctx.accounts.tick_array_bitmap_extension.as_ref(),
+        remaining_accounts,
+        &clmm_instruction::Swap {
+            amount: swap_amount,
+            other_amount_threshold: threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+        },
+        signer,
+    )?;
+
+    let (sqrt_price_after_x64, tick_after) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.sqrt_price_x64, pool_state.tick_current)
+    };
+    ctx.accounts.input_token_account.reload()?;
+    ctx.accounts.output_token_account.reload()?;
+    let amount_spent = input_balance_before.saturating_sub(ctx.accounts.input_token_account.amount);
+    let amount_received = ctx
+        .accounts
+        .output_token_account
+        .amount
+        .saturating_sub(output_balance_before);
+    let fee_paid = compute_trade_fee(amount_spent, ctx.accounts.amm_config.trade_fee_rate);
+    let slippage_bps = realized_slippage_bps(
+        amount,
+        expected_other_amount,
+        amount_spent,
+        amount_received,
+        is_base_input,
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let op_id = operation_id(&ctx.accounts.session_key.key())?;
+
+    // The protocol fee is a share of what the pool took, paid on top of it
+    let protocol_fee = compute_protocol_fee(amount_spent, protocol_fee_bps);
+    if protocol_fee > 0 {
+        let (insurance_amount, creator_amount) =
+            ctx.accounts
+                .collect_protocol_fee(protocol_fee, signer, &mut flows)?;
+        if let Some(pool_stats) = ctx.accounts.pool_stats.as_ref() {
+            pool_stats
+                .load_mut()?
+                .record_protocol_fee(zero_for_one, protocol_fee);
+        }
+        if let Some(global_stats) = ctx.accounts.global_stats.as_ref() {
+            global_stats.load_mut()?.record_protocol_fee(protocol_fee);
+        }
+        let user_seq = ctx.accounts.user_cfg.load_mut()?.next_event_seq();
+        emit_event!(
+            ctx,
+            ProtocolFeeCollected {
+                user: owner,
+                pool: ctx.accounts.pool_state.key(),
+                mint: ctx.accounts.input_token_account.mint,
+                fee: protocol_fee,
+                insurance_amount,
+                creator_amount,
+                fee_in_input: protocol_fee,
+                timestamp: now,
+                user_seq,
+                op_id,
+            }
+        );
+    }
+    flows.verify()?;
+    // The session's limits cap everything it spends, fee included
+    ctx.accounts.session.record_spend(
+        &ctx.accounts.input_token_account.mint,
+        amount_spent.saturating_add(protocol_fee),
+    )?;
+
+    ctx.accounts.user_cfg.load_mut()?.record_swap(
+        zero_for_one,
+        amount_spent,
+        fee_paid,
+        slippage_bps,
+        now,
+    );
+    if let Some(pool_stats) = ctx.accounts.pool_stats.as_ref() {
+        pool_stats
+            .load_mut()?
+            .record_swap(zero_for_one, amount_spent, now);
+    }
+    if let Some(global_stats) = ctx.accounts.global_stats.as_ref() {
+        global_stats.load_mut()?.record_swap(amount_spent, now);
+    }
+    let round_trips_flagged = record_round_trip(
+        &ctx.accounts.global_config,
+        &ctx.accounts.round_trip_counter,
+        zero_for_one,
+        now,
+    )?;
+    if let Some((round_trips, max_round_trips)) = round_trips_flagged {
+        let user_seq = ctx.accounts.user_cfg.load_mut()?.next_event_seq();
+        emit_event!(
+            ctx,
+            RoundTripsFlagged {
+                user: owner,
+                pool: ctx.accounts.pool_state.key(),
+                round_trips,
+                max_round_trips,
+                timestamp: now,
+                user_seq,
+                op_id,
+            }
+        );
+    }
+
+    let (ui_amount_in, ui_amount_out) = token_extensions::ui_amounts(
+        &ctx.accounts.input_vault_mint.to_account_info(),
+        &ctx.accounts.output_vault_mint.to_account_info(),
+        amount_spent,
+        amount_received,
+        now,
+    )?;
+    let user_seq = ctx.accounts.user_cfg.load_mut()?.next_event_seq();
+    emit_event!(
+        ctx,
+        SwapExecuted {
+            user: owner,
+            pool: ctx.accounts.pool_state.key(),
+            amount_in: amount_spent,
+            amount_out: amount_received,
+            expected_amount: expected_other_amount,
+            slippage_bps: bps,
+            is_base_input,
+            timestamp: now,
+            sqrt_price_before_x64,
+            sqrt_price_after_x64,
+            tick_after,
+            amm_fee: fee_paid,
+            protocol_fee,
+            realized_slippage_bps: slippage_bps,
+            default_slippage,
+            user_seq,
+            op_id,
+            ui_amount_in,
+            ui_amount_out,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn session_set_slippage(ctx: Context<SessionSetSlippage>, bps: u16) -> Result<()> {
+    require!(bps > 0, CustomError::InvalidSlippage);
+    require!(bps <= 500, CustomError::InvalidSlippage);
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts
+        .session
+        .authorize(SESSION_SCOPE_SET_SLIPPAGE, now)?;
+    let mut user = ctx.accounts.user_cfg.load_mut()?;
+    user.slippage_bps = bps;
+    user.last_activity_ts = now;
+    let user_seq = user.next_event_seq();
+
+    emit_event!(
+        ctx,
+        SlippageSet {
+            owner: ctx.accounts.session.owner,
+            slippage_bps: bps,
+            timestamp: now,
+            user_seq,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,706 @@
+//! Helpers shared by the instruction handlers: Raydium CPIs, validator and
+//! callback invocation, balance-flow checks and return data.
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::instruction::{
+    get_processed_sibling_instruction, get_stack_height,
+};
+
+use crate::contexts::*;
+
+/*
+ * Credit the liquidity a position held since its last accrual, if it is in
+ * range now, and restart its clock. A no-op unless all accounts are passed
+ */
+pub(crate) fn accrue_lp_points(
+    points_config: Option<&PointsConfig>,
+    points_ledger: Option<&mut PointsLedger>,
+    position_points: Option<&mut PositionPoints>,
+    position: &PersonalPositionState,
+    tick_current: i32,
+) -> Result<()> {
+    let (Some(points_config), Some(points_ledger), Some(position_points)) =
+        (points_config, points_ledger, position_points)
+    else {
+        return Ok(());
+    };
+    let now = Clock::get()?.unix_timestamp;
+    if position.tick_lower_index <= tick_current && tick_current < position.tick_upper_index {
+        let seconds = now.saturating_sub(position_points.last_accrual_ts).max(0) as u128;
+        let points = points_config.lp_points(position.liquidity, seconds);
+        points_ledger.lp_points = points_ledger.lp_points.saturating_add(points);
+        points_ledger.last_update_ts = now;
+    }
+    position_points.last_accrual_ts = now;
+    Ok(())
+}
+
+/*
+ * End a dry run: publish the results as return data, then fail with
+ * `SimulationComplete` so every state change is rolled back
+ */
+pub(crate) fn finish_simulation<T: AnchorSerialize>(result: &T) -> Result<()> {
+    write_return_data(result);
+    err!(CustomError::SimulationComplete)
+}
+
+/*
+ * CPI Raydium CLMM with `accounts` followed by the remaining accounts.
+ * Raydium looks for the tick array bitmap extension first, so when passed it
+ * leads the caller's remaining accounts. Unlike a `CpiContext`, which needs
+ * them as an owned Vec and copies them again to invoke, the remaining
+ * accounts are borrowed and appended once to a single account list. Fails
+ * with `InsufficientComputeBudget` first when the CPI clearly cannot finish
+ */
+pub(crate) fn invoke_clmm<'info, A, D>(
+    clmm_program: &Program<'info, AmmV3>,
+    accounts: A,
+    tick_array_bitmap_extension: Option<&UncheckedAccount<'info>>,
+    remaining_accounts: &[AccountInfo<'info>],
+    args: &D,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()>
+where
+    A: ToAccountMetas + ToAccountInfos<'info>,
+    D: InstructionData,
+{
+    let extension = tick_array_bitmap_extension.map(|extension| extension.to_account_info());
+    let extra_accounts = extension.iter().chain(remaining_accounts);
+    let extra_count = remaining_accounts.len() + extension.is_some() as usize;
+    require_compute_units(
+        MIN_CLMM_CPI_COMPUTE_UNITS + TICK_ARRAY_CPI_COMPUTE_UNITS * remaining_accounts.len() as u64,
+    )?;
+
+    let mut metas = accounts.to_account_metas(None);
+    let mut account_infos = accounts.to_account_infos();
+    metas.reserve_exact(extra_count);
+    account_infos.reserve_exact(extra_count + 1);
+    for account in extra_accounts {
+        metas.push(AccountMeta {
+            pubkey: *account.key,
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(clmm_program.to_account_info());
+
+    let instruction = Instruction {
+        program_id: clmm_program.key(),
+        accounts: metas,
+        data: args.data(),
+    };
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+    Ok(())
+}
+
+/*
+ * Fail with `InsufficientComputeBudget` unless at least `needed` compute
+ * units remain, logging the measured budget, rather than run out inside a CPI
+ * with its logs cut short
+ */
+pub(crate) fn require_compute_units(needed: u64) -> Result<()> {
+    let remaining = sol_remaining_compute_units();
+    if remaining < needed {
+        msg!("Compute units remaining: {}, needed: {}", remaining, needed);
+        return err!(CustomError::InsufficientComputeBudget);
+    }
+    Ok(())
+}
+
+/*
+ * CPI each registered validator program with the swap's parameters; any
+ * failure aborts the swap. `accounts` are the payer, pool, input and output
+ * token accounts. Validator programs are taken from the front of
+ * `remaining_accounts` in registry order. Returns how many were used. The
+ * instruction is built once and only its program swapped, and the account
+ * list stays on the stack, as the bump allocator never frees
+ */
+pub(crate) fn run_swap_validators<'info>(
+    swap_validators: &UncheckedAccount<'info>,
+    accounts: [AccountInfo<'info>; 4],
+    remaining_accounts: &[AccountInfo<'info>],
+    validation: &SwapValidation,
+) -> Result<usize> {
+    if swap_validators.data_is_empty() {
+        return Ok(0);
+    }
+    let registry = Account::<SwapValidatorRegistry>::try_from(swap_validators)?;
+    let validators = &registry.validators[..registry.validator_count as usize];
+    require!(
+        remaining_accounts.len() >= validators.len(),
+        CustomError::SwapValidatorMissing
+    );
+
+    let mut data = VALIDATE_SWAP_DISCRIMINATOR.to_vec();
+    validation
+        .serialize(&mut data)
+        .expect("serializing into a Vec cannot fail");
+    let mut instruction = Instruction {
+        program_id: Pubkey::default(),
+        // Read-only and never as signer, so a validator cannot move funds
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta::new_readonly(account.key(), false))
+            .collect(),
+        data,
+    };
+    for (validator, program) in validators.iter().zip(remaining_accounts) {
+        require_keys_eq!(program.key(), *validator, CustomError::SwapValidatorMissing);
+        instruction.program_id = *validator;
+        let [payer, pool, input, output] = accounts.clone();
+        invoke(&instruction, &[payer, pool, input, output, program.clone()])?;
+    }
+    Ok(validators.len())
+}
+
+/*
+ * Split remaining accounts at the callback program: the accounts after it
+ * belong to the callback, the ones before to the pool. Without a callback,
+ * or when it is not repeated there, all belong to the pool
+ */
+pub(crate) fn split_callback_accounts<'c, 'info>(
+    remaining_accounts: &'c [AccountInfo<'info>],
+    callback_program: Option<&UncheckedAccount<'info>>,
+) -> (&'c [AccountInfo<'info>], &'c [AccountInfo<'info>]) {
+    let marker = callback_program.and_then(|callback_program| {
+        remaining_accounts
+            .iter()
+            .position(|account| account.key == callback_program.key)
+    });
+    match marker {
+        Some(index) => (
+            &remaining_accounts[..index],
+            &remaining_accounts[index + 1..],
+        ),
+        None => (remaining_accounts, &[]),
+    }
+}
+
+/*
+ * CPI the caller's callback program. Its accounts keep the signer and
+ * writable flags they have in the transaction, so the callback can act for
+ * the user, e.g. to lend the swap output
+ */
+pub(crate) fn invoke_callback<T: AnchorSerialize>(
+    callback_program: &UncheckedAccount,
+    callback_accounts: &[AccountInfo],
+    discriminator: [u8; 8],
+    payload: &T,
+) -> Result<()> {
+    let mut data = discriminator.to_vec();
+    payload
+        .serialize(&mut data)
+        .expect("serializing into a Vec cannot fail");
+    let instruction = Instruction {
+        program_id: callback_program.key(),
+        accounts: callback_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+    // Sized up front, as growing it would strand the old buffer on the heap
+    let mut account_infos = Vec::with_capacity(callback_accounts.len() + 1);
+    account_infos.extend_from_slice(callback_accounts);
+    account_infos.push(callback_program.to_account_info());
+    invoke(&instruction, &account_infos)?;
+    Ok(())
+}
+
+/*
+ * Net balance changes an instruction expects of the token accounts its funds
+ * pass through: fee skims, program-owned vaults and escrows. Checked once
+ * the transfers are done, aborting if they don't add up. Each transfer is
+ * sized already; this guards against flows composing wrongly
+ */
+#[derive(Default)]
+pub(crate) struct FlowCheck<'info> {
+    /// Account, balance when first tracked, expected change since
+    entries: Vec<(AccountInfo<'info>, u64, i128)>,
+}
+
+impl<'info> FlowCheck<'info> {
+    /*
+     * Expect `account`'s balance to change by `delta` from now on. Deltas
+     * for the same account add up
+     */
+    pub(crate) fn expect(&mut self, account: &AccountInfo<'info>, delta: i128) -> Result<()> {
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|(tracked, _, _)| tracked.key == account.key)
+        {
+            entry.2 += delta;
+            return Ok(());
+        }
+        self.entries
+            .push((account.clone(), token_account_amount(account)?, delta));
+        Ok(())
+    }
+
+    /*
+     * Expect `account` to receive a transfer of `amount` of `mint`, net of
+     * any Token-2022 transfer fee
+     */
+    pub(crate) fn expect_credit(
+        &mut self,
+        account: &AccountInfo<'info>,
+        mint: &AccountInfo,
+        amount: u64,
+    ) -> Result<()> {
+        let fee = token_extensions::transfer_fee(mint, amount, Clock::get()?.epoch)?;
+        self.expect(account, (amount - fee) as i128)
+    }
+
+    pub(crate) fn verify(&self) -> Result<()> {
+        for (account, balance_before, delta) in &self.entries {
+            require!(
+                token_account_amount(account)? as i128 - *balance_before as i128 == *delta,
+                CustomError::FlowInvariantViolated
+            );
+        }
+        Ok(())
+    }
+}
+
+/*
+ * Sqrt price a fee oracle converts at: the oracle pool's mean tick over the
+ * oracle's TWAP window, from Raydium's observation ring. The spot price can
+ * be pushed within the transaction, so it only serves as a sanity check
+ * against the mean. Fails when the observations are stale or too short, or
+ * the spot price strays too far
+ */
+pub(crate) fn fee_oracle_sqrt_price(
+    fee_oracle: &FeeOracle,
+    pool_state: &PoolState,
+    observation_state: &ObservationState,
+    now: i64,
+) -> Result<u128> {
+    let observations = &observation_state.observations;
+    let count = observations.len();
+    let newest_index = observation_state.observation_index as usize;
+    let newest = &observations[newest_index];
+    require!(
+        newest.block_timestamp != 0
+            && now.saturating_sub(newest.block_timestamp as i64)
+                <= fee_oracle.max_staleness_secs as i64,
+        CustomError::FeeOracleStale
+    );
+    // Walk back from the newest observation to the first one old enough,
+    // stopping at slots the ring has not filled yet
+    let older = (1..count)
+        .map(|back| &observations[(newest_index + count - back) % count])
+        .take_while(|observation| observation.block_timestamp != 0)
+        .find(|observation| {
+            newest
+                .block_timestamp
+                .saturating_sub(observation.block_timestamp)
+                >= fee_oracle.twap_window_secs
+        })
+        .ok_or_else(|| error!(CustomError::FeeOracleStale))?;
+    let twap_tick = math::mean_tick(
+        (older.block_timestamp, older.tick_cumulative),
+        (newest.block_timestamp, newest.tick_cumulative),
+    )
+    .ok_or_else(|| error!(CustomError::FeeOracleStale))?;
+    require!(
+        pool_state.tick_current.abs_diff(twap_tick) <= fee_oracle.max_deviation_ticks,
+        CustomError::FeeOraclePriceDeviation
+    );
+    math::sqrt_price_at_tick(twap_tick)
+}
+
+/*
+ * Accounts a protocol fee moves through. `from` pays the whole fee on
+ * `authority`'s signature
+ */
+pub(crate) struct ProtocolFeeAccounts<'a, 'info> {
+    pub global_config: &'a Account<'info, GlobalConfig>,
+    pub fee_mint: &'a InterfaceAccount<'info, Mint>,
+    pub from: &'a InterfaceAccount<'info, TokenAccount>,
+    pub authority: AccountInfo<'info>,
+    pub treasury_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub insurance_vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub pool_creator_record: Option<&'a Account<'info, PoolCreatorRecord>>,
+    pub creator_fee_vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// Token program of `fee_mint`
+    pub token_program: AccountInfo<'info>,
+}
+
+/*
+ * Pay a protocol fee of `fee` fee mint units. The insurance share goes to
+ * the fee mint's insurance vault, the creator share of the remainder to the
+ * pool creator's vault, the rest to the treasury. Returns the insurance and
+ * creator shares
+ */
+pub(crate) fn collect_protocol_fee<'info>(
+    accounts: ProtocolFeeAccounts<'_, 'info>,
+    fee: u64,
+    signer_seeds: &[&[&[u8]]],
+    flows: &mut FlowCheck<'info>,
+) -> Result<(u64, u64)> {
+    let ProtocolFeeAccounts {
+        global_config,
+        fee_mint,
+        from,
+        authority,
+        treasury_token_account,
+        insurance_vault,
+        pool_creator_record,
+        creator_fee_vault,
+        token_program,
+    } = accounts;
+    require!(
+        treasury_token_account.owner == global_config.treasury
+            && treasury_token_account.mint == fee_mint.key(),
+        CustomError::InvalidTreasuryAccount
+    );
+    // Only insurance vaults are token accounts of the config
+    require!(
+        insurance_vault.owner == global_config.key() && insurance_vault.mint == fee_mint.key(),
+        CustomError::InvalidInsuranceVault
+    );
+
+    let insurance_amount = global_config.insurance_share(fee);
+    let creator_share = match pool_creator_record {
+        Some(pool_creator_record) if global_config.creator_share_bps > 0 => {
+            let creator_fee_vault =
+                creator_fee_vault.ok_or_else(|| error!(CustomError::InvalidCreatorFeeVault))?;
+            require!(
+                creator_fee_vault.owner == pool_creator_record.key()
+                    && creator_fee_vault.mint == fee_mint.key(),
+                CustomError::InvalidCreatorFeeVault
+            );
+            Some((
+                creator_fee_vault,
+                global_config.creator_share(fee - insurance_amount),
+            ))
+        }
+        _ => None,
+    };
+    let creator_amount = creator_share.map_or(0, |(_, amount)| amount);
+    let fee_mint_info = fee_mint.to_account_info();
+    flows.expect(&from.to_account_info(), -(fee as i128))?;
+    for (to, amount) in [
+        (insurance_vault, insurance_amount),
+        (
+            treasury_token_account,
+            fee - insurance_amount - creator_amount,
+        ),
+    ]
+    .into_iter()
+    .chain(creator_share)
+    {
+        if amount == 0 {
+            continue;
+        }
+        flows.expect_credit(&to.to_account_info(), &fee_mint_info, amount)?;
+        let cpi_accounts = TransferChecked {
+            from: from.to_account_info(),
+            mint: fee_mint_info.clone(),
+            to: to.to_account_info(),
+            authority: authority.clone(),
+        };
+        let cpi_context =
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds);
+        token_interface::transfer_checked(cpi_context, amount, fee_mint.decimals)?;
+    }
+    Ok((insurance_amount, creator_amount))
+}
+
+/*
+ * Resize a program-owned account to `new_len`, `payer` topping its lamports
+ * up to rent exemption first. Bytes past the old length are zeroed
+ */
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let old_len = account.data_len();
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        system_program::transfer(cpi_context, rent_due)?;
+    }
+    account.resize(new_len)?;
+    account.try_borrow_mut_data()?[old_len..].fill(0);
+    Ok(())
+}
+
+pub(crate) fn token_account_amount(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/*
+ * A reward Raydium pays out while decreasing liquidity, and its recipient's
+ * balance before the CPI
+ */
+pub(crate) struct RewardPayout<'info> {
+    pub reward_index: u8,
+    pub reward_mint: Pubkey,
+    pub recipient: AccountInfo<'info>,
+    pub balance_before: u64,
+}
+
+impl RewardPayout<'_> {
+    pub(crate) fn received(&self) -> Result<u64> {
+        Ok(token_account_amount(&self.recipient)?.saturating_sub(self.balance_before))
+    }
+}
+
+/*
+ * The rewards a Raydium decrease pays, from the remaining accounts it is
+ * passed: past the bitmap extension, which the CLMM program owns, they are
+ * (reward vault, recipient, reward mint) triples, one per initialized reward
+ * in index order
+ */
+pub(crate) fn reward_payouts<'info>(
+    clmm_program: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<Vec<RewardPayout<'info>>> {
+    let reward_accounts: Vec<&AccountInfo<'info>> = remaining_accounts
+        .iter()
+        .filter(|account| account.owner != clmm_program)
+        .collect();
+    reward_accounts
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(reward_index, triple)| {
+            Ok(RewardPayout {
+                reward_index: reward_index as u8,
+                reward_mint: triple[2].key(),
+                recipient: triple[1].clone(),
+                balance_before: token_account_amount(triple[1])?,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn write_return_data<T: AnchorSerialize>(result: &T) {
+    let mut data = Vec::new();
+    result
+        .serialize(&mut data)
+        .expect("serializing into a Vec cannot fail");
+    set_return_data(&data);
+}
+
+/*
+ * Owner's config from an always-passed `UserConfig` PDA, `None` until they
+ * set one up. A legacy config carries no caps, callers or sequence, so until
+ * it is migrated it counts as none, and exits stay open without migrating
+ */
+pub(crate) fn existing_user_config(
+    user_cfg: &UncheckedAccount,
+) -> Result<Option<AccountLoader<UserConfig>>> {
+    if user_cfg.data_is_empty() || !UserConfig::is_current(user_cfg) {
+        return Ok(None);
+    }
+    Ok(Some(AccountLoader::<UserConfig>::try_from(user_cfg)?))
+}
+
+/*
+ * Fail unless the transaction's top-level instruction belongs to one of the
+ * owner's allowed callers, when their config exists and restricts them
+ */
+pub(crate) fn check_owner_caller(
+    user_cfg: &UncheckedAccount,
+    instructions_sysvar: Option<&UncheckedAccount>,
+) -> Result<()> {
+    match existing_user_config(user_cfg)? {
+        Some(user_cfg) => user_cfg.load()?.check_caller(instructions_sysvar),
+        None => Ok(()),
+    }
+}
+
+/*
+ * Fail unless every destination is on the owner's allowlist, when it exists
+ * and is enforced
+ */
+pub(crate) fn check_destinations(
+    allowlist: &UncheckedAccount,
+    destinations: &[Pubkey],
+) -> Result<()> {
+    if allowlist.data_is_empty() {
+        return Ok(());
+    }
+    let allowlist = Account::<DestinationAllowlist>::try_from(allowlist)?;
+    let now = Clock::get()?.unix_timestamp;
+    if !allowlist.is_enforced(now) {
+        return Ok(());
+    }
+    for destination in destinations {
+        require!(
+            allowlist.allows(destination, now),
+            CustomError::DestinationNotAllowed
+        );
+    }
+    Ok(())
+}
+
+/*
+ * Count a swap towards the user's round trips on the pool. Returns the round
+ * trips in the window and the threshold when it was exceeded, failing
+ * instead when governance throttles. While governance tracks round trips the
+ * counter must exist, so a swap cannot opt out by leaving it uninitialized
+ */
+pub(crate) fn record_round_trip(
+    global_config: &GlobalConfig,
+    round_trip_counter: &UncheckedAccount,
+    zero_for_one: bool,
+    now: i64,
+) -> Result<Option<(u16, u16)>> {
+    if !global_config.tracks_round_trips() {
+        return Ok(None);
+    }
+    require!(
+        !round_trip_counter.data_is_empty(),
+        CustomError::RoundTripCounterRequired
+    );
+    let mut counter = Account::<RoundTripCounter>::try_from(round_trip_counter)?;
+    let round_trips = counter.record_swap(
+        zero_for_one,
+        now,
+        global_config.round_trip_window_secs as i64,
+    );
+    let flagged = round_trips > global_config.max_round_trips;
+    if flagged {
+        require!(
+            !global_config.throttles_round_trips(),
+            CustomError::RoundTripLimitExceeded
+        );
+        counter.times_flagged = counter.times_flagged.saturating_add(1);
+    }
+    counter.exit(&crate::ID)?;
+    Ok(flagged.then_some((round_trips, global_config.max_round_trips)))
+}
+
+/*
+ * Swap direction inferred from the input token account's mint: true when it
+ * sells token_0 for token_1. Fails unless the vaults are the pool's, in the
+ * order the direction requires
+ */
+pub(crate) fn swap_direction(
+    pool_state: &PoolState,
+    input_mint: &Pubkey,
+    input_vault: &Pubkey,
+    output_vault: &Pubkey,
+) -> Result<bool> {
+    let zero_for_one = if *input_mint == pool_state.token_mint_0 {
+        true
+    } else if *input_mint == pool_state.token_mint_1 {
+        false
+    } else {
+        return err!(CustomError::InvalidVault);
+    };
+    let (expected_input, expected_output) = if zero_for_one {
+        (pool_state.token_vault_0, pool_state.token_vault_1)
+    } else {
+        (pool_state.token_vault_1, pool_state.token_vault_0)
+    };
+    if *input_vault == expected_input && *output_vault == expected_output {
+        Ok(zero_for_one)
+    } else if *input_vault == expected_output && *output_vault == expected_input {
+        err!(CustomError::WrongVaultOrder)
+    } else {
+        err!(CustomError::InvalidVault)
+    }
+}
+
+/*
+ * Count a deposit or withdrawal of `amount_0` and `amount_1` against the
+ * owner's exposure, when their config caps it. Deposits fail past either
+ * cap; withdrawals only lower exposure and never fail
+ */
+pub(crate) fn track_exposure(
+    user_cfg: &mut UserConfig,
+    pool_exposure: Option<&mut PoolExposure>,
+    pool: &Pubkey,
+    pool_state: &PoolState,
+    amount_0: u64,
+    amount_1: u64,
+    deposit: bool,
+) -> Result<()> {
+    if !user_cfg.has_exposure_caps() {
+        return Ok(());
+    }
+    let value = user_cfg.exposure_value(
+        &pool_state.token_mint_0,
+        &pool_state.token_mint_1,
+        amount_0,
+        amount_1,
+        pool_state.sqrt_price_x64,
+    );
+    if !deposit {
+        // Pools priced out by a later quote mint were never counted
+        let Ok(value) = value else {
+            return Ok(());
+        };
+        user_cfg.remove_exposure(value);
+        if let Some(pool_exposure) = pool_exposure {
+            pool_exposure.exposure = pool_exposure.exposure.saturating_sub(value);
+        }
+        return Ok(());
+    }
+
+    let value = value?;
+    user_cfg.add_exposure(value)?;
+    let max_pool_exposure = user_cfg.max_pool_exposure;
+    match pool_exposure {
+        Some(pool_exposure) => {
+            pool_exposure.owner = user_cfg.owner;
+            pool_exposure.pool = *pool;
+            pool_exposure.add(value, max_pool_exposure)
+        }
+        None if max_pool_exposure > 0 => err!(CustomError::PoolExposureRequired),
+        None => Ok(()),
+    }
+}
+
+/*
+ * Next number in the owner's event sequence, zero when their config was not
+ * passed
+ */
+pub(crate) fn next_user_seq(user_cfg: Option<&AccountLoader<UserConfig>>) -> Result<u64> {
+    match user_cfg {
+        Some(user_cfg) => Ok(user_cfg.load_mut()?.next_event_seq()),
+        None => Ok(0),
+    }
+}
+
+/*
+ * ID shared by the events of one instruction: the first 8 bytes of the
+ * SHA-256 of the slot, the signer, the stack height and the instruction's
+ * index among the ones processed at that height. The index tells apart
+ * identical instructions in one transaction, top-level or through CPI
+ */
+pub(crate) fn operation_id(signer: &Pubkey) -> Result<u64> {
+    let slot = Clock::get()?.slot;
+    let mut index: u64 = 0;
+    while get_processed_sibling_instruction(index as usize).is_some() {
+        index += 1;
+    }
+    let hash = hashv(&[
+        &slot.to_le_bytes(),
+        signer.as_ref(),
+        &(get_stack_height() as u64).to_le_bytes(),
+        &index.to_le_bytes(),
+    ])
+    .to_bytes();
+    Ok(u64::from_le_bytes(hash[..8].try_into().unwrap()))
+}
@@ -0,0 +1,754 @@
+//! Positions co-owned through shares.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct CreateSharedPosition<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub position_nft_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = creator
+    )]
+    pub creator_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(constraint = personal_position.nft_mint == position_nft_mint.key())]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + SharedPosition::SIZE,
+        seeds = [b"shared_position", position_nft_mint.key().as_ref()],
+        bump
+    )]
+    pub shared_position: Box<Account<'info, SharedPosition>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + SharedPositionShare::SIZE,
+        seeds = [b"shared_share", shared_position.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_share: Box<Account<'info, SharedPositionShare>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = position_nft_mint,
+        associated_token::authority = shared_position,
+        associated_token::token_program = nft_token_program
+    )]
+    pub shared_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub nft_token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The creator's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", creator.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the creator restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SharedPositionAction<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"shared_position", shared_position.position_nft.as_ref()],
+        bump
+    )]
+    pub shared_position: Box<Account<'info, SharedPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = 8 + SharedPositionShare::SIZE,
+        seeds = [b"shared_share", shared_position.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub share: Box<Account<'info, SharedPositionShare>>,
+
+    #[account(
+        token::mint = shared_position.position_nft,
+        token::authority = shared_position
+    )]
+    pub shared_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = personal_position.nft_mint == shared_position.position_nft
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut, address = shared_position.pool)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Holds harvested fees until contributors claim them; always the shared
+    /// position's associated token account of the mint
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint,
+        token::authority = shared_position,
+        address = get_associated_token_address_with_program_id(
+            &shared_position.key(),
+            &token_vault_0.mint,
+            vault_0_mint.to_account_info().owner,
+        )
+    )]
+    pub shared_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint,
+        token::authority = shared_position,
+        address = get_associated_token_address_with_program_id(
+            &shared_position.key(),
+            &token_vault_1.mint,
+            vault_1_mint.to_account_info().owner,
+        )
+    )]
+    pub shared_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint,
+        token::authority = contributor
+    )]
+    pub contributor_token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint,
+        token::authority = contributor
+    )]
+    pub contributor_token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// CHECK: Raydium checks the memo program address
+    pub memo_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Checked for the governance pause flags by deposits only; withdrawals
+    /// and fee claims stay open while the program is paused
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The contributor's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", contributor.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the contributor restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The contributor's `DestinationAllowlist` PDA, read when
+    /// initialized
+    #[account(seeds = [b"destination_allowlist", contributor.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+
+    /// Risky Token-2022 extensions the contributor accepts on the pool's
+    /// mints, read by deposits
+    #[account(seeds = [b"extension_opt_in", contributor.key().as_ref()], bump)]
+    pub mint_extension_opt_in: Option<Account<'info, MintExtensionOptIn>>,
+}
+
+/*
+ * Sweep of a shared position's token account. Fee rounding strands dust in
+ * them once every share is withdrawn; with no shares left it is owed to
+ * nobody and goes to the creator
+ */
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    /// Anyone; the dust only ever goes to the beneficiary
+    pub sweeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"shared_position", shared_position.position_nft.as_ref()],
+        bump,
+        constraint = shared_position.total_shares == 0 @ CustomError::NothingToSweep
+    )]
+    pub shared_position: Box<Account<'info, SharedPosition>>,
+
+    /// Never the position NFT, which stays in the shared position's custody
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = shared_position,
+        constraint = source.mint != shared_position.position_nft @ CustomError::NothingToSweep
+    )]
+    pub source: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = shared_position.creator
+    )]
+    pub beneficiary_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SharedPositionAction<'info> {
+    /*
+     * Decrease the shared position into the shared token accounts and return
+     * what arrived. Zero liquidity only harvests fees
+     */
+    fn withdraw_to_shared(
+        &mut self,
+        liquidity: u128,
+        amount_0_min: u64,
+        amount_1_min: u64,
+        signer: &[&[&[u8]]],
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, u64)> {
+        let balance_0_before = self.shared_token_account_0.amount;
+        let balance_1_before = self.shared_token_account_1.amount;
+
+        // Build CPI accounts
+        let cpi_accounts = cpi::accounts::DecreaseLiquidityV2 {
+            nft_owner: self.shared_position.to_account_info(),
+            nft_account: self.shared_nft_account.to_account_info(),
+            personal_position: self.personal_position.to_account_info(),
+            pool_state: self.pool_state.to_account_info(),
+            protocol_position: self.protocol_position.to_account_info(),
+            token_vault_0: self.token_vault_0.to_account_info(),
+            token_vault_1: self.token_vault_1.to_account_info(),
+            tick_array_lower: self.tick_array_lower.to_account_info(),
+            tick_array_upper: self.tick_array_upper.to_account_info(),
+            recipient_token_account_0: self.shared_token_account_0.to_account_info(),
+            recipient_token_account_1: self.shared_token_account_1.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            token_program_2022: self.token_program_2022.to_account_info(),
+            memo_program: self.memo_program.to_account_info(),
+            vault_0_mint: self.vault_0_mint.to_account_info(),
+            vault_1_mint: self.vault_1_mint.to_account_info(),
+        };
+
+        // Execute CPI
+        invoke_clmm(
+            &self.clmm_program,
+            cpi_accounts,
+            None,
+            remaining_accounts,
+            &clmm_instruction::DecreaseLiquidityV2 {
+                liquidity,
+                amount_0_min,
+                amount_1_min,
+            },
+            signer,
+        )?;
+
+        self.shared_token_account_0.reload()?;
+        self.shared_token_account_1.reload()?;
+        self.personal_position.reload()?;
+        Ok((
+            self.shared_token_account_0
+                .amount
+                .saturating_sub(balance_0_before),
+            self.shared_token_account_1
+                .amount
+                .saturating_sub(balance_1_before),
+        ))
+    }
+
+    /*
+     * Collect the position's fees into the shared accounts, credit them to
+     * all shares and return the contributor's pending part
+     */
+    fn harvest_fees(
+        &mut self,
+        signer: &[&[&[u8]]],
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(u64, u64)> {
+        let (fees_0, fees_1) = self.withdraw_to_shared(0, 0, 0, signer, remaining_accounts)?;
+        self.shared_position.accrue_fees(fees_0, fees_1);
+        Ok(self.share.pending_fees(&self.shared_position))
+    }
+
+    fn transfer_to_shared(&mut self, amount_0: u64, amount_1: u64) -> Result<()> {
+        for (from, to, mint, amount) in [
+            (
+                &self.contributor_token_account_0,
+                &self.shared_token_account_0,
+                &self.vault_0_mint,
+                amount_0,
+            ),
+            (
+                &self.contributor_token_account_1,
+                &self.shared_token_account_1,
+                &self.vault_1_mint,
+                amount_1,
+            ),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.contributor.to_account_info(),
+            };
+            let cpi_context = CpiContext::new(self.token_program_of(mint), cpi_accounts);
+            token_interface::transfer_checked(cpi_context, amount, mint.decimals)?;
+        }
+        self.shared_token_account_0.reload()?;
+        self.shared_token_account_1.reload()
+    }
+
+    fn transfer_from_shared(
+        &self,
+        amount_0: u64,
+        amount_1: u64,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        for (from, to, mint, amount) in [
+            (
+                &self.shared_token_account_0,
+                &self.contributor_token_account_0,
+                &self.vault_0_mint,
+                amount_0,
+            ),
+            (
+                &self.shared_token_account_1,
+                &self.contributor_token_account_1,
+                &self.vault_1_mint,
+                amount_1,
+            ),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+            let cpi_accounts = TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.shared_position.to_account_info(),
+            };
+            let cpi_context =
+                CpiContext::new_with_signer(self.token_program_of(mint), cpi_accounts, signer);
+            token_interface::transfer_checked(cpi_context, amount, mint.decimals)?;
+        }
+        Ok(())
+    }
+
+    fn token_program_of(&self, mint: &InterfaceAccount<'info, Mint>) -> AccountInfo<'info> {
+        if mint.to_account_info().owner == &self.token_program_2022.key() {
+            self.token_program_2022.to_account_info()
+        } else {
+            self.token_program.to_account_info()
+        }
+    }
+}
+
+pub fn create_shared_position(ctx: Context<CreateSharedPosition>) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+
+    let liquidity = ctx.accounts.personal_position.liquidity;
+    require!(liquidity > 0, CustomError::ZeroLiquidity);
+
+    let shared = &mut ctx.accounts.shared_position;
+    shared.creator = ctx.accounts.creator.key();
+    shared.position_nft = ctx.accounts.position_nft_mint.key();
+    shared.pool = ctx.accounts.personal_position.pool_id;
+    shared.total_shares = liquidity;
+
+    let share = &mut ctx.accounts.creator_share;
+    share.shared_position = ctx.accounts.shared_position.key();
+    share.contributor = ctx.accounts.creator.key();
+    share.shares = liquidity;
+
+    // Move the NFT into the shared position's custody
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.creator_nft_account.to_account_info(),
+        mint: ctx.accounts.position_nft_mint.to_account_info(),
+        to: ctx.accounts.shared_nft_account.to_account_info(),
+        authority: ctx.accounts.creator.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(
+        ctx.accounts.nft_token_program.to_account_info(),
+        cpi_accounts,
+    );
+    token_interface::transfer_checked(cpi_context, 1, 0)?;
+
+    emit_event!(
+        ctx,
+        SharedPositionCreated {
+            creator: ctx.accounts.creator.key(),
+            position_nft: ctx.accounts.position_nft_mint.key(),
+            pool: ctx.accounts.personal_position.pool_id,
+            shares: liquidity,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn shared_deposit<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SharedPositionAction<'info>>,
+    liquidity: u128,
+    amount_0_max: u64,
+    amount_1_max: u64,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    token_extensions::require_allowed_extensions(
+        &[
+            ctx.accounts.vault_0_mint.to_account_info(),
+            ctx.accounts.vault_1_mint.to_account_info(),
+        ],
+        ctx.accounts.mint_extension_opt_in.as_deref(),
+    )?;
+
+    require!(liquidity > 0, CustomError::ZeroLiquidity);
+    let position_nft = ctx.accounts.shared_position.position_nft;
+    let signer_seeds: &[&[u8]] = &[
+        b"shared_position",
+        position_nft.as_ref(),
+        &[ctx.bumps.shared_position],
+    ];
+    let signer = &[signer_seeds];
+    let (pending_0, pending_1) = ctx.accounts.harvest_fees(signer, ctx.remaining_accounts)?;
+
+    // Stage the maxima in the shared accounts; Raydium pulls from there.
+    // A transfer-fee mint credits less than was sent, so what arrived is the
+    // balance change, and that is all Raydium may take or the refund return
+    ctx.accounts.shared_token_account_0.reload()?;
+    ctx.accounts.shared_token_account_1.reload()?;
+    let unstaged_0 = ctx.accounts.shared_token_account_0.amount;
+    let unstaged_1 = ctx.accounts.shared_token_account_1.amount;
+    ctx.accounts
+        .transfer_to_shared(amount_0_max, amount_1_max)?;
+    let staged_0 = ctx.accounts.shared_token_account_0.amount;
+    let staged_1 = ctx.accounts.shared_token_account_1.amount;
+    let received_0 = staged_0.saturating_sub(unstaged_0);
+    let received_1 = staged_1.saturating_sub(unstaged_1);
+    let liquidity_before = ctx.accounts.personal_position.liquidity;
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::IncreaseLiquidityV2 {
+        nft_owner: ctx.accounts.shared_position.to_account_info(),
+        nft_account: ctx.accounts.shared_nft_account.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        token_account_0: ctx.accounts.shared_token_account_0.to_account_info(),
+        token_account_1: ctx.accounts.shared_token_account_1.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+    };
+
+    // Execute CPI
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        None,
+        ctx.remaining_accounts,
+        &clmm_instruction::IncreaseLiquidityV2 {
+            liquidity,
+            amount_0_max: received_0,
+            amount_1_max: received_1,
+            base_flag: None,
+        },
+        signer,
+    )?;
+
+    ctx.accounts.shared_token_account_0.reload()?;
+    ctx.accounts.shared_token_account_1.reload()?;
+    ctx.accounts.personal_position.reload()?;
+    let amount_0 = staged_0.saturating_sub(ctx.accounts.shared_token_account_0.amount);
+    let amount_1 = staged_1.saturating_sub(ctx.accounts.shared_token_account_1.amount);
+    let shares = ctx
+        .accounts
+        .personal_position
+        .liquidity
+        .saturating_sub(liquidity_before);
+
+    // Refund what Raydium did not use, together with the pending fees
+    ctx.accounts.transfer_from_shared(
+        received_0
+            .saturating_sub(amount_0)
+            .saturating_add(pending_0),
+        received_1
+            .saturating_sub(amount_1)
+            .saturating_add(pending_1),
+        signer,
+    )?;
+
+    let shared = &mut ctx.accounts.shared_position;
+    shared.total_shares = shared.total_shares.saturating_add(shares);
+    let total_shares = shared.total_shares;
+    let share = &mut ctx.accounts.share;
+    share.shared_position = ctx.accounts.shared_position.key();
+    share.contributor = ctx.accounts.contributor.key();
+    share.shares = share.shares.saturating_add(shares);
+    share.reset_fee_debt(&ctx.accounts.shared_position);
+
+    emit_event!(
+        ctx,
+        SharedPositionChanged {
+            contributor: ctx.accounts.contributor.key(),
+            position_nft,
+            shares_delta: shares as i128,
+            shares: ctx.accounts.share.shares,
+            total_shares,
+            amount_0,
+            amount_1,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn shared_withdraw<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SharedPositionAction<'info>>,
+    shares: u128,
+    amount_0_min: u64,
+    amount_1_min: u64,
+) -> Result<()> {
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[
+            ctx.accounts.contributor_token_account_0.key(),
+            ctx.accounts.contributor_token_account_1.key(),
+        ],
+    )?;
+
+    require!(shares > 0, CustomError::ZeroLiquidity);
+    require!(
+        shares <= ctx.accounts.share.shares,
+        CustomError::InsufficientShares
+    );
+    let position_nft = ctx.accounts.shared_position.position_nft;
+    let signer_seeds: &[&[u8]] = &[
+        b"shared_position",
+        position_nft.as_ref(),
+        &[ctx.bumps.shared_position],
+    ];
+    let signer = &[signer_seeds];
+
+    let (sqrt_price_x64, tick_current) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.sqrt_price_x64, pool_state.tick_current)
+    };
+    let (principal_0, principal_1) = liquidity_math::get_delta_amounts_signed(
+        tick_current,
+        sqrt_price_x64,
+        ctx.accounts.personal_position.tick_lower_index,
+        ctx.accounts.personal_position.tick_upper_index,
+        -i128::try_from(shares).map_err(|_| CustomError::MathOverflow)?,
+    )?;
+
+    // Raydium pays the principal and every holder's fees in one transfer
+    let (received_0, received_1) = ctx.accounts.withdraw_to_shared(
+        shares,
+        amount_0_min,
+        amount_1_min,
+        signer,
+        ctx.remaining_accounts,
+    )?;
+    ctx.accounts.shared_position.accrue_fees(
+        received_0.saturating_sub(principal_0),
+        received_1.saturating_sub(principal_1),
+    );
+    let (pending_0, pending_1) = ctx
+        .accounts
+        .share
+        .pending_fees(&ctx.accounts.shared_position);
+    let amount_0 = received_0.min(principal_0);
+    let amount_1 = received_1.min(principal_1);
+
+    ctx.accounts.transfer_from_shared(
+        amount_0.saturating_add(pending_0),
+        amount_1.saturating_add(pending_1),
+        signer,
+    )?;
+
+    let shared = &mut ctx.accounts.shared_position;
+    shared.total_shares = shared.total_shares.saturating_sub(shares);
+    let total_shares = shared.total_shares;
+    let share = &mut ctx.accounts.share;
+    share.shares -= shares;
+    share.reset_fee_debt(&ctx.accounts.shared_position);
+
+    emit_event!(
+        ctx,
+        SharedPositionChanged {
+            contributor: ctx.accounts.contributor.key(),
+            position_nft,
+            shares_delta: -(shares as i128),
+            shares: ctx.accounts.share.shares,
+            total_shares,
+            amount_0,
+            amount_1,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn claim_shared_fees<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SharedPositionAction<'info>>,
+) -> Result<()> {
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[
+            ctx.accounts.contributor_token_account_0.key(),
+            ctx.accounts.contributor_token_account_1.key(),
+        ],
+    )?;
+
+    let position_nft = ctx.accounts.shared_position.position_nft;
+    let signer_seeds: &[&[u8]] = &[
+        b"shared_position",
+        position_nft.as_ref(),
+        &[ctx.bumps.shared_position],
+    ];
+    let signer = &[signer_seeds];
+    let (pending_0, pending_1) = ctx.accounts.harvest_fees(signer, ctx.remaining_accounts)?;
+
+    ctx.accounts
+        .transfer_from_shared(pending_0, pending_1, signer)?;
+    ctx.accounts
+        .share
+        .reset_fee_debt(&ctx.accounts.shared_position);
+
+    emit_event!(
+        ctx,
+        SharedFeesClaimed {
+            contributor: ctx.accounts.contributor.key(),
+            position_nft,
+            amount_0: pending_0,
+            amount_1: pending_1,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+    let amount = ctx.accounts.source.amount;
+    require!(amount > 0, CustomError::NothingToSweep);
+
+    let position_nft = ctx.accounts.shared_position.position_nft;
+    let signer_seeds: &[&[u8]] = &[
+        b"shared_position",
+        position_nft.as_ref(),
+        &[ctx.bumps.shared_position],
+    ];
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.source.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.beneficiary_token_account.to_account_info(),
+        authority: ctx.accounts.shared_position.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        &[signer_seeds],
+    );
+    token_interface::transfer_checked(cpi_context, amount, ctx.accounts.mint.decimals)?;
+
+    emit_event!(
+        ctx,
+        DustSwept {
+            sweeper: ctx.accounts.sweeper.key(),
+            source: ctx.accounts.source.key(),
+            beneficiary: ctx.accounts.shared_position.creator,
+            mint: ctx.accounts.mint.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
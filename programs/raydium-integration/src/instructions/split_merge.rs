@@ -0,0 +1,571 @@
+//! Splitting and merging positions.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SplitPosition<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    /// Owns the source position, receives the new one and pays its rent
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    #[account(
+        token::authority = nft_owner,
+        constraint = nft_account.mint == personal_position.nft_mint
+    )]
+    pub nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Validated by Raydium, shared by both positions
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub new_position_nft_mint: Signer<'info>,
+
+    /// CHECK: Token account where the new position NFT will be minted
+    #[account(mut)]
+    pub new_position_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: Personal position state of the new NFT, created by Raydium
+    #[account(
+        mut,
+        seeds = [POSITION_SEED.as_bytes(), new_position_nft_mint.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub new_personal_position: UncheckedAccount<'info>,
+
+    /// CHECK: To store metaplex metadata of the new NFT, only needed with
+    /// `with_metadata`
+    #[account(mut)]
+    pub metadata_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint,
+        token::authority = nft_owner
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint,
+        token::authority = nft_owner
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub metadata_program: Program<'info, Metadata>,
+
+    /// CHECK: Raydium checks the memo program address
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The owner's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", nft_owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The owner's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", nft_owner.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct MergePositions<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+
+    /// Owns both positions and receives the source's rent
+    #[account(mut)]
+    pub nft_owner: Signer<'info>,
+
+    /// CHECK: Burned by Raydium, which validates it against the personal position
+    #[account(mut, address = source_personal_position.nft_mint)]
+    pub source_position_nft_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::authority = nft_owner,
+        constraint = source_nft_account.mint == source_personal_position.nft_mint
+    )]
+    pub source_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = source_personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub source_personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(
+        token::authority = nft_owner,
+        constraint = target_nft_account.mint == target_personal_position.nft_mint
+    )]
+    pub target_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = target_personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub target_personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// CHECK: Validated by Raydium, shared by both positions
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by Raydium
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_0.mint,
+        token::authority = nft_owner
+    )]
+    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_vault_1.mint,
+        token::authority = nft_owner
+    )]
+    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
+    )]
+    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
+    )]
+    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = token_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// CHECK: Raydium checks the memo program address
+    pub memo_program: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [b"position_registry", nft_owner.key().as_ref()], bump)]
+    pub position_registry: Option<Account<'info, UserPositionRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"position_registry",
+            nft_owner.key().as_ref(),
+            &position_registry_page.page.to_le_bytes(),
+        ],
+        bump,
+        constraint = position_registry_page.owner == nft_owner.key()
+    )]
+    pub position_registry_page: Option<Account<'info, PositionRegistryPage>>,
+
+    /// Closed with the source position; its history is not carried over
+    #[account(
+        mut,
+        close = nft_owner,
+        seeds = [b"cost_basis", source_position_nft_mint.key().as_ref()],
+        bump
+    )]
+    pub source_cost_basis: Option<Account<'info, PositionCostBasis>>,
+
+    /// Checked for the governance pause flags
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// CHECK: The owner's `UserConfig` PDA, read for allowed callers when
+    /// initialized
+    #[account(seeds = [b"user_cfg", nft_owner.key().as_ref()], bump)]
+    pub user_cfg: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed when the owner restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: The owner's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", nft_owner.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+}
+
+pub fn split_position<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, SplitPosition<'info>>,
+    liquidity_to_move: u128,
+    with_metadata: bool,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[
+            ctx.accounts.token_account_0.key(),
+            ctx.accounts.token_account_1.key(),
+        ],
+    )?;
+
+    require!(liquidity_to_move > 0, CustomError::ZeroLiquidity);
+    require!(
+        liquidity_to_move < ctx.accounts.personal_position.liquidity,
+        CustomError::InvalidSplitLiquidity
+    );
+    require!(
+        !with_metadata || ctx.accounts.metadata_account.is_some(),
+        CustomError::MetadataAccountRequired
+    );
+    let tick_spacing = ctx.accounts.pool_state.load()?.tick_spacing;
+    let (tick_lower_index, tick_upper_index) = (
+        ctx.accounts.personal_position.tick_lower_index,
+        ctx.accounts.personal_position.tick_upper_index,
+    );
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::DecreaseLiquidityV2 {
+        nft_owner: ctx.accounts.nft_owner.to_account_info(),
+        nft_account: ctx.accounts.nft_account.to_account_info(),
+        personal_position: ctx.accounts.personal_position.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        recipient_token_account_0: ctx.accounts.token_account_0.to_account_info(),
+        recipient_token_account_1: ctx.accounts.token_account_1.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        memo_program: ctx.accounts.memo_program.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+    };
+
+    // Execute CPI. The withdrawal is redeposited below at the same price,
+    // so no minimum amounts are needed
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        None,
+        ctx.remaining_accounts,
+        &clmm_instruction::DecreaseLiquidityV2 {
+            liquidity: liquidity_to_move,
+            amount_0_min: 0,
+            amount_1_min: 0,
+        },
+        &[],
+    )?;
+
+    ctx.accounts.token_account_0.reload()?;
+    ctx.accounts.token_account_1.reload()?;
+    let metadata_account = ctx.accounts.metadata_account.as_ref().map_or_else(
+        || ctx.accounts.new_position_nft_mint.to_account_info(),
+        |metadata_account| metadata_account.to_account_info(),
+    );
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::OpenPositionV2 {
+        payer: ctx.accounts.nft_owner.to_account_info(),
+        position_nft_owner: ctx.accounts.nft_owner.to_account_info(),
+        position_nft_mint: ctx.accounts.new_position_nft_mint.to_account_info(),
+        position_nft_account: ctx.accounts.new_position_nft_account.to_account_info(),
+        metadata_account,
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        protocol_position: ctx.accounts.protocol_position.to_account_info(),
+        tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+        personal_position: ctx.accounts.new_personal_position.to_account_info(),
+        token_account_0: ctx.accounts.token_account_0.to_account_info(),
+        token_account_1: ctx.accounts.token_account_1.to_account_info(),
+        token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+        token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        metadata_program: ctx.accounts.metadata_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+    };
+
+    // Build CPI context
+    let cpi_context = CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts);
+
+    // Execute CPI. Deposit rounding may need a unit more than the
+    // withdrawal paid out, which the owner's balance covers
+    cpi::open_position_v2(
+        cpi_context,
+        tick_lower_index,
+        tick_upper_index,
+        math::tick_array_start_index(tick_lower_index, tick_spacing),
+        math::tick_array_start_index(tick_upper_index, tick_spacing),
+        liquidity_to_move,
+        ctx.accounts.token_account_0.amount,
+        ctx.accounts.token_account_1.amount,
+        with_metadata,
+        None,
+    )?;
+
+    emit_event!(
+        ctx,
+        PositionSplit {
+            user: ctx.accounts.nft_owner.key(),
+            pool: ctx.accounts.pool_state.key(),
+            source_position_nft: ctx.accounts.personal_position.nft_mint,
+            new_position_nft: ctx.accounts.new_position_nft_mint.key(),
+            tick_lower: tick_lower_index,
+            tick_upper: tick_upper_index,
+            liquidity_moved: liquidity_to_move,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn merge_positions<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MergePositions<'info>>,
+) -> Result<()> {
+    ctx.accounts.global_config.require_unpaused()?;
+    check_owner_caller(
+        &ctx.accounts.user_cfg,
+        ctx.accounts.instructions_sysvar.as_ref(),
+    )?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[
+            ctx.accounts.token_account_0.key(),
+            ctx.accounts.token_account_1.key(),
+        ],
+    )?;
+
+    let source = &ctx.accounts.source_personal_position;
+    let target = &ctx.accounts.target_personal_position;
+    require_keys_neq!(source.nft_mint, target.nft_mint, CustomError::SamePosition);
+    require!(
+        source.tick_lower_index == target.tick_lower_index
+            && source.tick_upper_index == target.tick_upper_index,
+        CustomError::PositionRangeMismatch
+    );
+    let liquidity = source.liquidity;
+    let (source_position_nft, target_position_nft) = (source.nft_mint, target.nft_mint);
+    let (tick_lower, tick_upper) = (source.tick_lower_index, source.tick_upper_index);
+
+    if liquidity > 0 {
+        // Build CPI accounts
+        let cpi_accounts = cpi::accounts::DecreaseLiquidityV2 {
+            nft_owner: ctx.accounts.nft_owner.to_account_info(),
+            nft_account: ctx.accounts.source_nft_account.to_account_info(),
+            personal_position: ctx.accounts.source_personal_position.to_account_info(),
+            pool_state: ctx.accounts.pool_state.to_account_info(),
+            protocol_position: ctx.accounts.protocol_position.to_account_info(),
+            token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+            token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+            tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+            tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+            recipient_token_account_0: ctx.accounts.token_account_0.to_account_info(),
+            recipient_token_account_1: ctx.accounts.token_account_1.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+            memo_program: ctx.accounts.memo_program.to_account_info(),
+            vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+            vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+        };
+
+        // Execute CPI. Redeposited below at the same price
+        invoke_clmm(
+            &ctx.accounts.clmm_program,
+            cpi_accounts,
+            None,
+            ctx.remaining_accounts,
+            &clmm_instruction::DecreaseLiquidityV2 {
+                liquidity,
+                amount_0_min: 0,
+                amount_1_min: 0,
+            },
+            &[],
+        )?;
+
+        ctx.accounts.token_account_0.reload()?;
+        ctx.accounts.token_account_1.reload()?;
+
+        // Build CPI accounts
+        let cpi_accounts = cpi::accounts::IncreaseLiquidityV2 {
+            nft_owner: ctx.accounts.nft_owner.to_account_info(),
+            nft_account: ctx.accounts.target_nft_account.to_account_info(),
+            pool_state: ctx.accounts.pool_state.to_account_info(),
+            protocol_position: ctx.accounts.protocol_position.to_account_info(),
+            personal_position: ctx.accounts.target_personal_position.to_account_info(),
+            tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
+            tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
+            token_account_0: ctx.accounts.token_account_0.to_account_info(),
+            token_account_1: ctx.accounts.token_account_1.to_account_info(),
+            token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
+            token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+            vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+            vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+        };
+
+        // Build CPI context
+        let cpi_context =
+            CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts);
+
+        // Execute CPI
+        cpi::increase_liquidity_v2(
+            cpi_context,
+            liquidity,
+            ctx.accounts.token_account_0.amount,
+            ctx.accounts.token_account_1.amount,
+            None,
+        )?;
+    }
+
+    // The NFT may be a legacy or a Token-2022 mint
+    let nft_token_program = if ctx.accounts.source_nft_account.to_account_info().owner
+        == &ctx.accounts.token_program_2022.key()
+    {
+        ctx.accounts.token_program_2022.to_account_info()
+    } else {
+        ctx.accounts.token_program.to_account_info()
+    };
+
+    // Build CPI accounts
+    let cpi_accounts = cpi::accounts::ClosePosition {
+        nft_owner: ctx.accounts.nft_owner.to_account_info(),
+        position_nft_mint: ctx.accounts.source_position_nft_mint.to_account_info(),
+        position_nft_account: ctx.accounts.source_nft_account.to_account_info(),
+        personal_position: ctx.accounts.source_personal_position.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: nft_token_program,
+    };
+
+    // Build CPI context
+    let cpi_context = CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts);
+    cpi::close_position(cpi_context)?;
+
+    match (
+        ctx.accounts.position_registry.as_mut(),
+        ctx.accounts.position_registry_page.as_mut(),
+    ) {
+        (Some(registry), Some(page)) => {
+            require!(
+                page.remove(&source_position_nft),
+                CustomError::PositionNotInRegistry
+            );
+            registry.position_count = registry.position_count.saturating_sub(1);
+        }
+        (None, None) => {}
+        _ => return err!(CustomError::RegistryAccountsMismatch),
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let op_id = operation_id(&ctx.accounts.nft_owner.key())?;
+    emit_event!(
+        ctx,
+        PositionsMerged {
+            user: ctx.accounts.nft_owner.key(),
+            pool: ctx.accounts.pool_state.key(),
+            source_position_nft,
+            target_position_nft,
+            liquidity_moved: liquidity,
+            timestamp: now,
+            op_id,
+        }
+    );
+    emit_event!(
+        ctx,
+        PositionClosed {
+            user: ctx.accounts.nft_owner.key(),
+            pool: ctx.accounts.pool_state.key(),
+            position_nft: source_position_nft,
+            tick_lower,
+            tick_upper,
+            timestamp: now,
+            op_id,
+        }
+    );
+
+    Ok(())
+}
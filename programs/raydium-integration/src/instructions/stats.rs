@@ -0,0 +1,73 @@
+//! Statistics account creation.
+use crate::contexts::*;
+
+#[derive(Accounts)]
+pub struct InitGlobalStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GlobalStats::SIZE,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: AccountLoader<'info, GlobalStats>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitPoolStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub pool_state: AccountLoader<'info, PoolState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PoolStats::SIZE,
+        seeds = [b"pool_stats", pool_state.key().as_ref()],
+        bump
+    )]
+    pub pool_stats: AccountLoader<'info, PoolStats>,
+    #[account(mut, seeds = [b"global_stats"], bump)]
+    pub global_stats: Option<AccountLoader<'info, GlobalStats>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitRoundTripCounter<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Only keys the counter
+    pub owner: UncheckedAccount<'info>,
+    pub pool_state: AccountLoader<'info, PoolState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RoundTripCounter::SIZE,
+        seeds = [b"round_trips", owner.key().as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub round_trip_counter: Account<'info, RoundTripCounter>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_global_stats(_ctx: Context<InitGlobalStats>) -> Result<()> {
+    Ok(())
+}
+
+pub fn init_pool_stats(ctx: Context<InitPoolStats>) -> Result<()> {
+    ctx.accounts.pool_stats.load_init()?.pool = ctx.accounts.pool_state.key();
+    if let Some(global_stats) = ctx.accounts.global_stats.as_ref() {
+        let mut global_stats = global_stats.load_mut()?;
+        global_stats.pools_tracked = global_stats.pools_tracked.saturating_add(1);
+    }
+    Ok(())
+}
+
+pub fn init_round_trip_counter(ctx: Context<InitRoundTripCounter>) -> Result<()> {
+    let round_trip_counter = &mut ctx.accounts.round_trip_counter;
+    round_trip_counter.owner = ctx.accounts.owner.key();
+    round_trip_counter.pool = ctx.accounts.pool_state.key();
+    Ok(())
+}
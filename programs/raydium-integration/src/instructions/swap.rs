@@ -0,0 +1,677 @@
+//! Proxied Raydium swaps.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct ProxySwap<'info> {
+    pub clmm_program: Program<'info, AmmV3>,
+    /// Authority over `input_token_account`. May be a PDA signing through
+    /// `invoke_signed`; the signer privilege is forwarded to Raydium as is.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_cfg", payer.key().as_ref()],
+        bump,
+        constraint = UserConfig::is_current(&user_cfg.to_account_info()) @ CustomError::UserConfigMigrationRequired
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    #[account(mut)]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(mut)]
+    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = input_vault.mint)]
+    pub input_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = output_vault.mint)]
+    pub output_vault_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(mut, constraint = tick_array.load()?.pool_id == pool_state.key())]
+    pub tick_array: AccountLoader<'info, TickArrayState>,
+
+    #[account(mut, seeds = [b"pool_stats", pool_state.key().as_ref()], bump)]
+    pub pool_stats: Option<AccountLoader<'info, PoolStats>>,
+
+    #[account(mut, seeds = [b"global_stats"], bump)]
+    pub global_stats: Option<AccountLoader<'info, GlobalStats>>,
+
+    /// CHECK: Raydium's tick array bitmap extension for the pool, needed by
+    /// pools with tick arrays initialized far from the current price
+    #[account(
+        seeds = [POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_state.key().as_ref()],
+        bump,
+        seeds::program = clmm_program,
+    )]
+    pub tick_array_bitmap_extension: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Instructions sysvar, needed when the payer restricts callers
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Pause flags, protocol fee and round-trip policy. The three accounts
+    /// below are required unless the fee is zero
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+
+    /// Mint the fee is paid in: the input mint to skim it from the swap
+    /// input, or a mint with a registered `FeeOracle` to pay it separately
+    pub fee_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Receives the protocol fee net of the insurance share; owned by
+    /// `global_config.treasury`
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Insurance fund vault of `fee_mint`
+    #[account(mut)]
+    pub insurance_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token program of `fee_mint` when it isn't `token_program`, e.g. a
+    /// Token-2022 mint the fee is paid in
+    pub fee_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Pass with `points_ledger` to earn swap points
+    #[account(seeds = [b"points_config"], bump)]
+    pub points_config: Option<Box<Account<'info, PointsConfig>>>,
+
+    #[account(mut, seeds = [b"points", payer.key().as_ref()], bump)]
+    pub points_ledger: Option<Box<Account<'info, PointsLedger>>>,
+
+    /// CHECK: The payer's `RoundTripCounter` PDA for the pool, always
+    /// passed; must be initialized while governance tracks round trips
+    #[account(
+        mut,
+        seeds = [b"round_trips", payer.key().as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub round_trip_counter: UncheckedAccount<'info>,
+
+    /// The four accounts below pay the fee in a mint other than the input's
+    pub fee_oracle: Option<Box<Account<'info, FeeOracle>>>,
+
+    pub fee_oracle_pool: Option<AccountLoader<'info, PoolState>>,
+
+    /// Observation account of `fee_oracle_pool`, the source of its TWAP
+    pub fee_oracle_observation: Option<AccountLoader<'info, ObservationState>>,
+
+    /// Payer's `fee_mint` account the fee is paid from
+    #[account(mut)]
+    pub fee_payment_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Pays the pool's creator a share of the protocol fee when the pool was
+    /// created through the proxy; `creator_fee_vault` is then required
+    #[account(seeds = [b"pool_creator", pool_state.key().as_ref()], bump)]
+    pub pool_creator_record: Option<Box<Account<'info, PoolCreatorRecord>>>,
+
+    /// Token account of `pool_creator_record` in `fee_mint`
+    #[account(mut)]
+    pub creator_fee_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: `SwapValidatorRegistry` PDA, always passed so registered
+    /// validators cannot be skipped; may be uninitialized
+    #[account(seeds = [b"swap_validators"], bump)]
+    pub swap_validators: UncheckedAccount<'info>,
+
+    /// CHECK: The payer's `DestinationAllowlist` PDA, read when initialized
+    #[account(seeds = [b"destination_allowlist", payer.key().as_ref()], bump)]
+    pub destination_allowlist: UncheckedAccount<'info>,
+
+    /// Risky Token-2022 extensions the payer accepts on the swapped mints
+    #[account(seeds = [b"extension_opt_in", payer.key().as_ref()], bump)]
+    pub mint_extension_opt_in: Option<Account<'info, MintExtensionOptIn>>,
+
+    /// CHECK: Caller-chosen program called after execution with the actual
+    /// amounts. Its accounts follow it at the end of the remaining accounts
+    #[account(executable)]
+    pub callback_program: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> ProxySwap<'info> {
+    /*
+     * Run the registered swap validators over this swap. Returns how many
+     * remaining accounts they used
+     */
+    fn run_swap_validators(
+        &self,
+        remaining_accounts: &[AccountInfo<'info>],
+        validation: &SwapValidation,
+    ) -> Result<usize> {
+        run_swap_validators(
+            &self.swap_validators,
+            [
+                self.payer.to_account_info(),
+                self.pool_state.to_account_info(),
+                self.input_token_account.to_account_info(),
+                self.output_token_account.to_account_info(),
+            ],
+            remaining_accounts,
+            validation,
+        )
+    }
+
+    /*
+     * Whether the protocol fee is skimmed from the swap input rather than
+     * paid in another mint
+     */
+    fn fee_paid_in_input(&self) -> bool {
+        self.fee_mint
+            .as_ref()
+            .map_or(true, |fee_mint| fee_mint.key() == self.input_vault.mint)
+    }
+
+    /*
+     * TWAP sqrt price of the oracle pool registered for the input and fee
+     * mints, and whether the input mint is its token_0
+     */
+    fn fee_oracle_price(&self, now: i64) -> Result<(u128, bool)> {
+        let required = || error!(CustomError::FeeOracleRequired);
+        let fee_mint = self.fee_mint.as_ref().ok_or_else(required)?;
+        let fee_oracle = self.fee_oracle.as_ref().ok_or_else(required)?;
+        let fee_oracle_pool = self.fee_oracle_pool.as_ref().ok_or_else(required)?;
+        let fee_oracle_observation = self.fee_oracle_observation.as_ref().ok_or_else(required)?;
+        let pool_state = fee_oracle_pool.load()?;
+        require!(
+            fee_oracle.input_mint == self.input_vault.mint
+                && fee_oracle.fee_mint == fee_mint.key()
+                && fee_oracle.pool == fee_oracle_pool.key()
+                && pool_state.observation_key == fee_oracle_observation.key(),
+            CustomError::InvalidFeeOracle
+        );
+        let sqrt_price_x64 = fee_oracle_sqrt_price(
+            fee_oracle,
+            &pool_state,
+            &fee_oracle_observation.load()?,
+            now,
+        )?;
+        Ok((sqrt_price_x64, fee_oracle.input_is_token_0))
+    }
+
+    /*
+     * Collect a protocol fee owed in input units: from the payer's input
+     * account, or converted at `oracle_price` from the fee payment account.
+     * Returns the fee charged and its insurance and creator shares, in fee
+     * mint units
+     */
+    fn collect_protocol_fee(
+        &self,
+        fee: u64,
+        oracle_price: Option<(u128, bool)>,
+        flows: &mut FlowCheck<'info>,
+    ) -> Result<(u64, u64, u64)> {
+        let required = || error!(CustomError::ProtocolFeeAccountsRequired);
+        let (from, fee) = match oracle_price {
+            None => (&*self.input_token_account, fee),
+            Some((sqrt_price_x64, input_is_token_0)) => (
+                self.fee_payment_account.as_deref().ok_or_else(required)?,
+                math::convert_at_sqrt_price(fee, sqrt_price_x64, input_is_token_0),
+            ),
+        };
+        let token_program = match &self.fee_token_program {
+            Some(fee_token_program) => fee_token_program.to_account_info(),
+            None => self.token_program.to_account_info(),
+        };
+        let (insurance_amount, creator_amount) = collect_protocol_fee(
+            ProtocolFeeAccounts {
+                global_config: &self.global_config,
+                fee_mint: self.fee_mint.as_deref().ok_or_else(required)?,
+                from,
+                authority: self.payer.to_account_info(),
+                treasury_token_account: self
+                    .treasury_token_account
+                    .as_deref()
+                    .ok_or_else(required)?,
+                insurance_vault: self.insurance_vault.as_deref().ok_or_else(required)?,
+                pool_creator_record: self.pool_creator_record.as_deref(),
+                creator_fee_vault: self.creator_fee_vault.as_deref(),
+                token_program,
+            },
+            fee,
+            &[],
+            flows,
+        )?;
+        Ok((fee, insurance_amount, creator_amount))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn proxy_swap<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, ProxySwap<'info>>,
+    amount: u64,
+    expected_other_amount: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    max_tick_crossings: Option<u16>,
+    partial_fill: bool,
+    simulate_only: bool,
+) -> Result<()> {
+    require!(amount > 0, CustomError::ZeroSwapAmount);
+    require!(
+        expected_other_amount > 0,
+        CustomError::InvalidExpectedAmount
+    );
+    require!(
+        !partial_fill || sqrt_price_limit_x64 != 0,
+        CustomError::PriceLimitRequired
+    );
+
+    ctx.accounts
+        .global_config
+        .require_active(FLAG_PAUSE_SWAPS, "swaps")?;
+    ctx.accounts
+        .user_cfg
+        .load()?
+        .check_caller(ctx.accounts.instructions_sysvar.as_ref())?;
+    check_destinations(
+        &ctx.accounts.destination_allowlist,
+        &[ctx.accounts.output_token_account.key()],
+    )?;
+    token_extensions::require_allowed_extensions(
+        &[
+            ctx.accounts.input_vault_mint.to_account_info(),
+            ctx.accounts.output_vault_mint.to_account_info(),
+        ],
+        ctx.accounts.mint_extension_opt_in.as_deref(),
+    )?;
+
+    // Registered validator programs lead the remaining accounts
+    let validator_count = ctx.accounts.run_swap_validators(
+        ctx.remaining_accounts,
+        &SwapValidation {
+            user: ctx.accounts.payer.key(),
+            pool: ctx.accounts.pool_state.key(),
+            input_mint: ctx.accounts.input_vault.mint,
+            output_mint: ctx.accounts.output_vault.mint,
+            amount,
+            expected_other_amount,
+            is_base_input,
+        },
+    )?;
+    let (remaining_accounts, callback_accounts) = split_callback_accounts(
+        &ctx.remaining_accounts[validator_count..],
+        ctx.accounts.callback_program.as_ref(),
+    );
+
+    // Governance can require users to have set a tolerance rather than
+    // swap on the default
+    let require_user_slippage = ctx.accounts.global_config.requires_user_slippage();
+    let (bps, default_slippage) = ctx
+        .accounts
+        .user_cfg
+        .load()?
+        .swap_slippage_bps(require_user_slippage)?;
+
+    // Exact-input swaps skimming the protocol fee leave room in `amount`
+    // for the fee on what the pool takes, so the pool gets less and the
+    // expected output shrinks with it
+    let protocol_fee_bps = ctx.accounts.global_config.protocol_fee_bps;
+    let fee_paid_in_input = ctx.accounts.fee_paid_in_input();
+    let swap_amount = if is_base_input && fee_paid_in_input {
+        math::amount_before_protocol_fee(amount, protocol_fee_bps)
+    } else {
+        amount
+    };
+    // Priced before the swap, which may move the oracle pool
+    let fee_oracle_price = if protocol_fee_bps > 0 && !fee_paid_in_input {
+        Some(
+            ctx.accounts
+                .fee_oracle_price(Clock::get()?.unix_timestamp)?,
+        )
+    } else {
+        None
+    };
+    // The bound applies to what the user's wallet shows of the other token
+    let threshold_mint = if is_base_input {
+        ctx.accounts.output_vault_mint.to_account_info()
+    } else {
+        ctx.accounts.input_vault_mint.to_account_info()
+    };
+    let threshold = prorate_threshold(
+        token_extensions::slippage_threshold(
+            &threshold_mint,
+            expected_other_amount,
+            bps,
+            is_base_input,
+            Clock::get()?.unix_timestamp,
+        )?,
+        swap_amount,
+        amount,
+        is_base_input,
+    );
+
+    debug_msg!(
+        "Swap | amount: {}, expected_other: {}, threshold: {}, slippage_bps: {}, is_base_input: {}",
+        amount,
+        expected_other_amount,
+        threshold,
+        bps,
+        is_base_input
+    );
+
+    let (sqrt_price_before_x64, zero_for_one) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (
+            pool_state.sqrt_price_x64,
+            swap_direction(
+                &pool_state,
+                &ctx.accounts.input_token_account.mint,
+                &ctx.accounts.input_vault.key(),
+                &ctx.accounts.output_vault.key(),
+            )?,
+        )
+    };
+
+    // Quote the swap over the supplied tick arrays before paying for the CPI
+    if let Some(max_tick_crossings) = max_tick_crossings {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        let extra_tick_arrays = remaining_accounts
+            .iter()
+            .map(AccountLoader::<TickArrayState>::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        let mut tick_arrays = Vec::with_capacity(extra_tick_arrays.len() + 1);
+        tick_arrays.push(ctx.accounts.tick_array.load()?);
+        for tick_array in extra_tick_arrays.iter() {
+            tick_arrays.push(tick_array.load()?);
+        }
+        require!(
+            tick_arrays
+                .iter()
+                .all(|tick_array| tick_array.pool_id == ctx.accounts.pool_state.key()),
+            CustomError::InvalidTickArray
+        );
+        let crossings = count_tick_crossings(
+            &pool_state,
+            &tick_arrays,
+            ctx.accounts.amm_config.trade_fee_rate,
+            swap_amount,
+            sqrt_price_limit_x64,
+            is_base_input,
+            zero_for_one,
+            max_tick_crossings,
+            Clock::get()?.unix_timestamp as u32,
+        )?;
+        debug_msg!("Quote | tick crossings: {}", crossings);
+    }
+
+    let input_balance_before = ctx.accounts.input_token_account.amount;
+    let output_balance_before = ctx.accounts.output_token_account.amount;
+    let mut flows = FlowCheck::default();
+
+    // Build CPI to Raydium AMM v3
+    let cpi_accounts = cpi::accounts::SwapSingle {
+        payer: ctx.accounts.payer.to_account_info(),
+        amm_config: ctx.accounts.amm_config.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        input_token_account: ctx.accounts.input_token_account.to_account_info(),
+        output_token_account: ctx.accounts.output_token_account.to_account_info(),
+        input_vault: ctx.accounts.input_vault.to_account_info(),
+        output_vault: ctx.accounts.output_vault.to_account_info(),
+        observation_state: ctx.accounts.observation_state.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        tick_array: ctx.accounts.tick_array.to_account_info(),
+    };
+
+    // A partial fill may stop at the price limit, so the full-size threshold
+    // is checked pro rata after the CPI instead
+    let cpi_threshold = match (partial_fill, is_base_input) {
+        (false, _) => threshold,
+        (true, true) => 0,
+        (true, false) => u64::MAX,
+    };
+    invoke_clmm(
+        &ctx.accounts.clmm_program,
+        cpi_accounts,
+        ctx.accounts.tick_array_bitmap_extension.as_ref(),
+        remaining_accounts,
+        &clmm_instruction::Swap {
+            amount: swap_amount,
+            other_amount_threshold: cpi_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+        },
+        &[],
+    )?;
+
+    // Pool state after the CPI reflects the executed swap
+    let (sqrt_price_after_x64, tick_after) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.sqrt_price_x64, pool_state.tick_current)
+    };
+
+    ctx.accounts.input_token_account.reload()?;
+    ctx.accounts.output_token_account.reload()?;
+    let amount_spent = input_balance_before.saturating_sub(ctx.accounts.input_token_account.amount);
+    let amount_received = ctx
+        .accounts
+        .output_token_account
+        .amount
+        .saturating_sub(output_balance_before);
+    let fee_paid = compute_trade_fee(amount_spent, ctx.accounts.amm_config.trade_fee_rate);
+    let slippage_bps = realized_slippage_bps(
+        amount,
+        expected_other_amount,
+        amount_spent,
+        amount_received,
+        is_base_input,
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let op_id = operation_id(&ctx.accounts.payer.key())?;
+
+    // The protocol fee is a share of what the pool took, paid on top of it
+    let protocol_fee = compute_protocol_fee(amount_spent, protocol_fee_bps);
+    if protocol_fee > 0 {
+        let (fee_charged, insurance_amount, creator_amount) =
+            ctx.accounts
+                .collect_protocol_fee(protocol_fee, fee_oracle_price, &mut flows)?;
+        if let Some(pool_stats) = ctx.accounts.pool_stats.as_ref() {
+            pool_stats
+                .load_mut()?
+                .record_protocol_fee(zero_for_one, protocol_fee);
+        }
+        if let Some(global_stats) = ctx.accounts.global_stats.as_ref() {
+            global_stats.load_mut()?.record_protocol_fee(protocol_fee);
+        }
+        let fee_mint = ctx
+            .accounts
+            .fee_mint
+            .as_ref()
+            .map_or(ctx.accounts.input_vault.mint, |fee_mint| fee_mint.key());
+        let user_seq = ctx.accounts.user_cfg.load_mut()?.next_event_seq();
+        emit_event!(
+            ctx,
+            ProtocolFeeCollected {
+                user: ctx.accounts.payer.key(),
+                pool: ctx.accounts.pool_state.key(),
+                mint: fee_mint,
+                fee: fee_charged,
+                insurance_amount,
+                creator_amount,
+                fee_in_input: protocol_fee,
+                timestamp: now,
+                user_seq,
+                op_id,
+            }
+        );
+    }
+
+    ctx.accounts.user_cfg.load_mut()?.record_swap(
+        zero_for_one,
+        amount_spent,
+        fee_paid,
+        slippage_bps,
+        now,
+    );
+    if let Some(pool_stats) = ctx.accounts.pool_stats.as_ref() {
+        pool_stats
+            .load_mut()?
+            .record_swap(zero_for_one, amount_spent, now);
+    }
+    if let Some(global_stats) = ctx.accounts.global_stats.as_ref() {
+        global_stats.load_mut()?.record_swap(amount_spent, now);
+    }
+    // Flagged round trips earn no points
+    let round_trips_flagged = record_round_trip(
+        &ctx.accounts.global_config,
+        &ctx.accounts.round_trip_counter,
+        zero_for_one,
+        now,
+    )?;
+    if let Some((round_trips, max_round_trips)) = round_trips_flagged {
+        let user_seq = ctx.accounts.user_cfg.load_mut()?.next_event_seq();
+        emit_event!(
+            ctx,
+            RoundTripsFlagged {
+                user: ctx.accounts.payer.key(),
+                pool: ctx.accounts.pool_state.key(),
+                round_trips,
+                max_round_trips,
+                timestamp: now,
+                user_seq,
+                op_id,
+            }
+        );
+    }
+    if let (None, Some(points_config), Some(points_ledger)) = (
+        round_trips_flagged,
+        ctx.accounts.points_config.as_ref(),
+        ctx.accounts.points_ledger.as_mut(),
+    ) {
+        let points = points_config.swap_points(amount_spent);
+        points_ledger.swap_points = points_ledger.swap_points.saturating_add(points);
+        points_ledger.last_update_ts = now;
+    }
+
+    let (ui_amount_in, ui_amount_out) = token_extensions::ui_amounts(
+        &ctx.accounts.input_vault_mint.to_account_info(),
+        &ctx.accounts.output_vault_mint.to_account_info(),
+        amount_spent,
+        amount_received,
+        now,
+    )?;
+    let user_seq = ctx.accounts.user_cfg.load_mut()?.next_event_seq();
+    emit_event!(
+        ctx,
+        SwapExecuted {
+            user: ctx.accounts.payer.key(),
+            pool: ctx.accounts.pool_state.key(),
+            amount_in: amount_spent,
+            amount_out: amount_received,
+            expected_amount: expected_other_amount,
+            slippage_bps: bps,
+            is_base_input,
+            timestamp: now,
+            sqrt_price_before_x64,
+            sqrt_price_after_x64,
+            tick_after,
+            amm_fee: fee_paid,
+            protocol_fee,
+            realized_slippage_bps: slippage_bps,
+            default_slippage,
+            user_seq,
+            op_id,
+            ui_amount_in,
+            ui_amount_out,
+        }
+    );
+
+    if partial_fill {
+        let amount_filled = if is_base_input {
+            amount_spent
+        } else {
+            amount_received
+        };
+        let prorated_threshold =
+            prorate_threshold(threshold, amount_filled, swap_amount, is_base_input);
+        if is_base_input {
+            require!(
+                amount_received >= prorated_threshold,
+                CustomError::SlippageExceeded
+            );
+        } else {
+            require!(
+                amount_spent <= prorated_threshold,
+                CustomError::SlippageExceeded
+            );
+        }
+
+        if amount_filled < swap_amount {
+            let user_seq = ctx.accounts.user_cfg.load_mut()?.next_event_seq();
+            emit_event!(
+                ctx,
+                SwapPartiallyFilled {
+                    user: ctx.accounts.payer.key(),
+                    pool: ctx.accounts.pool_state.key(),
+                    is_base_input,
+                    amount_requested: swap_amount,
+                    amount_filled,
+                    amount_in: amount_spent,
+                    amount_out: amount_received,
+                    timestamp: now,
+                    user_seq,
+                    op_id,
+                }
+            );
+        }
+
+        write_return_data(&SwapFill {
+            amount_requested: swap_amount,
+            amount_filled,
+            amount_in: amount_spent,
+            amount_out: amount_received,
+            amm_fee: fee_paid,
+            protocol_fee,
+        });
+    }
+
+    // The callback may move the user's funds, so the proxy's own flows
+    // are settled before it runs
+    flows.verify()?;
+
+    if let Some(callback_program) = ctx.accounts.callback_program.as_ref() {
+        invoke_callback(
+            callback_program,
+            callback_accounts,
+            SWAP_CALLBACK_DISCRIMINATOR,
+            &SwapCallback {
+                user: ctx.accounts.payer.key(),
+                pool: ctx.accounts.pool_state.key(),
+                input_mint: ctx.accounts.input_vault.mint,
+                output_mint: ctx.accounts.output_vault.mint,
+                amount_in: amount_spent,
+                amount_out: amount_received,
+            },
+        )?;
+    }
+
+    if simulate_only {
+        return finish_simulation(&SwapSimulation {
+            amount_in: amount_spent,
+            amount_out: amount_received,
+            fee: fee_paid,
+            sqrt_price_after_x64,
+            tick_after,
+            protocol_fee,
+        });
+    }
+
+    Ok(())
+}
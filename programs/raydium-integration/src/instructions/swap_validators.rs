@@ -0,0 +1,41 @@
+//! Admin-registered swap validators.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetSwapValidators<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + SwapValidatorRegistry::SIZE,
+        seeds = [b"swap_validators"],
+        bump
+    )]
+    pub swap_validators: Account<'info, SwapValidatorRegistry>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_swap_validators(ctx: Context<SetSwapValidators>, programs: Vec<Pubkey>) -> Result<()> {
+    require!(
+        programs.len() <= MAX_SWAP_VALIDATORS,
+        CustomError::TooManySwapValidators
+    );
+    let registry = &mut ctx.accounts.swap_validators;
+    registry.validators = [Pubkey::default(); MAX_SWAP_VALIDATORS];
+    registry.validators[..programs.len()].copy_from_slice(&programs);
+    registry.validator_count = programs.len() as u8;
+
+    emit_event!(
+        ctx,
+        SwapValidatorsSet {
+            programs,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
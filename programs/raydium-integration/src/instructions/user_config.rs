@@ -0,0 +1,286 @@
+//! User settings: slippage, mint extension opt-in, caller allowlist and
+//! exposure caps, and migrations of legacy configs.
+use crate::contexts::*;
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetSlippage<'info> {
+    /// Config owner. May be a PDA signing through `invoke_signed`, so it is
+    /// never debited for rent.
+    pub owner: Signer<'info>,
+    /// Funds the config account on first use
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + UserConfig::SIZE,
+        seeds = [b"user_cfg", owner.key().as_ref()],
+        bump
+    )]
+    pub user_cfg: AccountLoader<'info, UserConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct SetMintExtensionOptIn<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintExtensionOptIn::SIZE,
+        seeds = [b"extension_opt_in", owner.key().as_ref()],
+        bump
+    )]
+    pub mint_extension_opt_in: Account<'info, MintExtensionOptIn>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitPoolExposure<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub pool_state: AccountLoader<'info, PoolState>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PoolExposure::SIZE,
+        seeds = [b"pool_exposure", owner.key().as_ref(), pool_state.key().as_ref()],
+        bump
+    )]
+    pub pool_exposure: Account<'info, PoolExposure>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserConfig<'info> {
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Legacy-sized config; does not load as the current layout
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"user_cfg", owner.key().as_ref()],
+        bump
+    )]
+    pub user_cfg: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg_attr(feature = "event-cpi", event_cpi)]
+#[derive(Accounts)]
+pub struct AdminMigrateConfigs<'info> {
+    /// Pays the rent of growing legacy configs
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [b"global_config"], bump, has_one = admin @ CustomError::Unauthorized)]
+    pub global_config: Box<Account<'info, GlobalConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_slippage(ctx: Context<SetSlippage>, bps: u16) -> Result<()> {
+    require!(bps > 0, CustomError::InvalidSlippage);
+    require!(bps <= 500, CustomError::InvalidSlippage);
+    let now = Clock::get()?.unix_timestamp;
+    let mut user = load_or_init(&ctx.accounts.user_cfg)?;
+    user.owner = ctx.accounts.owner.key();
+    user.slippage_bps = bps;
+    user.last_activity_ts = now;
+    let user_seq = user.next_event_seq();
+
+    emit_event!(
+        ctx,
+        SlippageSet {
+            owner: ctx.accounts.owner.key(),
+            slippage_bps: bps,
+            timestamp: now,
+            user_seq,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn set_mint_extension_opt_in(
+    ctx: Context<SetMintExtensionOptIn>,
+    allowed_extensions: u8,
+) -> Result<()> {
+    require!(
+        allowed_extensions & !token_extensions::ALL_RISKY_EXTENSIONS == 0,
+        CustomError::UnknownExtensionFlag
+    );
+    let opt_in = &mut ctx.accounts.mint_extension_opt_in;
+    opt_in.owner = ctx.accounts.owner.key();
+    opt_in.allowed_extensions = allowed_extensions;
+
+    emit_event!(
+        ctx,
+        MintExtensionOptInSet {
+            owner: ctx.accounts.owner.key(),
+            allowed_extensions,
+            timestamp: Clock::get()?.unix_timestamp,
+        }
+    );
+
+    Ok(())
+}
+
+pub fn set_allowed_callers(ctx: Context<SetSlippage>, programs: Vec<Pubkey>) -> Result<()> {
+    require!(
+        programs.len() <= MAX_ALLOWED_CALLERS,
+        CustomError::TooManyAllowedCallers
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let mut user = load_or_init(&ctx.accounts.user_cfg)?;
+    user.owner = ctx.accounts.owner.key();
+    user.allowed_callers = [Pubkey::default(); MAX_ALLOWED_CALLERS];
+    user.allowed_callers[..programs.len()].copy_from_slice(&programs);
+    user.allowed_caller_count = programs.len() as u8;
+    user.last_activity_ts = now;
+    let user_seq = user.next_event_seq();
+
+    emit_event!(
+        ctx,
+        AllowedCallersSet {
+            owner: ctx.accounts.owner.key(),
+            programs,
+            timestamp: now,
+            user_seq,
+        }
+    );
+
+    Ok(())
+}
+
+/*
+ * Cap what the owner's deposits may hold per pool and in total, valued in
+ * `quote_mint` at the pool price. Zero lifts a cap. Exposure already counted
+ * is kept, so the quote mint only changes once it is back to zero
+ */
+pub fn set_exposure_caps(
+    ctx: Context<SetSlippage>,
+    quote_mint: Pubkey,
+    max_pool_exposure: u64,
+    max_total_exposure: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut user = load_or_init(&ctx.accounts.user_cfg)?;
+    let capped = max_pool_exposure > 0 || max_total_exposure > 0;
+    let current_quote_mint = user.exposure_quote_mint;
+    require!(
+        !capped || quote_mint != Pubkey::default(),
+        CustomError::InvalidExposureCaps
+    );
+    require!(
+        quote_mint == current_quote_mint || user.total_exposure == 0,
+        CustomError::InvalidExposureCaps
+    );
+    user.owner = ctx.accounts.owner.key();
+    user.exposure_quote_mint = quote_mint;
+    user.max_pool_exposure = max_pool_exposure;
+    user.max_total_exposure = max_total_exposure;
+    user.last_activity_ts = now;
+    let user_seq = user.next_event_seq();
+
+    emit_event!(
+        ctx,
+        ExposureCapsSet {
+            owner: ctx.accounts.owner.key(),
+            quote_mint,
+            max_pool_exposure,
+            max_total_exposure,
+            timestamp: now,
+            user_seq,
+        }
+    );
+
+    Ok(())
+}
+
+/*
+ * Start tracking the owner's exposure to a pool, for increasing positions
+ * opened before a per-pool cap. Opens create it on their own
+ */
+pub fn init_pool_exposure(ctx: Context<InitPoolExposure>) -> Result<()> {
+    let pool_exposure = &mut ctx.accounts.pool_exposure;
+    pool_exposure.owner = ctx.accounts.owner.key();
+    pool_exposure.pool = ctx.accounts.pool_state.key();
+    Ok(())
+}
+
+/*
+ * Grow a config from an earlier layout to the current one. The Borsh
+ * `owner` and `slippage_bps` it started with sit where the packed layout
+ * keeps them, and every later field starts at zero
+ */
+pub fn migrate_user_config(ctx: Context<MigrateUserConfig>) -> Result<()> {
+    let user_cfg = ctx.accounts.user_cfg.to_account_info();
+    let new_len = 8 + UserConfig::SIZE;
+    require!(user_cfg.data_len() < new_len, CustomError::ConfigUpToDate);
+    grow_account(
+        &user_cfg,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        new_len,
+    )
+}
+
+/*
+ * Grow each user config in `remaining_accounts` to the current layout, as
+ * `migrate_user_config` does, and lower a tolerance above `max_slippage_bps`
+ * to it, for when governance tightens the bound. Configs without a
+ * tolerance of their own already follow the default; configs current and
+ * within the bound are left untouched
+ */
+pub fn admin_migrate_configs<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, AdminMigrateConfigs<'info>>,
+    max_slippage_bps: u16,
+) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_CONFIG_MIGRATION_BATCH,
+        CustomError::TooManyConfigs
+    );
+    require!(
+        max_slippage_bps > 0 && max_slippage_bps <= 500,
+        CustomError::InvalidSlippage
+    );
+    let new_len = 8 + UserConfig::SIZE;
+    let (mut resized, mut normalized) = (0u32, 0u32);
+    for account in ctx.remaining_accounts {
+        // Checks the owner and discriminator, which legacy configs share
+        let user_cfg = AccountLoader::<UserConfig>::try_from(account)?;
+        if !UserConfig::is_current(account) {
+            grow_account(
+                account,
+                &ctx.accounts.admin,
+                &ctx.accounts.system_program,
+                new_len,
+            )?;
+            resized += 1;
+        }
+        let mut user = user_cfg.load_mut()?;
+        if user.slippage_bps > max_slippage_bps {
+            user.slippage_bps = max_slippage_bps;
+            normalized += 1;
+        }
+    }
+
+    emit_event!(
+        ctx,
+        ConfigsMigrated {
+            admin: ctx.accounts.admin.key(),
+            configs: ctx.remaining_accounts.len() as u32,
+            normalized,
+            timestamp: Clock::get()?.unix_timestamp,
+            max_slippage_bps,
+            resized,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,223 @@
+//! Read-only instructions that return data.
+use crate::contexts::*;
+
+#[derive(Accounts)]
+pub struct GetImpermanentLoss<'info> {
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        constraint = personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(
+        seeds = [b"cost_basis", personal_position.nft_mint.as_ref()],
+        bump
+    )]
+    pub cost_basis: Account<'info, PositionCostBasis>,
+}
+
+#[derive(Accounts)]
+pub struct GetLiquidityForAmounts {}
+
+#[derive(Accounts)]
+pub struct GetPositionValue<'info> {
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        constraint = personal_position.pool_id == pool_state.key() @ CustomError::PositionPoolMismatch
+    )]
+    pub personal_position: Box<Account<'info, PersonalPositionState>>,
+
+    #[account(constraint = tick_array_lower.load()?.pool_id == pool_state.key())]
+    pub tick_array_lower: AccountLoader<'info, TickArrayState>,
+
+    #[account(constraint = tick_array_upper.load()?.pool_id == pool_state.key())]
+    pub tick_array_upper: AccountLoader<'info, TickArrayState>,
+}
+
+#[derive(Accounts)]
+pub struct GetPoolHealth<'info> {
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    #[account(address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+    // remaining_accounts: the pool's tick arrays covering the band
+}
+
+#[derive(Accounts)]
+pub struct GetStatus<'info> {
+    #[account(seeds = [b"global_config"], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// Reports the flags a scheduled change would set, when one is pending
+    #[account(seeds = [b"pending_param_change"], bump)]
+    pub pending_param_change: Option<Account<'info, PendingParamChange>>,
+}
+
+pub fn get_impermanent_loss(ctx: Context<GetImpermanentLoss>) -> Result<ImpermanentLossSnapshot> {
+    let (sqrt_price_x64, tick_current) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        (pool_state.sqrt_price_x64, pool_state.tick_current)
+    };
+    let position = &ctx.accounts.personal_position;
+    let cost_basis = &ctx.accounts.cost_basis;
+
+    // Principal the position would return if fully withdrawn now
+    let (amount_0, amount_1) = liquidity_math::get_delta_amounts_signed(
+        tick_current,
+        sqrt_price_x64,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        -i128::try_from(position.liquidity).map_err(|_| CustomError::MathOverflow)?,
+    )?;
+
+    // Holding instead means keeping the deposits net of principal
+    // already withdrawn through the proxy
+    let hodl_amount_0 = cost_basis.deposited_0.saturating_sub(
+        cost_basis
+            .withdrawn_0
+            .saturating_sub(cost_basis.fees_collected_0),
+    );
+    let hodl_amount_1 = cost_basis.deposited_1.saturating_sub(
+        cost_basis
+            .withdrawn_1
+            .saturating_sub(cost_basis.fees_collected_1),
+    );
+
+    let position_value_token_1 = value_in_token_1(amount_0, amount_1, sqrt_price_x64);
+    let hodl_value_token_1 = value_in_token_1(hodl_amount_0, hodl_amount_1, sqrt_price_x64);
+    let il_bps = if hodl_value_token_1 == 0 {
+        0
+    } else {
+        let diff = position_value_token_1 as i128 - hodl_value_token_1 as i128;
+        (diff.saturating_mul(10_000) / hodl_value_token_1 as i128) as i64
+    };
+
+    Ok(ImpermanentLossSnapshot {
+        sqrt_price_x64,
+        amount_0,
+        amount_1,
+        hodl_amount_0,
+        hodl_amount_1,
+        position_value_token_1,
+        hodl_value_token_1,
+        il_bps,
+    })
+}
+
+pub fn get_position_value(ctx: Context<GetPositionValue>) -> Result<PositionValue> {
+    let position = &ctx.accounts.personal_position;
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let (sqrt_price_x64, tick_current) = (pool_state.sqrt_price_x64, pool_state.tick_current);
+
+    let (amount_0, amount_1) = liquidity_math::get_delta_amounts_signed(
+        tick_current,
+        sqrt_price_x64,
+        position.tick_lower_index,
+        position.tick_upper_index,
+        -i128::try_from(position.liquidity).map_err(|_| CustomError::MathOverflow)?,
+    )?;
+    let (fees_0, fees_1) = uncollected_fees(
+        &pool_state,
+        position,
+        &*ctx.accounts.tick_array_lower.load()?,
+        &*ctx.accounts.tick_array_upper.load()?,
+    )?;
+    let value_token_1 = value_in_token_1(
+        amount_0.saturating_add(fees_0),
+        amount_1.saturating_add(fees_1),
+        sqrt_price_x64,
+    );
+
+    Ok(PositionValue {
+        sqrt_price_x64,
+        liquidity: position.liquidity,
+        amount_0,
+        amount_1,
+        fees_0,
+        fees_1,
+        value_token_1,
+    })
+}
+
+pub fn get_liquidity_for_amounts(
+    _ctx: Context<GetLiquidityForAmounts>,
+    sqrt_price_x64: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<u128> {
+    crate::liquidity_for_amounts(
+        sqrt_price_x64,
+        tick_lower_index,
+        tick_upper_index,
+        amount_0,
+        amount_1,
+    )
+}
+
+pub fn get_pool_health<'a, 'b, 'c: 'info, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, GetPoolHealth<'info>>,
+    tick_radius: u32,
+) -> Result<PoolHealth> {
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let loaders = ctx
+        .remaining_accounts
+        .iter()
+        .map(AccountLoader::<TickArrayState>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+    let tick_arrays = loaders
+        .iter()
+        .map(|loader| loader.load())
+        .collect::<Result<Vec<_>>>()?;
+    require!(
+        tick_arrays
+            .iter()
+            .all(|tick_array| tick_array.pool_id == ctx.accounts.pool_state.key()),
+        CustomError::InvalidTickArray
+    );
+    let depth = liquidity_depth(&pool_state, &tick_arrays, tick_radius)?;
+
+    let observation_state = ctx.accounts.observation_state.load()?;
+    let last_observation = observation_state.observations
+        [observation_state.observation_index as usize]
+        .block_timestamp;
+    let observation_age = Clock::get()?
+        .unix_timestamp
+        .saturating_sub(last_observation as i64);
+
+    Ok(PoolHealth {
+        sqrt_price_x64: pool_state.sqrt_price_x64,
+        tick_current: pool_state.tick_current,
+        liquidity: pool_state.liquidity,
+        min_liquidity_in_range: depth.min_liquidity,
+        depth_0: depth.depth_0,
+        depth_1: depth.depth_1,
+        trade_fee_rate: ctx.accounts.amm_config.trade_fee_rate,
+        observation_age,
+    })
+}
+
+pub fn get_status(ctx: Context<GetStatus>) -> Result<ProgramStatus> {
+    let global_config = &ctx.accounts.global_config;
+    let (pending_flags, pending_activation_slot) = ctx
+        .accounts
+        .pending_param_change
+        .as_ref()
+        .map_or((0, 0), |pending| (pending.flags, pending.activation_slot));
+    Ok(ProgramStatus {
+        flags: global_config.flags,
+        paused: global_config.is_paused(),
+        swaps_paused: global_config.swaps_paused(),
+        requires_user_slippage: global_config.requires_user_slippage(),
+        protocol_fee_bps: global_config.protocol_fee_bps,
+        insurance_share_bps: global_config.insurance_share_bps,
+        pending_flags,
+        pending_activation_slot,
+    })
+}
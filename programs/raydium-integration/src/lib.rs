@@ -1,19 +1,58 @@
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::metadata::Metadata;
-use anchor_spl::token::Token;
-use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
-use raydium_amm_v3::{
-    cpi,
-    program::AmmV3,
-    states::{
-        AmmConfig, ObservationState, PoolState, TickArrayState, POSITION_SEED, TICK_ARRAY_SEED,
-    },
-};
 
 declare_id!("CrMxnHJvvk2eRP8H1DLtc2ZTQfzD9NSxJKkDquCzr1Qu");
 
-pub const DEFAULT_SLIPPAGE_BPS: u16 = 500;
+/*
+ * Emit an event through logs, or through a self-CPI when built with
+ * `event-cpi` so indexers can read it from inner instruction data
+ */
+macro_rules! emit_event {
+    ($ctx:expr, $event:expr) => {{
+        #[cfg(feature = "event-cpi")]
+        {
+            let ctx = &$ctx;
+            emit_cpi!($event);
+        }
+        #[cfg(not(feature = "event-cpi"))]
+        {
+            emit!($event);
+        }
+    }};
+}
+
+/*
+ * Verbose log line, only logged when built with `debug-logs`. Formatting
+ * costs compute on every call, and events already carry the same data
+ */
+macro_rules! debug_msg {
+    ($($arg:tt)*) => {{
+        if cfg!(feature = "debug-logs") {
+            msg!($($arg)*);
+        }
+    }};
+}
+
+pub mod constants;
+mod contexts;
+pub mod errors;
+pub mod events;
+mod heap;
+pub mod instructions;
+pub mod math;
+#[cfg(feature = "cli")]
+pub mod pda;
+pub mod state;
+pub mod token_extensions;
+
+pub use constants::*;
+pub use errors::*;
+pub use events::*;
+pub use instructions::*;
+pub use math::{
+    compute_protocol_fee, compute_slippage_threshold, count_tick_crossings, liquidity_for_amounts,
+    uncollected_fees, value_in_token_1,
+};
+pub use state::*;
 
 #[program]
 pub mod raydium_integration {
@@ -23,94 +62,115 @@ pub mod raydium_integration {
      * Set slippage for a user, default is 5%
      */
     pub fn set_slippage(ctx: Context<SetSlippage>, bps: u16) -> Result<()> {
-        require!(bps > 0, CustomError::InvalidSlippage);
-        require!(bps <= 500, CustomError::InvalidSlippage);
-        let user = &mut ctx.accounts.user_cfg;
-        user.owner = ctx.accounts.owner.key();
-        user.slippage_bps = bps;
+        instructions::set_slippage(ctx, bps)
+    }
+
+    /*
+     * Opt in to pools whose mints carry Token-2022 extensions that can alter
+     * transfers, as a bitmask of `token_extensions` flags. Zero opts out
+     */
+    pub fn set_mint_extension_opt_in(
+        ctx: Context<SetMintExtensionOptIn>,
+        allowed_extensions: u8,
+    ) -> Result<()> {
+        instructions::set_mint_extension_opt_in(ctx, allowed_extensions)
+    }
+
+    /*
+     * Restrict swaps and position opens paid by the owner to transactions
+     * whose top-level instruction belongs to one of `programs`. An empty list
+     * lifts the restriction
+     */
+    pub fn set_allowed_callers(ctx: Context<SetSlippage>, programs: Vec<Pubkey>) -> Result<()> {
+        instructions::set_allowed_callers(ctx, programs)
+    }
+
+    /*
+     * Cap the notional the owner's opens and increases may hold in one pool
+     * and across pools, valued in `quote_mint` at the pool price
+     */
+    pub fn set_exposure_caps(
+        ctx: Context<SetSlippage>,
+        quote_mint: Pubkey,
+        max_pool_exposure: u64,
+        max_total_exposure: u64,
+    ) -> Result<()> {
+        instructions::set_exposure_caps(ctx, quote_mint, max_pool_exposure, max_total_exposure)
+    }
+
+    /*
+     * Create the owner's exposure tracker for a pool
+     */
+    pub fn init_pool_exposure(ctx: Context<InitPoolExposure>) -> Result<()> {
+        instructions::init_pool_exposure(ctx)
+    }
+
+    /*
+     * Grow a user config created before the later fields were added,
+     * zero-filling the new fields
+     */
+    pub fn migrate_user_config(ctx: Context<MigrateUserConfig>) -> Result<()> {
+        instructions::migrate_user_config(ctx)
+    }
+
+    /*
+     * Grow up to `MAX_CONFIG_MIGRATION_BATCH` user configs, passed as
+     * writable remaining accounts, to the current layout and lower their
+     * tolerance to at most `max_slippage_bps`, admin only. The admin pays the
+     * extra rent
+     */
+    pub fn admin_migrate_configs<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, AdminMigrateConfigs<'info>>,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        instructions::admin_migrate_configs(ctx, max_slippage_bps)
+    }
+
+    /*
+     * Create the singleton protocol statistics account, permissionless
+     */
+    pub fn init_global_stats(ctx: Context<InitGlobalStats>) -> Result<()> {
+        instructions::init_global_stats(ctx)
+    }
 
-        emit!(SlippageSet {
-            owner: ctx.accounts.owner.key(),
-            slippage_bps: bps,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+    /*
+     * Create the statistics account for one pool, permissionless
+     */
+    pub fn init_pool_stats(ctx: Context<InitPoolStats>) -> Result<()> {
+        instructions::init_pool_stats(ctx)
+    }
 
-        Ok(())
+    /*
+     * Create the round-trip counter of one owner on one pool,
+     * permissionless
+     */
+    pub fn init_round_trip_counter(ctx: Context<InitRoundTripCounter>) -> Result<()> {
+        instructions::init_round_trip_counter(ctx)
     }
 
     /*
      * Swap tokens using Raydium CLMM, exact in or out
      */
-    pub fn proxy_swap(
-        ctx: Context<ProxySwap>,
+    pub fn proxy_swap<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ProxySwap<'info>>,
         amount: u64,
         expected_other_amount: u64,
         sqrt_price_limit_x64: u128,
         is_base_input: bool,
+        max_tick_crossings: Option<u16>,
+        partial_fill: bool,
+        simulate_only: bool,
     ) -> Result<()> {
-        require!(amount > 0, CustomError::ZeroSwapAmount);
-        require!(
-            expected_other_amount > 0,
-            CustomError::InvalidExpectedAmount
-        );
-
-        let user_cfg = &ctx.accounts.user_cfg;
-        let bps = if user_cfg.slippage_bps == 0 {
-            DEFAULT_SLIPPAGE_BPS
-        } else {
-            user_cfg.slippage_bps
-        };
-
-        require!(bps > 0 && bps <= 500, CustomError::InvalidSlippage);
-
-        let threshold = compute_slippage_threshold(expected_other_amount, bps, is_base_input);
-
-        msg!(
-            "Swap | amount: {}, expected_other: {}, threshold: {}, slippage_bps: {}, is_base_input: {}",
+        instructions::proxy_swap(
+            ctx,
             amount,
             expected_other_amount,
-            threshold,
-            bps,
-            is_base_input
-        );
-
-        // Build CPI to Raydium AMM v3
-        let cpi_accounts = cpi::accounts::SwapSingle {
-            payer: ctx.accounts.payer.to_account_info(),
-            amm_config: ctx.accounts.amm_config.to_account_info(),
-            pool_state: ctx.accounts.pool_state.to_account_info(),
-            input_token_account: ctx.accounts.input_token_account.to_account_info(),
-            output_token_account: ctx.accounts.output_token_account.to_account_info(),
-            input_vault: ctx.accounts.input_vault.to_account_info(),
-            output_vault: ctx.accounts.output_vault.to_account_info(),
-            observation_state: ctx.accounts.observation_state.to_account_info(),
-            token_program: ctx.accounts.token_program.to_account_info(),
-            tick_array: ctx.accounts.tick_array.to_account_info(),
-        };
-
-        // Build CPI context
-        let cpi_context =
-            CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts);
-        cpi::swap(
-            cpi_context,
-            amount,
-            threshold,
             sqrt_price_limit_x64,
             is_base_input,
-        )?;
-
-        emit!(SwapExecuted {
-            user: ctx.accounts.payer.key(),
-            pool: ctx.accounts.pool_state.key(),
-            amount_in: amount,
-            amount_out: expected_other_amount,
-            expected_amount: expected_other_amount,
-            slippage_bps: bps,
-            is_base_input,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
-
-        Ok(())
+            max_tick_crossings,
+            partial_fill,
+            simulate_only,
+        )
     }
 
     /*
@@ -126,52 +186,10 @@ pub mod raydium_integration {
         amount_0_max: u64,
         amount_1_max: u64,
         with_matedata: bool,
-        base_flag: Option<bool>,
-    ) -> Result<()> {
-        require!(
-            tick_lower_index < tick_upper_index,
-            CustomError::InvalidTickRange
-        );
-        require!(liquidity > 0, CustomError::ZeroLiquidity);
-        require!(
-            amount_0_max > 0 || amount_1_max > 0,
-            CustomError::ZeroDeposit
-        );
-
-        // Build CPI accounts
-        let cpi_accounts = cpi::accounts::OpenPositionV2 {
-            payer: ctx.accounts.payer.to_account_info(),
-            position_nft_owner: ctx.accounts.position_nft_owner.to_account_info(),
-            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
-            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
-            metadata_account: ctx.accounts.metadata_account.to_account_info(),
-            pool_state: ctx.accounts.pool_state.to_account_info(),
-            protocol_position: ctx.accounts.protocol_position.to_account_info(),
-            tick_array_lower: ctx.accounts.tick_array_lower.to_account_info(),
-            tick_array_upper: ctx.accounts.tick_array_upper.to_account_info(),
-            personal_position: ctx.accounts.personal_position.to_account_info(),
-            token_account_0: ctx.accounts.token_account_0.to_account_info(),
-            token_account_1: ctx.accounts.token_account_1.to_account_info(),
-            token_vault_0: ctx.accounts.token_vault_0.to_account_info(),
-            token_vault_1: ctx.accounts.token_vault_1.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            token_program: ctx.accounts.token_program.to_account_info(),
-            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-            metadata_program: ctx.accounts.metadata_program.to_account_info(),
-            token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
-            vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
-            vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
-        };
-
-        // Build CPI context
-        let cpi_context =
-            CpiContext::new(ctx.accounts.clmm_program.to_account_info(), cpi_accounts)
-                .with_remaining_accounts(ctx.remaining_accounts.to_vec());
-
-        // Execute CPI
-        cpi::open_position_v2(
-            cpi_context,
+        simulate_only: bool,
+    ) -> Result<()> {
+        instructions::proxy_open_position(
+            ctx,
             tick_lower_index,
             tick_upper_index,
             tick_array_lower_start_index,
@@ -180,301 +198,850 @@ pub mod raydium_integration {
             amount_0_max,
             amount_1_max,
             with_matedata,
-            base_flag,
-        )?;
-
-        emit!(PositionOpened {
-            user: ctx.accounts.payer.key(),
-            pool: ctx.accounts.pool_state.key(),
-            position_nft: ctx.accounts.position_nft_mint.key(),
-            tick_lower: tick_lower_index,
-            tick_upper: tick_upper_index,
+            simulate_only,
+        )
+    }
+
+    /*
+     * Open a position over the widest range the pool's tick spacing allows,
+     * deriving the ticks and tick array start indices on-chain
+     */
+    pub fn proxy_open_full_range_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ProxyOpenPosition<'info>>,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+        with_metadata: bool,
+        simulate_only: bool,
+    ) -> Result<()> {
+        instructions::proxy_open_full_range_position(
+            ctx,
             liquidity,
-            amount_0: amount_0_max,
-            amount_1: amount_1_max,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+            amount_0_max,
+            amount_1_max,
+            with_metadata,
+            simulate_only,
+        )
+    }
 
-        Ok(())
+    /*
+     * Open a position spanning `range_bps` below and above the current
+     * price, with tick bounds aligned outward to the pool's tick spacing
+     */
+    pub fn proxy_open_position_around_price<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ProxyOpenPosition<'info>>,
+        range_bps: u16,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+        with_metadata: bool,
+        simulate_only: bool,
+    ) -> Result<()> {
+        instructions::proxy_open_position_around_price(
+            ctx,
+            range_bps,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            with_metadata,
+            simulate_only,
+        )
     }
-}
 
-/*
- * ACCOUNT STRUCTS
- */
+    /*
+     * Let `delegate` open positions whose NFT goes to the owner, who then
+     * need not co-sign each open, e.g. a DAO treasury approving a contributor
+     */
+    pub fn approve_open_on_behalf(ctx: Context<ApproveOpenOnBehalf>) -> Result<()> {
+        instructions::approve_open_on_behalf(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct SetSlippage<'info> {
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    #[account(
-        init_if_needed,
-        payer = owner,
-        space = 8 + UserConfig::SIZE,
-        seeds = [b"user_cfg", owner.key().as_ref()],
-        bump
-    )]
-    pub user_cfg: Account<'info, UserConfig>,
-    pub system_program: Program<'info, System>,
-}
+    /*
+     * Withdraw a delegate's approval, refunding its rent to the owner
+     */
+    pub fn revoke_open_on_behalf(ctx: Context<RevokeOpenOnBehalf>) -> Result<()> {
+        instructions::revoke_open_on_behalf(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct ProxySwap<'info> {
-    pub clmm_program: Program<'info, AmmV3>,
-    pub payer: Signer<'info>,
+    /*
+     * Close an emptied position through Raydium, burning its NFT
+     */
+    pub fn proxy_close_position(ctx: Context<ProxyClosePosition>) -> Result<()> {
+        instructions::proxy_close_position(ctx)
+    }
 
-    #[account(
-        seeds = [b"user_cfg", payer.key().as_ref()],
-        bump
-    )]
-    pub user_cfg: Account<'info, UserConfig>,
+    /*
+     * Create an owner's position registry with its first page. Permissionless,
+     * the registry is only ever written by open/close
+     */
+    pub fn init_position_registry(ctx: Context<InitPositionRegistry>) -> Result<()> {
+        instructions::init_position_registry(ctx)
+    }
 
-    #[account(address = pool_state.load()?.amm_config)]
-    pub amm_config: Box<Account<'info, AmmConfig>>,
+    /*
+     * Append a new page once the existing ones are full
+     */
+    pub fn add_position_registry_page(ctx: Context<AddPositionRegistryPage>) -> Result<()> {
+        instructions::add_position_registry_page(ctx)
+    }
 
-    #[account(mut)]
-    pub pool_state: AccountLoader<'info, PoolState>,
+    /*
+     * Add liquidity to an existing position using Raydium CLMM
+     */
+    pub fn proxy_increase_liquidity<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ProxyIncreaseLiquidity<'info>>,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+        simulate_only: bool,
+    ) -> Result<()> {
+        instructions::proxy_increase_liquidity(
+            ctx,
+            liquidity,
+            amount_0_max,
+            amount_1_max,
+            simulate_only,
+        )
+    }
 
-    #[account(mut)]
-    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /*
+     * Remove liquidity from a position using Raydium CLMM. Raydium also pays
+     * out all fees owed, which the proxy separates from principal
+     */
+    pub fn proxy_decrease_liquidity<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ProxyDecreaseLiquidity<'info>>,
+        liquidity: u128,
+        amount_0_min: u64,
+        amount_1_min: u64,
+        simulate_only: bool,
+    ) -> Result<()> {
+        instructions::proxy_decrease_liquidity(
+            ctx,
+            liquidity,
+            amount_0_min,
+            amount_1_min,
+            simulate_only,
+        )
+    }
 
-    #[account(mut)]
-    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /*
+     * Read-only impermanent-loss snapshot of a position against its recorded
+     * cost basis, returned through return data
+     */
+    pub fn get_impermanent_loss(
+        ctx: Context<GetImpermanentLoss>,
+    ) -> Result<ImpermanentLossSnapshot> {
+        instructions::get_impermanent_loss(ctx)
+    }
 
-    #[account(mut)]
-    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /*
+     * Create the fee checkpoint ring for a position, permissionless
+     */
+    pub fn init_fee_checkpoint(ctx: Context<InitFeeCheckpoint>) -> Result<()> {
+        instructions::init_fee_checkpoint(ctx)
+    }
 
-    #[account(mut)]
-    pub output_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /*
+     * Stamp the position's cumulative fees into its checkpoint ring. Called
+     * periodically by keepers, at most once per FEE_CHECKPOINT_MIN_INTERVAL
+     */
+    pub fn stamp_fee_checkpoint(ctx: Context<StampFeeCheckpoint>) -> Result<()> {
+        instructions::stamp_fee_checkpoint(ctx)
+    }
 
-    #[account(mut, address = pool_state.load()?.observation_key)]
-    pub observation_state: AccountLoader<'info, ObservationState>,
+    /*
+     * Largest liquidity that amount_0 and amount_1 can fund in a range at a
+     * given sqrt price, returned through return data
+     */
+    pub fn get_liquidity_for_amounts(
+        ctx: Context<GetLiquidityForAmounts>,
+        sqrt_price_x64: u128,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        amount_0: u64,
+        amount_1: u64,
+    ) -> Result<u128> {
+        instructions::get_liquidity_for_amounts(
+            ctx,
+            sqrt_price_x64,
+            tick_lower_index,
+            tick_upper_index,
+            amount_0,
+            amount_1,
+        )
+    }
 
-    pub token_program: Program<'info, Token>,
+    /*
+     * Principal, uncollected fees and token_1 value of a position at the
+     * current pool price, returned through return data
+     */
+    pub fn get_position_value(ctx: Context<GetPositionValue>) -> Result<PositionValue> {
+        instructions::get_position_value(ctx)
+    }
 
-    #[account(mut, constraint = tick_array.load()?.pool_id == pool_state.key())]
-    pub tick_array: AccountLoader<'info, TickArrayState>,
-}
+    /*
+     * Liquidity and depth within `tick_radius` ticks of the price, the fee
+     * rate and oracle freshness of a pool, returned through return data
+     */
+    pub fn get_pool_health<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, GetPoolHealth<'info>>,
+        tick_radius: u32,
+    ) -> Result<PoolHealth> {
+        instructions::get_pool_health(ctx, tick_radius)
+    }
 
-#[derive(Accounts)]
-#[instruction(tick_lower_index: i32, tick_upper_index: i32,tick_array_lower_start_index:i32,tick_array_upper_start_index:i32)]
-pub struct ProxyOpenPosition<'info> {
-    pub clmm_program: Program<'info, AmmV3>,
-
-    #[account(mut)]
-    pub payer: Signer<'info>,
-
-    /// CHECK: Receives the position NFT
-    pub position_nft_owner: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub position_nft_mint: Signer<'info>,
-
-    /// CHECK: Token account where position NFT will be minted
-    #[account(mut)]
-    pub position_nft_account: UncheckedAccount<'info>,
-
-    /// CHECK: To store metaplex metadata
-    #[account(mut)]
-    pub metadata_account: UncheckedAccount<'info>,
-
-    #[account(mut)]
-    pub pool_state: AccountLoader<'info, PoolState>,
-
-    /// CHECK: Safety check performed inside function body
-    #[account(
-        mut,
-        seeds = [
-            POSITION_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &tick_lower_index.to_be_bytes(),
-            &tick_upper_index.to_be_bytes(),
-        ],
-        seeds::program = clmm_program,
-        bump,
-    )]
-    pub protocol_position: UncheckedAccount<'info>,
-
-    /// CHECK: Account to mark the lower tick as initialized
-    #[account(
-        mut,
-        seeds = [
-            TICK_ARRAY_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &tick_array_lower_start_index.to_be_bytes(),
-        ],
-        seeds::program = clmm_program,
-        bump,
-    )]
-    pub tick_array_lower: UncheckedAccount<'info>,
-
-    /// CHECK: Account to store data for the position's upper tick
-    #[account(
-        mut,
-        seeds = [
-            TICK_ARRAY_SEED.as_bytes(),
-            pool_state.key().as_ref(),
-            &tick_array_upper_start_index.to_be_bytes(),
-        ],
-        seeds::program = clmm_program,
-        bump,
-    )]
-    pub tick_array_upper: UncheckedAccount<'info>,
-
-    /// CHECK: personal position state
-    #[account(
-        mut,
-        seeds = [POSITION_SEED.as_bytes(), position_nft_mint.key().as_ref()],
-        bump,
-        seeds::program = clmm_program,
-    )]
-    pub personal_position: UncheckedAccount<'info>,
-
-    #[account(
-        mut,
-        token::mint = token_vault_0.mint
-    )]
-    pub token_account_0: Box<InterfaceAccount<'info, TokenAccount>>,
-
-    #[account(
-        mut,
-        token::mint = token_vault_1.mint
-    )]
-    pub token_account_1: Box<InterfaceAccount<'info, TokenAccount>>,
-
-    #[account(
-        mut,
-        constraint = token_vault_0.key() == pool_state.load()?.token_vault_0
-    )]
-    pub token_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
-
-    #[account(
-        mut,
-        constraint = token_vault_1.key() == pool_state.load()?.token_vault_1
-    )]
-    pub token_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
-
-    pub rent: Sysvar<'info, Rent>,
-
-    pub system_program: Program<'info, System>,
-
-    pub token_program: Program<'info, Token>,
-
-    pub associated_token_program: Program<'info, AssociatedToken>,
-
-    pub metadata_program: Program<'info, Metadata>,
-
-    pub token_program_2022: Program<'info, Token2022>,
-
-    #[account(
-        address = token_vault_0.mint
-    )]
-    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
-
-    #[account(
-        address = token_vault_1.mint
-    )]
-    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
-}
+    /*
+     * Governance flags, pause state and fees, plus any scheduled flag
+     * change, returned through return data
+     */
+    pub fn get_status(ctx: Context<GetStatus>) -> Result<ProgramStatus> {
+        instructions::get_status(ctx)
+    }
 
-/*
- * State and helpers
- */
-#[account]
-pub struct UserConfig {
-    pub owner: Pubkey,
-    pub slippage_bps: u16,
-}
-impl UserConfig {
-    pub const SIZE: usize = 32 + 2;
-}
+    /*
+     * Move part of a position's liquidity into a new position NFT over the
+     * same range. Fees owed to the source position are paid out to the owner
+     */
+    pub fn split_position<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SplitPosition<'info>>,
+        liquidity_to_move: u128,
+        with_metadata: bool,
+    ) -> Result<()> {
+        instructions::split_position(ctx, liquidity_to_move, with_metadata)
+    }
 
-/*
- * Compute slippage tolerance threshold (min output / max input)
- */
-fn compute_slippage_threshold(expected: u64, bps: u16, is_base_input: bool) -> u64 {
-    if is_base_input {
-        ((expected as u128 * (10_000u128 - bps as u128)) / 10_000u128) as u64
-    } else {
-        ((expected as u128 * (10_000u128 + bps as u128)) / 10_000u128) as u64
+    /*
+     * Fold a position into another with the same pool and range: withdraw
+     * all of the source, deposit it into the target, close the source
+     */
+    pub fn merge_positions<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MergePositions<'info>>,
+    ) -> Result<()> {
+        instructions::merge_positions(ctx)
     }
-}
 
-/*
- * Error codes
- */
-#[error_code]
-pub enum CustomError {
-    #[msg("Invalid slippage basis points")]
-    InvalidSlippage,
+    /*
+     * Lock a position NFT in a PDA and stream its liquidity to a beneficiary
+     * linearly between start_ts and end_ts
+     */
+    pub fn create_position_stream(
+        ctx: Context<CreatePositionStream>,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        instructions::create_position_stream(ctx, start_ts, end_ts)
+    }
 
-    #[msg("Invalid tick range")]
-    InvalidTickRange,
+    /*
+     * Withdraw the liquidity unlocked so far to the beneficiary. Once all of
+     * it is claimed the emptied NFT is released and the stream closed
+     */
+    pub fn claim_unlocked<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ClaimUnlocked<'info>>,
+        amount_0_min: u64,
+        amount_1_min: u64,
+    ) -> Result<()> {
+        instructions::claim_unlocked(ctx, amount_0_min, amount_1_min)
+    }
 
-    #[msg("Zero liquidity")]
-    ZeroLiquidity,
+    /*
+     * Lock a position NFT in a shared position PDA. The creator receives one
+     * share per unit of the position's liquidity
+     */
+    pub fn create_shared_position(ctx: Context<CreateSharedPosition>) -> Result<()> {
+        instructions::create_shared_position(ctx)
+    }
 
-    #[msg("Zero deposit")]
-    ZeroDeposit,
+    /*
+     * Add liquidity to a shared position for one share per unit of liquidity.
+     * Pending fees are harvested and paid out first
+     */
+    pub fn shared_deposit<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SharedPositionAction<'info>>,
+        liquidity: u128,
+        amount_0_max: u64,
+        amount_1_max: u64,
+    ) -> Result<()> {
+        instructions::shared_deposit(ctx, liquidity, amount_0_max, amount_1_max)
+    }
 
-    #[msg("Invalid vault")]
-    InvalidVault,
+    /*
+     * Redeem shares of a shared position for the liquidity they represent,
+     * paid out together with the contributor's pending fees
+     */
+    pub fn shared_withdraw<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SharedPositionAction<'info>>,
+        shares: u128,
+        amount_0_min: u64,
+        amount_1_min: u64,
+    ) -> Result<()> {
+        instructions::shared_withdraw(ctx, shares, amount_0_min, amount_1_min)
+    }
 
-    #[msg("Zero swap amount")]
-    ZeroSwapAmount,
+    /*
+     * Harvest the shared position's fees and pay out the contributor's part
+     */
+    pub fn claim_shared_fees<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SharedPositionAction<'info>>,
+    ) -> Result<()> {
+        instructions::claim_shared_fees(ctx)
+    }
 
-    #[msg("Invalid expected amount")]
-    InvalidExpectedAmount,
-}
+    /*
+     * Send what is left in a shared position's token account to its creator
+     * once every share is withdrawn, permissionless
+     */
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        instructions::sweep_dust(ctx)
+    }
 
-#[event]
-pub struct SlippageSet {
-    pub owner: Pubkey,
-    pub slippage_bps: u16,
-    pub timestamp: i64,
-}
+    /*
+     * Create the protocol governance config. Only the program's upgrade
+     * authority can call it, and it becomes the first admin
+     */
+    pub fn init_global_config(
+        ctx: Context<InitGlobalConfig>,
+        treasury: Pubkey,
+        protocol_fee_bps: u16,
+        insurance_share_bps: u16,
+    ) -> Result<()> {
+        instructions::init_global_config(ctx, treasury, protocol_fee_bps, insurance_share_bps)
+    }
 
-#[event]
-pub struct SwapExecuted {
-    pub user: Pubkey,
-    pub pool: Pubkey,
-    pub amount_in: u64,
-    pub amount_out: u64,
-    pub expected_amount: u64,
-    pub slippage_bps: u16,
-    pub is_base_input: bool,
-    pub timestamp: i64,
-}
+    /*
+     * Hand governance to a new admin, e.g. a multisig or DAO PDA
+     */
+    pub fn set_global_admin(ctx: Context<UpdateGlobalConfig>, new_admin: Pubkey) -> Result<()> {
+        instructions::set_global_admin(ctx, new_admin)
+    }
 
-#[event]
-pub struct PositionOpened {
-    pub user: Pubkey,
-    pub pool: Pubkey,
-    pub position_nft: Pubkey,
-    pub tick_lower: i32,
-    pub tick_upper: i32,
-    pub liquidity: u128,
-    pub amount_0: u64,
-    pub amount_1: u64,
-    pub timestamp: i64,
-}
+    /*
+     * Set or clear `FLAG_PAUSED` and `FLAG_PAUSE_SWAPS`, effective
+     * immediately, admin only. Other flags are left as they are
+     */
+    pub fn set_pause_flags(ctx: Context<UpdateGlobalConfig>, pause_flags: u8) -> Result<()> {
+        instructions::set_pause_flags(ctx, pause_flags)
+    }
 
-#[event]
-pub struct LiquidityIncreased {
-    pub user: Pubkey,
-    pub position_nft: Pubkey,
-    pub liquidity_added: u128,
-    pub amount_0_added: u64,
-    pub amount_1_added: u64,
-    pub timestamp: i64,
-}
+    /*
+     * Announce new fee settings, non-pause flags, creator share and
+     * round-trip policy that take effect at `activation_slot`, at least
+     * `MIN_PARAM_CHANGE_NOTICE_SLOTS` ahead, admin only. Replaces any change
+     * already pending
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub fn schedule_param_change(
+        ctx: Context<ScheduleParamChange>,
+        treasury: Pubkey,
+        protocol_fee_bps: u16,
+        insurance_share_bps: u16,
+        flags: u8,
+        creator_share_bps: u16,
+        round_trip_window_secs: u32,
+        max_round_trips: u16,
+        activation_slot: u64,
+    ) -> Result<()> {
+        instructions::schedule_param_change(
+            ctx,
+            treasury,
+            protocol_fee_bps,
+            insurance_share_bps,
+            flags,
+            creator_share_bps,
+            round_trip_window_secs,
+            max_round_trips,
+            activation_slot,
+        )
+    }
 
-#[event]
-pub struct LiquidityDecreased {
-    pub user: Pubkey,
-    pub position_nft: Pubkey,
-    pub liquidity_removed: u128,
-    pub amount_0_removed: u64,
-    pub amount_1_removed: u64,
-    pub timestamp: i64,
+    /*
+     * Write the pending change into the global config once its activation
+     * slot is reached, permissionless
+     */
+    pub fn apply_param_change(ctx: Context<ApplyParamChange>) -> Result<()> {
+        instructions::apply_param_change(ctx)
+    }
+
+    /*
+     * Drop the pending change, admin only
+     */
+    pub fn cancel_param_change(ctx: Context<CancelParamChange>) -> Result<()> {
+        instructions::cancel_param_change(ctx)
+    }
+
+    /*
+     * Create the insurance fund vault for one mint, permissionless
+     */
+    pub fn init_insurance_vault(ctx: Context<InitInsuranceVault>) -> Result<()> {
+        instructions::init_insurance_vault(ctx)
+    }
+
+    /*
+     * Compensate a user out of the insurance fund, admin only
+     */
+    pub fn pay_insurance_claim(ctx: Context<PayInsuranceClaim>, amount: u64) -> Result<()> {
+        instructions::pay_insurance_claim(ctx, amount)
+    }
+
+    /*
+     * Create a pool's creator fee vault for one mint, permissionless. Only
+     * pools created with `proxy_create_pool` have one
+     */
+    pub fn init_creator_fee_vault(ctx: Context<InitCreatorFeeVault>) -> Result<()> {
+        instructions::init_creator_fee_vault(ctx)
+    }
+
+    /*
+     * Pay out a creator fee vault to the pool's recorded creator
+     */
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        instructions::claim_creator_fees(ctx)
+    }
+
+    /*
+     * Set how many points swap volume and in-range LP liquidity earn, admin
+     * only. Zero rates pause accrual
+     */
+    pub fn set_points_rates(
+        ctx: Context<SetPointsRates>,
+        swap_points_per_unit: u64,
+        lp_points_per_unit: u64,
+    ) -> Result<()> {
+        instructions::set_points_rates(ctx, swap_points_per_unit, lp_points_per_unit)
+    }
+
+    /*
+     * Create an owner's points ledger, permissionless
+     */
+    pub fn init_points_ledger(ctx: Context<InitPointsLedger>) -> Result<()> {
+        instructions::init_points_ledger(ctx)
+    }
+
+    /*
+     * Start a position's LP points clock, permissionless
+     */
+    pub fn init_position_points(ctx: Context<InitPositionPoints>) -> Result<()> {
+        instructions::init_position_points(ctx)
+    }
+
+    /*
+     * Mark every unclaimed point as claimed and publish the amount as return
+     * data, so a campaign program can pay out against it through CPI
+     */
+    pub fn claim_points(ctx: Context<ClaimPoints>) -> Result<()> {
+        instructions::claim_points(ctx)
+    }
+
+    /*
+     * Accept protocol fees owed in `input_mint` paid in `fee_mint` instead,
+     * priced at the TWAP of a Raydium pool pairing the two, admin only. Fees
+     * fail to convert while the pool's latest observation is older than
+     * `max_staleness_secs`, or its tick is more than `max_deviation_ticks`
+     * from the TWAP
+     */
+    pub fn set_fee_oracle(
+        ctx: Context<SetFeeOracle>,
+        twap_window_secs: u32,
+        max_staleness_secs: u32,
+        max_deviation_ticks: u32,
+    ) -> Result<()> {
+        instructions::set_fee_oracle(
+            ctx,
+            twap_window_secs,
+            max_staleness_secs,
+            max_deviation_ticks,
+        )
+    }
+
+    /*
+     * Stop accepting fees in `fee_mint` for `input_mint`, admin only
+     */
+    pub fn close_fee_oracle(ctx: Context<CloseFeeOracle>) -> Result<()> {
+        instructions::close_fee_oracle(ctx)
+    }
+
+    /*
+     * Register the programs every swap is validated by, admin only. An empty
+     * list removes them all
+     */
+    pub fn set_swap_validators(
+        ctx: Context<SetSwapValidators>,
+        programs: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_swap_validators(ctx, programs)
+    }
+
+    /*
+     * Queue a token account for the owner's destination allowlist. It only
+     * counts after `DESTINATION_ADD_DELAY` seconds, so a stolen key cannot
+     * add its own account and withdraw right away
+     */
+    pub fn add_allowed_destination(
+        ctx: Context<UpdateDestinationAllowlist>,
+        token_account: Pubkey,
+    ) -> Result<()> {
+        instructions::add_allowed_destination(ctx, token_account)
+    }
+
+    /*
+     * Drop a token account from the allowlist, effective immediately
+     */
+    pub fn remove_allowed_destination(
+        ctx: Context<UpdateDestinationAllowlist>,
+        token_account: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_allowed_destination(ctx, token_account)
+    }
+
+    /*
+     * Turn enforcement of the allowlist on, at once, or off, after
+     * `DESTINATION_ADD_DELAY` seconds for the same reason adds wait
+     */
+    pub fn set_destination_allowlist_enabled(
+        ctx: Context<UpdateDestinationAllowlist>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_destination_allowlist_enabled(ctx, enabled)
+    }
+
+    /*
+     * Designate up to `MAX_GUARDIANS` keys, `threshold` of which can move the
+     * owner's config to a new key. Replacing the set drops any pending
+     * recovery
+     */
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::set_guardians(ctx, guardians, threshold)
+    }
+
+    /*
+     * Approve moving the owner's config to `new_owner`. Approving an owner
+     * other than the pending one restarts the proposal. Once `threshold`
+     * guardians agree, the recovery can execute after `RECOVERY_DELAY`
+     */
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>, new_owner: Pubkey) -> Result<()> {
+        instructions::approve_recovery(ctx, new_owner)
+    }
+
+    /*
+     * Drop the pending recovery; the owner's answer to a rogue proposal
+     * during the delay
+     */
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::cancel_recovery(ctx)
+    }
+
+    /*
+     * Move an approved recovery's config and guardian set to the new owner's
+     * PDAs once the delay has passed, permissionless. The old accounts are
+     * closed to the new owner
+     */
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        instructions::execute_recovery(ctx)
+    }
+
+    /*
+     * Let `session_key` sign the instructions in `scope`, a bitmask of
+     * `SESSION_SCOPE_*` flags, on the owner's behalf until `expiry`
+     */
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        expiry: i64,
+        scope: u8,
+    ) -> Result<()> {
+        instructions::create_session(ctx, session_key, expiry, scope)
+    }
+
+    /*
+     * End a session early, by its owner or the session key itself
+     */
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session(ctx)
+    }
+
+    /*
+     * Bound what a session's swaps can do: the pools (any when empty), the
+     * direction, and per input mint the input of one swap and of all swaps
+     * together. With any spend limits, other input mints are refused. Zero
+     * amounts mean no limit; what was already spent in a mint still counts
+     */
+    pub fn set_session_limits(
+        ctx: Context<SetSessionLimits>,
+        allowed_pools: Vec<Pubkey>,
+        direction: u8,
+        spend_limits: Vec<SessionSpendLimit>,
+    ) -> Result<()> {
+        instructions::set_session_limits(ctx, allowed_pools, direction, spend_limits)
+    }
+
+    /*
+     * Swap signed by a session key. The owner's token accounts are spent
+     * through the `session_authority` PDA, which they approve as delegate
+     */
+    pub fn session_swap<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, SessionSwap<'info>>,
+        amount: u64,
+        expected_other_amount: u64,
+        sqrt_price_limit_x64: u128,
+        is_base_input: bool,
+    ) -> Result<()> {
+        instructions::session_swap(
+            ctx,
+            amount,
+            expected_other_amount,
+            sqrt_price_limit_x64,
+            is_base_input,
+        )
+    }
+
+    /*
+     * Set the owner's slippage with a session key
+     */
+    pub fn session_set_slippage(ctx: Context<SessionSetSlippage>, bps: u16) -> Result<()> {
+        instructions::session_set_slippage(ctx, bps)
+    }
+
+    /*
+     * Escrow `amount_in` for a Dutch auction: the least output accepted
+     * falls linearly from `start_min_out` at `start_ts` to `end_min_out` at
+     * `end_ts`, and keepers fill the order through the pool once it pays
+     * that much. A transfer-fee mint escrows, and the fill sells, `amount_in`
+     * net of the fee
+     */
+    pub fn create_dutch_order(
+        ctx: Context<CreateDutchOrder>,
+        order_id: u64,
+        amount_in: u64,
+        start_min_out: u64,
+        end_min_out: u64,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        instructions::create_dutch_order(
+            ctx,
+            order_id,
+            amount_in,
+            start_min_out,
+            end_min_out,
+            start_ts,
+            end_ts,
+        )
+    }
+
+    /*
+     * Fill a Dutch order in full at the current auction price or better,
+     * permissionless. The escrow and order are closed to the owner
+     */
+    pub fn fill_dutch_order<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FillDutchOrder<'info>>,
+    ) -> Result<()> {
+        instructions::fill_dutch_order(ctx)
+    }
+
+    /*
+     * Return an unfilled Dutch order's escrow to its owner and close it
+     */
+    pub fn cancel_dutch_order(ctx: Context<CancelDutchOrder>) -> Result<()> {
+        instructions::cancel_dutch_order(ctx)
+    }
+
+    /*
+     * Escrow `amount_in` to be sold through the pool at `floor_price_x64`
+     * (output per unit of input, Q64.64) or better, at most `daily_max` a
+     * day. Keepers fill it in parts
+     */
+    pub fn create_floor_order(
+        ctx: Context<CreateFloorOrder>,
+        order_id: u64,
+        amount_in: u64,
+        floor_price_x64: u128,
+        daily_max: u64,
+    ) -> Result<()> {
+        instructions::create_floor_order(ctx, order_id, amount_in, floor_price_x64, daily_max)
+    }
+
+    /*
+     * Sell `amount_in` of a floor order's escrow at the floor price or
+     * better, within the day's allowance, permissionless. The escrow and
+     * order are closed to the owner once sold out
+     */
+    pub fn fill_floor_order<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, FillFloorOrder<'info>>,
+        amount_in: u64,
+    ) -> Result<()> {
+        instructions::fill_floor_order(ctx, amount_in)
+    }
+
+    /*
+     * Return what remains of a floor order's escrow to its owner and close it
+     */
+    pub fn cancel_floor_order(ctx: Context<CancelFloorOrder>) -> Result<()> {
+        instructions::cancel_floor_order(ctx)
+    }
+
+    /*
+     * First phase of a commit-reveal swap: store a hash of the swap's
+     * parameters and a secret salt, see `swap_commitment_hash`. Replaces any
+     * earlier commitment of the payer
+     */
+    pub fn commit_swap(ctx: Context<CommitSwap>, hash: [u8; 32]) -> Result<()> {
+        instructions::commit_swap(ctx, hash)
+    }
+
+    /*
+     * Second phase: swap with the committed parameters, revealed with their
+     * salt, between `MIN_REVEAL_DELAY_SLOTS` and `REVEAL_WINDOW_SLOTS` slots
+     * after the commitment. Searchers only learn the pool, side and size
+     * once the swap lands
+     */
+    pub fn reveal_and_swap<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, RevealAndSwap<'info>>,
+        amount: u64,
+        expected_other_amount: u64,
+        sqrt_price_limit_x64: u128,
+        is_base_input: bool,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_and_swap(
+            ctx,
+            amount,
+            expected_other_amount,
+            sqrt_price_limit_x64,
+            is_base_input,
+            salt,
+        )
+    }
+
+    /*
+     * Create a Raydium CLMM pool through the proxy, the first step of a
+     * launch: follow with `proxy_open_full_range_position`, and optionally
+     * `create_position_stream` to lock the position, in the same transaction.
+     * Records the creator, who earns a share of the pool's protocol fees
+     */
+    pub fn proxy_create_pool(
+        ctx: Context<ProxyCreatePool>,
+        sqrt_price_x64: u128,
+        open_time: u64,
+    ) -> Result<()> {
+        instructions::proxy_create_pool(ctx, sqrt_price_x64, open_time)
+    }
+
+    /*
+     * Start a reward emission on a pool, funded from the creator's token
+     * account. Only the creator Raydium recorded for the pool can call it
+     */
+    pub fn proxy_initialize_reward(
+        ctx: Context<ProxyInitializeReward>,
+        open_time: u64,
+        end_time: u64,
+        emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        instructions::proxy_initialize_reward(ctx, open_time, end_time, emissions_per_second_x64)
+    }
+
+    /*
+     * Change the rate or window of one of the pool's rewards, creator only
+     */
+    pub fn proxy_set_reward_params<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, ProxySetRewardParams<'info>>,
+        reward_index: u8,
+        emissions_per_second_x64: u128,
+        open_time: u64,
+        end_time: u64,
+    ) -> Result<()> {
+        instructions::proxy_set_reward_params(
+            ctx,
+            reward_index,
+            emissions_per_second_x64,
+            open_time,
+            end_time,
+        )
+    }
+
+    /*
+     * Move constant-product liquidity into a concentrated position: withdraw
+     * LP tokens from a Raydium CPMM pool and open a position over the chosen
+     * ticks in the CLMM pool of the same mints with the proceeds, atomically.
+     * Whatever the range cannot take stays in the user's token accounts
+     */
+    #[allow(clippy::too_many_arguments)]
+    pub fn migrate_cpmm_to_clmm<'a, 'b, 'c: 'info, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MigrateCpmmToClmm<'info>>,
+        lp_token_amount: u64,
+        minimum_token_0_amount: u64,
+        minimum_token_1_amount: u64,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_array_lower_start_index: i32,
+        tick_array_upper_start_index: i32,
+    ) -> Result<()> {
+        instructions::migrate_cpmm_to_clmm(
+            ctx,
+            lp_token_amount,
+            minimum_token_0_amount,
+            minimum_token_1_amount,
+            tick_lower_index,
+            tick_upper_index,
+            tick_array_lower_start_index,
+            tick_array_upper_start_index,
+        )
+    }
+
+    /*
+     * Initialize an OpenBook market for a new token, the first step of an
+     * AMM v4 launch. The client allocates the market, queue and book
+     * accounts owned by OpenBook, and the vaults owned by the market's vault
+     * signer, beforehand
+     */
+    pub fn proxy_create_openbook_market(
+        ctx: Context<ProxyCreateOpenbookMarket>,
+        coin_lot_size: u64,
+        pc_lot_size: u64,
+        vault_signer_nonce: u64,
+        pc_dust_threshold: u64,
+    ) -> Result<()> {
+        instructions::proxy_create_openbook_market(
+            ctx,
+            coin_lot_size,
+            pc_lot_size,
+            vault_signer_nonce,
+            pc_dust_threshold,
+        )
+    }
+
+    /*
+     * Create a Raydium AMM v4 pool on an OpenBook market and deposit the
+     * initial liquidity, which as constant-product liquidity spans the full
+     * price range. The creator pays Raydium's create fee and receives the LP
+     * tokens; follow with `lock_amm_lp` in the same transaction to lock them
+     * from the pool's first block
+     */
+    pub fn proxy_create_amm_pool(
+        ctx: Context<ProxyCreateAmmPool>,
+        nonce: u8,
+        open_time: u64,
+        init_coin_amount: u64,
+        init_pc_amount: u64,
+    ) -> Result<()> {
+        instructions::proxy_create_amm_pool(ctx, nonce, open_time, init_coin_amount, init_pc_amount)
+    }
+
+    /*
+     * Lock AMM LP tokens in a program escrow until `unlock_ts`. One lock per
+     * owner and LP mint
+     */
+    pub fn lock_amm_lp(ctx: Context<LockAmmLp>, amount: u64, unlock_ts: i64) -> Result<()> {
+        instructions::lock_amm_lp(ctx, amount, unlock_ts)
+    }
+
+    /*
+     * Return locked LP tokens to the owner once unlocked, closing the lock
+     */
+    pub fn unlock_amm_lp(ctx: Context<UnlockAmmLp>) -> Result<()> {
+        instructions::unlock_amm_lp(ctx)
+    }
 }
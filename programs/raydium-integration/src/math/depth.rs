@@ -0,0 +1,135 @@
+//! Liquidity around the current price, over supplied tick arrays.
+use std::ops::Deref;
+
+use anchor_lang::prelude::*;
+use raydium_amm_v3::states::{PoolState, TickArrayState, TICK_ARRAY_SIZE};
+
+use super::{amount_0_delta, amount_1_delta, sqrt_price_at_tick, MAX_TICK, MIN_TICK};
+use crate::CustomError;
+
+/// Tick arrays either side of zero that Raydium's default bitmap tracks
+const BITMAP_HALF_RANGE: i32 = 512;
+
+/// Liquidity within a band of ticks around the current price
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LiquidityDepth {
+    /// Least liquidity active anywhere in the band
+    pub min_liquidity: u128,
+    /// token_0 a swap pushing the price to the top of the band takes out
+    pub depth_0: u64,
+    /// token_1 a swap pushing the price to the bottom of the band takes out
+    pub depth_1: u64,
+}
+
+/*
+ * Whether Raydium's default bitmap marks the tick array starting at
+ * `start_index` initialized; `None` past its range, which only the bitmap
+ * extension covers
+ */
+fn bitmap_initialized(pool_state: &PoolState, start_index: i32) -> Option<bool> {
+    let ticks_in_array = TICK_ARRAY_SIZE * pool_state.tick_spacing as i32;
+    let offset = start_index / ticks_in_array + BITMAP_HALF_RANGE;
+    if !(0..2 * BITMAP_HALF_RANGE).contains(&offset) {
+        return None;
+    }
+    let bitmap = pool_state.tick_array_bitmap;
+    Some(bitmap[offset as usize / 64] & (1u64 << (offset % 64)) != 0)
+}
+
+/*
+ * Walk `tick_radius` ticks up and down from the current price over the
+ * supplied tick arrays. Every initialized array of the band the default
+ * bitmap knows of must be supplied, or liquidity it holds would go unseen
+ */
+pub fn liquidity_depth<T: Deref<Target = TickArrayState>>(
+    pool_state: &PoolState,
+    tick_arrays: &[T],
+    tick_radius: u32,
+) -> Result<LiquidityDepth> {
+    let tick_current = pool_state.tick_current;
+    let tick_spacing = pool_state.tick_spacing;
+    let radius = tick_radius.min((MAX_TICK - MIN_TICK) as u32) as i32;
+    let band_lower = tick_current.saturating_sub(radius).max(MIN_TICK);
+    let band_upper = tick_current.saturating_add(radius).min(MAX_TICK);
+
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    let mut start_index = super::tick_array_start_index(band_lower, tick_spacing);
+    while start_index <= band_upper {
+        if bitmap_initialized(pool_state, start_index) == Some(true) {
+            require!(
+                tick_arrays
+                    .iter()
+                    .any(|tick_array| tick_array.start_tick_index == start_index),
+                CustomError::TickArrayMissing
+            );
+        }
+        start_index += ticks_in_array;
+    }
+
+    // Initialized ticks strictly inside the band, crossings at its edges
+    // only change liquidity beyond it
+    let mut ticks: Vec<(i32, i128)> = tick_arrays
+        .iter()
+        .flat_map(|tick_array| tick_array.ticks.iter())
+        .filter(|tick| tick.liquidity_gross > 0)
+        .filter(|tick| tick.tick > band_lower && tick.tick < band_upper)
+        .map(|tick| (tick.tick, tick.liquidity_net))
+        .collect();
+    ticks.sort_unstable_by_key(|(tick, _)| *tick);
+    ticks.dedup_by_key(|(tick, _)| *tick);
+
+    let mut depth = LiquidityDepth {
+        min_liquidity: pool_state.liquidity,
+        ..Default::default()
+    };
+
+    // Up: token_0 leaves the pool, liquidity_net applies as each tick is crossed
+    let mut sqrt_price_x64 = pool_state.sqrt_price_x64;
+    let mut liquidity = pool_state.liquidity;
+    for &(tick, liquidity_net) in ticks.iter().filter(|(tick, _)| *tick > tick_current) {
+        let sqrt_price_tick_x64 = sqrt_price_at_tick(tick)?;
+        depth.depth_0 = depth.depth_0.saturating_add(amount_0_delta(
+            sqrt_price_x64,
+            sqrt_price_tick_x64,
+            liquidity,
+            false,
+        ));
+        sqrt_price_x64 = sqrt_price_tick_x64;
+        liquidity = liquidity
+            .checked_add_signed(liquidity_net)
+            .ok_or(CustomError::MathOverflow)?;
+        depth.min_liquidity = depth.min_liquidity.min(liquidity);
+    }
+    depth.depth_0 = depth.depth_0.saturating_add(amount_0_delta(
+        sqrt_price_x64,
+        sqrt_price_at_tick(band_upper)?,
+        liquidity,
+        false,
+    ));
+
+    // Down: token_1 leaves the pool, liquidity_net is undone at each tick
+    let mut sqrt_price_x64 = pool_state.sqrt_price_x64;
+    let mut liquidity = pool_state.liquidity;
+    for &(tick, liquidity_net) in ticks.iter().rev().filter(|(tick, _)| *tick <= tick_current) {
+        let sqrt_price_tick_x64 = sqrt_price_at_tick(tick)?;
+        depth.depth_1 = depth.depth_1.saturating_add(amount_1_delta(
+            sqrt_price_tick_x64,
+            sqrt_price_x64,
+            liquidity,
+            false,
+        ));
+        sqrt_price_x64 = sqrt_price_tick_x64;
+        liquidity = liquidity
+            .checked_add_signed(-liquidity_net)
+            .ok_or(CustomError::MathOverflow)?;
+        depth.min_liquidity = depth.min_liquidity.min(liquidity);
+    }
+    depth.depth_1 = depth.depth_1.saturating_add(amount_1_delta(
+        sqrt_price_at_tick(band_lower)?,
+        sqrt_price_x64,
+        liquidity,
+        false,
+    ));
+
+    Ok(depth)
+}
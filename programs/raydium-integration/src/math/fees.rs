@@ -0,0 +1,167 @@
+//! Slippage thresholds and protocol, trade and position fees.
+use anchor_lang::prelude::*;
+use raydium_amm_v3::libraries::big_num::U256;
+use raydium_amm_v3::states::{PersonalPositionState, PoolState, TickArrayState, TICK_ARRAY_SIZE};
+
+use crate::{CustomError, FEE_RATE_DENOMINATOR};
+
+/*
+ * Slippage threshold of a swap expecting `expected` of the other token,
+ * with a tolerance of `bps` (at most 10_000):
+ *
+ * - exact input (`is_base_input`): the minimum output,
+ *   `floor(expected * (10_000 - bps) / 10_000)`
+ * - exact output: the maximum input,
+ *   `ceil(expected * (10_000 + bps) / 10_000)`, saturating at u64::MAX
+ *
+ * Both round away from the expected amount, so the threshold is never
+ * tighter than the tolerance and a swap at exactly `bps` passes
+ */
+pub fn compute_slippage_threshold(expected: u64, bps: u16, is_base_input: bool) -> u64 {
+    let threshold = if is_base_input {
+        (expected as u128 * (10_000 - bps as u128)) / 10_000
+    } else {
+        (expected as u128 * (10_000 + bps as u128)).div_ceil(10_000)
+    };
+    threshold.min(u64::MAX as u128) as u64
+}
+
+/*
+ * Slippage a swap realized, in basis points of the expected amount scaled
+ * to the part of `amount` that executed: output short of it for exact
+ * input, input above it for exact output. Negative when the swap beat the
+ * expectation
+ */
+pub fn realized_slippage_bps(
+    amount: u64,
+    expected_other_amount: u64,
+    amount_spent: u64,
+    amount_received: u64,
+    is_base_input: bool,
+) -> i64 {
+    let (executed, other) = if is_base_input {
+        (amount_spent, amount_received)
+    } else {
+        (amount_received, amount_spent)
+    };
+    let expected = expected_other_amount as i128 * executed as i128 / amount.max(1) as i128;
+    if expected == 0 {
+        return 0;
+    }
+    let shortfall = if is_base_input {
+        expected - other as i128
+    } else {
+        other as i128 - expected
+    };
+    (shortfall * 10_000 / expected).clamp(i64::MIN as i128, i64::MAX as i128) as i64
+}
+
+/*
+ * Protocol fee on a pool input, rounded down in the user's favor
+ */
+pub fn compute_protocol_fee(amount: u64, protocol_fee_bps: u16) -> u64 {
+    (amount as u128 * protocol_fee_bps as u128 / 10_000) as u64
+}
+
+/*
+ * Share of `amount` a pool can take when the protocol fee on that share is
+ * paid from `amount` too, so the two together never exceed it
+ */
+pub fn amount_before_protocol_fee(amount: u64, protocol_fee_bps: u16) -> u64 {
+    (amount as u128 * 10_000 / (10_000 + protocol_fee_bps as u128)) as u64
+}
+
+/*
+ * Fee growth outside a tick, read from the tick array that holds it
+ */
+fn tick_fee_growth_outside(
+    tick_array: &TickArrayState,
+    tick_index: i32,
+    tick_spacing: u16,
+) -> Result<(u128, u128)> {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    require!(
+        tick_index >= tick_array.start_tick_index
+            && tick_index < tick_array.start_tick_index + ticks_in_array,
+        CustomError::InvalidTickArray
+    );
+    let offset = ((tick_index - tick_array.start_tick_index) / tick_spacing as i32) as usize;
+    let tick = &tick_array.ticks[offset];
+    Ok((tick.fee_growth_outside_0_x64, tick.fee_growth_outside_1_x64))
+}
+
+/*
+ * Fees owed to a position including growth not yet settled by Raydium,
+ * mirroring Raydium's fee-growth-inside accounting
+ */
+pub fn uncollected_fees(
+    pool_state: &PoolState,
+    position: &PersonalPositionState,
+    tick_array_lower: &TickArrayState,
+    tick_array_upper: &TickArrayState,
+) -> Result<(u64, u64)> {
+    let (lower_0, lower_1) = tick_fee_growth_outside(
+        tick_array_lower,
+        position.tick_lower_index,
+        pool_state.tick_spacing,
+    )?;
+    let (upper_0, upper_1) = tick_fee_growth_outside(
+        tick_array_upper,
+        position.tick_upper_index,
+        pool_state.tick_spacing,
+    )?;
+
+    let inside = |global: u128, lower: u128, upper: u128| {
+        let below = if pool_state.tick_current >= position.tick_lower_index {
+            lower
+        } else {
+            global.wrapping_sub(lower)
+        };
+        let above = if pool_state.tick_current < position.tick_upper_index {
+            upper
+        } else {
+            global.wrapping_sub(upper)
+        };
+        global.wrapping_sub(below).wrapping_sub(above)
+    };
+    let accrued = |inside: u128, last: u128| {
+        let fees = (U256::from(inside.wrapping_sub(last)) * U256::from(position.liquidity)) >> 64;
+        if fees > U256::from(u64::MAX) {
+            u64::MAX
+        } else {
+            fees.as_u64()
+        }
+    };
+
+    let inside_0 = inside(pool_state.fee_growth_global_0_x64, lower_0, upper_0);
+    let inside_1 = inside(pool_state.fee_growth_global_1_x64, lower_1, upper_1);
+    Ok((
+        position
+            .token_fees_owed_0
+            .saturating_add(accrued(inside_0, position.fee_growth_inside_0_last_x64)),
+        position
+            .token_fees_owed_1
+            .saturating_add(accrued(inside_1, position.fee_growth_inside_1_last_x64)),
+    ))
+}
+
+/*
+ * Scale a full-size slippage threshold down to the filled part of a swap:
+ * minimum output rounds up, maximum input rounds down
+ */
+pub fn prorate_threshold(threshold: u64, filled: u64, requested: u64, is_base_input: bool) -> u64 {
+    let scaled = threshold as u128 * filled as u128;
+    let prorated = if is_base_input {
+        scaled.div_ceil(requested as u128)
+    } else {
+        scaled / requested as u128
+    };
+    prorated as u64
+}
+
+/*
+ * AMM trade fee charged on an input amount, rounded up like Raydium does
+ */
+pub fn compute_trade_fee(amount_in: u64, trade_fee_rate: u32) -> u64 {
+    ((amount_in as u128 * trade_fee_rate as u128).div_ceil(FEE_RATE_DENOMINATOR as u128)) as u64
+}
@@ -0,0 +1,356 @@
+//! Tick, price and liquidity math shared by the program and off-chain clients.
+//!
+//! Build with the `no-entrypoint` or `cli` feature to use it from another crate.
+//! Sqrt prices are Q64.64 fixed point, the format Raydium stores in
+//! `PoolState::sqrt_price_x64`. Human prices are token_1 per token_0 in whole
+//! tokens.
+use anchor_lang::prelude::*;
+use raydium_amm_v3::libraries::{big_num::U256, swap_math, tick_math};
+use raydium_amm_v3::states::TICK_ARRAY_SIZE;
+
+use crate::CustomError;
+
+mod depth;
+mod fees;
+mod swap;
+
+pub use depth::*;
+pub use fees::*;
+pub use swap::*;
+
+pub use swap_math::SwapStep;
+pub use tick_math::{MAX_SQRT_PRICE_X64, MAX_TICK, MIN_SQRT_PRICE_X64, MIN_TICK};
+
+/// 2^64, the scale of a Q64.64 sqrt price
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+/// Price ratio between two adjacent ticks
+const TICK_BASE: f64 = 1.0001;
+
+/*
+ * Q64.64 sqrt price at a tick
+ */
+pub fn sqrt_price_at_tick(tick: i32) -> Result<u128> {
+    tick_math::get_sqrt_price_at_tick(tick)
+}
+
+/*
+ * Greatest tick whose sqrt price is at or below `sqrt_price_x64`
+ */
+pub fn tick_at_sqrt_price(sqrt_price_x64: u128) -> Result<i32> {
+    tick_math::get_tick_at_sqrt_price(sqrt_price_x64)
+}
+
+/*
+ * Human price of a Q64.64 sqrt price, adjusted for mint decimals
+ */
+pub fn sqrt_price_to_price(sqrt_price_x64: u128, decimals_0: u8, decimals_1: u8) -> f64 {
+    let sqrt_price = sqrt_price_x64 as f64 / Q64;
+    sqrt_price * sqrt_price * decimals_factor(decimals_0, decimals_1)
+}
+
+/*
+ * Q64.64 sqrt price of a human price, adjusted for mint decimals. Not exact;
+ * convert through a tick when the result must land on the tick grid
+ */
+pub fn price_to_sqrt_price(price: f64, decimals_0: u8, decimals_1: u8) -> u128 {
+    let raw_price = price / decimals_factor(decimals_0, decimals_1);
+    (raw_price.sqrt() * Q64) as u128
+}
+
+/*
+ * Human price at a tick, adjusted for mint decimals
+ */
+pub fn tick_to_price(tick: i32, decimals_0: u8, decimals_1: u8) -> f64 {
+    TICK_BASE.powi(tick) * decimals_factor(decimals_0, decimals_1)
+}
+
+/*
+ * Greatest tick whose price is at or below a human price, clamped to the
+ * valid tick range
+ */
+pub fn price_to_tick(price: f64, decimals_0: u8, decimals_1: u8) -> i32 {
+    let raw_price = price / decimals_factor(decimals_0, decimals_1);
+    let tick = (raw_price.ln() / TICK_BASE.ln()).floor();
+    tick.clamp(MIN_TICK as f64, MAX_TICK as f64) as i32
+}
+
+fn decimals_factor(decimals_0: u8, decimals_1: u8) -> f64 {
+    10f64.powi(decimals_0 as i32 - decimals_1 as i32)
+}
+
+/*
+ * Lowest and highest ticks usable with a tick spacing, the bounds of a
+ * full-range position
+ */
+pub fn full_range_ticks(tick_spacing: u16) -> (i32, i32) {
+    let tick_upper = MAX_TICK / tick_spacing as i32 * tick_spacing as i32;
+    (-tick_upper, tick_upper)
+}
+
+/*
+ * Tick bounds `range_bps` below and above a sqrt price, widened outward to
+ * the tick spacing and clamped to the full range
+ */
+pub fn ticks_around_price(
+    sqrt_price_x64: u128,
+    range_bps: u16,
+    tick_spacing: u16,
+) -> Result<(i32, i32)> {
+    require!(
+        range_bps > 0 && range_bps < 10_000,
+        CustomError::InvalidRangeBps
+    );
+    // sqrt(p * (1 ± bps)) = sqrt_p * sqrt(1 ± bps), in Q64.64
+    let price_x128 = U256::from(sqrt_price_x64) * U256::from(sqrt_price_x64);
+    let scaled_sqrt_price = |factor_bps: u64| {
+        let sqrt_price = (price_x128 * U256::from(factor_bps) / U256::from(10_000u64))
+            .integer_sqrt()
+            .as_u128();
+        sqrt_price.clamp(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64 - 1)
+    };
+    let tick_lower = tick_at_sqrt_price(scaled_sqrt_price(10_000 - range_bps as u64))?;
+    let tick_upper = tick_at_sqrt_price(scaled_sqrt_price(10_000 + range_bps as u64))?;
+
+    let spacing = tick_spacing as i32;
+    let (min_tick, max_tick) = full_range_ticks(tick_spacing);
+    let tick_lower = (tick_lower.div_euclid(spacing) * spacing).max(min_tick);
+    let mut tick_upper = ((tick_upper + spacing - 1).div_euclid(spacing) * spacing).min(max_tick);
+    if tick_upper <= tick_lower {
+        tick_upper = tick_lower + spacing;
+    }
+    Ok((tick_lower, tick_upper))
+}
+
+/*
+ * Start index of the tick array holding `tick`, the value Raydium seeds tick
+ * array PDAs with
+ */
+pub fn tick_array_start_index(tick: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+/*
+ * Value of a token pair in raw token_1 units at a Q64.64 sqrt price,
+ * saturating on overflow
+ */
+pub fn value_in_token_1(amount_0: u64, amount_1: u64, sqrt_price_x64: u128) -> u128 {
+    let sqrt_price = U256::from(sqrt_price_x64);
+    let amount_0_in_1 = ((U256::from(amount_0) * sqrt_price) >> 64)
+        .checked_mul(sqrt_price)
+        .map(|v| v >> 64)
+        .filter(|v| *v <= U256::from(u128::MAX))
+        .map_or(u128::MAX, |v| v.as_u128());
+    amount_0_in_1.saturating_add(amount_1 as u128)
+}
+
+/*
+ * Convert a raw amount of one pool token into the other at a Q64.64 sqrt
+ * price, rounding down and saturating on overflow
+ */
+pub fn convert_at_sqrt_price(amount: u64, sqrt_price_x64: u128, from_token_0: bool) -> u64 {
+    let converted = if from_token_0 {
+        value_in_token_1(amount, 0, sqrt_price_x64)
+    } else {
+        let price_x128 = U256::from(sqrt_price_x64) * U256::from(sqrt_price_x64);
+        if price_x128.is_zero() {
+            return 0;
+        }
+        ((U256::from(amount) << 128) / price_x128)
+            .min(U256::from(u128::MAX))
+            .as_u128()
+    };
+    converted.min(u64::MAX as u128) as u64
+}
+
+/*
+ * Mean tick between two (block_timestamp, tick_cumulative) observations of
+ * a pool, rounded towards negative infinity. None unless `newer` is later
+ */
+pub fn mean_tick(older: (u32, i64), newer: (u32, i64)) -> Option<i32> {
+    let elapsed = newer
+        .0
+        .checked_sub(older.0)
+        .filter(|elapsed| *elapsed > 0)?;
+    let delta = newer.1.checked_sub(older.1)?;
+    i32::try_from(delta.div_euclid(elapsed as i64)).ok()
+}
+
+/*
+ * Largest liquidity that amount_0 and amount_1 can fund in
+ * [tick_lower_index, tick_upper_index) at a Q64.64 sqrt price. Below the
+ * range only token_0 counts, above it only token_1
+ */
+pub fn liquidity_for_amounts(
+    sqrt_price_x64: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<u128> {
+    require!(
+        tick_lower_index < tick_upper_index,
+        CustomError::InvalidTickRange
+    );
+    let sqrt_price_lower_x64 = sqrt_price_at_tick(tick_lower_index)?;
+    let sqrt_price_upper_x64 = sqrt_price_at_tick(tick_upper_index)?;
+
+    let liquidity = if sqrt_price_x64 <= sqrt_price_lower_x64 {
+        liquidity_for_amount_0(sqrt_price_lower_x64, sqrt_price_upper_x64, amount_0)
+    } else if sqrt_price_x64 < sqrt_price_upper_x64 {
+        liquidity_for_amount_0(sqrt_price_x64, sqrt_price_upper_x64, amount_0).min(
+            liquidity_for_amount_1(sqrt_price_lower_x64, sqrt_price_x64, amount_1),
+        )
+    } else {
+        liquidity_for_amount_1(sqrt_price_lower_x64, sqrt_price_upper_x64, amount_1)
+    };
+    Ok(liquidity)
+}
+
+/*
+ * Raydium base flag for a deposit by amounts: true when amount_0 is the side
+ * that limits the liquidity, false when amount_1 is
+ */
+pub fn base_flag_for_amounts(
+    sqrt_price_x64: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+    amount_0: u64,
+    amount_1: u64,
+) -> Result<bool> {
+    require!(
+        tick_lower_index < tick_upper_index,
+        CustomError::InvalidTickRange
+    );
+    let sqrt_price_lower_x64 = sqrt_price_at_tick(tick_lower_index)?;
+    let sqrt_price_upper_x64 = sqrt_price_at_tick(tick_upper_index)?;
+
+    let base_0 = if sqrt_price_x64 <= sqrt_price_lower_x64 {
+        true
+    } else if sqrt_price_x64 < sqrt_price_upper_x64 {
+        liquidity_for_amount_0(sqrt_price_x64, sqrt_price_upper_x64, amount_0)
+            <= liquidity_for_amount_1(sqrt_price_lower_x64, sqrt_price_x64, amount_1)
+    } else {
+        false
+    };
+    Ok(base_0)
+}
+
+fn liquidity_for_amount_0(sqrt_price_a_x64: u128, sqrt_price_b_x64: u128, amount_0: u64) -> u128 {
+    let intermediate = (U256::from(sqrt_price_a_x64) * U256::from(sqrt_price_b_x64)) >> 64;
+    let liquidity =
+        U256::from(amount_0) * intermediate / U256::from(sqrt_price_b_x64 - sqrt_price_a_x64);
+    saturating_u128(liquidity)
+}
+
+fn liquidity_for_amount_1(sqrt_price_a_x64: u128, sqrt_price_b_x64: u128, amount_1: u64) -> u128 {
+    let liquidity = (U256::from(amount_1) << 64) / U256::from(sqrt_price_b_x64 - sqrt_price_a_x64);
+    saturating_u128(liquidity)
+}
+
+fn saturating_u128(value: U256) -> u128 {
+    if value > U256::from(u128::MAX) {
+        u128::MAX
+    } else {
+        value.as_u128()
+    }
+}
+
+fn saturating_u64(value: U256) -> u64 {
+    if value > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        value.as_u64()
+    }
+}
+
+fn div_round_up(numerator: U256, denominator: U256) -> U256 {
+    let quotient = numerator / denominator;
+    if quotient * denominator == numerator {
+        quotient
+    } else {
+        quotient + U256::one()
+    }
+}
+
+/*
+ * Token_0 amount between two sqrt prices for a given liquidity
+ */
+pub fn amount_0_delta(
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> u64 {
+    let (lower, upper) = if sqrt_price_a_x64 <= sqrt_price_b_x64 {
+        (sqrt_price_a_x64, sqrt_price_b_x64)
+    } else {
+        (sqrt_price_b_x64, sqrt_price_a_x64)
+    };
+    if lower == 0 {
+        return 0;
+    }
+    // Past U256 the amount is past u64 too, as sqrt prices stay below 2^96
+    let Some(numerator) = (U256::from(liquidity) << 64).checked_mul(U256::from(upper - lower))
+    else {
+        return u64::MAX;
+    };
+    let amount = if round_up {
+        div_round_up(
+            div_round_up(numerator, U256::from(upper)),
+            U256::from(lower),
+        )
+    } else {
+        numerator / U256::from(upper) / U256::from(lower)
+    };
+    saturating_u64(amount)
+}
+
+/*
+ * Token_1 amount between two sqrt prices for a given liquidity
+ */
+pub fn amount_1_delta(
+    sqrt_price_a_x64: u128,
+    sqrt_price_b_x64: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> u64 {
+    let difference = U256::from(sqrt_price_a_x64.abs_diff(sqrt_price_b_x64));
+    let product = U256::from(liquidity) * difference;
+    let amount = if round_up {
+        div_round_up(product, U256::one() << 64)
+    } else {
+        product >> 64
+    };
+    saturating_u64(amount)
+}
+
+/*
+ * Swap as far as possible towards `sqrt_price_target_x64` within one
+ * liquidity range, with Raydium's own swap math so a quote walks the pool
+ * exactly as the CPI will. Exact-in amounts exclude the trade fee, which
+ * comes as `fee_amount`
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn compute_swap_step(
+    sqrt_price_current_x64: u128,
+    sqrt_price_target_x64: u128,
+    liquidity: u128,
+    amount_remaining: u64,
+    fee_rate: u32,
+    is_base_input: bool,
+    zero_for_one: bool,
+    block_timestamp: u32,
+) -> Result<SwapStep> {
+    swap_math::compute_swap_step(
+        sqrt_price_current_x64,
+        sqrt_price_target_x64,
+        liquidity,
+        amount_remaining,
+        fee_rate,
+        is_base_input,
+        zero_for_one,
+        block_timestamp,
+    )
+}
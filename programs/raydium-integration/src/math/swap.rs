@@ -0,0 +1,113 @@
+//! Swap path simulation over supplied tick arrays.
+use std::ops::Deref;
+
+use anchor_lang::prelude::*;
+use raydium_amm_v3::states::{PoolState, TickArrayState, TICK_ARRAY_SIZE};
+
+use super::{compute_swap_step, sqrt_price_at_tick, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+use crate::CustomError;
+
+/*
+ * Walk the supplied tick arrays the way a swap would and count the
+ * initialized ticks it crosses. Stops early once `max_crossings` is
+ * exceeded; ticks beyond the last supplied array are never reached by the
+ * CPI either. `block_timestamp` feeds Raydium's step math as on chain
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn count_tick_crossings<T: Deref<Target = TickArrayState>>(
+    pool_state: &PoolState,
+    tick_arrays: &[T],
+    fee_rate: u32,
+    amount: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    zero_for_one: bool,
+    max_crossings: u16,
+    block_timestamp: u32,
+) -> Result<u16> {
+    let sqrt_price_limit_x64 = match sqrt_price_limit_x64 {
+        0 if zero_for_one => MIN_SQRT_PRICE_X64 + 1,
+        0 => MAX_SQRT_PRICE_X64 - 1,
+        limit => limit,
+    };
+    let mut sqrt_price_x64 = pool_state.sqrt_price_x64;
+    let mut tick_current = pool_state.tick_current;
+    let mut liquidity = pool_state.liquidity;
+    let mut amount_remaining = amount;
+    let mut crossings: u16 = 0;
+
+    for tick_array in tick_arrays {
+        // Walk the array in swap order, skipping ticks already behind the price
+        for index in 0..TICK_ARRAY_SIZE as usize {
+            let tick = if zero_for_one {
+                &tick_array.ticks[TICK_ARRAY_SIZE as usize - 1 - index]
+            } else {
+                &tick_array.ticks[index]
+            };
+            let ahead = if zero_for_one {
+                tick.tick <= tick_current
+            } else {
+                tick.tick > tick_current
+            };
+            if tick.liquidity_gross == 0 || !ahead {
+                continue;
+            }
+
+            let sqrt_price_tick_x64 = sqrt_price_at_tick(tick.tick)?;
+            let limit_first = if zero_for_one {
+                sqrt_price_limit_x64 > sqrt_price_tick_x64
+            } else {
+                sqrt_price_limit_x64 < sqrt_price_tick_x64
+            };
+            let target = if limit_first {
+                sqrt_price_limit_x64
+            } else {
+                sqrt_price_tick_x64
+            };
+
+            let step = compute_swap_step(
+                sqrt_price_x64,
+                target,
+                liquidity,
+                amount_remaining,
+                fee_rate,
+                is_base_input,
+                zero_for_one,
+                block_timestamp,
+            )?;
+            amount_remaining = if is_base_input {
+                amount_remaining.saturating_sub(step.amount_in.saturating_add(step.fee_amount))
+            } else {
+                amount_remaining.saturating_sub(step.amount_out)
+            };
+            sqrt_price_x64 = step.sqrt_price_next_x64;
+
+            if limit_first || sqrt_price_x64 != sqrt_price_tick_x64 {
+                return Ok(crossings);
+            }
+
+            crossings += 1;
+            require!(
+                crossings <= max_crossings,
+                CustomError::TooManyTickCrossings
+            );
+            let liquidity_net = if zero_for_one {
+                -tick.liquidity_net
+            } else {
+                tick.liquidity_net
+            };
+            liquidity = liquidity
+                .checked_add_signed(liquidity_net)
+                .ok_or(CustomError::MathOverflow)?;
+            tick_current = if zero_for_one {
+                tick.tick - 1
+            } else {
+                tick.tick
+            };
+            if amount_remaining == 0 {
+                return Ok(crossings);
+            }
+        }
+    }
+    Ok(crossings)
+}
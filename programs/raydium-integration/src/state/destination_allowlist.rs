@@ -0,0 +1,50 @@
+//! Per-user allowlist of swap output accounts.
+use anchor_lang::prelude::*;
+
+use crate::MAX_ALLOWED_DESTINATIONS;
+
+/*
+ * Token accounts a user's swap outputs and withdrawals may go to, PDA seeded
+ * by [b"destination_allowlist", owner]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct DestinationAllowlist {
+    pub owner: Pubkey,
+    pub enabled: bool,
+    /// When a requested disable takes effect, zero if none is pending
+    pub disable_at: i64,
+    pub destinations: [AllowedDestination; MAX_ALLOWED_DESTINATIONS],
+    pub destination_count: u8,
+}
+
+impl DestinationAllowlist {
+    pub const SIZE: usize = 32 + 1 + 8 + AllowedDestination::SIZE * MAX_ALLOWED_DESTINATIONS + 1;
+
+    pub fn is_enforced(&self, now: i64) -> bool {
+        self.enabled && (self.disable_at == 0 || now < self.disable_at)
+    }
+
+    pub fn position(&self, token_account: &Pubkey) -> Option<usize> {
+        self.destinations[..self.destination_count as usize]
+            .iter()
+            .position(|destination| destination.token_account == *token_account)
+    }
+
+    /// Listed and past its add delay
+    pub fn allows(&self, token_account: &Pubkey, now: i64) -> bool {
+        self.position(token_account)
+            .is_some_and(|index| self.destinations[index].active_at <= now)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct AllowedDestination {
+    pub token_account: Pubkey,
+    pub active_at: i64,
+}
+
+impl AllowedDestination {
+    pub const SIZE: usize = 32 + 8;
+}
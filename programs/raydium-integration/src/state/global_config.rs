@@ -0,0 +1,171 @@
+//! Governance-controlled protocol settings.
+use anchor_lang::prelude::*;
+
+use crate::{
+    CustomError, FLAG_PAUSED, FLAG_PAUSE_SWAPS, FLAG_REQUIRE_USER_SLIPPAGE,
+    FLAG_THROTTLE_ROUND_TRIPS, MAX_PROTOCOL_FEE_BPS, MAX_SWAP_VALIDATORS,
+};
+
+/*
+ * Protocol governance, PDA seeded by [b"global_config"]. Also the authority
+ * of the insurance fund vaults, token accounts seeded by
+ * [b"insurance_fund", mint]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct GlobalConfig {
+    pub admin: Pubkey,
+    /// Owner of the token accounts receiving protocol fees net of the
+    /// insurance share
+    pub treasury: Pubkey,
+    /// Charged on the pool input of proxy and session swaps
+    pub protocol_fee_bps: u16,
+    /// Share of each protocol fee routed to the insurance fund
+    pub insurance_share_bps: u16,
+    /// `FLAG_*` bits
+    pub flags: u8,
+    /// Share of each protocol fee, net of the insurance share, owed to the
+    /// creator of a pool created through the proxy
+    pub creator_share_bps: u16,
+    /// Seconds within which a swap reversing the previous one on the same
+    /// pool counts as a round trip
+    pub round_trip_window_secs: u32,
+    /// Round trips per window a user can make on one pool before being
+    /// flagged; zero turns detection off
+    pub max_round_trips: u16,
+}
+
+impl GlobalConfig {
+    pub const SIZE: usize = 32 + 32 + 2 + 2 + 1 + 2 + 4 + 2;
+
+    pub fn validate_fees(protocol_fee_bps: u16, insurance_share_bps: u16) -> Result<()> {
+        require!(
+            protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            CustomError::InvalidProtocolFee
+        );
+        require!(
+            insurance_share_bps <= 10_000,
+            CustomError::InvalidProtocolFee
+        );
+        Ok(())
+    }
+
+    /*
+     * Part of a protocol fee owed to the insurance fund, rounded down
+     */
+    pub fn insurance_share(&self, fee: u64) -> u64 {
+        (fee as u128 * self.insurance_share_bps as u128 / 10_000) as u64
+    }
+
+    /*
+     * Part of a protocol fee, net of the insurance share, owed to the
+     * pool's creator, rounded down
+     */
+    pub fn creator_share(&self, fee: u64) -> u64 {
+        (fee as u128 * self.creator_share_bps as u128 / 10_000) as u64
+    }
+
+    pub fn requires_user_slippage(&self) -> bool {
+        self.flags & FLAG_REQUIRE_USER_SLIPPAGE != 0
+    }
+
+    pub fn tracks_round_trips(&self) -> bool {
+        self.round_trip_window_secs > 0 && self.max_round_trips > 0
+    }
+
+    pub fn throttles_round_trips(&self) -> bool {
+        self.flags & FLAG_THROTTLE_ROUND_TRIPS != 0
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.flags & FLAG_PAUSED != 0
+    }
+
+    pub fn swaps_paused(&self) -> bool {
+        self.flags & FLAG_PAUSE_SWAPS != 0
+    }
+
+    /// Fail if governance paused the whole program
+    pub fn require_unpaused(&self) -> Result<()> {
+        require!(!self.is_paused(), CustomError::ProgramPaused);
+        Ok(())
+    }
+
+    /*
+     * Fail if the whole program is paused, or `feature_flag` is. The paused
+     * feature goes to the logs, since errors carry no data
+     */
+    pub fn require_active(&self, feature_flag: u8, feature: &str) -> Result<()> {
+        self.require_unpaused()?;
+        if self.flags & feature_flag != 0 {
+            msg!("Paused feature: {}", feature);
+            return err!(CustomError::FeaturePaused);
+        }
+        Ok(())
+    }
+}
+
+/*
+ * Governance change announced ahead of taking effect, PDA seeded by
+ * [b"pending_param_change"]. At most one is pending; scheduling again
+ * replaces it
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PendingParamChange {
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    /// Non-pause `FLAG_*` bits
+    pub flags: u8,
+    pub creator_share_bps: u16,
+    pub round_trip_window_secs: u32,
+    pub max_round_trips: u16,
+    /// First slot `apply_param_change` accepts
+    pub activation_slot: u64,
+    pub scheduled_slot: u64,
+}
+
+impl PendingParamChange {
+    pub const SIZE: usize = 32 + 2 + 2 + 1 + 2 + 4 + 2 + 8 + 8;
+}
+
+/*
+ * Raydium pool the admin designated to price protocol fees owed in
+ * `input_mint` when paid in `fee_mint`, PDA seeded by
+ * [b"fee_oracle", input_mint, fee_mint]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct FeeOracle {
+    pub input_mint: Pubkey,
+    pub fee_mint: Pubkey,
+    pub pool: Pubkey,
+    pub input_is_token_0: bool,
+    /// The fee is converted at the pool's mean tick over at least this long
+    pub twap_window_secs: u32,
+    /// Oldest the pool's latest observation can be
+    pub max_staleness_secs: u32,
+    /// Furthest the pool's current tick can stray from the mean tick; one
+    /// tick is about one basis point of price
+    pub max_deviation_ticks: u32,
+}
+
+impl FeeOracle {
+    pub const SIZE: usize = 32 + 32 + 32 + 1 + 4 + 4 + 4;
+}
+
+/*
+ * Programs every swap is validated by before it executes, PDA seeded by
+ * [b"swap_validators"]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct SwapValidatorRegistry {
+    pub validators: [Pubkey; MAX_SWAP_VALIDATORS],
+    pub validator_count: u8,
+}
+
+impl SwapValidatorRegistry {
+    pub const SIZE: usize = 32 * MAX_SWAP_VALIDATORS + 1;
+}
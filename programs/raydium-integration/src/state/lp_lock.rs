@@ -0,0 +1,20 @@
+//! AMM LP tokens locked through the proxy.
+use anchor_lang::prelude::*;
+
+/*
+ * LP tokens of a Raydium AMM v4 pool locked until `unlock_ts`, PDA seeded by
+ * [b"lp_lock", owner, lp_mint]. Authority of the escrow holding them, a token
+ * account seeded by [b"lp_lock_escrow", lp_lock]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct AmmLpLock {
+    pub owner: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub unlock_ts: i64,
+}
+
+impl AmmLpLock {
+    pub const SIZE: usize = 32 + 32 + 8 + 8;
+}
@@ -0,0 +1,49 @@
+//! Accounts the program owns, plus the payload and return-data types it
+//! exchanges with callers, validators and callbacks.
+use anchor_lang::prelude::*;
+
+mod destination_allowlist;
+mod global_config;
+mod lp_lock;
+mod orders;
+mod points;
+mod pool_creator;
+mod positions;
+mod recovery;
+mod session;
+mod stats;
+mod types;
+mod user_config;
+
+pub use destination_allowlist::*;
+pub use global_config::*;
+pub use lp_lock::*;
+pub use orders::*;
+pub use points::*;
+pub use pool_creator::*;
+pub use positions::*;
+pub use recovery::*;
+pub use session::*;
+pub use stats::*;
+pub use types::*;
+pub use user_config::*;
+
+const _: () = assert!(std::mem::size_of::<UserConfig>() == UserConfig::SIZE);
+const _: () = assert!(std::mem::size_of::<GlobalStats>() == GlobalStats::SIZE);
+const _: () = assert!(std::mem::size_of::<PoolStats>() == PoolStats::SIZE);
+
+/*
+ * Mutable view of an `init_if_needed` zero-copy account. One created by this
+ * instruction has no discriminator until exit, so it only loads through
+ * `load_init`
+ */
+pub(crate) fn load_or_init<'a, T: ZeroCopy + Owner>(
+    loader: &'a AccountLoader<'_, T>,
+) -> Result<std::cell::RefMut<'a, T>> {
+    let uninitialized = loader.to_account_info().try_borrow_data()?[..8] == [0; 8];
+    if uninitialized {
+        loader.load_init()
+    } else {
+        loader.load_mut()
+    }
+}
@@ -0,0 +1,142 @@
+//! Dutch-auction and price floor orders, and commit-reveal swap commitments.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use raydium_amm_v3::libraries::big_num::U256;
+
+use crate::FLOOR_ORDER_DAY_SECS;
+
+/*
+ * Input escrowed for a Dutch auction, PDA seeded by
+ * [b"dutch_order", owner, order_id (u64 LE)]. The escrow token account is
+ * the PDA seeded by [b"dutch_escrow", dutch_order]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct DutchOrder {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    /// Owner's token account the output is paid to
+    pub destination: Pubkey,
+    /// Input the escrow received, net of any transfer fee
+    pub amount_in: u64,
+    /// Least output accepted at `start_ts`, falling linearly to
+    /// `end_min_out` at `end_ts`
+    pub start_min_out: u64,
+    pub end_min_out: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+}
+
+impl DutchOrder {
+    pub const SIZE: usize = 32 + 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8;
+
+    /// Least output the order accepts at `now`
+    pub fn min_out(&self, now: i64) -> u64 {
+        if now <= self.start_ts {
+            return self.start_min_out;
+        }
+        if now >= self.end_ts {
+            return self.end_min_out;
+        }
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        let decay = (self.start_min_out - self.end_min_out) as u128 * elapsed / duration;
+        self.start_min_out - decay as u64
+    }
+}
+
+/*
+ * Input escrowed to be sold at or above a floor price, at most `daily_max`
+ * a day, PDA seeded by [b"floor_order", owner, order_id (u64 LE)]. The
+ * escrow token account is the PDA seeded by [b"floor_escrow", floor_order]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct FloorOrder {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    /// Owner's token account the output is paid to
+    pub destination: Pubkey,
+    /// Least output per unit of input, raw units in Q64.64
+    pub floor_price_x64: u128,
+    /// Input sold at most per day, days counted from `day_start_ts`
+    pub daily_max: u64,
+    pub day_start_ts: i64,
+    pub sold_today: u64,
+    /// Input sold over the order's life
+    pub total_sold: u64,
+}
+
+impl FloorOrder {
+    pub const SIZE: usize = 32 + 8 + 32 + 32 + 32 + 16 + 8 + 8 + 8 + 8;
+
+    /// Least output a fill of `amount_in` accepts, rounded up
+    pub fn min_out(&self, amount_in: u64) -> u64 {
+        let min_out =
+            (U256::from(amount_in) * U256::from(self.floor_price_x64) + U256::from(u64::MAX)) >> 64;
+        min_out.min(U256::from(u64::MAX)).as_u64()
+    }
+
+    /*
+     * Input that can still be sold today, starting a new day once a full
+     * one has passed
+     */
+    pub fn available_today(&mut self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.day_start_ts);
+        if elapsed >= FLOOR_ORDER_DAY_SECS {
+            self.day_start_ts = now - elapsed % FLOOR_ORDER_DAY_SECS;
+            self.sold_today = 0;
+        }
+        self.daily_max.saturating_sub(self.sold_today)
+    }
+}
+
+/*
+ * Pending commit-reveal swap of an owner, PDA seeded by
+ * [b"swap_commitment", owner]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct SwapCommitment {
+    pub owner: Pubkey,
+    pub hash: [u8; 32],
+    /// Slot the commitment was made in
+    pub slot: u64,
+}
+
+impl SwapCommitment {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
+
+/*
+ * SHA-256 a commit-reveal swap commits to: the pool, both token accounts,
+ * then `amount`, `expected_other_amount` and `sqrt_price_limit_x64` as
+ * little-endian integers, `is_base_input` as one byte, and the salt
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn swap_commitment_hash(
+    pool: &Pubkey,
+    input_token_account: &Pubkey,
+    output_token_account: &Pubkey,
+    amount: u64,
+    expected_other_amount: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    salt: &[u8; 32],
+) -> [u8; 32] {
+    hashv(&[
+        pool.as_ref(),
+        input_token_account.as_ref(),
+        output_token_account.as_ref(),
+        &amount.to_le_bytes(),
+        &expected_other_amount.to_le_bytes(),
+        &sqrt_price_limit_x64.to_le_bytes(),
+        &[is_base_input as u8],
+        salt,
+    ])
+    .to_bytes()
+}
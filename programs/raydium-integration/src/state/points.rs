@@ -0,0 +1,71 @@
+//! LP and swap points.
+use anchor_lang::prelude::*;
+
+use crate::POINTS_RATE_DENOMINATOR;
+
+/*
+ * Points rates set by the admin, PDA seeded by [b"points_config"]. Both are
+ * points per POINTS_RATE_DENOMINATOR units
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PointsConfig {
+    /// Per raw unit of swap input, summed across mints like the statistics
+    pub swap_points_per_unit: u64,
+    /// Per unit of in-range liquidity per second
+    pub lp_points_per_unit: u64,
+}
+
+impl PointsConfig {
+    pub const SIZE: usize = 8 + 8;
+
+    pub fn swap_points(&self, amount_in: u64) -> u128 {
+        amount_in as u128 * self.swap_points_per_unit as u128 / POINTS_RATE_DENOMINATOR
+    }
+
+    pub fn lp_points(&self, liquidity: u128, seconds: u128) -> u128 {
+        liquidity
+            .saturating_mul(seconds)
+            .saturating_mul(self.lp_points_per_unit as u128)
+            / POINTS_RATE_DENOMINATOR
+    }
+}
+
+/*
+ * Points an owner has earned, PDA seeded by [b"points", owner]. Updated by
+ * the interactions it is passed to
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PointsLedger {
+    pub owner: Pubkey,
+    pub swap_points: u128,
+    pub lp_points: u128,
+    pub claimed: u128,
+    pub last_update_ts: i64,
+}
+
+impl PointsLedger {
+    pub const SIZE: usize = 32 + 16 + 16 + 16 + 8;
+
+    pub fn unclaimed(&self) -> u128 {
+        self.swap_points
+            .saturating_add(self.lp_points)
+            .saturating_sub(self.claimed)
+    }
+}
+
+/*
+ * Last LP points accrual of a position, PDA seeded by
+ * [b"position_points", position_nft_mint]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PositionPoints {
+    pub position_nft: Pubkey,
+    pub last_accrual_ts: i64,
+}
+
+impl PositionPoints {
+    pub const SIZE: usize = 32 + 8;
+}
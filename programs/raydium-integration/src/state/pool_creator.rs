@@ -0,0 +1,19 @@
+//! Creators of pools opened through the proxy.
+use anchor_lang::prelude::*;
+
+/*
+ * Creator of a pool created with `proxy_create_pool`, PDA seeded by
+ * [b"pool_creator", pool]. Also the authority of the pool's creator fee
+ * vaults, token accounts seeded by [b"creator_fees", pool, mint]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PoolCreatorRecord {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+}
+
+impl PoolCreatorRecord {
+    pub const SIZE: usize = 32 + 32 + 8;
+}
@@ -0,0 +1,278 @@
+//! Position bookkeeping: registries, cost basis, fee checkpoints, streams,
+//! shared positions and approvals to open on an owner's behalf.
+use anchor_lang::prelude::*;
+use raydium_amm_v3::libraries::big_num::U256;
+
+use crate::{value_in_token_1, FEE_CHECKPOINT_CAPACITY};
+
+/*
+ * Index of the positions an owner opened through the proxy, PDA seeded by
+ * [b"position_registry", owner]. Mints live in pages seeded by
+ * [b"position_registry", owner, page (u32 LE)] for pages 0..page_count
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct UserPositionRegistry {
+    pub owner: Pubkey,
+    pub page_count: u32,
+    pub position_count: u32,
+}
+
+impl UserPositionRegistry {
+    pub const SIZE: usize = 32 + 4 + 4;
+}
+
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PositionRegistryPage {
+    pub owner: Pubkey,
+    pub page: u32,
+    pub positions: Vec<Pubkey>,
+}
+
+impl PositionRegistryPage {
+    pub const MAX_POSITIONS: usize = 32;
+    pub const SIZE: usize = 32 + 4 + 4 + 32 * Self::MAX_POSITIONS;
+
+    pub fn is_full(&self) -> bool {
+        self.positions.len() >= Self::MAX_POSITIONS
+    }
+
+    /// Removes `position_nft`, returning whether it was present
+    pub fn remove(&mut self, position_nft: &Pubkey) -> bool {
+        match self.positions.iter().position(|p| p == position_nft) {
+            Some(index) => {
+                self.positions.swap_remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/*
+ * Entry price and cumulative flows of a position opened through the proxy,
+ * PDA seeded by [b"cost_basis", position_nft_mint]. Only flows routed through
+ * the proxy are recorded. Values are in raw token_1 units at the price of
+ * each deposit/withdrawal
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PositionCostBasis {
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub open_sqrt_price_x64: u128,
+    pub opened_at: i64,
+    pub deposited_0: u64,
+    pub deposited_1: u64,
+    /// Principal plus fees received on withdrawals
+    pub withdrawn_0: u64,
+    pub withdrawn_1: u64,
+    pub fees_collected_0: u64,
+    pub fees_collected_1: u64,
+    pub cost_token_1: u128,
+    pub proceeds_token_1: u128,
+}
+
+impl PositionCostBasis {
+    pub const SIZE: usize = 32 + 32 + 16 + 8 + 8 * 6 + 16 + 16;
+
+    pub fn record_deposit(&mut self, amount_0: u64, amount_1: u64, sqrt_price_x64: u128) {
+        self.deposited_0 = self.deposited_0.saturating_add(amount_0);
+        self.deposited_1 = self.deposited_1.saturating_add(amount_1);
+        self.cost_token_1 =
+            self.cost_token_1
+                .saturating_add(value_in_token_1(amount_0, amount_1, sqrt_price_x64));
+    }
+
+    pub fn record_withdrawal(
+        &mut self,
+        amount_0: u64,
+        amount_1: u64,
+        fees_0: u64,
+        fees_1: u64,
+        sqrt_price_x64: u128,
+    ) {
+        self.withdrawn_0 = self.withdrawn_0.saturating_add(amount_0);
+        self.withdrawn_1 = self.withdrawn_1.saturating_add(amount_1);
+        self.fees_collected_0 = self.fees_collected_0.saturating_add(fees_0);
+        self.fees_collected_1 = self.fees_collected_1.saturating_add(fees_1);
+        self.proceeds_token_1 = self.proceeds_token_1.saturating_add(value_in_token_1(
+            amount_0,
+            amount_1,
+            sqrt_price_x64,
+        ));
+    }
+
+    pub fn realized_pnl(&self) -> i128 {
+        (self.proceeds_token_1 as i128).saturating_sub(self.cost_token_1 as i128)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct FeeCheckpointEntry {
+    pub timestamp: i64,
+    pub liquidity: u128,
+    /// Fees owed plus fees already collected through the proxy, raw units
+    pub cumulative_fees_0: u64,
+    pub cumulative_fees_1: u64,
+}
+
+/*
+ * Ring of the latest fee checkpoints of a position, PDA seeded by
+ * [b"fee_checkpoint", position_nft_mint]. Entry `head` is the next slot to
+ * be overwritten
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct FeeCheckpoint {
+    pub position_nft: Pubkey,
+    pub head: u32,
+    pub count: u32,
+    pub entries: [FeeCheckpointEntry; FEE_CHECKPOINT_CAPACITY],
+}
+
+impl FeeCheckpoint {
+    pub const SIZE: usize = 32 + 4 + 4 + (8 + 16 + 8 + 8) * FEE_CHECKPOINT_CAPACITY;
+
+    pub fn latest(&self) -> &FeeCheckpointEntry {
+        let index = (self.head as usize + FEE_CHECKPOINT_CAPACITY - 1) % FEE_CHECKPOINT_CAPACITY;
+        &self.entries[index]
+    }
+
+    pub fn push(&mut self, entry: FeeCheckpointEntry) {
+        self.entries[self.head as usize] = entry;
+        self.head = ((self.head as usize + 1) % FEE_CHECKPOINT_CAPACITY) as u32;
+        self.count = self
+            .count
+            .saturating_add(1)
+            .min(FEE_CHECKPOINT_CAPACITY as u32);
+    }
+}
+
+/*
+ * Linear unlock of a locked position, PDA seeded by
+ * [b"position_stream", position_nft_mint]. The PDA holds the NFT and is the
+ * only account able to withdraw from the position
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PositionStream {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    pub initial_liquidity: u128,
+    pub claimed_liquidity: u128,
+}
+
+impl PositionStream {
+    pub const SIZE: usize = 32 + 32 + 32 + 32 + 8 + 8 + 16 + 16;
+
+    pub fn unlocked_liquidity(&self, now: i64) -> u128 {
+        if now <= self.start_ts {
+            return 0;
+        }
+        if now >= self.end_ts {
+            return self.initial_liquidity;
+        }
+        let elapsed = (now - self.start_ts) as u64;
+        let duration = (self.end_ts - self.start_ts) as u64;
+        (U256::from(self.initial_liquidity) * U256::from(elapsed) / U256::from(duration)).as_u128()
+    }
+}
+
+/*
+ * Position NFT held by a PDA on behalf of several contributors, PDA seeded
+ * by [b"shared_position", position_nft_mint]. One share is one unit of the
+ * position's liquidity; fees are distributed through per-share accumulators
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct SharedPosition {
+    pub creator: Pubkey,
+    pub position_nft: Pubkey,
+    pub pool: Pubkey,
+    pub total_shares: u128,
+    /// Fees collected per share, Q64.64
+    pub fee_per_share_0_x64: u128,
+    pub fee_per_share_1_x64: u128,
+}
+
+impl SharedPosition {
+    pub const SIZE: usize = 32 + 32 + 32 + 16 + 16 + 16;
+
+    pub fn accrue_fees(&mut self, fees_0: u64, fees_1: u64) {
+        if self.total_shares == 0 {
+            return;
+        }
+        let per_share =
+            |fees: u64| ((U256::from(fees) << 64) / U256::from(self.total_shares)).as_u128();
+        self.fee_per_share_0_x64 = self.fee_per_share_0_x64.saturating_add(per_share(fees_0));
+        self.fee_per_share_1_x64 = self.fee_per_share_1_x64.saturating_add(per_share(fees_1));
+    }
+
+    /*
+     * Fees earned by `shares` since the accumulators started
+     */
+    pub fn fees_for(&self, shares: u128) -> (u128, u128) {
+        let earned = |fee_per_share_x64: u128| {
+            ((U256::from(shares) * U256::from(fee_per_share_x64)) >> 64).as_u128()
+        };
+        (
+            earned(self.fee_per_share_0_x64),
+            earned(self.fee_per_share_1_x64),
+        )
+    }
+}
+
+/*
+ * A contributor's stake in a shared position, PDA seeded by
+ * [b"shared_share", shared_position, contributor]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct SharedPositionShare {
+    pub shared_position: Pubkey,
+    pub contributor: Pubkey,
+    pub shares: u128,
+    pub fee_debt_0: u128,
+    pub fee_debt_1: u128,
+}
+
+impl SharedPositionShare {
+    pub const SIZE: usize = 32 + 32 + 16 + 16 + 16;
+
+    pub fn pending_fees(&self, shared: &SharedPosition) -> (u64, u64) {
+        let (earned_0, earned_1) = shared.fees_for(self.shares);
+        (
+            earned_0.saturating_sub(self.fee_debt_0) as u64,
+            earned_1.saturating_sub(self.fee_debt_1) as u64,
+        )
+    }
+
+    /*
+     * Mark all fees earned so far as paid, after a payout or share change
+     */
+    pub fn reset_fee_debt(&mut self, shared: &SharedPosition) {
+        (self.fee_debt_0, self.fee_debt_1) = shared.fees_for(self.shares);
+    }
+}
+
+/*
+ * An owner's standing approval for `delegate` to open positions whose NFT
+ * the owner receives, PDA seeded by [b"open_approval", owner, delegate]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct OpenApproval {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+}
+
+impl OpenApproval {
+    pub const SIZE: usize = 32 + 32;
+}
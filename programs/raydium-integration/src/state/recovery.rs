@@ -0,0 +1,64 @@
+//! Guardian-based owner recovery.
+use anchor_lang::prelude::*;
+
+use crate::{CustomError, MAX_GUARDIANS, RECOVERY_DELAY};
+
+/*
+ * Guardians able to move an owner's config to a new key, and the recovery
+ * they are voting on, PDA seeded by [b"recovery", owner]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct RecoveryConfig {
+    pub owner: Pubkey,
+    pub guardians: [Pubkey; MAX_GUARDIANS],
+    pub guardian_count: u8,
+    pub threshold: u8,
+    /// Proposed new owner, default when no recovery is pending
+    pub pending_owner: Pubkey,
+    /// Bit `i` is set once `guardians[i]` approved `pending_owner`
+    pub approvals: u8,
+    /// When the recovery can execute, zero until the threshold is met
+    pub ready_at: i64,
+}
+
+impl RecoveryConfig {
+    pub const SIZE: usize = 32 + 32 * MAX_GUARDIANS + 1 + 1 + 32 + 1 + 8;
+
+    /// Distinct, non-default guardians and a threshold they can reach
+    pub fn validate_guardians(guardians: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            guardians.len() <= MAX_GUARDIANS
+                && threshold > 0
+                && threshold as usize <= guardians.len(),
+            CustomError::InvalidGuardianSet
+        );
+        for (i, guardian) in guardians.iter().enumerate() {
+            require!(
+                *guardian != Pubkey::default() && !guardians[..i].contains(guardian),
+                CustomError::InvalidGuardianSet
+            );
+        }
+        Ok(())
+    }
+
+    pub fn guardian_index(&self, guardian: &Pubkey) -> Option<usize> {
+        self.guardians[..self.guardian_count as usize]
+            .iter()
+            .position(|candidate| candidate == guardian)
+    }
+
+    /// Record an approval, starting the delay when it meets the threshold
+    pub fn approve(&mut self, index: usize, now: i64) {
+        self.approvals |= 1 << index;
+        if self.ready_at == 0 && self.approvals.count_ones() >= self.threshold as u32 {
+            self.ready_at = now.saturating_add(RECOVERY_DELAY);
+        }
+    }
+
+    pub fn clear_pending(&mut self) {
+        self.pending_owner = Pubkey::default();
+        self.approvals = 0;
+        self.ready_at = 0;
+    }
+}
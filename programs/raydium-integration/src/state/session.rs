@@ -0,0 +1,152 @@
+//! Scoped session keys.
+use anchor_lang::prelude::*;
+
+use crate::{
+    CustomError, MAX_SESSION_POOLS, MAX_SESSION_SPEND_MINTS, SESSION_DIRECTION_ANY,
+    SESSION_DIRECTION_ONE_FOR_ZERO, SESSION_DIRECTION_ZERO_FOR_ONE,
+};
+
+/*
+ * Amount limits on a session's swaps spending one input mint, in that mint's
+ * raw units. Zero amounts mean no limit
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "cli", derive(serde::Serialize))]
+pub struct SessionSpendLimit {
+    #[cfg_attr(
+        feature = "cli",
+        serde(serialize_with = "crate::events::pubkey_string")
+    )]
+    pub mint: Pubkey,
+    /// Largest input a single swap may spend
+    pub max_trade_amount: u64,
+    /// Cap on the input all swaps may spend together
+    pub spend_cap: u64,
+}
+
+impl SessionSpendLimit {
+    pub const SIZE: usize = 32 + 8 + 8;
+}
+
+/*
+ * Short-lived key allowed to sign some instructions for the owner, PDA
+ * seeded by [b"session", user_cfg, session_key]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct Session {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expiry: i64,
+    /// Bitmask of `SESSION_SCOPE_*` flags
+    pub scope: u8,
+    /// Pools swaps are limited to, first `pool_count`; any pool when zero
+    pub allowed_pools: [Pubkey; MAX_SESSION_POOLS],
+    pub pool_count: u8,
+    /// One of the `SESSION_DIRECTION_*` values
+    pub direction: u8,
+    /// Amount limits per input mint, first `spend_limit_count`. Once any are
+    /// set, swaps spending another mint fail
+    pub spend_limits: [SessionSpendLimit; MAX_SESSION_SPEND_MINTS],
+    pub spend_limit_count: u8,
+    /// Input spent so far in each mint of `spend_limits`
+    pub spent: [u64; MAX_SESSION_SPEND_MINTS],
+}
+
+impl Session {
+    pub const SIZE: usize = 32
+        + 32
+        + 8
+        + 1
+        + 32 * MAX_SESSION_POOLS
+        + 1
+        + 1
+        + SessionSpendLimit::SIZE * MAX_SESSION_SPEND_MINTS
+        + 1
+        + 8 * MAX_SESSION_SPEND_MINTS;
+
+    /// Fail once expired or for instructions outside the scope
+    pub fn authorize(&self, scope: u8, now: i64) -> Result<()> {
+        require!(now < self.expiry, CustomError::SessionExpired);
+        require!(self.scope & scope != 0, CustomError::OutOfSessionScope);
+        Ok(())
+    }
+
+    /// Fail for pools or a direction the session does not allow
+    pub fn check_swap(&self, pool: &Pubkey, zero_for_one: bool) -> Result<()> {
+        require!(
+            self.pool_count == 0 || self.allowed_pools[..self.pool_count as usize].contains(pool),
+            CustomError::SessionPoolNotAllowed
+        );
+        let direction = if zero_for_one {
+            SESSION_DIRECTION_ZERO_FOR_ONE
+        } else {
+            SESSION_DIRECTION_ONE_FOR_ZERO
+        };
+        require!(
+            self.direction == SESSION_DIRECTION_ANY || self.direction == direction,
+            CustomError::SessionDirectionNotAllowed
+        );
+        Ok(())
+    }
+
+    /*
+     * Replace the amount limits. A mint keeps what was already spent in it
+     * when it stays listed
+     */
+    pub fn set_spend_limits(&mut self, spend_limits: &[SessionSpendLimit]) -> Result<()> {
+        require!(
+            spend_limits.len() <= MAX_SESSION_SPEND_MINTS,
+            CustomError::InvalidSessionSpendLimits
+        );
+        let mut spent = [0; MAX_SESSION_SPEND_MINTS];
+        for (index, limit) in spend_limits.iter().enumerate() {
+            require!(
+                !spend_limits[..index]
+                    .iter()
+                    .any(|other| other.mint == limit.mint),
+                CustomError::InvalidSessionSpendLimits
+            );
+            spent[index] = self.spent_in(&limit.mint);
+        }
+        self.spend_limits = [SessionSpendLimit::default(); MAX_SESSION_SPEND_MINTS];
+        self.spend_limits[..spend_limits.len()].copy_from_slice(spend_limits);
+        self.spend_limit_count = spend_limits.len() as u8;
+        self.spent = spent;
+        Ok(())
+    }
+
+    fn spend_limit_index(&self, mint: &Pubkey) -> Option<usize> {
+        self.spend_limits[..self.spend_limit_count as usize]
+            .iter()
+            .position(|limit| limit.mint == *mint)
+    }
+
+    /// Input spent so far in `mint`; zero when it has no limits
+    pub fn spent_in(&self, mint: &Pubkey) -> u64 {
+        self.spend_limit_index(mint)
+            .map_or(0, |index| self.spent[index])
+    }
+
+    /// Count a swap's input against its mint's per-trade and cumulative
+    /// limits
+    pub fn record_spend(&mut self, mint: &Pubkey, amount_spent: u64) -> Result<()> {
+        if self.spend_limit_count == 0 {
+            return Ok(());
+        }
+        let index = self
+            .spend_limit_index(mint)
+            .ok_or(error!(CustomError::SessionMintNotAllowed))?;
+        let limit = self.spend_limits[index];
+        require!(
+            limit.max_trade_amount == 0 || amount_spent <= limit.max_trade_amount,
+            CustomError::SessionTradeTooLarge
+        );
+        self.spent[index] = self.spent[index].saturating_add(amount_spent);
+        require!(
+            limit.spend_cap == 0 || self.spent[index] <= limit.spend_cap,
+            CustomError::SessionCapExceeded
+        );
+        Ok(())
+    }
+}
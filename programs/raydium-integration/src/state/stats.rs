@@ -0,0 +1,117 @@
+//! Protocol-wide and per-pool counters.
+use anchor_lang::prelude::*;
+
+/*
+ * Protocol-wide counters, PDA seeded by [b"global_stats"]. Volumes and fees
+ * are raw token units summed across mints
+ */
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct GlobalStats {
+    pub swap_count: u64,
+    pub volume: u128,
+    pub positions_opened: u64,
+    pub pools_tracked: u64,
+    pub protocol_fees_collected: u128,
+    pub last_update_ts: i64,
+}
+
+impl GlobalStats {
+    pub const SIZE: usize = 8 + 16 + 8 + 8 + 16 + 8;
+
+    pub fn record_swap(&mut self, amount_in: u64, now: i64) {
+        self.swap_count = self.swap_count.saturating_add(1);
+        self.volume = self.volume.saturating_add(amount_in as u128);
+        self.last_update_ts = now;
+    }
+
+    pub fn record_protocol_fee(&mut self, fee: u64) {
+        self.protocol_fees_collected = self.protocol_fees_collected.saturating_add(fee as u128);
+    }
+}
+
+/*
+ * Per-pool counters, PDA seeded by [b"pool_stats", pool]
+ */
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PoolStats {
+    pub pool: Pubkey,
+    pub swap_count: u64,
+    /// Cumulative token_0 input of zero-for-one swaps
+    pub volume_token_0_in: u128,
+    /// Cumulative token_1 input of one-for-zero swaps
+    pub volume_token_1_in: u128,
+    pub positions_opened: u64,
+    pub protocol_fees_0: u64,
+    pub protocol_fees_1: u64,
+    pub last_update_ts: i64,
+}
+
+impl PoolStats {
+    pub const SIZE: usize = 32 + 8 + 16 + 16 + 8 + 8 + 8 + 8;
+
+    pub fn record_swap(&mut self, zero_for_one: bool, amount_in: u64, now: i64) {
+        self.swap_count = self.swap_count.saturating_add(1);
+        if zero_for_one {
+            self.volume_token_0_in = self.volume_token_0_in.saturating_add(amount_in as u128);
+        } else {
+            self.volume_token_1_in = self.volume_token_1_in.saturating_add(amount_in as u128);
+        }
+        self.last_update_ts = now;
+    }
+
+    pub fn record_protocol_fee(&mut self, zero_for_one: bool, fee: u64) {
+        if zero_for_one {
+            self.protocol_fees_0 = self.protocol_fees_0.saturating_add(fee);
+        } else {
+            self.protocol_fees_1 = self.protocol_fees_1.saturating_add(fee);
+        }
+    }
+}
+
+/*
+ * Round trips one user makes on one pool, PDA seeded by
+ * [b"round_trips", owner, pool]: swaps reversing the direction of the
+ * previous one within the governance window
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct RoundTripCounter {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub last_zero_for_one: bool,
+    /// Zero before the first swap
+    pub last_swap_ts: i64,
+    pub window_start_ts: i64,
+    /// Round trips since `window_start_ts`
+    pub round_trips: u16,
+    /// Swaps that went past the threshold
+    pub times_flagged: u32,
+}
+
+impl RoundTripCounter {
+    pub const SIZE: usize = 32 + 32 + 1 + 8 + 8 + 2 + 4;
+
+    /*
+     * Count a swap, starting a new window once `window_secs` have passed.
+     * Returns the round trips in the current window
+     */
+    pub fn record_swap(&mut self, zero_for_one: bool, now: i64, window_secs: i64) -> u16 {
+        if now.saturating_sub(self.window_start_ts) >= window_secs {
+            self.window_start_ts = now;
+            self.round_trips = 0;
+        }
+        if self.last_swap_ts != 0
+            && zero_for_one != self.last_zero_for_one
+            && now.saturating_sub(self.last_swap_ts) < window_secs
+        {
+            self.round_trips = self.round_trips.saturating_add(1);
+        }
+        self.last_zero_for_one = zero_for_one;
+        self.last_swap_ts = now;
+        self.round_trips
+    }
+}
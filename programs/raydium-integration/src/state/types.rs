@@ -0,0 +1,179 @@
+//! Instruction payloads and return data, never stored on chain.
+use anchor_lang::prelude::*;
+
+/*
+ * Instruction data validators receive after `VALIDATE_SWAP_DISCRIMINATOR`,
+ * with the payer, pool, input and output token accounts as read-only
+ * accounts
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapValidation {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount: u64,
+    pub expected_other_amount: u64,
+    pub is_base_input: bool,
+}
+
+/*
+ * Callback payload after `SWAP_CALLBACK_DISCRIMINATOR`, with the amounts the
+ * swap actually moved
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapCallback {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+/*
+ * Callback payload after `LIQUIDITY_CALLBACK_DISCRIMINATOR`. Liquidity is
+ * positive when added, negative when removed; amounts are principal
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquidityCallback {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft: Pubkey,
+    pub liquidity_delta: i128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub fees_0: u64,
+    pub fees_1: u64,
+}
+
+/*
+ * Return data of `claim_points`
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PointsClaim {
+    pub amount: u128,
+    pub total_claimed: u128,
+}
+
+/*
+ * Return data of `get_impermanent_loss`. Values are raw token_1 units at the
+ * current price; `il_bps` is negative when the position trails holding
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ImpermanentLossSnapshot {
+    pub sqrt_price_x64: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub hodl_amount_0: u64,
+    pub hodl_amount_1: u64,
+    pub position_value_token_1: u128,
+    pub hodl_value_token_1: u128,
+    pub il_bps: i64,
+}
+
+/*
+ * Return data of `get_position_value`. Fees are owed but not yet collected,
+ * rewards are left out
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PositionValue {
+    pub sqrt_price_x64: u128,
+    pub liquidity: u128,
+    /// Principal the position would return if fully withdrawn now
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub fees_0: u64,
+    pub fees_1: u64,
+    /// Principal and fees in raw token_1 units at the current price
+    pub value_token_1: u128,
+}
+
+/*
+ * Return data of `get_pool_health`. Depth is what a swap moving the price
+ * `tick_radius` ticks would take out of the pool, before fees
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PoolHealth {
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    /// Liquidity active at the current price
+    pub liquidity: u128,
+    /// Least liquidity active anywhere within the radius
+    pub min_liquidity_in_range: u128,
+    /// token_0 available up to `tick_radius` ticks above the price
+    pub depth_0: u64,
+    /// token_1 available down to `tick_radius` ticks below the price
+    pub depth_1: u64,
+    /// Raydium's trade fee rate, in millionths
+    pub trade_fee_rate: u32,
+    /// Seconds since the pool's oracle last recorded an observation
+    pub observation_age: i64,
+}
+
+/*
+ * Return data of `get_status`, for UIs to check for and explain downtime
+ * before sending a transaction
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProgramStatus {
+    /// The global config's `FLAG_*` bits
+    pub flags: u8,
+    /// `FLAG_PAUSED`: everything adding risk fails; exits stay open
+    pub paused: bool,
+    /// `FLAG_PAUSE_SWAPS`: swaps passing the global config fail
+    pub swaps_paused: bool,
+    pub requires_user_slippage: bool,
+    pub protocol_fee_bps: u16,
+    pub insurance_share_bps: u16,
+    /// Flags of the scheduled parameter change; zero without one
+    pub pending_flags: u8,
+    /// Slot the scheduled change applies from; zero without one
+    pub pending_activation_slot: u64,
+}
+
+/*
+ * Return data of a `simulate_only` swap, from actual balance changes
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapSimulation {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub sqrt_price_after_x64: u128,
+    pub tick_after: i32,
+    /// The proxy's protocol fee, paid on top of `amount_in`
+    pub protocol_fee: u64,
+}
+
+/*
+ * Return data of a `partial_fill` swap. Input that was not spent never
+ * leaves the caller's token account
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SwapFill {
+    pub amount_requested: u64,
+    pub amount_filled: u64,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Raydium's trade fee, included in `amount_in`
+    pub amm_fee: u64,
+    /// The proxy's protocol fee, paid on top of `amount_in`
+    pub protocol_fee: u64,
+}
+
+/*
+ * Return data of a `simulate_only` open/increase/decrease. Amounts are
+ * principal; fees are only paid out on decrease
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquiditySimulation {
+    pub liquidity: u128,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub fees_0: u64,
+    pub fees_1: u64,
+    /// Principal as wallets display it for interest-bearing or scaled-UI mints
+    pub ui_amount_0: u64,
+    pub ui_amount_1: u64,
+}
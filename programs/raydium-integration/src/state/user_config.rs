@@ -0,0 +1,234 @@
+//! Per-user settings and opt-ins.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::math::{convert_at_sqrt_price, value_in_token_1};
+use crate::{CustomError, DEFAULT_SLIPPAGE_BPS, MAX_ALLOWED_CALLERS};
+
+/*
+ * Per-user settings, PDA seeded by [b"user_cfg", owner]
+ */
+#[account(zero_copy(unsafe))]
+#[repr(C, packed)]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct UserConfig {
+    pub owner: Pubkey,
+    pub slippage_bps: u16,
+    pub total_swaps: u64,
+    /// Cumulative input spent swapping token_0 for token_1, raw units
+    pub volume_zero_for_one: u128,
+    /// Cumulative input spent swapping token_1 for token_0, raw units
+    pub volume_one_for_zero: u128,
+    /// Cumulative AMM trade fees implied by the pools' fee rates, raw units
+    pub fees_paid: u128,
+    pub positions_opened: u64,
+    pub last_activity_ts: i64,
+    /// Programs allowed as the top-level caller, first `allowed_caller_count`
+    pub allowed_callers: [Pubkey; MAX_ALLOWED_CALLERS],
+    pub allowed_caller_count: u8,
+    /// Swaps whose realized slippage is summed below
+    pub slippage_samples: u64,
+    /// Sum of realized slippage in basis points, negative where swaps beat
+    /// their expected amount
+    pub realized_slippage_bps_sum: i64,
+    /// Mint exposure is valued in
+    pub exposure_quote_mint: Pubkey,
+    /// Most one pool may hold, in quote units; zero for no cap
+    pub max_pool_exposure: u64,
+    /// Most all pools together may hold, in quote units; zero for no cap
+    pub max_total_exposure: u64,
+    /// Deposits less withdrawals while capped, each valued at the pool price
+    /// of its time
+    pub total_exposure: u64,
+    /// Events stamped with the owner's sequence so far, see [`Self::next_event_seq`]
+    pub event_seq: u64,
+}
+
+impl UserConfig {
+    pub const SIZE: usize = 32
+        + 2
+        + 8
+        + 16
+        + 16
+        + 16
+        + 8
+        + 8
+        + 32 * MAX_ALLOWED_CALLERS
+        + 1
+        + 8
+        + 8
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8;
+    /// Size of the Borsh config from before the switch to zero-copy, `owner`
+    /// and `slippage_bps`. Later layouts only appended fields, so any config
+    /// grown and zero-filled to `SIZE` keeps both
+    pub const LEGACY_SIZE: usize = 32 + 2;
+
+    /// Whether a config account has the current layout. One in an earlier,
+    /// shorter layout does not load until `migrate_user_config` grows it
+    pub fn is_current(account: &AccountInfo) -> bool {
+        account.data_len() >= 8 + Self::SIZE
+    }
+
+    /// Fail unless the transaction's top-level instruction belongs to an
+    /// allowed caller; a no-op without an allowlist
+    pub fn check_caller(&self, instructions_sysvar: Option<&UncheckedAccount>) -> Result<()> {
+        if self.allowed_caller_count == 0 {
+            return Ok(());
+        }
+        let instructions_sysvar =
+            instructions_sysvar.ok_or(CustomError::InstructionsSysvarRequired)?;
+        let current_index = load_current_index_checked(instructions_sysvar)?;
+        let caller =
+            load_instruction_at_checked(current_index as usize, instructions_sysvar)?.program_id;
+        require!(
+            self.allowed_callers[..self.allowed_caller_count as usize].contains(&caller),
+            CustomError::CallerNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Configured slippage, or the default for users who never set one
+    pub fn effective_slippage_bps(&self) -> u16 {
+        if self.slippage_bps == 0 {
+            DEFAULT_SLIPPAGE_BPS
+        } else {
+            self.slippage_bps
+        }
+    }
+
+    /// Tolerance a swap runs with and whether it is the default; fails for
+    /// users who never set one when `require_user_slippage`
+    pub fn swap_slippage_bps(&self, require_user_slippage: bool) -> Result<(u16, bool)> {
+        let from_default = self.slippage_bps == 0;
+        require!(
+            !(from_default && require_user_slippage),
+            CustomError::SlippageNotSet
+        );
+        let bps = self.effective_slippage_bps();
+        require!(bps > 0 && bps <= 500, CustomError::InvalidSlippage);
+        Ok((bps, from_default))
+    }
+
+    /// Average realized slippage over the user's swaps, in basis points
+    pub fn average_realized_slippage_bps(&self) -> Option<i64> {
+        let samples = self.slippage_samples;
+        let sum = self.realized_slippage_bps_sum;
+        (samples > 0).then(|| sum / samples as i64)
+    }
+
+    /// Whether deposits count against exposure caps
+    pub fn has_exposure_caps(&self) -> bool {
+        self.max_pool_exposure > 0 || self.max_total_exposure > 0
+    }
+
+    /// Value of a pool's token pair in the quote mint at the pool price;
+    /// fails for pools that don't trade the quote mint
+    pub fn exposure_value(
+        &self,
+        token_mint_0: &Pubkey,
+        token_mint_1: &Pubkey,
+        amount_0: u64,
+        amount_1: u64,
+        sqrt_price_x64: u128,
+    ) -> Result<u64> {
+        let quote_mint = self.exposure_quote_mint;
+        if quote_mint == *token_mint_1 {
+            Ok(value_in_token_1(amount_0, amount_1, sqrt_price_x64).min(u64::MAX as u128) as u64)
+        } else if quote_mint == *token_mint_0 {
+            Ok(amount_0.saturating_add(convert_at_sqrt_price(amount_1, sqrt_price_x64, false)))
+        } else {
+            err!(CustomError::ExposureUnpriced)
+        }
+    }
+
+    /// Count `value` against the total cap
+    pub fn add_exposure(&mut self, value: u64) -> Result<()> {
+        let total = self.total_exposure.saturating_add(value);
+        let max = self.max_total_exposure;
+        require!(max == 0 || total <= max, CustomError::ExposureCapExceeded);
+        self.total_exposure = total;
+        Ok(())
+    }
+
+    pub fn remove_exposure(&mut self, value: u64) {
+        self.total_exposure = self.total_exposure.saturating_sub(value);
+    }
+
+    /// Sequence number of the next event about the owner, from 1
+    pub fn next_event_seq(&mut self) -> u64 {
+        self.event_seq = self.event_seq.saturating_add(1);
+        self.event_seq
+    }
+
+    pub fn record_swap(
+        &mut self,
+        zero_for_one: bool,
+        amount_in: u64,
+        fee: u64,
+        realized_slippage_bps: i64,
+        now: i64,
+    ) {
+        self.total_swaps = self.total_swaps.saturating_add(1);
+        self.slippage_samples = self.slippage_samples.saturating_add(1);
+        self.realized_slippage_bps_sum = self
+            .realized_slippage_bps_sum
+            .saturating_add(realized_slippage_bps);
+        if zero_for_one {
+            self.volume_zero_for_one = self.volume_zero_for_one.saturating_add(amount_in as u128);
+        } else {
+            self.volume_one_for_zero = self.volume_one_for_zero.saturating_add(amount_in as u128);
+        }
+        self.fees_paid = self.fees_paid.saturating_add(fee as u128);
+        self.last_activity_ts = now;
+    }
+}
+
+/*
+ * Token-2022 extensions a user accepts on pool mints when depositing, PDA
+ * seeded by [b"extension_opt_in", owner]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct MintExtensionOptIn {
+    pub owner: Pubkey,
+    /// Bitmask of `token_extensions` risky-extension flags
+    pub allowed_extensions: u8,
+}
+
+impl MintExtensionOptIn {
+    pub const SIZE: usize = 32 + 1;
+}
+
+/*
+ * A user's exposure to one pool while their config caps it, PDA seeded by
+ * [b"pool_exposure", owner, pool]
+ */
+#[account]
+#[cfg_attr(feature = "cli", derive(Debug))]
+pub struct PoolExposure {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    /// Deposits less withdrawals, in the config's quote units
+    pub exposure: u64,
+}
+
+impl PoolExposure {
+    pub const SIZE: usize = 32 + 32 + 8;
+
+    /// Count `value` against a per-pool cap of `max`, zero for none
+    pub fn add(&mut self, value: u64, max: u64) -> Result<()> {
+        let exposure = self.exposure.saturating_add(value);
+        require!(
+            max == 0 || exposure <= max,
+            CustomError::ExposureCapExceeded
+        );
+        self.exposure = exposure;
+        Ok(())
+    }
+}
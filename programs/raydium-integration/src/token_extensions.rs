@@ -0,0 +1,285 @@
+//! Token-2022 mint extension handling.
+//!
+//! Interest-bearing and scaled-UI-amount mints keep raw balances fixed while
+//! wallets display them multiplied by a factor that can change over time.
+//! Other extensions can change what a transfer does behind the pool's back;
+//! deposits into pools with such mints need the user's explicit opt-in.
+//! Extensions are read straight from the mint's TLV data, so the layouts below
+//! follow the Token-2022 program rather than a particular `spl-token-2022`
+//! crate version.
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
+
+use crate::{compute_slippage_threshold, CustomError, MintExtensionOptIn};
+
+/// A permanent delegate can move or burn tokens out of any account, vaults included
+pub const PERMANENT_DELEGATE: u8 = 1 << 0;
+/// Transfer hooks run arbitrary mint-chosen code on every transfer
+pub const TRANSFER_HOOK: u8 = 1 << 1;
+/// Confidential balances move outside the amounts the pool and proxy see
+pub const CONFIDENTIAL_TRANSFER: u8 = 1 << 2;
+/// A pause authority can halt every transfer, withdrawals included
+pub const PAUSABLE: u8 = 1 << 3;
+
+pub const ALL_RISKY_EXTENSIONS: u8 =
+    PERMANENT_DELEGATE | TRANSFER_HOOK | CONFIDENTIAL_TRANSFER | PAUSABLE;
+
+/// Size of a Token-2022 account base; extension data of mints is padded to it
+const BASE_ACCOUNT_LEN: usize = 165;
+
+/// Type-length-value entries start after the one-byte account type
+const TLV_START: usize = BASE_ACCOUNT_LEN + 1;
+
+const EXTENSION_UNINITIALIZED: u16 = 0;
+const EXTENSION_TRANSFER_FEE_CONFIG: u16 = 1;
+const EXTENSION_CONFIDENTIAL_TRANSFER_MINT: u16 = 4;
+const EXTENSION_INTEREST_BEARING_CONFIG: u16 = 10;
+const EXTENSION_PERMANENT_DELEGATE: u16 = 12;
+const EXTENSION_TRANSFER_HOOK: u16 = 14;
+const EXTENSION_CONFIDENTIAL_TRANSFER_FEE_CONFIG: u16 = 16;
+const EXTENSION_SCALED_UI_AMOUNT: u16 = 25;
+const EXTENSION_PAUSABLE: u16 = 26;
+
+/// Year length the Token-2022 interest-bearing extension compounds over
+const SECONDS_PER_YEAR: f64 = 60.0 * 60.0 * 24.0 * 365.24;
+
+const ONE_IN_BASIS_POINTS: f64 = 10_000.0;
+
+/*
+ * Factor wallets multiply this mint's raw amounts by at `unix_timestamp`.
+ * 1.0 for SPL Token mints and Token-2022 mints without either extension
+ */
+pub fn ui_multiplier(mint: &AccountInfo, unix_timestamp: i64) -> Result<f64> {
+    if mint.owner != &TOKEN_2022_PROGRAM_ID {
+        return Ok(1.0);
+    }
+    let data = mint.try_borrow_data()?;
+    let mut multiplier = 1.0;
+    if let Some(config) = extension_data(&data, EXTENSION_INTEREST_BEARING_CONFIG) {
+        multiplier *= interest_bearing_scale(config, unix_timestamp);
+    }
+    if let Some(config) = extension_data(&data, EXTENSION_SCALED_UI_AMOUNT) {
+        multiplier *= scaled_ui_multiplier(config, unix_timestamp);
+    }
+    Ok(multiplier)
+}
+
+/*
+ * Raw amount as a wallet displays it, still in base units, rounded down
+ */
+pub fn to_ui_amount(amount: u64, multiplier: f64) -> u64 {
+    (amount as f64 * multiplier) as u64
+}
+
+/*
+ * Raw amount a wallet-displayed amount corresponds to, rounded up or down
+ */
+pub fn to_raw_amount(ui_amount: u64, multiplier: f64, round_up: bool) -> u64 {
+    let amount = ui_amount as f64 / multiplier;
+    if round_up {
+        amount.ceil() as u64
+    } else {
+        amount as u64
+    }
+}
+
+/*
+ * `compute_slippage_threshold` applied to the amount wallets display for
+ * `mint` at `unix_timestamp`, then converted back to a raw bound. Rounds in
+ * the user's favour, so the displayed amount is never worse than the
+ * tolerance allows
+ */
+pub fn slippage_threshold(
+    mint: &AccountInfo,
+    expected: u64,
+    bps: u16,
+    is_base_input: bool,
+    unix_timestamp: i64,
+) -> Result<u64> {
+    let multiplier = ui_multiplier(mint, unix_timestamp)?;
+    if multiplier == 1.0 || multiplier <= 0.0 {
+        return Ok(compute_slippage_threshold(expected, bps, is_base_input));
+    }
+    let ui_threshold =
+        compute_slippage_threshold(to_ui_amount(expected, multiplier), bps, is_base_input);
+    Ok(to_raw_amount(ui_threshold, multiplier, is_base_input))
+}
+
+/*
+ * Pool token amounts as wallets display them at `unix_timestamp`
+ */
+pub fn ui_amounts(
+    mint_0: &AccountInfo,
+    mint_1: &AccountInfo,
+    amount_0: u64,
+    amount_1: u64,
+    unix_timestamp: i64,
+) -> Result<(u64, u64)> {
+    Ok((
+        to_ui_amount(amount_0, ui_multiplier(mint_0, unix_timestamp)?),
+        to_ui_amount(amount_1, ui_multiplier(mint_1, unix_timestamp)?),
+    ))
+}
+
+/*
+ * Fee Token-2022 withholds from a transfer of `amount` in `epoch`, zero for
+ * SPL Token mints and mints without a transfer fee
+ */
+pub fn transfer_fee(mint: &AccountInfo, amount: u64, epoch: u64) -> Result<u64> {
+    if mint.owner != &TOKEN_2022_PROGRAM_ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    Ok(extension_data(&data, EXTENSION_TRANSFER_FEE_CONFIG)
+        .map_or(0, |config| transfer_fee_for(config, amount, epoch)))
+}
+
+/*
+ * Risky-extension flags set on a mint, zero for SPL Token mints
+ */
+pub fn risky_extensions(mint: &AccountInfo) -> Result<u8> {
+    if mint.owner != &TOKEN_2022_PROGRAM_ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let flags = extension_types(&data)
+        .map(|extension_type| match extension_type {
+            EXTENSION_PERMANENT_DELEGATE => PERMANENT_DELEGATE,
+            EXTENSION_TRANSFER_HOOK => TRANSFER_HOOK,
+            EXTENSION_CONFIDENTIAL_TRANSFER_MINT | EXTENSION_CONFIDENTIAL_TRANSFER_FEE_CONFIG => {
+                CONFIDENTIAL_TRANSFER
+            }
+            EXTENSION_PAUSABLE => PAUSABLE,
+            _ => 0,
+        })
+        .fold(0, |flags, flag| flags | flag);
+    Ok(flags)
+}
+
+/*
+ * Fail unless every risky extension on the mints is covered by the owner's
+ * opt-in; without one, none are allowed
+ */
+pub fn require_allowed_extensions(
+    mints: &[AccountInfo],
+    opt_in: Option<&MintExtensionOptIn>,
+) -> Result<()> {
+    let allowed = opt_in.map_or(0, |opt_in| opt_in.allowed_extensions);
+    for mint in mints {
+        require!(
+            risky_extensions(mint)? & !allowed == 0,
+            CustomError::UnsupportedMintExtension
+        );
+    }
+    Ok(())
+}
+
+/*
+ * Value of the given extension entry, if the mint has it
+ */
+fn extension_data(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    extension_entries(data)
+        .find(|(entry_type, _)| *entry_type == extension_type)
+        .map(|(_, value)| value)
+}
+
+fn extension_types(data: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    extension_entries(data).map(|(entry_type, _)| entry_type)
+}
+
+/*
+ * (type, value) of each initialized TLV entry, stopping at the first
+ * malformed or uninitialized one
+ */
+fn extension_entries(data: &[u8]) -> impl Iterator<Item = (u16, &[u8])> + '_ {
+    let mut tlv = data.get(TLV_START..).unwrap_or_default();
+    std::iter::from_fn(move || {
+        let header = tlv.get(..4)?;
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let length = u16::from_le_bytes([header[2], header[3]]) as usize;
+        if entry_type == EXTENSION_UNINITIALIZED {
+            return None;
+        }
+        let value = tlv.get(4..4 + length)?;
+        tlv = &tlv[4 + length..];
+        Some((entry_type, value))
+    })
+}
+
+/*
+ * InterestBearingConfig: rate_authority (32), initialization_timestamp (i64),
+ * pre_update_average_rate (i16 bps), last_update_timestamp (i64),
+ * current_rate (i16 bps). Interest compounds continuously, at the average
+ * rate up to the last update and at the current rate since
+ */
+fn interest_bearing_scale(config: &[u8], unix_timestamp: i64) -> f64 {
+    let Some(config) = config.get(32..52) else {
+        return 1.0;
+    };
+    let initialization_timestamp = read_i64(&config[0..8]);
+    let pre_update_average_rate = read_i16(&config[8..10]);
+    let last_update_timestamp = read_i64(&config[10..18]);
+    let current_rate = read_i16(&config[18..20]);
+
+    let compound = |rate: i16, from: i64, to: i64| {
+        let years = to.saturating_sub(from) as f64 / SECONDS_PER_YEAR;
+        (rate as f64 / ONE_IN_BASIS_POINTS * years).exp()
+    };
+    compound(
+        pre_update_average_rate,
+        initialization_timestamp,
+        last_update_timestamp,
+    ) * compound(current_rate, last_update_timestamp, unix_timestamp)
+}
+
+/*
+ * ScaledUiAmountConfig: authority (32), multiplier (f64),
+ * new_multiplier_effective_timestamp (i64), new_multiplier (f64)
+ */
+fn scaled_ui_multiplier(config: &[u8], unix_timestamp: i64) -> f64 {
+    let Some(config) = config.get(32..56) else {
+        return 1.0;
+    };
+    if unix_timestamp >= read_i64(&config[8..16]) {
+        read_f64(&config[16..24])
+    } else {
+        read_f64(&config[0..8])
+    }
+}
+
+/*
+ * TransferFeeConfig: two authorities (64), withheld_amount (u64), then the
+ * older and newer TransferFee, each epoch (u64), maximum_fee (u64) and
+ * transfer_fee_basis_points (u16). The newer fee applies from its epoch on;
+ * fees round up and are capped at the maximum
+ */
+fn transfer_fee_for(config: &[u8], amount: u64, epoch: u64) -> u64 {
+    let Some(fees) = config.get(72..108) else {
+        return 0;
+    };
+    let fee = if epoch >= read_u64(&fees[18..26]) {
+        &fees[18..36]
+    } else {
+        &fees[0..18]
+    };
+    let maximum_fee = read_u64(&fee[8..16]);
+    let basis_points = u16::from_le_bytes([fee[16], fee[17]]) as u128;
+    let fee = (amount as u128 * basis_points).div_ceil(ONE_IN_BASIS_POINTS as u128);
+    (fee as u64).min(maximum_fee)
+}
+
+fn read_i16(bytes: &[u8]) -> i16 {
+    i16::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn read_i64(bytes: &[u8]) -> i64 {
+    i64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes.try_into().unwrap())
+}
+
+fn read_f64(bytes: &[u8]) -> f64 {
+    f64::from_le_bytes(bytes.try_into().unwrap())
+}
@@ -0,0 +1,219 @@
+//! Invariants of the slippage, fee and liquidity math over arbitrary inputs,
+//! u64::MAX and the tick bounds included. Every rounding goes the way its
+//! doc says: slippage thresholds round away from the expected amount so a
+//! swap at exactly the tolerance passes, deposits never exceed what the user
+//! offered, and fees round the way Raydium and the proxy charge them.
+use proptest::prelude::*;
+use raydium_integration::math::{
+    amount_0_delta, amount_1_delta, amount_before_protocol_fee, compute_protocol_fee,
+    compute_slippage_threshold, compute_trade_fee, liquidity_for_amounts, mean_tick,
+    prorate_threshold, realized_slippage_bps, sqrt_price_at_tick, MAX_SQRT_PRICE_X64, MAX_TICK,
+    MIN_SQRT_PRICE_X64, MIN_TICK,
+};
+use raydium_integration::FEE_RATE_DENOMINATOR;
+
+/*
+ * An ordered tick range inside Raydium's bounds
+ */
+fn tick_range() -> impl Strategy<Value = (i32, i32)> {
+    (MIN_TICK..MAX_TICK).prop_flat_map(|lower| (Just(lower), lower + 1..=MAX_TICK))
+}
+
+fn sqrt_price() -> impl Strategy<Value = u128> {
+    MIN_SQRT_PRICE_X64..MAX_SQRT_PRICE_X64
+}
+
+proptest! {
+    #[test]
+    fn min_output_is_the_floor_of_the_tolerance(expected: u64, bps in 0u16..=10_000) {
+        let threshold = compute_slippage_threshold(expected, bps, true) as u128;
+        let exact_x10000 = expected as u128 * (10_000 - bps as u128);
+        prop_assert!(threshold * 10_000 <= exact_x10000);
+        prop_assert!(exact_x10000 < (threshold + 1) * 10_000);
+        prop_assert!(threshold <= expected as u128);
+    }
+
+    #[test]
+    fn max_input_is_the_ceiling_of_the_tolerance(expected: u64, bps in 0u16..=10_000) {
+        let threshold = compute_slippage_threshold(expected, bps, false) as u128;
+        let exact_x10000 = expected as u128 * (10_000 + bps as u128);
+        if exact_x10000 > u64::MAX as u128 * 10_000 {
+            prop_assert_eq!(threshold, u64::MAX as u128);
+        } else {
+            prop_assert!(threshold * 10_000 >= exact_x10000);
+            prop_assert!(exact_x10000 + 10_000 > threshold * 10_000);
+        }
+        prop_assert!(threshold >= expected as u128);
+    }
+
+    #[test]
+    fn more_tolerance_only_loosens_thresholds(
+        expected: u64,
+        bps in 0u16..10_000,
+        extra in 1u16..=100,
+    ) {
+        let wider = bps.saturating_add(extra).min(10_000);
+        prop_assert!(
+            compute_slippage_threshold(expected, wider, true)
+                <= compute_slippage_threshold(expected, bps, true)
+        );
+        prop_assert!(
+            compute_slippage_threshold(expected, wider, false)
+                >= compute_slippage_threshold(expected, bps, false)
+        );
+    }
+
+    #[test]
+    fn swaps_within_the_threshold_realize_at_most_the_tolerance(
+        amount in 1u64..,
+        // Below 10_000 units, flooring the minimum output alone can cost
+        // more than a basis point
+        expected in 10_000u64..,
+        bps in 1u16..=500,
+        is_base_input: bool,
+    ) {
+        let threshold = compute_slippage_threshold(expected, bps, is_base_input);
+        let (spent, received) = if is_base_input {
+            (amount, threshold)
+        } else {
+            (threshold, amount)
+        };
+        let realized = realized_slippage_bps(amount, expected, spent, received, is_base_input);
+        prop_assert!(realized <= bps as i64, "{realized} > {bps}");
+        prop_assert_eq!(realized_slippage_bps(amount, expected, amount, expected, true), 0);
+    }
+
+    #[test]
+    fn prorated_thresholds_round_towards_the_user(
+        threshold: u64,
+        requested in 1u64..,
+        filled_share in 0.0f64..=1.0,
+    ) {
+        let filled = ((requested as f64 * filled_share) as u64).min(requested);
+        let exact = threshold as u128 * filled as u128;
+        let min_out = prorate_threshold(threshold, filled, requested, true) as u128;
+        let max_in = prorate_threshold(threshold, filled, requested, false) as u128;
+        // The minimum output rounds up, the maximum input down
+        prop_assert!(min_out * requested as u128 >= exact);
+        prop_assert!(max_in * requested as u128 <= exact);
+        prop_assert!(min_out <= threshold as u128 && max_in <= threshold as u128);
+    }
+
+    #[test]
+    fn trade_fee_rounds_up_and_stays_within_the_input(
+        amount_in: u64,
+        rate in 0u32..=FEE_RATE_DENOMINATOR as u32,
+    ) {
+        let fee = compute_trade_fee(amount_in, rate) as u128;
+        let exact = amount_in as u128 * rate as u128;
+        let denominator = FEE_RATE_DENOMINATOR as u128;
+        prop_assert!(fee * denominator >= exact);
+        prop_assert!(fee * denominator < exact + denominator);
+        prop_assert!(fee <= amount_in as u128);
+    }
+
+    #[test]
+    fn protocol_fee_rounds_down(amount: u64, bps in 0u16..=10_000) {
+        let fee = compute_protocol_fee(amount, bps) as u128;
+        prop_assert!(fee * 10_000 <= amount as u128 * bps as u128);
+        prop_assert!(fee <= amount as u128);
+    }
+
+    #[test]
+    fn pool_input_and_protocol_fee_fit_the_amount(amount: u64, bps in 0u16..=10_000) {
+        let pool_input = amount_before_protocol_fee(amount, bps);
+        let fee = compute_protocol_fee(pool_input, bps);
+        prop_assert!(pool_input as u128 + fee as u128 <= amount as u128);
+        // Rounding leaves at most a few units of the amount unused
+        prop_assert!(amount - pool_input - fee <= 2 + bps as u64 / 10_000);
+    }
+
+    #[test]
+    fn mean_tick_recovers_a_constant_tick(
+        tick in MIN_TICK..=MAX_TICK,
+        start in 0u32..u32::MAX / 2,
+        elapsed in 1u32..=86_400,
+        cumulative in -(1i64 << 60)..(1i64 << 60),
+    ) {
+        let newer = (start + elapsed, cumulative + tick as i64 * elapsed as i64);
+        prop_assert_eq!(mean_tick((start, cumulative), newer), Some(tick));
+        prop_assert_eq!(mean_tick(newer, newer), None);
+    }
+
+    #[test]
+    fn amount_deltas_round_by_at_most_one(
+        sqrt_price_a in sqrt_price(),
+        sqrt_price_b in sqrt_price(),
+        liquidity: u128,
+    ) {
+        let deltas: [fn(u128, u128, u128, bool) -> u64; 2] = [amount_0_delta, amount_1_delta];
+        for delta in deltas {
+            let down = delta(sqrt_price_a, sqrt_price_b, liquidity, false);
+            let up = delta(sqrt_price_a, sqrt_price_b, liquidity, true);
+            prop_assert!(down <= up);
+            prop_assert!(up - down <= 1);
+            // The order of the prices doesn't matter
+            prop_assert_eq!(up, delta(sqrt_price_b, sqrt_price_a, liquidity, true));
+        }
+    }
+
+    #[test]
+    fn deposits_never_exceed_the_amounts_offered(
+        (tick_lower, tick_upper) in tick_range(),
+        sqrt_price_x64 in sqrt_price(),
+        amount_0: u64,
+        amount_1: u64,
+    ) {
+        let liquidity =
+            liquidity_for_amounts(sqrt_price_x64, tick_lower, tick_upper, amount_0, amount_1)
+                .unwrap();
+        let lower = sqrt_price_at_tick(tick_lower).unwrap();
+        let upper = sqrt_price_at_tick(tick_upper).unwrap();
+        // Raydium takes deposits rounded up
+        let (deposit_0, deposit_1) = if sqrt_price_x64 <= lower {
+            (amount_0_delta(lower, upper, liquidity, true), 0)
+        } else if sqrt_price_x64 < upper {
+            (
+                amount_0_delta(sqrt_price_x64, upper, liquidity, true),
+                amount_1_delta(lower, sqrt_price_x64, liquidity, true),
+            )
+        } else {
+            (0, amount_1_delta(lower, upper, liquidity, true))
+        };
+        prop_assert!(deposit_0 <= amount_0, "token_0 {deposit_0} > {amount_0}");
+        prop_assert!(deposit_1 <= amount_1, "token_1 {deposit_1} > {amount_1}");
+    }
+
+    #[test]
+    fn liquidity_grows_with_the_amounts(
+        (tick_lower, tick_upper) in tick_range(),
+        sqrt_price_x64 in sqrt_price(),
+        amount_0 in 0u64..u64::MAX / 2,
+        amount_1 in 0u64..u64::MAX / 2,
+    ) {
+        let liquidity =
+            liquidity_for_amounts(sqrt_price_x64, tick_lower, tick_upper, amount_0, amount_1)
+                .unwrap();
+        let doubled = liquidity_for_amounts(
+            sqrt_price_x64,
+            tick_lower,
+            tick_upper,
+            amount_0 * 2,
+            amount_1 * 2,
+        )
+        .unwrap();
+        prop_assert!(doubled >= liquidity);
+    }
+}
+
+#[test]
+fn thresholds_hold_at_u64_max() {
+    assert_eq!(compute_slippage_threshold(u64::MAX, 0, true), u64::MAX);
+    assert_eq!(compute_slippage_threshold(u64::MAX, 0, false), u64::MAX);
+    assert_eq!(compute_slippage_threshold(u64::MAX, 500, false), u64::MAX);
+    assert_eq!(compute_slippage_threshold(u64::MAX, 10_000, true), 0);
+    assert_eq!(
+        amount_0_delta(MIN_SQRT_PRICE_X64, MAX_SQRT_PRICE_X64, u128::MAX, true),
+        u64::MAX
+    );
+}
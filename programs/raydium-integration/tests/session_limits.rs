@@ -0,0 +1,126 @@
+//! Session spend limits are kept per input mint: amounts in different mints
+//! never add up, a mint without limits is refused once any are set, and
+//! re-setting the limits keeps what a still-listed mint already spent.
+use anchor_lang::prelude::*;
+use raydium_integration::{
+    CustomError, Session, SessionSpendLimit, ALL_SESSION_SCOPES, MAX_SESSION_POOLS,
+    MAX_SESSION_SPEND_MINTS, SESSION_DIRECTION_ANY,
+};
+
+fn session() -> Session {
+    Session {
+        owner: Pubkey::new_unique(),
+        session_key: Pubkey::new_unique(),
+        expiry: i64::MAX,
+        scope: ALL_SESSION_SCOPES,
+        allowed_pools: [Pubkey::default(); MAX_SESSION_POOLS],
+        pool_count: 0,
+        direction: SESSION_DIRECTION_ANY,
+        spend_limits: [SessionSpendLimit::default(); MAX_SESSION_SPEND_MINTS],
+        spend_limit_count: 0,
+        spent: [0; MAX_SESSION_SPEND_MINTS],
+    }
+}
+
+fn limit(mint: Pubkey, max_trade_amount: u64, spend_cap: u64) -> SessionSpendLimit {
+    SessionSpendLimit {
+        mint,
+        max_trade_amount,
+        spend_cap,
+    }
+}
+
+#[test]
+fn no_limits_allow_any_mint() {
+    let mut session = session();
+    session
+        .record_spend(&Pubkey::new_unique(), u64::MAX)
+        .unwrap();
+}
+
+#[test]
+fn caps_are_kept_per_mint() {
+    let (usdc, bonk) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut session = session();
+    session
+        .set_spend_limits(&[limit(usdc, 0, 1_000), limit(bonk, 0, 1_000_000)])
+        .unwrap();
+
+    session.record_spend(&bonk, 900_000).unwrap();
+    // Bonk spent says nothing about USDC
+    session.record_spend(&usdc, 1_000).unwrap();
+    assert_eq!(session.spent_in(&usdc), 1_000);
+    assert_eq!(session.spent_in(&bonk), 900_000);
+    assert_eq!(
+        session.record_spend(&usdc, 1).unwrap_err(),
+        CustomError::SessionCapExceeded.into()
+    );
+}
+
+#[test]
+fn the_trade_limit_is_per_mint() {
+    let (usdc, bonk) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut session = session();
+    session
+        .set_spend_limits(&[limit(usdc, 100, 0), limit(bonk, 0, 0)])
+        .unwrap();
+    assert_eq!(
+        session.record_spend(&usdc, 101).unwrap_err(),
+        CustomError::SessionTradeTooLarge.into()
+    );
+    session.record_spend(&bonk, 1_000_000).unwrap();
+}
+
+#[test]
+fn refuses_mints_without_limits_once_any_are_set() {
+    let mut session = session();
+    session
+        .set_spend_limits(&[limit(Pubkey::new_unique(), 0, 0)])
+        .unwrap();
+    assert_eq!(
+        session.record_spend(&Pubkey::new_unique(), 1).unwrap_err(),
+        CustomError::SessionMintNotAllowed.into()
+    );
+}
+
+#[test]
+fn resetting_keeps_what_listed_mints_spent() {
+    let (usdc, bonk) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut session = session();
+    session
+        .set_spend_limits(&[limit(usdc, 0, 1_000), limit(bonk, 0, 1_000)])
+        .unwrap();
+    session.record_spend(&usdc, 600).unwrap();
+    session.record_spend(&bonk, 600).unwrap();
+
+    session.set_spend_limits(&[limit(usdc, 0, 1_000)]).unwrap();
+    assert_eq!(session.spent_in(&usdc), 600);
+    assert_eq!(
+        session.record_spend(&usdc, 401).unwrap_err(),
+        CustomError::SessionCapExceeded.into()
+    );
+    // Dropped and listed again, bonk starts over
+    session
+        .set_spend_limits(&[limit(usdc, 0, 1_000), limit(bonk, 0, 1_000)])
+        .unwrap();
+    assert_eq!(session.spent_in(&bonk), 0);
+}
+
+#[test]
+fn rejects_repeated_or_too_many_mints() {
+    let usdc = Pubkey::new_unique();
+    let mut session = session();
+    assert_eq!(
+        session
+            .set_spend_limits(&[limit(usdc, 0, 1), limit(usdc, 0, 2)])
+            .unwrap_err(),
+        CustomError::InvalidSessionSpendLimits.into()
+    );
+    let too_many: Vec<_> = (0..=MAX_SESSION_SPEND_MINTS)
+        .map(|_| limit(Pubkey::new_unique(), 0, 0))
+        .collect();
+    assert_eq!(
+        session.set_spend_limits(&too_many).unwrap_err(),
+        CustomError::InvalidSessionSpendLimits.into()
+    );
+}
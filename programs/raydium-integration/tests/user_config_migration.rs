@@ -0,0 +1,81 @@
+//! Configs written by the Borsh `UserConfig`, before the switch to zero-copy,
+//! are 42 bytes. They don't load as the current layout until
+//! `migrate_user_config` grows and zero-fills them, after which they keep
+//! their owner and tolerance.
+use anchor_lang::prelude::*;
+use raydium_integration::UserConfig;
+
+/*
+ * The account as the Borsh layout wrote it. `#[account]` hashed the same
+ * name, so it carries the zero-copy discriminator
+ */
+#[derive(AnchorSerialize)]
+struct LegacyUserConfig {
+    owner: Pubkey,
+    slippage_bps: u16,
+}
+
+fn legacy_config_data(owner: Pubkey, slippage_bps: u16) -> Vec<u8> {
+    let mut data = UserConfig::DISCRIMINATOR.to_vec();
+    LegacyUserConfig {
+        owner,
+        slippage_bps,
+    }
+    .serialize(&mut data)
+    .unwrap();
+    data
+}
+
+#[test]
+fn legacy_config_is_42_bytes_and_not_current() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = legacy_config_data(Pubkey::new_unique(), 75);
+    assert_eq!(data.len(), 8 + UserConfig::LEGACY_SIZE);
+    assert_eq!(data.len(), 42);
+    let program_id = raydium_integration::ID;
+    let info = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &program_id,
+        false,
+        0,
+    );
+    assert!(!UserConfig::is_current(&info));
+}
+
+#[test]
+fn migrated_legacy_config_loads_with_owner_and_tolerance() {
+    let user = Pubkey::new_unique();
+    let mut data = legacy_config_data(user, 75);
+    // What `migrate_user_config` leaves: grown to the current size, the new
+    // bytes zeroed
+    data.resize(8 + UserConfig::SIZE, 0);
+
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let program_id = raydium_integration::ID;
+    let info = AccountInfo::new(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        &mut data,
+        &program_id,
+        false,
+        0,
+    );
+    assert!(UserConfig::is_current(&info));
+    let loader = AccountLoader::<UserConfig>::try_from(&info).unwrap();
+    let config = loader.load().unwrap();
+    assert_eq!({ config.owner }, user);
+    assert_eq!({ config.slippage_bps }, 75);
+    assert_eq!(config.effective_slippage_bps(), 75);
+    assert_eq!({ config.total_swaps }, 0);
+    assert_eq!({ config.allowed_caller_count }, 0);
+    assert!(!config.has_exposure_caps());
+    assert_eq!({ config.event_seq }, 0);
+}
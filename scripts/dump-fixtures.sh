@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+# Dump the programs and accounts the Rust test harness
+# (crates/raydium-integration-harness) loads, from mainnet unless an RPC URL
+# is given. Extra account addresses to dump as fixtures can follow the URL.
+#
+#   scripts/dump-fixtures.sh [RPC_URL] [ADDRESS...]
+set -euo pipefail
+
+url="${1:-https://api.mainnet-beta.solana.com}"
+shift || true
+dir="$(cd "$(dirname "$0")/.." && pwd)/crates/raydium-integration-harness/tests/fixtures"
+mkdir -p "$dir/accounts"
+
+solana program dump -u "$url" CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK "$dir/raydium_amm_v3.so"
+solana program dump -u "$url" metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s "$dir/mpl_token_metadata.so"
+
+# The WSOL/USDC pool the TypeScript tests swap through, with its vaults and
+# observation account
+accounts=(
+    3ucNos4NbumPLZNWztqGHNFFgkHeRMBQAVemeeomsUxv
+    4ct7br2vTPzfdmY3S5HLtTxcGSBfn6pnw98hsS6v359A
+    5it83u57VRrVgc51oNV19TTmAJuffPx5GtGwQr7gQNUo
+    "$@"
+)
+for address in "${accounts[@]}"; do
+    solana account -u "$url" "$address" --output json --output-file "$dir/accounts/$address.json" >/dev/null
+done
+echo "fixtures written to $dir"
@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+# Pin the workspace's Raydium CLMM dependency to the commit a git ref of
+# raydium-io/raydium-clmm points at, master unless another branch or tag is
+# given, and lock it. Needs network access to GitHub.
+#
+#   scripts/pin-raydium-clmm.sh [REF]
+set -euo pipefail
+
+repo="https://github.com/raydium-io/raydium-clmm"
+ref="${1:-master}"
+root="$(cd "$(dirname "$0")/.." && pwd)"
+
+# An annotated tag lists the tag object first and the commit as `^{}`
+rev="$(git ls-remote "$repo" "$ref" "$ref^{}" | tail -n 1 | cut -f 1)"
+if [[ -z "$rev" ]]; then
+    echo "no ref $ref in $repo" >&2
+    exit 1
+fi
+
+sed -i.bak -E \
+    "s|^raydium-amm-v3 = \{ git = \"$repo\"[^}]*\}|raydium-amm-v3 = { git = \"$repo\", rev = \"$rev\" }|" \
+    "$root/Cargo.toml"
+rm "$root/Cargo.toml.bak"
+cargo update --manifest-path "$root/Cargo.toml" -p raydium-amm-v3
+echo "raydium-amm-v3 pinned to $rev ($ref); commit Cargo.toml and Cargo.lock"
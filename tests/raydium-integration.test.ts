@@ -61,6 +61,21 @@ describe("raydium_integration", () => {
     [Buffer.from("user_cfg"), wallet.toBuffer()],
     program.programId
   );
+  // Registry of swap validator programs, passed to every swap
+  const [SWAP_VALIDATORS] = PublicKey.findProgramAddressSync(
+    [Buffer.from("swap_validators")],
+    program.programId
+  );
+  // Wallet's destination allowlist, passed to every swap
+  const [DESTINATION_ALLOWLIST] = PublicKey.findProgramAddressSync(
+    [Buffer.from("destination_allowlist"), wallet.toBuffer()],
+    program.programId
+  );
+  // Protocol-wide config, required by every swap
+  const [GLOBAL_CONFIG] = PublicKey.findProgramAddressSync(
+    [Buffer.from("global_config")],
+    program.programId
+  );
 
   before(async () => {
     console.log("Initializing test state...");
@@ -78,6 +93,7 @@ describe("raydium_integration", () => {
       .setSlippage(SLIPPAGE_BPS) // 3%
       .accountsStrict({
         owner: wallet,
+        payer: wallet,
         userCfg: USER_CFG,
         systemProgram: SystemProgram.programId,
       }).rpc();
@@ -110,7 +126,10 @@ describe("raydium_integration", () => {
         amountIn,
         amountOut,
         sqrtPriceLimitX64,
-        isBaseInput
+        isBaseInput,
+        null, // max_tick_crossings
+        false, // partial_fill
+        false // simulate_only
       )
       .accountsStrict({
         clmmProgram: CLMM_PROGRAM,
@@ -122,9 +141,34 @@ describe("raydium_integration", () => {
         outputTokenAccount: usdcAta,
         inputVault: poolKeys.vaultA,
         outputVault: poolKeys.vaultB,
+        inputVaultMint: INPUT_VAULT_MINT,
+        outputVaultMint: OUTPUT_VAULT_MINT,
         observationState: poolKeys.observationId,
         tokenProgram: TOKEN_PROGRAM_ID,
         tickArray: tickArrayAddr,
+        poolStats: null,
+        globalStats: null,
+        tickArrayBitmapExtension: null,
+        instructionsSysvar: null,
+        globalConfig: GLOBAL_CONFIG,
+        feeMint: null,
+        treasuryTokenAccount: null,
+        insuranceVault: null,
+        feeTokenProgram: null,
+        pointsConfig: null,
+        pointsLedger: null,
+        roundTripCounter: PublicKey.findProgramAddressSync(
+          [Buffer.from("round_trips"), wallet.toBuffer(), poolInfo.id.toBuffer()],
+          program.programId
+        )[0],
+        feeOracle: null,
+        feeOraclePool: null,
+        feeOracleObservation: null,
+        feePaymentAccount: null,
+        swapValidators: SWAP_VALIDATORS,
+        destinationAllowlist: DESTINATION_ALLOWLIST,
+        mintExtensionOptIn: null,
+        callbackProgram: null,
       })
       .rpc({ skipPreflight: true, commitment: "confirmed" });
 
@@ -142,6 +186,87 @@ describe("raydium_integration", () => {
     expect(slippageBps.toNumber()).to.be.lessThanOrEqual(SLIPPAGE_BPS);
   });
 
+  it("simulates a swap without committing it", async () => {
+    const amountIn = new BN(100_000_000); // 0.1 SOL in lamports
+    const { poolInfo, amountOut, poolKeys } = await findOptimalPoolExactIn(
+      provider.connection,
+      wallet,
+      INPUT_VAULT_MINT,
+      OUTPUT_VAULT_MINT,
+      amountIn
+    );
+
+    const usdcAta = await ensureTokenAccount(provider, OUTPUT_VAULT_MINT, wallet);
+    const wsolAta = await ensureTokenAccount(provider, INPUT_VAULT_MINT, wallet);
+    await wrapSolToWsol(provider, wallet, wsolAta, WSOL_AMOUNT);
+    const usdcBefore = await getAccount(provider.connection, usdcAta);
+
+    const tickArrayAddr = await findCorrectTickArray(poolInfo, true);
+
+    const tx = await program.methods
+      .proxySwap(amountIn, amountOut, new BN(0), true, null, false, true)
+      .accountsStrict({
+        clmmProgram: CLMM_PROGRAM,
+        payer: wallet,
+        userCfg: USER_CFG,
+        ammConfig: poolKeys.ammConfig.id,
+        poolState: poolInfo.id,
+        inputTokenAccount: wsolAta,
+        outputTokenAccount: usdcAta,
+        inputVault: poolKeys.vaultA,
+        outputVault: poolKeys.vaultB,
+        inputVaultMint: INPUT_VAULT_MINT,
+        outputVaultMint: OUTPUT_VAULT_MINT,
+        observationState: poolKeys.observationId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        tickArray: tickArrayAddr,
+        poolStats: null,
+        globalStats: null,
+        tickArrayBitmapExtension: null,
+        instructionsSysvar: null,
+        globalConfig: GLOBAL_CONFIG,
+        feeMint: null,
+        treasuryTokenAccount: null,
+        insuranceVault: null,
+        feeTokenProgram: null,
+        pointsConfig: null,
+        pointsLedger: null,
+        roundTripCounter: PublicKey.findProgramAddressSync(
+          [Buffer.from("round_trips"), wallet.toBuffer(), poolInfo.id.toBuffer()],
+          program.programId
+        )[0],
+        feeOracle: null,
+        feeOraclePool: null,
+        feeOracleObservation: null,
+        feePaymentAccount: null,
+        swapValidators: SWAP_VALIDATORS,
+        destinationAllowlist: DESTINATION_ALLOWLIST,
+        mintExtensionOptIn: null,
+        callbackProgram: null,
+      })
+      .transaction();
+    tx.feePayer = wallet;
+    tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+
+    const { value } = await provider.connection.simulateTransaction(tx);
+
+    // Dry runs always fail with SimulationComplete after writing return data
+    expect(JSON.stringify(value.err)).to.include("Custom");
+    expect(value.logs.join("\n")).to.include("SimulationComplete");
+    expect(value.returnData, "missing return data").to.not.be.null;
+
+    // SwapSimulation: amount_in u64, amount_out u64, fee u64, ...
+    const data = Buffer.from(value.returnData.data[0], "base64");
+    const simulatedIn = new BN(data.subarray(0, 8), "le");
+    const simulatedOut = new BN(data.subarray(8, 16), "le");
+    expect(simulatedIn.eq(amountIn)).to.be.true;
+    expect(simulatedOut.gtn(0)).to.be.true;
+
+    // Nothing was committed
+    const usdcAfter = await getAccount(provider.connection, usdcAta);
+    expect(usdcAfter.amount.toString()).to.equal(usdcBefore.amount.toString());
+  });
+
   it("finds best pool and swaps exact out (WSOL → USDC)", async () => {
     // Add small delay to avoid transaction conflicts
     await new Promise(resolve => setTimeout(resolve, 1000));
@@ -179,6 +304,7 @@ describe("raydium_integration", () => {
       .setSlippage(MAX_SLIPPAGE_BPS)
       .accountsStrict({
         owner: walletPubkey,
+        payer: walletPubkey,
         userCfg: USER_CFG,
         systemProgram: SystemProgram.programId,
       })
@@ -189,7 +315,7 @@ describe("raydium_integration", () => {
 
     // Execute swap via Anchor proxySwap instruction
     const txSig = await program.methods
-      .proxySwap(desiredOut, maxAmountIn, sqrtPriceLimitX64, isBaseInput)
+      .proxySwap(desiredOut, maxAmountIn, sqrtPriceLimitX64, isBaseInput, null, false, false)
       .accountsStrict({
         clmmProgram: CLMM_PROGRAM,
         payer: walletPubkey,
@@ -200,9 +326,34 @@ describe("raydium_integration", () => {
         outputTokenAccount: usdcAta,
         inputVault,
         outputVault,
+        inputVaultMint: inputMint,
+        outputVaultMint: outputMint,
         observationState: poolKeys.observationId,
         tokenProgram: TOKEN_PROGRAM_ID,
         tickArray: tickArrayAddr,
+        poolStats: null,
+        globalStats: null,
+        tickArrayBitmapExtension: null,
+        instructionsSysvar: null,
+        globalConfig: GLOBAL_CONFIG,
+        feeMint: null,
+        treasuryTokenAccount: null,
+        insuranceVault: null,
+        feeTokenProgram: null,
+        pointsConfig: null,
+        pointsLedger: null,
+        roundTripCounter: PublicKey.findProgramAddressSync(
+          [Buffer.from("round_trips"), wallet.toBuffer(), poolInfo.id.toBuffer()],
+          program.programId
+        )[0],
+        feeOracle: null,
+        feeOraclePool: null,
+        feeOracleObservation: null,
+        feePaymentAccount: null,
+        swapValidators: SWAP_VALIDATORS,
+        destinationAllowlist: DESTINATION_ALLOWLIST,
+        mintExtensionOptIn: null,
+        callbackProgram: null,
       }).rpc({ skipPreflight: true, commitment: "confirmed" });
 
     console.log("Exact swap out executed successfully!");
@@ -308,11 +459,12 @@ describe("raydium_integration", () => {
             amount0Max,
             amount1Max,
             true,   // with metadata
-            null    // base_flag
+            false   // simulate_only
           ).preInstructions([memoIx, computeIx])
           .accountsStrict({
             clmmProgram: CLMM_PROGRAM,
             payer: wallet,
+            userCfg: USER_CFG,
             positionNftOwner: wallet,
             positionNftMint: positionNftMint.publicKey,
             positionNftAccount,
@@ -326,14 +478,27 @@ describe("raydium_integration", () => {
             tokenAccount1,
             tokenVault0,
             tokenVault1,
-            rent: SYSVAR_RENT_PUBKEY,
-            systemProgram: SystemProgram.programId,
-            tokenProgram: TOKEN_PROGRAM_ID,
-            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-            metadataProgram: METADATA_PROGRAM_ID,
-            tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+            programs: {
+              rent: SYSVAR_RENT_PUBKEY,
+              systemProgram: SystemProgram.programId,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+              metadataProgram: METADATA_PROGRAM_ID,
+              tokenProgram2022: TOKEN_2022_PROGRAM_ID,
+            },
             vault0Mint: mintA,
             vault1Mint: mintB,
+            poolStats: null,
+            globalStats: null,
+            positionRegistry: null,
+            positionRegistryPage: null,
+            costBasis: null,
+            tickArrayBitmapExtension: null,
+            mintExtensionOptIn: null,
+            instructionsSysvar: null,
+            openApproval: null,
+            poolExposure: null,
+            globalConfig: GLOBAL_CONFIG,
           }).transaction();
 
         const { blockhash, lastValidBlockHeight } = await provider.connection.getLatestBlockhash("finalized");